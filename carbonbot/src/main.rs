@@ -22,6 +22,8 @@ pub fn crawl(
         crawl_candlestick(exchange, market_type, None, tx, None);
     } else if msg_type == MessageType::OpenInterest {
         crawl_open_interest(exchange, market_type, tx, None);
+    } else if msg_type == MessageType::Liquidation {
+        crawl_liquidation(exchange, market_type, tx, None);
     } else if msg_type == MessageType::Other {
         crawl_other(exchange, market_type, tx, None);
     } else {
@@ -1,12 +1,256 @@
 use crypto_market_type::MarketType;
+use lazy_static::lazy_static;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+
+use crypto_pair::http_get;
+
+lazy_static! {
+    // face values of COIN-margined contracts, in USD
+    static ref INVERSE_CONTRACT_VALUES: HashMap<String, f64> = {
+        // offline data, in case the network is down
+        let mut m: HashMap<String, f64> = vec![
+            ("ADA/USD", 10_f64),
+            ("BCH/USD", 10_f64),
+            ("BNB/USD", 10_f64),
+            ("BTC/USD", 100_f64),
+            ("DOGE/USD", 10_f64),
+            ("DOT/USD", 10_f64),
+            ("EOS/USD", 10_f64),
+            ("ETC/USD", 10_f64),
+            ("ETH/USD", 10_f64),
+            ("FIL/USD", 10_f64),
+            ("LINK/USD", 10_f64),
+            ("LTC/USD", 10_f64),
+            ("TRX/USD", 10_f64),
+            ("UNI/USD", 10_f64),
+            ("XRP/USD", 10_f64),
+        ]
+        .into_iter()
+        .map(|x| (x.0.to_string(), x.1))
+        .collect();
+
+        let from_online = fetch_inverse_contract_values();
+        for (pair, contract_value) in from_online {
+            m.insert(pair, contract_value);
+        }
+
+        m
+    };
+
+    // base coins per contract of USD-margined contracts
+    static ref LINEAR_CONTRACT_VALUES: HashMap<String, f64> = {
+        // offline data, in case the network is down
+        let mut m: HashMap<String, f64> = vec![
+            ("1000SHIB/USDT", 1_f64),
+            ("1000XEC/USDT", 1_f64),
+            ("BTC/USDT", 1_f64),
+            ("BTCDOM/USDT", 1_f64),
+            ("ETH/USDT", 1_f64),
+        ]
+        .into_iter()
+        .map(|x| (x.0.to_string(), x.1))
+        .collect();
+
+        let from_online = fetch_linear_contract_values();
+        for (pair, contract_value) in from_online {
+            m.insert(pair, contract_value);
+        }
+
+        m
+    };
+
+    static ref OPTION_UNITS: HashMap<String, f64> = {
+        // offline data, in case the network is down
+        let mut m: HashMap<String, f64> = vec![
+            ("BNB/USDT", 1_f64),
+            ("BTC/USDT", 1_f64),
+            ("ETH/USDT", 1_f64),
+        ]
+        .into_iter()
+        .map(|x| (x.0.to_string(), x.1))
+        .collect();
+
+        let from_online = fetch_option_units();
+        for (pair, unit) in from_online {
+            m.insert(pair, unit);
+        }
+
+        m
+    };
+}
+
+// get the contractSize field of COIN-margined symbols
+fn fetch_inverse_contract_values() -> BTreeMap<String, f64> {
+    if cfg!(feature = "offline") {
+        return BTreeMap::new();
+    }
+    match http_get("binance", "https://dapi.binance.com/dapi/v1/exchangeInfo") {
+        Ok(txt) => parse_inverse_contract_values(&txt),
+        Err(_) => BTreeMap::new(),
+    }
+}
+
+fn parse_inverse_contract_values(txt: &str) -> BTreeMap<String, f64> {
+    #[derive(Serialize, Deserialize)]
+    #[allow(non_snake_case)]
+    struct InverseSymbol {
+        symbol: String,
+        contractSize: f64,
+    }
+    #[derive(Serialize, Deserialize)]
+    struct ExchangeInfo {
+        symbols: Vec<InverseSymbol>,
+    }
+    let mut mapping: BTreeMap<String, f64> = BTreeMap::new();
+
+    if let Ok(exchange_info) = serde_json::from_str::<ExchangeInfo>(txt) {
+        for symbol in exchange_info.symbols {
+            if let Some(pair) = crypto_pair::normalize_pair(&symbol.symbol, "binance") {
+                mapping.insert(pair, symbol.contractSize);
+            }
+        }
+    }
+
+    mapping
+}
+
+// get the multipliers of USD-margined symbols
+fn fetch_linear_contract_values() -> BTreeMap<String, f64> {
+    if cfg!(feature = "offline") {
+        return BTreeMap::new();
+    }
+    match http_get("binance", "https://fapi.binance.com/fapi/v1/exchangeInfo") {
+        Ok(txt) => parse_linear_contract_values(&txt),
+        Err(_) => BTreeMap::new(),
+    }
+}
+
+// Quantities of USD-margined contracts are in units of baseAsset, which
+// carries the multiplier of small coins, e.g., 1000SHIB of 1000SHIBUSDT, and
+// is the base of the normalized pair, so one contract is one base coin.
+fn parse_linear_contract_values(txt: &str) -> BTreeMap<String, f64> {
+    #[derive(Serialize, Deserialize)]
+    #[allow(non_snake_case)]
+    struct LinearSymbol {
+        baseAsset: String,
+        quoteAsset: String,
+    }
+    #[derive(Serialize, Deserialize)]
+    struct ExchangeInfo {
+        symbols: Vec<LinearSymbol>,
+    }
+    let mut mapping: BTreeMap<String, f64> = BTreeMap::new();
+
+    if let Ok(exchange_info) = serde_json::from_str::<ExchangeInfo>(txt) {
+        for symbol in exchange_info.symbols {
+            let pair = format!("{}/{}", symbol.baseAsset, symbol.quoteAsset).to_uppercase();
+            mapping.insert(pair, 1.0);
+        }
+    }
+
+    mapping
+}
+
+// get the unit field of option symbols, i.e., how many underlying coins per contract
+fn fetch_option_units() -> BTreeMap<String, f64> {
+    if cfg!(feature = "offline") {
+        return BTreeMap::new();
+    }
+    #[derive(Serialize, Deserialize)]
+    struct OptionSymbol {
+        symbol: String,
+        unit: f64,
+    }
+    #[derive(Serialize, Deserialize)]
+    #[allow(non_snake_case)]
+    struct ExchangeInfo {
+        optionSymbols: Vec<OptionSymbol>,
+    }
+    let mut mapping: BTreeMap<String, f64> = BTreeMap::new();
+
+    if let Ok(txt) = http_get("binance", "https://eapi.binance.com/eapi/v1/exchangeInfo") {
+        if let Ok(exchange_info) = serde_json::from_str::<ExchangeInfo>(&txt) {
+            for option_symbol in exchange_info.optionSymbols {
+                if let Some(pair) = crypto_pair::normalize_pair(&option_symbol.symbol, "binance") {
+                    mapping.insert(pair, option_symbol.unit);
+                }
+            }
+        }
+    }
+
+    mapping
+}
 
 pub(crate) fn get_contract_value(market_type: MarketType, pair: &str) -> Option<f64> {
     match market_type {
         MarketType::InverseSwap | MarketType::InverseFuture => {
-            Some(if pair.starts_with("BTC") { 100.0 } else { 10.0 })
+            Some(INVERSE_CONTRACT_VALUES.get(pair).copied().unwrap_or(
+                if pair.starts_with("BTC/") {
+                    100.0
+                } else {
+                    10.0
+                },
+            ))
+        }
+        MarketType::LinearSwap | MarketType::LinearFuture => {
+            Some(LINEAR_CONTRACT_VALUES.get(pair).copied().unwrap_or(1.0))
         }
-        MarketType::LinearSwap | MarketType::LinearFuture => Some(1.0),
-        MarketType::EuropeanOption => Some(1.0),
+        MarketType::EuropeanOption => Some(OPTION_UNITS.get(pair).copied().unwrap_or(1.0)),
         _ => None,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        fetch_inverse_contract_values, fetch_linear_contract_values, fetch_option_units,
+        parse_inverse_contract_values, parse_linear_contract_values,
+    };
+
+    #[test]
+    fn inverse() {
+        let mapping = fetch_inverse_contract_values();
+        for (pair, contract_value) in &mapping {
+            println!("(\"{}\", {}_f64),", pair, contract_value);
+        }
+    }
+
+    #[test]
+    fn linear() {
+        let mapping = fetch_linear_contract_values();
+        for (pair, contract_value) in &mapping {
+            println!("(\"{}\", {}_f64),", pair, contract_value);
+        }
+    }
+
+    #[test]
+    fn parse_inverse() {
+        // captured from https://dapi.binance.com/dapi/v1/exchangeInfo, trimmed
+        let txt = r#"{"timezone":"UTC","serverTime":1640000000000,"symbols":[{"symbol":"BTCUSD_PERP","pair":"BTCUSD","contractType":"PERPETUAL","baseAsset":"BTC","quoteAsset":"USD","contractSize":100},{"symbol":"ETHUSD_220325","pair":"ETHUSD","contractType":"CURRENT_QUARTER","baseAsset":"ETH","quoteAsset":"USD","contractSize":10}]}"#;
+        let mapping = parse_inverse_contract_values(txt);
+        assert_eq!(2, mapping.len());
+        assert_eq!(Some(&100.0), mapping.get("BTC/USD"));
+        assert_eq!(Some(&10.0), mapping.get("ETH/USD"));
+    }
+
+    #[test]
+    fn parse_linear() {
+        // captured from https://fapi.binance.com/fapi/v1/exchangeInfo, trimmed
+        let txt = r#"{"timezone":"UTC","serverTime":1640000000000,"symbols":[{"symbol":"BTCUSDT","pair":"BTCUSDT","contractType":"PERPETUAL","baseAsset":"BTC","quoteAsset":"USDT"},{"symbol":"1000SHIBUSDT","pair":"1000SHIBUSDT","contractType":"PERPETUAL","baseAsset":"1000SHIB","quoteAsset":"USDT"},{"symbol":"ETHUSDT_220325","pair":"ETHUSDT","contractType":"CURRENT_QUARTER","baseAsset":"ETH","quoteAsset":"USDT"}]}"#;
+        let mapping = parse_linear_contract_values(txt);
+        assert_eq!(3, mapping.len());
+        assert_eq!(Some(&1.0), mapping.get("BTC/USDT"));
+        // the multiplier is part of the base
+        assert_eq!(Some(&1.0), mapping.get("1000SHIB/USDT"));
+        assert_eq!(Some(&1.0), mapping.get("ETH/USDT"));
+    }
+
+    #[test]
+    fn european_option() {
+        let mapping = fetch_option_units();
+        for (pair, unit) in &mapping {
+            println!("(\"{}\", {}_f64),", pair, unit);
+        }
+    }
+}
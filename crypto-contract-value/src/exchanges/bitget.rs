@@ -3,7 +3,7 @@ use lazy_static::lazy_static;
 use serde::{Deserialize, Serialize};
 use std::collections::{BTreeMap, HashMap};
 
-use super::utils::http_get;
+use crypto_pair::http_get;
 
 lazy_static! {
     static ref LINEAR_SWAP_CONTRACT_VALUES: HashMap<String, f64> = {
@@ -48,6 +48,9 @@ lazy_static! {
 }
 
 fn fetch_contract_val() -> BTreeMap<String, f64> {
+    if cfg!(feature = "offline") {
+        return BTreeMap::new();
+    }
     // See https://bitgetlimited.github.io/apidoc/en/swap/#contract-information
     #[derive(Serialize, Deserialize)]
     #[allow(non_snake_case)]
@@ -59,7 +62,10 @@ fn fetch_contract_val() -> BTreeMap<String, f64> {
 
     let mut mapping: BTreeMap<String, f64> = BTreeMap::new();
 
-    if let Ok(txt) = http_get("https://capi.bitget.com/api/swap/v3/market/contracts") {
+    if let Ok(txt) = http_get(
+        "bitget",
+        "https://capi.bitget.com/api/swap/v3/market/contracts",
+    ) {
         if let Ok(swap_markets) = serde_json::from_str::<Vec<SwapMarket>>(&txt) {
             for swap_market in swap_markets.iter().filter(|x| x.forwardContractFlag) {
                 mapping.insert(
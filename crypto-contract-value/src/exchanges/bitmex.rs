@@ -1,7 +1,7 @@
 use std::collections::{BTreeMap, HashMap};
 
-use super::utils::http_get;
 use crypto_market_type::MarketType;
+use crypto_pair::http_get;
 use lazy_static::lazy_static;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
@@ -55,9 +55,12 @@ struct Instrument {
 }
 
 fn fetch_contract_values() -> BTreeMap<String, i64> {
+    if cfg!(feature = "offline") {
+        return BTreeMap::new();
+    }
     let mut mapping: BTreeMap<String, i64> = BTreeMap::new();
 
-    if let Ok(text) = http_get("https://www.bitmex.com/api/v1/instrument/active") {
+    if let Ok(text) = http_get("bitmex", "https://www.bitmex.com/api/v1/instrument/active") {
         let instruments: Vec<Instrument> = serde_json::from_str::<Vec<Instrument>>(&text)
             .unwrap()
             .into_iter()
@@ -3,7 +3,7 @@ use lazy_static::lazy_static;
 use serde::{Deserialize, Serialize};
 use std::collections::{BTreeMap, HashMap};
 
-use super::utils::http_get;
+use crypto_pair::http_get;
 
 lazy_static! {
     static ref CONTRACT_VALUES: HashMap<MarketType, HashMap<String, f64>> = {
@@ -251,6 +251,9 @@ const LINEAR_FUTURE_URL: &str = "https://api.gateio.ws/api/v4/delivery/usdt/cont
 // https://api.gateio.ws/api/v4/futures/usdt/contracts
 // https://api.gateio.ws/api/v4/delivery/usdt/contracts
 fn fetch_quanto_multipliers(url: &str) -> BTreeMap<String, f64> {
+    if cfg!(feature = "offline") {
+        return BTreeMap::new();
+    }
     #[derive(Serialize, Deserialize)]
     struct RawMarket {
         name: String,
@@ -259,7 +262,7 @@ fn fetch_quanto_multipliers(url: &str) -> BTreeMap<String, f64> {
 
     let mut mapping: BTreeMap<String, f64> = BTreeMap::new();
 
-    if let Ok(txt) = http_get(url) {
+    if let Ok(txt) = http_get("gate", url) {
         if let Ok(markets) = serde_json::from_str::<Vec<RawMarket>>(&txt) {
             for market in markets.iter() {
                 let mut contract_value = market.quanto_multiplier.parse::<f64>().unwrap();
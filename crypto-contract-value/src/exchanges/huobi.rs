@@ -3,7 +3,7 @@ use lazy_static::lazy_static;
 use serde::{Deserialize, Serialize};
 use std::collections::{BTreeMap, HashMap};
 
-use super::utils::http_get;
+use crypto_pair::http_get;
 
 lazy_static! {
     static ref CONTRACT_VALUES: HashMap<MarketType, HashMap<String, f64>> = {
@@ -162,18 +162,74 @@ lazy_static! {
             m
         };
 
+        // Inverse contracts are worth 100 USD for BTC and 10 USD for others,
+        // online data covers new coins with different face values
+        let inverse_swap: HashMap<String, f64> = {
+            // offline data, in case the network is down
+            let mut m: HashMap<String, f64> = INVERSE_CONTRACT_VALUES
+                .iter()
+                .map(|x| (x.0.to_string(), x.1))
+                .collect();
+
+            let from_online = fetch_contract_size(INVERSE_SWAP_URL);
+            for (pair, contract_value) in from_online {
+                m.insert(pair, contract_value);
+            }
+
+            m
+        };
+
+        let inverse_future: HashMap<String, f64> = {
+            // offline data, in case the network is down
+            let mut m: HashMap<String, f64> = INVERSE_CONTRACT_VALUES
+                .iter()
+                .map(|x| (x.0.to_string(), x.1))
+                .collect();
+
+            let from_online = fetch_contract_size(INVERSE_FUTURE_URL);
+            for (pair, contract_value) in from_online {
+                m.insert(pair, contract_value);
+            }
+
+            m
+        };
+
         let mut result = HashMap::<MarketType, HashMap<String, f64>>::new();
+        result.insert(MarketType::InverseSwap, inverse_swap);
+        result.insert(MarketType::InverseFuture, inverse_future);
         result.insert(MarketType::LinearSwap, linear_swap);
         result.insert(MarketType::EuropeanOption, linear_option);
         result
     };
 }
 
+// Offline contract values of inverse swaps and futures, which share the same face values
+const INVERSE_CONTRACT_VALUES: &[(&str, f64)] = &[
+    ("ADA/USD", 10_f64),
+    ("BCH/USD", 10_f64),
+    ("BSV/USD", 10_f64),
+    ("BTC/USD", 100_f64),
+    ("DOT/USD", 10_f64),
+    ("EOS/USD", 10_f64),
+    ("ETC/USD", 10_f64),
+    ("ETH/USD", 10_f64),
+    ("FIL/USD", 10_f64),
+    ("LINK/USD", 10_f64),
+    ("LTC/USD", 10_f64),
+    ("TRX/USD", 10_f64),
+    ("XRP/USD", 10_f64),
+];
+
+const INVERSE_SWAP_URL: &str = "https://api.hbdm.com/swap-api/v1/swap_contract_info";
+const INVERSE_FUTURE_URL: &str = "https://api.hbdm.com/api/v1/contract_contract_info";
 const LINEAR_SWAP_URL: &str = "https://api.hbdm.com/linear-swap-api/v1/swap_contract_info";
 const LINEAR_OPTION_URL: &str = "https://api.hbdm.com/option-api/v1/option_contract_info";
 
 // get the contract_size field.
 fn fetch_contract_size(url: &str) -> BTreeMap<String, f64> {
+    if cfg!(feature = "offline") {
+        return BTreeMap::new();
+    }
     #[derive(Serialize, Deserialize)]
     struct RawMarket {
         symbol: String,
@@ -190,13 +246,18 @@ fn fetch_contract_size(url: &str) -> BTreeMap<String, f64> {
 
     let mut mapping: BTreeMap<String, f64> = BTreeMap::new();
 
-    if let Ok(txt) = http_get(url) {
+    if let Ok(txt) = http_get("huobi", url) {
         if let Ok(response) = serde_json::from_str::<Response>(&txt) {
             for market in response.data.iter() {
-                mapping.insert(
-                    crypto_pair::normalize_pair(&market.contract_code, "huobi").unwrap(),
-                    market.contract_size,
-                );
+                let pair = if market.contract_code.contains('-') {
+                    crypto_pair::normalize_pair(&market.contract_code, "huobi")
+                } else {
+                    // inverse futures, e.g., BTC211231
+                    Some(format!("{}/USD", market.symbol))
+                };
+                if let Some(pair) = pair {
+                    mapping.insert(pair, market.contract_size);
+                }
             }
         }
     }
@@ -207,18 +268,74 @@ fn fetch_contract_size(url: &str) -> BTreeMap<String, f64> {
 pub(crate) fn get_contract_value(market_type: MarketType, pair: &str) -> Option<f64> {
     match market_type {
         MarketType::InverseSwap | MarketType::InverseFuture => {
-            Some(if pair.starts_with("BTC") { 100.0 } else { 10.0 })
+            Some(CONTRACT_VALUES[&market_type].get(pair).copied().unwrap_or(
+                if pair.starts_with("BTC/") {
+                    100.0
+                } else {
+                    10.0
+                },
+            ))
         }
-        MarketType::LinearSwap | MarketType::LinearFuture | MarketType::EuropeanOption => {
-            Some(CONTRACT_VALUES[&market_type][pair])
+        // USDT-margined futures have the same contract sizes as swaps
+        MarketType::LinearSwap | MarketType::LinearFuture => {
+            CONTRACT_VALUES[&MarketType::LinearSwap].get(pair).copied()
         }
+        MarketType::EuropeanOption => CONTRACT_VALUES[&market_type].get(pair).copied(),
         _ => None,
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::{fetch_contract_size, LINEAR_OPTION_URL, LINEAR_SWAP_URL};
+    use super::{
+        fetch_contract_size, get_contract_value, INVERSE_FUTURE_URL, INVERSE_SWAP_URL,
+        LINEAR_OPTION_URL, LINEAR_SWAP_URL,
+    };
+    use crypto_market_type::MarketType;
+
+    #[test]
+    fn inverse_swap() {
+        let mapping = fetch_contract_size(INVERSE_SWAP_URL);
+        for (pair, contract_value) in &mapping {
+            println!("(\"{}\", {}_f64),", pair, contract_value);
+        }
+    }
+
+    #[test]
+    fn inverse_future() {
+        let mapping = fetch_contract_size(INVERSE_FUTURE_URL);
+        for (pair, contract_value) in &mapping {
+            println!("(\"{}\", {}_f64),", pair, contract_value);
+        }
+    }
+
+    #[test]
+    fn offline_inverse() {
+        // listed in the offline tables, no matter the network is up or not
+        for market_type in [MarketType::InverseSwap, MarketType::InverseFuture] {
+            assert_eq!(
+                Some(&100.0),
+                super::CONTRACT_VALUES[&market_type].get("BTC/USD")
+            );
+            assert_eq!(
+                Some(&10.0),
+                super::CONTRACT_VALUES[&market_type].get("ETH/USD")
+            );
+        }
+    }
+
+    #[test]
+    fn contract_values() {
+        for market_type in [MarketType::InverseSwap, MarketType::InverseFuture] {
+            assert_eq!(Some(100.0), get_contract_value(market_type, "BTC/USD"));
+            assert_eq!(Some(10.0), get_contract_value(market_type, "ETH/USD"));
+        }
+        for market_type in [MarketType::LinearSwap, MarketType::LinearFuture] {
+            assert_eq!(Some(0.001), get_contract_value(market_type, "BTC/USDT"));
+            assert_eq!(None, get_contract_value(market_type, "NONEXISTENT/USDT"));
+        }
+        assert_eq!(None, get_contract_value(MarketType::Spot, "BTC/USDT"));
+    }
 
     #[test]
     fn linear_swap() {
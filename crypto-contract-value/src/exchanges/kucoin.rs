@@ -4,7 +4,7 @@ use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::collections::{BTreeMap, HashMap};
 
-use super::utils::http_get;
+use crypto_pair::http_get;
 
 lazy_static! {
     static ref LINEAR_CONTRACT_VALUES: HashMap<String, f64> = {
@@ -113,9 +113,15 @@ struct ResponseMsg {
 
 // get the multiplier field from linear markets
 fn fetch_linear_multipliers() -> BTreeMap<String, f64> {
+    if cfg!(feature = "offline") {
+        return BTreeMap::new();
+    }
     let mut mapping: BTreeMap<String, f64> = BTreeMap::new();
 
-    if let Ok(txt) = http_get("https://api-futures.kucoin.com/api/v1/contracts/active") {
+    if let Ok(txt) = http_get(
+        "kucoin",
+        "https://api-futures.kucoin.com/api/v1/contracts/active",
+    ) {
         if let Ok(resp) = serde_json::from_str::<ResponseMsg>(&txt) {
             for swap_market in resp.data.iter().filter(|x| !x.isInverse) {
                 mapping.insert(
@@ -1,5 +1,3 @@
-mod utils;
-
 pub(super) mod binance;
 pub(super) mod bitfinex;
 pub(super) mod bitget;
@@ -3,7 +3,7 @@ use lazy_static::lazy_static;
 use serde::{Deserialize, Serialize};
 use std::collections::{BTreeMap, HashMap};
 
-use super::utils::http_get;
+use crypto_pair::http_get;
 
 lazy_static! {
     static ref LINEAR_CONTRACT_VALUES: HashMap<String, f64> = {
@@ -160,6 +160,9 @@ lazy_static! {
 
 // get the contractSize field from linear markets
 fn fetch_linear_contract_sizes() -> BTreeMap<String, f64> {
+    if cfg!(feature = "offline") {
+        return BTreeMap::new();
+    }
     #[derive(Serialize, Deserialize)]
     #[allow(non_snake_case)]
     struct SwapMarket {
@@ -179,7 +182,7 @@ fn fetch_linear_contract_sizes() -> BTreeMap<String, f64> {
 
     let mut mapping: BTreeMap<String, f64> = BTreeMap::new();
 
-    if let Ok(txt) = http_get("https://contract.mexc.com/api/v1/contract/detail") {
+    if let Ok(txt) = http_get("mxc", "https://contract.mexc.com/api/v1/contract/detail") {
         if let Ok(resp) = serde_json::from_str::<ResponseMsg>(&txt) {
             for linear_market in resp.data.iter().filter(|x| x.settleCoin == x.quoteCoin) {
                 mapping.insert(
@@ -2,11 +2,13 @@ use crypto_market_type::MarketType;
 use lazy_static::lazy_static;
 use serde::{Deserialize, Serialize};
 use std::collections::{BTreeMap, HashMap};
+use std::sync::RwLock;
 
-use super::utils::http_get;
+use crypto_pair::http_get;
 
 lazy_static! {
-    static ref CONTRACT_VALUES: HashMap<MarketType, HashMap<String, f64>> = {
+    // refreshed by refresh_contract_values() when new contracts are listed
+    static ref CONTRACT_VALUES: RwLock<HashMap<MarketType, HashMap<String, f64>>> = {
         let linear_swap: HashMap<String, f64> = {
             // offline data, in case the network is down
             let mut m: HashMap<String, f64> = vec![
@@ -174,13 +176,16 @@ lazy_static! {
         result.insert(MarketType::LinearSwap, linear_swap);
         result.insert(MarketType::LinearFuture, linear_future);
         result.insert(MarketType::EuropeanOption, option);
-        result
+        RwLock::new(result)
     };
 }
 
 // get the contract_val field
 // market_type, futures, swap, option
 fn fetch_contract_val(market_type: &str) -> BTreeMap<String, f64> {
+    if cfg!(feature = "offline") {
+        return BTreeMap::new();
+    }
     #[derive(Serialize, Deserialize)]
     struct Instrument {
         instrument_id: String,
@@ -190,14 +195,17 @@ fn fetch_contract_val(market_type: &str) -> BTreeMap<String, f64> {
     }
     let mut mapping: BTreeMap<String, f64> = BTreeMap::new();
 
-    if let Ok(txt) = http_get(&format!(
-        "https://www.okex.com/api/{}/v3/instruments",
-        market_type
-    )) {
+    if let Ok(txt) = http_get(
+        "okex",
+        &format!("https://www.okex.com/api/{}/v3/instruments", market_type),
+    ) {
         if let Ok(instruments) = serde_json::from_str::<Vec<Instrument>>(&txt) {
             for instrument in instruments.into_iter().filter(|x| x.is_inverse == "false") {
-                let pair = crypto_pair::normalize_pair(&instrument.instrument_id, "okex").unwrap();
-                mapping.insert(pair, instrument.contract_val.parse::<f64>().unwrap());
+                let pair = crypto_pair::normalize_pair(&instrument.instrument_id, "okex");
+                let contract_val = instrument.contract_val.parse::<f64>();
+                if let (Some(pair), Ok(contract_val)) = (pair, contract_val) {
+                    mapping.insert(pair, contract_val);
+                }
             }
         }
     }
@@ -211,15 +219,36 @@ pub(crate) fn get_contract_value(market_type: MarketType, pair: &str) -> Option<
             Some(if pair.starts_with("BTC") { 100.0 } else { 10.0 })
         }
         MarketType::LinearSwap | MarketType::LinearFuture | MarketType::EuropeanOption => {
-            Some(CONTRACT_VALUES[&market_type][pair])
+            CONTRACT_VALUES
+                .read()
+                .unwrap()
+                .get(&market_type)
+                .and_then(|m| m.get(pair).copied())
         }
         _ => None,
     }
 }
 
+// Fetches contract values of linear markets again and merges them, returns
+// false if the market type has no online source.
+pub(crate) fn refresh_contract_values(market_type: MarketType) -> bool {
+    let from_online = match market_type {
+        MarketType::LinearSwap => fetch_contract_val("swap"),
+        MarketType::LinearFuture => fetch_contract_val("futures"),
+        _ => return false,
+    };
+    let mut contract_values = CONTRACT_VALUES.write().unwrap();
+    let m = contract_values.entry(market_type).or_default();
+    for (pair, contract_value) in from_online {
+        m.insert(pair, contract_value);
+    }
+    true
+}
+
 #[cfg(test)]
 mod tests {
-    use super::fetch_contract_val;
+    use super::{fetch_contract_val, get_contract_value, refresh_contract_values};
+    use crypto_market_type::MarketType;
 
     #[test]
     fn linear_swap() {
@@ -236,4 +265,17 @@ mod tests {
             println!("(\"{}\", {}_f64),", pair, contract_value);
         }
     }
+
+    #[test]
+    fn unknown_pair() {
+        assert_eq!(
+            Some(0.01),
+            get_contract_value(MarketType::LinearSwap, "BTC/USDT")
+        );
+        assert_eq!(
+            None,
+            get_contract_value(MarketType::LinearSwap, "NONEXISTENT/USDT")
+        );
+        assert!(!refresh_contract_values(MarketType::Spot));
+    }
 }
@@ -3,7 +3,7 @@ use lazy_static::lazy_static;
 use serde::{Deserialize, Serialize};
 use std::collections::{BTreeMap, HashMap};
 
-use super::utils::http_get;
+use crypto_pair::http_get;
 
 lazy_static! {
     static ref SWAP_CONTRACT_VALUES: HashMap<String, f64> = {
@@ -52,6 +52,9 @@ struct SwapMarket {
 
 // See https://zbgapi.github.io/docs/future/v1/en/#public-get-contracts
 fn fetch_swap_markets_raw() -> Vec<SwapMarket> {
+    if cfg!(feature = "offline") {
+        return Vec::new();
+    }
     #[derive(Serialize, Deserialize)]
     struct ResMsg {
         message: String,
@@ -64,7 +67,10 @@ fn fetch_swap_markets_raw() -> Vec<SwapMarket> {
         datas: Vec<SwapMarket>,
         resMsg: ResMsg,
     }
-    if let Ok(txt) = http_get("https://www.zbg.com/exchange/api/v1/future/common/contracts") {
+    if let Ok(txt) = http_get(
+        "zbg",
+        "https://www.zbg.com/exchange/api/v1/future/common/contracts",
+    ) {
         if let Ok(resp) = serde_json::from_str::<Response>(&txt) {
             if resp.resMsg.code != "1" {
                 Vec::new()
@@ -25,3 +25,46 @@ pub fn get_contract_value(exchange: &str, market_type: MarketType, pair: &str) -
         _ => panic!("Unknown exchange {}", exchange),
     }
 }
+
+/// Fetches contract values again, so that contracts listed after the first
+/// call of `get_contract_value()` can be looked up.
+///
+/// Returns false if the exchange and market type don't support refreshing,
+/// currently only okex linear markets do.
+pub fn refresh_contract_values(exchange: &str, market_type: MarketType) -> bool {
+    match exchange {
+        "okex" => exchanges::okex::refresh_contract_values(market_type),
+        _ => false,
+    }
+}
+
+#[cfg(all(test, feature = "offline"))]
+mod tests {
+    use crypto_market_type::MarketType;
+    use crypto_pair::NUM_HTTP_REQUESTS;
+    use std::sync::atomic::Ordering;
+
+    #[test]
+    fn no_http_requests() {
+        for exchange in [
+            "binance", "bitget", "bitmex", "gate", "huobi", "kucoin", "mxc", "okex", "zbg",
+        ] {
+            for market_type in [
+                MarketType::InverseSwap,
+                MarketType::LinearSwap,
+                MarketType::InverseFuture,
+                MarketType::LinearFuture,
+            ] {
+                let pair = if market_type == MarketType::InverseSwap
+                    || market_type == MarketType::InverseFuture
+                {
+                    "BTC/USD"
+                } else {
+                    "BTC/USDT"
+                };
+                let _ = crate::get_contract_value(exchange, market_type, pair);
+            }
+        }
+        assert_eq!(0, NUM_HTTP_REQUESTS.load(Ordering::Relaxed));
+    }
+}
@@ -0,0 +1,291 @@
+use std::collections::VecDeque;
+use std::sync::mpsc::{Receiver, Sender};
+use std::sync::{Arc, Condvar, Mutex};
+
+use crate::Message;
+
+/// What a bounded channel does with a new message when it's full.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BackpressurePolicy {
+    /// Waits for the consumer and never drops messages.
+    ///
+    /// Messages go through a `sync_channel(capacity)`, the relay thread stalls
+    /// once it's full until the consumer catches up. Crawl functions take an
+    /// unbounded `Sender`, so the websocket read loop itself doesn't stall and
+    /// keeps answering pings, but messages pile up in that `Sender` while the
+    /// relay waits. Pick a drop policy if the consumer can stay slower for long.
+    Block,
+    /// Drops the oldest buffered message to make room for the new one,
+    /// suitable for consumers that only care about the latest state.
+    DropOldest,
+    /// Drops the new message, keeping the buffered ones.
+    DropNewest,
+}
+
+/// Creates a channel that buffers at most `capacity` messages.
+///
+/// Pass the `Sender` to one crawl function and read messages from the
+/// `BoundedReceiver`, each crawler can have its own capacity and policy.
+/// A relay thread moves messages from the crawler into a ring buffer, or a
+/// `sync_channel` for `BackpressurePolicy::Block`, and applies `policy` when
+/// it's full. Dropped messages are counted by
+/// `BoundedReceiver::num_dropped()` and, with the `metrics` feature, by
+/// `crypto_crawler_dropped_messages_total`.
+///
+/// ## Example
+///
+/// ```rust
+/// use crypto_crawler::{bounded_channel, crawl_trade, BackpressurePolicy, MarketType};
+///
+/// let (tx, rx) = bounded_channel(10000, BackpressurePolicy::DropOldest);
+/// std::thread::spawn(move || {
+///     for msg in rx.iter() {
+///         println!("{}", msg);
+///     }
+/// });
+///
+/// crawl_trade("binance", MarketType::Spot, Some(&["BTCUSDT".to_string()]), tx, Some(5));
+/// ```
+pub fn bounded_channel(
+    capacity: usize,
+    policy: BackpressurePolicy,
+) -> (Sender<Message>, BoundedReceiver) {
+    assert!(capacity > 0, "capacity must be positive");
+    let (tx, rx) = std::sync::mpsc::channel::<Message>();
+    if policy == BackpressurePolicy::Block {
+        let (sync_tx, sync_rx) = std::sync::mpsc::sync_channel::<Message>(capacity);
+        std::thread::Builder::new()
+            .name("backpressure-relay".to_string())
+            .spawn(move || {
+                for msg in rx {
+                    if sync_tx.send(msg).is_err() {
+                        // the receiver is gone, dropping rx makes crawlers fail to send
+                        return;
+                    }
+                }
+            })
+            .unwrap();
+        return (
+            tx,
+            BoundedReceiver {
+                inner: Inner::Blocking(sync_rx),
+            },
+        );
+    }
+
+    let shared = Arc::new(Shared {
+        state: Mutex::new(State {
+            buffer: VecDeque::with_capacity(capacity),
+            num_dropped: 0,
+            closed: false,
+            receiver_dropped: false,
+        }),
+        not_empty: Condvar::new(),
+        capacity,
+        policy,
+    });
+
+    let relay = shared.clone();
+    std::thread::Builder::new()
+        .name("backpressure-relay".to_string())
+        .spawn(move || {
+            for msg in rx {
+                if !relay.push(msg) {
+                    // the receiver is gone, dropping rx makes crawlers fail to send
+                    return;
+                }
+            }
+            relay.state.lock().unwrap().closed = true;
+            relay.not_empty.notify_all();
+        })
+        .unwrap();
+
+    (
+        tx,
+        BoundedReceiver {
+            inner: Inner::Ring(shared),
+        },
+    )
+}
+
+/// The receiving half of `bounded_channel()`.
+pub struct BoundedReceiver {
+    inner: Inner,
+}
+
+enum Inner {
+    // BackpressurePolicy::Block
+    Blocking(Receiver<Message>),
+    // the drop policies
+    Ring(Arc<Shared>),
+}
+
+struct Shared {
+    state: Mutex<State>,
+    not_empty: Condvar,
+    capacity: usize,
+    policy: BackpressurePolicy,
+}
+
+struct State {
+    buffer: VecDeque<Message>,
+    num_dropped: u64,
+    // all senders are dropped
+    closed: bool,
+    receiver_dropped: bool,
+}
+
+impl Shared {
+    // Buffers a message according to the policy, returns false if the receiver is dropped
+    fn push(&self, msg: Message) -> bool {
+        let mut state = self.state.lock().unwrap();
+        if state.buffer.len() >= self.capacity {
+            match self.policy {
+                BackpressurePolicy::Block => unreachable!("Block is built on sync_channel"),
+                BackpressurePolicy::DropOldest => {
+                    let dropped = state.buffer.pop_front().unwrap();
+                    state.num_dropped += 1;
+                    #[cfg(feature = "metrics")]
+                    crate::metrics::record_dropped(&dropped);
+                    drop(dropped);
+                }
+                BackpressurePolicy::DropNewest => {
+                    state.num_dropped += 1;
+                    #[cfg(feature = "metrics")]
+                    crate::metrics::record_dropped(&msg);
+                    return !state.receiver_dropped;
+                }
+            }
+        }
+        if state.receiver_dropped {
+            return false;
+        }
+        state.buffer.push_back(msg);
+        self.not_empty.notify_one();
+        true
+    }
+}
+
+impl BoundedReceiver {
+    /// Waits for the next message, returns None once all senders are
+    /// dropped and the buffer is empty.
+    pub fn recv(&self) -> Option<Message> {
+        match &self.inner {
+            Inner::Blocking(rx) => rx.recv().ok(),
+            Inner::Ring(shared) => {
+                let mut state = shared.state.lock().unwrap();
+                loop {
+                    if let Some(msg) = state.buffer.pop_front() {
+                        return Some(msg);
+                    }
+                    if state.closed {
+                        return None;
+                    }
+                    state = shared.not_empty.wait(state).unwrap();
+                }
+            }
+        }
+    }
+
+    /// Returns a buffered message without waiting.
+    pub fn try_recv(&self) -> Option<Message> {
+        match &self.inner {
+            Inner::Blocking(rx) => rx.try_recv().ok(),
+            Inner::Ring(shared) => shared.state.lock().unwrap().buffer.pop_front(),
+        }
+    }
+
+    /// Returns an iterator that waits for messages until all senders are dropped.
+    pub fn iter(&self) -> impl Iterator<Item = Message> + '_ {
+        std::iter::from_fn(move || self.recv())
+    }
+
+    /// Number of messages dropped because the buffer was full, always 0
+    /// with `BackpressurePolicy::Block`.
+    pub fn num_dropped(&self) -> u64 {
+        match &self.inner {
+            Inner::Blocking(_) => 0,
+            Inner::Ring(shared) => shared.state.lock().unwrap().num_dropped,
+        }
+    }
+}
+
+impl Drop for BoundedReceiver {
+    fn drop(&mut self) {
+        if let Inner::Ring(shared) = &self.inner {
+            shared.state.lock().unwrap().receiver_dropped = true;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{bounded_channel, BackpressurePolicy, Inner};
+    use crate::{MarketType, Message, MessageType};
+
+    fn message(i: usize) -> Message {
+        Message::new(
+            "binance".to_string(),
+            MarketType::Spot,
+            MessageType::Trade,
+            i.to_string(),
+        )
+    }
+
+    fn send_all(policy: BackpressurePolicy) -> (Vec<String>, u64) {
+        let (tx, rx) = bounded_channel(3, policy);
+        for i in 0..5 {
+            tx.send(message(i)).unwrap();
+        }
+        drop(tx);
+        // wait for the relay to finish before consuming
+        if let Inner::Ring(shared) = &rx.inner {
+            while !shared.state.lock().unwrap().closed {
+                std::thread::yield_now();
+            }
+        }
+        let jsons = rx.iter().map(|msg| msg.json).collect();
+        (jsons, rx.num_dropped())
+    }
+
+    #[test]
+    fn drop_oldest() {
+        assert_eq!(
+            (vec!["2".into(), "3".into(), "4".into()], 2),
+            send_all(BackpressurePolicy::DropOldest)
+        );
+    }
+
+    #[test]
+    fn drop_newest() {
+        assert_eq!(
+            (vec!["0".into(), "1".into(), "2".into()], 2),
+            send_all(BackpressurePolicy::DropNewest)
+        );
+    }
+
+    #[test]
+    fn block() {
+        let (tx, rx) = bounded_channel(1, BackpressurePolicy::Block);
+        let producer = std::thread::spawn(move || {
+            for i in 0..5 {
+                tx.send(message(i)).unwrap();
+            }
+        });
+        let jsons = rx.iter().map(|msg| msg.json).collect::<Vec<String>>();
+        producer.join().unwrap();
+        assert_eq!(vec!["0", "1", "2", "3", "4"], jsons);
+        assert_eq!(0, rx.num_dropped());
+    }
+
+    #[test]
+    fn receiver_dropped() {
+        let (tx, rx) = bounded_channel(1, BackpressurePolicy::Block);
+        tx.send(message(0)).unwrap();
+        drop(rx);
+        // the relay exits, so sending fails eventually instead of blocking forever
+        while tx.send(message(1)).is_ok() {
+            std::thread::yield_now();
+        }
+    }
+}
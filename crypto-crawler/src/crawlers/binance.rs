@@ -6,7 +6,7 @@ use crate::{msg::Message, MessageType};
 use crypto_markets::MarketType;
 use crypto_ws_client::*;
 
-use super::utils::create_conversion_thread;
+use super::utils::{create_conversion_thread, fetch_symbols_retry};
 
 const EXCHANGE_NAME: &str = "binance";
 
@@ -275,6 +275,92 @@ pub(crate) fn crawl_funding_rate(
     }
 }
 
+#[allow(clippy::unnecessary_unwrap)]
+pub(crate) fn crawl_index_price(
+    market_type: MarketType,
+    symbols: Option<&[String]>,
+    tx: Sender<Message>,
+    duration: Option<u64>,
+) {
+    let tx = create_conversion_thread(
+        EXCHANGE_NAME.to_string(),
+        MessageType::IndexPrice,
+        market_type,
+        tx,
+    );
+
+    match market_type {
+        MarketType::InverseSwap | MarketType::InverseFuture => {
+            let symbols: Vec<String> = if symbols.is_none() || symbols.unwrap().is_empty() {
+                fetch_symbols_retry(EXCHANGE_NAME, market_type)
+            } else {
+                symbols.unwrap().to_vec()
+            };
+            // indices are per pair, e.g., btcusd of BTCUSD_PERP and BTCUSD_210625
+            let mut pairs: Vec<String> = symbols
+                .iter()
+                .map(|symbol| symbol.split('_').next().unwrap().to_lowercase())
+                .collect();
+            pairs.sort();
+            pairs.dedup();
+            let channels: Vec<String> = pairs
+                .into_iter()
+                .map(|pair| format!("{}@indexPrice", pair))
+                .collect();
+
+            let ws_client = BinanceInverseWSClient::new(tx, None);
+            ws_client.subscribe(&channels);
+            ws_client.run(duration);
+        }
+        MarketType::LinearSwap | MarketType::LinearFuture => {
+            // markPriceUpdate of USDⓈ-M symbols carries the index price
+            let channels: Vec<String> = if symbols.is_none() || symbols.unwrap().is_empty() {
+                vec!["!markPrice@arr".to_string()]
+            } else {
+                symbols
+                    .unwrap()
+                    .iter()
+                    .map(|symbol| format!("{}@markPrice", symbol.to_lowercase()))
+                    .collect()
+            };
+
+            let ws_client = BinanceLinearWSClient::new(tx, None);
+            ws_client.subscribe(&channels);
+            ws_client.run(duration);
+        }
+        _ => panic!("Binance {} does NOT have index prices", market_type),
+    }
+}
+
+pub(crate) fn crawl_liquidation(
+    market_type: MarketType,
+    tx: Sender<Message>,
+    duration: Option<u64>,
+) {
+    let channels = vec!["!forceOrder@arr".to_string()];
+
+    let tx = create_conversion_thread(
+        EXCHANGE_NAME.to_string(),
+        MessageType::Liquidation,
+        market_type,
+        tx,
+    );
+
+    match market_type {
+        MarketType::InverseSwap | MarketType::InverseFuture => {
+            let ws_client = BinanceInverseWSClient::new(tx, None);
+            ws_client.subscribe(&channels);
+            ws_client.run(duration);
+        }
+        MarketType::LinearSwap | MarketType::LinearFuture => {
+            let ws_client = BinanceLinearWSClient::new(tx, None);
+            ws_client.subscribe(&channels);
+            ws_client.run(duration);
+        }
+        _ => panic!("Binance {} does NOT have liquidations", market_type),
+    }
+}
+
 pub(crate) fn crawl_candlestick(
     market_type: MarketType,
     symbol_interval_list: Option<&[(String, usize)]>,
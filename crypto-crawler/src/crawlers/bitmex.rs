@@ -19,6 +19,8 @@ fn crawl_all(msg_type: MessageType, tx: Sender<Message>, duration: Option<u64>)
         MessageType::BBO => "quote",
         MessageType::L2Snapshot => "orderBookL2",
         MessageType::FundingRate => "funding",
+        MessageType::Liquidation => "liquidation",
+        MessageType::IndexPrice => "instrument",
         _ => panic!("unsupported message type {}", msg_type),
     };
     let channels = vec![channel.to_string()];
@@ -166,6 +168,44 @@ pub(crate) fn crawl_funding_rate(
     }
 }
 
+// The liquidation table covers all markets, so `market_type` is ignored
+pub(crate) fn crawl_liquidation(
+    _market_type: MarketType,
+    tx: Sender<Message>,
+    duration: Option<u64>,
+) {
+    crawl_all(MessageType::Liquidation, tx, duration);
+}
+
+// Indices such as .BXBT are not tradable, so `market_type` is ignored
+pub(crate) fn crawl_index_price(
+    _market_type: MarketType,
+    symbols: Option<&[String]>,
+    tx: Sender<Message>,
+    duration: Option<u64>,
+) {
+    match symbols {
+        Some(list) if !list.is_empty() => {
+            let tx = create_conversion_thread(
+                EXCHANGE_NAME.to_string(),
+                MessageType::IndexPrice,
+                MarketType::Unknown,
+                tx,
+            );
+            let channels: Vec<String> = list
+                .iter()
+                .map(|symbol| format!("instrument:{}", symbol))
+                .collect();
+
+            let ws_client = BitmexWSClient::new(tx, None);
+            ws_client.subscribe(&channels);
+            ws_client.run(duration);
+        }
+        // the instrument table has all instruments, non-index ones are skipped by the parser
+        _ => crawl_all(MessageType::IndexPrice, tx, duration),
+    }
+}
+
 pub(crate) fn crawl_candlestick(
     market_type: MarketType,
     symbol_interval_list: Option<&[(String, usize)]>,
@@ -0,0 +1,32 @@
+use super::utils::{check_args, create_conversion_thread, fetch_symbols_retry};
+use crate::{msg::Message, MessageType};
+use crypto_markets::MarketType;
+use crypto_ws_client::*;
+use std::sync::mpsc::Sender;
+
+const EXCHANGE_NAME: &str = "coinbase_pro";
+
+pub(crate) fn crawl_l2_event_batched(
+    market_type: MarketType,
+    symbols: Option<&[String]>,
+    tx: Sender<Message>,
+    duration: Option<u64>,
+) {
+    let symbols: Vec<String> = match symbols {
+        Some(list) if !list.is_empty() => {
+            check_args(EXCHANGE_NAME, market_type, list);
+            list.to_vec()
+        }
+        _ => fetch_symbols_retry(EXCHANGE_NAME, market_type),
+    };
+    let tx = create_conversion_thread(
+        EXCHANGE_NAME.to_string(),
+        MessageType::L2Event,
+        market_type,
+        tx,
+    );
+
+    let ws_client = CoinbaseProWSClient::new(tx, None);
+    ws_client.subscribe_orderbook_batch(&symbols);
+    ws_client.run(duration);
+}
@@ -31,7 +31,7 @@ pub(crate) fn crawl_l2_event(
             // Huobi Spot market.$symbol.mbp.$levels must use wss://api.huobi.pro/feed
             // or wss://api-aws.huobi.pro/feed
             let ws_client = HuobiSpotWSClient::new(tx, Some("wss://api.huobi.pro/feed"));
-            ws_client.subscribe_orderbook(&symbols);
+            ws_client.subscribe_orderbook(&symbols).unwrap();
             ws_client.run(duration);
         }
         MarketType::InverseFuture
@@ -4,10 +4,14 @@ mod utils;
 pub(super) mod binance;
 pub(super) mod bitget;
 pub(super) mod bitmex;
+pub(super) mod coinbase_pro;
 pub(super) mod deribit;
 pub(super) mod huobi;
 pub(super) mod kucoin;
 pub(super) mod okex;
 
 pub use utils::fetch_symbols_retry;
-pub(super) use utils::{crawl_candlestick_ext, crawl_event, crawl_open_interest, crawl_snapshot};
+pub(super) use utils::{
+    crawl_bbo_from_l2_event, crawl_bbo_from_l2_topk, crawl_candlestick_ext, crawl_event,
+    crawl_open_interest, crawl_snapshot, spawn_crawler_thread,
+};
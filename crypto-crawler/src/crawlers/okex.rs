@@ -78,3 +78,39 @@ pub(crate) fn crawl_funding_rate(
         _ => panic!("OKEx {} does NOT have funding rates", market_type),
     }
 }
+
+#[allow(clippy::unnecessary_unwrap)]
+pub(crate) fn crawl_index_price(
+    market_type: MarketType,
+    symbols: Option<&[String]>,
+    tx: Sender<Message>,
+    duration: Option<u64>,
+) {
+    let tx = create_conversion_thread(
+        EXCHANGE_NAME.to_string(),
+        MessageType::IndexPrice,
+        market_type,
+        tx,
+    );
+
+    let indices: Vec<String> = if symbols.is_none() || symbols.unwrap().is_empty() {
+        // indices are named after underlying pairs, e.g., BTC-USD of BTC-USD-SWAP and BTC-USD-210625
+        let mut indices: Vec<String> = fetch_symbols_retry(EXCHANGE_NAME, market_type)
+            .iter()
+            .map(|symbol| symbol.split('-').take(2).collect::<Vec<&str>>().join("-"))
+            .collect();
+        indices.sort();
+        indices.dedup();
+        indices
+    } else {
+        symbols.unwrap().to_vec()
+    };
+    let channels: Vec<String> = indices
+        .into_iter()
+        .map(|index| format!("index/ticker:{}", index))
+        .collect();
+
+    let ws_client = OkexWSClient::new(tx, None);
+    ws_client.subscribe(&channels);
+    ws_client.run(duration);
+}
@@ -1,10 +1,12 @@
 use std::{
+    collections::{BTreeSet, HashMap},
+    ops::Deref,
     sync::{
         atomic::{AtomicBool, Ordering},
         mpsc::{
             self, {Receiver, Sender},
         },
-        Arc,
+        Arc, Mutex,
     },
     thread::JoinHandle,
     time::{Duration, Instant, SystemTime},
@@ -12,7 +14,8 @@ use std::{
 
 use crate::utils::{REST_LOCKS, WS_LOCKS};
 use crypto_markets::{fetch_symbols, get_market_types, MarketType};
-use crypto_rest_client::{fetch_l2_snapshot, fetch_l3_snapshot, fetch_open_interest};
+use crypto_msg_parser::{BboMsg, OrderBookManager};
+use crypto_rest_client::{fetch_l2_snapshot_with_depth, fetch_l3_snapshot, fetch_open_interest};
 use crypto_ws_client::*;
 use log::*;
 use rand::Rng;
@@ -121,11 +124,14 @@ fn get_cooldown_time_per_request(exchange: &str, market_type: MarketType) -> Dur
 }
 
 /// Crawl leve2 or level3 orderbook snapshots through RESTful APIs.
+///
+/// `depth` only applies to level2 snapshots.
 pub(crate) fn crawl_snapshot(
     exchange: &str,
     market_type: MarketType,
     msg_type: MessageType, // L2Snapshot or L3Snapshot
     symbols: Option<&[String]>,
+    depth: Option<usize>,
     tx: Sender<Message>,
     duration: Option<u64>,
 ) {
@@ -173,7 +179,9 @@ pub(crate) fn crawl_snapshot(
                 lock_.lock().unwrap();
             }
             let resp = match msg_type {
-                MessageType::L2Snapshot => fetch_l2_snapshot(exchange, market_type, symbol, None),
+                MessageType::L2Snapshot => {
+                    fetch_l2_snapshot_with_depth(exchange, market_type, symbol, depth, None)
+                }
                 MessageType::L3Snapshot => fetch_l3_snapshot(exchange, market_type, symbol, None),
                 _ => panic!("msg_type must be L2Snapshot or L3Snapshot"),
             };
@@ -189,6 +197,8 @@ pub(crate) fn crawl_snapshot(
                     success_count += 1;
                     backoff_factor = 1;
                     let message = Message::new(exchange.to_string(), market_type, msg_type, msg);
+                    #[cfg(feature = "metrics")]
+                    crate::metrics::record_message(&message);
                     tx.send(message).unwrap();
                 }
                 Err(err) => {
@@ -231,6 +241,8 @@ pub(crate) fn crawl_snapshot(
 pub(crate) fn crawl_open_interest(
     exchange: &str,
     market_type: MarketType,
+    symbols: Option<&[String]>,
+    interval: Option<u64>,
     tx: Sender<Message>,
     duration: Option<u64>,
 ) {
@@ -244,8 +256,9 @@ pub(crate) fn crawl_open_interest(
         .unwrap()
         .clone();
     loop {
+        let round_start = Instant::now();
         match exchange {
-            "bitz" | "deribit" | "dydx" | "ftx" | "huobi" | "kucoin" => {
+            "bitmex" | "bitz" | "deribit" | "dydx" | "ftx" | "huobi" | "kucoin" => {
                 let mut lock_ = lock.lock().unwrap();
                 if !lock_.owns_lock() {
                     lock_.lock().unwrap();
@@ -258,6 +271,8 @@ pub(crate) fn crawl_open_interest(
                         MessageType::OpenInterest,
                         json,
                     );
+                    #[cfg(feature = "metrics")]
+                    crate::metrics::record_message(&message);
                     tx.send(message).unwrap();
                 }
                 // Cooldown after each request, and make all other processes wait
@@ -268,7 +283,10 @@ pub(crate) fn crawl_open_interest(
                 }
             }
             "binance" | "bitget" | "bybit" | "gate" | "okex" | "zbg" => {
-                let real_symbols = fetch_symbols_retry(exchange, market_type);
+                let real_symbols = match symbols {
+                    Some(symbols) if !symbols.is_empty() => symbols.to_vec(),
+                    _ => fetch_symbols_retry(exchange, market_type),
+                };
 
                 let mut index = 0_usize;
                 let mut success_count = 0_u64;
@@ -297,6 +315,8 @@ pub(crate) fn crawl_open_interest(
                                 MessageType::OpenInterest,
                                 msg,
                             );
+                            #[cfg(feature = "metrics")]
+                            crate::metrics::record_message(&message);
                             tx.send(message).unwrap();
                         }
                         Err(err) => {
@@ -335,17 +355,27 @@ pub(crate) fn crawl_open_interest(
                 break;
             }
         }
-        std::thread::sleep(cooldown_time * 2); // if real_symbols is empty, CPU will be 100% without this line
+        if let Some(seconds) = interval {
+            // Poll once per interval, a round slower than the interval starts the next one immediately
+            let interval = Duration::from_secs(seconds);
+            let elapsed = round_start.elapsed();
+            if elapsed < interval {
+                std::thread::sleep(interval - elapsed);
+            }
+        } else {
+            std::thread::sleep(cooldown_time * 2); // if real_symbols is empty, CPU will be 100% without this line
+        }
     }
 }
 
-fn subscribe_with_lock(
+fn subscribe_or_unsubscribe_with_lock(
     exchange: &str,
     market_type: MarketType,
     msg_type: MessageType,
     symbols: &[String],
-    ws_client: Arc<dyn WSClient>,
-) {
+    ws_client: CrawlerWSClient,
+    subscribe: bool,
+) -> Result<(), UnsupportedChannel> {
     let lock = WS_LOCKS
         .get(exchange)
         .unwrap()
@@ -357,32 +387,43 @@ fn subscribe_with_lock(
     if interval.is_some() && !lock.owns_lock() {
         lock.lock().unwrap();
     }
-    match msg_type {
-        MessageType::BBO => ws_client.subscribe_bbo(symbols),
-        MessageType::Trade => ws_client.subscribe_trade(symbols),
-        MessageType::L2Event => ws_client.subscribe_orderbook(symbols),
-        MessageType::L3Event => ws_client.subscribe_l3_orderbook(symbols),
-        MessageType::L2TopK => ws_client.subscribe_orderbook_topk(symbols),
-        MessageType::Ticker => ws_client.subscribe_ticker(symbols),
+    let result = match (msg_type, subscribe) {
+        (MessageType::BBO, true) => ws_client.subscribe_bbo(symbols),
+        (MessageType::BBO, false) => ws_client.unsubscribe_bbo(symbols),
+        (MessageType::Trade, true) => ws_client.subscribe_trade(symbols),
+        (MessageType::Trade, false) => ws_client.unsubscribe_trade(symbols),
+        (MessageType::L2Event, true) => ws_client.subscribe_orderbook(symbols),
+        (MessageType::L2Event, false) => ws_client.unsubscribe_orderbook(symbols),
+        (MessageType::L3Event, true) => ws_client.subscribe_l3_orderbook(symbols),
+        (MessageType::L3Event, false) => ws_client.unsubscribe_l3_orderbook(symbols),
+        (MessageType::L2TopK, true) => ws_client.subscribe_orderbook_topk(symbols),
+        (MessageType::L2TopK, false) => ws_client.unsubscribe_orderbook_topk(symbols),
+        (MessageType::Ticker, true) => ws_client.subscribe_ticker(symbols),
+        (MessageType::Ticker, false) => ws_client.unsubscribe_ticker(symbols),
         _ => panic!(
             "{} {} does NOT have {} websocket channel",
             exchange, market_type, msg_type
         ),
     };
+    if result.is_ok() {
+        ws_client.update_symbols(symbols, subscribe);
+    }
     if let Some(interval) = interval {
         std::thread::sleep(Duration::from_millis(interval));
         if lock.owns_lock() {
             lock.unlock().unwrap();
         }
     }
+    result
 }
 
-fn subscribe_candlestick_with_lock(
+fn subscribe_or_unsubscribe_candlestick_with_lock(
     exchange: &str,
     market_type: MarketType,
     symbol_interval_list: &[(String, usize)],
-    ws_client: Arc<dyn WSClient>,
-) {
+    ws_client: CrawlerWSClient,
+    subscribe: bool,
+) -> Result<(), UnsupportedChannel> {
     let lock = WS_LOCKS
         .get(exchange)
         .unwrap()
@@ -394,13 +435,33 @@ fn subscribe_candlestick_with_lock(
     if interval.is_some() && !lock.owns_lock() {
         lock.lock().unwrap();
     }
-    ws_client.subscribe_candlestick(symbol_interval_list);
+    let raw_intervals: Vec<(String, Interval)> = symbol_interval_list
+        .iter()
+        .map(|(symbol, secs)| {
+            let interval = Interval::from_secs(*secs)
+                .unwrap_or_else(|| panic!("Unknown candlestick interval {} seconds", secs));
+            (symbol.clone(), interval)
+        })
+        .collect();
+    let result = if subscribe {
+        ws_client.subscribe_candlestick(&raw_intervals)
+    } else {
+        ws_client.unsubscribe_candlestick(&raw_intervals)
+    };
+    if result.is_ok() {
+        let symbols: Vec<String> = symbol_interval_list
+            .iter()
+            .map(|(symbol, _)| symbol.clone())
+            .collect();
+        ws_client.update_symbols(&symbols, subscribe);
+    }
     if let Some(interval) = interval {
         std::thread::sleep(Duration::from_millis(interval));
         if lock.owns_lock() {
             lock.unlock().unwrap();
         }
     }
+    result
 }
 
 fn get_connection_interval_ms(exchange: &str, _market_type: MarketType) -> Option<u64> {
@@ -422,7 +483,7 @@ fn get_send_interval_ms(exchange: &str, _market_type: MarketType) -> Option<u64>
     }
 }
 
-fn get_num_subscriptions_per_connection(exchange: &str) -> usize {
+fn max_channels_per_connection(exchange: &str) -> usize {
     match exchange {
         // A single connection can listen to a maximum of 200 streams
         "binance" => 200, // https://binance-docs.github.io/apidocs/futures/en/#websocket-market-streams
@@ -435,12 +496,61 @@ fn get_num_subscriptions_per_connection(exchange: &str) -> usize {
     }
 }
 
+// Splits items into the fewest shards allowed by max_per_shard, and balances
+// them so that shard sizes differ by at most one, e.g., 500 binance symbols
+// become 167 + 167 + 166 instead of 200 + 200 + 100.
+fn split_into_shards<T: Clone>(items: &[T], max_per_shard: usize) -> Vec<Vec<T>> {
+    if items.is_empty() {
+        return Vec::new();
+    }
+    let num_shards = (items.len() - 1) / max_per_shard + 1;
+    let base = items.len() / num_shards;
+    let remainder = items.len() % num_shards;
+    let mut shards = Vec::with_capacity(num_shards);
+    let mut start = 0;
+    for i in 0..num_shards {
+        let end = start + base + usize::from(i < remainder);
+        shards.push(items[start..end].to_vec());
+        start = end;
+    }
+    shards
+}
+
+// A websocket client created by crawlers, together with the symbols it has
+// subscribed to, which are listed in MessageType::Reset messages
+#[derive(Clone)]
+struct CrawlerWSClient {
+    ws_client: Arc<dyn WSClient + Send + Sync>,
+    symbols: Arc<Mutex<BTreeSet<String>>>,
+}
+
+impl CrawlerWSClient {
+    fn update_symbols(&self, symbols: &[String], subscribe: bool) {
+        let mut guard = self.symbols.lock().unwrap();
+        for symbol in symbols {
+            if subscribe {
+                guard.insert(symbol.clone());
+            } else {
+                guard.remove(symbol);
+            }
+        }
+    }
+}
+
+impl Deref for CrawlerWSClient {
+    type Target = dyn WSClient + Send + Sync;
+
+    fn deref(&self) -> &Self::Target {
+        self.ws_client.as_ref()
+    }
+}
+
 fn create_ws_client(
     exchange: &str,
     market_type: MarketType,
     msg_type: MessageType,
     tx: Sender<Message>,
-) -> Arc<dyn WSClient + Send + Sync> {
+) -> CrawlerWSClient {
     let lock = WS_LOCKS
         .get(exchange)
         .unwrap()
@@ -455,7 +565,21 @@ fn create_ws_client(
             std::thread::sleep(Duration::from_millis(interval));
         }
     }
-    let tx = create_conversion_thread(exchange.to_string(), msg_type, market_type, tx);
+    let symbols = Arc::new(Mutex::new(BTreeSet::new()));
+    let (reset_tx, reset_rx) = if get_reset_on_reconnect() {
+        let (reset_tx, reset_rx) = std::sync::mpsc::channel();
+        (Some(reset_tx), Some(reset_rx))
+    } else {
+        (None, None)
+    };
+    let tx = create_conversion_thread_ext(
+        exchange.to_string(),
+        msg_type,
+        market_type,
+        tx,
+        symbols.clone(),
+        reset_rx,
+    );
     let ws_client: Arc<dyn WSClient + Send + Sync> = match exchange {
         "binance" => match market_type {
             MarketType::Spot => Arc::new(BinanceSpotWSClient::new(tx, None)),
@@ -538,104 +662,351 @@ fn create_ws_client(
     if interval.is_some() && lock.owns_lock() {
         lock.unlock().unwrap();
     }
-    ws_client
+    ws_client.set_reset_on_reconnect(reset_tx);
+    #[cfg(feature = "metrics")]
+    crate::metrics::track_ws_client(exchange, market_type, &ws_client);
+    CrawlerWSClient { ws_client, symbols }
+}
+
+// Whether websocket clients reconnect in place and send MessageType::Reset
+// instead of exiting the process, read from RESET_ON_RECONNECT, default to false
+fn get_reset_on_reconnect() -> bool {
+    match std::env::var("RESET_ON_RECONNECT") {
+        Ok(value) => parse_bool_env("RESET_ON_RECONNECT", &value, false),
+        Err(_) => false,
+    }
+}
+
+// Parses true/false, 1/0, yes/no and on/off case-insensitively, warns and
+// returns `default` otherwise
+fn parse_bool_env(name: &str, value: &str, default: bool) -> bool {
+    match value.trim().to_lowercase().as_str() {
+        "true" | "1" | "yes" | "on" => true,
+        "false" | "0" | "no" | "off" | "" => false,
+        _ => {
+            warn!(
+                "Invalid value {} of {}, fall back to {}",
+                value, name, default
+            );
+            default
+        }
+    }
+}
+
+// How often to re-fetch symbols, read from REFRESH_SYMBOLS_INTERVAL in
+// seconds, default to one hour
+fn get_refresh_symbols_interval() -> Duration {
+    match std::env::var("REFRESH_SYMBOLS_INTERVAL") {
+        Ok(value) => parse_refresh_symbols_interval(&value),
+        Err(_) => Duration::from_secs(3600),
+    }
+}
+
+// Falls back to one hour if `value` is not a number, and clamps it between
+// one minute and one day, since 0 would re-fetch symbols in a busy loop
+fn parse_refresh_symbols_interval(value: &str) -> Duration {
+    let seconds = match value.trim().parse::<u64>() {
+        Ok(seconds) => seconds,
+        Err(_) => {
+            warn!(
+                "Invalid value {} of REFRESH_SYMBOLS_INTERVAL, fall back to 3600",
+                value
+            );
+            3600
+        }
+    };
+    let clamped = seconds.clamp(60, 86400);
+    if clamped != seconds {
+        warn!(
+            "REFRESH_SYMBOLS_INTERVAL {} is out of [60, 86400], use {} instead",
+            seconds, clamped
+        );
+    }
+    Duration::from_secs(clamped)
+}
+
+// Stack size in bytes of crawler threads of the exchange, read from
+// CRAWLER_THREAD_STACK_SIZE_{EXCHANGE} then CRAWLER_THREAD_STACK_SIZE,
+// e.g., CRAWLER_THREAD_STACK_SIZE_BITMEX=8388608, None means the std default
+fn get_thread_stack_size(exchange: &str) -> Option<usize> {
+    let name = format!("CRAWLER_THREAD_STACK_SIZE_{}", exchange.to_uppercase());
+    match std::env::var(&name) {
+        Ok(value) => parse_stack_size_env(&name, &value),
+        Err(_) => std::env::var("CRAWLER_THREAD_STACK_SIZE")
+            .ok()
+            .and_then(|value| parse_stack_size_env("CRAWLER_THREAD_STACK_SIZE", &value)),
+    }
+}
+
+// Parses a stack size in bytes, warns and falls back to the std default if
+// it's not a number or smaller than 64 KiB, which would overflow right away
+fn parse_stack_size_env(name: &str, value: &str) -> Option<usize> {
+    match value.trim().parse::<usize>() {
+        Ok(size) if size >= 64 * 1024 => Some(size),
+        _ => {
+            warn!(
+                "Invalid value {} of {}, fall back to the default stack size",
+                value, name
+            );
+            None
+        }
+    }
+}
+
+// crawler-{exchange}-{market_type}-{msg_type}, followed by -{role} if not empty
+fn crawler_thread_name(
+    exchange: &str,
+    market_type: MarketType,
+    msg_type: MessageType,
+    role: &str,
+) -> String {
+    let name = format!("crawler-{}-{}-{}", exchange, market_type, msg_type);
+    if role.is_empty() {
+        name
+    } else {
+        format!("{}-{}", name, role)
+    }
+}
+
+// Spawns a named thread, so that a stuck thread can be identified in a debugger
+pub(crate) fn spawn_crawler_thread<F>(
+    exchange: &str,
+    market_type: MarketType,
+    msg_type: MessageType,
+    role: &str,
+    f: F,
+) -> JoinHandle<()>
+where
+    F: FnOnce() + Send + 'static,
+{
+    let mut builder = std::thread::Builder::new().name(crawler_thread_name(
+        exchange,
+        market_type,
+        msg_type,
+        role,
+    ));
+    if let Some(stack_size) = get_thread_stack_size(exchange) {
+        builder = builder.stack_size(stack_size);
+    }
+    builder.spawn(f).unwrap()
+}
+
+// Symbols listed and delisted since the last refresh
+#[derive(Debug, PartialEq)]
+struct SymbolChange {
+    added: Vec<String>,
+    removed: Vec<String>,
+}
+
+impl SymbolChange {
+    // New symbols are in `latest` only, delisted symbols in `subscribed` only
+    fn diff(subscribed: &[String], latest: &[String]) -> Self {
+        SymbolChange {
+            added: latest
+                .iter()
+                .filter(|s| !subscribed.contains(s))
+                .cloned()
+                .collect(),
+            removed: subscribed
+                .iter()
+                .filter(|s| !latest.contains(s))
+                .cloned()
+                .collect(),
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty()
+    }
+
+    // Updates `subscribed` to the latest symbols
+    fn apply(&self, subscribed: &mut Vec<String>) {
+        subscribed.retain(|s| !self.removed.contains(s));
+        subscribed.extend(self.added.iter().cloned());
+    }
 }
 
 fn create_symbol_discovery_thread(
     exchange: String,
     market_type: MarketType,
+    msg_type: MessageType,
     should_stop: Arc<AtomicBool>,
     subscribed_symbols: Vec<String>,
-    tx: Sender<Vec<String>>, // send out new symbols
+    tx: Sender<SymbolChange>, // send out listed and delisted symbols
+    tx_msg: Sender<Message>,  // send out MessageType::SymbolChange messages
 ) -> JoinHandle<()> {
-    let num_topics_per_connection = get_num_subscriptions_per_connection(&exchange);
-    std::thread::spawn(move || {
-        let mut subscribed_symbols = subscribed_symbols;
-        let mut num_subscribed_of_last_client =
-            subscribed_symbols.len() % num_topics_per_connection;
-        let mut rng = rand::thread_rng();
-        while !should_stop.load(Ordering::Acquire) {
-            // update symbols every hour
-            std::thread::sleep(Duration::from_secs(3600));
-            let latest_symbols = if exchange == "binance" {
-                fetch_symbols_retry(&exchange, market_type)
-                    .into_iter()
-                    .map(|s| s.to_lowercase())
-                    .collect()
-            } else {
-                fetch_symbols_retry(&exchange, market_type)
-            };
-            let mut new_symbols: Vec<String> = latest_symbols
-                .iter()
-                .filter(|s| !subscribed_symbols.contains(s))
-                .cloned()
-                .collect();
+    let num_topics_per_connection = max_channels_per_connection(&exchange);
+    let refresh_interval = get_refresh_symbols_interval();
+    let exchange_clone = exchange.clone();
+    spawn_crawler_thread(
+        &exchange_clone,
+        market_type,
+        msg_type,
+        "symbol-discovery",
+        move || {
+            let mut subscribed_symbols = subscribed_symbols;
+            let mut num_subscribed_of_last_client =
+                split_into_shards(&subscribed_symbols, num_topics_per_connection)
+                    .last()
+                    .map_or(0, |shard| shard.len());
+            let mut rng = rand::thread_rng();
+            while !should_stop.load(Ordering::Acquire) {
+                std::thread::sleep(refresh_interval);
+                let latest_symbols: Vec<String> = if exchange == "binance" {
+                    fetch_symbols_retry(&exchange, market_type)
+                        .into_iter()
+                        .map(|s| s.to_lowercase())
+                        .collect()
+                } else {
+                    fetch_symbols_retry(&exchange, market_type)
+                };
+                if latest_symbols.is_empty() {
+                    // fetch_symbols_retry() failed, do NOT treat all symbols as delisted
+                    continue;
+                }
+                let change = SymbolChange::diff(&subscribed_symbols, &latest_symbols);
 
-            if !new_symbols.is_empty() {
-                warn!("Found new symbols: {}", new_symbols.join(", "));
-                tx.send(new_symbols.clone()).unwrap();
-                num_subscribed_of_last_client += new_symbols.len();
-                subscribed_symbols.append(&mut new_symbols);
-            }
-            if num_subscribed_of_last_client >= num_topics_per_connection {
-                warn!(
-                    "The last connection has subscribed {} topics, which is more than {}, restarting the process",
-                    num_subscribed_of_last_client, num_topics_per_connection,
-                );
-                let millis = rng.gen_range(3000_u64..10000_u64);
-                std::thread::sleep(Duration::from_millis(millis)); // sleep for a random time
-                std::process::exit(0); // pm2 will restart the whole process
+                if !change.is_empty() {
+                    if !change.added.is_empty() {
+                        warn!("Found new symbols: {}", change.added.join(", "));
+                        // so that messages of new contracts can be parsed
+                        crypto_contract_value::refresh_contract_values(&exchange, market_type);
+                    }
+                    if !change.removed.is_empty() {
+                        warn!("Found delisted symbols: {}", change.removed.join(", "));
+                    }
+                    let json =
+                        serde_json::json!({"added": change.added, "removed": change.removed})
+                            .to_string();
+                    let message = Message::new(
+                        exchange.clone(),
+                        market_type,
+                        MessageType::SymbolChange,
+                        json,
+                    );
+                    tx_msg.send(message).unwrap();
+
+                    num_subscribed_of_last_client += change.added.len();
+                    change.apply(&mut subscribed_symbols);
+                    tx.send(change).unwrap();
+                }
+                if num_subscribed_of_last_client >= num_topics_per_connection {
+                    warn!(
+                        "The last connection has subscribed {} topics, which is more than {}, restarting the process",
+                        num_subscribed_of_last_client, num_topics_per_connection,
+                    );
+                    let millis = rng.gen_range(3000_u64..10000_u64);
+                    std::thread::sleep(Duration::from_millis(millis)); // sleep for a random time
+                    std::process::exit(0); // pm2 will restart the whole process
+                }
             }
-        }
-    })
+        },
+    )
 }
 
-fn create_new_symbol_receiver_thread(
+// New symbols are subscribed by the last client, while delisted symbols are
+// unsubscribed from all clients, since each client only sends unsubscribe
+// commands for channels subscribed by itself.
+fn create_symbol_change_receiver_thread(
     exchange: String,
     msg_type: MessageType,
     market_type: MarketType,
-    rx: Receiver<Vec<String>>,
-    ws_client: Arc<dyn WSClient + Send + Sync>,
+    rx: Receiver<SymbolChange>,
+    ws_clients: Vec<CrawlerWSClient>,
 ) -> JoinHandle<()> {
-    std::thread::spawn(move || {
-        for new_symbols in rx {
-            subscribe_with_lock(
-                &exchange,
-                market_type,
-                msg_type,
-                &new_symbols,
-                ws_client.clone(),
-            );
-        }
-    })
+    let exchange_clone = exchange.clone();
+    spawn_crawler_thread(
+        &exchange_clone,
+        market_type,
+        msg_type,
+        "symbol-change",
+        move || {
+            for change in rx {
+                if !change.added.is_empty() {
+                    if let Err(err) = subscribe_or_unsubscribe_with_lock(
+                        &exchange,
+                        market_type,
+                        msg_type,
+                        &change.added,
+                        ws_clients.last().unwrap().clone(),
+                        true,
+                    ) {
+                        error!("{} {}", market_type, err);
+                    }
+                }
+                if !change.removed.is_empty() {
+                    for ws_client in ws_clients.iter() {
+                        if let Err(err) = subscribe_or_unsubscribe_with_lock(
+                            &exchange,
+                            market_type,
+                            msg_type,
+                            &change.removed,
+                            ws_client.clone(),
+                            false,
+                        ) {
+                            error!("{} {}", market_type, err);
+                        }
+                    }
+                }
+            }
+        },
+    )
 }
 
-fn create_new_symbol_receiver_thread_candlestick(
+fn create_symbol_change_receiver_thread_candlestick(
     exchange: String,
     market_type: MarketType,
     intervals: Vec<usize>,
-    rx: Receiver<Vec<String>>,
-    ws_client: Arc<dyn WSClient + Send + Sync>,
+    rx: Receiver<SymbolChange>,
+    ws_clients: Vec<CrawlerWSClient>,
 ) -> JoinHandle<()> {
-    std::thread::spawn(move || {
-        for new_symbols in rx {
-            let new_symbol_interval_list = new_symbols
-                .iter()
-                .flat_map(|symbol| {
-                    intervals
-                        .clone()
-                        .into_iter()
-                        .map(move |interval| (symbol.clone(), interval))
-                })
-                .collect::<Vec<(String, usize)>>();
-            subscribe_candlestick_with_lock(
-                &exchange,
-                market_type,
-                new_symbol_interval_list.as_slice(),
-                ws_client.clone(),
-            );
-        }
-    })
+    let to_symbol_interval_list = move |symbols: &[String]| {
+        symbols
+            .iter()
+            .flat_map(|symbol| {
+                intervals
+                    .clone()
+                    .into_iter()
+                    .map(move |interval| (symbol.clone(), interval))
+            })
+            .collect::<Vec<(String, usize)>>()
+    };
+    let exchange_clone = exchange.clone();
+    spawn_crawler_thread(
+        &exchange_clone,
+        market_type,
+        MessageType::Candlestick,
+        "symbol-change",
+        move || {
+            for change in rx {
+                if !change.added.is_empty() {
+                    if let Err(err) = subscribe_or_unsubscribe_candlestick_with_lock(
+                        &exchange,
+                        market_type,
+                        to_symbol_interval_list(&change.added).as_slice(),
+                        ws_clients.last().unwrap().clone(),
+                        true,
+                    ) {
+                        error!("{} {}", market_type, err);
+                    }
+                }
+                if !change.removed.is_empty() {
+                    let symbol_interval_list = to_symbol_interval_list(&change.removed);
+                    for ws_client in ws_clients.iter() {
+                        if let Err(err) = subscribe_or_unsubscribe_candlestick_with_lock(
+                            &exchange,
+                            market_type,
+                            symbol_interval_list.as_slice(),
+                            ws_client.clone(),
+                            false,
+                        ) {
+                            error!("{} {}", market_type, err);
+                        }
+                    }
+                }
+            }
+        },
+    )
 }
 
 // create a thread to convert Sender<Message> Sender<String>
@@ -645,13 +1016,57 @@ pub(crate) fn create_conversion_thread(
     market_type: MarketType,
     tx: Sender<Message>,
 ) -> Sender<String> {
-    let (tx_raw, rx_raw) = std::sync::mpsc::channel();
-    std::thread::spawn(move || {
-        for json in rx_raw {
-            let msg = Message::new(exchange.clone(), market_type, msg_type, json);
-            tx.send(msg).unwrap();
-        }
-    });
+    create_conversion_thread_ext(
+        exchange,
+        msg_type,
+        market_type,
+        tx,
+        Arc::new(Mutex::new(BTreeSet::new())),
+        None,
+    )
+}
+
+// Same as create_conversion_thread(), and converts reset notifications from
+// `reset_rx` into MessageType::Reset messages listing `symbols`
+fn create_conversion_thread_ext(
+    exchange: String,
+    msg_type: MessageType,
+    market_type: MarketType,
+    tx: Sender<Message>,
+    symbols: Arc<Mutex<BTreeSet<String>>>,
+    reset_rx: Option<Receiver<ResetNotification>>,
+) -> Sender<String> {
+    let (tx_raw, rx_raw) = std::sync::mpsc::channel::<String>();
+    let exchange_clone = exchange.clone();
+    spawn_crawler_thread(
+        &exchange_clone,
+        market_type,
+        msg_type,
+        "conversion",
+        move || {
+            for json in rx_raw {
+                // sent before any message of the new connection
+                let num_resets = reset_rx.as_ref().map_or(0, |rx| rx.try_iter().count());
+                if num_resets > 0 {
+                    let symbols: Vec<String> = symbols.lock().unwrap().iter().cloned().collect();
+                    warn!(
+                        "{} {} {} reconnected, resetting {}",
+                        exchange,
+                        market_type,
+                        msg_type,
+                        symbols.join(", ")
+                    );
+                    let json = serde_json::json!({ "symbols": symbols }).to_string();
+                    let msg = Message::new(exchange.clone(), market_type, MessageType::Reset, json);
+                    tx.send(msg).unwrap();
+                }
+                let msg = Message::new(exchange.clone(), market_type, msg_type, json);
+                #[cfg(feature = "metrics")]
+                crate::metrics::record_message(&msg);
+                tx.send(msg).unwrap();
+            }
+        },
+    );
     tx_raw
 }
 
@@ -663,7 +1078,7 @@ pub(crate) fn crawl_event(
     tx: Sender<Message>,
     duration: Option<u64>,
 ) {
-    let num_topics_per_connection = get_num_subscriptions_per_connection(exchange);
+    let num_topics_per_connection = max_channels_per_connection(exchange);
     let is_empty = match symbols {
         Some(list) => {
             if list.is_empty() {
@@ -694,107 +1109,100 @@ pub(crate) fn crawl_event(
         return;
     }
 
-    // create a thread to discover new symbols
-    let (tx_symbols, rx_symbols): (Sender<Vec<String>>, Receiver<Vec<String>>) = mpsc::channel();
+    // create a thread to discover listed and delisted symbols
+    let (tx_symbols, rx_symbols): (Sender<SymbolChange>, Receiver<SymbolChange>) = mpsc::channel();
     let symbol_discovery_thread_stop = Arc::new(AtomicBool::new(false));
     let symbol_discovery_thread = if automatic_symbol_discovery {
         let thread = create_symbol_discovery_thread(
             exchange.to_string(),
             market_type,
+            msg_type,
             symbol_discovery_thread_stop.clone(),
             real_symbols.clone(),
             tx_symbols,
+            tx.clone(),
         );
         Some(thread)
     } else {
         None
     };
 
-    // create a thread to convert Sender<String> to Sender<Message>
-
-    let new_symbol_receiver_thread = if real_symbols.len() <= num_topics_per_connection {
+    let symbol_change_receiver_thread = if real_symbols.len() <= num_topics_per_connection {
         let ws_client = create_ws_client(exchange, market_type, msg_type, tx);
-        subscribe_with_lock(
+        let subscribed = subscribe_or_unsubscribe_with_lock(
             exchange,
             market_type,
             msg_type,
             &real_symbols,
             ws_client.clone(),
+            true,
         );
-        let new_symbol_receiver_thread = if automatic_symbol_discovery {
-            let thread = create_new_symbol_receiver_thread(
+        let symbol_change_receiver_thread = if automatic_symbol_discovery {
+            let thread = create_symbol_change_receiver_thread(
                 exchange.to_string(),
                 msg_type,
                 market_type,
                 rx_symbols,
-                ws_client.clone(),
+                vec![ws_client.clone()],
             );
             Some(thread)
         } else {
             None
         };
-        ws_client.run(duration);
+        match subscribed {
+            Ok(()) => ws_client.run(duration),
+            Err(err) => error!("{} {}", market_type, err),
+        }
         ws_client.close();
-        new_symbol_receiver_thread
+        symbol_change_receiver_thread
     } else {
-        // split to chunks
-        let mut chunks: Vec<Vec<String>> = Vec::new();
-        for i in (0..real_symbols.len()).step_by(num_topics_per_connection) {
-            let chunk = (&real_symbols
-                [i..(std::cmp::min(i + num_topics_per_connection, real_symbols.len()))])
-                .to_vec();
-            chunks.push(chunk);
-        }
+        // one connection per shard, each running in its own thread
+        let chunks = split_into_shards(&real_symbols, num_topics_per_connection);
         assert!(chunks.len() > 1);
-        let n = chunks.len();
 
-        let last_ws_client = create_ws_client(exchange, market_type, msg_type, tx.clone());
+        // create all clients beforehand so that delisted symbols can be unsubscribed from any of them
+        let ws_clients: Vec<CrawlerWSClient> = chunks
+            .iter()
+            .map(|_| create_ws_client(exchange, market_type, msg_type, tx.clone()))
+            .collect();
         let mut join_handles: Vec<std::thread::JoinHandle<()>> = Vec::new();
-        for (index, chunk) in chunks.into_iter().enumerate() {
+        for (index, (chunk, ws_client)) in chunks
+            .into_iter()
+            .zip(ws_clients.iter().cloned())
+            .enumerate()
+        {
             let exchange_clone = exchange.to_string();
-            let tx_clone = tx.clone();
-            let last_ws_client_clone = last_ws_client.clone();
-            let handle = std::thread::Builder::new()
-                .name(format!(
-                    "websocket.{}.{}.{}.{}",
-                    exchange, msg_type, market_type, index
-                ))
-                .spawn(move || {
+            let handle = spawn_crawler_thread(
+                exchange,
+                market_type,
+                msg_type,
+                &format!("ws-{}", index),
+                move || {
                     let exchange: &str = exchange_clone.as_str();
-                    if index == n - 1 {
-                        subscribe_with_lock(
-                            exchange,
-                            market_type,
-                            msg_type,
-                            &chunk,
-                            last_ws_client_clone.clone(),
-                        );
-                        last_ws_client_clone.run(duration);
-                        last_ws_client_clone.close();
-                    } else {
-                        let ws_client = create_ws_client(exchange, market_type, msg_type, tx_clone);
-                        subscribe_with_lock(
-                            exchange,
-                            market_type,
-                            msg_type,
-                            &chunk,
-                            ws_client.clone(),
-                        );
-                        ws_client.run(duration);
-                        ws_client.close();
+                    match subscribe_or_unsubscribe_with_lock(
+                        exchange,
+                        market_type,
+                        msg_type,
+                        &chunk,
+                        ws_client.clone(),
+                        true,
+                    ) {
+                        Ok(()) => ws_client.run(duration),
+                        Err(err) => error!("{} {}", market_type, err),
                     }
-                })
-                .unwrap();
+                    ws_client.close();
+                },
+            );
             join_handles.push(handle);
         }
         drop(tx);
-        let new_symbol_receiver_thread = if automatic_symbol_discovery {
-            let thread = create_new_symbol_receiver_thread(
+        let symbol_change_receiver_thread = if automatic_symbol_discovery {
+            let thread = create_symbol_change_receiver_thread(
                 exchange.to_string(),
                 msg_type,
                 market_type,
                 rx_symbols,
-                last_ws_client,
+                ws_clients,
             );
             Some(thread)
         } else {
@@ -803,15 +1211,174 @@ pub(crate) fn crawl_event(
         for handle in join_handles {
             handle.join().unwrap();
         }
-        new_symbol_receiver_thread
+        symbol_change_receiver_thread
     };
     symbol_discovery_thread_stop.store(true, Ordering::Release);
     if let Some(thread) = symbol_discovery_thread {
         thread.join().unwrap();
-        new_symbol_receiver_thread.unwrap().join().unwrap();
+        symbol_change_receiver_thread.unwrap().join().unwrap();
     }
 }
 
+// Crawls level2 top-k snapshots and derives synthetic BBO messages from them,
+// for exchanges without a BBO channel
+pub(crate) fn crawl_bbo_from_l2_topk(
+    exchange: &str,
+    market_type: MarketType,
+    symbols: Option<&[String]>,
+    tx: Sender<Message>,
+    duration: Option<u64>,
+) {
+    let (tx_topk, rx_topk) = mpsc::channel::<Message>();
+    let exchange_clone = exchange.to_string();
+    let derive_thread = spawn_crawler_thread(
+        exchange,
+        market_type,
+        MessageType::BBO,
+        "derive",
+        move || {
+            for msg in rx_topk {
+                if msg.msg_type != MessageType::L2TopK {
+                    // e.g., SymbolChange
+                    tx.send(msg).unwrap();
+                    continue;
+                }
+                let orderbooks = match crypto_msg_parser::parse_l2(
+                    &exchange_clone,
+                    market_type,
+                    &msg.json,
+                    Some(msg.received_at as i64),
+                ) {
+                    Ok(orderbooks) => orderbooks,
+                    Err(err) => {
+                        warn!("Failed to parse {}, {}", msg.json, err);
+                        #[cfg(feature = "metrics")]
+                        crate::metrics::record_parse_error(
+                            &exchange_clone,
+                            market_type,
+                            MessageType::L2TopK,
+                        );
+                        continue;
+                    }
+                };
+                for bbo in orderbooks.iter().filter_map(BboMsg::from_snapshot) {
+                    let mut bbo_msg = Message::new(
+                        exchange_clone.clone(),
+                        market_type,
+                        MessageType::BBO,
+                        serde_json::to_string(&bbo).unwrap(),
+                    );
+                    bbo_msg.received_at = msg.received_at;
+                    tx.send(bbo_msg).unwrap();
+                }
+            }
+        },
+    );
+
+    crawl_event(
+        exchange,
+        MessageType::L2TopK,
+        market_type,
+        symbols,
+        tx_topk,
+        duration,
+    );
+    derive_thread.join().unwrap();
+}
+
+// Maintains orderbooks from level2 incremental messages in rx, and sends a
+// BBO message to tx whenever the best bid or ask of a symbol changes.
+fn derive_bbo_from_l2_event(
+    exchange: &str,
+    market_type: MarketType,
+    rx: Receiver<Message>,
+    tx: Sender<Message>,
+) {
+    let mut manager = OrderBookManager::new();
+    let mut last_bbo = HashMap::<String, (f64, f64, f64, f64)>::new();
+    for msg in rx {
+        if msg.msg_type != MessageType::L2Event {
+            // e.g., SymbolChange
+            tx.send(msg).unwrap();
+            continue;
+        }
+        let orderbooks = match crypto_msg_parser::parse_l2(
+            exchange,
+            market_type,
+            &msg.json,
+            Some(msg.received_at as i64),
+        ) {
+            Ok(orderbooks) => orderbooks,
+            Err(err) => {
+                warn!("Failed to parse {}, {}", msg.json, err);
+                #[cfg(feature = "metrics")]
+                crate::metrics::record_parse_error(exchange, market_type, MessageType::L2Event);
+                continue;
+            }
+        };
+        for orderbook in orderbooks {
+            let symbol = orderbook.symbol.clone();
+            if !manager.update(orderbook) {
+                continue;
+            }
+            let bbo = match manager
+                .snapshot(exchange, market_type, &symbol, 1)
+                .as_ref()
+                .and_then(BboMsg::from_snapshot)
+            {
+                Some(bbo) => bbo,
+                None => continue,
+            };
+            let top = (
+                bbo.bid_price,
+                bbo.bid_quantity,
+                bbo.ask_price,
+                bbo.ask_quantity,
+            );
+            if last_bbo.get(&symbol) == Some(&top) {
+                continue;
+            }
+            last_bbo.insert(symbol, top);
+            let mut bbo_msg = Message::new(
+                exchange.to_string(),
+                market_type,
+                MessageType::BBO,
+                serde_json::to_string(&bbo).unwrap(),
+            );
+            bbo_msg.received_at = msg.received_at;
+            tx.send(bbo_msg).unwrap();
+        }
+    }
+}
+
+pub(crate) fn crawl_bbo_from_l2_event(
+    exchange: &str,
+    market_type: MarketType,
+    symbols: Option<&[String]>,
+    tx: Sender<Message>,
+    duration: Option<u64>,
+) {
+    let (tx_event, rx_event) = mpsc::channel::<Message>();
+    let exchange_clone = exchange.to_string();
+    let derive_thread = spawn_crawler_thread(
+        exchange,
+        market_type,
+        MessageType::BBO,
+        "derive",
+        move || derive_bbo_from_l2_event(&exchange_clone, market_type, rx_event, tx),
+    );
+
+    crawl_event(
+        exchange,
+        MessageType::L2Event,
+        market_type,
+        symbols,
+        tx_event,
+        duration,
+    );
+    derive_thread.join().unwrap();
+}
+
 // from 1m to 5m
 fn get_candlestick_intervals(exchange: &str, market_type: MarketType) -> Vec<usize> {
     match exchange {
@@ -839,7 +1406,7 @@ pub(crate) fn crawl_candlestick_ext(
     tx: Sender<Message>,
     duration: Option<u64>,
 ) {
-    let num_topics_per_connection = get_num_subscriptions_per_connection(exchange);
+    let num_topics_per_connection = max_channels_per_connection(exchange);
     let is_empty = match symbol_interval_list {
         Some(list) => {
             if list.is_empty() {
@@ -880,101 +1447,105 @@ pub(crate) fn crawl_candlestick_ext(
         error!("symbol_interval_list is empty due to fetch_symbols_retry() failure");
         return;
     }
-    let real_symbols: Vec<String> = symbol_interval_list.iter().map(|t| t.0.clone()).collect();
-    let real_intervals: Vec<usize> = symbol_interval_list.iter().map(|t| t.1).collect();
+    let mut real_symbols: Vec<String> = symbol_interval_list.iter().map(|t| t.0.clone()).collect();
+    real_symbols.sort();
+    real_symbols.dedup();
+    let mut real_intervals: Vec<usize> = symbol_interval_list.iter().map(|t| t.1).collect();
+    real_intervals.sort_unstable();
+    real_intervals.dedup();
 
-    // create a thread to discover new symbols
-    let (tx_symbols, rx_symbols): (Sender<Vec<String>>, Receiver<Vec<String>>) = mpsc::channel();
+    // create a thread to discover listed and delisted symbols
+    let (tx_symbols, rx_symbols): (Sender<SymbolChange>, Receiver<SymbolChange>) = mpsc::channel();
     let symbol_discovery_thread_stop = Arc::new(AtomicBool::new(false));
     let symbol_discovery_thread = if automatic_symbol_discovery {
         let thread = create_symbol_discovery_thread(
             exchange.to_string(),
             market_type,
+            MessageType::Candlestick,
             symbol_discovery_thread_stop.clone(),
             real_symbols,
             tx_symbols,
+            tx.clone(),
         );
         Some(thread)
     } else {
         None
     };
 
-    let new_symbol_receiver_thread = if symbol_interval_list.len() <= num_topics_per_connection {
+    let symbol_change_receiver_thread = if symbol_interval_list.len() <= num_topics_per_connection {
         let ws_client = create_ws_client(exchange, market_type, MessageType::Candlestick, tx);
-        subscribe_candlestick_with_lock(
+        let subscribed = subscribe_or_unsubscribe_candlestick_with_lock(
             exchange,
             market_type,
             symbol_interval_list.as_slice(),
             ws_client.clone(),
+            true,
         );
-        let new_symbol_receiver_thread = if automatic_symbol_discovery {
-            let thread = create_new_symbol_receiver_thread_candlestick(
+        let symbol_change_receiver_thread = if automatic_symbol_discovery {
+            let thread = create_symbol_change_receiver_thread_candlestick(
                 exchange.to_string(),
                 market_type,
                 real_intervals,
                 rx_symbols,
-                ws_client.clone(),
+                vec![ws_client.clone()],
             );
             Some(thread)
         } else {
             None
         };
-        ws_client.run(duration);
+        match subscribed {
+            Ok(()) => ws_client.run(duration),
+            Err(err) => error!("{} {}", market_type, err),
+        }
         ws_client.close();
-        new_symbol_receiver_thread
+        symbol_change_receiver_thread
     } else {
-        // split to chunks
-        let mut chunks: Vec<Vec<(String, usize)>> = Vec::new();
-        for i in (0..symbol_interval_list.len()).step_by(num_topics_per_connection) {
-            let chunk: Vec<(String, usize)> = (&symbol_interval_list
-                [i..(std::cmp::min(i + num_topics_per_connection, symbol_interval_list.len()))])
-                .to_vec();
-            chunks.push(chunk);
-        }
+        // one connection per shard, each running in its own thread
+        let chunks = split_into_shards(&symbol_interval_list, num_topics_per_connection);
         assert!(chunks.len() > 1);
-        let n = chunks.len();
 
-        let last_ws_client =
-            create_ws_client(exchange, market_type, MessageType::Candlestick, tx.clone());
+        // create all clients beforehand so that delisted symbols can be unsubscribed from any of them
+        let ws_clients: Vec<CrawlerWSClient> = chunks
+            .iter()
+            .map(|_| create_ws_client(exchange, market_type, MessageType::Candlestick, tx.clone()))
+            .collect();
         let mut join_handles: Vec<std::thread::JoinHandle<()>> = Vec::new();
-        for (index, chunk) in chunks.into_iter().enumerate() {
+        for (index, (chunk, ws_client)) in chunks
+            .into_iter()
+            .zip(ws_clients.iter().cloned())
+            .enumerate()
+        {
             let exchange_clone = exchange.to_string();
-            let tx_clone = tx.clone();
-            let last_ws_client_clone = last_ws_client.clone();
-            let handle = std::thread::spawn(move || {
-                let exchange: &str = exchange_clone.as_str();
-                if index == n - 1 {
-                    subscribe_candlestick_with_lock(
-                        exchange,
-                        market_type,
-                        chunk.as_slice(),
-                        last_ws_client_clone.clone(),
-                    );
-                    last_ws_client_clone.run(duration);
-                    last_ws_client_clone.close();
-                } else {
-                    let ws_client =
-                        create_ws_client(exchange, market_type, MessageType::Candlestick, tx_clone);
-                    subscribe_candlestick_with_lock(
+            let handle = spawn_crawler_thread(
+                exchange,
+                market_type,
+                MessageType::Candlestick,
+                &format!("ws-{}", index),
+                move || {
+                    let exchange: &str = exchange_clone.as_str();
+                    match subscribe_or_unsubscribe_candlestick_with_lock(
                         exchange,
                         market_type,
                         chunk.as_slice(),
                         ws_client.clone(),
-                    );
-                    ws_client.run(duration);
+                        true,
+                    ) {
+                        Ok(()) => ws_client.run(duration),
+                        Err(err) => error!("{} {}", market_type, err),
+                    }
                     ws_client.close();
-                }
-            });
+                },
+            );
             join_handles.push(handle);
         }
         drop(tx);
-        let new_symbol_receiver_thread = if automatic_symbol_discovery {
-            let thread = create_new_symbol_receiver_thread_candlestick(
+        let symbol_change_receiver_thread = if automatic_symbol_discovery {
+            let thread = create_symbol_change_receiver_thread_candlestick(
                 exchange.to_string(),
                 market_type,
                 real_intervals,
                 rx_symbols,
-                last_ws_client,
+                ws_clients,
             );
             Some(thread)
         } else {
@@ -983,11 +1554,222 @@ pub(crate) fn crawl_candlestick_ext(
         for handle in join_handles {
             handle.join().unwrap();
         }
-        new_symbol_receiver_thread
+        symbol_change_receiver_thread
     };
     symbol_discovery_thread_stop.store(true, Ordering::Release);
     if let Some(thread) = symbol_discovery_thread {
         thread.join().unwrap();
-        new_symbol_receiver_thread.unwrap().join().unwrap();
+        symbol_change_receiver_thread.unwrap().join().unwrap();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        create_conversion_thread_ext, derive_bbo_from_l2_event, max_channels_per_connection,
+        parse_bool_env, parse_refresh_symbols_interval, parse_stack_size_env, spawn_crawler_thread,
+        split_into_shards, SymbolChange,
+    };
+    use crate::{Message, MessageType};
+    use crypto_markets::MarketType;
+    use crypto_msg_parser::BboMsg;
+    use crypto_ws_client::ResetNotification;
+    use std::{
+        collections::BTreeSet,
+        sync::{Arc, Mutex},
+        time::Duration,
+    };
+
+    #[test]
+    fn binance_500_symbols_3_connections() {
+        let symbols: Vec<String> = (0..500).map(|i| format!("symbol{}", i)).collect();
+        let shards = split_into_shards(&symbols, max_channels_per_connection("binance"));
+        assert_eq!(3, shards.len());
+        assert_eq!(
+            vec![167, 167, 166],
+            shards.iter().map(|s| s.len()).collect::<Vec<_>>()
+        );
+        assert_eq!(symbols, shards.concat());
+    }
+
+    #[test]
+    fn unlimited() {
+        let symbols: Vec<String> = (0..500).map(|i| format!("symbol{}", i)).collect();
+        assert_eq!(
+            1,
+            split_into_shards(&symbols, max_channels_per_connection("bitmex")).len()
+        );
+        assert!(split_into_shards::<String>(&[], 200).is_empty());
+    }
+
+    #[test]
+    fn bool_env() {
+        assert!(parse_bool_env("RESET_ON_RECONNECT", "True", false));
+        assert!(parse_bool_env("RESET_ON_RECONNECT", "1", false));
+        assert!(!parse_bool_env("RESET_ON_RECONNECT", "off", true));
+        assert!(!parse_bool_env("RESET_ON_RECONNECT", "ture", false));
+        assert!(parse_bool_env("RESET_ON_RECONNECT", "ture", true));
+    }
+
+    #[test]
+    fn stack_size_env() {
+        let name = "CRAWLER_THREAD_STACK_SIZE";
+        assert_eq!(Some(8388608), parse_stack_size_env(name, "8388608"));
+        assert_eq!(Some(8388608), parse_stack_size_env(name, " 8388608\n"));
+        assert_eq!(None, parse_stack_size_env(name, "8M"));
+        assert_eq!(None, parse_stack_size_env(name, "-1"));
+        assert_eq!(None, parse_stack_size_env(name, "1024"));
+    }
+
+    #[test]
+    fn refresh_symbols_interval() {
+        assert_eq!(
+            Duration::from_secs(600),
+            parse_refresh_symbols_interval("600")
+        );
+        assert_eq!(
+            Duration::from_secs(3600),
+            parse_refresh_symbols_interval("1h")
+        );
+        assert_eq!(Duration::from_secs(60), parse_refresh_symbols_interval("0"));
+        assert_eq!(
+            Duration::from_secs(86400),
+            parse_refresh_symbols_interval("604800")
+        );
+    }
+
+    #[test]
+    fn symbol_change() {
+        let to_vec = |symbols: &[&str]| symbols.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+        let mut subscribed = to_vec(&["BTCUSDT", "ETHUSDT", "LUNAUSDT"]);
+
+        // unchanged
+        assert!(SymbolChange::diff(&subscribed, &subscribed.clone()).is_empty());
+
+        // listed and delisted at the same time
+        let latest = to_vec(&["ETHUSDT", "BTCUSDT", "APTUSDT"]);
+        let change = SymbolChange::diff(&subscribed, &latest);
+        assert_eq!(
+            SymbolChange {
+                added: to_vec(&["APTUSDT"]),
+                removed: to_vec(&["LUNAUSDT"]),
+            },
+            change
+        );
+        change.apply(&mut subscribed);
+        assert_eq!(to_vec(&["BTCUSDT", "ETHUSDT", "APTUSDT"]), subscribed);
+
+        // the next refresh diffs against the updated symbols
+        assert!(SymbolChange::diff(&subscribed, &latest).is_empty());
+        let change = SymbolChange::diff(&subscribed, &to_vec(&["BTCUSDT"]));
+        assert!(change.added.is_empty());
+        assert_eq!(to_vec(&["ETHUSDT", "APTUSDT"]), change.removed);
+        change.apply(&mut subscribed);
+        assert_eq!(to_vec(&["BTCUSDT"]), subscribed);
+    }
+
+    #[test]
+    fn thread_name() {
+        let handle = spawn_crawler_thread(
+            "binance",
+            MarketType::Spot,
+            MessageType::Trade,
+            "ws-0",
+            || {
+                assert_eq!(
+                    Some("crawler-binance-spot-trade-ws-0"),
+                    std::thread::current().name()
+                );
+            },
+        );
+        handle.join().unwrap();
+    }
+
+    #[test]
+    fn reset_before_resubscription_data() {
+        let symbols = Arc::new(Mutex::new(BTreeSet::from(["BTCUSDT".to_string()])));
+        let (tx, rx) = std::sync::mpsc::channel();
+        let (reset_tx, reset_rx) = std::sync::mpsc::channel();
+        let tx_raw = create_conversion_thread_ext(
+            "binance".to_string(),
+            MessageType::L2Event,
+            MarketType::Spot,
+            tx,
+            symbols,
+            Some(reset_rx),
+        );
+        // what the websocket client sends on reconnect
+        tx_raw
+            .send(r#"{"e":"depthUpdate","u":1}"#.to_string())
+            .unwrap();
+        let first = rx.recv().unwrap();
+        assert_eq!(MessageType::L2Event, first.msg_type);
+        reset_tx
+            .send(ResetNotification {
+                channels: vec!["btcusdt@depth".to_string()],
+            })
+            .unwrap();
+        tx_raw
+            .send(r#"{"e":"depthUpdate","u":100}"#.to_string())
+            .unwrap();
+        drop(tx_raw);
+
+        let msgs: Vec<crate::Message> = rx.iter().collect();
+        assert_eq!(2, msgs.len());
+        assert_eq!(MessageType::Reset, msgs[0].msg_type);
+        assert_eq!(r#"{"symbols":["BTCUSDT"]}"#, msgs[0].json);
+        assert_eq!(MessageType::L2Event, msgs[1].msg_type);
+        assert_eq!(r#"{"e":"depthUpdate","u":100}"#, msgs[1].json);
+    }
+
+    #[test]
+    fn bbo_from_l2_event() {
+        let (tx_event, rx_event) = std::sync::mpsc::channel::<Message>();
+        let (tx, rx) = std::sync::mpsc::channel::<Message>();
+        for json in [
+            // an update before the snapshot is dropped
+            r#"{"time":1622697759,"channel":"futures.order_book","event":"update","error":null,"result":[{"p":"38550","s":500,"c":"BTC_USDT_20210625","id":90062643}]}"#,
+            r#"{"time":1622697760,"channel":"futures.order_book","event":"all","error":null,"result":{"contract":"BTC_USDT_20210625","asks":[{"p":"38624.6","s":500},{"p":"38708.3","s":500},{"p":"38821","s":2000}],"bids":[{"p":"38538","s":500},{"p":"38460","s":500},{"p":"38373","s":2000}]}}"#,
+            // below the best bid, no BBO
+            r#"{"time":1622697761,"channel":"futures.order_book","event":"update","error":null,"result":[{"p":"38373","s":1000,"c":"BTC_USDT_20210625","id":90062644}]}"#,
+            r#"{"time":1622697762,"channel":"futures.order_book","event":"update","error":null,"result":[{"p":"38550","s":500,"c":"BTC_USDT_20210625","id":90062645}]}"#,
+        ] {
+            tx_event
+                .send(Message::new(
+                    "gate".to_string(),
+                    MarketType::LinearFuture,
+                    MessageType::L2Event,
+                    json.to_string(),
+                ))
+                .unwrap();
+        }
+        tx_event
+            .send(Message::new(
+                "gate".to_string(),
+                MarketType::LinearFuture,
+                MessageType::SymbolChange,
+                "{}".to_string(),
+            ))
+            .unwrap();
+        drop(tx_event);
+        derive_bbo_from_l2_event("gate", MarketType::LinearFuture, rx_event, tx);
+
+        let messages: Vec<Message> = rx.into_iter().collect();
+        assert_eq!(3, messages.len());
+        let bbos: Vec<BboMsg> = messages[..2]
+            .iter()
+            .map(|msg| {
+                assert_eq!(MessageType::BBO, msg.msg_type);
+                serde_json::from_str(&msg.json).unwrap()
+            })
+            .collect();
+        for bbo in bbos.iter() {
+            assert!(bbo.synthetic);
+            assert_eq!("BTC_USDT_20210625", bbo.symbol);
+            assert_eq!(38624.6, bbo.ask_price);
+        }
+        assert_eq!(38538.0, bbos[0].bid_price);
+        assert_eq!(38550.0, bbos[1].bid_price);
+        assert_eq!(MessageType::SymbolChange, messages[2].msg_type);
     }
 }
@@ -0,0 +1,101 @@
+use std::collections::HashSet;
+
+/// Selects symbols by their normalized pairs, e.g., `BTC/USDT`.
+///
+/// Pairs are normalized by crypto-pair, so `BTC/USDT` matches binance
+/// `BTCUSDT`, okex `BTC-USDT`, huobi `btcusdt` and so forth.
+///
+/// ## Example
+///
+/// ```
+/// use crypto_crawler::PairFilter;
+///
+/// // all pairs except binance leveraged tokens of BTC
+/// let filter = PairFilter::new().exclude(&["BTCUP/USDT", "BTCDOWN/USDT"]);
+/// assert!(filter.matches("binance", "BTCUSDT"));
+/// assert!(!filter.matches("binance", "BTCUPUSDT"));
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct PairFilter {
+    include: Option<HashSet<String>>,
+    exclude: HashSet<String>,
+}
+
+impl PairFilter {
+    /// Creates a filter matching all symbols.
+    pub fn new() -> Self {
+        PairFilter::default()
+    }
+
+    /// Matches only these pairs, symbols that can't be normalized are dropped.
+    pub fn include(mut self, pairs: &[&str]) -> Self {
+        self.include = Some(pairs.iter().map(|pair| pair.to_string()).collect());
+        self
+    }
+
+    /// Never matches these pairs, takes precedence over `include()`.
+    pub fn exclude(mut self, pairs: &[&str]) -> Self {
+        self.exclude = pairs.iter().map(|pair| pair.to_string()).collect();
+        self
+    }
+
+    /// Returns true if the pair of the exchange-specific `symbol` passes the filter.
+    pub fn matches(&self, exchange: &str, symbol: &str) -> bool {
+        match crypto_pair::normalize_pair_cached(symbol, exchange) {
+            Some(pair) => {
+                let included = match &self.include {
+                    Some(include) => include.contains(&pair),
+                    None => true,
+                };
+                included && !self.exclude.contains(&pair)
+            }
+            None => self.include.is_none(),
+        }
+    }
+
+    /// Returns symbols passing the filter, in the original order.
+    pub fn apply(&self, exchange: &str, symbols: &[String]) -> Vec<String> {
+        symbols
+            .iter()
+            .filter(|symbol| self.matches(exchange, symbol))
+            .cloned()
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::PairFilter;
+
+    fn symbols(list: &[&str]) -> Vec<String> {
+        list.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn exclude() {
+        let filter = PairFilter::new().exclude(&["BTC/USDT", "BTCUP/USDT"]);
+        assert_eq!(
+            symbols(&["ETHUSDT", "BTCDOWNUSDT"]),
+            filter.apply(
+                "binance",
+                &symbols(&["BTCUSDT", "ETHUSDT", "BTCUPUSDT", "BTCDOWNUSDT"])
+            )
+        );
+        // the same pair in another native format
+        assert_eq!(
+            symbols(&["ETH-USDT"]),
+            filter.apply("okex", &symbols(&["BTC-USDT", "ETH-USDT"]))
+        );
+    }
+
+    #[test]
+    fn include() {
+        let filter = PairFilter::new()
+            .include(&["BTC/USDT", "ETH/USDT"])
+            .exclude(&["ETH/USDT"]);
+        assert_eq!(
+            symbols(&["BTCUSDT"]),
+            filter.apply("binance", &symbols(&["BTCUSDT", "ETHUSDT", "BNBUSDT"]))
+        );
+    }
+}
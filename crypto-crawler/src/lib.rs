@@ -159,21 +159,39 @@
 //! // Crawl funding rates for all symbols of binance COIN-margined perpetual markets, only run for 5 seconds
 //! crawl_funding_rate("binance", MarketType::InverseSwap, None, tx, Some(5));
 //! ```
+mod backpressure;
 mod crawlers;
+mod filter;
+#[cfg(feature = "metrics")]
+pub mod metrics;
 mod msg;
+mod replay;
 mod utils;
+mod writer;
 
-use std::sync::mpsc::Sender;
+use std::collections::HashSet;
+use std::sync::mpsc::{Receiver, Sender};
 
+pub use backpressure::{bounded_channel, BackpressurePolicy, BoundedReceiver};
 pub use crawlers::fetch_symbols_retry;
-pub use crypto_market_type::MarketType;
+pub use crypto_market_type::{MarketType, MessageType};
+pub use crypto_msg_parser::{KlineMsg, OrderBookManager, OrderBookMsg, ResyncRequest};
+pub use filter::PairFilter;
 pub use msg::*;
+pub use replay::replay_from_file;
 pub use utils::get_hot_spot_symbols;
+pub use writer::{MessageWriter, OutputFormat};
 
 /// Crawl realtime trades.
 ///
 /// If `symbols` is None or empty, this API will crawl realtime trades for all symbols in the `market_type`
-/// market, and launch a thread to discover new symbols every hour. And so forth for all other APIs.
+/// market, and launch a thread to refresh symbols every hour. And so forth for all other APIs.
+///
+/// The refresh interval can be changed by the `REFRESH_SYMBOLS_INTERVAL` environment variable in seconds.
+/// Each refresh subscribes to newly listed symbols, unsubscribes from delisted symbols, and sends a
+/// `MessageType::SymbolChange` message to `tx` if the symbol universe changes.
+/// Contract values of newly listed symbols are refreshed as well if the exchange supports it,
+/// e.g., okex contract values are fetched again from `/api/{swap,futures}/v3/instruments`.
 pub fn crawl_trade(
     exchange: &str,
     market_type: MarketType,
@@ -181,6 +199,7 @@ pub fn crawl_trade(
     tx: Sender<Message>,
     duration: Option<u64>,
 ) {
+    check_message_type(exchange, market_type, MessageType::Trade);
     match exchange {
         "binance" => crawlers::binance::crawl_trade(market_type, symbols, tx, duration),
         "bitmex" => crawlers::bitmex::crawl_trade(market_type, symbols, tx, duration),
@@ -201,7 +220,155 @@ pub fn crawl_trade(
     }
 }
 
+/// Crawl realtime trades of all symbols in the `market_type` market whose pairs pass `filter`.
+///
+/// Symbols are fetched once, unlike `crawl_trade()` with `symbols` None,
+/// newly listed symbols are not subscribed automatically.
+pub fn crawl_trade_all(
+    exchange: &str,
+    market_type: MarketType,
+    filter: &PairFilter,
+    tx: Sender<Message>,
+    duration: Option<u64>,
+) {
+    let symbols = filter.apply(exchange, &fetch_symbols_retry(exchange, market_type));
+    if !symbols.is_empty() {
+        crawl_trade(exchange, market_type, Some(&symbols), tx, duration);
+    }
+}
+
+/// Crawl realtime trades of the same pairs on multiple exchanges into one channel.
+///
+/// Each config is `(exchange, market_type, pairs)`, pairs are normalized, e.g.,
+/// `BTC/USDT`, and are converted to symbols of the exchange by
+/// `crypto_pair::denormalize_pair()`. Pairs that can't be converted, e.g.,
+/// futures with delivery dates, are skipped with a warning, and so is a
+/// config left without symbols. Each exchange is crawled in its own thread,
+/// messages tell their source by the `exchange` field.
+///
+/// ## Example
+///
+/// ```no_run
+/// use crypto_crawler::{crawl_trade_multi, MarketType, Message};
+///
+/// let pairs = vec!["BTC/USDT".to_string()];
+/// let (tx, rx) = std::sync::mpsc::channel::<Message>();
+/// std::thread::spawn(move || {
+///     for msg in rx {
+///         println!("{} {}", msg.exchange, msg.json);
+///     }
+/// });
+/// crawl_trade_multi(
+///     vec![
+///         ("binance".to_string(), MarketType::Spot, pairs.clone()),
+///         ("okex".to_string(), MarketType::Spot, pairs.clone()),
+///         ("coinbase_pro".to_string(), MarketType::Spot, vec!["BTC/USD".to_string()]),
+///     ],
+///     tx,
+///     None,
+/// );
+/// ```
+pub fn crawl_trade_multi(
+    configs: Vec<(String, MarketType, Vec<String>)>,
+    tx: Sender<Message>,
+    duration: Option<u64>,
+) {
+    let handles = configs
+        .into_iter()
+        .filter_map(|(exchange, market_type, pairs)| {
+            let symbols = pairs
+                .iter()
+                .filter_map(|pair| {
+                    let symbol = crypto_pair::denormalize_pair(pair, &exchange, market_type);
+                    if symbol.is_none() {
+                        log::warn!("{} has no symbol for {} in {}", exchange, pair, market_type);
+                    }
+                    symbol
+                })
+                .collect::<Vec<String>>();
+            if symbols.is_empty() {
+                return None;
+            }
+            let tx = tx.clone();
+            let name = exchange.clone();
+            Some(crawlers::spawn_crawler_thread(
+                &name,
+                market_type,
+                MessageType::Trade,
+                "multi",
+                move || crawl_trade(&exchange, market_type, Some(&symbols), tx, duration),
+            ))
+        })
+        .collect::<Vec<std::thread::JoinHandle<()>>>();
+    drop(tx);
+    for handle in handles {
+        handle.join().unwrap();
+    }
+}
+
+/// Crawl realtime trades of a base asset in all market types of the exchange,
+/// e.g., spot, futures, swap and option trades of `BTC` on okex.
+///
+/// Symbols are resolved by `crypto_markets::fetch_symbols_by_base()`, only
+/// markets currently trading are subscribed. Each market type is crawled in
+/// its own thread, market types without a trade channel are skipped.
+///
+/// Returns the subscribed symbols as soon as the threads are started, so
+/// that the caller knows the coverage, even if `duration` is `None`. Each
+/// thread holds a clone of `tx`, the receiver stops iterating once all of
+/// them have finished. An empty list is returned if symbols can't be resolved.
+pub fn crawl_trade_by_base(
+    exchange: &str,
+    base: &str,
+    tx: Sender<Message>,
+    duration: Option<u64>,
+) -> Vec<(MarketType, String)> {
+    let symbols = match crypto_markets::fetch_symbols_by_base(exchange, base) {
+        Ok(symbols) => symbols,
+        Err(err) => {
+            log::error!("Failed to fetch {} symbols of {}, {}", exchange, base, err);
+            return Vec::new();
+        }
+    };
+    let mut subscribed = Vec::new();
+    for market_type in crypto_markets::get_market_types(exchange) {
+        if !supported_message_types(exchange, market_type).contains(&MessageType::Trade) {
+            continue;
+        }
+        let symbols_of_type: Vec<String> = symbols
+            .iter()
+            .filter(|(x, _)| *x == market_type)
+            .map(|(_, symbol)| symbol.clone())
+            .collect();
+        if symbols_of_type.is_empty() {
+            continue;
+        }
+        subscribed.extend(
+            symbols_of_type
+                .iter()
+                .map(|symbol| (market_type, symbol.clone())),
+        );
+
+        let tx = tx.clone();
+        let exchange = exchange.to_string();
+        crawlers::spawn_crawler_thread(
+            &exchange.clone(),
+            market_type,
+            MessageType::Trade,
+            "base",
+            move || crawl_trade(&exchange, market_type, Some(&symbols_of_type), tx, duration),
+        );
+    }
+    subscribed
+}
+
 /// Crawl level2 orderbook update events.
+///
+/// If the `RESET_ON_RECONNECT` environment variable is `true`, a dropped
+/// connection is re-established in place instead of exiting the process, and
+/// a `MessageType::Reset` message listing the affected symbols is sent before
+/// updates of the new connection. Discard orderbooks of these symbols and wait
+/// for a new snapshot when receiving it.
 pub fn crawl_l2_event(
     exchange: &str,
     market_type: MarketType,
@@ -209,6 +376,7 @@ pub fn crawl_l2_event(
     tx: Sender<Message>,
     duration: Option<u64>,
 ) {
+    check_message_type(exchange, market_type, MessageType::L2Event);
     match exchange {
         "binance" => crawlers::binance::crawl_l2_event(market_type, symbols, tx, duration),
         "bitmex" => crawlers::bitmex::crawl_l2_event(market_type, symbols, tx, duration),
@@ -231,6 +399,44 @@ pub fn crawl_l2_event(
     }
 }
 
+/// Crawl level2 orderbook update events batched by the exchange.
+///
+/// Messages are parsed the same way as `crawl_l2_event()`, but arrive less
+/// frequently with more changes in each, which lowers bandwidth. Only
+/// coinbase_pro is supported, via its `level2_batch` channel.
+pub fn crawl_l2_event_batched(
+    exchange: &str,
+    market_type: MarketType,
+    symbols: Option<&[String]>,
+    tx: Sender<Message>,
+    duration: Option<u64>,
+) {
+    match exchange {
+        "coinbase_pro" => {
+            crawlers::coinbase_pro::crawl_l2_event_batched(market_type, symbols, tx, duration)
+        }
+        _ => panic!(
+            "{} does NOT have the batched level2 websocket channel",
+            exchange
+        ),
+    }
+}
+
+/// Crawl level2 orderbook update events of all symbols in the `market_type`
+/// market whose pairs pass `filter`, see `crawl_trade_all()`.
+pub fn crawl_l2_event_all(
+    exchange: &str,
+    market_type: MarketType,
+    filter: &PairFilter,
+    tx: Sender<Message>,
+    duration: Option<u64>,
+) {
+    let symbols = filter.apply(exchange, &fetch_symbols_retry(exchange, market_type));
+    if !symbols.is_empty() {
+        crawl_l2_event(exchange, market_type, Some(&symbols), tx, duration);
+    }
+}
+
 /// Crawl level3 orderbook update events.
 pub fn crawl_l3_event(
     exchange: &str,
@@ -239,6 +445,7 @@ pub fn crawl_l3_event(
     tx: Sender<Message>,
     duration: Option<u64>,
 ) {
+    check_message_type(exchange, market_type, MessageType::L3Event);
     match exchange {
         "bitfinex" | "bitstamp" | "coinbase_pro" | "kucoin" => crawlers::crawl_event(
             exchange,
@@ -263,17 +470,141 @@ pub fn crawl_l2_snapshot(
     tx: Sender<Message>,
     duration: Option<u64>,
 ) {
+    crawl_l2_snapshot_with_depth(exchange, market_type, symbols, None, tx, duration)
+}
+
+/// Crawl level2 orderbook snapshots with limited depth through RESTful APIs.
+///
+/// `depth` None means the maximum depth of the exchange, otherwise it is
+/// clamped to the exchange's maximum. Shallow snapshots are much smaller,
+/// which saves bandwidth and parsing time.
+///
+/// # Panics
+///
+/// Panics if `depth` is `Some(0)`.
+pub fn crawl_l2_snapshot_with_depth(
+    exchange: &str,
+    market_type: MarketType,
+    symbols: Option<&[String]>,
+    depth: Option<usize>,
+    tx: Sender<Message>,
+    duration: Option<u64>,
+) {
+    check_message_type(exchange, market_type, MessageType::L2Snapshot);
+    assert_ne!(Some(0), depth, "depth must be positive");
     crawlers::crawl_snapshot(
         exchange,
         market_type,
         MessageType::L2Snapshot,
         symbols,
+        depth,
         tx,
         duration,
     )
 }
 
+/// Fetch a level2 orderbook snapshot through RESTful APIs and parse it.
+///
+/// Use it to seed an orderbook before applying messages from
+/// `crawl_l2_event()`: buffer the events, fetch the snapshot, then drop events
+/// whose `seq_id` is not greater than the snapshot's `seq_id`.
+///
+/// Only binance and okex are supported. okex snapshots have no `seq_id`,
+/// align them with events by `timestamp` instead.
+pub fn fetch_l2_snapshot(
+    exchange: &str,
+    market_type: MarketType,
+    symbol: &str,
+) -> Result<OrderBookMsg, Box<dyn std::error::Error + Send + Sync>> {
+    let json = crypto_rest_client::fetch_l2_snapshot(exchange, market_type, symbol, Some(3))?;
+    let mut orderbooks =
+        crypto_msg_parser::parse_l2_snapshot(exchange, market_type, symbol, &json)?;
+    orderbooks
+        .pop()
+        .ok_or_else(|| format!("Empty {} {} snapshot of {}", exchange, market_type, symbol).into())
+}
+
+/// Fetch historical candlesticks through RESTful APIs and parse them.
+///
+/// Returns candlesticks whose open time is between `start` and `end`
+/// inclusive, both in milliseconds, oldest first. `interval` is in seconds,
+/// the same as in `crawl_candlestick()`. RESTful APIs return a limited number
+/// of candlesticks per request, so the range is fetched page by page.
+///
+/// Only binance spot and linear markets are supported, other exchanges and
+/// markets, as well as negative `start` or `end`, return an error.
+pub fn fetch_candlestick_history(
+    exchange: &str,
+    market_type: MarketType,
+    symbol: &str,
+    interval: usize,
+    start: i64,
+    end: i64,
+) -> Result<Vec<KlineMsg>, Box<dyn std::error::Error + Send + Sync>> {
+    if start < 0 || end < 0 {
+        return Err(format!("Invalid time range [{}, {}]", start, end).into());
+    }
+    let mut klines: Vec<KlineMsg> = Vec::new();
+    let mut start_time = start;
+    while start_time <= end {
+        let json = crypto_rest_client::fetch_candlestick(
+            exchange,
+            market_type,
+            symbol,
+            interval,
+            Some(start_time as u64),
+            Some(end as u64),
+        )?;
+        let page = crypto_msg_parser::parse_candlestick_history(
+            exchange,
+            market_type,
+            symbol,
+            interval,
+            &json,
+        )?;
+        match page.last() {
+            Some(last) => start_time = last.timestamp + 1,
+            None => break,
+        }
+        klines.extend(page);
+    }
+    Ok(klines)
+}
+
+/// Fetches a level2 snapshot for every `ResyncRequest` until `rx` is closed.
+///
+/// Hook it to `OrderBookManager::on_resync()` in a separate thread, and feed
+/// snapshots received from `tx` back to the manager, so that books resume
+/// after sequence gaps. Failed fetches are logged and skipped, and the book
+/// of the symbol stays out of sync until a snapshot is fed to the manager.
+pub fn resync_l2_snapshots(rx: Receiver<ResyncRequest>, tx: Sender<OrderBookMsg>) {
+    for request in rx {
+        match fetch_l2_snapshot(&request.exchange, request.market_type, &request.symbol) {
+            Ok(snapshot) => {
+                if tx.send(snapshot).is_err() {
+                    break;
+                }
+            }
+            Err(err) => log::error!(
+                "Failed to resync {} {} {}, {}",
+                request.exchange,
+                request.market_type,
+                request.symbol,
+                err
+            ),
+        }
+    }
+}
+
 /// Crawl best bid and ask.
+///
+/// Bitget, Bitstamp, Bybit and MXC have no BBO channel, so their BBO messages
+/// are derived from level2 top-k snapshots. Gate futures have neither, so
+/// their orderbooks are maintained from level2 incremental messages by
+/// `OrderBookManager`, and a BBO message is sent whenever the best bid or ask
+/// changes. The `json` of such a message is a serialized
+/// `crypto_msg_parser::BboMsg` with `synthetic` set to true, instead of the
+/// original message.
 pub fn crawl_bbo(
     exchange: &str,
     market_type: MarketType,
@@ -281,10 +612,17 @@ pub fn crawl_bbo(
     tx: Sender<Message>,
     duration: Option<u64>,
 ) {
+    check_message_type(exchange, market_type, MessageType::BBO);
     match exchange {
         "binance" => crawlers::binance::crawl_bbo(market_type, symbols, tx, duration),
         "bitmex" => crawlers::bitmex::crawl_bbo(market_type, symbols, tx, duration),
         "kucoin" => crawlers::kucoin::crawl_bbo(market_type, symbols, tx, duration),
+        "gate"
+            if market_type == MarketType::InverseFuture
+                || market_type == MarketType::LinearFuture =>
+        {
+            crawlers::crawl_bbo_from_l2_event(exchange, market_type, symbols, tx, duration)
+        }
         "bitfinex" | "deribit" | "ftx" | "gate" | "huobi" | "kraken" | "okex" => {
             crawlers::crawl_event(
                 exchange,
@@ -295,6 +633,9 @@ pub fn crawl_bbo(
                 duration,
             )
         }
+        "bitget" | "bitstamp" | "bybit" | "mxc" => {
+            crawlers::crawl_bbo_from_l2_topk(exchange, market_type, symbols, tx, duration)
+        }
         _ => panic!("{} does NOT have BBO websocket channel", exchange),
     }
 }
@@ -307,6 +648,7 @@ pub fn crawl_l2_topk(
     tx: Sender<Message>,
     duration: Option<u64>,
 ) {
+    check_message_type(exchange, market_type, MessageType::L2TopK);
     match exchange {
         "binance" => crawlers::binance::crawl_l2_topk(market_type, symbols, tx, duration),
         "bitmex" => crawlers::bitmex::crawl_l2_topk(market_type, symbols, tx, duration),
@@ -335,11 +677,13 @@ pub fn crawl_l3_snapshot(
     tx: Sender<Message>,
     duration: Option<u64>,
 ) {
+    check_message_type(exchange, market_type, MessageType::L3Snapshot);
     crawlers::crawl_snapshot(
         exchange,
         market_type,
         MessageType::L3Snapshot,
         symbols,
+        None,
         tx,
         duration,
     )
@@ -356,6 +700,7 @@ pub fn crawl_ticker(
     tx: Sender<Message>,
     duration: Option<u64>,
 ) {
+    check_message_type(exchange, market_type, MessageType::Ticker);
     match exchange {
         "binance" => crawlers::binance::crawl_ticker(market_type, symbols, tx, duration),
         "bitfinex" | "bitget" | "bithumb" | "bitz" | "bybit" | "coinbase_pro" | "deribit"
@@ -379,6 +724,7 @@ pub fn crawl_funding_rate(
     tx: Sender<Message>,
     duration: Option<u64>,
 ) {
+    check_message_type(exchange, market_type, MessageType::FundingRate);
     let func = match exchange {
         "binance" => crawlers::binance::crawl_funding_rate,
         "bitget" => crawlers::bitget::crawl_funding_rate,
@@ -390,6 +736,37 @@ pub fn crawl_funding_rate(
     func(market_type, symbols, tx, duration);
 }
 
+/// Crawl index prices.
+///
+/// Indices are named differently from trading symbols:
+///
+/// * OKEx `index/ticker`, e.g., `BTC-USD`, the index of `BTC-USD-SWAP`
+/// * Binance `@indexPrice` of COIN-M pairs such as `BTCUSD`, and `@markPrice`
+///   of USDⓈ-M symbols, which carries the index price as well
+/// * BitMEX `instrument` of indices such as `.BXBT`
+///
+/// If `symbols` is None, all indices of the `market_type` are crawled.
+/// Messages can be parsed by `crypto_msg_parser::parse_index_price()`.
+pub fn crawl_index_price(
+    exchange: &str,
+    market_type: MarketType,
+    symbols: Option<&[String]>,
+    tx: Sender<Message>,
+    duration: Option<u64>,
+) {
+    check_message_type(exchange, market_type, MessageType::IndexPrice);
+    let func = match exchange {
+        "binance" => crawlers::binance::crawl_index_price,
+        "bitmex" => crawlers::bitmex::crawl_index_price,
+        "okex" => crawlers::okex::crawl_index_price,
+        _ => panic!(
+            "{} does NOT have the index price websocket channel",
+            exchange
+        ),
+    };
+    func(market_type, symbols, tx, duration);
+}
+
 /// Crawl candlestick(i.e., OHLCV) data.
 ///
 /// If `symbol_interval_list` is None or empty, this API will crawl candlesticks from
@@ -401,6 +778,7 @@ pub fn crawl_candlestick(
     tx: Sender<Message>,
     duration: Option<u64>,
 ) {
+    check_message_type(exchange, market_type, MessageType::Candlestick);
     match exchange {
         "binance" => {
             crawlers::binance::crawl_candlestick(market_type, symbol_interval_list, tx, duration)
@@ -423,6 +801,28 @@ pub fn crawl_candlestick(
     };
 }
 
+/// Crawl liquidation orders of all symbols.
+///
+/// Binance publishes them via the `!forceOrder@arr` stream, BitMEX via the
+/// `liquidation` table, in which case `market_type` should be `Unknown`.
+/// Messages can be parsed by `crypto_msg_parser::parse_liquidation()`.
+pub fn crawl_liquidation(
+    exchange: &str,
+    market_type: MarketType,
+    tx: Sender<Message>,
+    duration: Option<u64>,
+) {
+    check_message_type(exchange, market_type, MessageType::Liquidation);
+    match exchange {
+        "binance" => crawlers::binance::crawl_liquidation(market_type, tx, duration),
+        "bitmex" => crawlers::bitmex::crawl_liquidation(market_type, tx, duration),
+        _ => panic!(
+            "{} does NOT have the liquidation websocket channel",
+            exchange
+        ),
+    }
+}
+
 /// Crawl all open interest.
 pub fn crawl_open_interest(
     exchange: &str,
@@ -430,5 +830,306 @@ pub fn crawl_open_interest(
     tx: Sender<Message>,
     duration: Option<u64>,
 ) {
-    crawlers::crawl_open_interest(exchange, market_type, tx, duration)
+    check_message_type(exchange, market_type, MessageType::OpenInterest);
+    crawlers::crawl_open_interest(exchange, market_type, None, None, tx, duration)
+}
+
+/// Crawl open interest of `symbols`, polling once every `interval` seconds.
+///
+/// Open interest is only available via RESTful APIs, so each round fetches
+/// `symbols`, or all symbols of the market if `symbols` is None or empty,
+/// and then sleeps for the rest of `interval`. Exchanges returning all
+/// symbols in one response, e.g., bitmex, ignore `symbols`.
+/// Messages can be parsed by `crypto_msg_parser::parse_open_interest()`.
+pub fn crawl_open_interest_with_interval(
+    exchange: &str,
+    market_type: MarketType,
+    symbols: Option<&[String]>,
+    interval: u64,
+    tx: Sender<Message>,
+    duration: Option<u64>,
+) {
+    check_message_type(exchange, market_type, MessageType::OpenInterest);
+    crawlers::crawl_open_interest(exchange, market_type, symbols, Some(interval), tx, duration)
+}
+
+// exchanges supported by this crate
+const ALL_EXCHANGES: &[&str] = &[
+    "binance",
+    "bitfinex",
+    "bitget",
+    "bithumb",
+    "bitmex",
+    "bitstamp",
+    "bitz",
+    "bybit",
+    "coinbase_pro",
+    "deribit",
+    "dydx",
+    "ftx",
+    "gate",
+    "huobi",
+    "kraken",
+    "kucoin",
+    "mxc",
+    "okex",
+    "zbg",
+];
+
+// Panics if `msg_type` is not listed by `supported_message_types()`
+fn check_message_type(exchange: &str, market_type: MarketType, msg_type: MessageType) {
+    if !supported_message_types(exchange, market_type).contains(&msg_type) {
+        panic!(
+            "{} {} does NOT have the {} channel",
+            exchange, market_type, msg_type
+        );
+    }
+}
+
+/// Message types that can be crawled from the `market_type` market of `exchange`.
+///
+/// Crawl functions check it before crawling and panic with a message type not
+/// listed here. `MarketType::Unknown` lists market-wide crawls, i.e., all
+/// BitMEX symbols at once, and open interest of ftx and kucoin. An unknown
+/// exchange or a market the exchange doesn't have returns an empty set. Some
+/// symbols may still lack a channel, e.g., orderbooks of a newly listed symbol.
+pub fn supported_message_types(exchange: &str, market_type: MarketType) -> HashSet<MessageType> {
+    let mut msg_types = HashSet::new();
+    if market_type == MarketType::Unknown {
+        match exchange {
+            // crawls all BitMEX symbols at once
+            "bitmex" => msg_types.extend([
+                MessageType::Trade,
+                MessageType::L2Event,
+                MessageType::L2Snapshot,
+                MessageType::L2TopK,
+                MessageType::BBO,
+                MessageType::FundingRate,
+                MessageType::IndexPrice,
+                MessageType::Candlestick,
+                MessageType::Liquidation,
+                MessageType::OpenInterest,
+            ]),
+            // open interest of all symbols in one request
+            "ftx" | "kucoin" => {
+                msg_types.insert(MessageType::OpenInterest);
+            }
+            _ => (),
+        }
+        return msg_types;
+    }
+    if !ALL_EXCHANGES.contains(&exchange)
+        || !crypto_market_type::get_market_types(exchange).contains(&market_type)
+    {
+        return msg_types;
+    }
+
+    let is_swap = market_type == MarketType::InverseSwap || market_type == MarketType::LinearSwap;
+    let is_future_or_swap = is_swap
+        || market_type == MarketType::InverseFuture
+        || market_type == MarketType::LinearFuture;
+    let mut add = |msg_type: MessageType, exchanges: &[&str]| {
+        if exchanges.contains(&exchange) {
+            msg_types.insert(msg_type);
+        }
+    };
+
+    // keep in sync with the crawl functions above
+    add(MessageType::Trade, ALL_EXCHANGES);
+    add(MessageType::L2Event, ALL_EXCHANGES);
+    add(MessageType::L2Snapshot, ALL_EXCHANGES);
+    add(
+        MessageType::L2TopK,
+        &[
+            "binance", "bitget", "bitmex", "bitstamp", "bybit", "deribit", "huobi", "kucoin",
+            "mxc", "okex",
+        ],
+    );
+    add(
+        MessageType::L3Event,
+        &["bitfinex", "bitstamp", "coinbase_pro", "kucoin"],
+    );
+    add(
+        MessageType::L3Snapshot,
+        &["bitfinex", "bitstamp", "coinbase_pro", "kucoin"],
+    );
+    // bitget, bitstamp, bybit and mxc BBO are derived from L2TopK, gate
+    // futures BBO from L2Event
+    add(
+        MessageType::BBO,
+        &[
+            "binance", "bitfinex", "bitget", "bitmex", "bitstamp", "bybit", "deribit", "ftx",
+            "gate", "huobi", "kraken", "kucoin", "mxc", "okex",
+        ],
+    );
+    add(
+        MessageType::Ticker,
+        &[
+            "binance",
+            "bitfinex",
+            "bitget",
+            "bithumb",
+            "bitz",
+            "bybit",
+            "coinbase_pro",
+            "deribit",
+            "gate",
+            "huobi",
+            "kraken",
+            "kucoin",
+            "okex",
+            "zbg",
+        ],
+    );
+    // mxc spot has no ticker channel
+    if market_type != MarketType::Spot {
+        add(MessageType::Ticker, &["mxc"]);
+    }
+    add(
+        MessageType::Candlestick,
+        &[
+            "binance", "bitfinex", "bitget", "bitmex", "bitz", "bybit", "deribit", "gate", "huobi",
+            "kraken", "kucoin", "mxc", "okex", "zbg",
+        ],
+    );
+    if market_type != MarketType::Spot {
+        add(
+            MessageType::OpenInterest,
+            &[
+                "binance", "bitget", "bitmex", "bitz", "bybit", "deribit", "dydx", "ftx", "gate",
+                "huobi", "kucoin", "okex", "zbg",
+            ],
+        );
+    }
+    if is_swap {
+        add(
+            MessageType::FundingRate,
+            &["binance", "bitget", "bitmex", "huobi", "okex"],
+        );
+    }
+    if is_future_or_swap {
+        add(MessageType::IndexPrice, &["binance", "okex"]);
+        add(MessageType::Liquidation, &["binance"]);
+    }
+    if exchange == "bitmex" {
+        // all BitMEX markets are derivatives
+        msg_types.insert(MessageType::IndexPrice);
+        msg_types.insert(MessageType::Liquidation);
+    }
+
+    msg_types
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{supported_message_types, MarketType, MessageType, ALL_EXCHANGES};
+
+    #[test]
+    fn message_types() {
+        let bitstamp = supported_message_types("bitstamp", MarketType::Spot);
+        assert!(bitstamp.contains(&MessageType::Trade));
+        assert!(bitstamp.contains(&MessageType::L3Event));
+        assert!(!bitstamp.contains(&MessageType::Ticker));
+
+        let gate = supported_message_types("gate", MarketType::Spot);
+        assert!(!gate.contains(&MessageType::L2TopK));
+        assert!(!gate.contains(&MessageType::FundingRate));
+
+        let binance_swap = supported_message_types("binance", MarketType::LinearSwap);
+        assert!(binance_swap.contains(&MessageType::FundingRate));
+        assert!(binance_swap.contains(&MessageType::Liquidation));
+        assert!(!supported_message_types("binance", MarketType::Spot)
+            .contains(&MessageType::FundingRate));
+
+        let bitmex = supported_message_types("bitmex", MarketType::Unknown);
+        assert!(bitmex.contains(&MessageType::Trade));
+        assert!(bitmex.contains(&MessageType::Liquidation));
+        assert!(!bitmex.contains(&MessageType::Ticker));
+        assert_eq!(
+            vec![MessageType::OpenInterest],
+            supported_message_types("kucoin", MarketType::Unknown)
+                .into_iter()
+                .collect::<Vec<MessageType>>()
+        );
+        assert!(supported_message_types("bitstamp", MarketType::LinearSwap).is_empty());
+        assert!(supported_message_types("unknown", MarketType::Spot).is_empty());
+    }
+
+    #[test]
+    fn every_exchange_and_market() {
+        // channels the websocket clients of these markets return UnsupportedChannel for
+        let unsupported = [
+            ("gate", MarketType::InverseFuture, MessageType::L2TopK),
+            ("gate", MarketType::LinearFuture, MessageType::L2TopK),
+            ("mxc", MarketType::Spot, MessageType::Ticker),
+            ("bitstamp", MarketType::Spot, MessageType::Ticker),
+            ("coinbase_pro", MarketType::Spot, MessageType::Candlestick),
+            ("ftx", MarketType::LinearSwap, MessageType::Ticker),
+        ];
+        for exchange in ALL_EXCHANGES {
+            let market_types = crypto_market_type::get_market_types(exchange);
+            assert!(!market_types.is_empty(), "{}", exchange);
+            for market_type in market_types {
+                let msg_types = supported_message_types(exchange, market_type);
+                for msg_type in [
+                    MessageType::Trade,
+                    MessageType::L2Event,
+                    MessageType::L2Snapshot,
+                ] {
+                    assert!(
+                        msg_types.contains(&msg_type),
+                        "{} {} {}",
+                        exchange,
+                        market_type,
+                        msg_type
+                    );
+                }
+                let is_swap =
+                    market_type == MarketType::InverseSwap || market_type == MarketType::LinearSwap;
+                if !is_swap {
+                    assert!(!msg_types.contains(&MessageType::FundingRate));
+                }
+                if market_type == MarketType::Spot {
+                    assert!(!msg_types.contains(&MessageType::OpenInterest));
+                    assert!(!msg_types.contains(&MessageType::IndexPrice));
+                    assert!(!msg_types.contains(&MessageType::Liquidation));
+                }
+                for (_, _, msg_type) in unsupported
+                    .iter()
+                    .filter(|(x, y, _)| x == exchange && *y == market_type)
+                {
+                    assert!(
+                        !msg_types.contains(msg_type),
+                        "{} {} {}",
+                        exchange,
+                        market_type,
+                        msg_type
+                    );
+                }
+            }
+        }
+        // futures BBO is derived from L2Event
+        for market_type in [
+            MarketType::Spot,
+            MarketType::InverseSwap,
+            MarketType::LinearSwap,
+            MarketType::InverseFuture,
+            MarketType::LinearFuture,
+        ] {
+            assert!(supported_message_types("gate", market_type).contains(&MessageType::BBO));
+        }
+        // derived from L2TopK
+        for (exchange, market_type) in [
+            ("bitget", MarketType::LinearSwap),
+            ("bybit", MarketType::InverseFuture),
+            ("mxc", MarketType::Spot),
+        ] {
+            let msg_types = supported_message_types(exchange, market_type);
+            assert!(msg_types.contains(&MessageType::BBO));
+            assert!(msg_types.contains(&MessageType::L2TopK));
+        }
+        assert!(
+            supported_message_types("mxc", MarketType::LinearSwap).contains(&MessageType::Ticker)
+        );
+    }
 }
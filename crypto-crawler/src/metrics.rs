@@ -0,0 +1,188 @@
+//! Prometheus metrics of crawlers, available with the `metrics` feature.
+//!
+//! All crawl functions record into one registry, labeled by exchange,
+//! market type and message type, so that the throughput of all connections
+//! can be scraped from a single `/metrics` endpoint.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use crypto_crawler::{crawl_trade, metrics, MarketType};
+//!
+//! let (tx, rx) = std::sync::mpsc::channel();
+//! std::thread::spawn(move || for _msg in rx {});
+//!
+//! crawl_trade("binance", MarketType::Spot, Some(&["BTCUSDT".to_string()]), tx, Some(5));
+//! // Serve this text at /metrics
+//! println!("{}", metrics::gather());
+//! ```
+
+use crate::{Message, MessageType};
+use crypto_market_type::MarketType;
+use crypto_ws_client::WSClient;
+use lazy_static::lazy_static;
+use prometheus::{Encoder, IntCounterVec, Opts, Registry, TextEncoder};
+use std::sync::{Arc, Mutex, Weak};
+
+// A websocket client created by crawlers, its reconnects are read from WSClient::stats()
+struct TrackedClient {
+    exchange: String,
+    market_type: MarketType,
+    client: Weak<dyn WSClient + Send + Sync>,
+    // num_reconnects already added to RECONNECTS
+    num_reconnects: usize,
+}
+
+fn register_counter_vec(name: &str, help: &str, labels: &[&str]) -> IntCounterVec {
+    let counter = IntCounterVec::new(Opts::new(name, help), labels).unwrap();
+    REGISTRY.register(Box::new(counter.clone())).unwrap();
+    counter
+}
+
+lazy_static! {
+    static ref REGISTRY: Registry = Registry::new();
+    static ref MESSAGES: IntCounterVec = register_counter_vec(
+        "crypto_crawler_messages_total",
+        "Number of messages received",
+        &["exchange", "market_type", "msg_type"],
+    );
+    static ref BYTES: IntCounterVec = register_counter_vec(
+        "crypto_crawler_bytes_total",
+        "Number of bytes of received messages",
+        &["exchange", "market_type", "msg_type"],
+    );
+    static ref PARSE_ERRORS: IntCounterVec = register_counter_vec(
+        "crypto_crawler_parse_errors_total",
+        "Number of messages failed to parse",
+        &["exchange", "market_type", "msg_type"],
+    );
+    static ref DROPPED: IntCounterVec = register_counter_vec(
+        "crypto_crawler_dropped_messages_total",
+        "Number of messages dropped by bounded channels",
+        &["exchange", "market_type", "msg_type"],
+    );
+    static ref RECONNECTS: IntCounterVec = register_counter_vec(
+        "crypto_crawler_reconnects_total",
+        "Number of websocket reconnects",
+        &["exchange", "market_type"],
+    );
+    static ref WS_CLIENTS: Mutex<Vec<TrackedClient>> = Mutex::new(Vec::new());
+}
+
+/// The registry of all crawler metrics.
+///
+/// Register it into an existing exporter, or use `gather()` directly.
+pub fn registry() -> &'static Registry {
+    // make sure all metrics are registered even if nothing was recorded yet
+    lazy_static::initialize(&MESSAGES);
+    lazy_static::initialize(&BYTES);
+    lazy_static::initialize(&PARSE_ERRORS);
+    lazy_static::initialize(&DROPPED);
+    lazy_static::initialize(&RECONNECTS);
+    &REGISTRY
+}
+
+/// Renders all crawler metrics in the Prometheus text format.
+pub fn gather() -> String {
+    update_reconnects();
+    let mut buffer = Vec::new();
+    TextEncoder::new()
+        .encode(&registry().gather(), &mut buffer)
+        .unwrap();
+    String::from_utf8(buffer).unwrap()
+}
+
+/// Counts a message that failed to parse.
+///
+/// Crawlers only parse messages they derive new messages from, call this
+/// when parsing crawled messages downstream to get all errors in one place.
+pub fn record_parse_error(exchange: &str, market_type: MarketType, msg_type: MessageType) {
+    PARSE_ERRORS
+        .with_label_values(&[exchange, &market_type.to_string(), &msg_type.to_string()])
+        .inc();
+}
+
+pub(crate) fn record_message(msg: &Message) {
+    let labels = [
+        msg.exchange.as_str(),
+        &msg.market_type.to_string(),
+        &msg.msg_type.to_string(),
+    ];
+    MESSAGES.with_label_values(&labels).inc();
+    BYTES
+        .with_label_values(&labels)
+        .inc_by(msg.json.len() as u64);
+}
+
+pub(crate) fn record_dropped(msg: &Message) {
+    DROPPED
+        .with_label_values(&[
+            msg.exchange.as_str(),
+            &msg.market_type.to_string(),
+            &msg.msg_type.to_string(),
+        ])
+        .inc();
+}
+
+pub(crate) fn track_ws_client(
+    exchange: &str,
+    market_type: MarketType,
+    client: &Arc<dyn WSClient + Send + Sync>,
+) {
+    WS_CLIENTS.lock().unwrap().push(TrackedClient {
+        exchange: exchange.to_string(),
+        market_type,
+        client: Arc::downgrade(client),
+        num_reconnects: 0,
+    });
+}
+
+// Adds reconnects since the last gather() to RECONNECTS and forgets dropped clients
+fn update_reconnects() {
+    WS_CLIENTS.lock().unwrap().retain_mut(|tracked| {
+        if let Some(client) = tracked.client.upgrade() {
+            let num_reconnects = client.stats().num_reconnects;
+            RECONNECTS
+                .with_label_values(&[&tracked.exchange, &tracked.market_type.to_string()])
+                .inc_by((num_reconnects - tracked.num_reconnects) as u64);
+            tracked.num_reconnects = num_reconnects;
+            true
+        } else {
+            false
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{gather, record_dropped, record_message, record_parse_error};
+    use crate::{MarketType, Message, MessageType};
+
+    #[test]
+    fn counters() {
+        let msg = Message::new(
+            "binance".to_string(),
+            MarketType::Spot,
+            MessageType::Trade,
+            r#"{"e":"trade"}"#.to_string(),
+        );
+        record_message(&msg);
+        record_message(&msg);
+        record_parse_error("binance", MarketType::Spot, MessageType::Trade);
+        record_dropped(&msg);
+
+        let text = gather();
+        assert!(text.contains(
+            r#"crypto_crawler_messages_total{exchange="binance",market_type="spot",msg_type="trade"} 2"#
+        ));
+        assert!(text.contains(
+            r#"crypto_crawler_bytes_total{exchange="binance",market_type="spot",msg_type="trade"} 26"#
+        ));
+        assert!(text.contains(
+            r#"crypto_crawler_parse_errors_total{exchange="binance",market_type="spot",msg_type="trade"} 1"#
+        ));
+        assert!(text.contains(
+            r#"crypto_crawler_dropped_messages_total{exchange="binance",market_type="spot",msg_type="trade"} 1"#
+        ));
+    }
+}
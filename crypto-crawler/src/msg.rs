@@ -1,62 +1,33 @@
-use super::MarketType;
+use super::{MarketType, MessageType};
 use serde::{Deserialize, Serialize};
 use std::convert::TryInto;
 use std::time::{SystemTime, UNIX_EPOCH};
-use strum_macros::{Display, EnumString};
-
-/// Message types.
-///
-/// L2Snapshot and L2TopK are very similar, the former is from RESTful API, the latter is from websocket.
-#[derive(Copy, Clone, PartialEq, Serialize, Deserialize, Display, Debug, EnumString)]
-#[serde(rename_all = "snake_case")]
-#[strum(serialize_all = "snake_case")]
-pub enum MessageType {
-    /// All other messages
-    Other,
-    /// tick-by-tick trade messages
-    Trade,
-    /// Incremental level2 orderbook updates
-    L2Event,
-    /// Level2 snapshot from RESTful API
-    L2Snapshot,
-    /// Level2 top K snapshots from websocket
-    #[serde(rename = "l2_topk")]
-    #[strum(serialize = "l2_topk")]
-    L2TopK,
-    /// Incremental level3 orderbook updates
-    L3Event,
-    /// Level3 snapshot from RESTful API
-    L3Snapshot,
-    /// Best bid and ask
-    #[serde(rename = "bbo")]
-    #[allow(clippy::upper_case_acronyms)]
-    BBO,
-    /// 24hr rolling window ticker
-    Ticker,
-    /// OHLCV candlestick
-    Candlestick,
-    /// Funding rate
-    FundingRate,
-    // Open interest
-    OpenInterest,
-}
 
 /// Message represents messages received by crawlers.
+///
+/// All fields except `json` are filled in by the crawl function that received
+/// the message, so downstream consumers can route a message to the right
+/// parser purely by `exchange`, `market_type` and `msg_type`, without sniffing
+/// the payload.
 #[derive(Serialize, Deserialize)]
 pub struct Message {
     /// The exchange name, unique for each exchage
     pub exchange: String,
-    /// Market type
+    /// Market type, which is the `market_type` passed to the crawl function
     pub market_type: MarketType,
-    /// Message type
+    /// Message type, determined by the crawl function, e.g., `crawl_trade()`
+    /// always produces `MessageType::Trade`
     pub msg_type: MessageType,
-    /// Unix timestamp in milliseconds
+    /// Unix timestamp in milliseconds, set when the message is received from
+    /// the exchange, not the exchange timestamp inside `json`
     pub received_at: u64,
-    /// the original message
+    /// The original message, exactly as received from the websocket or RESTful API,
+    /// except synthetic BBO messages, see `crawl_bbo()`
     pub json: String,
 }
 
 impl Message {
+    /// Creates a message with `received_at` set to the current time.
     pub fn new(
         exchange: String,
         market_type: MarketType,
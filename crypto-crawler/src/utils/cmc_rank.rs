@@ -278,17 +278,16 @@ lazy_static! {
 }
 
 fn http_get(url: &str) -> Result<String, reqwest::Error> {
-    let mut headers = header::HeaderMap::new();
+    let mut headers = crypto_pair::headers("coinmarketcap");
     headers.insert(
         header::CONTENT_TYPE,
         header::HeaderValue::from_static("application/json"),
     );
 
     let client = reqwest::blocking::Client::builder()
-         .default_headers(headers)
-         .user_agent("Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/87.0.4280.88 Safari/537.36")
-         .gzip(true)
-         .build()?;
+        .default_headers(headers)
+        .gzip(true)
+        .build()?;
     let response = client.get(url).send()?;
 
     match response.error_for_status() {
@@ -0,0 +1,267 @@
+use std::fs::{File, OpenOptions};
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::Receiver;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use crate::Message;
+
+type SymbolExtractor = Box<dyn Fn(&Message) -> Option<String> + Send>;
+
+const CSV_HEADER: &str = "exchange,market_type,msg_type,symbol,received_at,json";
+
+/// Output formats supported by `MessageWriter`.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum OutputFormat {
+    /// One JSON object per line, same as `Message::to_string()`
+    JsonLines,
+    /// CSV with columns `exchange,market_type,msg_type,symbol,received_at,json`
+    Csv,
+}
+
+/// Writes crawled messages to a file, with optional rotation by size or time.
+///
+/// Messages are appended to `path`. When the file exceeds `max_bytes` or has
+/// been open longer than `interval`, it is renamed to `{path}.{unix_millis}`
+/// and a fresh file is opened at `path`.
+///
+/// `Message` doesn't carry a symbol, so the `symbol` CSV column is empty unless
+/// a symbol extractor is set via `with_symbol_extractor()`, e.g., a closure
+/// around `crypto_msg_parser::extract_symbol()`.
+///
+/// ## Example
+///
+/// ```no_run
+/// use crypto_crawler::{crawl_trade, MarketType, MessageWriter, OutputFormat};
+///
+/// let (tx, rx) = std::sync::mpsc::channel();
+/// std::thread::spawn(move || {
+///     let writer = MessageWriter::new("/tmp/trades.csv", OutputFormat::Csv)
+///         .unwrap()
+///         .rotate_by_size(64 * 1024 * 1024);
+///     writer.consume(rx).unwrap();
+/// });
+///
+/// crawl_trade("binance", MarketType::Spot, None, tx, Some(5));
+/// ```
+pub struct MessageWriter {
+    path: PathBuf,
+    format: OutputFormat,
+    max_bytes: Option<u64>,
+    interval: Option<Duration>,
+    symbol_extractor: Option<SymbolExtractor>,
+    writer: BufWriter<File>,
+    written: u64,
+    opened_at: Instant,
+}
+
+impl MessageWriter {
+    /// Opens `path` for appending, creating it if it doesn't exist.
+    pub fn new<P: AsRef<Path>>(path: P, format: OutputFormat) -> std::io::Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        let (writer, written) = Self::open(&path, format)?;
+        Ok(MessageWriter {
+            path,
+            format,
+            max_bytes: None,
+            interval: None,
+            symbol_extractor: None,
+            writer,
+            written,
+            opened_at: Instant::now(),
+        })
+    }
+
+    /// Rotates the file once it reaches `max_bytes` bytes.
+    pub fn rotate_by_size(mut self, max_bytes: u64) -> Self {
+        self.max_bytes = Some(max_bytes);
+        self
+    }
+
+    /// Rotates the file every `interval` seconds.
+    pub fn rotate_by_time(mut self, interval: u64) -> Self {
+        self.interval = Some(Duration::from_secs(interval));
+        self
+    }
+
+    /// Sets a function to fill the `symbol` CSV column.
+    pub fn with_symbol_extractor<F>(mut self, extractor: F) -> Self
+    where
+        F: Fn(&Message) -> Option<String> + Send + 'static,
+    {
+        self.symbol_extractor = Some(Box::new(extractor));
+        self
+    }
+
+    /// Writes one message, rotating the file first if needed.
+    pub fn write(&mut self, msg: &Message) -> std::io::Result<()> {
+        if self.should_rotate() {
+            self.rotate()?;
+        }
+        let line = match self.format {
+            OutputFormat::JsonLines => format!("{}\n", msg),
+            OutputFormat::Csv => {
+                let symbol = self
+                    .symbol_extractor
+                    .as_ref()
+                    .and_then(|f| f(msg))
+                    .unwrap_or_default();
+                format!(
+                    "{},{},{},{},{},{}\n",
+                    escape_csv(&msg.exchange),
+                    msg.market_type,
+                    msg.msg_type,
+                    escape_csv(&symbol),
+                    msg.received_at,
+                    escape_csv(&msg.json)
+                )
+            }
+        };
+        self.writer.write_all(line.as_bytes())?;
+        self.written += line.len() as u64;
+        Ok(())
+    }
+
+    /// Flushes buffered data to disk.
+    pub fn flush(&mut self) -> std::io::Result<()> {
+        self.writer.flush()
+    }
+
+    /// Writes all messages from `rx` until the sender side is dropped.
+    pub fn consume(mut self, rx: Receiver<Message>) -> std::io::Result<()> {
+        for msg in rx {
+            self.write(&msg)?;
+        }
+        self.flush()
+    }
+
+    fn should_rotate(&self) -> bool {
+        let header_len = if self.format == OutputFormat::Csv {
+            CSV_HEADER.len() as u64 + 1
+        } else {
+            0
+        };
+        if self.written <= header_len {
+            return false;
+        }
+        self.max_bytes.is_some_and(|max| self.written >= max)
+            || self
+                .interval
+                .is_some_and(|interval| self.opened_at.elapsed() >= interval)
+    }
+
+    fn rotate(&mut self) -> std::io::Result<()> {
+        self.writer.flush()?;
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_millis();
+        let mut rotated = PathBuf::from(format!("{}.{}", self.path.display(), now));
+        let mut n = 1;
+        while rotated.exists() {
+            rotated = PathBuf::from(format!("{}.{}.{}", self.path.display(), now, n));
+            n += 1;
+        }
+        std::fs::rename(&self.path, &rotated)?;
+        let (writer, written) = Self::open(&self.path, self.format)?;
+        self.writer = writer;
+        self.written = written;
+        self.opened_at = Instant::now();
+        Ok(())
+    }
+
+    fn open(path: &Path, format: OutputFormat) -> std::io::Result<(BufWriter<File>, u64)> {
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        let mut written = file.metadata()?.len();
+        let mut writer = BufWriter::new(file);
+        if format == OutputFormat::Csv && written == 0 {
+            writeln!(writer, "{}", CSV_HEADER)?;
+            written = CSV_HEADER.len() as u64 + 1;
+        }
+        Ok((writer, written))
+    }
+}
+
+impl Drop for MessageWriter {
+    fn drop(&mut self) {
+        let _ = self.writer.flush();
+    }
+}
+
+fn escape_csv(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{MessageWriter, OutputFormat};
+    use crate::{MarketType, Message, MessageType};
+
+    fn temp_path(name: &str) -> std::path::PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "crypto-crawler-writer-{}-{}",
+            name,
+            std::process::id()
+        ));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        dir.join("messages")
+    }
+
+    fn sample_msg() -> Message {
+        Message::new(
+            "binance".to_string(),
+            MarketType::Spot,
+            MessageType::Trade,
+            r#"{"s":"BTCUSDT","p":"1.0"}"#.to_string(),
+        )
+    }
+
+    #[test]
+    fn csv() {
+        let path = temp_path("csv");
+        let mut writer = MessageWriter::new(&path, OutputFormat::Csv)
+            .unwrap()
+            .with_symbol_extractor(|_| Some("BTCUSDT".to_string()));
+        let msg = sample_msg();
+        writer.write(&msg).unwrap();
+        writer.flush().unwrap();
+
+        let content = std::fs::read_to_string(&path).unwrap();
+        let lines: Vec<&str> = content.lines().collect();
+        assert_eq!(
+            "exchange,market_type,msg_type,symbol,received_at,json",
+            lines[0]
+        );
+        assert_eq!(
+            format!(
+                r#"binance,spot,trade,BTCUSDT,{},"{{""s"":""BTCUSDT"",""p"":""1.0""}}""#,
+                msg.received_at
+            ),
+            lines[1]
+        );
+    }
+
+    #[test]
+    fn json_lines_rotate_by_size() {
+        let path = temp_path("jsonl");
+        let mut writer = MessageWriter::new(&path, OutputFormat::JsonLines)
+            .unwrap()
+            .rotate_by_size(1);
+        for _ in 0..3 {
+            writer.write(&sample_msg()).unwrap();
+        }
+        writer.flush().unwrap();
+
+        let files = std::fs::read_dir(path.parent().unwrap()).unwrap().count();
+        assert_eq!(3, files);
+        let content = std::fs::read_to_string(&path).unwrap();
+        let msg: Message = serde_json::from_str(content.trim_end()).unwrap();
+        assert_eq!("binance", msg.exchange);
+        assert_eq!(MessageType::Trade, msg.msg_type);
+    }
+}
@@ -131,6 +131,18 @@ fn test_crawl_funding_rate(market_type: MarketType, symbol: &str) {
     )
 }
 
+#[test_case(MarketType::InverseSwap, "BTCUSD")]
+#[test_case(MarketType::LinearSwap, "BTCUSDT")]
+fn test_crawl_index_price(market_type: MarketType, symbol: &str) {
+    test_one_symbol!(
+        crawl_index_price,
+        EXCHANGE_NAME,
+        market_type,
+        symbol,
+        MessageType::IndexPrice
+    )
+}
+
 #[test_case(MarketType::Spot)]
 #[test_case(MarketType::InverseFuture)]
 #[test_case(MarketType::LinearFuture)]
@@ -139,3 +151,38 @@ fn test_crawl_funding_rate(market_type: MarketType, symbol: &str) {
 fn test_crawl_candlestick(market_type: MarketType) {
     gen_test_crawl_candlestick!(EXCHANGE_NAME, market_type)
 }
+
+#[test_case(MarketType::Spot, "BTCUSDT")]
+#[test_case(MarketType::LinearSwap, "BTCUSDT")]
+fn test_fetch_candlestick_history(market_type: MarketType, symbol: &str) {
+    // 1500 one-minute candlesticks span two pages
+    let start = 1633046400000;
+    let end = start + 1499 * 60000;
+    let klines =
+        fetch_candlestick_history(EXCHANGE_NAME, market_type, symbol, 60, start, end).unwrap();
+
+    assert_eq!(1500, klines.len());
+    assert_eq!(start, klines[0].timestamp);
+    assert_eq!(end, klines[1499].timestamp);
+    assert!(klines
+        .windows(2)
+        .all(|pair| pair[1].timestamp - pair[0].timestamp == 60000));
+    assert!(klines.iter().all(|kline| kline.period == "1m"));
+}
+
+#[test]
+fn test_fetch_candlestick_history_invalid() {
+    assert!(
+        fetch_candlestick_history(EXCHANGE_NAME, MarketType::Spot, "BTCUSDT", 60, -1, 0).is_err()
+    );
+    assert!(fetch_candlestick_history(
+        EXCHANGE_NAME,
+        MarketType::InverseSwap,
+        "BTCUSD_PERP",
+        60,
+        0,
+        0
+    )
+    .is_err());
+    assert!(fetch_candlestick_history("bitmex", MarketType::Spot, "XBTUSD", 60, 0, 0).is_err());
+}
@@ -45,6 +45,18 @@ fn test_crawl_l2_topk(market_type: MarketType, symbol: &str) {
     )
 }
 
+#[test_case(MarketType::InverseSwap, "btcusd")]
+#[test_case(MarketType::LinearSwap, "cmt_btcusdt")]
+fn test_crawl_bbo(market_type: MarketType, symbol: &str) {
+    test_one_symbol!(
+        crawl_bbo,
+        EXCHANGE_NAME,
+        market_type,
+        symbol,
+        MessageType::BBO
+    )
+}
+
 #[test_case(MarketType::Spot, "btc_usdt")]
 #[test_case(MarketType::InverseSwap, "btcusd")]
 #[test_case(MarketType::LinearSwap, "cmt_btcusdt")]
@@ -19,6 +19,7 @@ fn crawl_all(msg_type: MessageType) {
         MessageType::BBO => crawl_bbo,
         MessageType::L2TopK => crawl_l2_topk,
         MessageType::FundingRate => crawl_funding_rate,
+        MessageType::IndexPrice => crawl_index_price,
         _ => panic!("unsupported message type {}", msg_type),
     };
     crawl_func(EXCHANGE_NAME, MarketType::Unknown, None, tx, Some(0));
@@ -63,6 +64,11 @@ fn test_crawl_funding_rate_all() {
     crawl_all(MessageType::FundingRate);
 }
 
+#[test]
+fn test_crawl_index_price_all() {
+    crawl_all(MessageType::IndexPrice);
+}
+
 #[test]
 fn test_crawl_candlestick_rate_all() {
     let (tx, rx) = std::sync::mpsc::channel();
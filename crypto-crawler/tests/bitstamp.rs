@@ -31,6 +31,17 @@ fn test_crawl_l2_event(market_type: MarketType, symbol: &str) {
     )
 }
 
+#[test_case(MarketType::Spot, "btcusd")]
+fn test_crawl_bbo(market_type: MarketType, symbol: &str) {
+    test_one_symbol!(
+        crawl_bbo,
+        EXCHANGE_NAME,
+        market_type,
+        symbol,
+        MessageType::BBO
+    )
+}
+
 #[test_case(MarketType::Spot, "btcusd")]
 fn test_crawl_l2_topk(market_type: MarketType, symbol: &str) {
     test_one_symbol!(
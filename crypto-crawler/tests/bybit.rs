@@ -48,6 +48,19 @@ fn test_crawl_l2_topk(market_type: MarketType, symbol: &str) {
     )
 }
 
+#[test_case(MarketType::InverseFuture, "BTCUSDZ21")]
+#[test_case(MarketType::InverseSwap, "BTCUSD")]
+#[test_case(MarketType::LinearSwap, "BTCUSDT")]
+fn test_crawl_bbo(market_type: MarketType, symbol: &str) {
+    test_one_symbol!(
+        crawl_bbo,
+        EXCHANGE_NAME,
+        market_type,
+        symbol,
+        MessageType::BBO
+    )
+}
+
 #[test_case(MarketType::InverseFuture, "BTCUSDZ21")]
 #[test_case(MarketType::InverseSwap, "BTCUSD")]
 #[test_case(MarketType::LinearSwap, "BTCUSDT")]
@@ -31,6 +31,17 @@ fn test_crawl_l2_event(market_type: MarketType, symbol: &str) {
     )
 }
 
+#[test_case(MarketType::Spot, "BTC-USD")]
+fn test_crawl_l2_event_batched(market_type: MarketType, symbol: &str) {
+    test_one_symbol!(
+        crawl_l2_event_batched,
+        EXCHANGE_NAME,
+        market_type,
+        symbol,
+        MessageType::L2Event
+    )
+}
+
 #[test_case(MarketType::Spot, "BTC-USD")]
 fn test_crawl_l2_snapshot(market_type: MarketType, symbol: &str) {
     test_one_symbol!(
@@ -0,0 +1,57 @@
+mod utils;
+
+use crypto_crawler::*;
+use std::collections::HashSet;
+use utils::parse;
+
+#[test]
+fn crawl_trade_multi_exchanges() {
+    let (tx, rx) = std::sync::mpsc::channel();
+    let pairs = vec!["BTC/USDT".to_string()];
+    crawl_trade_multi(
+        vec![
+            ("binance".to_string(), MarketType::Spot, pairs.clone()),
+            ("okex".to_string(), MarketType::Spot, pairs.clone()),
+            (
+                "coinbase_pro".to_string(),
+                MarketType::Spot,
+                vec!["BTC/USD".to_string()],
+            ),
+            // no symbol for futures, skipped
+            ("okex".to_string(), MarketType::InverseFuture, pairs),
+        ],
+        tx,
+        Some(0),
+    );
+
+    let messages = rx.iter().collect::<Vec<Message>>();
+    let exchanges = messages
+        .iter()
+        .map(|msg| msg.exchange.clone())
+        .collect::<HashSet<String>>();
+    assert_eq!(
+        vec!["binance", "coinbase_pro", "okex"]
+            .into_iter()
+            .map(|x| x.to_string())
+            .collect::<HashSet<String>>(),
+        exchanges
+    );
+    for msg in messages {
+        assert_eq!(MessageType::Trade, msg.msg_type);
+        assert!(parse(msg));
+    }
+}
+
+#[test]
+fn crawl_trade_by_base_okex() {
+    let (tx, rx) = std::sync::mpsc::channel();
+    let symbols = crawl_trade_by_base("okex", "BTC", tx, Some(0));
+    assert!(symbols.contains(&(MarketType::Spot, "BTC-USDT".to_string())));
+    assert!(symbols.contains(&(MarketType::LinearSwap, "BTC-USDT-SWAP".to_string())));
+
+    let market_types = rx
+        .iter()
+        .map(|msg| msg.market_type.to_string())
+        .collect::<HashSet<String>>();
+    assert!(market_types.len() > 1);
+}
@@ -47,6 +47,18 @@ fn test_crawl_l2_topk(market_type: MarketType, symbol: &str) {
     )
 }
 
+#[test_case(MarketType::LinearSwap, "BTC_USDT")]
+#[test_case(MarketType::InverseSwap, "BTC_USD")]
+fn test_crawl_bbo(market_type: MarketType, symbol: &str) {
+    test_one_symbol!(
+        crawl_bbo,
+        EXCHANGE_NAME,
+        market_type,
+        symbol,
+        MessageType::BBO
+    )
+}
+
 #[test_case(MarketType::Spot, "BTC_USDT")]
 #[test_case(MarketType::LinearSwap, "BTC_USDT")]
 #[test_case(MarketType::InverseSwap, "BTC_USD")]
@@ -126,6 +126,18 @@ fn test_crawl_funding_rate(market_type: MarketType, symbol: &str) {
     )
 }
 
+#[test_case(MarketType::InverseSwap, "BTC-USD")]
+#[test_case(MarketType::LinearSwap, "BTC-USDT")]
+fn test_crawl_index_price(market_type: MarketType, symbol: &str) {
+    test_one_symbol!(
+        crawl_index_price,
+        EXCHANGE_NAME,
+        market_type,
+        symbol,
+        MessageType::IndexPrice
+    )
+}
+
 #[test_case(MarketType::Spot, "BTC-USDT")]
 #[test_case(MarketType::InverseFuture, "BTC-USD-211231")]
 #[test_case(MarketType::LinearFuture, "BTC-USDT-211231")]
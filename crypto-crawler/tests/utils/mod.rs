@@ -18,9 +18,20 @@ pub(crate) fn parse(msg: Message) -> bool {
                 .is_ok(),
             }
         }
+        // derived from level2 top-k snapshots
+        MessageType::BBO
+            if ["bitget", "bitstamp", "bybit", "mxc"].contains(&msg.exchange.as_str()) =>
+        {
+            serde_json::from_str::<crypto_msg_parser::BboMsg>(&msg.json)
+                .map(|bbo| bbo.synthetic)
+                .unwrap_or(false)
+        }
         MessageType::FundingRate => {
             crypto_msg_parser::parse_funding_rate(&msg.exchange, msg.market_type, &msg.json).is_ok()
         }
+        MessageType::IndexPrice => {
+            crypto_msg_parser::parse_index_price(&msg.exchange, msg.market_type, &msg.json).is_ok()
+        }
         _ => true,
     }
 }
@@ -46,6 +46,59 @@ pub enum MarketType {
     BVOL,
 }
 
+/// Message types, shared by crypto-ws-client, crypto-msg-parser and crypto-crawler.
+///
+/// L2Snapshot and L2TopK are very similar, the former is from RESTful API, the latter is from websocket.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, Display, Debug, EnumString)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum MessageType {
+    /// All other messages
+    Other,
+    /// tick-by-tick trade messages
+    Trade,
+    /// Incremental level2 orderbook updates
+    L2Event,
+    /// Level2 snapshot from RESTful API
+    L2Snapshot,
+    /// Level2 top K snapshots from websocket
+    #[serde(rename = "l2_topk")]
+    #[strum(serialize = "l2_topk")]
+    L2TopK,
+    /// Incremental level3 orderbook updates
+    L3Event,
+    /// Level3 snapshot from RESTful API
+    L3Snapshot,
+    /// Best bid and ask
+    #[serde(rename = "bbo")]
+    #[allow(clippy::upper_case_acronyms)]
+    BBO,
+    /// 24hr rolling window ticker
+    Ticker,
+    /// OHLCV candlestick
+    Candlestick,
+    /// Funding rate
+    FundingRate,
+    /// Open interest
+    OpenInterest,
+    /// Liquidation orders
+    Liquidation,
+    /// Mark price
+    MarkPrice,
+    /// Index price, i.e., the weighted average price on spot markets
+    IndexPrice,
+    /// Symbols listed or delisted, sent by crypto-crawler when the symbol
+    /// universe changes, the `json` field is `{"added":[...],"removed":[...]}`
+    SymbolChange,
+    /// A websocket connection was re-established, sent by crypto-crawler
+    /// before data of the new connection, the `json` field is
+    /// `{"symbols":[...]}`. Sequence numbers restart, so discard orderbooks
+    /// and other state of these symbols. Only sent if the `RESET_ON_RECONNECT`
+    /// environment variable is `true`, otherwise crawlers exit the process
+    /// when a connection drops.
+    Reset,
+}
+
 /// Get market types of a cryptocurrency exchange.
 pub fn get_market_types(exchange: &str) -> Vec<MarketType> {
     match exchange {
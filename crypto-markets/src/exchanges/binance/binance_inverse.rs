@@ -1,5 +1,5 @@
-use super::utils::{binance_http_get, parse_filter};
-use crate::{error::Result, market::*, Market, MarketType};
+use super::utils::{binance_contract_status, binance_http_get, parse_filter};
+use crate::{error::Result, market::*, HttpClient, Market, MarketType};
 
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
@@ -40,8 +40,8 @@ struct FutureMarket {
 }
 
 // see <https://binance-docs.github.io/apidocs/delivery/en/#exchange-information>
-fn fetch_inverse_markets_raw() -> Result<Vec<FutureMarket>> {
-    let txt = binance_http_get("https://dapi.binance.com/dapi/v1/exchangeInfo")?;
+fn fetch_inverse_markets_raw(client: &dyn HttpClient) -> Result<Vec<FutureMarket>> {
+    let txt = binance_http_get(client, "https://dapi.binance.com/dapi/v1/exchangeInfo")?;
     let resp = serde_json::from_str::<BinanceResponse<FutureMarket>>(&txt)?;
     let symbols: Vec<FutureMarket> = resp
         .symbols
@@ -51,8 +51,8 @@ fn fetch_inverse_markets_raw() -> Result<Vec<FutureMarket>> {
     Ok(symbols)
 }
 
-pub(super) fn fetch_inverse_future_symbols() -> Result<Vec<String>> {
-    let symbols = fetch_inverse_markets_raw()?
+pub(super) fn fetch_inverse_future_symbols(client: &dyn HttpClient) -> Result<Vec<String>> {
+    let symbols = fetch_inverse_markets_raw(client)?
         .into_iter()
         .filter(|m| m.contractType != "PERPETUAL")
         .map(|m| m.symbol)
@@ -60,8 +60,8 @@ pub(super) fn fetch_inverse_future_symbols() -> Result<Vec<String>> {
     Ok(symbols)
 }
 
-pub(super) fn fetch_inverse_swap_symbols() -> Result<Vec<String>> {
-    let symbols = fetch_inverse_markets_raw()?
+pub(super) fn fetch_inverse_swap_symbols(client: &dyn HttpClient) -> Result<Vec<String>> {
+    let symbols = fetch_inverse_markets_raw(client)?
         .into_iter()
         .filter(|m| m.contractType == "PERPETUAL")
         .map(|m| m.symbol)
@@ -69,11 +69,12 @@ pub(super) fn fetch_inverse_swap_symbols() -> Result<Vec<String>> {
     Ok(symbols)
 }
 
-fn fetch_future_markets_internal() -> Result<Vec<Market>> {
-    let raw_markets = fetch_inverse_markets_raw()?;
+fn fetch_future_markets_internal(client: &dyn HttpClient) -> Result<Vec<Market>> {
+    let raw_markets = fetch_inverse_markets_raw(client)?;
     let markets = raw_markets
         .into_iter()
         .map(|m| {
+            let status = binance_contract_status(&m.contractStatus);
             Market {
                 exchange: "binance".to_string(),
                 market_type: if m.contractType == "PERPETUAL" {
@@ -88,7 +89,8 @@ fn fetch_future_markets_internal() -> Result<Vec<Market>> {
                 base: m.baseAsset.clone(),
                 quote: m.quoteAsset.clone(),
                 settle: Some(m.marginAsset.clone()),
-                active: m.contractStatus == "TRADING",
+                active: status == MarketStatus::Trading,
+                status,
                 margin: true,
                 // see https://www.binance.com/en/fee/futureFee
                 fees: Fees {
@@ -126,16 +128,16 @@ fn fetch_future_markets_internal() -> Result<Vec<Market>> {
     Ok(markets)
 }
 
-pub(super) fn fetch_inverse_future_markets() -> Result<Vec<Market>> {
-    let markets = fetch_future_markets_internal()?
+pub(super) fn fetch_inverse_future_markets(client: &dyn HttpClient) -> Result<Vec<Market>> {
+    let markets = fetch_future_markets_internal(client)?
         .into_iter()
         .filter(|m| m.market_type == MarketType::InverseFuture)
         .collect();
     Ok(markets)
 }
 
-pub(super) fn fetch_inverse_swap_markets() -> Result<Vec<Market>> {
-    let markets = fetch_future_markets_internal()?
+pub(super) fn fetch_inverse_swap_markets(client: &dyn HttpClient) -> Result<Vec<Market>> {
+    let markets = fetch_future_markets_internal(client)?
         .into_iter()
         .filter(|m| m.market_type == MarketType::InverseSwap)
         .collect();
@@ -1,5 +1,5 @@
-use super::utils::{binance_http_get, parse_filter};
-use crate::{error::Result, market::*, Market, MarketType};
+use super::utils::{binance_contract_status, binance_http_get, parse_filter};
+use crate::{error::Result, market::*, HttpClient, Market, MarketType};
 
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
@@ -38,8 +38,8 @@ struct LinearSwapMarket {
 }
 
 // see <https://binance-docs.github.io/apidocs/futures/en/#exchange-information>
-fn fetch_linear_markets_raw() -> Result<Vec<LinearSwapMarket>> {
-    let txt = binance_http_get("https://fapi.binance.com/fapi/v1/exchangeInfo")?;
+fn fetch_linear_markets_raw(client: &dyn HttpClient) -> Result<Vec<LinearSwapMarket>> {
+    let txt = binance_http_get(client, "https://fapi.binance.com/fapi/v1/exchangeInfo")?;
     let resp = serde_json::from_str::<BinanceResponse<LinearSwapMarket>>(&txt)?;
     let symbols: Vec<LinearSwapMarket> = resp
         .symbols
@@ -49,8 +49,8 @@ fn fetch_linear_markets_raw() -> Result<Vec<LinearSwapMarket>> {
     Ok(symbols)
 }
 
-pub(super) fn fetch_linear_swap_symbols() -> Result<Vec<String>> {
-    let symbols = fetch_linear_markets_raw()?
+pub(super) fn fetch_linear_swap_symbols(client: &dyn HttpClient) -> Result<Vec<String>> {
+    let symbols = fetch_linear_markets_raw(client)?
         .into_iter()
         .filter(|m| m.contractType == "PERPETUAL")
         .map(|m| m.symbol)
@@ -58,8 +58,8 @@ pub(super) fn fetch_linear_swap_symbols() -> Result<Vec<String>> {
     Ok(symbols)
 }
 
-pub(super) fn fetch_linear_future_symbols() -> Result<Vec<String>> {
-    let symbols = fetch_linear_markets_raw()?
+pub(super) fn fetch_linear_future_symbols(client: &dyn HttpClient) -> Result<Vec<String>> {
+    let symbols = fetch_linear_markets_raw(client)?
         .into_iter()
         .filter(|m| m.contractType != "PERPETUAL")
         .map(|m| m.symbol)
@@ -67,11 +67,12 @@ pub(super) fn fetch_linear_future_symbols() -> Result<Vec<String>> {
     Ok(symbols)
 }
 
-fn fetch_linear_markets() -> Result<Vec<Market>> {
-    let raw_markets = fetch_linear_markets_raw()?;
+fn fetch_linear_markets(client: &dyn HttpClient) -> Result<Vec<Market>> {
+    let raw_markets = fetch_linear_markets_raw(client)?;
     let markets = raw_markets
         .into_iter()
         .map(|m| {
+            let status = binance_contract_status(&m.status);
             Market {
                 exchange: "binance".to_string(),
                 market_type: if m.contractType == "PERPETUAL" {
@@ -86,7 +87,8 @@ fn fetch_linear_markets() -> Result<Vec<Market>> {
                 base: m.baseAsset.clone(),
                 quote: m.quoteAsset.clone(),
                 settle: Some(m.marginAsset.clone()),
-                active: m.status == "TRADING",
+                active: status == MarketStatus::Trading,
+                status,
                 margin: true,
                 // see https://www.binance.com/en/fee/futureFee
                 fees: Fees {
@@ -124,8 +126,8 @@ fn fetch_linear_markets() -> Result<Vec<Market>> {
     Ok(markets)
 }
 
-pub(super) fn fetch_linear_swap_markets() -> Result<Vec<Market>> {
-    let markets = fetch_linear_markets()?;
+pub(super) fn fetch_linear_swap_markets(client: &dyn HttpClient) -> Result<Vec<Market>> {
+    let markets = fetch_linear_markets(client)?;
     let swap_markets = markets
         .into_iter()
         .filter(|m| m.market_type == MarketType::LinearSwap)
@@ -133,8 +135,8 @@ pub(super) fn fetch_linear_swap_markets() -> Result<Vec<Market>> {
     Ok(swap_markets)
 }
 
-pub(super) fn fetch_linear_future_markets() -> Result<Vec<Market>> {
-    let markets = fetch_linear_markets()?;
+pub(super) fn fetch_linear_future_markets(client: &dyn HttpClient) -> Result<Vec<Market>> {
+    let markets = fetch_linear_markets(client)?;
     let future_markets = markets
         .into_iter()
         .filter(|m| m.market_type == MarketType::LinearFuture)
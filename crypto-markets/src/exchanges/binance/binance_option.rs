@@ -1,5 +1,5 @@
 use super::utils::binance_http_get;
-use crate::{error::Result, market::*, Market, MarketType};
+use crate::{error::Result, market::*, HttpClient, Market, MarketType};
 
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
@@ -30,7 +30,7 @@ struct OptionMarket {
     extra: HashMap<String, Value>,
 }
 
-fn fetch_option_markets_raw() -> Result<Vec<OptionMarket>> {
+fn fetch_option_markets_raw(client: &dyn HttpClient) -> Result<Vec<OptionMarket>> {
     #[derive(Serialize, Deserialize)]
     #[allow(non_snake_case)]
     struct OptionData {
@@ -48,21 +48,21 @@ fn fetch_option_markets_raw() -> Result<Vec<OptionMarket>> {
         data: OptionData,
     }
 
-    let txt = binance_http_get("https://vapi.binance.com/vapi/v1/exchangeInfo")?;
+    let txt = binance_http_get(client, "https://vapi.binance.com/vapi/v1/exchangeInfo")?;
     let resp = serde_json::from_str::<BinanceOptionResponse>(&txt)?;
     Ok(resp.data.optionSymbols)
 }
 
-pub(super) fn fetch_option_symbols() -> Result<Vec<String>> {
-    let symbols = fetch_option_markets_raw()?
+pub(super) fn fetch_option_symbols(client: &dyn HttpClient) -> Result<Vec<String>> {
+    let symbols = fetch_option_markets_raw(client)?
         .into_iter()
         .map(|m| m.symbol)
         .collect::<Vec<String>>();
     Ok(symbols)
 }
 
-pub(super) fn fetch_option_markets() -> Result<Vec<Market>> {
-    let raw_markets = fetch_option_markets_raw()?;
+pub(super) fn fetch_option_markets(client: &dyn HttpClient) -> Result<Vec<Market>> {
+    let raw_markets = fetch_option_markets_raw(client)?;
     let markets = raw_markets
         .into_iter()
         .map(|m| {
@@ -78,6 +78,7 @@ pub(super) fn fetch_option_markets() -> Result<Vec<Market>> {
                 quote: m.quoteAsset.clone(),
                 settle: Some(m.quoteAsset.clone()),
                 active: true,
+                status: MarketStatus::Trading,
                 margin: true,
                 // see https://www.binance.com/en/fee/optionFee
                 fees: Fees {
@@ -1,5 +1,5 @@
 use super::utils::{binance_http_get, parse_filter};
-use crate::{error::Result, market::*, Market, MarketType};
+use crate::{error::Result, market::*, HttpClient, Market, MarketType};
 
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
@@ -28,8 +28,8 @@ struct SpotMarket {
 }
 
 // see <https://binance-docs.github.io/apidocs/spot/en/#exchange-information>
-fn fetch_spot_markets_raw() -> Result<Vec<SpotMarket>> {
-    let txt = binance_http_get("https://api.binance.com/api/v3/exchangeInfo")?;
+fn fetch_spot_markets_raw(client: &dyn HttpClient) -> Result<Vec<SpotMarket>> {
+    let txt = binance_http_get(client, "https://api.binance.com/api/v3/exchangeInfo")?;
     let resp = serde_json::from_str::<BinanceResponse<SpotMarket>>(&txt)?;
     Ok(resp
         .symbols
@@ -38,8 +38,8 @@ fn fetch_spot_markets_raw() -> Result<Vec<SpotMarket>> {
         .collect())
 }
 
-pub(super) fn fetch_spot_symbols() -> Result<Vec<String>> {
-    let symbols = fetch_spot_markets_raw()?
+pub(super) fn fetch_spot_symbols(client: &dyn HttpClient) -> Result<Vec<String>> {
+    let symbols = fetch_spot_markets_raw(client)?
         .into_iter()
         .filter(|m| m.status == "TRADING" && m.isSpotTradingAllowed)
         .map(|m| m.symbol)
@@ -47,11 +47,16 @@ pub(super) fn fetch_spot_symbols() -> Result<Vec<String>> {
     Ok(symbols)
 }
 
-pub(super) fn fetch_spot_markets() -> Result<Vec<Market>> {
-    let raw_markets = fetch_spot_markets_raw()?;
+pub(super) fn fetch_spot_markets(client: &dyn HttpClient) -> Result<Vec<Market>> {
+    let raw_markets = fetch_spot_markets_raw(client)?;
     let markets = raw_markets
         .into_iter()
         .map(|m| {
+            let status = match m.status.as_str() {
+                "TRADING" if m.isSpotTradingAllowed => MarketStatus::Trading,
+                "BREAK" => MarketStatus::Delisted,
+                _ => MarketStatus::Halted,
+            };
             Market {
                 exchange: "binance".to_string(),
                 market_type: MarketType::Spot,
@@ -62,7 +67,8 @@ pub(super) fn fetch_spot_markets() -> Result<Vec<Market>> {
                 base: m.baseAsset.clone(),
                 quote: m.quoteAsset.clone(),
                 settle: None,
-                active: m.status == "TRADING" && m.isSpotTradingAllowed,
+                active: status == MarketStatus::Trading,
+                status,
                 margin: m.isMarginTradingAllowed,
                 // see https://www.binance.com/en/fee/trading
                 fees: Fees {
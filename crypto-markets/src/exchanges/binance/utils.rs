@@ -1,5 +1,6 @@
 use super::super::utils::http_get;
 use crate::error::{Error, Result};
+use crate::{HttpClient, MarketStatus};
 
 use serde_json::Value;
 use std::collections::HashMap;
@@ -22,8 +23,8 @@ fn check_code_in_body(resp: String) -> Result<String> {
     }
 }
 
-pub(super) fn binance_http_get(url: &str) -> Result<String> {
-    let ret = http_get(url, None);
+pub(super) fn binance_http_get(client: &dyn HttpClient, url: &str) -> Result<String> {
+    let ret = http_get(client, url, None);
     match ret {
         Ok(resp) => check_code_in_body(resp),
         Err(_) => ret,
@@ -42,3 +43,12 @@ pub(super) fn parse_filter<'a>(
         .as_str()
         .unwrap()
 }
+
+// see https://binance-docs.github.io/apidocs/futures/en/#public-endpoints-info
+pub(super) fn binance_contract_status(status: &str) -> MarketStatus {
+    match status {
+        "TRADING" => MarketStatus::Trading,
+        "DELIVERED" | "CLOSE" => MarketStatus::Delisted,
+        _ => MarketStatus::Halted,
+    }
+}
@@ -1,18 +1,22 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 use super::utils::http_get;
+use crate::HttpClient;
 use crate::{
     error::Result,
     market::{Fees, Precision, QuantityLimit},
-    Market, MarketType,
+    Market, MarketStatus, MarketType,
 };
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
-pub(crate) fn fetch_symbols(market_type: MarketType) -> Result<Vec<String>> {
+pub(crate) fn fetch_symbols(
+    client: &dyn HttpClient,
+    market_type: MarketType,
+) -> Result<Vec<String>> {
     match market_type {
-        MarketType::Spot => fetch_spot_symbols(),
-        MarketType::LinearSwap => fetch_linear_swap_symbols(),
+        MarketType::Spot => fetch_spot_symbols(client),
+        MarketType::LinearSwap => fetch_linear_swap_symbols(client),
         _ => panic!("Unsupported market_type: {}", market_type),
     }
 }
@@ -31,9 +35,9 @@ struct RawMarket {
     extra: HashMap<String, Value>,
 }
 
-fn fetch_raw_markets() -> Result<Vec<RawMarket>> {
+fn fetch_raw_markets(client: &dyn HttpClient) -> Result<Vec<RawMarket>> {
     // can NOT use v2 API due to https://github.com/bitfinexcom/bitfinex-api-py/issues/95
-    let text = http_get("https://api.bitfinex.com/v1/symbols_details", None)?;
+    let text = http_get(client, "https://api.bitfinex.com/v1/symbols_details", None)?;
     let markets = serde_json::from_str::<Vec<RawMarket>>(&text)?;
     let markets = markets
         .into_iter()
@@ -42,8 +46,11 @@ fn fetch_raw_markets() -> Result<Vec<RawMarket>> {
     Ok(markets)
 }
 
-pub(crate) fn fetch_markets(market_type: MarketType) -> Result<Vec<Market>> {
-    let raw_markets = fetch_raw_markets()?;
+pub(crate) fn fetch_markets(
+    client: &dyn HttpClient,
+    market_type: MarketType,
+) -> Result<Vec<Market>> {
+    let raw_markets = fetch_raw_markets(client)?;
     let raw_markets: Vec<RawMarket> = match market_type {
         MarketType::Spot => raw_markets
             .into_iter()
@@ -55,6 +62,10 @@ pub(crate) fn fetch_markets(market_type: MarketType) -> Result<Vec<Market>> {
             .collect(),
         _ => panic!("Unsupported market_type: {}", market_type),
     };
+    // symbols_details lists paused pairs too, the conf endpoints only list trading ones
+    let trading_symbols = fetch_symbols(client, market_type)?
+        .into_iter()
+        .collect::<HashSet<String>>();
     let markets: Vec<Market> = raw_markets
         .into_iter()
         .map(|m| {
@@ -73,10 +84,16 @@ pub(crate) fn fetch_markets(market_type: MarketType) -> Result<Vec<Market>> {
                     symbol[(symbol.len() - 3)..].to_string(),
                 )
             };
+            let symbol = format!("t{}", symbol);
+            let status = if trading_symbols.contains(&symbol) {
+                MarketStatus::Trading
+            } else {
+                MarketStatus::Halted
+            };
             Market {
                 exchange: "bitfinex".to_string(),
                 market_type,
-                symbol: format!("t{}", symbol),
+                symbol,
                 base_id,
                 quote_id: quote_id.clone(),
                 settle_id: if market_type == MarketType::LinearSwap {
@@ -91,7 +108,8 @@ pub(crate) fn fetch_markets(market_type: MarketType) -> Result<Vec<Market>> {
                 } else {
                     None
                 },
-                active: true,
+                active: status == MarketStatus::Trading,
+                status,
                 margin: m.margin,
                 // see https://www.bitfinex.com/fees
                 fees: if market_type == MarketType::Spot {
@@ -131,8 +149,9 @@ pub(crate) fn fetch_markets(market_type: MarketType) -> Result<Vec<Market>> {
 }
 
 // see <https://docs.bitfinex.com/reference#rest-public-conf>
-fn fetch_spot_symbols() -> Result<Vec<String>> {
+fn fetch_spot_symbols(client: &dyn HttpClient) -> Result<Vec<String>> {
     let text = http_get(
+        client,
         "https://api-pub.bitfinex.com/v2/conf/pub:list:pair:exchange",
         None,
     )?;
@@ -146,8 +165,9 @@ fn fetch_spot_symbols() -> Result<Vec<String>> {
 }
 
 // see <https://docs.bitfinex.com/reference#rest-public-conf>
-fn fetch_linear_swap_symbols() -> Result<Vec<String>> {
+fn fetch_linear_swap_symbols(client: &dyn HttpClient) -> Result<Vec<String>> {
     let text = http_get(
+        client,
         "https://api-pub.bitfinex.com/v2/conf/pub:list:pair:futures",
         None,
     )?;
@@ -167,9 +187,10 @@ mod tests {
     use super::super::utils::http_get;
     use super::{fetch_linear_swap_symbols, fetch_raw_markets, fetch_spot_symbols};
     use crate::error::Result;
+    use crate::{DefaultHttpClient, HttpClient};
 
-    fn _fetch_symbols(url: &str) -> Result<Vec<String>> {
-        let text = http_get(url, None)?;
+    fn _fetch_symbols(client: &dyn HttpClient, url: &str) -> Result<Vec<String>> {
+        let text = http_get(client, url, None)?;
         let arr = serde_json::from_str::<Vec<Value>>(&text)?;
         let arr = serde_json::from_value::<Vec<Value>>(arr[0].clone())?;
         let symbols = arr
@@ -180,21 +201,25 @@ mod tests {
         Ok(symbols)
     }
 
-    fn _fetch_spot_symbols() -> Result<Vec<String>> {
-        _fetch_symbols("https://api-pub.bitfinex.com/v2/conf/pub:info:pair")
+    fn _fetch_spot_symbols(client: &dyn HttpClient) -> Result<Vec<String>> {
+        _fetch_symbols(client, "https://api-pub.bitfinex.com/v2/conf/pub:info:pair")
     }
 
-    fn _fetch_linear_swap_symbols() -> Result<Vec<String>> {
-        _fetch_symbols("https://api-pub.bitfinex.com/v2/conf/pub:info:pair:futures")
+    fn _fetch_linear_swap_symbols(client: &dyn HttpClient) -> Result<Vec<String>> {
+        _fetch_symbols(
+            client,
+            "https://api-pub.bitfinex.com/v2/conf/pub:info:pair:futures",
+        )
     }
 
     #[test]
     fn test_spot_symbols() {
-        let mut symbols1 = _fetch_spot_symbols().unwrap();
-        let symbols2 = fetch_spot_symbols().unwrap();
+        let client = &DefaultHttpClient;
+        let mut symbols1 = _fetch_spot_symbols(client).unwrap();
+        let symbols2 = fetch_spot_symbols(client).unwrap();
         assert_eq!(symbols1, symbols2);
 
-        let mut symbols3: Vec<String> = fetch_raw_markets()
+        let mut symbols3: Vec<String> = fetch_raw_markets(client)
             .unwrap()
             .into_iter()
             .map(|m| format!("t{}", m.pair.to_uppercase()))
@@ -207,11 +232,12 @@ mod tests {
 
     #[test]
     fn test_linear_swap_symbols() {
-        let mut symbols1 = _fetch_linear_swap_symbols().unwrap();
-        let symbols2 = fetch_linear_swap_symbols().unwrap();
+        let client = &DefaultHttpClient;
+        let mut symbols1 = _fetch_linear_swap_symbols(client).unwrap();
+        let symbols2 = fetch_linear_swap_symbols(client).unwrap();
         assert_eq!(symbols1, symbols2);
 
-        let mut symbols3: Vec<String> = fetch_raw_markets()
+        let mut symbols3: Vec<String> = fetch_raw_markets(client)
             .unwrap()
             .into_iter()
             .map(|m| format!("t{}", m.pair.to_uppercase()))
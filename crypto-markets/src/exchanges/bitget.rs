@@ -1,28 +1,35 @@
 use std::collections::HashMap;
 
 use super::utils::http_get;
+use crate::HttpClient;
 use crate::{
     error::{Error, Result},
-    Fees, Market, MarketType, Precision,
+    Fees, Market, MarketStatus, MarketType, Precision,
 };
 
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
-pub(crate) fn fetch_symbols(market_type: MarketType) -> Result<Vec<String>> {
+pub(crate) fn fetch_symbols(
+    client: &dyn HttpClient,
+    market_type: MarketType,
+) -> Result<Vec<String>> {
     match market_type {
-        MarketType::Spot => fetch_spot_symbols(),
-        MarketType::InverseSwap => fetch_inverse_swap_symbols(),
-        MarketType::LinearSwap => fetch_linear_swap_symbols(),
+        MarketType::Spot => fetch_spot_symbols(client),
+        MarketType::InverseSwap => fetch_inverse_swap_symbols(client),
+        MarketType::LinearSwap => fetch_linear_swap_symbols(client),
         _ => panic!("Unsupported market_type: {}", market_type),
     }
 }
 
-pub(crate) fn fetch_markets(market_type: MarketType) -> Result<Vec<Market>> {
+pub(crate) fn fetch_markets(
+    client: &dyn HttpClient,
+    market_type: MarketType,
+) -> Result<Vec<Market>> {
     match market_type {
-        MarketType::Spot => fetch_spot_markets(),
-        MarketType::InverseSwap => fetch_inverse_swap_markets(),
-        MarketType::LinearSwap => fetch_linear_swap_markets(),
+        MarketType::Spot => fetch_spot_markets(client),
+        MarketType::InverseSwap => fetch_inverse_swap_markets(client),
+        MarketType::LinearSwap => fetch_linear_swap_markets(client),
         _ => panic!("Unsupported market_type: {}", market_type),
     }
 }
@@ -90,34 +97,42 @@ struct SwapMarket {
     extra: HashMap<String, Value>,
 }
 
-fn fetch_spot_markets_raw() -> Result<Vec<SpotMarket>> {
-    let txt = http_get("https://api.bitget.com/data/v1/common/symbols", None)?;
+fn fetch_spot_markets_raw(client: &dyn HttpClient) -> Result<Vec<SpotMarket>> {
+    let txt = http_get(
+        client,
+        "https://api.bitget.com/data/v1/common/symbols",
+        None,
+    )?;
     let resp = serde_json::from_str::<Response>(&txt)?;
     if resp.status != "ok" {
         Err(Error(txt))
     } else {
-        Ok(resp
-            .data
-            .into_iter()
-            .filter(|m| m.status == "online")
-            .collect())
+        Ok(resp.data)
     }
 }
 
-fn fetch_swap_markets_raw() -> Result<Vec<SwapMarket>> {
-    let txt = http_get("https://capi.bitget.com/api/swap/v3/market/contracts", None)?;
+fn fetch_swap_markets_raw(client: &dyn HttpClient) -> Result<Vec<SwapMarket>> {
+    let txt = http_get(
+        client,
+        "https://capi.bitget.com/api/swap/v3/market/contracts",
+        None,
+    )?;
     let markets = serde_json::from_str::<Vec<SwapMarket>>(&txt)?;
     Ok(markets)
 }
 
-fn fetch_spot_symbols() -> Result<Vec<String>> {
-    let markets = fetch_spot_markets_raw()?;
-    let symbols: Vec<String> = markets.into_iter().map(|m| m.symbol).collect();
+fn fetch_spot_symbols(client: &dyn HttpClient) -> Result<Vec<String>> {
+    let markets = fetch_spot_markets_raw(client)?;
+    let symbols: Vec<String> = markets
+        .into_iter()
+        .filter(|m| m.status == "online")
+        .map(|m| m.symbol)
+        .collect();
     Ok(symbols)
 }
 
-fn fetch_inverse_swap_symbols() -> Result<Vec<String>> {
-    let symbols = fetch_swap_markets_raw()?
+fn fetch_inverse_swap_symbols(client: &dyn HttpClient) -> Result<Vec<String>> {
+    let symbols = fetch_swap_markets_raw(client)?
         .into_iter()
         .filter(|m| !m.forwardContractFlag)
         .map(|m| m.symbol)
@@ -125,8 +140,8 @@ fn fetch_inverse_swap_symbols() -> Result<Vec<String>> {
     Ok(symbols)
 }
 
-fn fetch_linear_swap_symbols() -> Result<Vec<String>> {
-    let symbols = fetch_swap_markets_raw()?
+fn fetch_linear_swap_symbols(client: &dyn HttpClient) -> Result<Vec<String>> {
+    let symbols = fetch_swap_markets_raw(client)?
         .into_iter()
         .filter(|m| m.forwardContractFlag)
         .map(|m| m.symbol)
@@ -134,8 +149,8 @@ fn fetch_linear_swap_symbols() -> Result<Vec<String>> {
     Ok(symbols)
 }
 
-fn fetch_spot_markets() -> Result<Vec<Market>> {
-    let markets: Vec<Market> = fetch_spot_markets_raw()?
+fn fetch_spot_markets(client: &dyn HttpClient) -> Result<Vec<Market>> {
+    let markets: Vec<Market> = fetch_spot_markets_raw(client)?
         .into_iter()
         .map(|m| {
             let info = serde_json::to_value(&m)
@@ -148,6 +163,11 @@ fn fetch_spot_markets() -> Result<Vec<Market>> {
                 let v: Vec<&str> = pair.split('/').collect();
                 (v[0].to_string(), v[1].to_string())
             };
+            let status = if m.status == "online" {
+                MarketStatus::Trading
+            } else {
+                MarketStatus::Halted
+            };
             Market {
                 exchange: "bitget".to_string(),
                 market_type: MarketType::Spot,
@@ -158,7 +178,8 @@ fn fetch_spot_markets() -> Result<Vec<Market>> {
                 base,
                 quote,
                 settle: None,
-                active: true,
+                active: status == MarketStatus::Trading,
+                status,
                 margin: false,
                 // see https://www.bitget.com/en/rate?tab=1
                 fees: Fees {
@@ -199,7 +220,9 @@ fn to_market(raw_market: &SwapMarket) -> Market {
         base,
         quote,
         settle: Some(raw_market.coin.to_string()),
+        // contracts don't have a status and are listed while trading
         active: true,
+        status: MarketStatus::Trading,
         margin: true,
         // see https://www.bitget.com/en/rate?tab=1
         fees: Fees {
@@ -221,8 +244,8 @@ fn to_market(raw_market: &SwapMarket) -> Market {
     }
 }
 
-fn fetch_inverse_swap_markets() -> Result<Vec<Market>> {
-    let markets = fetch_swap_markets_raw()?
+fn fetch_inverse_swap_markets(client: &dyn HttpClient) -> Result<Vec<Market>> {
+    let markets = fetch_swap_markets_raw(client)?
         .into_iter()
         .filter(|m| !m.forwardContractFlag)
         .map(|m| to_market(&m))
@@ -230,8 +253,8 @@ fn fetch_inverse_swap_markets() -> Result<Vec<Market>> {
     Ok(markets)
 }
 
-fn fetch_linear_swap_markets() -> Result<Vec<Market>> {
-    let markets = fetch_swap_markets_raw()?
+fn fetch_linear_swap_markets(client: &dyn HttpClient) -> Result<Vec<Market>> {
+    let markets = fetch_swap_markets_raw(client)?
         .into_iter()
         .filter(|m| m.forwardContractFlag)
         .map(|m| to_market(&m))
@@ -1,24 +1,32 @@
 use std::collections::HashMap;
+use std::time::{SystemTime, UNIX_EPOCH};
 
 use super::utils::http_get;
+use crate::HttpClient;
 use crate::{
     error::{Error, Result},
-    Fees, Market, MarketType, Precision,
+    Fees, Market, MarketStatus, MarketType, Precision,
 };
 
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
-pub(crate) fn fetch_symbols(market_type: MarketType) -> Result<Vec<String>> {
+pub(crate) fn fetch_symbols(
+    client: &dyn HttpClient,
+    market_type: MarketType,
+) -> Result<Vec<String>> {
     match market_type {
-        MarketType::Spot => fetch_spot_symbols(),
+        MarketType::Spot => fetch_spot_symbols(client),
         _ => panic!("Unsupported market_type: {}", market_type),
     }
 }
 
-pub(crate) fn fetch_markets(market_type: MarketType) -> Result<Vec<Market>> {
+pub(crate) fn fetch_markets(
+    client: &dyn HttpClient,
+    market_type: MarketType,
+) -> Result<Vec<Market>> {
     match market_type {
-        MarketType::Spot => fetch_spot_markets(),
+        MarketType::Spot => fetch_spot_markets(client),
         _ => panic!("Unsupported market_type: {}", market_type),
     }
 }
@@ -74,8 +82,9 @@ struct Response {
 }
 
 // see https://github.com/bithumb-pro/bithumb.pro-official-api-docs/blob/master/rest-api.md#2-config-detail
-fn fetch_spot_coing() -> Result<Data> {
+fn fetch_spot_coing(client: &dyn HttpClient) -> Result<Data> {
     let txt = http_get(
+        client,
         "https://global-openapi.bithumb.pro/openapi/v1/spot/config",
         None,
     )?;
@@ -87,8 +96,8 @@ fn fetch_spot_coing() -> Result<Data> {
     }
 }
 
-fn fetch_spot_symbols() -> Result<Vec<String>> {
-    let symbols = fetch_spot_coing()?
+fn fetch_spot_symbols(client: &dyn HttpClient) -> Result<Vec<String>> {
+    let symbols = fetch_spot_coing(client)?
         .spotConfig
         .into_iter()
         .map(|m| m.symbol)
@@ -96,8 +105,12 @@ fn fetch_spot_symbols() -> Result<Vec<String>> {
     Ok(symbols)
 }
 
-fn fetch_spot_markets() -> Result<Vec<Market>> {
-    let markets = fetch_spot_coing()?
+fn fetch_spot_markets(client: &dyn HttpClient) -> Result<Vec<Market>> {
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_millis() as i64;
+    let markets = fetch_spot_coing(client)?
         .spotConfig
         .into_iter()
         .map(|m| {
@@ -115,6 +128,11 @@ fn fetch_spot_markets() -> Result<Vec<Market>> {
                 let v: Vec<&str> = m.symbol.split('-').collect();
                 (v[0].to_string(), v[1].to_string())
             };
+            let status = if m.openTime > now {
+                MarketStatus::Halted
+            } else {
+                MarketStatus::Trading
+            };
             Market {
                 exchange: "bithumb".to_string(),
                 market_type: MarketType::Spot,
@@ -125,7 +143,9 @@ fn fetch_spot_markets() -> Result<Vec<Market>> {
                 base,
                 quote,
                 settle: None,
-                active: true,
+                // listed before trading starts at openTime
+                active: status == MarketStatus::Trading,
+                status,
                 margin: false,
                 // see https://www.bitglobal.com/en-us/fee
                 fees: Fees {
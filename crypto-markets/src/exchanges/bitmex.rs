@@ -1,26 +1,33 @@
 use std::collections::HashMap;
 
 use super::utils::http_get;
+use crate::HttpClient;
 use crate::{
     error::Result,
     market::{Fees, Precision},
-    Market, MarketType,
+    Market, MarketStatus, MarketType,
 };
 
 use chrono::DateTime;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
-pub(crate) fn fetch_symbols(market_type: MarketType) -> Result<Vec<String>> {
-    let instruments = fetch_instruments(market_type)?;
+pub(crate) fn fetch_symbols(
+    client: &dyn HttpClient,
+    market_type: MarketType,
+) -> Result<Vec<String>> {
+    let instruments = fetch_instruments(client, market_type)?;
     Ok(instruments
         .into_iter()
         .map(|x| x.symbol)
         .collect::<Vec<String>>())
 }
 
-pub(crate) fn fetch_markets(market_type: MarketType) -> Result<Vec<Market>> {
-    let instruments = fetch_instruments(market_type)?;
+pub(crate) fn fetch_markets(
+    client: &dyn HttpClient,
+    market_type: MarketType,
+) -> Result<Vec<Market>> {
+    let instruments = fetch_instruments(client, market_type)?;
     let markets: Vec<Market> = instruments
         .into_iter()
         .map(|x| {
@@ -37,6 +44,11 @@ pub(crate) fn fetch_markets(market_type: MarketType) -> Result<Vec<Market>> {
                 (v[0].to_string(), v[1].to_string())
             };
 
+            let status = match x.state.as_str() {
+                "Open" => MarketStatus::Trading,
+                "Unlisted" | "Settled" => MarketStatus::Delisted,
+                _ => MarketStatus::Halted,
+            };
             Market {
                 exchange: "bitmex".to_string(),
                 market_type,
@@ -50,7 +62,8 @@ pub(crate) fn fetch_markets(market_type: MarketType) -> Result<Vec<Market>> {
                     x.settlCurrency.as_str(),
                     "bitmex",
                 )),
-                active: x.state == "Open",
+                active: status == MarketStatus::Trading,
+                status,
                 margin: true,
                 fees: Fees {
                     maker: x.makerFee,
@@ -160,8 +173,12 @@ struct Instrument {
     extra: HashMap<String, Value>,
 }
 
-fn fetch_instruments(market_type: MarketType) -> Result<Vec<Instrument>> {
-    let text = http_get("https://www.bitmex.com/api/v1/instrument/active", None)?;
+fn fetch_instruments(client: &dyn HttpClient, market_type: MarketType) -> Result<Vec<Instrument>> {
+    let text = http_get(
+        client,
+        "https://www.bitmex.com/api/v1/instrument/active",
+        None,
+    )?;
     let instruments: Vec<Instrument> = serde_json::from_str::<Vec<Instrument>>(&text)?
         .into_iter()
         .filter(|x| x.state == "Open")
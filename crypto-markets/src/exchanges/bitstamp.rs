@@ -1,20 +1,26 @@
 use super::utils::http_get;
-use crate::{error::Result, Fees, Market, MarketType, Precision};
+use crate::{error::Result, Fees, HttpClient, Market, MarketStatus, MarketType, Precision};
 
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::collections::HashMap;
 
-pub(crate) fn fetch_symbols(market_type: MarketType) -> Result<Vec<String>> {
+pub(crate) fn fetch_symbols(
+    client: &dyn HttpClient,
+    market_type: MarketType,
+) -> Result<Vec<String>> {
     match market_type {
-        MarketType::Spot => fetch_spot_symbols(),
+        MarketType::Spot => fetch_spot_symbols(client),
         _ => panic!("Unsupported market_type: {}", market_type),
     }
 }
 
-pub(crate) fn fetch_markets(market_type: MarketType) -> Result<Vec<Market>> {
+pub(crate) fn fetch_markets(
+    client: &dyn HttpClient,
+    market_type: MarketType,
+) -> Result<Vec<Market>> {
     match market_type {
-        MarketType::Spot => fetch_spot_markets(),
+        MarketType::Spot => fetch_spot_markets(client),
         _ => panic!("Unsupported market_type: {}", market_type),
     }
 }
@@ -33,25 +39,27 @@ struct SpotMarket {
 }
 
 // see <https://www.bitstamp.net/api/>
-fn fetch_spot_markets_raw() -> Result<Vec<SpotMarket>> {
-    let txt = http_get("https://www.bitstamp.net/api/v2/trading-pairs-info/", None)?;
+fn fetch_spot_markets_raw(client: &dyn HttpClient) -> Result<Vec<SpotMarket>> {
+    let txt = http_get(
+        client,
+        "https://www.bitstamp.net/api/v2/trading-pairs-info/",
+        None,
+    )?;
     let markets = serde_json::from_str::<Vec<SpotMarket>>(&txt)?;
-    Ok(markets
-        .into_iter()
-        .filter(|m| m.trading == "Enabled")
-        .collect())
+    Ok(markets)
 }
 
-fn fetch_spot_symbols() -> Result<Vec<String>> {
-    let symbols = fetch_spot_markets_raw()?
+fn fetch_spot_symbols(client: &dyn HttpClient) -> Result<Vec<String>> {
+    let symbols = fetch_spot_markets_raw(client)?
         .into_iter()
+        .filter(|m| m.trading == "Enabled")
         .map(|m| m.url_symbol)
         .collect::<Vec<String>>();
     Ok(symbols)
 }
 
-fn fetch_spot_markets() -> Result<Vec<Market>> {
-    let markets = fetch_spot_markets_raw()?
+fn fetch_spot_markets(client: &dyn HttpClient) -> Result<Vec<Market>> {
+    let markets = fetch_spot_markets_raw(client)?
         .into_iter()
         .map(|m| {
             let info = serde_json::to_value(&m)
@@ -68,6 +76,11 @@ fn fetch_spot_markets() -> Result<Vec<Market>> {
                 let v: Vec<&str> = m.name.split('/').collect();
                 (v[0].to_string(), v[1].to_string())
             };
+            let status = if m.trading == "Enabled" {
+                MarketStatus::Trading
+            } else {
+                MarketStatus::Halted
+            };
             Market {
                 exchange: "bitstamp".to_string(),
                 market_type: MarketType::Spot,
@@ -78,7 +91,8 @@ fn fetch_spot_markets() -> Result<Vec<Market>> {
                 base,
                 quote,
                 settle: None,
-                active: true,
+                active: status == MarketStatus::Trading,
+                status,
                 margin: true,
                 // see https://www.bitstamp.net/fee-schedule/
                 fees: Fees {
@@ -2,6 +2,7 @@ use std::collections::HashMap;
 
 use super::super::utils::http_get;
 use crate::error::{Error, Result};
+use crate::HttpClient;
 
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
@@ -36,8 +37,8 @@ struct Response {
 }
 
 // See https://apidocv2.bitz.plus/en/#get-data-of-trading-pairs
-fn fetch_spot_markets_raw() -> Result<Vec<SpotMarket>> {
-    let txt = http_get("https://apiv2.bitz.com/V2/Market/symbolList", None)?;
+fn fetch_spot_markets_raw(client: &dyn HttpClient) -> Result<Vec<SpotMarket>> {
+    let txt = http_get(client, "https://apiv2.bitz.com/V2/Market/symbolList", None)?;
     let resp = serde_json::from_str::<Response>(&txt)?;
     if resp.status != 200 {
         Err(Error(txt))
@@ -52,8 +53,8 @@ fn fetch_spot_markets_raw() -> Result<Vec<SpotMarket>> {
     }
 }
 
-pub(super) fn fetch_spot_symbols() -> Result<Vec<String>> {
-    let markets = fetch_spot_markets_raw()?;
+pub(super) fn fetch_spot_symbols(client: &dyn HttpClient) -> Result<Vec<String>> {
+    let markets = fetch_spot_markets_raw(client)?;
     let symbols: Vec<String> = markets.into_iter().map(|m| m.symbol).collect();
     Ok(symbols)
 }
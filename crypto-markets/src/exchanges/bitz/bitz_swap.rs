@@ -2,6 +2,7 @@ use std::collections::HashMap;
 
 use super::super::utils::http_get;
 use crate::error::{Error, Result};
+use crate::HttpClient;
 
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
@@ -47,8 +48,12 @@ struct Response {
 }
 
 // See https://apidocv2.bitz.plus/en/#get-market-list-of-contract-transactions
-fn fetch_swap_markets_raw() -> Result<Vec<SwapMarket>> {
-    let txt = http_get("https://apiv2.bitz.com/V2/Market/getContractCoin", None)?;
+fn fetch_swap_markets_raw(client: &dyn HttpClient) -> Result<Vec<SwapMarket>> {
+    let txt = http_get(
+        client,
+        "https://apiv2.bitz.com/V2/Market/getContractCoin",
+        None,
+    )?;
     let resp = serde_json::from_str::<Response>(&txt)?;
     if resp.status != 200 {
         Err(Error(txt))
@@ -58,8 +63,8 @@ fn fetch_swap_markets_raw() -> Result<Vec<SwapMarket>> {
     }
 }
 
-pub(super) fn fetch_inverse_swap_symbols() -> Result<Vec<String>> {
-    let symbols = fetch_swap_markets_raw()?
+pub(super) fn fetch_inverse_swap_symbols(client: &dyn HttpClient) -> Result<Vec<String>> {
+    let symbols = fetch_swap_markets_raw(client)?
         .into_iter()
         .filter(|m| m.isreverse == "1")
         .map(|m| m.pair)
@@ -67,8 +72,8 @@ pub(super) fn fetch_inverse_swap_symbols() -> Result<Vec<String>> {
     Ok(symbols)
 }
 
-pub(super) fn fetch_linear_swap_symbols() -> Result<Vec<String>> {
-    let symbols = fetch_swap_markets_raw()?
+pub(super) fn fetch_linear_swap_symbols(client: &dyn HttpClient) -> Result<Vec<String>> {
+    let symbols = fetch_swap_markets_raw(client)?
         .into_iter()
         .filter(|m| m.isreverse == "-1" && m.settleAnchor == "USDT")
         .map(|m| m.pair)
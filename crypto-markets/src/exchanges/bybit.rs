@@ -1,27 +1,35 @@
 use std::collections::HashMap;
 
 use super::utils::http_get;
-use crate::{error::Result, Fees, Market, MarketType, Precision, QuantityLimit};
+use crate::{
+    error::Result, Fees, HttpClient, Market, MarketStatus, MarketType, Precision, QuantityLimit,
+};
 
 use chrono::prelude::*;
 use chrono::DateTime;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
-pub(crate) fn fetch_symbols(market_type: MarketType) -> Result<Vec<String>> {
+pub(crate) fn fetch_symbols(
+    client: &dyn HttpClient,
+    market_type: MarketType,
+) -> Result<Vec<String>> {
     match market_type {
-        MarketType::InverseSwap => fetch_inverse_swap_symbols(),
-        MarketType::LinearSwap => fetch_linear_swap_symbols(),
-        MarketType::InverseFuture => fetch_inverse_future_symbols(),
+        MarketType::InverseSwap => fetch_inverse_swap_symbols(client),
+        MarketType::LinearSwap => fetch_linear_swap_symbols(client),
+        MarketType::InverseFuture => fetch_inverse_future_symbols(client),
         _ => panic!("Unsupported market_type: {}", market_type),
     }
 }
 
-pub(crate) fn fetch_markets(market_type: MarketType) -> Result<Vec<Market>> {
+pub(crate) fn fetch_markets(
+    client: &dyn HttpClient,
+    market_type: MarketType,
+) -> Result<Vec<Market>> {
     match market_type {
-        MarketType::InverseSwap => fetch_inverse_swap_markets(),
-        MarketType::LinearSwap => fetch_linear_swap_markets(),
-        MarketType::InverseFuture => fetch_inverse_future_markets(),
+        MarketType::InverseSwap => fetch_inverse_swap_markets(client),
+        MarketType::LinearSwap => fetch_linear_swap_markets(client),
+        MarketType::InverseFuture => fetch_inverse_future_markets(client),
         _ => panic!("Unsupported market_type: {}", market_type),
     }
 }
@@ -74,8 +82,8 @@ struct Response {
 }
 
 // See https://bybit-exchange.github.io/docs/inverse/#t-querysymbol
-fn fetch_markets_raw() -> Result<Vec<BybitMarket>> {
-    let txt = http_get("https://api.bybit.com/v2/public/symbols", None)?;
+fn fetch_markets_raw(client: &dyn HttpClient) -> Result<Vec<BybitMarket>> {
+    let txt = http_get(client, "https://api.bybit.com/v2/public/symbols", None)?;
     let resp = serde_json::from_str::<Response>(&txt)?;
     assert_eq!(resp.ret_code, 0);
     Ok(resp
@@ -85,8 +93,8 @@ fn fetch_markets_raw() -> Result<Vec<BybitMarket>> {
         .collect())
 }
 
-fn fetch_inverse_swap_symbols() -> Result<Vec<String>> {
-    let symbols = fetch_markets_raw()?
+fn fetch_inverse_swap_symbols(client: &dyn HttpClient) -> Result<Vec<String>> {
+    let symbols = fetch_markets_raw(client)?
         .into_iter()
         .filter(|m| m.name == m.alias && m.quote_currency == "USD")
         .map(|m| m.name)
@@ -94,8 +102,8 @@ fn fetch_inverse_swap_symbols() -> Result<Vec<String>> {
     Ok(symbols)
 }
 
-fn fetch_linear_swap_symbols() -> Result<Vec<String>> {
-    let symbols = fetch_markets_raw()?
+fn fetch_linear_swap_symbols(client: &dyn HttpClient) -> Result<Vec<String>> {
+    let symbols = fetch_markets_raw(client)?
         .into_iter()
         .filter(|m| m.name == m.alias && m.quote_currency == "USDT")
         .map(|m| m.name)
@@ -103,8 +111,8 @@ fn fetch_linear_swap_symbols() -> Result<Vec<String>> {
     Ok(symbols)
 }
 
-fn fetch_inverse_future_symbols() -> Result<Vec<String>> {
-    let symbols = fetch_markets_raw()?
+fn fetch_inverse_future_symbols(client: &dyn HttpClient) -> Result<Vec<String>> {
+    let symbols = fetch_markets_raw(client)?
         .into_iter()
         .filter(|m| {
             m.quote_currency == "USD" && (&m.name[(m.name.len() - 2)..]).parse::<i64>().is_ok()
@@ -147,6 +155,11 @@ fn to_market(raw_market: &BybitMarket) -> Market {
     } else {
         None
     };
+    let status = match raw_market.status.as_str() {
+        "Trading" => MarketStatus::Trading,
+        "Closed" => MarketStatus::Delisted,
+        _ => MarketStatus::Halted,
+    };
     Market {
         exchange: "bybit".to_string(),
         market_type: if raw_market.name == raw_market.alias {
@@ -171,7 +184,8 @@ fn to_market(raw_market: &BybitMarket) -> Market {
         } else {
             Some(raw_market.base_currency.to_string())
         },
-        active: raw_market.status == "Trading",
+        active: status == MarketStatus::Trading,
+        status,
         margin: true,
         fees: Fees {
             maker: raw_market.maker_fee.parse::<f64>().unwrap(),
@@ -195,8 +209,8 @@ fn to_market(raw_market: &BybitMarket) -> Market {
     }
 }
 
-fn fetch_inverse_swap_markets() -> Result<Vec<Market>> {
-    let markets = fetch_markets_raw()?
+fn fetch_inverse_swap_markets(client: &dyn HttpClient) -> Result<Vec<Market>> {
+    let markets = fetch_markets_raw(client)?
         .into_iter()
         .filter(|m| m.name == m.alias && m.quote_currency == "USD")
         .map(|m| to_market(&m))
@@ -204,8 +218,8 @@ fn fetch_inverse_swap_markets() -> Result<Vec<Market>> {
     Ok(markets)
 }
 
-fn fetch_linear_swap_markets() -> Result<Vec<Market>> {
-    let markets = fetch_markets_raw()?
+fn fetch_linear_swap_markets(client: &dyn HttpClient) -> Result<Vec<Market>> {
+    let markets = fetch_markets_raw(client)?
         .into_iter()
         .filter(|m| m.name == m.alias && m.quote_currency == "USDT")
         .map(|m| to_market(&m))
@@ -213,8 +227,8 @@ fn fetch_linear_swap_markets() -> Result<Vec<Market>> {
     Ok(markets)
 }
 
-fn fetch_inverse_future_markets() -> Result<Vec<Market>> {
-    let markets = fetch_markets_raw()?
+fn fetch_inverse_future_markets(client: &dyn HttpClient) -> Result<Vec<Market>> {
+    let markets = fetch_markets_raw(client)?
         .into_iter()
         .filter(|m| {
             m.quote_currency == "USD" && (&m.name[(m.name.len() - 2)..]).parse::<i64>().is_ok()
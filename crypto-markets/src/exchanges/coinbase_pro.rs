@@ -1,18 +1,26 @@
 use super::utils::http_get;
-use crate::{error::Result, Fees, Market, MarketType, Precision, QuantityLimit};
+use crate::{
+    error::Result, Fees, HttpClient, Market, MarketStatus, MarketType, Precision, QuantityLimit,
+};
 
 use serde::{Deserialize, Serialize};
 
-pub(crate) fn fetch_symbols(market_type: MarketType) -> Result<Vec<String>> {
+pub(crate) fn fetch_symbols(
+    client: &dyn HttpClient,
+    market_type: MarketType,
+) -> Result<Vec<String>> {
     match market_type {
-        MarketType::Spot => fetch_spot_symbols(),
+        MarketType::Spot => fetch_spot_symbols(client),
         _ => panic!("Unsupported market_type: {}", market_type),
     }
 }
 
-pub(crate) fn fetch_markets(market_type: MarketType) -> Result<Vec<Market>> {
+pub(crate) fn fetch_markets(
+    client: &dyn HttpClient,
+    market_type: MarketType,
+) -> Result<Vec<Market>> {
     match market_type {
-        MarketType::Spot => fetch_spot_markets(),
+        MarketType::Spot => fetch_spot_markets(client),
         _ => panic!("Unsupported market_type: {}", market_type),
     }
 }
@@ -39,14 +47,14 @@ struct SpotMarket {
 }
 
 // see <https://docs.pro.coinbase.com/#products>
-fn fetch_spot_markets_raw() -> Result<Vec<SpotMarket>> {
-    let txt = http_get("https://api.pro.coinbase.com/products", None)?;
+fn fetch_spot_markets_raw(client: &dyn HttpClient) -> Result<Vec<SpotMarket>> {
+    let txt = http_get(client, "https://api.pro.coinbase.com/products", None)?;
     let markets = serde_json::from_str::<Vec<SpotMarket>>(&txt)?;
     Ok(markets)
 }
 
-fn fetch_spot_symbols() -> Result<Vec<String>> {
-    let symbols = fetch_spot_markets_raw()?
+fn fetch_spot_symbols(client: &dyn HttpClient) -> Result<Vec<String>> {
+    let symbols = fetch_spot_markets_raw(client)?
         .into_iter()
         .filter(|m| !m.trading_disabled && m.status == "online" && !m.cancel_only)
         .map(|m| m.id)
@@ -54,8 +62,8 @@ fn fetch_spot_symbols() -> Result<Vec<String>> {
     Ok(symbols)
 }
 
-fn fetch_spot_markets() -> Result<Vec<Market>> {
-    let markets = fetch_spot_markets_raw()?
+fn fetch_spot_markets(client: &dyn HttpClient) -> Result<Vec<Market>> {
+    let markets = fetch_spot_markets_raw(client)?
         .into_iter()
         .map(|m| {
             let info = serde_json::to_value(&m)
@@ -68,6 +76,11 @@ fn fetch_spot_markets() -> Result<Vec<Market>> {
                 let v: Vec<&str> = pair.split('/').collect();
                 (v[0].to_string(), v[1].to_string())
             };
+            let status = match m.status.as_str() {
+                "online" if !m.trading_disabled && !m.cancel_only => MarketStatus::Trading,
+                "delisted" => MarketStatus::Delisted,
+                _ => MarketStatus::Halted,
+            };
             Market {
                 exchange: "coinbase_pro".to_string(),
                 market_type: MarketType::Spot,
@@ -78,7 +91,8 @@ fn fetch_spot_markets() -> Result<Vec<Market>> {
                 base,
                 quote,
                 settle: None,
-                active: !m.trading_disabled && m.status == "online" && !m.cancel_only,
+                active: status == MarketStatus::Trading,
+                status,
                 margin: m.margin_enabled,
                 // // see https://pro.coinbase.com/fees, https://pro.coinbase.com/orders/fees
                 fees: Fees {
@@ -1,21 +1,27 @@
 mod utils;
 
-use crate::{error::Result, Market, MarketType};
+use crate::{error::Result, HttpClient, Market, MarketType};
 
-pub(crate) fn fetch_symbols(market_type: MarketType) -> Result<Vec<String>> {
+pub(crate) fn fetch_symbols(
+    client: &dyn HttpClient,
+    market_type: MarketType,
+) -> Result<Vec<String>> {
     match market_type {
-        MarketType::InverseFuture => utils::fetch_inverse_future_symbols(),
-        MarketType::InverseSwap => utils::fetch_inverse_swap_symbols(),
-        MarketType::EuropeanOption => utils::fetch_option_symbols(),
+        MarketType::InverseFuture => utils::fetch_inverse_future_symbols(client),
+        MarketType::InverseSwap => utils::fetch_inverse_swap_symbols(client),
+        MarketType::EuropeanOption => utils::fetch_option_symbols(client),
         _ => panic!("Unsupported market_type: {}", market_type),
     }
 }
 
-pub(crate) fn fetch_markets(market_type: MarketType) -> Result<Vec<Market>> {
+pub(crate) fn fetch_markets(
+    client: &dyn HttpClient,
+    market_type: MarketType,
+) -> Result<Vec<Market>> {
     match market_type {
-        MarketType::InverseFuture => utils::fetch_inverse_future_markets(),
-        MarketType::InverseSwap => utils::fetch_inverse_swap_markets(),
-        MarketType::EuropeanOption => utils::fetch_option_markets(),
+        MarketType::InverseFuture => utils::fetch_inverse_future_markets(client),
+        MarketType::InverseSwap => utils::fetch_inverse_swap_markets(client),
+        MarketType::EuropeanOption => utils::fetch_option_markets(client),
         _ => panic!("Unsupported market_type: {}", market_type),
     }
 }
@@ -1,8 +1,9 @@
 use super::super::utils::http_get;
+use crate::HttpClient;
 use crate::{
     error::{Error, Result},
     market::{Fees, Precision, QuantityLimit},
-    Market,
+    Market, MarketStatus,
 };
 
 use crypto_market_type::MarketType;
@@ -19,8 +20,8 @@ fn check_error_in_body(body: String) -> Result<String> {
     }
 }
 
-pub(super) fn deribit_http_get(url: &str) -> Result<String> {
-    let ret = http_get(url, None);
+pub(super) fn deribit_http_get(client: &dyn HttpClient, url: &str) -> Result<String> {
+    let ret = http_get(client, url, None);
     match ret {
         Ok(body) => check_error_in_body(body),
         Err(_) => ret,
@@ -75,20 +76,24 @@ struct Instrument {
 /// `kind`, available values are `future` and `option`.
 ///
 /// Example: <https://www.deribit.com/api/v2/public/get_instruments?currency=BTC&kind=future>
-fn fetch_instruments(currency: &str, kind: &str) -> Result<Vec<Instrument>> {
+fn fetch_instruments(
+    client: &dyn HttpClient,
+    currency: &str,
+    kind: &str,
+) -> Result<Vec<Instrument>> {
     let url = format!(
         "https://www.deribit.com/api/v2/public/get_instruments?currency={}&kind={}",
         currency, kind
     );
-    let txt = deribit_http_get(&url)?;
+    let txt = deribit_http_get(client, &url)?;
     let resp = serde_json::from_str::<DeribitResponse<Instrument>>(&txt)?;
     Ok(resp.result)
 }
 
-fn fetch_raw_markets(kind: &str) -> Result<Vec<Instrument>> {
+fn fetch_raw_markets(client: &dyn HttpClient, kind: &str) -> Result<Vec<Instrument>> {
     let mut all_markets: Vec<Instrument> = Vec::new();
 
-    let result = fetch_instruments("BTC", kind);
+    let result = fetch_instruments(client, "BTC", kind);
     match result {
         Ok(mut instruments) => {
             all_markets.append(&mut instruments);
@@ -98,7 +103,7 @@ fn fetch_raw_markets(kind: &str) -> Result<Vec<Instrument>> {
         }
     }
 
-    let result = fetch_instruments("ETH", kind);
+    let result = fetch_instruments(client, "ETH", kind);
     match result {
         Ok(mut instruments) => {
             all_markets.append(&mut instruments);
@@ -111,14 +116,14 @@ fn fetch_raw_markets(kind: &str) -> Result<Vec<Instrument>> {
     Ok(all_markets.into_iter().filter(|x| x.is_active).collect())
 }
 
-fn fetch_symbols(kind: &str) -> Result<Vec<String>> {
-    let all_markets = fetch_raw_markets(kind)?;
+fn fetch_symbols(client: &dyn HttpClient, kind: &str) -> Result<Vec<String>> {
+    let all_markets = fetch_raw_markets(client, kind)?;
     let all_symbols: Vec<String> = all_markets.into_iter().map(|x| x.instrument_name).collect();
     Ok(all_symbols)
 }
 
-pub(super) fn fetch_inverse_future_symbols() -> Result<Vec<String>> {
-    let result = fetch_symbols("future");
+pub(super) fn fetch_inverse_future_symbols(client: &dyn HttpClient) -> Result<Vec<String>> {
+    let result = fetch_symbols(client, "future");
     match result {
         Ok(symbols) => Ok(symbols
             .into_iter()
@@ -128,8 +133,8 @@ pub(super) fn fetch_inverse_future_symbols() -> Result<Vec<String>> {
     }
 }
 
-pub(super) fn fetch_inverse_swap_symbols() -> Result<Vec<String>> {
-    let result = fetch_symbols("future");
+pub(super) fn fetch_inverse_swap_symbols(client: &dyn HttpClient) -> Result<Vec<String>> {
+    let result = fetch_symbols(client, "future");
     match result {
         Ok(symbols) => Ok(symbols
             .into_iter()
@@ -139,8 +144,8 @@ pub(super) fn fetch_inverse_swap_symbols() -> Result<Vec<String>> {
     }
 }
 
-pub(super) fn fetch_option_symbols() -> Result<Vec<String>> {
-    fetch_symbols("option")
+pub(super) fn fetch_option_symbols(client: &dyn HttpClient) -> Result<Vec<String>> {
+    fetch_symbols(client, "option")
 }
 
 fn to_market(raw_market: &Instrument) -> Market {
@@ -158,6 +163,11 @@ fn to_market(raw_market: &Instrument) -> Market {
         let v: Vec<&str> = pair.split('/').collect();
         (v[0].to_string(), v[1].to_string())
     };
+    let status = if raw_market.is_active {
+        MarketStatus::Trading
+    } else {
+        MarketStatus::Delisted
+    };
     Market {
         exchange: "deribit".to_string(),
         market_type,
@@ -168,7 +178,8 @@ fn to_market(raw_market: &Instrument) -> Market {
         base: base.clone(),
         quote,
         settle: Some(base),
-        active: raw_market.is_active,
+        active: status == MarketStatus::Trading,
+        status,
         margin: true,
         fees: Fees {
             maker: raw_market.maker_commission,
@@ -196,8 +207,8 @@ fn to_market(raw_market: &Instrument) -> Market {
     }
 }
 
-pub(super) fn fetch_inverse_future_markets() -> Result<Vec<Market>> {
-    let raw_markets = fetch_raw_markets("future")?;
+pub(super) fn fetch_inverse_future_markets(client: &dyn HttpClient) -> Result<Vec<Market>> {
+    let raw_markets = fetch_raw_markets(client, "future")?;
     let markets: Vec<Market> = raw_markets
         .into_iter()
         .filter(|x| !x.instrument_name.ends_with("-PERPETUAL"))
@@ -206,8 +217,8 @@ pub(super) fn fetch_inverse_future_markets() -> Result<Vec<Market>> {
     Ok(markets)
 }
 
-pub(super) fn fetch_inverse_swap_markets() -> Result<Vec<Market>> {
-    let raw_markets = fetch_raw_markets("future")?;
+pub(super) fn fetch_inverse_swap_markets(client: &dyn HttpClient) -> Result<Vec<Market>> {
+    let raw_markets = fetch_raw_markets(client, "future")?;
     let markets: Vec<Market> = raw_markets
         .into_iter()
         .filter(|x| x.instrument_name.ends_with("-PERPETUAL"))
@@ -216,8 +227,8 @@ pub(super) fn fetch_inverse_swap_markets() -> Result<Vec<Market>> {
     Ok(markets)
 }
 
-pub(super) fn fetch_option_markets() -> Result<Vec<Market>> {
-    let raw_markets = fetch_raw_markets("option")?;
+pub(super) fn fetch_option_markets(client: &dyn HttpClient) -> Result<Vec<Market>> {
+    let raw_markets = fetch_raw_markets(client, "option")?;
     let markets: Vec<Market> = raw_markets.into_iter().map(|x| to_market(&x)).collect();
     Ok(markets)
 }
@@ -1,7 +1,7 @@
 use std::collections::HashMap;
 
 use super::super::utils::http_get;
-use crate::{error::Result, Fees, Market, Precision, QuantityLimit};
+use crate::{error::Result, Fees, HttpClient, Market, MarketStatus, Precision, QuantityLimit};
 
 use crypto_market_type::MarketType;
 use serde::{Deserialize, Serialize};
@@ -32,8 +32,8 @@ struct MarketsResponse {
 }
 
 // See https://docs.dydx.exchange/#get-markets
-fn fetch_markets_raw() -> Result<Vec<PerpetualMarket>> {
-    let txt = http_get(format!("{}/v3/markets", BASE_URL).as_str(), None)?;
+fn fetch_markets_raw(client: &dyn HttpClient) -> Result<Vec<PerpetualMarket>> {
+    let txt = http_get(client, format!("{}/v3/markets", BASE_URL).as_str(), None)?;
     let resp = serde_json::from_str::<MarketsResponse>(&txt)?;
     Ok(resp
         .markets
@@ -43,8 +43,8 @@ fn fetch_markets_raw() -> Result<Vec<PerpetualMarket>> {
         .collect::<Vec<PerpetualMarket>>())
 }
 
-pub(super) fn fetch_linear_swap_symbols() -> Result<Vec<String>> {
-    let markets = fetch_markets_raw()?;
+pub(super) fn fetch_linear_swap_symbols(client: &dyn HttpClient) -> Result<Vec<String>> {
+    let markets = fetch_markets_raw(client)?;
     let symbols = markets
         .into_iter()
         .map(|m| m.market)
@@ -52,8 +52,8 @@ pub(super) fn fetch_linear_swap_symbols() -> Result<Vec<String>> {
     Ok(symbols)
 }
 
-pub(super) fn fetch_linear_swap_markets() -> Result<Vec<Market>> {
-    let markets = fetch_markets_raw()?
+pub(super) fn fetch_linear_swap_markets(client: &dyn HttpClient) -> Result<Vec<Market>> {
+    let markets = fetch_markets_raw(client)?
         .into_iter()
         .map(|m| {
             let info = serde_json::to_value(&m)
@@ -66,6 +66,11 @@ pub(super) fn fetch_linear_swap_markets() -> Result<Vec<Market>> {
                 let v: Vec<&str> = pair.split('/').collect();
                 (v[0].to_string(), v[1].to_string())
             };
+            let status = if m.status == "ONLINE" {
+                MarketStatus::Trading
+            } else {
+                MarketStatus::Halted
+            };
             Market {
                 exchange: "dydx".to_string(),
                 market_type: MarketType::LinearSwap,
@@ -76,7 +81,8 @@ pub(super) fn fetch_linear_swap_markets() -> Result<Vec<Market>> {
                 base,
                 quote: quote.clone(),
                 settle: Some(quote),
-                active: m.status == "ONLINE",
+                active: status == MarketStatus::Trading,
+                status,
                 margin: true,
                 // see https://trade.dydx.exchange/portfolio/fees
                 fees: Fees {
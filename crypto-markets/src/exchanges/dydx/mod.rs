@@ -1,17 +1,23 @@
 mod dydx_swap;
 
-use crate::{error::Result, Market, MarketType};
+use crate::{error::Result, HttpClient, Market, MarketType};
 
-pub(crate) fn fetch_symbols(market_type: MarketType) -> Result<Vec<String>> {
+pub(crate) fn fetch_symbols(
+    client: &dyn HttpClient,
+    market_type: MarketType,
+) -> Result<Vec<String>> {
     match market_type {
-        MarketType::LinearSwap => dydx_swap::fetch_linear_swap_symbols(),
+        MarketType::LinearSwap => dydx_swap::fetch_linear_swap_symbols(client),
         _ => panic!("dydX does NOT have the {} market", market_type),
     }
 }
 
-pub(crate) fn fetch_markets(market_type: MarketType) -> Result<Vec<Market>> {
+pub(crate) fn fetch_markets(
+    client: &dyn HttpClient,
+    market_type: MarketType,
+) -> Result<Vec<Market>> {
     match market_type {
-        MarketType::LinearSwap => dydx_swap::fetch_linear_swap_markets(),
+        MarketType::LinearSwap => dydx_swap::fetch_linear_swap_markets(client),
         _ => panic!("dydX does NOT have the {} market", market_type),
     }
 }
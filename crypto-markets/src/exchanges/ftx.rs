@@ -1,31 +1,37 @@
 use std::collections::HashMap;
 
 use super::utils::http_get;
-use crate::{error::Result, Fees, Market, MarketType, Precision};
+use crate::{error::Result, Fees, HttpClient, Market, MarketStatus, MarketType, Precision};
 
 use chrono::prelude::*;
 use chrono::DateTime;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
-pub(crate) fn fetch_symbols(market_type: MarketType) -> Result<Vec<String>> {
+pub(crate) fn fetch_symbols(
+    client: &dyn HttpClient,
+    market_type: MarketType,
+) -> Result<Vec<String>> {
     match market_type {
-        MarketType::Spot => fetch_spot_symbols(),
-        MarketType::LinearSwap => fetch_linear_swap_symbols(),
-        MarketType::LinearFuture => fetch_linear_future_symbols(),
-        MarketType::Move => fetch_move_symbols(),
-        MarketType::BVOL => fetch_bvol_symbols(),
+        MarketType::Spot => fetch_spot_symbols(client),
+        MarketType::LinearSwap => fetch_linear_swap_symbols(client),
+        MarketType::LinearFuture => fetch_linear_future_symbols(client),
+        MarketType::Move => fetch_move_symbols(client),
+        MarketType::BVOL => fetch_bvol_symbols(client),
         _ => panic!("Unsupported market_type: {}", market_type),
     }
 }
 
-pub(crate) fn fetch_markets(market_type: MarketType) -> Result<Vec<Market>> {
+pub(crate) fn fetch_markets(
+    client: &dyn HttpClient,
+    market_type: MarketType,
+) -> Result<Vec<Market>> {
     match market_type {
-        MarketType::Spot => fetch_spot_markets(),
-        MarketType::LinearSwap => fetch_linear_swap_markets(),
-        MarketType::LinearFuture => fetch_linear_future_markets(),
-        MarketType::Move => fetch_move_markets(),
-        MarketType::BVOL => fetch_bvol_markets(),
+        MarketType::Spot => fetch_spot_markets(client),
+        MarketType::LinearSwap => fetch_linear_swap_markets(client),
+        MarketType::LinearFuture => fetch_linear_future_markets(client),
+        MarketType::Move => fetch_move_markets(client),
+        MarketType::BVOL => fetch_bvol_markets(client),
         _ => panic!("Unsupported market_type: {}", market_type),
     }
 }
@@ -54,16 +60,16 @@ struct Response {
     result: Vec<FtxMarket>,
 }
 
-fn fetch_markets_raw() -> Result<Vec<FtxMarket>> {
-    let txt = http_get("https://ftx.com/api/markets", None)?;
+fn fetch_markets_raw(client: &dyn HttpClient) -> Result<Vec<FtxMarket>> {
+    let txt = http_get(client, "https://ftx.com/api/markets", None)?;
     let resp = serde_json::from_str::<Response>(&txt)?;
     assert!(resp.success);
     let valid: Vec<FtxMarket> = resp.result.into_iter().filter(|x| x.enabled).collect();
     Ok(valid)
 }
 
-fn fetch_spot_symbols() -> Result<Vec<String>> {
-    let markets = fetch_markets_raw()?;
+fn fetch_spot_symbols(client: &dyn HttpClient) -> Result<Vec<String>> {
+    let markets = fetch_markets_raw(client)?;
     let symbols: Vec<String> = markets
         .into_iter()
         .filter(|x| x.type_ == "spot")
@@ -72,8 +78,8 @@ fn fetch_spot_symbols() -> Result<Vec<String>> {
     Ok(symbols)
 }
 
-fn fetch_linear_swap_symbols() -> Result<Vec<String>> {
-    let markets = fetch_markets_raw()?;
+fn fetch_linear_swap_symbols(client: &dyn HttpClient) -> Result<Vec<String>> {
+    let markets = fetch_markets_raw(client)?;
     let symbols: Vec<String> = markets
         .into_iter()
         .filter(|x| x.type_ == "future" && x.name.ends_with("-PERP"))
@@ -82,8 +88,8 @@ fn fetch_linear_swap_symbols() -> Result<Vec<String>> {
     Ok(symbols)
 }
 
-fn fetch_linear_future_symbols() -> Result<Vec<String>> {
-    let markets = fetch_markets_raw()?;
+fn fetch_linear_future_symbols(client: &dyn HttpClient) -> Result<Vec<String>> {
+    let markets = fetch_markets_raw(client)?;
     let symbols: Vec<String> = markets
         .into_iter()
         .filter(|x| {
@@ -98,8 +104,8 @@ fn fetch_linear_future_symbols() -> Result<Vec<String>> {
     Ok(symbols)
 }
 
-fn fetch_move_symbols() -> Result<Vec<String>> {
-    let markets = fetch_markets_raw()?;
+fn fetch_move_symbols(client: &dyn HttpClient) -> Result<Vec<String>> {
+    let markets = fetch_markets_raw(client)?;
     let symbols: Vec<String> = markets
         .into_iter()
         .filter(|x| x.type_ == "future" && x.name.contains("-MOVE-"))
@@ -108,8 +114,8 @@ fn fetch_move_symbols() -> Result<Vec<String>> {
     Ok(symbols)
 }
 
-fn fetch_bvol_symbols() -> Result<Vec<String>> {
-    let markets = fetch_markets_raw()?;
+fn fetch_bvol_symbols(client: &dyn HttpClient) -> Result<Vec<String>> {
+    let markets = fetch_markets_raw(client)?;
     let symbols: Vec<String> = markets
         .into_iter()
         .filter(|x| x.type_ == "spot" && x.name.contains("BVOL/"))
@@ -168,6 +174,11 @@ fn to_market(raw_market: &FtxMarket) -> Market {
     } else {
         None
     };
+    let status = if raw_market.enabled {
+        MarketStatus::Trading
+    } else {
+        MarketStatus::Halted
+    };
     Market {
         exchange: "ftx".to_string(),
         market_type,
@@ -194,7 +205,8 @@ fn to_market(raw_market: &FtxMarket) -> Market {
         } else {
             Some("USD".to_string())
         },
-        active: raw_market.enabled,
+        active: status == MarketStatus::Trading,
+        status,
         margin: true,
         // see https://help.ftx.com/hc/en-us/articles/360024479432-Fees
         fees: Fees {
@@ -220,8 +232,8 @@ fn to_market(raw_market: &FtxMarket) -> Market {
     }
 }
 
-fn fetch_spot_markets() -> Result<Vec<Market>> {
-    let markets: Vec<Market> = fetch_markets_raw()?
+fn fetch_spot_markets(client: &dyn HttpClient) -> Result<Vec<Market>> {
+    let markets: Vec<Market> = fetch_markets_raw(client)?
         .into_iter()
         .filter(|x| x.type_ == "spot")
         .map(|x| to_market(&x))
@@ -229,8 +241,8 @@ fn fetch_spot_markets() -> Result<Vec<Market>> {
     Ok(markets)
 }
 
-fn fetch_linear_swap_markets() -> Result<Vec<Market>> {
-    let markets = fetch_markets_raw()?;
+fn fetch_linear_swap_markets(client: &dyn HttpClient) -> Result<Vec<Market>> {
+    let markets = fetch_markets_raw(client)?;
     let symbols: Vec<Market> = markets
         .into_iter()
         .filter(|x| x.type_ == "future" && x.name.ends_with("-PERP"))
@@ -239,8 +251,8 @@ fn fetch_linear_swap_markets() -> Result<Vec<Market>> {
     Ok(symbols)
 }
 
-fn fetch_linear_future_markets() -> Result<Vec<Market>> {
-    let markets: Vec<Market> = fetch_markets_raw()?
+fn fetch_linear_future_markets(client: &dyn HttpClient) -> Result<Vec<Market>> {
+    let markets: Vec<Market> = fetch_markets_raw(client)?
         .into_iter()
         .filter(|x| {
             x.type_ == "future"
@@ -254,8 +266,8 @@ fn fetch_linear_future_markets() -> Result<Vec<Market>> {
     Ok(markets)
 }
 
-fn fetch_move_markets() -> Result<Vec<Market>> {
-    let markets: Vec<Market> = fetch_markets_raw()?
+fn fetch_move_markets(client: &dyn HttpClient) -> Result<Vec<Market>> {
+    let markets: Vec<Market> = fetch_markets_raw(client)?
         .into_iter()
         .filter(|x| x.type_ == "future" && x.name.contains("-MOVE-"))
         .map(|x| to_market(&x))
@@ -263,8 +275,8 @@ fn fetch_move_markets() -> Result<Vec<Market>> {
     Ok(markets)
 }
 
-fn fetch_bvol_markets() -> Result<Vec<Market>> {
-    let markets: Vec<Market> = fetch_markets_raw()?
+fn fetch_bvol_markets(client: &dyn HttpClient) -> Result<Vec<Market>> {
+    let markets: Vec<Market> = fetch_markets_raw(client)?
         .into_iter()
         .filter(|x| x.type_ == "spot" && x.name.contains("BVOL/"))
         .map(|x| to_market(&x))
@@ -1,7 +1,7 @@
 use std::collections::HashMap;
 
 use super::super::utils::http_get;
-use crate::{error::Result, Fees, Market, Precision, QuantityLimit};
+use crate::{error::Result, Fees, HttpClient, Market, MarketStatus, Precision, QuantityLimit};
 
 use crypto_market_type::MarketType;
 use serde::{Deserialize, Serialize};
@@ -25,8 +25,12 @@ struct SpotMarket {
 }
 
 // See https://www.gateio.pro/docs/apiv4/zh_CN/index.html#611e43ef81
-fn fetch_spot_markets_raw() -> Result<Vec<SpotMarket>> {
-    let txt = http_get("https://api.gateio.ws/api/v4/spot/currency_pairs", None)?;
+fn fetch_spot_markets_raw(client: &dyn HttpClient) -> Result<Vec<SpotMarket>> {
+    let txt = http_get(
+        client,
+        "https://api.gateio.ws/api/v4/spot/currency_pairs",
+        None,
+    )?;
     let markets = serde_json::from_str::<Vec<SpotMarket>>(&txt)?;
     Ok(markets
         .into_iter()
@@ -34,14 +38,14 @@ fn fetch_spot_markets_raw() -> Result<Vec<SpotMarket>> {
         .collect::<Vec<SpotMarket>>())
 }
 
-pub(super) fn fetch_spot_symbols() -> Result<Vec<String>> {
-    let markets = fetch_spot_markets_raw()?;
+pub(super) fn fetch_spot_symbols(client: &dyn HttpClient) -> Result<Vec<String>> {
+    let markets = fetch_spot_markets_raw(client)?;
     let symbols: Vec<String> = markets.into_iter().map(|m| m.id).collect();
     Ok(symbols)
 }
 
-pub(super) fn fetch_spot_markets() -> Result<Vec<Market>> {
-    let markets: Vec<Market> = fetch_spot_markets_raw()?
+pub(super) fn fetch_spot_markets(client: &dyn HttpClient) -> Result<Vec<Market>> {
+    let markets: Vec<Market> = fetch_spot_markets_raw(client)?
         .into_iter()
         .map(|raw_market| {
             let info = serde_json::to_value(&raw_market)
@@ -55,6 +59,11 @@ pub(super) fn fetch_spot_markets() -> Result<Vec<Market>> {
                 (v[0].to_string(), v[1].to_string())
             };
 
+            let status = if raw_market.trade_status == "tradable" {
+                MarketStatus::Trading
+            } else {
+                MarketStatus::Halted
+            };
             Market {
                 exchange: "gate".to_string(),
                 market_type: MarketType::Spot,
@@ -65,7 +74,8 @@ pub(super) fn fetch_spot_markets() -> Result<Vec<Market>> {
                 base,
                 quote,
                 settle: None,
-                active: raw_market.trade_status == "tradable",
+                active: status == MarketStatus::Trading,
+                status,
                 margin: false,
                 fees: Fees {
                     maker: raw_market.fee.parse::<f64>().unwrap() / 100_f64,
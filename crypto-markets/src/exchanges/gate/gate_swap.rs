@@ -1,7 +1,7 @@
 use std::collections::HashMap;
 
 use super::super::utils::http_get;
-use crate::{error::Result, Fees, Market, Precision, QuantityLimit};
+use crate::{error::Result, Fees, HttpClient, Market, MarketStatus, Precision, QuantityLimit};
 
 use crypto_market_type::MarketType;
 use serde::{Deserialize, Serialize};
@@ -32,8 +32,9 @@ struct SwapMarket {
 }
 
 // See https://www.gateio.pro/docs/apiv4/zh_CN/index.html#595cd9fe3c
-fn fetch_swap_markets_raw(settle: &str) -> Result<Vec<SwapMarket>> {
+fn fetch_swap_markets_raw(client: &dyn HttpClient, settle: &str) -> Result<Vec<SwapMarket>> {
     let txt = http_get(
+        client,
         format!("https://api.gateio.ws/api/v4/futures/{}/contracts", settle).as_str(),
         None,
     )?;
@@ -44,16 +45,16 @@ fn fetch_swap_markets_raw(settle: &str) -> Result<Vec<SwapMarket>> {
         .collect::<Vec<SwapMarket>>())
 }
 
-pub(super) fn fetch_inverse_swap_symbols() -> Result<Vec<String>> {
-    let symbols = fetch_swap_markets_raw("btc")?
+pub(super) fn fetch_inverse_swap_symbols(client: &dyn HttpClient) -> Result<Vec<String>> {
+    let symbols = fetch_swap_markets_raw(client, "btc")?
         .into_iter()
         .map(|m| m.name)
         .collect::<Vec<String>>();
     Ok(symbols)
 }
 
-pub(super) fn fetch_linear_swap_symbols() -> Result<Vec<String>> {
-    let symbols = fetch_swap_markets_raw("usdt")?
+pub(super) fn fetch_linear_swap_symbols(client: &dyn HttpClient) -> Result<Vec<String>> {
+    let symbols = fetch_swap_markets_raw(client, "usdt")?
         .into_iter()
         .map(|m| m.name)
         .collect::<Vec<String>>();
@@ -84,6 +85,11 @@ fn to_market(raw_market: &SwapMarket) -> Market {
     }
     assert!(quanto_multiplier > 0.0);
 
+    let status = if raw_market.in_delisting {
+        MarketStatus::Delisted
+    } else {
+        MarketStatus::Trading
+    };
     Market {
         exchange: "gate".to_string(),
         market_type,
@@ -102,7 +108,8 @@ fn to_market(raw_market: &SwapMarket) -> Market {
         } else {
             Some(quote)
         },
-        active: !raw_market.in_delisting,
+        active: status == MarketStatus::Trading,
+        status,
         margin: true,
         fees: Fees {
             maker: raw_market.maker_fee_rate.parse::<f64>().unwrap(),
@@ -126,16 +133,16 @@ fn to_market(raw_market: &SwapMarket) -> Market {
     }
 }
 
-pub(super) fn fetch_inverse_swap_markets() -> Result<Vec<Market>> {
-    let markets = fetch_swap_markets_raw("btc")?
+pub(super) fn fetch_inverse_swap_markets(client: &dyn HttpClient) -> Result<Vec<Market>> {
+    let markets = fetch_swap_markets_raw(client, "btc")?
         .into_iter()
         .map(|m| to_market(&m))
         .collect::<Vec<Market>>();
     Ok(markets)
 }
 
-pub(super) fn fetch_linear_swap_markets() -> Result<Vec<Market>> {
-    let markets = fetch_swap_markets_raw("usdt")?
+pub(super) fn fetch_linear_swap_markets(client: &dyn HttpClient) -> Result<Vec<Market>> {
+    let markets = fetch_swap_markets_raw(client, "usdt")?
         .into_iter()
         .map(|m| to_market(&m))
         .collect::<Vec<Market>>();
@@ -2,26 +2,32 @@ mod gate_future;
 mod gate_spot;
 mod gate_swap;
 
-use crate::{error::Result, Market, MarketType};
+use crate::{error::Result, HttpClient, Market, MarketType};
 
-pub(crate) fn fetch_symbols(market_type: MarketType) -> Result<Vec<String>> {
+pub(crate) fn fetch_symbols(
+    client: &dyn HttpClient,
+    market_type: MarketType,
+) -> Result<Vec<String>> {
     match market_type {
-        MarketType::Spot => gate_spot::fetch_spot_symbols(),
-        MarketType::InverseSwap => gate_swap::fetch_inverse_swap_symbols(),
-        MarketType::LinearSwap => gate_swap::fetch_linear_swap_symbols(),
-        MarketType::InverseFuture => gate_future::fetch_inverse_future_symbols(),
-        MarketType::LinearFuture => gate_future::fetch_linear_future_symbols(),
+        MarketType::Spot => gate_spot::fetch_spot_symbols(client),
+        MarketType::InverseSwap => gate_swap::fetch_inverse_swap_symbols(client),
+        MarketType::LinearSwap => gate_swap::fetch_linear_swap_symbols(client),
+        MarketType::InverseFuture => gate_future::fetch_inverse_future_symbols(client),
+        MarketType::LinearFuture => gate_future::fetch_linear_future_symbols(client),
         _ => panic!("Unsupported market_type: {}", market_type),
     }
 }
 
-pub(crate) fn fetch_markets(market_type: MarketType) -> Result<Vec<Market>> {
+pub(crate) fn fetch_markets(
+    client: &dyn HttpClient,
+    market_type: MarketType,
+) -> Result<Vec<Market>> {
     match market_type {
-        MarketType::Spot => gate_spot::fetch_spot_markets(),
-        MarketType::InverseSwap => gate_swap::fetch_inverse_swap_markets(),
-        MarketType::LinearSwap => gate_swap::fetch_linear_swap_markets(),
-        MarketType::InverseFuture => gate_future::fetch_inverse_future_markets(),
-        MarketType::LinearFuture => gate_future::fetch_linear_future_markets(),
+        MarketType::Spot => gate_spot::fetch_spot_markets(client),
+        MarketType::InverseSwap => gate_swap::fetch_inverse_swap_markets(client),
+        MarketType::LinearSwap => gate_swap::fetch_linear_swap_markets(client),
+        MarketType::InverseFuture => gate_future::fetch_inverse_future_markets(client),
+        MarketType::LinearFuture => gate_future::fetch_linear_future_markets(client),
         _ => panic!("Unsupported market_type: {}", market_type),
     }
 }
@@ -1,7 +1,8 @@
-use super::utils::huobi_http_get;
+use super::utils::{huobi_contract_status, huobi_http_get};
+use crate::HttpClient;
 use crate::{
     error::Result,
-    market::{Fees, Precision},
+    market::{Fees, MarketStatus, Precision},
     Market,
 };
 
@@ -36,8 +37,8 @@ struct Response {
 }
 
 // see <https://huobiapi.github.io/docs/dm/v1/en/#get-contract-info>
-fn fetch_future_markets_raw() -> Result<Vec<FutureMarket>> {
-    let txt = huobi_http_get("https://api.hbdm.com/api/v1/contract_contract_info")?;
+fn fetch_future_markets_raw(client: &dyn HttpClient) -> Result<Vec<FutureMarket>> {
+    let txt = huobi_http_get(client, "https://api.hbdm.com/api/v1/contract_contract_info")?;
     let resp = serde_json::from_str::<Response>(&txt)?;
     let result: Vec<FutureMarket> = resp
         .data
@@ -47,8 +48,8 @@ fn fetch_future_markets_raw() -> Result<Vec<FutureMarket>> {
     Ok(result)
 }
 
-pub(super) fn fetch_inverse_future_symbols() -> Result<Vec<String>> {
-    let symbols = fetch_future_markets_raw()?
+pub(super) fn fetch_inverse_future_symbols(client: &dyn HttpClient) -> Result<Vec<String>> {
+    let symbols = fetch_future_markets_raw(client)?
         .into_iter()
         .map(|m| {
             m.symbol.to_string()
@@ -64,8 +65,8 @@ pub(super) fn fetch_inverse_future_symbols() -> Result<Vec<String>> {
     Ok(symbols)
 }
 
-pub(super) fn fetch_inverse_future_markets() -> Result<Vec<Market>> {
-    let markets = fetch_future_markets_raw()?
+pub(super) fn fetch_inverse_future_markets(client: &dyn HttpClient) -> Result<Vec<Market>> {
+    let markets = fetch_future_markets_raw(client)?
         .into_iter()
         .map(|m| {
             let symbol = m.symbol.to_string()
@@ -81,6 +82,7 @@ pub(super) fn fetch_inverse_future_markets() -> Result<Vec<Market>> {
                 let v: Vec<&str> = pair.split('/').collect();
                 (v[0].to_string(), v[1].to_string())
             };
+            let status = huobi_contract_status(m.contract_status);
             Market {
                 exchange: "huobi".to_string(),
                 market_type: MarketType::InverseFuture,
@@ -91,7 +93,8 @@ pub(super) fn fetch_inverse_future_markets() -> Result<Vec<Market>> {
                 base: base.clone(),
                 quote,
                 settle: Some(base),
-                active: m.contract_status == 1,
+                active: status == MarketStatus::Trading,
+                status,
                 margin: true,
                 // see https://futures.huobi.com/en-us/contract/fee_rate/
                 fees: Fees {
@@ -1,7 +1,8 @@
-use super::utils::huobi_http_get;
+use super::utils::{huobi_contract_status, huobi_http_get};
+use crate::HttpClient;
 use crate::{
     error::Result,
-    market::{Fees, Precision},
+    market::{Fees, MarketStatus, Precision},
     Market,
 };
 
@@ -32,8 +33,11 @@ struct Response {
 }
 
 // see <https://huobiapi.github.io/docs/usdt_swap/v1/en/#general-query-swap-info>
-fn fetch_linear_swap_markets_raw() -> Result<Vec<LinearSwapMarket>> {
-    let txt = huobi_http_get("https://api.hbdm.com/linear-swap-api/v1/swap_contract_info")?;
+fn fetch_linear_swap_markets_raw(client: &dyn HttpClient) -> Result<Vec<LinearSwapMarket>> {
+    let txt = huobi_http_get(
+        client,
+        "https://api.hbdm.com/linear-swap-api/v1/swap_contract_info",
+    )?;
     let resp = serde_json::from_str::<Response>(&txt)?;
     let result: Vec<LinearSwapMarket> = resp
         .data
@@ -43,16 +47,16 @@ fn fetch_linear_swap_markets_raw() -> Result<Vec<LinearSwapMarket>> {
     Ok(result)
 }
 
-pub(super) fn fetch_linear_swap_symbols() -> Result<Vec<String>> {
-    let symbols = fetch_linear_swap_markets_raw()?
+pub(super) fn fetch_linear_swap_symbols(client: &dyn HttpClient) -> Result<Vec<String>> {
+    let symbols = fetch_linear_swap_markets_raw(client)?
         .into_iter()
         .map(|m| m.contract_code)
         .collect::<Vec<String>>();
     Ok(symbols)
 }
 
-pub(super) fn fetch_linear_swap_markets() -> Result<Vec<Market>> {
-    let markets = fetch_linear_swap_markets_raw()?
+pub(super) fn fetch_linear_swap_markets(client: &dyn HttpClient) -> Result<Vec<Market>> {
+    let markets = fetch_linear_swap_markets_raw(client)?
         .into_iter()
         .map(|m| {
             let info = serde_json::to_value(&m)
@@ -65,6 +69,7 @@ pub(super) fn fetch_linear_swap_markets() -> Result<Vec<Market>> {
                 let v: Vec<&str> = pair.split('/').collect();
                 (v[0].to_string(), v[1].to_string())
             };
+            let status = huobi_contract_status(m.contract_status);
             Market {
                 exchange: "huobi".to_string(),
                 market_type: MarketType::LinearSwap,
@@ -75,7 +80,8 @@ pub(super) fn fetch_linear_swap_markets() -> Result<Vec<Market>> {
                 base,
                 quote: quote.clone(),
                 settle: Some(quote),
-                active: m.contract_status == 1,
+                active: status == MarketStatus::Trading,
+                status,
                 margin: true,
                 // see https://futures.huobi.com/en-us/linear_swap/fee_rate/
                 fees: Fees {
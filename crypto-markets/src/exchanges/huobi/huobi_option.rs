@@ -1,5 +1,6 @@
 use super::utils::huobi_http_get;
 use crate::error::Result;
+use crate::HttpClient;
 
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
@@ -32,8 +33,11 @@ struct Response {
 }
 
 // see <https://huobiapi.github.io/docs/option/v1/en/#query-option-info>
-fn fetch_option_markets_raw() -> Result<Vec<OptionMarket>> {
-    let txt = huobi_http_get("https://api.hbdm.com/option-api/v1/option_contract_info")?;
+fn fetch_option_markets_raw(client: &dyn HttpClient) -> Result<Vec<OptionMarket>> {
+    let txt = huobi_http_get(
+        client,
+        "https://api.hbdm.com/option-api/v1/option_contract_info",
+    )?;
     let resp = serde_json::from_str::<Response>(&txt)?;
     let result: Vec<OptionMarket> = resp
         .data
@@ -43,8 +47,8 @@ fn fetch_option_markets_raw() -> Result<Vec<OptionMarket>> {
     Ok(result)
 }
 
-pub(super) fn fetch_option_symbols() -> Result<Vec<String>> {
-    let symbols = fetch_option_markets_raw()?
+pub(super) fn fetch_option_symbols(client: &dyn HttpClient) -> Result<Vec<String>> {
+    let symbols = fetch_option_markets_raw(client)?
         .into_iter()
         .filter(|m| m.contract_status == 1)
         .map(|m| m.contract_code)
@@ -1,8 +1,9 @@
 use super::utils::huobi_http_get;
+use crate::HttpClient;
 use crate::{
     error::Result,
     market::{Fees, Precision, QuantityLimit},
-    Market,
+    Market, MarketStatus,
 };
 
 use crypto_market_type::MarketType;
@@ -41,8 +42,8 @@ struct Response {
 }
 
 // see <https://huobiapi.github.io/docs/spot/v1/en/#get-all-supported-trading-symbol>
-fn fetch_spot_markets_raw() -> Result<Vec<SpotMarket>> {
-    let txt = huobi_http_get("https://api.huobi.pro/v1/common/symbols")?;
+fn fetch_spot_markets_raw(client: &dyn HttpClient) -> Result<Vec<SpotMarket>> {
+    let txt = huobi_http_get(client, "https://api.huobi.pro/v1/common/symbols")?;
     let resp = serde_json::from_str::<Response>(&txt)?;
     let result: Vec<SpotMarket> = resp
         .data
@@ -52,16 +53,16 @@ fn fetch_spot_markets_raw() -> Result<Vec<SpotMarket>> {
     Ok(result)
 }
 
-pub(super) fn fetch_spot_symbols() -> Result<Vec<String>> {
-    let symbols = fetch_spot_markets_raw()?
+pub(super) fn fetch_spot_symbols(client: &dyn HttpClient) -> Result<Vec<String>> {
+    let symbols = fetch_spot_markets_raw(client)?
         .into_iter()
         .map(|m| m.symbol)
         .collect::<Vec<String>>();
     Ok(symbols)
 }
 
-pub(super) fn fetch_spot_markets() -> Result<Vec<Market>> {
-    let markets = fetch_spot_markets_raw()?
+pub(super) fn fetch_spot_markets(client: &dyn HttpClient) -> Result<Vec<Market>> {
+    let markets = fetch_spot_markets_raw(client)?
         .into_iter()
         .map(|m| {
             let info = serde_json::to_value(&m)
@@ -74,6 +75,11 @@ pub(super) fn fetch_spot_markets() -> Result<Vec<Market>> {
                 let v: Vec<&str> = pair.split('/').collect();
                 (v[0].to_string(), v[1].to_string())
             };
+            let status = match m.state.as_str() {
+                "online" => MarketStatus::Trading,
+                "offline" => MarketStatus::Delisted,
+                _ => MarketStatus::Halted,
+            };
             Market {
                 exchange: "huobi".to_string(),
                 market_type: MarketType::Spot,
@@ -84,7 +90,8 @@ pub(super) fn fetch_spot_markets() -> Result<Vec<Market>> {
                 base,
                 quote,
                 settle: None,
-                active: m.state == "online",
+                active: status == MarketStatus::Trading,
+                status,
                 margin: true,
                 // see https://www.huobi.com/en-us/fee/
                 fees: Fees {
@@ -1,5 +1,6 @@
 use super::super::utils::http_get;
 use crate::error::{Error, Result};
+use crate::{HttpClient, MarketStatus};
 
 use serde_json::Value;
 use std::collections::HashMap;
@@ -22,10 +23,20 @@ fn check_status_in_body(resp: String) -> Result<String> {
     }
 }
 
-pub(super) fn huobi_http_get(url: &str) -> Result<String> {
-    let ret = http_get(url, None);
+pub(super) fn huobi_http_get(client: &dyn HttpClient, url: &str) -> Result<String> {
+    let ret = http_get(client, url, None);
     match ret {
         Ok(resp) => check_status_in_body(resp),
         Err(_) => ret,
     }
 }
+
+// see https://huobiapi.github.io/docs/dm/v1/en/#get-contract-info
+pub(super) fn huobi_contract_status(contract_status: i64) -> MarketStatus {
+    match contract_status {
+        1 => MarketStatus::Trading,
+        // Delisting, Settlement Completed, Delivered
+        0 | 7 | 8 => MarketStatus::Delisted,
+        _ => MarketStatus::Halted,
+    }
+}
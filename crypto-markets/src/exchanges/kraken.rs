@@ -1,24 +1,31 @@
 use std::collections::HashMap;
 
 use super::utils::http_get;
+use crate::HttpClient;
 use crate::{
     error::{Error, Result},
-    Fees, Market, MarketType, Precision, QuantityLimit,
+    Fees, Market, MarketStatus, MarketType, Precision, QuantityLimit,
 };
 
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
-pub(crate) fn fetch_symbols(market_type: MarketType) -> Result<Vec<String>> {
+pub(crate) fn fetch_symbols(
+    client: &dyn HttpClient,
+    market_type: MarketType,
+) -> Result<Vec<String>> {
     match market_type {
-        MarketType::Spot => fetch_spot_symbols(),
+        MarketType::Spot => fetch_spot_symbols(client),
         _ => panic!("Unsupported market_type: {}", market_type),
     }
 }
 
-pub(crate) fn fetch_markets(market_type: MarketType) -> Result<Vec<Market>> {
+pub(crate) fn fetch_markets(
+    client: &dyn HttpClient,
+    market_type: MarketType,
+) -> Result<Vec<Market>> {
     match market_type {
-        MarketType::Spot => fetch_spot_markets(),
+        MarketType::Spot => fetch_spot_markets(client),
         _ => panic!("Unsupported market_type: {}", market_type),
     }
 }
@@ -41,6 +48,9 @@ struct SpotMarket {
     margin_call: i64,
     margin_stop: i64,
     ordermin: String,
+    // online, cancel_only, post_only, limit_only or reduce_only, absent in older responses
+    #[serde(skip_serializing_if = "Option::is_none")]
+    status: Option<String>,
 }
 
 #[derive(Serialize, Deserialize)]
@@ -67,8 +77,8 @@ fn check_error_in_body(resp: String) -> Result<String> {
     }
 }
 
-pub(super) fn kraken_http_get(url: &str) -> Result<String> {
-    let ret = http_get(url, None);
+pub(super) fn kraken_http_get(client: &dyn HttpClient, url: &str) -> Result<String> {
+    let ret = http_get(client, url, None);
     match ret {
         Ok(resp) => check_error_in_body(resp),
         Err(_) => ret,
@@ -76,8 +86,8 @@ pub(super) fn kraken_http_get(url: &str) -> Result<String> {
 }
 
 // see <https://www.kraken.com/features/api#get-tradable-pairs>
-fn fetch_spot_markets_raw() -> Result<Vec<SpotMarket>> {
-    let txt = kraken_http_get("https://api.kraken.com/0/public/AssetPairs")?;
+fn fetch_spot_markets_raw(client: &dyn HttpClient) -> Result<Vec<SpotMarket>> {
+    let txt = kraken_http_get(client, "https://api.kraken.com/0/public/AssetPairs")?;
     let obj = serde_json::from_str::<HashMap<String, Value>>(&txt)?;
     let markets = obj
         .get("result")
@@ -91,16 +101,16 @@ fn fetch_spot_markets_raw() -> Result<Vec<SpotMarket>> {
     Ok(markets)
 }
 
-fn fetch_spot_symbols() -> Result<Vec<String>> {
-    let symbols = fetch_spot_markets_raw()?
+fn fetch_spot_symbols(client: &dyn HttpClient) -> Result<Vec<String>> {
+    let symbols = fetch_spot_markets_raw(client)?
         .into_iter()
         .filter_map(|m| m.wsname)
         .collect::<Vec<String>>();
     Ok(symbols)
 }
 
-fn fetch_spot_markets() -> Result<Vec<Market>> {
-    let markets = fetch_spot_markets_raw()?
+fn fetch_spot_markets(client: &dyn HttpClient) -> Result<Vec<Market>> {
+    let markets = fetch_spot_markets_raw(client)?
         .into_iter()
         .map(|m| {
             let info = serde_json::to_value(&m)
@@ -114,6 +124,10 @@ fn fetch_spot_markets() -> Result<Vec<Market>> {
                 let v: Vec<&str> = pair.split('/').collect();
                 (v[0].to_string(), v[1].to_string())
             };
+            let status = match m.status.as_deref() {
+                None | Some("online") => MarketStatus::Trading,
+                _ => MarketStatus::Halted,
+            };
             Market {
                 exchange: "kraken".to_string(),
                 market_type: MarketType::Spot,
@@ -124,7 +138,8 @@ fn fetch_spot_markets() -> Result<Vec<Market>> {
                 base,
                 quote,
                 settle: None,
-                active: true,
+                active: status == MarketStatus::Trading,
+                status,
                 margin: false,
                 // see https://support.kraken.com/hc/en-us/articles/360000526126-What-are-Maker-and-Taker-fees-
                 fees: Fees {
@@ -1,9 +1,10 @@
 use std::collections::HashMap;
 
 use super::super::utils::http_get;
+use crate::HttpClient;
 use crate::{
     error::{Error, Result},
-    Fees, Market, Precision, QuantityLimit,
+    Fees, Market, MarketStatus, Precision, QuantityLimit,
 };
 
 use crypto_market_type::MarketType;
@@ -40,8 +41,8 @@ struct Response {
 }
 
 // See https://docs.kucoin.com/#get-symbols-list
-fn fetch_spot_markets_raw() -> Result<Vec<SpotMarket>> {
-    let txt = http_get("https://api.kucoin.com/api/v1/symbols", None)?;
+fn fetch_spot_markets_raw(client: &dyn HttpClient) -> Result<Vec<SpotMarket>> {
+    let txt = http_get(client, "https://api.kucoin.com/api/v1/symbols", None)?;
     let resp = serde_json::from_str::<Response>(&txt)?;
     if resp.code != "200000" {
         Err(Error(txt))
@@ -55,14 +56,14 @@ fn fetch_spot_markets_raw() -> Result<Vec<SpotMarket>> {
     }
 }
 
-pub(super) fn fetch_spot_symbols() -> Result<Vec<String>> {
-    let markets = fetch_spot_markets_raw()?;
+pub(super) fn fetch_spot_symbols(client: &dyn HttpClient) -> Result<Vec<String>> {
+    let markets = fetch_spot_markets_raw(client)?;
     let symbols: Vec<String> = markets.into_iter().map(|m| m.symbol).collect();
     Ok(symbols)
 }
 
-pub(super) fn fetch_spot_markets() -> Result<Vec<Market>> {
-    let markets: Vec<Market> = fetch_spot_markets_raw()?
+pub(super) fn fetch_spot_markets(client: &dyn HttpClient) -> Result<Vec<Market>> {
+    let markets: Vec<Market> = fetch_spot_markets_raw(client)?
         .into_iter()
         .map(|m| {
             let info = serde_json::to_value(&m)
@@ -75,6 +76,11 @@ pub(super) fn fetch_spot_markets() -> Result<Vec<Market>> {
                 let v: Vec<&str> = pair.split('/').collect();
                 (v[0].to_string(), v[1].to_string())
             };
+            let status = if m.enableTrading {
+                MarketStatus::Trading
+            } else {
+                MarketStatus::Halted
+            };
             Market {
                 exchange: "kucoin".to_string(),
                 market_type: MarketType::Spot,
@@ -85,7 +91,8 @@ pub(super) fn fetch_spot_markets() -> Result<Vec<Market>> {
                 base,
                 quote,
                 settle: None,
-                active: m.enableTrading,
+                active: status == MarketStatus::Trading,
+                status,
                 margin: m.isMarginEnabled,
                 // see https://www.bitstamp.net/fee-schedule/
                 fees: Fees {
@@ -1,9 +1,10 @@
 use std::collections::HashMap;
 
 use super::super::utils::http_get;
+use crate::HttpClient;
 use crate::{
     error::{Error, Result},
-    Fees, Market, Precision,
+    Fees, Market, MarketStatus, Precision,
 };
 
 use crypto_market_type::MarketType;
@@ -56,8 +57,9 @@ struct Response {
 }
 
 // See https://docs.kucoin.com/#get-symbols-list
-fn fetch_swap_markets_raw() -> Result<Vec<SwapMarket>> {
+fn fetch_swap_markets_raw(client: &dyn HttpClient) -> Result<Vec<SwapMarket>> {
     let txt = http_get(
+        client,
         "https://api-futures.kucoin.com/api/v1/contracts/active",
         None,
     )?;
@@ -74,8 +76,8 @@ fn fetch_swap_markets_raw() -> Result<Vec<SwapMarket>> {
     }
 }
 
-pub(super) fn fetch_inverse_swap_symbols() -> Result<Vec<String>> {
-    let markets = fetch_swap_markets_raw()?;
+pub(super) fn fetch_inverse_swap_symbols(client: &dyn HttpClient) -> Result<Vec<String>> {
+    let markets = fetch_swap_markets_raw(client)?;
     let symbols: Vec<String> = markets
         .into_iter()
         .filter(|x| x.isInverse && x.type_ == "FFWCSX")
@@ -84,8 +86,8 @@ pub(super) fn fetch_inverse_swap_symbols() -> Result<Vec<String>> {
     Ok(symbols)
 }
 
-pub(super) fn fetch_linear_swap_symbols() -> Result<Vec<String>> {
-    let markets = fetch_swap_markets_raw()?;
+pub(super) fn fetch_linear_swap_symbols(client: &dyn HttpClient) -> Result<Vec<String>> {
+    let markets = fetch_swap_markets_raw(client)?;
     let symbols: Vec<String> = markets
         .into_iter()
         .filter(|x| !x.isInverse && x.type_ == "FFWCSX")
@@ -94,8 +96,8 @@ pub(super) fn fetch_linear_swap_symbols() -> Result<Vec<String>> {
     Ok(symbols)
 }
 
-pub(super) fn fetch_inverse_future_symbols() -> Result<Vec<String>> {
-    let markets = fetch_swap_markets_raw()?;
+pub(super) fn fetch_inverse_future_symbols(client: &dyn HttpClient) -> Result<Vec<String>> {
+    let markets = fetch_swap_markets_raw(client)?;
     let symbols: Vec<String> = markets
         .into_iter()
         .filter(|x| x.isInverse && x.type_ == "FFICSX")
@@ -123,6 +125,11 @@ fn to_market(raw_market: &SwapMarket) -> Market {
         );
     };
 
+    let status = match raw_market.status.as_str() {
+        "Open" => MarketStatus::Trading,
+        "Settled" | "Closed" => MarketStatus::Delisted,
+        _ => MarketStatus::Halted,
+    };
     Market {
         exchange: "kucoin".to_string(),
         market_type,
@@ -141,7 +148,8 @@ fn to_market(raw_market: &SwapMarket) -> Market {
         } else {
             Some(quote)
         },
-        active: raw_market.status == "Open",
+        active: status == MarketStatus::Trading,
+        status,
         margin: true,
         fees: Fees {
             maker: raw_market.makerFeeRate,
@@ -162,8 +170,8 @@ fn to_market(raw_market: &SwapMarket) -> Market {
     }
 }
 
-pub(super) fn fetch_inverse_swap_markets() -> Result<Vec<Market>> {
-    let markets: Vec<Market> = fetch_swap_markets_raw()?
+pub(super) fn fetch_inverse_swap_markets(client: &dyn HttpClient) -> Result<Vec<Market>> {
+    let markets: Vec<Market> = fetch_swap_markets_raw(client)?
         .into_iter()
         .filter(|x| x.isInverse && x.type_ == "FFWCSX")
         .map(|m| to_market(&m))
@@ -171,8 +179,8 @@ pub(super) fn fetch_inverse_swap_markets() -> Result<Vec<Market>> {
     Ok(markets)
 }
 
-pub(super) fn fetch_linear_swap_markets() -> Result<Vec<Market>> {
-    let markets: Vec<Market> = fetch_swap_markets_raw()?
+pub(super) fn fetch_linear_swap_markets(client: &dyn HttpClient) -> Result<Vec<Market>> {
+    let markets: Vec<Market> = fetch_swap_markets_raw(client)?
         .into_iter()
         .filter(|x| !x.isInverse && x.type_ == "FFWCSX")
         .map(|m| to_market(&m))
@@ -180,8 +188,8 @@ pub(super) fn fetch_linear_swap_markets() -> Result<Vec<Market>> {
     Ok(markets)
 }
 
-pub(super) fn fetch_inverse_future_markets() -> Result<Vec<Market>> {
-    let markets: Vec<Market> = fetch_swap_markets_raw()?
+pub(super) fn fetch_inverse_future_markets(client: &dyn HttpClient) -> Result<Vec<Market>> {
+    let markets: Vec<Market> = fetch_swap_markets_raw(client)?
         .into_iter()
         .filter(|x| x.isInverse && x.type_ == "FFICSX")
         .map(|m| to_market(&m))
@@ -1,24 +1,30 @@
 mod kucoin_spot;
 mod kucoin_swap;
 
-use crate::{error::Result, Market, MarketType};
+use crate::{error::Result, HttpClient, Market, MarketType};
 
-pub(crate) fn fetch_symbols(market_type: MarketType) -> Result<Vec<String>> {
+pub(crate) fn fetch_symbols(
+    client: &dyn HttpClient,
+    market_type: MarketType,
+) -> Result<Vec<String>> {
     match market_type {
-        MarketType::Spot => kucoin_spot::fetch_spot_symbols(),
-        MarketType::InverseSwap => kucoin_swap::fetch_inverse_swap_symbols(),
-        MarketType::LinearSwap => kucoin_swap::fetch_linear_swap_symbols(),
-        MarketType::InverseFuture => kucoin_swap::fetch_inverse_future_symbols(),
+        MarketType::Spot => kucoin_spot::fetch_spot_symbols(client),
+        MarketType::InverseSwap => kucoin_swap::fetch_inverse_swap_symbols(client),
+        MarketType::LinearSwap => kucoin_swap::fetch_linear_swap_symbols(client),
+        MarketType::InverseFuture => kucoin_swap::fetch_inverse_future_symbols(client),
         _ => panic!("Unsupported market_type: {}", market_type),
     }
 }
 
-pub(crate) fn fetch_markets(market_type: MarketType) -> Result<Vec<Market>> {
+pub(crate) fn fetch_markets(
+    client: &dyn HttpClient,
+    market_type: MarketType,
+) -> Result<Vec<Market>> {
     match market_type {
-        MarketType::Spot => kucoin_spot::fetch_spot_markets(),
-        MarketType::InverseSwap => kucoin_swap::fetch_inverse_swap_markets(),
-        MarketType::LinearSwap => kucoin_swap::fetch_linear_swap_markets(),
-        MarketType::InverseFuture => kucoin_swap::fetch_inverse_future_markets(),
+        MarketType::Spot => kucoin_spot::fetch_spot_markets(client),
+        MarketType::InverseSwap => kucoin_swap::fetch_inverse_swap_markets(client),
+        MarketType::LinearSwap => kucoin_swap::fetch_linear_swap_markets(client),
+        MarketType::InverseFuture => kucoin_swap::fetch_inverse_future_markets(client),
         _ => panic!("Unsupported market_type: {}", market_type),
     }
 }
@@ -1,5 +1,5 @@
 use super::utils::mxc_http_get;
-use crate::{error::Result, Fees, Market, Precision, QuantityLimit};
+use crate::{error::Result, Fees, HttpClient, Market, MarketStatus, Precision, QuantityLimit};
 
 use crypto_market_type::MarketType;
 use serde::{Deserialize, Serialize};
@@ -28,8 +28,8 @@ struct Response {
 }
 
 // see <https://mxcdevelop.github.io/APIDoc/open.api.v2.en.html#all-symbols>
-fn fetch_spot_markets_raw() -> Result<Vec<SpotMarket>> {
-    let txt = mxc_http_get("https://www.mexc.com/open/api/v2/market/symbols")?;
+fn fetch_spot_markets_raw(client: &dyn HttpClient) -> Result<Vec<SpotMarket>> {
+    let txt = mxc_http_get(client, "https://www.mexc.com/open/api/v2/market/symbols")?;
     let resp = serde_json::from_str::<Response>(&txt)?;
     Ok(resp
         .data
@@ -38,16 +38,16 @@ fn fetch_spot_markets_raw() -> Result<Vec<SpotMarket>> {
         .collect())
 }
 
-pub(super) fn fetch_spot_symbols() -> Result<Vec<String>> {
-    let symbols = fetch_spot_markets_raw()?
+pub(super) fn fetch_spot_symbols(client: &dyn HttpClient) -> Result<Vec<String>> {
+    let symbols = fetch_spot_markets_raw(client)?
         .into_iter()
         .map(|m| m.symbol)
         .collect::<Vec<String>>();
     Ok(symbols)
 }
 
-pub(super) fn fetch_spot_markets() -> Result<Vec<Market>> {
-    let markets = fetch_spot_markets_raw()?
+pub(super) fn fetch_spot_markets(client: &dyn HttpClient) -> Result<Vec<Market>> {
+    let markets = fetch_spot_markets_raw(client)?
         .into_iter()
         .map(|m| {
             let info = serde_json::to_value(&m)
@@ -64,6 +64,11 @@ pub(super) fn fetch_spot_markets() -> Result<Vec<Market>> {
                 let v: Vec<&str> = m.symbol.split('_').collect();
                 (v[0].to_string(), v[1].to_string())
             };
+            let status = if m.state == "ENABLED" && !m.limited {
+                MarketStatus::Trading
+            } else {
+                MarketStatus::Halted
+            };
             Market {
                 exchange: "mxc".to_string(),
                 market_type: MarketType::Spot,
@@ -74,7 +79,8 @@ pub(super) fn fetch_spot_markets() -> Result<Vec<Market>> {
                 base,
                 quote,
                 settle: None,
-                active: m.state == "ENABLED" && !m.limited,
+                active: status == MarketStatus::Trading,
+                status,
                 margin: false,
                 fees: Fees {
                     maker: m.maker_fee_rate.parse::<f64>().unwrap(),
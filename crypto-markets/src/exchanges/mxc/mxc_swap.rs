@@ -1,5 +1,5 @@
 use super::utils::mxc_http_get;
-use crate::{error::Result, Fees, Market, Precision, QuantityLimit};
+use crate::{error::Result, Fees, HttpClient, Market, MarketStatus, Precision, QuantityLimit};
 
 use crypto_market_type::MarketType;
 use serde::{Deserialize, Serialize};
@@ -48,8 +48,8 @@ struct Response {
 }
 
 // see <https://github.com/mxcdevelop/APIDoc/blob/master/contract/contract-api.md#contract-interface-public>
-fn fetch_swap_markets_raw() -> Result<Vec<SwapMarket>> {
-    let txt = mxc_http_get("https://contract.mexc.com/api/v1/contract/detail")?;
+fn fetch_swap_markets_raw(client: &dyn HttpClient) -> Result<Vec<SwapMarket>> {
+    let txt = mxc_http_get(client, "https://contract.mexc.com/api/v1/contract/detail")?;
     let resp = serde_json::from_str::<Response>(&txt)?;
     Ok(resp
         .data
@@ -58,8 +58,8 @@ fn fetch_swap_markets_raw() -> Result<Vec<SwapMarket>> {
         .collect())
 }
 
-pub(super) fn fetch_linear_swap_symbols() -> Result<Vec<String>> {
-    let symbols = fetch_swap_markets_raw()?
+pub(super) fn fetch_linear_swap_symbols(client: &dyn HttpClient) -> Result<Vec<String>> {
+    let symbols = fetch_swap_markets_raw(client)?
         .into_iter()
         .filter(|m| m.settleCoin == m.quoteCoin)
         .map(|m| m.symbol)
@@ -67,8 +67,8 @@ pub(super) fn fetch_linear_swap_symbols() -> Result<Vec<String>> {
     Ok(symbols)
 }
 
-pub(super) fn fetch_inverse_swap_symbols() -> Result<Vec<String>> {
-    let symbols = fetch_swap_markets_raw()?
+pub(super) fn fetch_inverse_swap_symbols(client: &dyn HttpClient) -> Result<Vec<String>> {
+    let symbols = fetch_swap_markets_raw(client)?
         .into_iter()
         .filter(|m| m.settleCoin == m.baseCoin)
         .map(|m| m.symbol)
@@ -90,6 +90,11 @@ fn to_market(raw_market: &SwapMarket) -> Market {
         panic!("unexpected market type");
     };
 
+    let status = match raw_market.state {
+        0 if !raw_market.isHidden => MarketStatus::Trading,
+        2 | 3 => MarketStatus::Delisted,
+        _ => MarketStatus::Halted,
+    };
     Market {
         exchange: "mxc".to_string(),
         market_type,
@@ -100,7 +105,8 @@ fn to_market(raw_market: &SwapMarket) -> Market {
         base,
         quote,
         settle: Some(raw_market.settleCoin.to_string()),
-        active: raw_market.state == 0 && !raw_market.isHidden,
+        active: status == MarketStatus::Trading,
+        status,
         margin: true,
         fees: Fees {
             maker: raw_market.makerFeeRate,
@@ -124,8 +130,8 @@ fn to_market(raw_market: &SwapMarket) -> Market {
     }
 }
 
-pub(super) fn fetch_linear_swap_markets() -> Result<Vec<Market>> {
-    let markets = fetch_swap_markets_raw()?
+pub(super) fn fetch_linear_swap_markets(client: &dyn HttpClient) -> Result<Vec<Market>> {
+    let markets = fetch_swap_markets_raw(client)?
         .into_iter()
         .filter(|m| m.settleCoin == m.quoteCoin)
         .map(|m| to_market(&m))
@@ -133,8 +139,8 @@ pub(super) fn fetch_linear_swap_markets() -> Result<Vec<Market>> {
     Ok(markets)
 }
 
-pub(super) fn fetch_inverse_swap_markets() -> Result<Vec<Market>> {
-    let markets = fetch_swap_markets_raw()?
+pub(super) fn fetch_inverse_swap_markets(client: &dyn HttpClient) -> Result<Vec<Market>> {
+    let markets = fetch_swap_markets_raw(client)?
         .into_iter()
         .filter(|m| m.settleCoin == m.baseCoin)
         .map(|m| to_market(&m))
@@ -1,5 +1,6 @@
 use super::super::utils::http_get;
 use crate::error::{Error, Result};
+use crate::HttpClient;
 
 use serde_json::Value;
 use std::collections::HashMap;
@@ -23,8 +24,8 @@ fn check_code_in_body(resp: String) -> Result<String> {
     }
 }
 
-pub(super) fn mxc_http_get(url: &str) -> Result<String> {
-    let ret = http_get(url, None);
+pub(super) fn mxc_http_get(client: &dyn HttpClient, url: &str) -> Result<String> {
+    let ret = http_get(client, url, None);
     match ret {
         Ok(resp) => check_code_in_body(resp),
         Err(_) => ret,
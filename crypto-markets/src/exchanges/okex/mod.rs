@@ -3,28 +3,69 @@ pub(super) mod okex_option;
 pub(super) mod okex_spot;
 pub(super) mod okex_swap;
 
-use crate::{error::Result, Market, MarketType};
+use crate::{error::Result, HttpClient, Market, MarketStatus, MarketType};
+use serde_json::Value;
+use std::collections::HashMap;
 
-pub(crate) fn fetch_symbols(market_type: MarketType) -> Result<Vec<String>> {
+pub(crate) fn fetch_symbols(
+    client: &dyn HttpClient,
+    market_type: MarketType,
+) -> Result<Vec<String>> {
     match market_type {
-        MarketType::Spot => okex_spot::fetch_spot_symbols(),
-        MarketType::InverseFuture => okex_future::fetch_inverse_future_symbols(),
-        MarketType::LinearFuture => okex_future::fetch_linear_future_symbols(),
-        MarketType::InverseSwap => okex_swap::fetch_inverse_swap_symbols(),
-        MarketType::LinearSwap => okex_swap::fetch_linear_swap_symbols(),
-        MarketType::EuropeanOption => okex_option::fetch_option_symbols(),
+        MarketType::Spot => okex_spot::fetch_spot_symbols(client),
+        MarketType::InverseFuture => okex_future::fetch_inverse_future_symbols(client),
+        MarketType::LinearFuture => okex_future::fetch_linear_future_symbols(client),
+        MarketType::InverseSwap => okex_swap::fetch_inverse_swap_symbols(client),
+        MarketType::LinearSwap => okex_swap::fetch_linear_swap_symbols(client),
+        MarketType::EuropeanOption => okex_option::fetch_option_symbols(client),
         _ => panic!("Unsupported market_type: {}", market_type),
     }
 }
 
-pub(crate) fn fetch_markets(market_type: MarketType) -> Result<Vec<Market>> {
+pub(crate) fn fetch_markets(
+    client: &dyn HttpClient,
+    market_type: MarketType,
+) -> Result<Vec<Market>> {
     match market_type {
-        MarketType::Spot => okex_spot::fetch_spot_markets(),
-        MarketType::InverseFuture => okex_future::fetch_inverse_future_markets(),
-        MarketType::LinearFuture => okex_future::fetch_linear_future_markets(),
-        MarketType::InverseSwap => okex_swap::fetch_inverse_swap_markets(),
-        MarketType::LinearSwap => okex_swap::fetch_linear_swap_markets(),
-        MarketType::EuropeanOption => okex_option::fetch_option_markets(),
+        MarketType::Spot => okex_spot::fetch_spot_markets(client),
+        MarketType::InverseFuture => okex_future::fetch_inverse_future_markets(client),
+        MarketType::LinearFuture => okex_future::fetch_linear_future_markets(client),
+        MarketType::InverseSwap => okex_swap::fetch_inverse_swap_markets(client),
+        MarketType::LinearSwap => okex_swap::fetch_linear_swap_markets(client),
+        MarketType::EuropeanOption => okex_option::fetch_option_markets(client),
         _ => panic!("Unsupported market_type: {}", market_type),
     }
 }
+
+// Maps the `state` of an instrument, the v3 API has codes and the v5 API has names.
+//
+// Only v3 options have `state`, v3 spot, futures and swap instruments
+// are listed while trading, so they are trading if `state` is absent.
+fn okex_status(state: Option<&str>) -> MarketStatus {
+    match state {
+        None | Some("2") | Some("live") => MarketStatus::Trading,
+        // 1 or preopen, 3 or suspend, 4 or settlement
+        Some(_) => MarketStatus::Halted,
+    }
+}
+
+// The status of v3 spot, futures and swap instruments, which may have `state` in `extra`
+fn okex_status_of(extra: &HashMap<String, Value>) -> MarketStatus {
+    okex_status(extra.get("state").and_then(|x| x.as_str()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::okex_status;
+    use crate::MarketStatus;
+
+    #[test]
+    fn status() {
+        assert_eq!(MarketStatus::Trading, okex_status(None));
+        assert_eq!(MarketStatus::Trading, okex_status(Some("2")));
+        assert_eq!(MarketStatus::Trading, okex_status(Some("live")));
+        assert_eq!(MarketStatus::Halted, okex_status(Some("1")));
+        assert_eq!(MarketStatus::Halted, okex_status(Some("preopen")));
+        assert_eq!(MarketStatus::Halted, okex_status(Some("suspend")));
+    }
+}
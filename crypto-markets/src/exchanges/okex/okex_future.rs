@@ -1,9 +1,10 @@
 use std::collections::HashMap;
 
 use super::super::utils::http_get;
+use crate::HttpClient;
 use crate::{
     error::Result,
-    market::{Fees, Precision},
+    market::{Fees, MarketStatus, Precision},
     Market,
 };
 
@@ -35,14 +36,18 @@ struct FutureMarket {
 }
 
 // see <https://www.okex.com/docs/en/#futures-contract_information>
-fn fetch_future_markets_raw() -> Result<Vec<FutureMarket>> {
-    let txt = http_get("https://www.okex.com/api/futures/v3/instruments", None)?;
+fn fetch_future_markets_raw(client: &dyn HttpClient) -> Result<Vec<FutureMarket>> {
+    let txt = http_get(
+        client,
+        "https://www.okex.com/api/futures/v3/instruments",
+        None,
+    )?;
     let markets = serde_json::from_str::<Vec<FutureMarket>>(&txt)?;
     Ok(markets)
 }
 
-pub(super) fn fetch_linear_future_symbols() -> Result<Vec<String>> {
-    let symbols = fetch_future_markets_raw()?
+pub(super) fn fetch_linear_future_symbols(client: &dyn HttpClient) -> Result<Vec<String>> {
+    let symbols = fetch_future_markets_raw(client)?
         .into_iter()
         .filter(|x| x.is_inverse == "false")
         .map(|m| m.instrument_id)
@@ -50,8 +55,8 @@ pub(super) fn fetch_linear_future_symbols() -> Result<Vec<String>> {
     Ok(symbols)
 }
 
-pub(super) fn fetch_inverse_future_symbols() -> Result<Vec<String>> {
-    let symbols = fetch_future_markets_raw()?
+pub(super) fn fetch_inverse_future_symbols(client: &dyn HttpClient) -> Result<Vec<String>> {
+    let symbols = fetch_future_markets_raw(client)?
         .into_iter()
         .filter(|x| x.is_inverse == "true")
         .map(|m| m.instrument_id)
@@ -69,6 +74,7 @@ fn to_market(raw_market: &FutureMarket) -> Market {
         DateTime::parse_from_rfc3339(format!("{}T00:00:00+00:00", raw_market.delivery).as_str())
             .unwrap()
             .timestamp_millis();
+    let status = super::okex_status_of(&raw_market.extra);
     Market {
         exchange: "okex".to_string(),
         market_type: if raw_market.is_inverse == "true" {
@@ -83,7 +89,8 @@ fn to_market(raw_market: &FutureMarket) -> Market {
         base,
         quote,
         settle: Some(raw_market.settlement_currency.to_string()),
-        active: true,
+        active: status == MarketStatus::Trading,
+        status,
         margin: true,
         // see https://www.okex.com/fees.html
         fees: Fees {
@@ -105,8 +112,8 @@ fn to_market(raw_market: &FutureMarket) -> Market {
     }
 }
 
-pub(super) fn fetch_linear_future_markets() -> Result<Vec<Market>> {
-    let markets = fetch_future_markets_raw()?
+pub(super) fn fetch_linear_future_markets(client: &dyn HttpClient) -> Result<Vec<Market>> {
+    let markets = fetch_future_markets_raw(client)?
         .into_iter()
         .filter(|x| x.is_inverse == "false")
         .map(|m| to_market(&m))
@@ -114,8 +121,8 @@ pub(super) fn fetch_linear_future_markets() -> Result<Vec<Market>> {
     Ok(markets)
 }
 
-pub(super) fn fetch_inverse_future_markets() -> Result<Vec<Market>> {
-    let markets = fetch_future_markets_raw()?
+pub(super) fn fetch_inverse_future_markets(client: &dyn HttpClient) -> Result<Vec<Market>> {
+    let markets = fetch_future_markets_raw(client)?
         .into_iter()
         .filter(|x| x.is_inverse == "true")
         .map(|m| to_market(&m))
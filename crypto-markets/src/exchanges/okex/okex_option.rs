@@ -1,9 +1,10 @@
 use std::collections::HashMap;
 
 use super::super::utils::http_get;
+use crate::HttpClient;
 use crate::{
     error::Result,
-    market::{Fees, Precision},
+    market::{Fees, MarketStatus, Precision},
     Market,
 };
 
@@ -34,14 +35,18 @@ struct OptionMarket {
 }
 
 // see <https://www.okex.com/docs/en/#option-option---instrument>
-fn fetch_option_markets_raw() -> Result<Vec<OptionMarket>> {
-    let txt = http_get("https://www.okex.com/api/option/v3/underlying", None)?;
+fn fetch_option_markets_raw(client: &dyn HttpClient) -> Result<Vec<OptionMarket>> {
+    let txt = http_get(
+        client,
+        "https://www.okex.com/api/option/v3/underlying",
+        None,
+    )?;
     let underlying_indexes = serde_json::from_str::<Vec<String>>(&txt)?;
 
     let mut markets = Vec::<OptionMarket>::new();
     for index in underlying_indexes.iter() {
         let url = format!("https://www.okex.com/api/option/v3/instruments/{}", index);
-        let txt = http_get(url.as_str(), None)?;
+        let txt = http_get(client, url.as_str(), None)?;
         let mut arr = serde_json::from_str::<Vec<OptionMarket>>(&txt)?;
         markets.append(&mut arr);
     }
@@ -49,8 +54,8 @@ fn fetch_option_markets_raw() -> Result<Vec<OptionMarket>> {
     Ok(markets)
 }
 
-pub(super) fn fetch_option_symbols() -> Result<Vec<String>> {
-    let symbols = fetch_option_markets_raw()?
+pub(super) fn fetch_option_symbols(client: &dyn HttpClient) -> Result<Vec<String>> {
+    let symbols = fetch_option_markets_raw(client)?
         .into_iter()
         .map(|m| m.instrument_id)
         .collect::<Vec<String>>();
@@ -70,6 +75,7 @@ fn to_market(raw_market: &OptionMarket) -> Market {
     let delivery_time = DateTime::parse_from_rfc3339(&raw_market.delivery)
         .unwrap()
         .timestamp_millis();
+    let status = super::okex_status(Some(&raw_market.state));
     Market {
         exchange: "okex".to_string(),
         market_type: MarketType::EuropeanOption,
@@ -80,7 +86,8 @@ fn to_market(raw_market: &OptionMarket) -> Market {
         base,
         quote,
         settle: Some(raw_market.settlement_currency.to_string()),
-        active: true,
+        active: status == MarketStatus::Trading,
+        status,
         margin: true,
         // see https://www.okex.com/fees.html
         fees: Fees {
@@ -102,8 +109,8 @@ fn to_market(raw_market: &OptionMarket) -> Market {
     }
 }
 
-pub(super) fn fetch_option_markets() -> Result<Vec<Market>> {
-    let markets = fetch_option_markets_raw()?
+pub(super) fn fetch_option_markets(client: &dyn HttpClient) -> Result<Vec<Market>> {
+    let markets = fetch_option_markets_raw(client)?
         .into_iter()
         .map(|m| to_market(&m))
         .collect::<Vec<Market>>();
@@ -1,9 +1,10 @@
 use std::collections::HashMap;
 
 use super::super::utils::http_get;
+use crate::HttpClient;
 use crate::{
     error::Result,
-    market::{Fees, Precision, QuantityLimit},
+    market::{Fees, MarketStatus, Precision, QuantityLimit},
     Market,
 };
 
@@ -26,14 +27,14 @@ struct SpotMarket {
 }
 
 // see <https://www.okex.com/docs/en/#spot-currency>
-fn fetch_spot_markets_raw() -> Result<Vec<SpotMarket>> {
-    let txt = http_get("https://www.okex.com/api/spot/v3/instruments", None)?;
+fn fetch_spot_markets_raw(client: &dyn HttpClient) -> Result<Vec<SpotMarket>> {
+    let txt = http_get(client, "https://www.okex.com/api/spot/v3/instruments", None)?;
     let markets = serde_json::from_str::<Vec<SpotMarket>>(&txt)?;
     Ok(markets)
 }
 
-pub(super) fn fetch_spot_symbols() -> Result<Vec<String>> {
-    let symbols = fetch_spot_markets_raw()?
+pub(super) fn fetch_spot_symbols(client: &dyn HttpClient) -> Result<Vec<String>> {
+    let symbols = fetch_spot_markets_raw(client)?
         .into_iter()
         .map(|m| m.instrument_id)
         .collect::<Vec<String>>();
@@ -46,6 +47,7 @@ fn to_market(raw_market: &SpotMarket) -> Market {
         let v: Vec<&str> = pair.split('/').collect();
         (v[0].to_string(), v[1].to_string())
     };
+    let status = super::okex_status_of(&raw_market.extra);
     Market {
         exchange: "okex".to_string(),
         market_type: MarketType::Spot,
@@ -56,7 +58,8 @@ fn to_market(raw_market: &SpotMarket) -> Market {
         base,
         quote,
         settle: None,
-        active: true,
+        active: status == MarketStatus::Trading,
+        status,
         margin: true,
         // see https://www.okex.com/fees.html
         fees: Fees {
@@ -81,8 +84,8 @@ fn to_market(raw_market: &SpotMarket) -> Market {
     }
 }
 
-pub(super) fn fetch_spot_markets() -> Result<Vec<Market>> {
-    let markets = fetch_spot_markets_raw()?
+pub(super) fn fetch_spot_markets(client: &dyn HttpClient) -> Result<Vec<Market>> {
+    let markets = fetch_spot_markets_raw(client)?
         .into_iter()
         .map(|m| to_market(&m))
         .collect::<Vec<Market>>();
@@ -1,9 +1,10 @@
 use std::collections::HashMap;
 
 use super::super::utils::http_get;
+use crate::HttpClient;
 use crate::{
     error::Result,
-    market::{Fees, Precision},
+    market::{Fees, MarketStatus, Precision},
     Market,
 };
 
@@ -34,14 +35,14 @@ struct SwapMarket {
 }
 
 // see <https://www.okex.com/docs/en/#swap-swap---contract_information>
-fn fetch_swap_markets_raw() -> Result<Vec<SwapMarket>> {
-    let txt = http_get("https://www.okex.com/api/swap/v3/instruments", None)?;
+fn fetch_swap_markets_raw(client: &dyn HttpClient) -> Result<Vec<SwapMarket>> {
+    let txt = http_get(client, "https://www.okex.com/api/swap/v3/instruments", None)?;
     let markets = serde_json::from_str::<Vec<SwapMarket>>(&txt)?;
     Ok(markets)
 }
 
-pub(super) fn fetch_linear_swap_symbols() -> Result<Vec<String>> {
-    let symbols = fetch_swap_markets_raw()?
+pub(super) fn fetch_linear_swap_symbols(client: &dyn HttpClient) -> Result<Vec<String>> {
+    let symbols = fetch_swap_markets_raw(client)?
         .into_iter()
         .filter(|x| x.is_inverse == "false")
         .map(|m| m.instrument_id)
@@ -49,8 +50,8 @@ pub(super) fn fetch_linear_swap_symbols() -> Result<Vec<String>> {
     Ok(symbols)
 }
 
-pub(super) fn fetch_inverse_swap_symbols() -> Result<Vec<String>> {
-    let symbols = fetch_swap_markets_raw()?
+pub(super) fn fetch_inverse_swap_symbols(client: &dyn HttpClient) -> Result<Vec<String>> {
+    let symbols = fetch_swap_markets_raw(client)?
         .into_iter()
         .filter(|x| x.is_inverse == "true")
         .map(|m| m.instrument_id)
@@ -67,6 +68,7 @@ fn to_market(raw_market: &SwapMarket) -> Market {
     // let delivery_time = DateTime::parse_from_rfc3339(&raw_market.delivery)
     //     .unwrap()
     //     .timestamp_millis();
+    let status = super::okex_status_of(&raw_market.extra);
     Market {
         exchange: "okex".to_string(),
         market_type: if raw_market.is_inverse == "true" {
@@ -81,7 +83,8 @@ fn to_market(raw_market: &SwapMarket) -> Market {
         base,
         quote,
         settle: Some(raw_market.settlement_currency.to_string()),
-        active: true,
+        active: status == MarketStatus::Trading,
+        status,
         margin: true,
         // see https://www.okex.com/fees.html
         fees: Fees {
@@ -103,8 +106,8 @@ fn to_market(raw_market: &SwapMarket) -> Market {
     }
 }
 
-pub(super) fn fetch_linear_swap_markets() -> Result<Vec<Market>> {
-    let markets = fetch_swap_markets_raw()?
+pub(super) fn fetch_linear_swap_markets(client: &dyn HttpClient) -> Result<Vec<Market>> {
+    let markets = fetch_swap_markets_raw(client)?
         .into_iter()
         .filter(|x| x.is_inverse == "false")
         .map(|m| to_market(&m))
@@ -112,8 +115,8 @@ pub(super) fn fetch_linear_swap_markets() -> Result<Vec<Market>> {
     Ok(markets)
 }
 
-pub(super) fn fetch_inverse_swap_markets() -> Result<Vec<Market>> {
-    let markets = fetch_swap_markets_raw()?
+pub(super) fn fetch_inverse_swap_markets(client: &dyn HttpClient) -> Result<Vec<Market>> {
+    let markets = fetch_swap_markets_raw(client)?
         .into_iter()
         .filter(|x| x.is_inverse == "true")
         .map(|m| to_market(&m))
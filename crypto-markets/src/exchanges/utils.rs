@@ -1,9 +1,12 @@
-use reqwest::header;
-
-use crate::error::{Error, Result};
+use crate::error::Result;
+use crate::HttpClient;
 use std::collections::HashMap;
 
-pub(super) fn http_get(url: &str, params: Option<&HashMap<String, String>>) -> Result<String> {
+pub(super) fn http_get(
+    client: &dyn HttpClient,
+    url: &str,
+    params: Option<&HashMap<String, String>>,
+) -> Result<String> {
     let mut full_url = url.to_string();
     if let Some(params) = params {
         let mut first = true;
@@ -16,25 +19,7 @@ pub(super) fn http_get(url: &str, params: Option<&HashMap<String, String>>) -> R
             }
         }
     }
-    // println!("{}", full_url);
-
-    let mut headers = header::HeaderMap::new();
-    headers.insert(
-        header::CONTENT_TYPE,
-        header::HeaderValue::from_static("application/json"),
-    );
-
-    let client = reqwest::blocking::Client::builder()
-         .default_headers(headers)
-         .user_agent("Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/87.0.4280.88 Safari/537.36")
-         .gzip(true)
-         .build()?;
-    let response = client.get(full_url.as_str()).send()?;
-
-    match response.error_for_status() {
-        Ok(resp) => Ok(resp.text()?),
-        Err(error) => Err(Error::from(error)),
-    }
+    client.get(full_url.as_str())
 }
 
 #[allow(dead_code)]
@@ -57,6 +42,8 @@ fn precision_from_string(s: &str) -> i64 {
 mod tests {
     use std::collections::HashMap;
 
+    use crate::DefaultHttpClient;
+
     use serde_json::Value;
 
     // System proxies are enabled by default, see <https://docs.rs/reqwest/latest/reqwest/#proxies>
@@ -64,7 +51,12 @@ mod tests {
     #[ignore]
     fn use_system_socks_proxy() {
         std::env::set_var("https_proxy", "socks5://127.0.0.1:9050");
-        let text = super::http_get("https://check.torproject.org/api/ip", None).unwrap();
+        let text = super::http_get(
+            &DefaultHttpClient,
+            "https://check.torproject.org/api/ip",
+            None,
+        )
+        .unwrap();
         let obj = serde_json::from_str::<HashMap<String, Value>>(&text).unwrap();
         assert!(obj.get("IsTor").unwrap().as_bool().unwrap());
     }
@@ -73,7 +65,12 @@ mod tests {
     #[ignore]
     fn use_system_https_proxy() {
         std::env::set_var("https_proxy", "http://127.0.0.1:8118");
-        let text = super::http_get("https://check.torproject.org/api/ip", None).unwrap();
+        let text = super::http_get(
+            &DefaultHttpClient,
+            "https://check.torproject.org/api/ip",
+            None,
+        )
+        .unwrap();
         let obj = serde_json::from_str::<HashMap<String, Value>>(&text).unwrap();
         assert!(obj.get("IsTor").unwrap().as_bool().unwrap());
     }
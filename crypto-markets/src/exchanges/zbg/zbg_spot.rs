@@ -1,9 +1,10 @@
 use std::collections::HashMap;
 
 use super::super::utils::http_get;
+use crate::HttpClient;
 use crate::{
     error::{Error, Result},
-    Fees, Market, Precision, QuantityLimit,
+    Fees, Market, MarketStatus, Precision, QuantityLimit,
 };
 
 use crypto_market_type::MarketType;
@@ -42,8 +43,12 @@ struct Response {
 }
 
 // See https://zbgapi.github.io/docs/spot/v1/en/#public-get-all-supported-trading-symbols
-fn fetch_spot_markets_raw() -> Result<Vec<SpotMarket>> {
-    let txt = http_get("https://www.zbg.com/exchange/api/v1/common/symbols", None)?;
+fn fetch_spot_markets_raw(client: &dyn HttpClient) -> Result<Vec<SpotMarket>> {
+    let txt = http_get(
+        client,
+        "https://www.zbg.com/exchange/api/v1/common/symbols",
+        None,
+    )?;
     let resp = serde_json::from_str::<Response>(&txt)?;
     if resp.resMsg.code != "1" {
         Err(Error(txt))
@@ -57,14 +62,14 @@ fn fetch_spot_markets_raw() -> Result<Vec<SpotMarket>> {
     }
 }
 
-pub(super) fn fetch_spot_symbols() -> Result<Vec<String>> {
-    let markets = fetch_spot_markets_raw()?;
+pub(super) fn fetch_spot_symbols(client: &dyn HttpClient) -> Result<Vec<String>> {
+    let markets = fetch_spot_markets_raw(client)?;
     let symbols: Vec<String> = markets.into_iter().map(|m| m.symbol).collect();
     Ok(symbols)
 }
 
-pub(super) fn fetch_spot_markets() -> Result<Vec<Market>> {
-    let markets: Vec<Market> = fetch_spot_markets_raw()?
+pub(super) fn fetch_spot_markets(client: &dyn HttpClient) -> Result<Vec<Market>> {
+    let markets: Vec<Market> = fetch_spot_markets_raw(client)?
         .into_iter()
         .map(|m| {
             let info = serde_json::to_value(&m)
@@ -77,6 +82,11 @@ pub(super) fn fetch_spot_markets() -> Result<Vec<Market>> {
                 let v: Vec<&str> = pair.split('/').collect();
                 (v[0].to_string(), v[1].to_string())
             };
+            let status = if m.state == "online" {
+                MarketStatus::Trading
+            } else {
+                MarketStatus::Halted
+            };
             Market {
                 exchange: "zbg".to_string(),
                 market_type: MarketType::Spot,
@@ -87,7 +97,8 @@ pub(super) fn fetch_spot_markets() -> Result<Vec<Market>> {
                 base,
                 quote,
                 settle: None,
-                active: m.state == "online",
+                active: status == MarketStatus::Trading,
+                status,
                 margin: false,
                 // TODO: need to find zbg spot fees
                 fees: Fees {
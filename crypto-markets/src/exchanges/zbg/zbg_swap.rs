@@ -1,9 +1,10 @@
 use std::collections::HashMap;
 
 use super::super::utils::http_get;
+use crate::HttpClient;
 use crate::{
     error::{Error, Result},
-    Fees, Market, Precision,
+    Fees, Market, MarketStatus, Precision,
 };
 
 use crypto_market_type::MarketType;
@@ -43,8 +44,9 @@ struct Response {
 }
 
 // See https://zbgapi.github.io/docs/future/v1/en/#public-get-contracts
-fn fetch_swap_markets_raw() -> Result<Vec<SwapMarket>> {
+fn fetch_swap_markets_raw(client: &dyn HttpClient) -> Result<Vec<SwapMarket>> {
     let txt = http_get(
+        client,
         "https://www.zbg.com/exchange/api/v1/future/common/contracts",
         None,
     )?;
@@ -56,8 +58,8 @@ fn fetch_swap_markets_raw() -> Result<Vec<SwapMarket>> {
     }
 }
 
-pub(super) fn fetch_inverse_swap_symbols() -> Result<Vec<String>> {
-    let symbols = fetch_swap_markets_raw()?
+pub(super) fn fetch_inverse_swap_symbols(client: &dyn HttpClient) -> Result<Vec<String>> {
+    let symbols = fetch_swap_markets_raw(client)?
         .into_iter()
         .map(|m| m.symbol)
         .filter(|x| x.ends_with("_USD-R"))
@@ -65,8 +67,8 @@ pub(super) fn fetch_inverse_swap_symbols() -> Result<Vec<String>> {
     Ok(symbols)
 }
 
-pub(super) fn fetch_linear_swap_symbols() -> Result<Vec<String>> {
-    let symbols = fetch_swap_markets_raw()?
+pub(super) fn fetch_linear_swap_symbols(client: &dyn HttpClient) -> Result<Vec<String>> {
+    let symbols = fetch_swap_markets_raw(client)?
         .into_iter()
         .map(|m| m.symbol)
         .filter(|x| x.ends_with("_USDT"))
@@ -113,6 +115,7 @@ fn to_market(raw_market: &SwapMarket) -> Market {
         quote,
         settle: Some(raw_market.currencyName.to_uppercase()),
         active: true,
+        status: MarketStatus::Trading,
         margin: true,
         fees: Fees {
             maker: raw_market.makerFeeRatio.parse::<f64>().unwrap(),
@@ -133,8 +136,8 @@ fn to_market(raw_market: &SwapMarket) -> Market {
     }
 }
 
-pub(super) fn fetch_inverse_swap_markets() -> Result<Vec<Market>> {
-    let markets = fetch_swap_markets_raw()?
+pub(super) fn fetch_inverse_swap_markets(client: &dyn HttpClient) -> Result<Vec<Market>> {
+    let markets = fetch_swap_markets_raw(client)?
         .into_iter()
         .filter(|m| m.symbol.ends_with("_USD-R"))
         .map(|m| to_market(&m))
@@ -142,8 +145,8 @@ pub(super) fn fetch_inverse_swap_markets() -> Result<Vec<Market>> {
     Ok(markets)
 }
 
-pub(super) fn fetch_linear_swap_markets() -> Result<Vec<Market>> {
-    let markets = fetch_swap_markets_raw()?
+pub(super) fn fetch_linear_swap_markets(client: &dyn HttpClient) -> Result<Vec<Market>> {
+    let markets = fetch_swap_markets_raw(client)?
         .into_iter()
         .filter(|m| m.symbol.ends_with("_USDT"))
         .map(|m| to_market(&m))
@@ -0,0 +1,54 @@
+use crate::error::{Error, Result};
+
+use reqwest::header;
+use std::sync::Arc;
+
+/// A blocking HTTP client used to fetch markets.
+///
+/// Implement this trait to share a connection pool across all fetches,
+/// use a custom TLS configuration or mock the HTTP layer in tests.
+///
+/// The `*_with_client()` functions also install the client process-wide by
+/// `crypto_pair::set_http_client()`, so that the data fetched lazily by
+/// `crypto-pair`, `crypto-contract-value` and `crypto-msg-parser`, e.g.,
+/// currency mappings, contract values and BitMEX tick sizes, goes through it
+/// as well. `crypto-rest-client` still sends its own requests.
+pub trait HttpClient: Send + Sync {
+    /// Sends a GET request and returns the response body.
+    fn get(&self, url: &str) -> std::result::Result<String, Error>;
+}
+
+/// The default HTTP client, based on `reqwest::blocking`.
+///
+/// It sends `crypto_pair::DEFAULT_USER_AGENT`. Functions without a client
+/// argument, e.g., `fetch_symbols()`, send the headers set for the exchange by
+/// `crypto_pair::set_user_agent()` and `crypto_pair::set_header()` instead.
+pub struct DefaultHttpClient;
+
+impl HttpClient for DefaultHttpClient {
+    fn get(&self, url: &str) -> Result<String> {
+        get_with_headers(url, crypto_pair::headers(""))
+    }
+}
+
+// The client of functions without a client argument, which sends the
+// headers set for `exchange`
+pub(crate) struct ExchangeHttpClient<'a>(pub &'a str);
+
+impl HttpClient for ExchangeHttpClient<'_> {
+    fn get(&self, url: &str) -> Result<String> {
+        get_with_headers(url, crypto_pair::headers(self.0))
+    }
+}
+
+// Routes crypto_pair::http_get() through `client`
+pub(crate) fn install(client: &Arc<dyn HttpClient>) {
+    let client = client.clone();
+    crypto_pair::set_http_client(Some(Arc::new(move |_exchange: &str, url: &str| {
+        client.get(url).map_err(|err| err.into())
+    })));
+}
+
+fn get_with_headers(url: &str, headers: header::HeaderMap) -> Result<String> {
+    crypto_pair::get_with_headers(url, headers, None).map_err(Error::from)
+}
@@ -12,40 +12,168 @@
 
 mod error;
 mod exchanges;
+mod http_client;
 mod market;
 
 pub use crypto_market_type::{get_market_types, MarketType};
 pub use error::Error;
-pub use market::{Fees, Market, Precision, QuantityLimit};
+pub use http_client::{DefaultHttpClient, HttpClient};
+pub use market::{Fees, Market, MarketStatus, Precision, QuantityLimit};
 
 use error::Result;
+use http_client::{install, ExchangeHttpClient};
+use std::sync::Arc;
 
 /// Fetch trading symbols.
 pub fn fetch_symbols(exchange: &str, market_type: MarketType) -> Result<Vec<String>> {
+    fetch_symbols_internal(exchange, market_type, &ExchangeHttpClient(exchange))
+}
+
+/// Fetch trading symbols with a user-provided HTTP client.
+///
+/// `client` is installed for the data fetched lazily by other crates too,
+/// see `HttpClient`.
+pub fn fetch_symbols_with_client(
+    exchange: &str,
+    market_type: MarketType,
+    client: Arc<dyn HttpClient>,
+) -> Result<Vec<String>> {
+    install(&client);
+    fetch_symbols_internal(exchange, market_type, client.as_ref())
+}
+
+fn fetch_symbols_internal(
+    exchange: &str,
+    market_type: MarketType,
+    client: &dyn HttpClient,
+) -> Result<Vec<String>> {
     match exchange {
-        "binance" => exchanges::binance::fetch_symbols(market_type),
-        "bitfinex" => exchanges::bitfinex::fetch_symbols(market_type),
-        "bitget" => exchanges::bitget::fetch_symbols(market_type),
-        "bithumb" => exchanges::bithumb::fetch_symbols(market_type),
-        "bitmex" => exchanges::bitmex::fetch_symbols(market_type),
-        "bitstamp" => exchanges::bitstamp::fetch_symbols(market_type),
-        "bitz" => exchanges::bitz::fetch_symbols(market_type),
-        "bybit" => exchanges::bybit::fetch_symbols(market_type),
-        "coinbase_pro" => exchanges::coinbase_pro::fetch_symbols(market_type),
-        "deribit" => exchanges::deribit::fetch_symbols(market_type),
-        "dydx" => exchanges::dydx::fetch_symbols(market_type),
-        "ftx" => exchanges::ftx::fetch_symbols(market_type),
-        "gate" => exchanges::gate::fetch_symbols(market_type),
-        "huobi" => exchanges::huobi::fetch_symbols(market_type),
-        "kraken" => exchanges::kraken::fetch_symbols(market_type),
-        "kucoin" => exchanges::kucoin::fetch_symbols(market_type),
-        "mxc" => exchanges::mxc::fetch_symbols(market_type),
-        "okex" => exchanges::okex::fetch_symbols(market_type),
-        "zbg" => exchanges::zbg::fetch_symbols(market_type),
+        "binance" => exchanges::binance::fetch_symbols(client, market_type),
+        "bitfinex" => exchanges::bitfinex::fetch_symbols(client, market_type),
+        "bitget" => exchanges::bitget::fetch_symbols(client, market_type),
+        "bithumb" => exchanges::bithumb::fetch_symbols(client, market_type),
+        "bitmex" => exchanges::bitmex::fetch_symbols(client, market_type),
+        "bitstamp" => exchanges::bitstamp::fetch_symbols(client, market_type),
+        "bitz" => exchanges::bitz::fetch_symbols(client, market_type),
+        "bybit" => exchanges::bybit::fetch_symbols(client, market_type),
+        "coinbase_pro" => exchanges::coinbase_pro::fetch_symbols(client, market_type),
+        "deribit" => exchanges::deribit::fetch_symbols(client, market_type),
+        "dydx" => exchanges::dydx::fetch_symbols(client, market_type),
+        "ftx" => exchanges::ftx::fetch_symbols(client, market_type),
+        "gate" => exchanges::gate::fetch_symbols(client, market_type),
+        "huobi" => exchanges::huobi::fetch_symbols(client, market_type),
+        "kraken" => exchanges::kraken::fetch_symbols(client, market_type),
+        "kucoin" => exchanges::kucoin::fetch_symbols(client, market_type),
+        "mxc" => exchanges::mxc::fetch_symbols(client, market_type),
+        "okex" => exchanges::okex::fetch_symbols(client, market_type),
+        "zbg" => exchanges::zbg::fetch_symbols(client, market_type),
         _ => panic!("Unsupported exchange {}", exchange),
     }
 }
 
+/// Fetch symbols of markets which are currently trading.
+///
+/// Unlike `fetch_symbols()`, halted and delisted markets are filtered out by
+/// their `status`, so that subscribing to them won't fail.
+///
+/// # Example
+///
+/// ```
+/// use crypto_markets::{fetch_trading_symbols, MarketType};
+/// let symbols = fetch_trading_symbols("binance", MarketType::Spot).unwrap();
+/// assert!(!symbols.is_empty());
+/// ```
+pub fn fetch_trading_symbols(exchange: &str, market_type: MarketType) -> Result<Vec<String>> {
+    fetch_trading_symbols_internal(exchange, market_type, &ExchangeHttpClient(exchange))
+}
+
+/// Fetch symbols of markets which are currently trading with a user-provided HTTP client.
+///
+/// `client` is installed for the data fetched lazily by other crates too,
+/// see `HttpClient`.
+pub fn fetch_trading_symbols_with_client(
+    exchange: &str,
+    market_type: MarketType,
+    client: Arc<dyn HttpClient>,
+) -> Result<Vec<String>> {
+    install(&client);
+    fetch_trading_symbols_internal(exchange, market_type, client.as_ref())
+}
+
+fn fetch_trading_symbols_internal(
+    exchange: &str,
+    market_type: MarketType,
+    client: &dyn HttpClient,
+) -> Result<Vec<String>> {
+    let symbols = fetch_markets_internal(exchange, market_type, client)?
+        .into_iter()
+        .filter(|m| m.status == MarketStatus::Trading)
+        .map(|m| m.symbol)
+        .collect();
+    Ok(symbols)
+}
+
+/// Fetch symbols of a base asset in all market types of an exchange.
+///
+/// For example, `BTC` on okex resolves to spot, futures, swap and option
+/// symbols, i.e., all markets whose unified `base` equals `base` ignoring case.
+/// Only markets currently trading are returned, in the order of
+/// `get_market_types()`. Market types failing to fetch are logged and
+/// skipped, so that one broken endpoint doesn't hide the others.
+///
+/// # Example
+///
+/// ```
+/// use crypto_markets::fetch_symbols_by_base;
+/// let symbols = fetch_symbols_by_base("okex", "BTC").unwrap();
+/// assert!(symbols.iter().any(|(_, symbol)| symbol == "BTC-USDT"));
+/// ```
+pub fn fetch_symbols_by_base(exchange: &str, base: &str) -> Result<Vec<(MarketType, String)>> {
+    fetch_symbols_by_base_internal(exchange, base, &ExchangeHttpClient(exchange))
+}
+
+/// Fetch symbols of a base asset in all market types of an exchange with a user-provided HTTP client.
+///
+/// `client` is installed for the data fetched lazily by other crates too,
+/// see `HttpClient`.
+pub fn fetch_symbols_by_base_with_client(
+    exchange: &str,
+    base: &str,
+    client: Arc<dyn HttpClient>,
+) -> Result<Vec<(MarketType, String)>> {
+    install(&client);
+    fetch_symbols_by_base_internal(exchange, base, client.as_ref())
+}
+
+fn fetch_symbols_by_base_internal(
+    exchange: &str,
+    base: &str,
+    client: &dyn HttpClient,
+) -> Result<Vec<(MarketType, String)>> {
+    let mut symbols = Vec::new();
+    for market_type in get_market_types(exchange) {
+        let markets = match fetch_markets_internal(exchange, market_type, client) {
+            Ok(markets) => markets,
+            Err(err) => {
+                log::warn!(
+                    "Failed to fetch {} {} markets, skipped, {}",
+                    exchange,
+                    market_type,
+                    err
+                );
+                continue;
+            }
+        };
+        symbols.extend(
+            markets
+                .into_iter()
+                .filter(|m| m.status == MarketStatus::Trading && m.base.eq_ignore_ascii_case(base))
+                .map(|m| (market_type, m.symbol)),
+        );
+    }
+    Ok(symbols)
+}
+
 /// Fetch trading markets of a cryptocurrency exchange.
 ///
 /// # Arguments
@@ -62,26 +190,61 @@ pub fn fetch_symbols(exchange: &str, market_type: MarketType) -> Result<Vec<Stri
 /// println!("{}", serde_json::to_string_pretty(&markets).unwrap())
 /// ```
 pub fn fetch_markets(exchange: &str, market_type: MarketType) -> Result<Vec<Market>> {
+    fetch_markets_internal(exchange, market_type, &ExchangeHttpClient(exchange))
+}
+
+/// Fetch trading markets of a cryptocurrency exchange with a user-provided HTTP client.
+///
+/// # Arguments
+///
+/// * `exchange` - The exchange name
+/// * `market_type` - The market type
+/// * `client` - The HTTP client to send requests, installed for the data
+///   fetched lazily by other crates too, see `HttpClient`
+///
+/// # Example
+///
+/// ```
+/// use crypto_markets::{fetch_markets_with_client, DefaultHttpClient, MarketType};
+/// use std::sync::Arc;
+/// let markets =
+///     fetch_markets_with_client("binance", MarketType::Spot, Arc::new(DefaultHttpClient)).unwrap();
+/// assert!(!markets.is_empty());
+/// ```
+pub fn fetch_markets_with_client(
+    exchange: &str,
+    market_type: MarketType,
+    client: Arc<dyn HttpClient>,
+) -> Result<Vec<Market>> {
+    install(&client);
+    fetch_markets_internal(exchange, market_type, client.as_ref())
+}
+
+fn fetch_markets_internal(
+    exchange: &str,
+    market_type: MarketType,
+    client: &dyn HttpClient,
+) -> Result<Vec<Market>> {
     match exchange {
-        "binance" => exchanges::binance::fetch_markets(market_type),
-        "bitfinex" => exchanges::bitfinex::fetch_markets(market_type),
-        "bitget" => exchanges::bitget::fetch_markets(market_type),
-        "bithumb" => exchanges::bithumb::fetch_markets(market_type),
-        "bitmex" => exchanges::bitmex::fetch_markets(market_type),
-        "bitstamp" => exchanges::bitstamp::fetch_markets(market_type),
-        "bitz" => exchanges::bitz::fetch_markets(market_type),
-        "bybit" => exchanges::bybit::fetch_markets(market_type),
-        "coinbase_pro" => exchanges::coinbase_pro::fetch_markets(market_type),
-        "deribit" => exchanges::deribit::fetch_markets(market_type),
-        "dydx" => exchanges::dydx::fetch_markets(market_type),
-        "ftx" => exchanges::ftx::fetch_markets(market_type),
-        "gate" => exchanges::gate::fetch_markets(market_type),
-        "huobi" => exchanges::huobi::fetch_markets(market_type),
-        "kraken" => exchanges::kraken::fetch_markets(market_type),
-        "kucoin" => exchanges::kucoin::fetch_markets(market_type),
-        "mxc" => exchanges::mxc::fetch_markets(market_type),
-        "okex" => exchanges::okex::fetch_markets(market_type),
-        "zbg" => exchanges::zbg::fetch_markets(market_type),
+        "binance" => exchanges::binance::fetch_markets(client, market_type),
+        "bitfinex" => exchanges::bitfinex::fetch_markets(client, market_type),
+        "bitget" => exchanges::bitget::fetch_markets(client, market_type),
+        "bithumb" => exchanges::bithumb::fetch_markets(client, market_type),
+        "bitmex" => exchanges::bitmex::fetch_markets(client, market_type),
+        "bitstamp" => exchanges::bitstamp::fetch_markets(client, market_type),
+        "bitz" => exchanges::bitz::fetch_markets(client, market_type),
+        "bybit" => exchanges::bybit::fetch_markets(client, market_type),
+        "coinbase_pro" => exchanges::coinbase_pro::fetch_markets(client, market_type),
+        "deribit" => exchanges::deribit::fetch_markets(client, market_type),
+        "dydx" => exchanges::dydx::fetch_markets(client, market_type),
+        "ftx" => exchanges::ftx::fetch_markets(client, market_type),
+        "gate" => exchanges::gate::fetch_markets(client, market_type),
+        "huobi" => exchanges::huobi::fetch_markets(client, market_type),
+        "kraken" => exchanges::kraken::fetch_markets(client, market_type),
+        "kucoin" => exchanges::kucoin::fetch_markets(client, market_type),
+        "mxc" => exchanges::mxc::fetch_markets(client, market_type),
+        "okex" => exchanges::okex::fetch_markets(client, market_type),
+        "zbg" => exchanges::zbg::fetch_markets(client, market_type),
         _ => panic!("Unsupported exchange {}", exchange),
     }
 }
@@ -24,6 +24,17 @@ pub struct QuantityLimit {
     pub max: Option<f64>,
 }
 
+/// Trading status of a market.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum MarketStatus {
+    /// Orders can be placed and matched.
+    Trading,
+    /// Temporarily not trading, e.g., suspended, pre-listing or settling.
+    Halted,
+    /// Delisted, expired or delivered, will not trade again.
+    Delisted,
+}
+
 /// Market contains all information about a market
 #[derive(Clone, Serialize, Deserialize)]
 pub struct Market {
@@ -47,8 +58,10 @@ pub struct Market {
     /// settlement currency, i.e., collateral currency, always None for spot markets
     #[serde(skip_serializing_if = "Option::is_none")]
     pub settle: Option<String>,
-    /// market status
+    /// market status, true if `status` is `MarketStatus::Trading`
     pub active: bool,
+    /// Trading, halted or delisted, parsed from the exchange's status field
+    pub status: MarketStatus,
     /// Margin enabled.
     ///
     /// * All contract markets are margin enabled, including future, swap and option.
@@ -176,3 +176,52 @@ fn fetch_option_markets() {
 fn test_contract_values(market_type: MarketType) {
     check_contract_values!(EXCHANGE_NAME, market_type);
 }
+
+#[test]
+fn fetch_trading_symbols_with_mock_client() {
+    use crypto_markets::{
+        fetch_markets_with_client, fetch_trading_symbols_with_client, Error, HttpClient,
+        MarketStatus,
+    };
+    use std::sync::Arc;
+
+    struct MockHttpClient;
+
+    impl HttpClient for MockHttpClient {
+        fn get(&self, url: &str) -> Result<String, Error> {
+            // installed process-wide, so other tests may send requests too
+            if url != "https://api.binance.com/api/v3/exchangeInfo" {
+                return Err(Error(format!("{} not mocked", url)));
+            }
+            Ok(r#"{"symbols":[{"symbol":"BTCUSDT","status":"TRADING","baseAsset":"BTC","baseAssetPrecision":8,"quoteAsset":"USDT","quotePrecision":8,"quoteAssetPrecision":8,"isSpotTradingAllowed":true,"isMarginTradingAllowed":true,"filters":[{"filterType":"PRICE_FILTER","tickSize":"0.01000000"},{"filterType":"LOT_SIZE","minQty":"0.00001000","maxQty":"9000.00000000","stepSize":"0.00001000"}]},{"symbol":"BCCUSDT","status":"BREAK","baseAsset":"BCC","baseAssetPrecision":8,"quoteAsset":"USDT","quotePrecision":8,"quoteAssetPrecision":8,"isSpotTradingAllowed":true,"isMarginTradingAllowed":false,"filters":[{"filterType":"PRICE_FILTER","tickSize":"0.01000000"},{"filterType":"LOT_SIZE","minQty":"0.00001000","maxQty":"9000.00000000","stepSize":"0.00001000"}]},{"symbol":"LUNAUSDT","status":"HALT","baseAsset":"LUNA","baseAssetPrecision":8,"quoteAsset":"USDT","quotePrecision":8,"quoteAssetPrecision":8,"isSpotTradingAllowed":true,"isMarginTradingAllowed":false,"filters":[{"filterType":"PRICE_FILTER","tickSize":"0.01000000"},{"filterType":"LOT_SIZE","minQty":"0.00001000","maxQty":"9000.00000000","stepSize":"0.00001000"}]}]}"#.to_string())
+        }
+    }
+
+    let markets =
+        fetch_markets_with_client(EXCHANGE_NAME, MarketType::Spot, Arc::new(MockHttpClient))
+            .unwrap();
+    let statuses = markets
+        .iter()
+        .map(|m| m.status)
+        .collect::<Vec<MarketStatus>>();
+    assert_eq!(
+        statuses,
+        vec![
+            MarketStatus::Trading,
+            MarketStatus::Delisted,
+            MarketStatus::Halted
+        ]
+    );
+    assert_eq!(
+        markets.iter().map(|m| m.active).collect::<Vec<bool>>(),
+        vec![true, false, false]
+    );
+
+    let symbols = fetch_trading_symbols_with_client(
+        EXCHANGE_NAME,
+        MarketType::Spot,
+        Arc::new(MockHttpClient),
+    )
+    .unwrap();
+    assert_eq!(symbols, vec!["BTCUSDT".to_string()]);
+}
@@ -30,3 +30,41 @@ fn fetch_spot_markets() {
     assert_eq!(btcusd.precision.lot_size, 0.01);
     assert!(btcusd.quantity_limit.is_none());
 }
+
+#[test]
+fn fetch_spot_markets_with_mock_client() {
+    use crypto_markets::{
+        fetch_markets_with_client, fetch_symbols_with_client, Error, HttpClient, MarketStatus,
+    };
+    use std::sync::Arc;
+
+    struct MockHttpClient;
+
+    impl HttpClient for MockHttpClient {
+        fn get(&self, url: &str) -> Result<String, Error> {
+            // installed process-wide, so other tests may send requests too
+            if url != "https://www.bitstamp.net/api/v2/trading-pairs-info/" {
+                return Err(Error(format!("{} not mocked", url)));
+            }
+            Ok(r#"[{"name":"BTC/USD","url_symbol":"btcusd","base_decimals":8,"counter_decimals":2,"instant_order_counter_decimals":2,"minimum_order":"10.0 USD","trading":"Enabled","instant_and_market_orders":"Enabled","description":"Bitcoin / U.S. dollar"},{"name":"ETH/USD","url_symbol":"ethusd","base_decimals":8,"counter_decimals":2,"instant_order_counter_decimals":2,"minimum_order":"10.0 USD","trading":"Disabled","instant_and_market_orders":"Disabled","description":"Ether / U.S. dollar"}]"#.to_string())
+        }
+    }
+
+    let symbols =
+        fetch_symbols_with_client(EXCHANGE_NAME, MarketType::Spot, Arc::new(MockHttpClient))
+            .unwrap();
+    assert_eq!(symbols, vec!["btcusd".to_string()]);
+
+    let markets =
+        fetch_markets_with_client(EXCHANGE_NAME, MarketType::Spot, Arc::new(MockHttpClient))
+            .unwrap();
+    // halted markets are listed too
+    assert_eq!(markets.len(), 2);
+    assert_eq!(markets[0].symbol, "btcusd");
+    assert_eq!(markets[0].base, "BTC");
+    assert_eq!(markets[0].quote, "USD");
+    assert_eq!(markets[0].status, MarketStatus::Trading);
+    assert!(markets[0].active);
+    assert_eq!(markets[1].status, MarketStatus::Halted);
+    assert!(!markets[1].active);
+}
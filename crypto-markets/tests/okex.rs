@@ -1,4 +1,6 @@
-use crypto_markets::{fetch_markets, fetch_symbols, get_market_types, MarketType};
+use crypto_markets::{
+    fetch_markets, fetch_symbols, fetch_symbols_by_base, get_market_types, MarketType,
+};
 use test_case::test_case;
 
 #[macro_use]
@@ -11,6 +13,41 @@ fn fetch_all_symbols() {
     gen_all_symbols!();
 }
 
+#[test]
+fn fetch_btc_symbols() {
+    let symbols = fetch_symbols_by_base(EXCHANGE_NAME, "btc").unwrap();
+    assert!(symbols.contains(&(MarketType::Spot, "BTC-USDT".to_string())));
+    assert!(symbols.contains(&(MarketType::InverseSwap, "BTC-USD-SWAP".to_string())));
+    assert!(symbols.contains(&(MarketType::LinearSwap, "BTC-USDT-SWAP".to_string())));
+    assert!(symbols
+        .iter()
+        .any(|(market_type, _)| *market_type == MarketType::EuropeanOption));
+    assert!(symbols.iter().all(|(_, symbol)| symbol.starts_with("BTC-")));
+}
+
+#[test]
+fn fetch_btc_symbols_skips_failed_market_types() {
+    use crypto_markets::{fetch_symbols_by_base_with_client, Error, HttpClient};
+    use std::sync::Arc;
+
+    // Only the spot endpoint works
+    struct MockHttpClient;
+
+    impl HttpClient for MockHttpClient {
+        fn get(&self, url: &str) -> Result<String, Error> {
+            if url == "https://www.okex.com/api/spot/v3/instruments" {
+                Ok(r#"[{"base_currency":"BTC","category":"1","instrument_id":"BTC-USDT","min_size":"0.00001","quote_currency":"USDT","size_increment":"0.00000001","tick_size":"0.1"},{"base_currency":"ETH","category":"1","instrument_id":"ETH-USDT","min_size":"0.0001","quote_currency":"USDT","size_increment":"0.000001","tick_size":"0.01"}]"#.to_string())
+            } else {
+                Err(Error("503 Service Unavailable".to_string()))
+            }
+        }
+    }
+
+    let symbols =
+        fetch_symbols_by_base_with_client(EXCHANGE_NAME, "BTC", Arc::new(MockHttpClient)).unwrap();
+    assert_eq!(vec![(MarketType::Spot, "BTC-USDT".to_string())], symbols);
+}
+
 #[test]
 fn fetch_spot_symbols() {
     let symbols = fetch_symbols(EXCHANGE_NAME, MarketType::Spot).unwrap();
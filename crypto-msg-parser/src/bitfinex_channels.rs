@@ -0,0 +1,78 @@
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+
+/// Restores the channel information of raw Bitfinex websocket messages.
+///
+/// Bitfinex data messages only carry a numeric `chanId`, e.g.,
+/// `[17470,"te",[...]]`, the channel and symbol it stands for are sent only
+/// once in the `subscribed` event. `BitfinexWSClient` in crypto-ws-client
+/// replaces the `chanId` by the channel information before sending messages
+/// out, which is the format `parse_trade()` and `parse_l2()` expect.
+///
+/// Use this to do the same for messages received by other means, e.g.,
+/// frames stored by a raw sender or captured by another websocket library.
+#[derive(Default)]
+pub struct BitfinexChannels {
+    // chanId -> channel information, e.g., {"channel":"trades","symbol":"tBTCUSD"}
+    channels: HashMap<i64, String>,
+}
+
+impl BitfinexChannels {
+    pub fn new() -> Self {
+        BitfinexChannels::default()
+    }
+
+    /// Processes a raw message.
+    ///
+    /// Returns the message with its `chanId` replaced by the channel
+    /// information, or None for events, heartbeats and data of unknown
+    /// channels. `subscribed` and `unsubscribed` events update the mapping.
+    pub fn process(&mut self, msg: &str) -> Option<String> {
+        let msg = msg.trim();
+        if msg.starts_with('{') {
+            if let Ok(obj) = serde_json::from_str::<Map<String, Value>>(msg) {
+                self.on_event(obj);
+            }
+            return None;
+        }
+        if !msg.starts_with('[') {
+            return None;
+        }
+        // te, tu, see https://blog.bitfinex.com/api/websocket-api-update/
+        let i = msg.find(',')?;
+        let chan_id = msg[1..i].trim().parse::<i64>().ok()?;
+        if msg[i + 1..].trim_start().starts_with(r#""hb""#) {
+            // see <https://docs.bitfinex.com/docs/ws-general#heartbeating>
+            return None;
+        }
+        let channel_info = self.channels.get(&chan_id)?;
+        Some(format!("[{}{}", channel_info, &msg[i..]))
+    }
+
+    /// Returns the channel information of a `chanId`.
+    pub fn get(&self, chan_id: i64) -> Option<&str> {
+        self.channels.get(&chan_id).map(|x| x.as_str())
+    }
+
+    fn on_event(&mut self, mut obj: Map<String, Value>) {
+        let event = obj.get("event").and_then(|x| x.as_str()).unwrap_or("");
+        let chan_id = match obj.get("chanId").and_then(|x| x.as_i64()) {
+            Some(chan_id) => chan_id,
+            None => return,
+        };
+        match event {
+            "subscribed" => {
+                // keep the same fields as BitfinexWSClient
+                obj.remove("event");
+                obj.remove("chanId");
+                obj.remove("pair");
+                self.channels
+                    .insert(chan_id, serde_json::to_string(&obj).unwrap());
+            }
+            "unsubscribed" => {
+                self.channels.remove(&chan_id);
+            }
+            _ => (),
+        }
+    }
+}
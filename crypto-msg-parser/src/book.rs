@@ -0,0 +1,491 @@
+use crate::{MarketType, Order, OrderBookMsg, SeqGapDetector};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::mpsc::Sender;
+
+/// Signals that the orderbook of a symbol is out of sync and needs a fresh snapshot.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ResyncRequest {
+    pub exchange: String,
+    pub market_type: MarketType,
+    pub symbol: String,
+}
+
+/// How often `OrderBookManager` emits snapshots, see `on_snapshot()`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SnapshotCadence {
+    /// After every N applied updates of a symbol
+    EveryUpdates(usize),
+    /// When at least N milliseconds have passed since the last snapshot of a
+    /// symbol, measured by message timestamps so that replays emit the same
+    /// snapshots
+    Interval(i64),
+}
+
+/// Maintains level2 orderbooks of symbols from snapshots and incremental updates.
+///
+/// Updates are checked by `SeqGapDetector` before being applied. When some
+/// updates are missing, the out-of-order update is NOT applied, a
+/// `ResyncRequest` is sent to the channel set by `on_resync()`, and later
+/// updates of the symbol are buffered until a snapshot arrives, e.g., one
+/// fetched by `crypto_crawler::fetch_l2_snapshot()`. Buffered updates,
+/// including the one that revealed the gap, are then replayed on top of the
+/// snapshot: updates covered by the snapshot are skipped, and the rest are
+/// checked for gaps one by one, so that a snapshot older than the buffered
+/// updates triggers another resync instead of a corrupted book.
+///
+/// A resync that gets no snapshot is requested again once the buffered
+/// updates span `resync_timeout()` milliseconds, and a book that buffers more
+/// than `max_buffered()` updates is dropped, i.e., later updates are dropped
+/// until the snapshot arrives.
+///
+/// Updates of a symbol without any snapshot are dropped, since there is no
+/// book to apply them to.
+///
+/// okex books channels send a `snapshot` action followed by `update` actions,
+/// each carrying a CRC32 checksum of the top 25 levels of the whole book. The
+/// manager keeps the original price and size strings of okex books, validates
+/// the checksum after the snapshot and every update, and requests a resync on
+/// mismatch, which for okex means resubscribing to get a new snapshot.
+/// Messages without the original JSON are not validated.
+///
+/// With `check_crossed()`, a book whose best bid reaches its best ask is
+/// treated as out of sync the same way.
+///
+/// For consumers that can't replay updates, `on_snapshot()` emits top-N
+/// snapshots of the maintained books periodically.
+pub struct OrderBookManager {
+    books: HashMap<(String, MarketType, String), Book>,
+    detector: SeqGapDetector,
+    resync_tx: Option<Sender<ResyncRequest>>,
+    auto_snapshot: Option<AutoSnapshot>,
+    check_crossed: bool,
+    max_buffered: usize,
+    resync_timeout: i64,
+}
+
+impl Default for OrderBookManager {
+    fn default() -> Self {
+        OrderBookManager {
+            books: HashMap::new(),
+            detector: SeqGapDetector::default(),
+            resync_tx: None,
+            auto_snapshot: None,
+            check_crossed: false,
+            max_buffered: 10000,
+            resync_timeout: 30000,
+        }
+    }
+}
+
+#[derive(Default)]
+struct Book {
+    latest: Option<OrderBookMsg>,
+    // updates received while waiting for a snapshot, None if the book is in sync
+    buffered: Option<Vec<OrderBookMsg>>,
+    // timestamp of the message that triggered the last resync request
+    resync_requested_at: i64,
+    // changes since the last emitted snapshot
+    num_changes: usize,
+    // timestamp of the last emitted snapshot
+    last_snapshot_at: Option<i64>,
+    // original levels of books validated by checksums, i.e., okex
+    checksum_levels: Option<ChecksumLevels>,
+}
+
+// price bits -> (price, size) as sent by the exchange
+#[derive(Default)]
+struct ChecksumLevels {
+    asks: HashMap<u64, (String, String)>,
+    bids: HashMap<u64, (String, String)>,
+}
+
+// Levels and checksum of an okex books message
+struct ChecksumMsg {
+    asks: Vec<(String, String)>,
+    bids: Vec<(String, String)>,
+    checksum: i32,
+}
+
+struct AutoSnapshot {
+    snapshot_tx: Sender<OrderBookMsg>,
+    depth: usize,
+    cadence: SnapshotCadence,
+}
+
+impl OrderBookManager {
+    pub fn new() -> Self {
+        OrderBookManager::default()
+    }
+
+    /// Sends a `ResyncRequest` to `resync_tx` when a gap is detected.
+    pub fn on_resync(mut self, resync_tx: Sender<ResyncRequest>) -> Self {
+        self.resync_tx = Some(resync_tx);
+        self
+    }
+
+    /// Drops a book waiting for a resync once it buffers more than `max_buffered` updates, 10000 by default.
+    pub fn max_buffered(mut self, max_buffered: usize) -> Self {
+        self.max_buffered = max_buffered;
+        self
+    }
+
+    /// Requests a resync again if no snapshot arrives within `timeout`
+    /// milliseconds, measured by message timestamps, 30000 by default.
+    pub fn resync_timeout(mut self, timeout: i64) -> Self {
+        self.resync_timeout = timeout;
+        self
+    }
+
+    /// Requests a resync when a snapshot or an updated book is crossed.
+    ///
+    /// Books of well-behaved exchanges are never crossed, see
+    /// `OrderBookMsg::is_crossed()`, so a crossed book means a bug or a
+    /// dropped update. It stays out of sync until the next snapshot.
+    pub fn check_crossed(mut self) -> Self {
+        self.check_crossed = true;
+        self
+    }
+
+    /// Sends top-`depth` snapshots of changed books to `snapshot_tx` at `cadence`.
+    ///
+    /// Snapshots are built by `snapshot()`, books waiting for a resync are
+    /// skipped until they are in sync again.
+    pub fn on_snapshot(
+        mut self,
+        snapshot_tx: Sender<OrderBookMsg>,
+        depth: usize,
+        cadence: SnapshotCadence,
+    ) -> Self {
+        self.auto_snapshot = Some(AutoSnapshot {
+            snapshot_tx,
+            depth,
+            cadence,
+        });
+        self
+    }
+
+    /// Applies a level2 message, returns true if the book changed.
+    pub fn update(&mut self, msg: OrderBookMsg) -> bool {
+        let key = (msg.exchange.clone(), msg.market_type, msg.symbol.clone());
+        let changed = self.update_book(msg);
+        if changed {
+            self.emit_snapshot(&key);
+        }
+        changed
+    }
+
+    fn update_book(&mut self, msg: OrderBookMsg) -> bool {
+        let key = (msg.exchange.clone(), msg.market_type, msg.symbol.clone());
+        if msg.snapshot {
+            if msg.seq_id.is_some() {
+                self.detector.check(&msg);
+            } else {
+                self.detector
+                    .reset(&msg.exchange, msg.market_type, &msg.symbol);
+            }
+            let checksum_msg = parse_checksum_msg(&msg);
+            let book = self.books.entry(key).or_default();
+            let buffered = book.buffered.take().unwrap_or_default();
+            book.checksum_levels = None;
+            if let Some(checksum_msg) = checksum_msg {
+                let mut levels = ChecksumLevels::default();
+                let valid = levels.apply(checksum_msg);
+                book.checksum_levels = Some(levels);
+                if !valid {
+                    book.start_resync(self.resync_tx.as_ref(), &msg);
+                    book.latest = Some(msg);
+                    return false;
+                }
+            }
+            if self.check_crossed && msg.is_crossed() {
+                book.start_resync(self.resync_tx.as_ref(), &msg);
+                book.latest = Some(msg);
+                return false;
+            }
+            book.latest = Some(msg);
+            for update in buffered {
+                // goes out of sync again if the snapshot is older than the buffered updates
+                self.apply_update(update);
+            }
+            return true;
+        }
+
+        match self.books.get_mut(&key) {
+            Some(book) if book.latest.is_some() => {
+                if book.buffered.is_some() {
+                    self.buffer_update(msg);
+                    false
+                } else {
+                    self.apply_update(msg)
+                }
+            }
+            _ => false,
+        }
+    }
+
+    // Buffers an update of a book waiting for a resync, the book must exist
+    fn buffer_update(&mut self, msg: OrderBookMsg) {
+        let key = (msg.exchange.clone(), msg.market_type, msg.symbol.clone());
+        let book = self.books.get_mut(&key).unwrap();
+        let buffered = book.buffered.get_or_insert_with(Vec::new);
+        if buffered.len() >= self.max_buffered {
+            // the snapshot is unlikely to arrive, forget the book and start over
+            self.books.remove(&key);
+            self.detector
+                .reset(&msg.exchange, msg.market_type, &msg.symbol);
+            send_resync(self.resync_tx.as_ref(), &msg);
+            return;
+        }
+        buffered.push(msg);
+        let msg = buffered.last().unwrap();
+        if msg.timestamp - book.resync_requested_at >= self.resync_timeout {
+            // the last request failed or got lost
+            book.resync_requested_at = msg.timestamp;
+            send_resync(self.resync_tx.as_ref(), msg);
+        }
+    }
+
+    /// Returns the orderbook of a symbol as a snapshot, sorted by price.
+    ///
+    /// Returns None if there is no snapshot of the symbol yet, or the book
+    /// is waiting for a snapshot to resync.
+    pub fn get(
+        &self,
+        exchange: &str,
+        market_type: MarketType,
+        symbol: &str,
+    ) -> Option<OrderBookMsg> {
+        let book = self
+            .books
+            .get(&(exchange.to_string(), market_type, symbol.to_string()))?;
+        if book.buffered.is_some() {
+            return None;
+        }
+        book.latest.as_ref().map(|latest| sorted_book(latest, None))
+    }
+
+    /// Returns the best `depth` levels of each side of a symbol's orderbook.
+    ///
+    /// The message is marked as a snapshot, so that it can replace the book
+    /// of a consumer. Returns None in the same cases as `get()`.
+    pub fn snapshot(
+        &self,
+        exchange: &str,
+        market_type: MarketType,
+        symbol: &str,
+        depth: usize,
+    ) -> Option<OrderBookMsg> {
+        let book = self
+            .books
+            .get(&(exchange.to_string(), market_type, symbol.to_string()))?;
+        if book.buffered.is_some() {
+            return None;
+        }
+        book.latest
+            .as_ref()
+            .map(|latest| sorted_book(latest, Some(depth)))
+    }
+
+    // Sends a snapshot of the book if on_snapshot() is set and it's due
+    fn emit_snapshot(&mut self, key: &(String, MarketType, String)) {
+        let auto_snapshot = match self.auto_snapshot.as_ref() {
+            Some(auto_snapshot) => auto_snapshot,
+            None => return,
+        };
+        let book = match self.books.get_mut(key) {
+            Some(book) if book.buffered.is_none() => book,
+            _ => return,
+        };
+        let latest = match book.latest.as_ref() {
+            Some(latest) => latest,
+            None => return,
+        };
+        book.num_changes += 1;
+        let due = match auto_snapshot.cadence {
+            SnapshotCadence::EveryUpdates(n) => book.num_changes >= n,
+            SnapshotCadence::Interval(interval) => match book.last_snapshot_at {
+                Some(last_snapshot_at) => latest.timestamp - last_snapshot_at >= interval,
+                None => true,
+            },
+        };
+        if due {
+            book.num_changes = 0;
+            book.last_snapshot_at = Some(latest.timestamp);
+            // the receiver may have been dropped if nobody consumes snapshots
+            let _ = auto_snapshot
+                .snapshot_tx
+                .send(sorted_book(latest, Some(auto_snapshot.depth)));
+        }
+    }
+
+    // Applies an update on top of the latest book, the book must exist
+    fn apply_update(&mut self, msg: OrderBookMsg) -> bool {
+        let key = (msg.exchange.clone(), msg.market_type, msg.symbol.clone());
+        if self.books[&key].buffered.is_some() {
+            // went out of sync while applying buffered updates
+            self.buffer_update(msg);
+            return false;
+        }
+        let book = self.books.get_mut(&key).unwrap();
+        let latest = book.latest.as_mut().unwrap();
+        if let (Some(seq_id), Some(last_seq_id)) = (msg.seq_id, latest.seq_id) {
+            if seq_id <= last_seq_id {
+                // stale update, already covered by the book
+                return false;
+            }
+        }
+        if self.detector.check(&msg) {
+            book.start_resync(self.resync_tx.as_ref(), &msg);
+            // newer than the book, replayed if the snapshot doesn't cover it
+            book.buffered = Some(vec![msg]);
+            return false;
+        }
+        if let Some(levels) = book.checksum_levels.as_mut() {
+            if let Some(checksum_msg) = parse_checksum_msg(&msg) {
+                if !levels.apply(checksum_msg) {
+                    book.start_resync(self.resync_tx.as_ref(), &msg);
+                    return false;
+                }
+            }
+        }
+
+        apply_orders(&mut latest.asks, msg.asks);
+        apply_orders(&mut latest.bids, msg.bids);
+        latest.timestamp = msg.timestamp;
+        latest.timestamp_us = msg.timestamp_us;
+        latest.prev_seq_id = latest.seq_id;
+        latest.seq_id = msg.seq_id;
+        latest.json = String::new();
+        if self.check_crossed && latest.is_crossed() {
+            book.buffered = Some(Vec::new());
+            book.resync_requested_at = latest.timestamp;
+            send_resync(self.resync_tx.as_ref(), latest);
+            return false;
+        }
+        true
+    }
+}
+
+impl Book {
+    // Marks the book out of sync and requests a snapshot
+    fn start_resync(&mut self, resync_tx: Option<&Sender<ResyncRequest>>, msg: &OrderBookMsg) {
+        self.buffered = Some(Vec::new());
+        self.resync_requested_at = msg.timestamp;
+        send_resync(resync_tx, msg);
+    }
+}
+
+// Sorts asks ascending and bids descending, keeping the best `depth` levels
+fn sorted_book(latest: &OrderBookMsg, depth: Option<usize>) -> OrderBookMsg {
+    let mut orderbook = latest.clone();
+    orderbook.asks.sort_by(|a, b| a.price.total_cmp(&b.price));
+    orderbook.bids.sort_by(|a, b| b.price.total_cmp(&a.price));
+    if let Some(depth) = depth {
+        orderbook.asks.truncate(depth);
+        orderbook.bids.truncate(depth);
+        orderbook.snapshot = true;
+    }
+    orderbook
+}
+
+// Replaces price levels with new quantities, 0 removes the level
+fn apply_orders(book: &mut Vec<Order>, orders: Vec<Order>) {
+    for order in orders {
+        let pos = book.iter().position(|x| x.price == order.price);
+        match (pos, order.quantity_base == 0.0) {
+            (Some(pos), true) => {
+                book.remove(pos);
+            }
+            (Some(pos), false) => book[pos] = order,
+            (None, true) => {}
+            (None, false) => book.push(order),
+        }
+    }
+}
+
+fn send_resync(resync_tx: Option<&Sender<ResyncRequest>>, msg: &OrderBookMsg) {
+    if let Some(resync_tx) = resync_tx {
+        // the receiver may have been dropped if nobody handles resyncs
+        let _ = resync_tx.send(ResyncRequest {
+            exchange: msg.exchange.clone(),
+            market_type: msg.market_type,
+            symbol: msg.symbol.clone(),
+        });
+    }
+}
+
+// Extracts original levels and the checksum from okex books messages
+fn parse_checksum_msg(msg: &OrderBookMsg) -> Option<ChecksumMsg> {
+    if msg.exchange != "okex" {
+        return None;
+    }
+    let json = serde_json::from_str::<Value>(&msg.json).ok()?;
+    let data = json.pointer("/data/0")?;
+    let parse_levels = |side: &str| -> Option<Vec<(String, String)>> {
+        data.get(side)?
+            .as_array()?
+            .iter()
+            .map(|level| {
+                Some((
+                    level.get(0)?.as_str()?.to_string(),
+                    level.get(1)?.as_str()?.to_string(),
+                ))
+            })
+            .collect()
+    };
+    Some(ChecksumMsg {
+        asks: parse_levels("asks")?,
+        bids: parse_levels("bids")?,
+        checksum: data.get("checksum")?.as_i64()? as i32,
+    })
+}
+
+impl ChecksumLevels {
+    // Applies levels of a message, returns true if the checksum matches
+    fn apply(&mut self, msg: ChecksumMsg) -> bool {
+        fn apply_side(side: &mut HashMap<u64, (String, String)>, levels: Vec<(String, String)>) {
+            for (price, size) in levels {
+                let key = price.parse::<f64>().unwrap_or(0.0).to_bits();
+                if size.parse::<f64>().unwrap_or(0.0) == 0.0 {
+                    side.remove(&key);
+                } else {
+                    side.insert(key, (price, size));
+                }
+            }
+        }
+        apply_side(&mut self.asks, msg.asks);
+        apply_side(&mut self.bids, msg.bids);
+        self.checksum() == msg.checksum
+    }
+
+    // CRC32 of `bid1_price:bid1_size:ask1_price:ask1_size:bid2_price:...` over
+    // the best 25 levels, see <https://www.okx.com/docs-v5/en/#order-book-trading-market-data-ws-order-book-channel>
+    fn checksum(&self) -> i32 {
+        fn sorted(
+            side: &HashMap<u64, (String, String)>,
+            descending: bool,
+        ) -> Vec<&(String, String)> {
+            let mut keys = side.keys().copied().collect::<Vec<u64>>();
+            keys.sort_by(|a, b| f64::from_bits(*a).total_cmp(&f64::from_bits(*b)));
+            if descending {
+                keys.reverse();
+            }
+            keys.truncate(25);
+            keys.into_iter().map(|key| &side[&key]).collect()
+        }
+        let asks = sorted(&self.asks, false);
+        let bids = sorted(&self.bids, true);
+
+        let mut fields = Vec::new();
+        for i in 0..asks.len().max(bids.len()) {
+            for side in [&bids, &asks] {
+                if let Some((price, size)) = side.get(i) {
+                    fields.push(price.as_str());
+                    fields.push(size.as_str());
+                }
+            }
+        }
+        crc32fast::hash(fields.join(":").as_bytes()) as i32
+    }
+}
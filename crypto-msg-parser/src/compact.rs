@@ -0,0 +1,146 @@
+use crate::{MarketType, Order, OrderBookMsg};
+use std::collections::HashMap;
+
+/// Merges level2 incremental updates of each symbol within a time window into one message.
+///
+/// High-frequency L2 streams often touch the same price level several times
+/// within a few milliseconds. Since every level in an update carries the new
+/// absolute quantity, only the last quantity of each price level in a window
+/// matters, so merging them reduces volume without losing information.
+/// A quantity of 0 still means the level is removed.
+///
+/// Snapshots are never merged, they close the pending window of the symbol
+/// and are emitted as is.
+///
+/// Windows are based on the `timestamp` of messages, so the same input
+/// always produces the same output, no matter it is realtime or replayed.
+/// A quiet symbol would hold its last update forever, so realtime callers
+/// should call `tick()` periodically, e.g., once per `window` milliseconds.
+pub struct OrderBookCompactor {
+    window: i64,
+    pending: HashMap<(String, MarketType, String), PendingUpdate>,
+}
+
+struct PendingUpdate {
+    // timestamp of the first update in the window
+    window_start: i64,
+    // asks and bids merged so far, stamped with the latest update
+    merged: OrderBookMsg,
+    // number of updates merged
+    count: usize,
+}
+
+impl OrderBookCompactor {
+    /// Creates a compactor.
+    ///
+    /// # Arguments
+    ///
+    /// * `window` - Merge updates of a symbol within `window` milliseconds
+    pub fn new(window: u64) -> Self {
+        OrderBookCompactor {
+            window: window as i64,
+            pending: HashMap::new(),
+        }
+    }
+
+    /// Feeds a level2 message, returns messages ready to be emitted, in order.
+    ///
+    /// An update is buffered until a message of the same symbol arrives at
+    /// least `window` milliseconds after the first buffered update, a
+    /// snapshot of the same symbol arrives, or `tick()` closes the window.
+    pub fn update(&mut self, msg: OrderBookMsg) -> Vec<OrderBookMsg> {
+        let key = (msg.exchange.clone(), msg.market_type, msg.symbol.clone());
+        let mut emitted = Vec::new();
+
+        if msg.snapshot {
+            if let Some(pending) = self.pending.remove(&key) {
+                emitted.push(pending.finish());
+            }
+            emitted.push(msg);
+            return emitted;
+        }
+
+        match self.pending.get_mut(&key) {
+            Some(pending) if msg.timestamp - pending.window_start < self.window => {
+                pending.merge(msg);
+            }
+            _ => {
+                if let Some(pending) = self.pending.insert(
+                    key,
+                    PendingUpdate {
+                        window_start: msg.timestamp,
+                        merged: msg,
+                        count: 1,
+                    },
+                ) {
+                    emitted.push(pending.finish());
+                }
+            }
+        }
+        emitted
+    }
+
+    /// Emits buffered updates whose window has closed at `now`, in order.
+    ///
+    /// `now` is a Unix timestamp in milliseconds, i.e., the current time in
+    /// realtime, or the timestamp of the latest message when replaying.
+    pub fn tick(&mut self, now: i64) -> Vec<OrderBookMsg> {
+        let window = self.window;
+        let expired: Vec<(String, MarketType, String)> = self
+            .pending
+            .iter()
+            .filter(|(_, pending)| now - pending.window_start >= window)
+            .map(|(key, _)| key.clone())
+            .collect();
+        let mut emitted: Vec<OrderBookMsg> = expired
+            .into_iter()
+            .filter_map(|key| self.pending.remove(&key))
+            .map(|pending| pending.finish())
+            .collect();
+        emitted.sort_by_key(|msg| msg.timestamp);
+        emitted
+    }
+
+    /// Emits all buffered updates.
+    pub fn flush(&mut self) -> Vec<OrderBookMsg> {
+        let mut emitted: Vec<OrderBookMsg> = self
+            .pending
+            .drain()
+            .map(|(_, pending)| pending.finish())
+            .collect();
+        emitted.sort_by_key(|msg| msg.timestamp);
+        emitted
+    }
+}
+
+impl PendingUpdate {
+    fn merge(&mut self, msg: OrderBookMsg) {
+        merge_orders(&mut self.merged.asks, msg.asks);
+        merge_orders(&mut self.merged.bids, msg.bids);
+        // the first update keeps prev_seq_id, so that gaps can still be detected
+        self.merged.seq_id = msg.seq_id;
+        self.merged.timestamp = msg.timestamp;
+        self.merged.timestamp_us = msg.timestamp_us;
+        self.count += 1;
+    }
+
+    fn finish(mut self) -> OrderBookMsg {
+        if self.count > 1 {
+            // raw messages are dropped, keeping them would defeat the purpose
+            self.merged.json = String::new();
+        }
+        self.merged.asks.sort_by(|a, b| a.price.total_cmp(&b.price));
+        self.merged.bids.sort_by(|a, b| b.price.total_cmp(&a.price));
+        self.merged
+    }
+}
+
+// Later quantities of the same price level replace earlier ones
+fn merge_orders(merged: &mut Vec<Order>, orders: Vec<Order>) {
+    for order in orders {
+        match merged.iter_mut().find(|x| x.price == order.price) {
+            Some(existing) => *existing = order,
+            None => merged.push(order),
+        }
+    }
+}
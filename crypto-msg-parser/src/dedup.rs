@@ -0,0 +1,68 @@
+use crate::{MarketType, TradeMsg};
+use std::collections::{HashMap, HashSet, VecDeque};
+
+/// Drops trades already seen, e.g., recent trades replayed by the exchange
+/// after a reconnect and resubscribe.
+///
+/// Trades are identified by `trade_id` within each symbol, and only the last
+/// `capacity` trade ids of each symbol are remembered, so memory stays bounded
+/// no matter how long it runs. Trades without `trade_id` can't be checked and
+/// are never dropped.
+pub struct TradeDeduplicator {
+    capacity: usize,
+    windows: HashMap<(String, MarketType, String), TradeIdWindow>,
+}
+
+// The most recent trade ids of a symbol, oldest first
+#[derive(Default)]
+struct TradeIdWindow {
+    ids: HashSet<String>,
+    order: VecDeque<String>,
+}
+
+impl TradeDeduplicator {
+    /// Creates a deduplicator remembering the last `capacity` trade ids of each symbol.
+    ///
+    /// Exchanges replay at most a few hundred trades on subscription, 1000 is
+    /// a safe choice.
+    pub fn new(capacity: usize) -> Self {
+        assert!(capacity > 0, "capacity must be positive");
+        TradeDeduplicator {
+            capacity,
+            windows: HashMap::new(),
+        }
+    }
+
+    /// Returns true if the trade has been seen, otherwise remembers it and returns false.
+    pub fn is_duplicate(&mut self, trade: &TradeMsg) -> bool {
+        if trade.trade_id.is_empty() {
+            return false;
+        }
+        let window = self
+            .windows
+            .entry((
+                trade.exchange.clone(),
+                trade.market_type,
+                trade.symbol.clone(),
+            ))
+            .or_default();
+        if window.ids.contains(&trade.trade_id) {
+            return true;
+        }
+        if window.order.len() >= self.capacity {
+            let oldest = window.order.pop_front().unwrap();
+            window.ids.remove(&oldest);
+        }
+        window.ids.insert(trade.trade_id.clone());
+        window.order.push_back(trade.trade_id.clone());
+        false
+    }
+
+    /// Removes trades seen before, including duplicates within `trades`.
+    pub fn filter(&mut self, trades: Vec<TradeMsg>) -> Vec<TradeMsg> {
+        trades
+            .into_iter()
+            .filter(|trade| !self.is_duplicate(trade))
+            .collect()
+    }
+}
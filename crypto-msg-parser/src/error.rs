@@ -0,0 +1,129 @@
+use crypto_market_type::MarketType;
+use std::fmt;
+
+/// Errors returned by parse functions.
+#[derive(Debug)]
+pub enum ParseError {
+    /// The message is not valid JSON or doesn't match the expected layout
+    Json(serde_json::Error),
+    /// The exchange is not supported by this parse function
+    UnsupportedExchange(String),
+    /// The exchange doesn't have this kind of message in the market
+    UnsupportedMarketType(String, MarketType),
+    /// The symbol can't be normalized to a pair
+    UnknownSymbol(String),
+    /// Contract value of the pair is unknown, so quantities can't be calculated
+    MissingContractValue(String, MarketType, String),
+    /// A field exists but its value can't be interpreted
+    MalformedField(String),
+    /// A field can't be parsed, with the raw message truncated for context
+    InvalidField {
+        exchange: String,
+        field: String,
+        value: String,
+        raw_msg: String,
+    },
+}
+
+pub(crate) type Result<T> = std::result::Result<T, ParseError>;
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::Json(err) => write!(f, "{}", err),
+            ParseError::UnsupportedExchange(exchange) => {
+                write!(f, "{} is NOT supported yet", exchange)
+            }
+            ParseError::UnsupportedMarketType(exchange, market_type) => {
+                write!(f, "{} {} is NOT supported", exchange, market_type)
+            }
+            ParseError::UnknownSymbol(symbol) => write!(f, "Unknown symbol {}", symbol),
+            ParseError::MissingContractValue(exchange, market_type, pair) => write!(
+                f,
+                "Contract value of {} {} {} is unknown",
+                exchange, market_type, pair
+            ),
+            ParseError::MalformedField(reason) => write!(f, "Malformed field, {}", reason),
+            ParseError::InvalidField {
+                exchange,
+                field,
+                value,
+                raw_msg,
+            } => write!(
+                f,
+                "Invalid {} {:?} from {}, raw message: {}",
+                field, value, exchange, raw_msg
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ParseError::Json(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<serde_json::Error> for ParseError {
+    fn from(err: serde_json::Error) -> Self {
+        ParseError::Json(err)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ParseError;
+    use crate::{parse_funding_rate, parse_trade};
+    use crypto_market_type::MarketType;
+
+    #[test]
+    fn unsupported_exchange() {
+        let err = parse_trade("nonexistent", MarketType::Spot, "{}")
+            .err()
+            .unwrap();
+        assert!(
+            matches!(err, ParseError::UnsupportedExchange(exchange) if exchange == "nonexistent")
+        );
+    }
+
+    #[test]
+    fn unsupported_market_type() {
+        let err = parse_funding_rate("binance", MarketType::Spot, "{}")
+            .err()
+            .unwrap();
+        assert!(matches!(
+            err,
+            ParseError::UnsupportedMarketType(_, MarketType::Spot)
+        ));
+    }
+
+    #[test]
+    fn unknown_symbol() {
+        let msg = r#"{"stream":"nosuchpair@aggTrade","data":{"e":"aggTrade","E":1616176861895,"s":"NOSUCHPAIR","a":609402283,"p":"58186.26","q":"0.002","f":1069479426,"l":1069479426,"T":1616176861893,"m":true}}"#;
+        let err = parse_trade("binance", MarketType::LinearSwap, msg)
+            .err()
+            .unwrap();
+        assert!(matches!(err, ParseError::UnknownSymbol(symbol) if symbol == "NOSUCHPAIR"));
+    }
+
+    #[test]
+    fn invalid_number() {
+        let msg = r#"{"stream":"btcusdt@aggTrade","data":{"e":"aggTrade","E":1616176861895,"s":"BTCUSDT","a":609402283,"p":"not a price","q":"0.002","f":1069479426,"l":1069479426,"T":1616176861893,"m":true}}"#;
+        let err = parse_trade("binance", MarketType::LinearSwap, msg)
+            .err()
+            .unwrap();
+        assert!(matches!(err, ParseError::InvalidField { field, .. } if field == "p"));
+    }
+
+    #[test]
+    fn invalid_json() {
+        let err = parse_trade("binance", MarketType::Spot, "not json")
+            .err()
+            .unwrap();
+        assert!(matches!(err, ParseError::Json(_)));
+        assert!(std::error::Error::source(&err).is_some());
+    }
+}
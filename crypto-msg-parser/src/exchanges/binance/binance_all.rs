@@ -1,10 +1,19 @@
 use crypto_market_type::MarketType;
 
-use crate::{FundingRateMsg, MessageType, Order, OrderBookMsg, TradeMsg, TradeSide};
+use crate::{
+    BboMsg, FundingRateMsg, IndexPriceMsg, KlineMsg, LiquidationMsg, MarkPriceMsg, MessageType,
+    OpenInterestMsg, Order, OrderBookMsg, TickerMsg, TradeMsg, TradeSide,
+};
 
-use super::super::utils::calc_quantity_and_volume;
+use chrono::prelude::*;
+
+use super::super::utils::{
+    calc_open_interest_value, calc_quantity_and_volume, interval_to_period, normalize_pair,
+    original_json, parse_field, raw_json,
+};
+use crate::error::{ParseError, Result};
 use serde::{Deserialize, Serialize};
-use serde_json::{Result, Value};
+use serde_json::Value;
 use std::collections::HashMap;
 
 const EXCHANGE_NAME: &str = "binance";
@@ -50,6 +59,19 @@ pub type RawOrder = [String; 2];
 
 // see https://binance-docs.github.io/apidocs/spot/en/#diff-depth-stream
 // https://binance-docs.github.io/apidocs/delivery/en/#diff-book-depth-streams
+//
+// To maintain a local orderbook, binance documents the following procedure:
+//
+// 1. Subscribe to the depth stream and buffer the events
+// 2. Fetch a snapshot from the REST API, its lastUpdateId becomes seq_id
+// 3. Drop buffered events with u < lastUpdateId
+// 4. The first event to apply must satisfy U <= lastUpdateId <= u, spot
+//    events start at lastUpdateId + 1 instead
+// 5. Afterwards each event's pu must equal the u of the previous event,
+//    for spot, which has no pu, U must equal the previous u + 1
+//
+// parse_l2() maps u to seq_id and pu (or U - 1 for spot) to prev_seq_id,
+// so that OrderBookManager can follow exactly this procedure.
 #[derive(Serialize, Deserialize)]
 #[allow(non_snake_case)]
 struct RawOrderbookMsg {
@@ -66,6 +88,47 @@ struct RawOrderbookMsg {
     extra: HashMap<String, Value>,
 }
 
+// see https://binance-docs.github.io/apidocs/spot/en/#individual-symbol-book-ticker-streams
+// https://binance-docs.github.io/apidocs/futures/en/#individual-symbol-book-ticker-streams
+#[derive(Serialize, Deserialize)]
+#[allow(non_snake_case)]
+struct RawBboMsg {
+    u: u64,         // order book updateId
+    s: String,      // Symbol
+    b: String,      // best bid price
+    B: String,      // best bid qty
+    a: String,      // best ask price
+    A: String,      // best ask qty
+    E: Option<i64>, // Event time, futures only
+    T: Option<i64>, // Transaction time, futures only
+    #[serde(flatten)]
+    extra: HashMap<String, Value>,
+}
+
+// see https://binance-docs.github.io/apidocs/spot/en/#individual-symbol-ticker-streams
+// https://binance-docs.github.io/apidocs/futures/en/#individual-symbol-ticker-streams
+#[derive(Serialize, Deserialize)]
+#[allow(non_snake_case)]
+struct RawTickerMsg {
+    e: String,         // Event type
+    E: i64,            // Event time
+    s: String,         // Symbol
+    P: String,         // Price change percent
+    o: String,         // Open price
+    h: String,         // High price
+    l: String,         // Low price
+    c: String,         // Last price
+    Q: String,         // Last quantity
+    v: String,         // Total traded base asset volume, contracts for inverse markets
+    q: String,         // Total traded quote asset volume, base asset for inverse markets
+    b: Option<String>, // Best bid price, spot only
+    B: Option<String>, // Best bid quantity, spot only
+    a: Option<String>, // Best ask price, spot only
+    A: Option<String>, // Best ask quantity, spot only
+    #[serde(flatten)]
+    extra: HashMap<String, Value>,
+}
+
 #[derive(Serialize, Deserialize)]
 struct WebsocketMsg<T: Sized> {
     stream: String,
@@ -74,91 +137,211 @@ struct WebsocketMsg<T: Sized> {
 
 pub(crate) fn parse_trade(market_type: MarketType, msg: &str) -> Result<Vec<TradeMsg>> {
     let obj = serde_json::from_str::<HashMap<String, Value>>(msg)?;
-    let data = obj.get("data").unwrap();
-    let event_type = data["e"].as_str().unwrap();
+    // Combined streams wrap the payload in `data`, raw streams don't
+    let data = if let Some(data) = obj.get("data") {
+        data.clone()
+    } else {
+        serde_json::from_str::<Value>(msg)?
+    };
+    let event_type = data["e"].as_str().unwrap_or_default();
 
-    match event_type {
+    // `@trade` and `@aggTrade` share some field names with different meanings,
+    // for example, `a` is the aggregate trade ID in aggTrade but the seller
+    // order ID in trade, so each of them must be deserialized by its own struct.
+    let (symbol, price, quantity, timestamp, is_buyer_maker, trade_id) = match event_type {
         "aggTrade" => {
-            let agg_trade: AggTradeMsg = serde_json::from_value(data.clone()).unwrap();
-            let pair = crypto_pair::normalize_pair(&agg_trade.s, EXCHANGE_NAME).unwrap();
-            let price = agg_trade.p.parse::<f64>().unwrap();
-            let quantity = agg_trade.q.parse::<f64>().unwrap();
-            let (quantity_base, quantity_quote, quantity_contract) =
-                calc_quantity_and_volume(EXCHANGE_NAME, market_type, &pair, price, quantity);
-            let trade = TradeMsg {
-                exchange: EXCHANGE_NAME.to_string(),
-                market_type,
-                symbol: agg_trade.s.clone(),
-                pair,
-                msg_type: MessageType::Trade,
-                timestamp: agg_trade.T,
-                price,
-                quantity_base,
-                quantity_quote,
-                quantity_contract,
-                side: if agg_trade.m {
-                    TradeSide::Sell
-                } else {
-                    TradeSide::Buy
-                },
-                trade_id: agg_trade.a.to_string(),
-                json: msg.to_string(),
-            };
-
-            Ok(vec![trade])
+            let agg_trade: AggTradeMsg = serde_json::from_value(data)?;
+            (
+                agg_trade.s,
+                agg_trade.p,
+                agg_trade.q,
+                agg_trade.T,
+                agg_trade.m,
+                agg_trade.a,
+            )
         }
         "trade" => {
-            let raw_trade: RawTradeMsg = serde_json::from_value(data.clone()).unwrap();
-            let pair = crypto_pair::normalize_pair(&raw_trade.s, EXCHANGE_NAME).unwrap();
-            let price = raw_trade.p.parse::<f64>().unwrap();
-            let quantity = raw_trade.q.parse::<f64>().unwrap();
-            let (quantity_base, quantity_quote, quantity_contract) =
-                calc_quantity_and_volume(EXCHANGE_NAME, market_type, &pair, price, quantity);
-            let trade = TradeMsg {
-                exchange: EXCHANGE_NAME.to_string(),
-                market_type,
-                symbol: raw_trade.s.clone(),
-                pair,
-                msg_type: MessageType::Trade,
-                timestamp: raw_trade.T,
-                price,
-                quantity_base,
-                quantity_quote,
-                quantity_contract,
-                side: if raw_trade.m {
-                    TradeSide::Sell
-                } else {
-                    TradeSide::Buy
-                },
-                trade_id: raw_trade.t.to_string(),
-                json: msg.to_string(),
-            };
-
-            Ok(vec![trade])
+            let raw_trade: RawTradeMsg = serde_json::from_value(data)?;
+            (
+                raw_trade.s,
+                raw_trade.p,
+                raw_trade.q,
+                raw_trade.T,
+                raw_trade.m,
+                raw_trade.t,
+            )
         }
-        _ => panic!("Unsupported event type {}", event_type),
+        _ => {
+            return Err(ParseError::MalformedField(format!(
+                "Unsupported event type {} in {}",
+                event_type, msg
+            )))
+        }
+    };
+
+    let pair = normalize_pair(&symbol, EXCHANGE_NAME)?;
+    let price = parse_field(EXCHANGE_NAME, "p", &price, msg)?;
+    let quantity = parse_field(EXCHANGE_NAME, "q", &quantity, msg)?;
+    let (quantity_base, quantity_quote, quantity_contract) =
+        calc_quantity_and_volume(EXCHANGE_NAME, market_type, &pair, price, quantity)?;
+    let trade = TradeMsg {
+        exchange: EXCHANGE_NAME.to_string(),
+        market_type,
+        symbol,
+        pair,
+        msg_type: MessageType::Trade,
+        timestamp,
+        timestamp_us: None,
+        price,
+        quantity_base,
+        quantity_quote,
+        quantity_contract,
+        // The buyer is the maker, which means the seller is the taker
+        side: if is_buyer_maker {
+            TradeSide::Sell
+        } else {
+            TradeSide::Buy
+        },
+        trade_id: trade_id.to_string(),
+        json: original_json(msg),
+    };
+
+    Ok(vec![trade])
+}
+
+pub(crate) fn parse_bbo(
+    market_type: MarketType,
+    msg: &str,
+    timestamp: Option<i64>,
+) -> Result<Vec<BboMsg>> {
+    let ws_msg = serde_json::from_str::<WebsocketMsg<RawBboMsg>>(msg)?;
+    let raw_bbo = ws_msg.data;
+    let pair = normalize_pair(&raw_bbo.s, EXCHANGE_NAME)?;
+
+    let bid_price = parse_field(EXCHANGE_NAME, "b", &raw_bbo.b, msg)?;
+    let ask_price = parse_field(EXCHANGE_NAME, "a", &raw_bbo.a, msg)?;
+    let (bid_quantity, _, _) = calc_quantity_and_volume(
+        EXCHANGE_NAME,
+        market_type,
+        &pair,
+        bid_price,
+        parse_field(EXCHANGE_NAME, "B", &raw_bbo.B, msg)?,
+    )?;
+    let (ask_quantity, _, _) = calc_quantity_and_volume(
+        EXCHANGE_NAME,
+        market_type,
+        &pair,
+        ask_price,
+        parse_field(EXCHANGE_NAME, "A", &raw_bbo.A, msg)?,
+    )?;
+
+    // Spot bookTicker messages don't have timestamp
+    let timestamp = raw_bbo.T.or(raw_bbo.E).or(timestamp).ok_or_else(|| {
+        ParseError::MalformedField(format!("No timestamp in {} and none given", msg))
+    })?;
+
+    let bbo = BboMsg {
+        exchange: EXCHANGE_NAME.to_string(),
+        market_type,
+        symbol: raw_bbo.s.clone(),
+        pair,
+        msg_type: MessageType::BBO,
+        timestamp,
+        bid_price,
+        bid_quantity,
+        ask_price,
+        ask_quantity,
+        synthetic: false,
+        json: original_json(msg),
+    };
+    Ok(vec![bbo])
+}
+
+pub(crate) fn parse_ticker(market_type: MarketType, msg: &str) -> Result<Vec<TickerMsg>> {
+    let ws_msg = serde_json::from_str::<WebsocketMsg<RawTickerMsg>>(msg)?;
+    let raw_ticker = ws_msg.data;
+    if raw_ticker.e != "24hrTicker" {
+        return Err(ParseError::MalformedField(format!(
+            "event type {} is not 24hrTicker",
+            raw_ticker.e
+        )));
     }
+    let pair = normalize_pair(&raw_ticker.s, EXCHANGE_NAME)?;
+    let parse = |field: &str, value: &str| parse_field::<f64>(EXCHANGE_NAME, field, value, msg);
+    let parse_opt = |field: &str, value: &Option<String>| -> Result<Option<f64>> {
+        value.as_deref().map(|x| parse(field, x)).transpose()
+    };
+
+    let close = parse("c", &raw_ticker.c)?;
+    let (volume, quote_volume) =
+        if market_type == MarketType::InverseSwap || market_type == MarketType::InverseFuture {
+            // v is the volume in contracts and q is the volume in base currency
+            let (_, quote_volume, _) = calc_quantity_and_volume(
+                EXCHANGE_NAME,
+                market_type,
+                &pair,
+                close,
+                parse("v", &raw_ticker.v)?,
+            )?;
+            (parse("q", &raw_ticker.q)?, quote_volume)
+        } else {
+            (parse("v", &raw_ticker.v)?, parse("q", &raw_ticker.q)?)
+        };
+    let (last_quantity, _, _) = calc_quantity_and_volume(
+        EXCHANGE_NAME,
+        market_type,
+        &pair,
+        close,
+        parse("Q", &raw_ticker.Q)?,
+    )?;
+
+    let ticker = TickerMsg {
+        exchange: EXCHANGE_NAME.to_string(),
+        market_type,
+        symbol: raw_ticker.s.clone(),
+        pair,
+        msg_type: MessageType::Ticker,
+        timestamp: raw_ticker.E,
+        open: parse("o", &raw_ticker.o)?,
+        high: parse("h", &raw_ticker.h)?,
+        low: parse("l", &raw_ticker.l)?,
+        close,
+        volume,
+        quote_volume,
+        price_change_percent: Some(parse("P", &raw_ticker.P)?),
+        last_quantity: Some(last_quantity),
+        best_bid_price: parse_opt("b", &raw_ticker.b)?,
+        best_bid_quantity: parse_opt("B", &raw_ticker.B)?,
+        best_ask_price: parse_opt("a", &raw_ticker.a)?,
+        best_ask_quantity: parse_opt("A", &raw_ticker.A)?,
+        open_interest: None,
+        open_interest_quote: None,
+        json: original_json(msg),
+    };
+    Ok(vec![ticker])
 }
 
 pub(crate) fn parse_l2(market_type: MarketType, msg: &str) -> Result<Vec<OrderBookMsg>> {
     let ws_msg = serde_json::from_str::<WebsocketMsg<RawOrderbookMsg>>(msg)?;
-    let pair = crypto_pair::normalize_pair(&ws_msg.data.s, EXCHANGE_NAME).unwrap();
+    let pair = normalize_pair(&ws_msg.data.s, EXCHANGE_NAME)?;
 
-    let parse_order = |raw_order: &RawOrder| -> Order {
-        let price = raw_order[0].parse::<f64>().unwrap();
+    let parse_order = |raw_order: &RawOrder| -> Result<Order> {
+        let price = parse_field(EXCHANGE_NAME, "price", &raw_order[0], msg)?;
         let (quantity_base, quantity_quote, quantity_contract) = calc_quantity_and_volume(
             EXCHANGE_NAME,
             market_type,
             &pair,
             price,
-            raw_order[1].parse::<f64>().unwrap(),
-        );
-        Order {
+            parse_field(EXCHANGE_NAME, "quantity", &raw_order[1], msg)?,
+        )?;
+        Ok(Order {
             price,
             quantity_base,
             quantity_quote,
             quantity_contract,
-        }
+            order_id: None,
+            order_type: None,
+        })
     };
 
     let orderbook = OrderBookMsg {
@@ -170,28 +353,154 @@ pub(crate) fn parse_l2(market_type: MarketType, msg: &str) -> Result<Vec<OrderBo
         timestamp: if market_type == MarketType::Spot {
             ws_msg.data.E
         } else {
-            ws_msg.data.T.unwrap()
+            ws_msg.data.T.ok_or_else(|| {
+                ParseError::MalformedField(format!("No transaction time T in {}", msg))
+            })?
         },
+        timestamp_us: None,
         seq_id: Some(ws_msg.data.u),
-        prev_seq_id: ws_msg.data.pu,
+        // Spot messages don't have pu, the previous message ends right before U if there is no gap
+        prev_seq_id: ws_msg.data.pu.or_else(|| ws_msg.data.U.checked_sub(1)),
         asks: ws_msg
             .data
             .a
             .iter()
-            .map(|raw_order| parse_order(raw_order))
-            .collect::<Vec<Order>>(),
+            .map(&parse_order)
+            .collect::<Result<Vec<Order>>>()?,
         bids: ws_msg
             .data
             .b
             .iter()
-            .map(|raw_order| parse_order(raw_order))
-            .collect::<Vec<Order>>(),
+            .map(&parse_order)
+            .collect::<Result<Vec<Order>>>()?,
         snapshot: false,
-        json: msg.to_string(),
+        json: original_json(msg),
+    };
+    Ok(vec![orderbook])
+}
+
+// https://binance-docs.github.io/apidocs/spot/en/#order-book
+// https://binance-docs.github.io/apidocs/futures/en/#order-book
+#[derive(Serialize, Deserialize)]
+#[allow(non_snake_case)]
+struct RawL2SnapshotMsg {
+    lastUpdateId: u64,
+    E: Option<i64>, // Message output time, not available in spot
+    T: Option<i64>, // Transaction time, not available in spot
+    bids: Vec<RawOrder>,
+    asks: Vec<RawOrder>,
+    #[serde(flatten)]
+    extra: HashMap<String, Value>,
+}
+
+pub(crate) fn parse_l2_snapshot(
+    market_type: MarketType,
+    symbol: &str,
+    msg: &str,
+) -> Result<Vec<OrderBookMsg>> {
+    let raw_snapshot = serde_json::from_str::<RawL2SnapshotMsg>(msg)?;
+    let pair = normalize_pair(symbol, EXCHANGE_NAME)?;
+
+    let parse_order = |raw_order: &RawOrder| -> Result<Order> {
+        let price = parse_field(EXCHANGE_NAME, "price", &raw_order[0], msg)?;
+        let (quantity_base, quantity_quote, quantity_contract) = calc_quantity_and_volume(
+            EXCHANGE_NAME,
+            market_type,
+            &pair,
+            price,
+            parse_field(EXCHANGE_NAME, "quantity", &raw_order[1], msg)?,
+        )?;
+        Ok(Order {
+            price,
+            quantity_base,
+            quantity_quote,
+            quantity_contract,
+            order_id: None,
+            order_type: None,
+        })
+    };
+
+    let orderbook = OrderBookMsg {
+        exchange: EXCHANGE_NAME.to_string(),
+        market_type,
+        symbol: symbol.to_string(),
+        pair: pair.clone(),
+        msg_type: MessageType::L2Snapshot,
+        timestamp: raw_snapshot
+            .T
+            .or(raw_snapshot.E)
+            .unwrap_or_else(|| Utc::now().timestamp_millis()),
+        timestamp_us: None,
+        seq_id: Some(raw_snapshot.lastUpdateId),
+        prev_seq_id: None,
+        asks: raw_snapshot
+            .asks
+            .iter()
+            .map(&parse_order)
+            .collect::<Result<Vec<Order>>>()?,
+        bids: raw_snapshot
+            .bids
+            .iter()
+            .map(&parse_order)
+            .collect::<Result<Vec<Order>>>()?,
+        snapshot: true,
+        json: original_json(msg),
     };
     Ok(vec![orderbook])
 }
 
+// https://binance-docs.github.io/apidocs/spot/en/#kline-candlestick-data
+// https://binance-docs.github.io/apidocs/futures/en/#kline-candlestick-data
+#[derive(Serialize, Deserialize)]
+struct RawKline(
+    i64,    // Open time
+    String, // Open
+    String, // High
+    String, // Low
+    String, // Close
+    String, // Volume
+    i64,    // Close time
+    String, // Quote asset volume
+    i64,    // Number of trades
+    String, // Taker buy base asset volume
+    String, // Taker buy quote asset volume
+    Value,  // Ignore
+);
+
+pub(crate) fn parse_candlestick_history(
+    market_type: MarketType,
+    symbol: &str,
+    interval: usize,
+    msg: &str,
+) -> Result<Vec<KlineMsg>> {
+    let raw_klines = serde_json::from_str::<Vec<RawKline>>(msg)?;
+    let pair = normalize_pair(symbol, EXCHANGE_NAME)?;
+    let period = interval_to_period(interval);
+    let parse = |field: &str, value: &str| parse_field::<f64>(EXCHANGE_NAME, field, value, msg);
+
+    raw_klines
+        .iter()
+        .map(|raw_kline| {
+            Ok(KlineMsg {
+                exchange: EXCHANGE_NAME.to_string(),
+                market_type,
+                symbol: symbol.to_string(),
+                pair: pair.clone(),
+                msg_type: MessageType::Candlestick,
+                timestamp: raw_kline.0,
+                json: raw_json(raw_kline),
+                open: parse("open", &raw_kline.1)?,
+                high: parse("high", &raw_kline.2)?,
+                low: parse("low", &raw_kline.3)?,
+                close: parse("close", &raw_kline.4)?,
+                volume: parse("volume", &raw_kline.5)?,
+                period: period.clone(),
+                quote_volume: Some(parse("quote_volume", &raw_kline.7)?),
+            })
+        })
+        .collect()
+}
+
 #[derive(Serialize, Deserialize)]
 #[allow(non_snake_case)]
 struct RawFundingRateMsg {
@@ -207,43 +516,243 @@ struct RawFundingRateMsg {
     extra: HashMap<String, Value>,
 }
 
+// Extracts markPriceUpdate events from @markPrice and !markPrice@arr
+fn parse_mark_price_events(msg: &str) -> Result<Vec<RawFundingRateMsg>> {
+    let obj = serde_json::from_str::<HashMap<String, Value>>(msg)?;
+    let stream = obj
+        .get("stream")
+        .and_then(|stream| stream.as_str())
+        .unwrap_or_default();
+    if stream.starts_with("!markPrice@arr") {
+        Ok(serde_json::from_value::<Vec<RawFundingRateMsg>>(
+            obj.get("data").cloned().unwrap_or(Value::Null),
+        )?)
+    } else if stream.ends_with("@markPrice") || stream.contains("@markPrice@") {
+        Ok(vec![serde_json::from_value::<RawFundingRateMsg>(
+            obj.get("data").cloned().unwrap_or(Value::Null),
+        )?])
+    } else {
+        Err(ParseError::MalformedField(format!(
+            "Unknown mark price message {}",
+            msg
+        )))
+    }
+}
+
 pub(crate) fn parse_funding_rate(
     market_type: MarketType,
     msg: &str,
 ) -> Result<Vec<FundingRateMsg>> {
-    let obj = serde_json::from_str::<HashMap<String, Value>>(msg)?;
-    let stream = obj.get("stream").unwrap().as_str().unwrap();
-    let data = if stream == "!markPrice@arr" {
-        obj.get("data")
-            .unwrap()
-            .as_array()
-            .unwrap()
-            .iter()
-            .map(|x| serde_json::from_value::<RawFundingRateMsg>(x.clone()).unwrap())
-            .collect()
-    } else if stream.ends_with("@markPrice") {
-        vec![serde_json::from_value::<RawFundingRateMsg>(obj.get("data").unwrap().clone()).unwrap()]
-    } else {
-        panic!("Unknown funding rate messaeg {}", msg);
-    };
-    let mut funding_rates: Vec<FundingRateMsg> = data
+    let mut funding_rates: Vec<FundingRateMsg> = parse_mark_price_events(msg)?
         .into_iter()
         .filter(|x| !x.r.is_empty())
-        .map(|raw_msg| FundingRateMsg {
-            exchange: EXCHANGE_NAME.to_string(),
-            market_type,
-            symbol: raw_msg.s.clone(),
-            pair: crypto_pair::normalize_pair(&raw_msg.s, EXCHANGE_NAME).unwrap(),
-            msg_type: MessageType::FundingRate,
-            timestamp: raw_msg.E,
-            funding_rate: raw_msg.r.parse::<f64>().unwrap(),
-            funding_time: raw_msg.T,
-            estimated_rate: None,
-            json: serde_json::to_string(&raw_msg).unwrap(),
+        .map(|raw_msg| {
+            Ok(FundingRateMsg {
+                exchange: EXCHANGE_NAME.to_string(),
+                market_type,
+                symbol: raw_msg.s.clone(),
+                pair: normalize_pair(&raw_msg.s, EXCHANGE_NAME)?,
+                msg_type: MessageType::FundingRate,
+                timestamp: raw_msg.E,
+                funding_rate: parse_field(EXCHANGE_NAME, "r", &raw_msg.r, msg)?,
+                funding_time: raw_msg.T,
+                estimated_rate: None,
+                mark_price: Some(parse_field(EXCHANGE_NAME, "p", &raw_msg.p, msg)?),
+                json: raw_json(&raw_msg),
+            })
         })
-        .collect();
+        .collect::<Result<Vec<FundingRateMsg>>>()?;
     if funding_rates.len() == 1 {
-        funding_rates[0].json = msg.to_string();
+        funding_rates[0].json = original_json(msg);
     }
     Ok(funding_rates)
 }
+
+pub(crate) fn parse_mark_price(market_type: MarketType, msg: &str) -> Result<Vec<MarkPriceMsg>> {
+    let mut mark_prices: Vec<MarkPriceMsg> = parse_mark_price_events(msg)?
+        .into_iter()
+        .map(|raw_msg| {
+            let index_price = match raw_msg.i.as_deref() {
+                Some(i) => Some(parse_field(EXCHANGE_NAME, "i", i, msg)?),
+                None => None,
+            };
+            // P is empty or zero outside of the settlement window
+            let estimated_settle_price = match raw_msg.P.parse::<f64>() {
+                Ok(price) if price > 0.0 => Some(price),
+                _ => None,
+            };
+            Ok(MarkPriceMsg {
+                exchange: EXCHANGE_NAME.to_string(),
+                market_type,
+                symbol: raw_msg.s.clone(),
+                pair: normalize_pair(&raw_msg.s, EXCHANGE_NAME)?,
+                msg_type: MessageType::MarkPrice,
+                timestamp: raw_msg.E,
+                mark_price: parse_field(EXCHANGE_NAME, "p", &raw_msg.p, msg)?,
+                index_price,
+                estimated_settle_price,
+                json: raw_json(&raw_msg),
+            })
+        })
+        .collect::<Result<Vec<MarkPriceMsg>>>()?;
+    if mark_prices.len() == 1 {
+        mark_prices[0].json = original_json(msg);
+    }
+    Ok(mark_prices)
+}
+
+// see https://binance-docs.github.io/apidocs/delivery/en/#index-price-stream
+#[derive(Serialize, Deserialize)]
+#[allow(non_snake_case)]
+struct RawIndexPriceMsg {
+    e: String, // Event type
+    E: i64,    // Event time
+    i: String, // Pair
+    p: String, // Index price
+    #[serde(flatten)]
+    extra: HashMap<String, Value>,
+}
+
+pub(crate) fn parse_index_price(market_type: MarketType, msg: &str) -> Result<Vec<IndexPriceMsg>> {
+    let obj = serde_json::from_str::<HashMap<String, Value>>(msg)?;
+    let stream = obj
+        .get("stream")
+        .and_then(|stream| stream.as_str())
+        .unwrap_or_default();
+    let mut index_prices: Vec<IndexPriceMsg> =
+        if stream.ends_with("@indexPrice") || stream.contains("@indexPrice@") {
+            let raw_msg = serde_json::from_value::<RawIndexPriceMsg>(
+                obj.get("data").cloned().unwrap_or(Value::Null),
+            )?;
+            // COIN-M pairs such as BTCUSD are pairs of perpetual swaps without the suffix
+            let pair = normalize_pair(&format!("{}_PERP", raw_msg.i), EXCHANGE_NAME)?;
+            vec![IndexPriceMsg {
+                exchange: EXCHANGE_NAME.to_string(),
+                market_type,
+                symbol: raw_msg.i.clone(),
+                pair,
+                msg_type: MessageType::IndexPrice,
+                timestamp: raw_msg.E,
+                index_price: parse_field(EXCHANGE_NAME, "p", &raw_msg.p, msg)?,
+                json: raw_json(&raw_msg),
+            }]
+        } else {
+            // markPriceUpdate carries the index price of the symbol
+            parse_mark_price_events(msg)?
+                .into_iter()
+                .filter_map(|raw_msg| raw_msg.i.clone().map(|i| (i, raw_msg)))
+                .map(|(i, raw_msg)| {
+                    Ok(IndexPriceMsg {
+                        exchange: EXCHANGE_NAME.to_string(),
+                        market_type,
+                        symbol: raw_msg.s.clone(),
+                        pair: normalize_pair(&raw_msg.s, EXCHANGE_NAME)?,
+                        msg_type: MessageType::IndexPrice,
+                        timestamp: raw_msg.E,
+                        index_price: parse_field(EXCHANGE_NAME, "i", &i, msg)?,
+                        json: raw_json(&raw_msg),
+                    })
+                })
+                .collect::<Result<Vec<IndexPriceMsg>>>()?
+        };
+    if index_prices.len() == 1 {
+        index_prices[0].json = original_json(msg);
+    }
+    Ok(index_prices)
+}
+
+// see https://binance-docs.github.io/apidocs/futures/en/#open-interest
+// see https://binance-docs.github.io/apidocs/delivery/en/#open-interest
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawOpenInterestMsg {
+    symbol: String,
+    open_interest: String,
+    time: i64,
+    #[serde(flatten)]
+    extra: HashMap<String, Value>,
+}
+
+pub(crate) fn parse_open_interest(
+    market_type: MarketType,
+    msg: &str,
+) -> Result<Vec<OpenInterestMsg>> {
+    let raw_msg = serde_json::from_str::<RawOpenInterestMsg>(msg)?;
+    let pair = normalize_pair(&raw_msg.symbol, EXCHANGE_NAME)?;
+    let open_interest = parse_field(EXCHANGE_NAME, "openInterest", &raw_msg.open_interest, msg)?;
+    let open_interest_msg = OpenInterestMsg {
+        exchange: EXCHANGE_NAME.to_string(),
+        market_type,
+        symbol: raw_msg.symbol,
+        open_interest_value: calc_open_interest_value(
+            EXCHANGE_NAME,
+            market_type,
+            &pair,
+            open_interest,
+        ),
+        pair,
+        msg_type: MessageType::OpenInterest,
+        timestamp: raw_msg.time,
+        open_interest,
+        json: original_json(msg),
+    };
+    Ok(vec![open_interest_msg])
+}
+
+// see https://binance-docs.github.io/apidocs/futures/en/#liquidation-order-streams
+#[derive(Serialize, Deserialize)]
+#[allow(non_snake_case)]
+struct RawForceOrder {
+    s: String,  // Symbol
+    S: String,  // Side, SELL means a long position was liquidated
+    p: String,  // Price
+    ap: String, // Average Price
+    q: String,  // Original Quantity
+    T: i64,     // Order Trade Time
+    #[serde(flatten)]
+    extra: HashMap<String, Value>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[allow(non_snake_case)]
+struct RawLiquidationMsg {
+    e: String, // Event type, forceOrder
+    E: i64,    // Event time
+    o: RawForceOrder,
+    #[serde(flatten)]
+    extra: HashMap<String, Value>,
+}
+
+pub(crate) fn parse_liquidation(market_type: MarketType, msg: &str) -> Result<Vec<LiquidationMsg>> {
+    let ws_msg = serde_json::from_str::<WebsocketMsg<RawLiquidationMsg>>(msg)?;
+    let raw_order = ws_msg.data.o;
+    let pair = normalize_pair(&raw_order.s, EXCHANGE_NAME)?;
+    // average price is 0 if the order hasn't been filled yet
+    let price = match parse_field::<f64>(EXCHANGE_NAME, "ap", &raw_order.ap, msg)? {
+        ap if ap > 0.0 => ap,
+        _ => parse_field(EXCHANGE_NAME, "p", &raw_order.p, msg)?,
+    };
+    let (quantity_base, quantity_quote, quantity_contract) = calc_quantity_and_volume(
+        EXCHANGE_NAME,
+        market_type,
+        &pair,
+        price,
+        parse_field(EXCHANGE_NAME, "q", &raw_order.q, msg)?,
+    )?;
+
+    let liquidation = LiquidationMsg {
+        exchange: EXCHANGE_NAME.to_string(),
+        market_type,
+        symbol: raw_order.s.clone(),
+        pair,
+        msg_type: MessageType::Liquidation,
+        timestamp: raw_order.T,
+        side: parse_field::<TradeSide>(EXCHANGE_NAME, "S", &raw_order.S, msg)?,
+        price,
+        quantity_base,
+        quantity_quote,
+        quantity_contract,
+        json: original_json(msg),
+    };
+    Ok(vec![liquidation])
+}
@@ -1,13 +1,36 @@
 use crypto_market_type::MarketType;
 
-use crate::{MessageType, TradeMsg, TradeSide};
+use crate::{MessageType, Order, OrderBookMsg, TradeMsg, TradeSide};
 
+use super::super::utils::{
+    calc_quantity_and_volume, normalize_pair, original_json, parse_field, raw_json,
+};
+
+use crate::error::{ParseError, Result};
 use serde::{Deserialize, Serialize};
-use serde_json::{Result, Value};
+use serde_json::Value;
 use std::collections::HashMap;
 
 const EXCHANGE_NAME: &str = "binance";
 
+// Option symbols look like BTC-220930-40000-C, i.e., underlying-expiry(YYMMDD)-strike-C/P,
+// options are quoted and settled in USDT
+fn get_pair(symbol: &str) -> Option<String> {
+    let v: Vec<&str> = symbol.split('-').collect();
+    if v.len() != 4 {
+        return None;
+    }
+    let (underlying, expiry, strike, option_type) = (v[0], v[1], v[2], v[3]);
+    if expiry.len() != 6 || expiry.parse::<u32>().is_err() || strike.parse::<f64>().is_err() {
+        return None;
+    }
+    if option_type != "C" && option_type != "P" {
+        return None;
+    }
+    Some(format!("{}/USDT", underlying))
+}
+
+// Legacy vanilla options, see https://binance-docs.github.io/apidocs/voptions/en/
 #[derive(Serialize, Deserialize)]
 #[allow(non_snake_case)]
 struct OptionTradeMsg {
@@ -32,6 +55,38 @@ struct OptionTradeAllMsg {
     t: Vec<OptionTradeMsg>,
 }
 
+// European options, see https://binance-docs.github.io/apidocs/voptions/en/#trade-streams
+#[derive(Serialize, Deserialize)]
+#[allow(non_snake_case)]
+struct EapiTradeMsg {
+    e: String, // Event type
+    E: i64,    // Event time
+    s: String, // Symbol
+    t: Value,  // Trade ID
+    p: String, // Price
+    q: String, // Quantity
+    T: i64,    // Trade completed time
+    S: Value,  // Direction, -1 means sell, 1 means buy
+    #[serde(flatten)]
+    extra: HashMap<String, Value>,
+}
+
+// see https://binance-docs.github.io/apidocs/voptions/en/#partial-book-depth-streams
+#[derive(Serialize, Deserialize)]
+#[allow(non_snake_case)]
+struct EapiOrderbookMsg {
+    e: String,      // Event type
+    E: i64,         // Event time
+    T: i64,         // Transaction time
+    s: String,      // Symbol
+    u: Option<u64>, // Update ID
+    pu: Option<u64>,
+    b: Vec<[String; 2]>,
+    a: Vec<[String; 2]>,
+    #[serde(flatten)]
+    extra: HashMap<String, Value>,
+}
+
 #[derive(Serialize, Deserialize)]
 struct WebsocketMsg<T: Sized> {
     stream: String,
@@ -40,30 +95,51 @@ struct WebsocketMsg<T: Sized> {
 
 pub(crate) fn parse_trade(msg: &str) -> Result<Vec<TradeMsg>> {
     let obj = serde_json::from_str::<HashMap<String, Value>>(msg)?;
-    let data = obj.get("data").unwrap();
-    let event_type = data.get("e").unwrap().as_str().unwrap();
+    let data = obj
+        .get("data")
+        .ok_or_else(|| ParseError::MalformedField(format!("No data in {}", msg)))?;
+    let event_type = data["e"]
+        .as_str()
+        .ok_or_else(|| ParseError::MalformedField(format!("No event type in {}", msg)))?;
 
-    assert_eq!(event_type, "trade_all");
+    match event_type {
+        "trade_all" => parse_trade_all(data.clone(), msg),
+        "trade" => parse_eapi_trade(msg),
+        _ => Err(ParseError::MalformedField(format!(
+            "Unsupported event type {}",
+            event_type
+        ))),
+    }
+}
 
-    let all_trades: OptionTradeAllMsg = serde_json::from_value(data.clone()).unwrap();
-    let trades: Vec<TradeMsg> = all_trades
+fn parse_trade_all(data: Value, msg: &str) -> Result<Vec<TradeMsg>> {
+    let all_trades: OptionTradeAllMsg = serde_json::from_value(data)?;
+    let trades = all_trades
         .t
         .into_iter()
         .map(|trade| {
-            let pair = crypto_pair::normalize_pair(&trade.S, EXCHANGE_NAME).unwrap();
-            let price = trade.p.parse::<f64>().unwrap();
-            let quantity = trade.q.parse::<f64>().unwrap();
-            TradeMsg {
+            let pair = normalize_pair(&trade.S, EXCHANGE_NAME)?;
+            let price = parse_field(EXCHANGE_NAME, "p", &trade.p, msg)?;
+            let quantity = parse_field(EXCHANGE_NAME, "q", &trade.q, msg)?;
+            let (quantity_base, quantity_quote, quantity_contract) = calc_quantity_and_volume(
+                EXCHANGE_NAME,
+                MarketType::EuropeanOption,
+                &pair,
+                price,
+                quantity,
+            )?;
+            Ok(TradeMsg {
                 exchange: EXCHANGE_NAME.to_string(),
                 market_type: MarketType::EuropeanOption,
                 symbol: trade.S.clone(),
                 pair,
                 msg_type: MessageType::Trade,
                 timestamp: trade.T,
+                timestamp_us: None,
                 price,
-                quantity_base: quantity,
-                quantity_quote: price * quantity,
-                quantity_contract: Some(quantity),
+                quantity_base,
+                quantity_quote,
+                quantity_contract,
                 side: if trade.s == "1" {
                     // TODO: find out the meaning of the field s
                     TradeSide::Sell
@@ -71,9 +147,106 @@ pub(crate) fn parse_trade(msg: &str) -> Result<Vec<TradeMsg>> {
                     TradeSide::Buy
                 },
                 trade_id: trade.a.to_string(),
-                json: serde_json::to_string(&trade).unwrap(),
-            }
+                json: raw_json(&trade),
+            })
         })
-        .collect();
+        .collect::<Result<Vec<TradeMsg>>>()?;
     Ok(trades)
 }
+
+fn parse_eapi_trade(msg: &str) -> Result<Vec<TradeMsg>> {
+    let ws_msg = serde_json::from_str::<WebsocketMsg<EapiTradeMsg>>(msg)?;
+    let raw_trade = ws_msg.data;
+    let pair =
+        get_pair(&raw_trade.s).ok_or_else(|| ParseError::UnknownSymbol(raw_trade.s.clone()))?;
+    let price = parse_field(EXCHANGE_NAME, "p", &raw_trade.p, msg)?;
+    let quantity = parse_field(EXCHANGE_NAME, "q", &raw_trade.q, msg)?;
+    let (quantity_base, quantity_quote, quantity_contract) = calc_quantity_and_volume(
+        EXCHANGE_NAME,
+        MarketType::EuropeanOption,
+        &pair,
+        price,
+        quantity,
+    )?;
+    // S is sent as either a number or a string
+    let direction = match &raw_trade.S {
+        Value::String(s) => s.clone(),
+        other => other.to_string(),
+    };
+    let trade = TradeMsg {
+        exchange: EXCHANGE_NAME.to_string(),
+        market_type: MarketType::EuropeanOption,
+        symbol: raw_trade.s.clone(),
+        pair,
+        msg_type: MessageType::Trade,
+        timestamp: raw_trade.T,
+        timestamp_us: None,
+        price,
+        quantity_base,
+        quantity_quote,
+        quantity_contract,
+        side: if direction == "-1" {
+            TradeSide::Sell
+        } else {
+            TradeSide::Buy
+        },
+        trade_id: match &raw_trade.t {
+            Value::String(s) => s.clone(),
+            other => other.to_string(),
+        },
+        json: original_json(msg),
+    };
+    Ok(vec![trade])
+}
+
+pub(crate) fn parse_l2(msg: &str) -> Result<Vec<OrderBookMsg>> {
+    let ws_msg = serde_json::from_str::<WebsocketMsg<EapiOrderbookMsg>>(msg)?;
+    let raw_orderbook = ws_msg.data;
+    let pair = get_pair(&raw_orderbook.s)
+        .ok_or_else(|| ParseError::UnknownSymbol(raw_orderbook.s.clone()))?;
+
+    let parse_order = |raw_order: &[String; 2]| -> Result<Order> {
+        let price = parse_field(EXCHANGE_NAME, "price", &raw_order[0], msg)?;
+        let (quantity_base, quantity_quote, quantity_contract) = calc_quantity_and_volume(
+            EXCHANGE_NAME,
+            MarketType::EuropeanOption,
+            &pair,
+            price,
+            parse_field(EXCHANGE_NAME, "quantity", &raw_order[1], msg)?,
+        )?;
+        Ok(Order {
+            price,
+            quantity_base,
+            quantity_quote,
+            quantity_contract,
+            order_id: None,
+            order_type: None,
+        })
+    };
+
+    let orderbook = OrderBookMsg {
+        exchange: EXCHANGE_NAME.to_string(),
+        market_type: MarketType::EuropeanOption,
+        symbol: raw_orderbook.s.clone(),
+        pair: pair.clone(),
+        msg_type: MessageType::L2Event,
+        timestamp: raw_orderbook.T,
+        timestamp_us: None,
+        seq_id: raw_orderbook.u,
+        prev_seq_id: raw_orderbook.pu,
+        asks: raw_orderbook
+            .a
+            .iter()
+            .map(&parse_order)
+            .collect::<Result<Vec<Order>>>()?,
+        bids: raw_orderbook
+            .b
+            .iter()
+            .map(&parse_order)
+            .collect::<Result<Vec<Order>>>()?,
+        // Partial book depth streams push top-k snapshots
+        snapshot: true,
+        json: original_json(msg),
+    };
+    Ok(vec![orderbook])
+}
@@ -5,14 +5,41 @@ use std::collections::HashMap;
 
 use crypto_market_type::MarketType;
 
-use crate::{FundingRateMsg, OrderBookMsg, TradeMsg};
+use crate::{
+    BboMsg, FundingRateMsg, IndexPriceMsg, KlineMsg, LiquidationMsg, MarkPriceMsg, OpenInterestMsg,
+    OrderBookMsg, TickerMsg, TradeMsg,
+};
 
-use serde_json::{Result, Value};
+use super::registry::ExchangeParser;
+use crate::error::{ParseError, Result};
+use serde_json::Value;
+
+pub(crate) const PARSER: ExchangeParser = ExchangeParser {
+    name: "binance",
+    extract_symbol,
+    parse_trade,
+    parse_l2: |market_type, msg, _timestamp| parse_l2(market_type, msg),
+    parse_trade_with_options: None,
+    parse_l2_with_options: None,
+    parse_l2_snapshot: Some(parse_l2_snapshot),
+    parse_bbo: Some(parse_bbo),
+    parse_ticker: Some(parse_ticker),
+    parse_funding_rate: Some(parse_funding_rate),
+    parse_mark_price: Some(parse_mark_price),
+    parse_index_price: Some(parse_index_price),
+    parse_liquidation: Some(parse_liquidation),
+    parse_open_interest: Some(parse_open_interest),
+    parse_candlestick_history: Some(parse_candlestick_history),
+};
 
 pub(crate) fn extract_symbol(_market_type: MarketType, msg: &str) -> Option<String> {
-    let obj = serde_json::from_str::<HashMap<String, Value>>(msg).unwrap();
-    let data = obj.get("data").unwrap();
-    let symbol = data["s"].as_str().unwrap();
+    let obj = serde_json::from_str::<HashMap<String, Value>>(msg).ok()?;
+    let data = obj.get("data").unwrap_or(&Value::Null);
+    let symbol = if data.is_null() {
+        obj.get("s")?.as_str()?
+    } else {
+        data["s"].as_str()?
+    };
     Some(symbol.to_string())
 }
 
@@ -31,14 +58,122 @@ pub(crate) fn parse_funding_rate(
     if market_type == MarketType::InverseSwap || market_type == MarketType::LinearSwap {
         binance_all::parse_funding_rate(market_type, msg)
     } else {
-        panic!("Binance {} does NOT have funding rates", market_type);
+        Err(ParseError::UnsupportedMarketType(
+            "binance".to_string(),
+            market_type,
+        ))
+    }
+}
+
+pub(crate) fn parse_mark_price(market_type: MarketType, msg: &str) -> Result<Vec<MarkPriceMsg>> {
+    if market_type == MarketType::Spot || market_type == MarketType::EuropeanOption {
+        Err(ParseError::UnsupportedMarketType(
+            "binance".to_string(),
+            market_type,
+        ))
+    } else {
+        binance_all::parse_mark_price(market_type, msg)
+    }
+}
+
+pub(crate) fn parse_index_price(market_type: MarketType, msg: &str) -> Result<Vec<IndexPriceMsg>> {
+    if market_type == MarketType::Spot || market_type == MarketType::EuropeanOption {
+        Err(ParseError::UnsupportedMarketType(
+            "binance".to_string(),
+            market_type,
+        ))
+    } else {
+        binance_all::parse_index_price(market_type, msg)
     }
 }
 
 pub(crate) fn parse_l2(market_type: MarketType, msg: &str) -> Result<Vec<OrderBookMsg>> {
     if market_type == MarketType::EuropeanOption {
-        Ok(Vec::new())
+        binance_option::parse_l2(msg)
     } else {
         binance_all::parse_l2(market_type, msg)
     }
 }
+
+pub(crate) fn parse_bbo(
+    market_type: MarketType,
+    msg: &str,
+    timestamp: Option<i64>,
+) -> Result<Vec<BboMsg>> {
+    if market_type == MarketType::EuropeanOption {
+        Err(ParseError::UnsupportedMarketType(
+            "binance".to_string(),
+            market_type,
+        ))
+    } else {
+        binance_all::parse_bbo(market_type, msg, timestamp)
+    }
+}
+
+pub(crate) fn parse_ticker(market_type: MarketType, msg: &str) -> Result<Vec<TickerMsg>> {
+    if market_type == MarketType::EuropeanOption {
+        Err(ParseError::UnsupportedMarketType(
+            "binance".to_string(),
+            market_type,
+        ))
+    } else {
+        binance_all::parse_ticker(market_type, msg)
+    }
+}
+
+pub(crate) fn parse_open_interest(
+    market_type: MarketType,
+    msg: &str,
+) -> Result<Vec<OpenInterestMsg>> {
+    if market_type == MarketType::Spot || market_type == MarketType::EuropeanOption {
+        Err(ParseError::UnsupportedMarketType(
+            "binance".to_string(),
+            market_type,
+        ))
+    } else {
+        binance_all::parse_open_interest(market_type, msg)
+    }
+}
+
+pub(crate) fn parse_liquidation(market_type: MarketType, msg: &str) -> Result<Vec<LiquidationMsg>> {
+    if market_type == MarketType::Spot || market_type == MarketType::EuropeanOption {
+        Err(ParseError::UnsupportedMarketType(
+            "binance".to_string(),
+            market_type,
+        ))
+    } else {
+        binance_all::parse_liquidation(market_type, msg)
+    }
+}
+
+pub(crate) fn parse_l2_snapshot(
+    market_type: MarketType,
+    symbol: &str,
+    msg: &str,
+) -> Result<Vec<OrderBookMsg>> {
+    if market_type == MarketType::EuropeanOption {
+        Err(ParseError::UnsupportedMarketType(
+            "binance".to_string(),
+            market_type,
+        ))
+    } else {
+        binance_all::parse_l2_snapshot(market_type, symbol, msg)
+    }
+}
+
+pub(crate) fn parse_candlestick_history(
+    market_type: MarketType,
+    symbol: &str,
+    interval: usize,
+    msg: &str,
+) -> Result<Vec<KlineMsg>> {
+    match market_type {
+        MarketType::Spot | MarketType::LinearFuture | MarketType::LinearSwap => {
+            binance_all::parse_candlestick_history(market_type, symbol, interval, msg)
+        }
+        _ => Err(ParseError::UnsupportedMarketType(
+            "binance".to_string(),
+            market_type,
+        )),
+    }
+}
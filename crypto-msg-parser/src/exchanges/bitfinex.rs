@@ -5,75 +5,105 @@ use crate::{
     TradeSide,
 };
 
-use serde_json::{Result, Value};
+use crate::error::{ParseError, Result};
+use crate::exchanges::utils::{normalize_pair, original_json, raw_json};
+use serde_json::Value;
 
 const EXCHANGE_NAME: &str = "bitfinex";
 
 pub(crate) fn extract_symbol(_market_type: MarketType, msg: &str) -> Option<String> {
-    let arr = serde_json::from_str::<Vec<Value>>(msg).unwrap();
+    let arr = serde_json::from_str::<Vec<Value>>(msg).ok()?;
 
-    let symbol = arr[0].as_object().unwrap()["symbol"].as_str().unwrap();
+    let symbol = arr.first()?.as_object()?.get("symbol")?.as_str()?;
     Some(symbol.to_string())
 }
 
-fn parse_one_trade(market_type: MarketType, symbol: &str, nums: &[f64]) -> TradeMsg {
-    assert_eq!(4, nums.len());
-    let pair = crypto_pair::normalize_pair(symbol, EXCHANGE_NAME).unwrap();
+// The first element is the channel information inserted by BitfinexWSClient,
+// raw messages only have a chanId there, see BitfinexChannels.
+fn extract_channel_symbol(arr: &[Value]) -> Result<&str> {
+    match arr.first() {
+        Some(Value::Object(obj)) => obj.get("symbol").and_then(|x| x.as_str()).ok_or_else(|| {
+            ParseError::MalformedField("bitfinex channel has no symbol".to_string())
+        }),
+        Some(Value::Number(chan_id)) => Err(ParseError::MalformedField(format!(
+            "bitfinex chanId {} is not resolved, use BitfinexChannels",
+            chan_id
+        ))),
+        _ => Err(ParseError::MalformedField(
+            "bitfinex message without channel".to_string(),
+        )),
+    }
+}
+
+fn parse_one_trade(market_type: MarketType, symbol: &str, nums: &[f64]) -> Result<TradeMsg> {
+    if nums.len() != 4 {
+        return Err(ParseError::MalformedField(format!(
+            "bitfinex trade {:?} should have 4 fields",
+            nums
+        )));
+    }
+    let pair = normalize_pair(symbol, EXCHANGE_NAME)?;
     let trade_id = nums[0] as i64;
     let timestamp = nums[1] as i64;
     let quantity = f64::abs(nums[2]);
     let price = nums[3];
 
     let (quantity_base, quantity_quote, quantity_contract) =
-        calc_quantity_and_volume(EXCHANGE_NAME, market_type, &pair, price, quantity);
+        calc_quantity_and_volume(EXCHANGE_NAME, market_type, &pair, price, quantity)?;
 
-    TradeMsg {
+    Ok(TradeMsg {
         exchange: EXCHANGE_NAME.to_string(),
         market_type,
         symbol: symbol.to_string(),
         pair,
         msg_type: MessageType::Trade,
         timestamp,
+        timestamp_us: None,
         price,
         quantity_base,
         quantity_quote,
         quantity_contract,
-        side: if quantity < 0.0 {
+        // negative amount means a sell
+        side: if nums[2] < 0.0 {
             TradeSide::Sell
         } else {
             TradeSide::Buy
         },
         trade_id: trade_id.to_string(),
-        json: serde_json::to_string(&nums).unwrap(),
-    }
+        json: raw_json(&nums),
+    })
 }
 
 pub(crate) fn parse_trade(market_type: MarketType, msg: &str) -> Result<Vec<TradeMsg>> {
     let arr = serde_json::from_str::<Vec<Value>>(msg)?;
 
-    let symbol = arr[0].as_object().unwrap()["symbol"].as_str().unwrap();
+    let symbol = extract_channel_symbol(&arr)?;
 
     // see https://docs.bitfinex.com/reference#ws-public-trades
-    match arr[1].as_str() {
-        Some(_) => {
+    match arr.get(1).map(|x| x.as_str()) {
+        Some(Some(_)) if arr.len() > 2 => {
             // te, tu
-            let nums: Vec<f64> = serde_json::from_value(arr[2].clone()).unwrap();
-            let mut trade = parse_one_trade(market_type, symbol, &nums);
-            trade.json = msg.to_string();
+            let nums: Vec<f64> = serde_json::from_value(arr[2].clone())?;
+            let mut trade = parse_one_trade(market_type, symbol, &nums)?;
+            trade.json = original_json(msg);
             Ok(vec![trade])
         }
-        None => {
+        Some(None) => {
             // snapshot
-            let nums_arr: Vec<Vec<f64>> = serde_json::from_value(arr[1].clone()).unwrap();
+            let nums_arr: Vec<Vec<f64>> = serde_json::from_value(arr[1].clone())?;
             let mut trades: Vec<TradeMsg> = nums_arr
                 .iter()
                 .map(|nums| parse_one_trade(market_type, symbol, nums))
-                .collect();
+                .collect::<Result<Vec<TradeMsg>>>()?;
             if trades.len() == 1 {
-                trades[0].json = msg.to_string();
+                trades[0].json = original_json(msg);
             }
             Ok(trades)
         }
+        _ => Err(ParseError::MalformedField(format!(
+            "Unknown trade message {}",
+            msg
+        ))),
     }
 }
 
@@ -84,20 +114,23 @@ pub(crate) fn parse_l2(
 ) -> Result<Vec<OrderBookMsg>> {
     let ws_msg = serde_json::from_str::<Vec<Value>>(msg)?;
 
-    let symbol = ws_msg[0].as_object().unwrap()["symbol"].as_str().unwrap();
-    let pair = crypto_pair::normalize_pair(symbol, EXCHANGE_NAME).unwrap();
-
-    let data = ws_msg[1].clone();
-    if data.as_array().unwrap().is_empty() {
-        return Ok(vec![]);
-    }
-
-    let snapshot = {
-        let arr = data.as_array().unwrap();
-        arr[0].is_array()
+    let symbol = extract_channel_symbol(&ws_msg)?;
+    let pair = normalize_pair(symbol, EXCHANGE_NAME)?;
+
+    let data = match ws_msg.get(1) {
+        Some(Value::Array(arr)) if arr.is_empty() => return Ok(vec![]),
+        Some(data @ Value::Array(_)) => data.clone(),
+        _ => {
+            return Err(ParseError::MalformedField(format!(
+                "Unknown orderbook message {}",
+                msg
+            )))
+        }
     };
 
-    let parse_order = |x: &[f64; 3]| -> Order {
+    let snapshot = data[0].is_array();
+
+    let parse_order = |x: &[f64; 3]| -> Result<Order> {
         let price = x[0];
         // delete price level if count = 0
         let quantity = if (x[1] as i32) == 0 {
@@ -107,14 +140,16 @@ pub(crate) fn parse_l2(
         };
 
         let (quantity_base, quantity_quote, quantity_contract) =
-            calc_quantity_and_volume(EXCHANGE_NAME, market_type, &pair, price, quantity);
+            calc_quantity_and_volume(EXCHANGE_NAME, market_type, &pair, price, quantity)?;
 
-        Order {
+        Ok(Order {
             price,
             quantity_base,
             quantity_quote,
             quantity_contract,
-        }
+            order_id: None,
+            order_type: None,
+        })
     };
 
     let mut orderbook = OrderBookMsg {
@@ -124,24 +159,25 @@ pub(crate) fn parse_l2(
         pair: pair.clone(),
         msg_type: MessageType::L2Event,
         timestamp,
+        timestamp_us: None,
         seq_id: None,
         prev_seq_id: None,
         asks: Vec::new(),
         bids: Vec::new(),
         snapshot,
-        json: msg.to_string(),
+        json: original_json(msg),
     };
 
     let raw_orders = if snapshot {
         // snapshot
-        serde_json::from_value::<Vec<[f64; 3]>>(data).unwrap()
+        serde_json::from_value::<Vec<[f64; 3]>>(data)?
     } else {
         // update
-        let raw_order = serde_json::from_value::<[f64; 3]>(data).unwrap();
+        let raw_order = serde_json::from_value::<[f64; 3]>(data)?;
         vec![raw_order]
     };
     for raw_order in raw_orders.iter() {
-        let order = parse_order(raw_order);
+        let order = parse_order(raw_order)?;
         if raw_order[2] > 0.0 {
             orderbook.bids.push(order);
         } else {
@@ -1,15 +1,27 @@
 use crypto_market_type::MarketType;
 
-use super::utils::calc_quantity_and_volume;
+use super::utils::{
+    calc_quantity_and_volume, normalize_pair, original_json, parse_field, raw_json,
+};
 use crate::{FundingRateMsg, MessageType, Order, OrderBookMsg, TradeMsg, TradeSide};
 
+use crate::error::{ParseError, Result};
 use chrono::prelude::*;
 use serde::{Deserialize, Serialize};
-use serde_json::{Result, Value};
+use serde_json::Value;
 use std::collections::HashMap;
 
 const EXCHANGE_NAME: &str = "bitget";
 
+// bitget settles funding every hour
+const FUNDING_INTERVAL_MS: i64 = 3600000;
+
+// Rounds a settlement time to the nearest hour, bitget sometimes sends
+// times a few milliseconds off the hour.
+fn align_funding_time(funding_time: i64) -> i64 {
+    (funding_time + FUNDING_INTERVAL_MS / 2) / FUNDING_INTERVAL_MS * FUNDING_INTERVAL_MS
+}
+
 // see https://bitgetlimited.github.io/apidoc/en/swap/#public-trading-channel
 #[derive(Serialize, Deserialize)]
 struct SwapTradeMsg {
@@ -41,56 +53,48 @@ struct WebsocketMsg<T: Sized> {
 }
 
 pub(crate) fn extract_symbol(_market_type: MarketType, msg: &str) -> Option<String> {
-    let ws_msg = serde_json::from_str::<WebsocketMsg<Value>>(msg).unwrap();
-    let instrument_ids = ws_msg
-        .data
-        .iter()
-        .map(|v| v["instrument_id"].as_str().unwrap())
-        .collect::<Vec<&str>>();
-    if instrument_ids.is_empty() {
-        None
-    } else {
-        Some(instrument_ids[0].to_string())
+    if let Ok(ws_msg) = serde_json::from_str::<MixWebsocketMsg<Value>>(msg) {
+        return ws_msg.arg.get("instId").cloned();
     }
+    let ws_msg = serde_json::from_str::<WebsocketMsg<Value>>(msg).ok()?;
+    ws_msg.data.first()?["instrument_id"]
+        .as_str()
+        .map(|s| s.to_string())
 }
 
 pub(crate) fn parse_trade(market_type: MarketType, msg: &str) -> Result<Vec<TradeMsg>> {
     let ws_msg = serde_json::from_str::<WebsocketMsg<SwapTradeMsg>>(msg)?;
-    let mut trades: Vec<TradeMsg> = ws_msg
+    let mut trades = ws_msg
         .data
         .into_iter()
         .map(|raw_trade| {
-            let pair =
-                crypto_pair::normalize_pair(&raw_trade.instrument_id, EXCHANGE_NAME).unwrap();
-            let price = raw_trade.price.parse::<f64>().unwrap();
-            let size = raw_trade.size.parse::<f64>().unwrap();
+            let pair = normalize_pair(&raw_trade.instrument_id, EXCHANGE_NAME)?;
+            let price = parse_field(EXCHANGE_NAME, "price", &raw_trade.price, msg)?;
+            let size = parse_field(EXCHANGE_NAME, "size", &raw_trade.size, msg)?;
             let (quantity_base, quantity_quote, quantity_contract) =
-                calc_quantity_and_volume(EXCHANGE_NAME, market_type, &pair, price, size);
+                calc_quantity_and_volume(EXCHANGE_NAME, market_type, &pair, price, size)?;
 
-            TradeMsg {
+            Ok(TradeMsg {
                 exchange: EXCHANGE_NAME.to_string(),
                 market_type,
                 symbol: raw_trade.instrument_id.clone(),
                 pair,
                 msg_type: MessageType::Trade,
-                timestamp: raw_trade.timestamp.parse::<i64>().unwrap(),
+                timestamp: parse_field(EXCHANGE_NAME, "timestamp", &raw_trade.timestamp, msg)?,
+                timestamp_us: None,
                 price,
                 quantity_base,
                 quantity_quote,
                 quantity_contract,
-                side: if raw_trade.side == "sell" {
-                    TradeSide::Sell
-                } else {
-                    TradeSide::Buy
-                },
+                side: parse_field::<TradeSide>(EXCHANGE_NAME, "side", &raw_trade.side, msg)?,
                 // Use timestamp as ID because bitget doesn't provide trade_id
                 trade_id: raw_trade.timestamp.to_string(),
-                json: serde_json::to_string(&raw_trade).unwrap(),
-            }
+                json: raw_json(&raw_trade),
+            })
         })
-        .collect();
+        .collect::<Result<Vec<TradeMsg>>>()?;
     if trades.len() == 1 {
-        trades[0].json = msg.to_string();
+        trades[0].json = original_json(msg);
     }
     Ok(trades)
 }
@@ -105,55 +109,147 @@ struct RawFundingRateMsg {
     extra: HashMap<String, Value>,
 }
 
+// https://bitgetlimited.github.io/apidoc/en/mix/#tickers-channel
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct MixTickerMsg {
+    inst_id: String,
+    last: String,
+    capital_rate: String,
+    next_settle_time: i64,
+    mark_price: String,
+    system_time: i64,
+    #[serde(flatten)]
+    extra: HashMap<String, Value>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct MixWebsocketMsg<T: Sized> {
+    action: String,
+    arg: HashMap<String, String>,
+    data: Vec<T>,
+}
+
 pub(crate) fn parse_funding_rate(
     market_type: MarketType,
     msg: &str,
 ) -> Result<Vec<FundingRateMsg>> {
+    if let Ok(ws_msg) = serde_json::from_str::<MixWebsocketMsg<MixTickerMsg>>(msg) {
+        // The ticker channel carries funding rate and mark price together
+        return parse_funding_rate_from_ticker(market_type, msg, ws_msg);
+    }
     let ws_msg = serde_json::from_str::<WebsocketMsg<RawFundingRateMsg>>(msg)?;
 
-    let mut rates: Vec<FundingRateMsg> = ws_msg
+    let mut rates = ws_msg
         .data
         .into_iter()
-        .map(|raw_msg| FundingRateMsg {
-            exchange: EXCHANGE_NAME.to_string(),
-            market_type,
-            symbol: raw_msg.instrument_id.clone(),
-            pair: crypto_pair::normalize_pair(&raw_msg.instrument_id, EXCHANGE_NAME).unwrap(),
-            msg_type: MessageType::FundingRate,
-            timestamp: Utc::now().timestamp_millis(),
-            funding_rate: raw_msg.funding_rate.parse::<f64>().unwrap(),
-            funding_time: raw_msg.funding_time.parse::<i64>().unwrap(),
-            estimated_rate: None,
-            json: serde_json::to_string(&raw_msg).unwrap(),
+        .map(|raw_msg| {
+            let funding_time = align_funding_time(parse_field(
+                EXCHANGE_NAME,
+                "funding_time",
+                &raw_msg.funding_time,
+                msg,
+            )?);
+            Ok(FundingRateMsg {
+                exchange: EXCHANGE_NAME.to_string(),
+                market_type,
+                symbol: raw_msg.instrument_id.clone(),
+                pair: normalize_pair(&raw_msg.instrument_id, EXCHANGE_NAME)?,
+                msg_type: MessageType::FundingRate,
+                // swap/funding_rate messages don't have a timestamp
+                timestamp: Utc::now().timestamp_millis(),
+                funding_rate: parse_field(
+                    EXCHANGE_NAME,
+                    "funding_rate",
+                    &raw_msg.funding_rate,
+                    msg,
+                )?,
+                funding_time,
+                estimated_rate: None,
+                mark_price: None,
+                json: raw_json(&raw_msg),
+            })
+        })
+        .collect::<Result<Vec<FundingRateMsg>>>()?;
+    if rates.len() == 1 {
+        rates[0].json = original_json(msg);
+    }
+    Ok(rates)
+}
+
+fn parse_funding_rate_from_ticker(
+    market_type: MarketType,
+    msg: &str,
+    ws_msg: MixWebsocketMsg<MixTickerMsg>,
+) -> Result<Vec<FundingRateMsg>> {
+    let mut rates = ws_msg
+        .data
+        .into_iter()
+        .map(|raw_msg| {
+            let quote = if market_type == MarketType::InverseSwap {
+                "USD"
+            } else {
+                "USDT"
+            };
+            let pair = raw_msg
+                .inst_id
+                .strip_suffix(quote)
+                .map(|base| format!("{}/{}", base, quote))
+                .ok_or_else(|| ParseError::UnknownSymbol(raw_msg.inst_id.clone()))?;
+            Ok(FundingRateMsg {
+                exchange: EXCHANGE_NAME.to_string(),
+                market_type,
+                symbol: raw_msg.inst_id.clone(),
+                pair,
+                msg_type: MessageType::FundingRate,
+                timestamp: raw_msg.system_time,
+                funding_rate: parse_field(
+                    EXCHANGE_NAME,
+                    "capitalRate",
+                    &raw_msg.capital_rate,
+                    msg,
+                )?,
+                funding_time: align_funding_time(raw_msg.next_settle_time),
+                estimated_rate: None,
+                mark_price: Some(parse_field(
+                    EXCHANGE_NAME,
+                    "markPrice",
+                    &raw_msg.mark_price,
+                    msg,
+                )?),
+                json: raw_json(&raw_msg),
+            })
         })
-        .collect();
+        .collect::<Result<Vec<FundingRateMsg>>>()?;
     if rates.len() == 1 {
-        rates[0].json = msg.to_string();
+        rates[0].json = original_json(msg);
     }
     Ok(rates)
 }
 
 pub(crate) fn parse_l2(market_type: MarketType, msg: &str) -> Result<Vec<OrderBookMsg>> {
     let ws_msg = serde_json::from_str::<WebsocketMsg<SwapOrderbookMsg>>(msg)?;
-    let snapshot = ws_msg.action.unwrap() == "partial";
+    let snapshot = ws_msg.action.as_deref() == Some("partial");
     let mut orderbooks = Vec::<OrderBookMsg>::new();
 
     for raw_orderbook in ws_msg.data.iter() {
         let symbol = raw_orderbook.instrument_id.as_str();
-        let pair = crypto_pair::normalize_pair(symbol, EXCHANGE_NAME).unwrap();
-        let timestamp = raw_orderbook.timestamp.parse::<i64>().unwrap();
+        let pair = normalize_pair(symbol, EXCHANGE_NAME)?;
+        let timestamp = parse_field(EXCHANGE_NAME, "timestamp", &raw_orderbook.timestamp, msg)?;
 
-        let parse_order = |raw_order: &[String; 2]| -> Order {
-            let price = raw_order[0].parse::<f64>().unwrap();
-            let quantity = raw_order[1].parse::<f64>().unwrap();
+        let parse_order = |raw_order: &[String; 2]| -> Result<Order> {
+            let price = parse_field(EXCHANGE_NAME, "price", &raw_order[0], msg)?;
+            let quantity = parse_field(EXCHANGE_NAME, "quantity", &raw_order[1], msg)?;
             let (quantity_base, quantity_quote, quantity_contract) =
-                calc_quantity_and_volume(EXCHANGE_NAME, market_type, &pair, price, quantity);
-            Order {
+                calc_quantity_and_volume(EXCHANGE_NAME, market_type, &pair, price, quantity)?;
+            Ok(Order {
                 price,
                 quantity_base,
                 quantity_quote,
                 quantity_contract,
-            }
+                order_id: None,
+                order_type: None,
+            })
         };
 
         let orderbook = OrderBookMsg {
@@ -163,12 +259,21 @@ pub(crate) fn parse_l2(market_type: MarketType, msg: &str) -> Result<Vec<OrderBo
             pair: pair.clone(),
             msg_type: MessageType::L2Event,
             timestamp,
+            timestamp_us: None,
             seq_id: None,
             prev_seq_id: None,
-            asks: raw_orderbook.asks.iter().map(|x| parse_order(x)).collect(),
-            bids: raw_orderbook.bids.iter().map(|x| parse_order(x)).collect(),
+            asks: raw_orderbook
+                .asks
+                .iter()
+                .map(&parse_order)
+                .collect::<Result<Vec<Order>>>()?,
+            bids: raw_orderbook
+                .bids
+                .iter()
+                .map(&parse_order)
+                .collect::<Result<Vec<Order>>>()?,
             snapshot,
-            json: msg.to_string(),
+            json: original_json(msg),
         };
 
         orderbooks.push(orderbook)
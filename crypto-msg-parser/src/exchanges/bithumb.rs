@@ -2,8 +2,10 @@ use crypto_market_type::MarketType;
 
 use crate::{MessageType, Order, OrderBookMsg, TradeMsg, TradeSide};
 
+use crate::error::{ParseError, Result};
+use crate::exchanges::utils::{normalize_pair, original_json, parse_field, raw_json};
 use serde::{Deserialize, Serialize};
-use serde_json::{Result, Value};
+use serde_json::Value;
 use std::collections::HashMap;
 
 const EXCHANGE_NAME: &str = "bithumb";
@@ -41,19 +43,13 @@ struct WebsocketMsg<T: Sized> {
 }
 
 pub(crate) fn extract_symbol(_market_type: MarketType, msg: &str) -> Option<String> {
-    let ws_msg = serde_json::from_str::<WebsocketMsg<Value>>(msg).unwrap();
-    if ws_msg.data.is_object() {
-        Some(ws_msg.data["symbol"].as_str().unwrap().to_string())
-    } else if ws_msg.data.is_array() {
-        let arr = ws_msg.data.as_array().unwrap();
-        let symbols = arr
-            .iter()
-            .map(|v| v["symbol"].as_str().unwrap())
-            .collect::<Vec<&str>>();
-        Some(symbols[0].to_string())
+    let ws_msg = serde_json::from_str::<WebsocketMsg<Value>>(msg).ok()?;
+    let symbol = if let Some(arr) = ws_msg.data.as_array() {
+        arr.first()?["symbol"].as_str()
     } else {
-        panic!("Unknown message format: {}", msg);
-    }
+        ws_msg.data["symbol"].as_str()
+    };
+    symbol.map(|symbol| symbol.to_string())
 }
 
 pub(crate) fn parse_trade(market_type: MarketType, msg: &str) -> Result<Vec<TradeMsg>> {
@@ -67,37 +63,37 @@ pub(crate) fn parse_trade(market_type: MarketType, msg: &str) -> Result<Vec<Trad
         let ws_msg = serde_json::from_str::<WebsocketMsg<SpotTradeMsg>>(msg)?;
         vec![ws_msg.data]
     } else {
-        panic!("Invalid trade msg {}", msg);
+        return Err(ParseError::MalformedField(format!(
+            "Invalid trade msg {}",
+            msg
+        )));
     };
     let mut trades: Vec<TradeMsg> = raw_trades
         .into_iter()
         .map(|raw_trade| {
-            let price = raw_trade.p.parse::<f64>().unwrap();
-            let quantity = raw_trade.v.parse::<f64>().unwrap();
-            let timestamp = raw_trade.t.parse::<i64>().unwrap() * 1000;
-            TradeMsg {
+            let price = parse_field(EXCHANGE_NAME, "p", &raw_trade.p, msg)?;
+            let quantity = parse_field(EXCHANGE_NAME, "v", &raw_trade.v, msg)?;
+            let timestamp = parse_field::<i64>(EXCHANGE_NAME, "t", &raw_trade.t, msg)? * 1000;
+            Ok(TradeMsg {
                 exchange: EXCHANGE_NAME.to_string(),
                 market_type,
                 symbol: raw_trade.symbol.to_string(),
-                pair: crypto_pair::normalize_pair(&raw_trade.symbol, EXCHANGE_NAME).unwrap(),
+                pair: normalize_pair(&raw_trade.symbol, EXCHANGE_NAME)?,
                 msg_type: MessageType::Trade,
                 timestamp,
+                timestamp_us: None,
                 price,
                 quantity_base: quantity,
                 quantity_quote: price * quantity,
                 quantity_contract: None,
-                side: if raw_trade.s == "sell" {
-                    TradeSide::Sell
-                } else {
-                    TradeSide::Buy
-                },
+                side: parse_field::<TradeSide>(EXCHANGE_NAME, "s", &raw_trade.s, msg)?,
                 trade_id: raw_trade.ver.clone(),
-                json: serde_json::to_string(&raw_trade).unwrap(),
-            }
+                json: raw_json(&raw_trade),
+            })
         })
-        .collect();
+        .collect::<Result<Vec<TradeMsg>>>()?;
     if trades.len() == 1 {
-        trades[0].json = msg.to_string();
+        trades[0].json = original_json(msg);
     }
     Ok(trades)
 }
@@ -110,22 +106,27 @@ pub(crate) fn parse_l2(market_type: MarketType, msg: &str) -> Result<Vec<OrderBo
     } else if ws_msg.code == "00007" {
         false
     } else {
-        panic!("Unknown code {}", ws_msg.code);
+        return Err(ParseError::MalformedField(format!(
+            "Unknown code {}",
+            ws_msg.code
+        )));
     };
     let symbol = ws_msg.data.symbol;
-    let pair = crypto_pair::normalize_pair(&symbol, EXCHANGE_NAME).unwrap();
+    let pair = normalize_pair(&symbol, EXCHANGE_NAME)?;
     let timestamp = ws_msg.timestamp;
 
-    let parse_order = |raw_order: &[String; 2]| -> Order {
-        let price = raw_order[0].parse::<f64>().unwrap();
-        let quantity_base = raw_order[1].parse::<f64>().unwrap();
+    let parse_order = |raw_order: &[String; 2]| -> Result<Order> {
+        let price = parse_field(EXCHANGE_NAME, "price", &raw_order[0], msg)?;
+        let quantity_base = parse_field(EXCHANGE_NAME, "quantity", &raw_order[1], msg)?;
 
-        Order {
+        Ok(Order {
             price,
             quantity_base,
             quantity_quote: price * quantity_base,
             quantity_contract: None,
-        }
+            order_id: None,
+            order_type: None,
+        })
     };
 
     let orderbook = OrderBookMsg {
@@ -135,12 +136,23 @@ pub(crate) fn parse_l2(market_type: MarketType, msg: &str) -> Result<Vec<OrderBo
         pair,
         msg_type: MessageType::L2Event,
         timestamp,
+        timestamp_us: None,
         seq_id: ws_msg.data.ver.parse().ok(),
         prev_seq_id: None,
-        asks: ws_msg.data.s.iter().map(|x| parse_order(x)).collect(),
-        bids: ws_msg.data.b.iter().map(|x| parse_order(x)).collect(),
+        asks: ws_msg
+            .data
+            .s
+            .iter()
+            .map(parse_order)
+            .collect::<Result<Vec<Order>>>()?,
+        bids: ws_msg
+            .data
+            .b
+            .iter()
+            .map(parse_order)
+            .collect::<Result<Vec<Order>>>()?,
         snapshot,
-        json: msg.to_string(),
+        json: original_json(msg),
     };
 
     Ok(vec![orderbook])
@@ -1,14 +1,21 @@
 use crypto_market_type::MarketType;
 
-use crate::exchanges::utils::{calc_quantity_and_volume, http_get};
+use crate::exchanges::utils::{
+    calc_open_interest_value, calc_quantity_and_volume, normalize_pair, original_json, parse_field,
+    parse_rfc3339, raw_json,
+};
 use crate::Order;
-use crate::{FundingRateMsg, MessageType, OrderBookMsg, TradeMsg, TradeSide};
+use crate::{
+    FundingRateMsg, IndexPriceMsg, LiquidationMsg, MessageType, OpenInterestMsg, OrderBookMsg,
+    TradeMsg, TradeSide,
+};
+use crypto_pair::http_get;
 
+use crate::error::{ParseError, Result};
 use chrono::prelude::*;
-use chrono::DateTime;
 use lazy_static::lazy_static;
 use serde::{Deserialize, Serialize};
-use serde_json::{Result, Value};
+use serde_json::Value;
 use std::collections::{BTreeMap, HashMap};
 
 const EXCHANGE_NAME: &str = "bitmex";
@@ -357,6 +364,9 @@ lazy_static! {
 }
 
 fn fetch_tick_sizes() -> BTreeMap<String, (usize, f64)> {
+    if cfg!(feature = "offline") {
+        return BTreeMap::new();
+    }
     #[derive(Serialize, Deserialize)]
     #[allow(non_snake_case)]
     struct TickSize {
@@ -371,7 +381,7 @@ fn fetch_tick_sizes() -> BTreeMap<String, (usize, f64)> {
             "https://www.bitmex.com/api/v1/instrument?columns=symbol,tickSize&start={}&count=500",
             start
         );
-        if let Ok(txt) = http_get(url.as_str()) {
+        if let Ok(txt) = http_get(EXCHANGE_NAME, url.as_str()) {
             if let Ok(tick_sizes) = serde_json::from_str::<Vec<TickSize>>(&txt) {
                 let n = tick_sizes.len();
                 for (index, tick_size) in tick_sizes.into_iter().enumerate() {
@@ -448,22 +458,55 @@ struct WebsocketMsg<T: Sized> {
     data: Vec<T>,
 }
 
-pub(crate) fn extract_symbol(_market_type: MarketType, msg: &str) -> Option<String> {
-    let ws_msg = serde_json::from_str::<WebsocketMsg<Value>>(msg).unwrap();
-    let symbols = ws_msg
-        .data
-        .iter()
-        .map(|v| v["symbol"].as_str().unwrap())
-        .collect::<Vec<&str>>();
-    if symbols.is_empty() {
-        None
+/// Get the message type of a websocket message from its `table` field.
+///
+/// One connection can carry multiple tables, e.g., `trade:XBTUSD` and
+/// `orderBookL2:XBTUSD` subscribed in one command, use this to route each
+/// message to the right parse function. Returns None for tables that can't
+/// be parsed and for non-table messages such as subscribe responses.
+pub fn get_msg_type(msg: &str) -> Option<MessageType> {
+    #[derive(Deserialize)]
+    struct TableMsg {
+        table: String,
+    }
+
+    let table_msg = serde_json::from_str::<TableMsg>(msg).ok()?;
+    match table_msg.table.as_str() {
+        "trade" => Some(MessageType::Trade),
+        "orderBookL2" | "orderBookL2_25" => Some(MessageType::L2Event),
+        "quote" => Some(MessageType::BBO),
+        "funding" => Some(MessageType::FundingRate),
+        "liquidation" => Some(MessageType::Liquidation),
+        "instrument" => Some(MessageType::IndexPrice),
+        table if table.starts_with("tradeBin") => Some(MessageType::Candlestick),
+        _ => None,
+    }
+}
+
+// Refuses a message of another table, whose data would fail to deserialize
+// with a less helpful error, or worse, deserialize into the wrong type.
+fn ensure_msg_type(msg: &str, expected: MessageType) -> Result<()> {
+    if get_msg_type(msg) == Some(expected) {
+        Ok(())
     } else {
-        Some(symbols[0].to_string())
+        Err(ParseError::MalformedField(format!(
+            "Expected a {} message, got {}",
+            expected, msg
+        )))
     }
 }
 
+pub(crate) fn extract_symbol(_market_type: MarketType, msg: &str) -> Option<String> {
+    let ws_msg = serde_json::from_str::<WebsocketMsg<Value>>(msg).ok()?;
+    let symbol = ws_msg.data.first()?["symbol"].as_str()?;
+    Some(symbol.to_string())
+}
+
 // Copied from crypto-markets/tests/bitmex.rs
 fn get_market_type_from_symbol(symbol: &str) -> MarketType {
+    if symbol.len() < 3 {
+        return MarketType::Unknown;
+    }
     let date = &symbol[(symbol.len() - 2)..];
     if date.parse::<i64>().is_ok() {
         // future
@@ -489,41 +532,40 @@ fn get_market_type_from_symbol(symbol: &str) -> MarketType {
 }
 
 pub(crate) fn parse_trade(market_type: MarketType, msg: &str) -> Result<Vec<TradeMsg>> {
+    ensure_msg_type(msg, MessageType::Trade)?;
     let ws_msg = serde_json::from_str::<WebsocketMsg<RawTradeMsg>>(msg)?;
     let raw_trades = ws_msg.data;
     let mut trades: Vec<TradeMsg> = raw_trades
         .into_iter()
         .map(|raw_trade| {
             // assert_eq!(raw_trade.foreignNotional, raw_trade.homeNotional * raw_trade.price); // tiny diff actually exists
-            let timestamp = DateTime::parse_from_rfc3339(&raw_trade.timestamp).unwrap();
+            let timestamp = parse_rfc3339(EXCHANGE_NAME, "timestamp", &raw_trade.timestamp, msg)?;
+            let side = parse_field::<TradeSide>(EXCHANGE_NAME, "side", &raw_trade.side, msg)?;
             let market_type = if market_type == MarketType::Unknown {
                 get_market_type_from_symbol(&raw_trade.symbol)
             } else {
                 market_type
             };
-            TradeMsg {
+            Ok(TradeMsg {
                 exchange: EXCHANGE_NAME.to_string(),
                 market_type,
                 symbol: raw_trade.symbol.to_string(),
-                pair: crypto_pair::normalize_pair(&raw_trade.symbol, EXCHANGE_NAME).unwrap(),
+                pair: normalize_pair(&raw_trade.symbol, EXCHANGE_NAME)?,
                 msg_type: MessageType::Trade,
                 timestamp: timestamp.timestamp_millis(),
+                timestamp_us: None,
                 price: raw_trade.price,
                 quantity_base: raw_trade.homeNotional,
                 quantity_quote: raw_trade.foreignNotional,
                 quantity_contract: Some(raw_trade.size),
-                side: if raw_trade.side == "Sell" {
-                    TradeSide::Sell
-                } else {
-                    TradeSide::Buy
-                },
+                side,
                 trade_id: raw_trade.trdMatchID.clone(),
-                json: serde_json::to_string(&raw_trade).unwrap(),
-            }
+                json: raw_json(&raw_trade),
+            })
         })
-        .collect();
+        .collect::<Result<Vec<TradeMsg>>>()?;
     if trades.len() == 1 {
-        trades[0].json = msg.to_string();
+        trades[0].json = original_json(msg);
     }
     Ok(trades)
 }
@@ -532,37 +574,227 @@ pub(crate) fn parse_funding_rate(
     market_type: MarketType,
     msg: &str,
 ) -> Result<Vec<FundingRateMsg>> {
+    ensure_msg_type(msg, MessageType::FundingRate)?;
     let ws_msg = serde_json::from_str::<WebsocketMsg<RawFundingRateMsg>>(msg)?;
     let mut rates: Vec<FundingRateMsg> = ws_msg
         .data
         .into_iter()
         .map(|raw_msg| {
-            let settlement_time = DateTime::parse_from_rfc3339(&raw_msg.timestamp).unwrap();
+            let settlement_time =
+                parse_rfc3339(EXCHANGE_NAME, "timestamp", &raw_msg.timestamp, msg)?;
             let market_type = if market_type == MarketType::Unknown {
                 get_market_type_from_symbol(&raw_msg.symbol)
             } else {
                 market_type
             };
-            FundingRateMsg {
+            Ok(FundingRateMsg {
                 exchange: EXCHANGE_NAME.to_string(),
                 market_type,
                 symbol: raw_msg.symbol.clone(),
-                pair: crypto_pair::normalize_pair(&raw_msg.symbol, EXCHANGE_NAME).unwrap(),
+                pair: normalize_pair(&raw_msg.symbol, EXCHANGE_NAME)?,
                 msg_type: MessageType::FundingRate,
                 timestamp: Utc::now().timestamp_millis(),
                 funding_rate: raw_msg.fundingRate,
                 funding_time: settlement_time.timestamp_millis(),
                 estimated_rate: None,
-                json: serde_json::to_string(&raw_msg).unwrap(),
-            }
+                mark_price: None,
+                json: raw_json(&raw_msg),
+            })
         })
-        .collect();
+        .collect::<Result<Vec<FundingRateMsg>>>()?;
     if rates.len() == 1 {
-        rates[0].json = msg.to_string();
+        rates[0].json = original_json(msg);
     }
     Ok(rates)
 }
 
+// see https://www.bitmex.com/api/explorer/#!/Instrument/Instrument_get
+#[derive(Serialize, Deserialize)]
+#[allow(non_snake_case)]
+struct RawOpenInterestMsg {
+    symbol: String,
+    timestamp: String,
+    openInterest: Option<f64>,
+    openValue: Option<f64>,
+    #[serde(flatten)]
+    extra: HashMap<String, Value>,
+}
+
+pub(crate) fn parse_open_interest(
+    market_type: MarketType,
+    msg: &str,
+) -> Result<Vec<OpenInterestMsg>> {
+    let raw_msgs = serde_json::from_str::<Vec<RawOpenInterestMsg>>(msg)?;
+    let mut open_interests: Vec<OpenInterestMsg> = raw_msgs
+        .into_iter()
+        // indices such as .BXBT have no open interest
+        .filter(|raw_msg| raw_msg.openInterest.is_some())
+        .map(|raw_msg| {
+            let timestamp = parse_rfc3339(EXCHANGE_NAME, "timestamp", &raw_msg.timestamp, msg)?;
+            let market_type = if market_type == MarketType::Unknown {
+                get_market_type_from_symbol(&raw_msg.symbol)
+            } else {
+                market_type
+            };
+            let pair = normalize_pair(&raw_msg.symbol, EXCHANGE_NAME)?;
+            let open_interest = raw_msg.openInterest.unwrap_or_default();
+            Ok(OpenInterestMsg {
+                exchange: EXCHANGE_NAME.to_string(),
+                market_type,
+                symbol: raw_msg.symbol.clone(),
+                open_interest_value: calc_open_interest_value(
+                    EXCHANGE_NAME,
+                    market_type,
+                    &pair,
+                    open_interest,
+                ),
+                pair,
+                msg_type: MessageType::OpenInterest,
+                timestamp: timestamp.timestamp_millis(),
+                open_interest,
+                json: raw_json(&raw_msg),
+            })
+        })
+        .collect::<Result<Vec<OpenInterestMsg>>>()?;
+    if open_interests.len() == 1 {
+        open_interests[0].json = original_json(msg);
+    }
+    Ok(open_interests)
+}
+
+// Index instruments of the `instrument` table, e.g., .BXBT
+#[derive(Serialize, Deserialize)]
+#[allow(non_snake_case)]
+struct RawIndexMsg {
+    symbol: String,
+    timestamp: String,
+    lastPrice: Option<f64>,
+    #[serde(flatten)]
+    extra: HashMap<String, Value>,
+}
+
+// .BXBT is the index of XBT/USD, .BETH of ETH/USD, .BXBTUSDT of XBT/USDT, etc.
+fn index_to_pair(symbol: &str) -> Result<String> {
+    let currencies = symbol
+        .strip_prefix(".B")
+        .ok_or_else(|| ParseError::UnknownSymbol(symbol.to_string()))?;
+    let (base, quote) = match currencies.strip_suffix("USDT") {
+        Some(base) => (base, "USDT"),
+        None => (currencies.strip_suffix("USD").unwrap_or(currencies), "USD"),
+    };
+    if base.is_empty() || !base.chars().all(|c| c.is_ascii_uppercase()) {
+        return Err(ParseError::UnknownSymbol(symbol.to_string()));
+    }
+    Ok(format!(
+        "{}/{}",
+        if base == "XBT" { "BTC" } else { base },
+        quote
+    ))
+}
+
+pub(crate) fn parse_index_price(market_type: MarketType, msg: &str) -> Result<Vec<IndexPriceMsg>> {
+    let ws_msg = serde_json::from_str::<WebsocketMsg<Value>>(msg)?;
+    if ws_msg.table != "instrument" {
+        return Err(ParseError::MalformedField(format!(
+            "Unknown index price message {}",
+            msg
+        )));
+    }
+    let raw_msgs = ws_msg
+        .data
+        .into_iter()
+        // indices start with a dot, other instruments are tradable contracts
+        .filter(|x| x["symbol"].as_str().unwrap_or_default().starts_with('.'))
+        .map(serde_json::from_value::<RawIndexMsg>)
+        .collect::<serde_json::Result<Vec<RawIndexMsg>>>()?;
+    let mut index_prices: Vec<IndexPriceMsg> = raw_msgs
+        .into_iter()
+        // updates without lastPrice only touch other fields
+        .filter_map(|raw_msg| raw_msg.lastPrice.map(|index_price| (raw_msg, index_price)))
+        .map(|(raw_msg, index_price)| {
+            let timestamp = parse_rfc3339(EXCHANGE_NAME, "timestamp", &raw_msg.timestamp, msg)?;
+            Ok(IndexPriceMsg {
+                exchange: EXCHANGE_NAME.to_string(),
+                market_type,
+                symbol: raw_msg.symbol.clone(),
+                pair: index_to_pair(&raw_msg.symbol)?,
+                msg_type: MessageType::IndexPrice,
+                timestamp: timestamp.timestamp_millis(),
+                index_price,
+                json: raw_json(&raw_msg),
+            })
+        })
+        .collect::<Result<Vec<IndexPriceMsg>>>()?;
+    if index_prices.len() == 1 {
+        index_prices[0].json = original_json(msg);
+    }
+    Ok(index_prices)
+}
+
+// https://www.bitmex.com/app/wsAPI#Liquidation
+#[derive(Serialize, Deserialize)]
+#[allow(non_snake_case)]
+struct RawLiquidationMsg {
+    orderID: String,
+    symbol: String,
+    side: String,
+    price: f64,
+    leavesQty: f64,
+    #[serde(flatten)]
+    extra: HashMap<String, Value>,
+}
+
+pub(crate) fn parse_liquidation(market_type: MarketType, msg: &str) -> Result<Vec<LiquidationMsg>> {
+    let ws_msg = serde_json::from_str::<WebsocketMsg<Value>>(msg)?;
+    // update and delete only track the remaining quantity of existing liquidation orders
+    if ws_msg.action != "insert" {
+        return Ok(Vec::new());
+    }
+    // liquidation messages don't have a timestamp
+    let timestamp = Utc::now().timestamp_millis();
+    let raw_msgs = ws_msg
+        .data
+        .into_iter()
+        .map(serde_json::from_value::<RawLiquidationMsg>)
+        .collect::<serde_json::Result<Vec<RawLiquidationMsg>>>()?;
+    let mut liquidations: Vec<LiquidationMsg> = raw_msgs
+        .into_iter()
+        .map(|raw_msg| {
+            let market_type = if market_type == MarketType::Unknown {
+                get_market_type_from_symbol(&raw_msg.symbol)
+            } else {
+                market_type
+            };
+            let pair = normalize_pair(&raw_msg.symbol, EXCHANGE_NAME)?;
+            let (quantity_base, quantity_quote, quantity_contract) = calc_quantity_and_volume(
+                EXCHANGE_NAME,
+                market_type,
+                &pair,
+                raw_msg.price,
+                raw_msg.leavesQty,
+            )?;
+            Ok(LiquidationMsg {
+                exchange: EXCHANGE_NAME.to_string(),
+                market_type,
+                symbol: raw_msg.symbol.clone(),
+                pair,
+                msg_type: MessageType::Liquidation,
+                timestamp,
+                side: parse_field::<TradeSide>(EXCHANGE_NAME, "side", &raw_msg.side, msg)?,
+                price: raw_msg.price,
+                quantity_base,
+                quantity_quote,
+                quantity_contract,
+                json: raw_json(&raw_msg),
+            })
+        })
+        .collect::<Result<Vec<LiquidationMsg>>>()?;
+    if liquidations.len() == 1 {
+        liquidations[0].json = original_json(msg);
+    }
+    Ok(liquidations)
+}
+
 /// convert ID to price
 /// https://www.bitmex.com/app/wsAPI#OrderBookL2
 /// price = (100000000 * symbolIdx - ID) * tickSize
@@ -587,35 +819,40 @@ pub(crate) fn parse_l2(
     msg: &str,
     timestamp: i64,
 ) -> Result<Vec<OrderBookMsg>> {
+    ensure_msg_type(msg, MessageType::L2Event)?;
     let ws_msg = serde_json::from_str::<WebsocketMsg<RawOrder>>(msg)?;
     let snapshot = ws_msg.action == "partial";
     if ws_msg.data.is_empty() {
         return Ok(Vec::new());
     }
     let symbol = ws_msg.data[0].symbol.clone();
-    let pair = crypto_pair::normalize_pair(&symbol, EXCHANGE_NAME).unwrap();
+    let pair = normalize_pair(&symbol, EXCHANGE_NAME)?;
     let market_type = if market_type == MarketType::Unknown {
         get_market_type_from_symbol(&symbol)
     } else {
         market_type
     };
 
-    let parse_order = |raw_order: &RawOrder| -> Order {
+    let parse_order = |raw_order: &RawOrder| -> Result<Order> {
         let price = if let Some(p) = raw_order.price {
             p
-        } else {
+        } else if SYMBOL_INDEX_AND_TICK_SIZE_MAP.contains_key(&raw_order.symbol) {
             id_to_price(&raw_order.symbol, raw_order.id)
+        } else {
+            return Err(ParseError::UnknownSymbol(raw_order.symbol.clone()));
         };
 
         let quantity = raw_order.size.unwrap_or(0.0); // 0.0 means delete
         let (quantity_base, quantity_quote, quantity_contract) =
-            calc_quantity_and_volume(EXCHANGE_NAME, market_type, &pair, price, quantity);
-        Order {
+            calc_quantity_and_volume(EXCHANGE_NAME, market_type, &pair, price, quantity)?;
+        Ok(Order {
             price,
             quantity_base,
             quantity_quote,
             quantity_contract,
-        }
+            order_id: None,
+            order_type: None,
+        })
     };
 
     let orderbook = OrderBookMsg {
@@ -625,22 +862,23 @@ pub(crate) fn parse_l2(
         pair: pair.clone(),
         msg_type: MessageType::L2Event,
         timestamp,
+        timestamp_us: None,
         seq_id: None,
         prev_seq_id: None,
         asks: ws_msg
             .data
             .iter()
             .filter(|x| x.side == "Sell")
-            .map(|x| parse_order(x))
-            .collect(),
+            .map(parse_order)
+            .collect::<Result<Vec<Order>>>()?,
         bids: ws_msg
             .data
             .iter()
             .filter(|x| x.side == "Buy")
-            .map(|x| parse_order(x))
-            .collect(),
+            .map(parse_order)
+            .collect::<Result<Vec<Order>>>()?,
         snapshot,
-        json: msg.to_string(),
+        json: original_json(msg),
     };
 
     Ok(vec![orderbook])
@@ -658,6 +896,15 @@ mod tests {
         }
     }
 
+    #[test]
+    #[cfg(feature = "offline")]
+    fn test_offline_tick_sizes() {
+        use std::sync::atomic::Ordering;
+
+        assert_eq!(51366.5, super::id_to_price("XBTUSD", 8794863350));
+        assert_eq!(0, crypto_pair::NUM_HTTP_REQUESTS.load(Ordering::Relaxed));
+    }
+
     #[test]
     fn test_id_to_price() {
         // data are from https://www.bitmex.com/api/v1/orderBook/L2?symbol=XBTUSD&depth=25
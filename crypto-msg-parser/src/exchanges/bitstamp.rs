@@ -2,8 +2,10 @@ use crypto_market_type::MarketType;
 
 use crate::{MessageType, Order, OrderBookMsg, TradeMsg, TradeSide};
 
+use crate::error::{ParseError, Result};
+use crate::exchanges::utils::{normalize_pair, original_json, parse_field};
 use serde::{Deserialize, Serialize};
-use serde_json::{Result, Value};
+use serde_json::Value;
 use std::collections::HashMap;
 
 const EXCHANGE_NAME: &str = "bitstamp";
@@ -44,38 +46,65 @@ struct WebsocketMsg<T: Sized> {
     data: T,
 }
 
+// Channel names are a prefix followed by the symbol, e.g., live_trades_btcusd
+const CHANNEL_PREFIXES: &[&str] = &[
+    "live_trades_",
+    "diff_order_book_",
+    "order_book_",
+    "detail_order_book_",
+    "live_orders_",
+];
+
+fn channel_to_symbol(channel: &str) -> Option<&str> {
+    CHANNEL_PREFIXES
+        .iter()
+        .find_map(|prefix| channel.strip_prefix(prefix))
+}
+
 pub(crate) fn extract_symbol(_market_type: MarketType, msg: &str) -> Option<String> {
-    let ws_msg = serde_json::from_str::<WebsocketMsg<Value>>(msg).unwrap();
-    let channel = ws_msg.channel;
-    let pos = channel.rfind('_').unwrap();
-    let symbol = &channel[pos + 1..];
-    Some(symbol.to_string())
+    let ws_msg = serde_json::from_str::<WebsocketMsg<Value>>(msg).ok()?;
+    channel_to_symbol(&ws_msg.channel).map(|symbol| symbol.to_string())
 }
 
 pub(crate) fn parse_trade(market_type: MarketType, msg: &str) -> Result<Vec<TradeMsg>> {
     let ws_msg = serde_json::from_str::<WebsocketMsg<SpotTradeMsg>>(msg)?;
-    let symbol = ws_msg.channel.strip_prefix("live_trades_").unwrap();
-    let pair = crypto_pair::normalize_pair(symbol, EXCHANGE_NAME).unwrap();
+    let symbol = ws_msg.channel.strip_prefix("live_trades_").ok_or_else(|| {
+        ParseError::MalformedField(format!("Unexpected trade channel {}", ws_msg.channel))
+    })?;
+    let pair = normalize_pair(symbol, EXCHANGE_NAME)?;
     let raw_trade = ws_msg.data;
+    let side = match raw_trade.type_ {
+        0 => TradeSide::Buy,
+        1 => TradeSide::Sell,
+        _ => {
+            return Err(ParseError::MalformedField(format!(
+                "Unknown trade type {}",
+                raw_trade.type_
+            )))
+        }
+    };
 
+    let timestamp_us = parse_field::<i64>(
+        EXCHANGE_NAME,
+        "microtimestamp",
+        &raw_trade.microtimestamp,
+        msg,
+    )?;
     let trade = TradeMsg {
         exchange: EXCHANGE_NAME.to_string(),
         market_type,
         symbol: symbol.to_string(),
         pair,
         msg_type: MessageType::Trade,
-        timestamp: raw_trade.microtimestamp.parse::<i64>().unwrap() / 1000,
+        timestamp: timestamp_us / 1000,
+        timestamp_us: Some(timestamp_us),
         price: raw_trade.price,
         quantity_base: raw_trade.amount,
         quantity_quote: raw_trade.price * raw_trade.amount,
         quantity_contract: None,
-        side: if raw_trade.type_ == 1 {
-            TradeSide::Sell
-        } else {
-            TradeSide::Buy
-        },
+        side,
         trade_id: raw_trade.id.to_string(),
-        json: msg.to_string(),
+        json: original_json(msg),
     };
 
     Ok(vec![trade])
@@ -83,35 +112,63 @@ pub(crate) fn parse_trade(market_type: MarketType, msg: &str) -> Result<Vec<Trad
 
 pub(crate) fn parse_l2(market_type: MarketType, msg: &str) -> Result<Vec<OrderBookMsg>> {
     let ws_msg = serde_json::from_str::<WebsocketMsg<SpotOrderbookMsg>>(msg)?;
-    let symbol = ws_msg.channel.strip_prefix("diff_order_book_").unwrap();
-    let pair = crypto_pair::normalize_pair(symbol, EXCHANGE_NAME).unwrap();
+    // order_book_ pushes full snapshots of top 100 levels, while
+    // diff_order_book_ pushes changed levels only
+    let (symbol, snapshot) = if let Some(symbol) = ws_msg.channel.strip_prefix("diff_order_book_") {
+        (symbol, false)
+    } else if let Some(symbol) = ws_msg.channel.strip_prefix("order_book_") {
+        (symbol, true)
+    } else {
+        return Err(ParseError::MalformedField(format!(
+            "Unexpected orderbook channel {}",
+            ws_msg.channel
+        )));
+    };
+    let pair = normalize_pair(symbol, EXCHANGE_NAME)?;
     let raw_orderbook = ws_msg.data;
 
-    let parse_order = |raw_order: &[String; 2]| -> Order {
-        let price = raw_order[0].parse::<f64>().unwrap();
-        let quantity_base = raw_order[1].parse::<f64>().unwrap();
+    let parse_order = |raw_order: &[String; 2]| -> Result<Order> {
+        let price = parse_field(EXCHANGE_NAME, "price", &raw_order[0], msg)?;
+        let quantity_base = parse_field(EXCHANGE_NAME, "amount", &raw_order[1], msg)?;
 
-        Order {
+        Ok(Order {
             price,
             quantity_base,
             quantity_quote: price * quantity_base,
             quantity_contract: None,
-        }
+            order_id: None,
+            order_type: None,
+        })
     };
 
+    let timestamp_us = parse_field::<i64>(
+        EXCHANGE_NAME,
+        "microtimestamp",
+        &raw_orderbook.microtimestamp,
+        msg,
+    )?;
     let orderbook = OrderBookMsg {
         exchange: EXCHANGE_NAME.to_string(),
         market_type,
         symbol: symbol.to_string(),
         pair,
         msg_type: MessageType::L2Event,
-        timestamp: raw_orderbook.microtimestamp.parse::<i64>().unwrap() / 1000,
+        timestamp: timestamp_us / 1000,
+        timestamp_us: Some(timestamp_us),
         seq_id: None,
         prev_seq_id: None,
-        asks: raw_orderbook.asks.iter().map(|x| parse_order(x)).collect(),
-        bids: raw_orderbook.bids.iter().map(|x| parse_order(x)).collect(),
-        snapshot: false,
-        json: msg.to_string(),
+        asks: raw_orderbook
+            .asks
+            .iter()
+            .map(parse_order)
+            .collect::<Result<Vec<Order>>>()?,
+        bids: raw_orderbook
+            .bids
+            .iter()
+            .map(parse_order)
+            .collect::<Result<Vec<Order>>>()?,
+        snapshot,
+        json: original_json(msg),
     };
 
     Ok(vec![orderbook])
@@ -2,8 +2,10 @@ use crypto_market_type::MarketType;
 
 use crate::{MessageType, Order, OrderBookMsg, TradeMsg, TradeSide};
 
+use crate::error::{ParseError, Result};
+use crate::exchanges::utils::{normalize_pair, original_json, parse_field, raw_json};
 use serde::{Deserialize, Serialize};
-use serde_json::{Result, Value};
+use serde_json::Value;
 use std::collections::HashMap;
 
 const EXCHANGE_NAME: &str = "bitz";
@@ -49,7 +51,7 @@ struct WebsocketMsg<T: Sized> {
 }
 
 pub(crate) fn extract_symbol(_market_type: MarketType, msg: &str) -> Option<String> {
-    let ws_msg = serde_json::from_str::<WebsocketMsg<Value>>(msg).unwrap();
+    let ws_msg = serde_json::from_str::<WebsocketMsg<Value>>(msg).ok()?;
     let symbol = ws_msg.params.symbol.as_str();
     Some(symbol.to_string())
 }
@@ -57,42 +59,39 @@ pub(crate) fn extract_symbol(_market_type: MarketType, msg: &str) -> Option<Stri
 pub(crate) fn parse_trade(market_type: MarketType, msg: &str) -> Result<Vec<TradeMsg>> {
     let ws_msg = serde_json::from_str::<WebsocketMsg<Vec<SpotTradeMsg>>>(msg)?;
     let symbol = ws_msg.params.symbol.as_str();
-    let pair = crypto_pair::normalize_pair(symbol, EXCHANGE_NAME).unwrap();
+    let pair = normalize_pair(symbol, EXCHANGE_NAME)?;
 
     let mut trades: Vec<TradeMsg> = ws_msg
         .data
         .into_iter()
         .map(|raw_trade| {
-            let price = raw_trade.p.parse::<f64>().unwrap();
-            let quantity = raw_trade.n.parse::<f64>().unwrap();
+            let price = parse_field(EXCHANGE_NAME, "p", &raw_trade.p, msg)?;
+            let quantity = parse_field(EXCHANGE_NAME, "n", &raw_trade.n, msg)?;
             let timestamp = if raw_trade.id.is_empty() {
                 raw_trade.T * 1000
             } else {
-                raw_trade.id.parse::<i64>().unwrap()
+                parse_field(EXCHANGE_NAME, "id", &raw_trade.id, msg)?
             };
-            TradeMsg {
+            Ok(TradeMsg {
                 exchange: EXCHANGE_NAME.to_string(),
                 market_type,
                 symbol: symbol.to_string(),
                 pair: pair.clone(),
                 msg_type: MessageType::Trade,
                 timestamp,
+                timestamp_us: None,
                 price,
                 quantity_base: quantity,
                 quantity_quote: price * quantity,
                 quantity_contract: None,
-                side: if raw_trade.s == "sell" {
-                    TradeSide::Sell
-                } else {
-                    TradeSide::Buy
-                },
+                side: parse_field::<TradeSide>(EXCHANGE_NAME, "s", &raw_trade.s, msg)?,
                 trade_id: timestamp.to_string(),
-                json: serde_json::to_string(&raw_trade).unwrap(),
-            }
+                json: raw_json(&raw_trade),
+            })
         })
-        .collect();
+        .collect::<Result<Vec<TradeMsg>>>()?;
     if trades.len() == 1 {
-        trades[0].json = msg.to_string();
+        trades[0].json = original_json(msg);
     }
     Ok(trades)
 }
@@ -101,24 +100,32 @@ pub(crate) fn parse_l2(market_type: MarketType, msg: &str) -> Result<Vec<OrderBo
     let ws_msg = serde_json::from_str::<WebsocketMsg<SpotOrderbookMsg>>(msg)?;
     debug_assert_eq!(ws_msg.action, "Pushdata.depth");
     let symbol = ws_msg.params.symbol.as_str();
-    let pair = crypto_pair::normalize_pair(symbol, EXCHANGE_NAME).unwrap();
+    let pair = normalize_pair(symbol, EXCHANGE_NAME)?;
 
-    let parse_order = |raw_order: &[Value; 3]| -> Order {
-        let price = raw_order[0].as_str().unwrap().parse::<f64>().unwrap();
+    let parse_number = |value: &Value, field: &str| -> Result<f64> {
+        let value = value.as_str().ok_or_else(|| {
+            ParseError::MalformedField(format!("{} is not a string in {}", field, msg))
+        })?;
+        parse_field(EXCHANGE_NAME, field, value, msg)
+    };
+    let parse_order = |raw_order: &[Value; 3]| -> Result<Order> {
+        let price = parse_number(&raw_order[0], "price")?;
         let (quantity_base, quantity_quote) = if raw_order[1].is_i64() {
             (0.0, 0.0)
         } else {
-            let base = raw_order[1].as_str().unwrap().parse::<f64>().unwrap();
-            let quote = raw_order[2].as_str().unwrap().parse::<f64>().unwrap();
+            let base = parse_number(&raw_order[1], "quantity")?;
+            let quote = parse_number(&raw_order[2], "volume")?;
             (base, quote)
         };
 
-        Order {
+        Ok(Order {
             price,
             quantity_base,
             quantity_quote,
             quantity_contract: None,
-        }
+            order_id: None,
+            order_type: None,
+        })
     };
 
     let orderbook = OrderBookMsg {
@@ -128,20 +135,25 @@ pub(crate) fn parse_l2(market_type: MarketType, msg: &str) -> Result<Vec<OrderBo
         pair,
         msg_type: MessageType::L2Event,
         timestamp: ws_msg.time,
+        timestamp_us: None,
         seq_id: None,
         prev_seq_id: None,
         asks: if let Some(asks) = ws_msg.data.asks {
-            asks.iter().map(|x| parse_order(x)).collect()
+            asks.iter()
+                .map(parse_order)
+                .collect::<Result<Vec<Order>>>()?
         } else {
             Vec::new()
         },
         bids: if let Some(bids) = ws_msg.data.bids {
-            bids.iter().map(|x| parse_order(x)).collect()
+            bids.iter()
+                .map(parse_order)
+                .collect::<Result<Vec<Order>>>()?
         } else {
             Vec::new()
         },
         snapshot: false,
-        json: msg.to_string(),
+        json: original_json(msg),
     };
 
     Ok(vec![orderbook])
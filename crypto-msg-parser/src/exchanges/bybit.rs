@@ -5,8 +5,10 @@ use crate::{
     TradeSide,
 };
 
+use crate::error::{ParseError, Result};
+use crate::exchanges::utils::{normalize_pair, original_json, parse_field, raw_json};
 use serde::{Deserialize, Serialize};
-use serde_json::{Result, Value};
+use serde_json::Value;
 use std::collections::HashMap;
 
 const EXCHANGE_NAME: &str = "bybit";
@@ -71,25 +73,26 @@ struct OrderbookDelta {
     insert: Vec<RawOrder>,
 }
 
+#[derive(Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+enum OrderbookMsgType {
+    Snapshot,
+    Delta,
+}
+
 #[derive(Serialize, Deserialize)]
 struct RawOrderbookMsg {
     topic: String,
     #[serde(rename = "type")]
-    type_: String,
+    type_: OrderbookMsgType,
     data: Value,
     timestamp_e6: Value, // i64 or String
 }
 
 pub(crate) fn extract_symbol(_market_type: MarketType, msg: &str) -> Option<String> {
-    let ws_msg = serde_json::from_str::<HashMap<String, Value>>(msg).unwrap();
-    let arr = ws_msg
-        .get("topic")
-        .unwrap()
-        .as_str()
-        .unwrap()
-        .split('.')
-        .collect::<Vec<&str>>();
-    Some(arr[1].to_string())
+    let ws_msg = serde_json::from_str::<HashMap<String, Value>>(msg).ok()?;
+    let topic = ws_msg.get("topic")?.as_str()?;
+    topic.split('.').nth(1).map(|symbol| symbol.to_string())
 }
 
 pub(crate) fn parse_trade(market_type: MarketType, msg: &str) -> Result<Vec<TradeMsg>> {
@@ -100,31 +103,35 @@ pub(crate) fn parse_trade(market_type: MarketType, msg: &str) -> Result<Vec<Trad
             let mut trades: Vec<TradeMsg> = ws_msg
                 .data
                 .into_iter()
-                .map(|raw_trade| TradeMsg {
-                    exchange: EXCHANGE_NAME.to_string(),
-                    market_type,
-                    symbol: raw_trade.symbol.clone(),
-                    pair: crypto_pair::normalize_pair(&raw_trade.symbol, EXCHANGE_NAME).unwrap(),
-                    msg_type: MessageType::Trade,
-                    timestamp: raw_trade.trade_time_ms,
-                    price: raw_trade.price,
-                    quantity_base: raw_trade.size / raw_trade.price,
-                    // Each inverse contract value is 1 USD, see:
-                    // https://www.bybit.com/data/basic/inverse/contract-detail?symbol=BTCUSD
-                    // https://www.bybit.com/data/basic/future-inverse/contract-detail?symbol=BTCUSD0625
-                    quantity_quote: raw_trade.size,
-                    quantity_contract: Some(raw_trade.size),
-                    side: if raw_trade.side == "Sell" {
-                        TradeSide::Sell
-                    } else {
-                        TradeSide::Buy
-                    },
-                    trade_id: raw_trade.trade_id.clone(),
-                    json: serde_json::to_string(&raw_trade).unwrap(),
+                .map(|raw_trade| {
+                    Ok(TradeMsg {
+                        exchange: EXCHANGE_NAME.to_string(),
+                        market_type,
+                        symbol: raw_trade.symbol.clone(),
+                        pair: normalize_pair(&raw_trade.symbol, EXCHANGE_NAME)?,
+                        msg_type: MessageType::Trade,
+                        timestamp: raw_trade.trade_time_ms,
+                        timestamp_us: None,
+                        price: raw_trade.price,
+                        quantity_base: raw_trade.size / raw_trade.price,
+                        // Each inverse contract value is 1 USD, see:
+                        // https://www.bybit.com/data/basic/inverse/contract-detail?symbol=BTCUSD
+                        // https://www.bybit.com/data/basic/future-inverse/contract-detail?symbol=BTCUSD0625
+                        quantity_quote: raw_trade.size,
+                        quantity_contract: Some(raw_trade.size),
+                        side: parse_field::<TradeSide>(
+                            EXCHANGE_NAME,
+                            "side",
+                            &raw_trade.side,
+                            msg,
+                        )?,
+                        trade_id: raw_trade.trade_id.clone(),
+                        json: raw_json(&raw_trade),
+                    })
                 })
-                .collect();
+                .collect::<Result<Vec<TradeMsg>>>()?;
             if trades.len() == 1 {
-                trades[0].json = msg.to_string();
+                trades[0].json = original_json(msg);
             }
             Ok(trades)
         }
@@ -135,68 +142,87 @@ pub(crate) fn parse_trade(market_type: MarketType, msg: &str) -> Result<Vec<Trad
                 .data
                 .into_iter()
                 .map(|raw_trade| {
-                    let price = raw_trade.price.parse::<f64>().unwrap();
-                    TradeMsg {
+                    let price = parse_field(EXCHANGE_NAME, "price", &raw_trade.price, msg)?;
+                    Ok(TradeMsg {
                         exchange: EXCHANGE_NAME.to_string(),
                         market_type,
                         symbol: raw_trade.symbol.clone(),
-                        pair: crypto_pair::normalize_pair(&raw_trade.symbol, EXCHANGE_NAME)
-                            .unwrap(),
+                        pair: normalize_pair(&raw_trade.symbol, EXCHANGE_NAME)?,
                         msg_type: MessageType::Trade,
-                        timestamp: raw_trade.trade_time_ms.parse::<i64>().unwrap(),
+                        timestamp: parse_field(
+                            EXCHANGE_NAME,
+                            "trade_time_ms",
+                            &raw_trade.trade_time_ms,
+                            msg,
+                        )?,
+                        timestamp_us: None,
                         price,
                         // Each linear contract value is 1 coin, see:
                         // https://www.bybit.com/data/basic/linear/contract-detail?symbol=BTCUSDT
                         quantity_base: raw_trade.size,
                         quantity_quote: price * raw_trade.size,
                         quantity_contract: Some(raw_trade.size),
-                        side: if raw_trade.side == "Sell" {
-                            TradeSide::Sell
-                        } else {
-                            TradeSide::Buy
-                        },
+                        side: parse_field::<TradeSide>(
+                            EXCHANGE_NAME,
+                            "side",
+                            &raw_trade.side,
+                            msg,
+                        )?,
                         trade_id: raw_trade.trade_id.clone(),
-                        json: serde_json::to_string(&raw_trade).unwrap(),
-                    }
+                        json: raw_json(&raw_trade),
+                    })
                 })
-                .collect();
+                .collect::<Result<Vec<TradeMsg>>>()?;
             if trades.len() == 1 {
-                trades[0].json = msg.to_string();
+                trades[0].json = original_json(msg);
             }
             Ok(trades)
         }
-        _ => panic!("Unknown market_type {}", market_type),
+        _ => Err(ParseError::UnsupportedMarketType(
+            EXCHANGE_NAME.to_string(),
+            market_type,
+        )),
     }
 }
 
 pub(crate) fn parse_l2(market_type: MarketType, msg: &str) -> Result<Vec<OrderBookMsg>> {
     let ws_msg = serde_json::from_str::<RawOrderbookMsg>(msg)?;
-    let symbol = ws_msg.topic.strip_prefix("orderBookL2_25.").unwrap();
-    let pair = crypto_pair::normalize_pair(symbol, EXCHANGE_NAME).unwrap();
-    let snapshot = ws_msg.type_ == "snapshot";
-    let timestamp = if ws_msg.timestamp_e6.is_i64() {
-        ws_msg.timestamp_e6.as_i64().unwrap()
+    let symbol = ws_msg
+        .topic
+        .strip_prefix("orderBookL2_25.")
+        .ok_or_else(|| ParseError::MalformedField(format!("Unknown topic in {}", msg)))?;
+    let pair = normalize_pair(symbol, EXCHANGE_NAME)?;
+    let snapshot = ws_msg.type_ == OrderbookMsgType::Snapshot;
+    let timestamp = if let Some(timestamp_e6) = ws_msg.timestamp_e6.as_i64() {
+        timestamp_e6
+    } else if let Some(timestamp_e6) = ws_msg.timestamp_e6.as_str() {
+        parse_field(EXCHANGE_NAME, "timestamp_e6", timestamp_e6, msg)?
     } else {
-        ws_msg
-            .timestamp_e6
-            .as_str()
-            .unwrap()
-            .parse::<i64>()
-            .unwrap()
+        return Err(ParseError::MalformedField(format!(
+            "Invalid timestamp_e6 in {}",
+            msg
+        )));
     } / 1000;
 
-    let parse_order = |raw_order: &RawOrder| -> Order {
-        let price = raw_order.price.parse::<f64>().unwrap();
-        let quantity = raw_order.size.unwrap_or(0.0);
+    // Deleted levels have quantity 0, the size of inserted and updated levels is the new quantity
+    let parse_order = |raw_order: &RawOrder, deleted: bool| -> Result<Order> {
+        let price = parse_field(EXCHANGE_NAME, "price", &raw_order.price, msg)?;
+        let quantity = if deleted {
+            0.0
+        } else {
+            raw_order.size.unwrap_or(0.0)
+        };
         let (quantity_base, quantity_quote, quantity_contract) =
-            calc_quantity_and_volume(EXCHANGE_NAME, market_type, &pair, price, quantity);
+            calc_quantity_and_volume(EXCHANGE_NAME, market_type, &pair, price, quantity)?;
 
-        Order {
+        Ok(Order {
             price,
             quantity_base,
             quantity_quote,
             quantity_contract,
-        }
+            order_id: None,
+            order_type: None,
+        })
     };
 
     let mut orderbook = OrderBookMsg {
@@ -206,45 +232,60 @@ pub(crate) fn parse_l2(market_type: MarketType, msg: &str) -> Result<Vec<OrderBo
         pair: pair.to_string(),
         msg_type: MessageType::L2Event,
         timestamp,
+        timestamp_us: None,
         seq_id: None,
         prev_seq_id: None,
         asks: Vec::new(),
         bids: Vec::new(),
         snapshot,
-        json: msg.to_string(),
+        json: original_json(msg),
     };
 
     let raw_orders = match market_type {
         MarketType::InverseSwap | MarketType::InverseFuture => {
             if snapshot {
-                serde_json::from_value::<Vec<RawOrder>>(ws_msg.data).unwrap()
+                serde_json::from_value::<Vec<RawOrder>>(ws_msg.data)?
+                    .into_iter()
+                    .map(|x| (x, false))
+                    .collect()
             } else {
-                let tmp = serde_json::from_value::<OrderbookDelta>(ws_msg.data).unwrap();
-                let mut v = Vec::<RawOrder>::new();
-                v.extend(tmp.delete);
-                v.extend(tmp.update);
-                v.extend(tmp.insert);
+                let tmp = serde_json::from_value::<OrderbookDelta>(ws_msg.data)?;
+                let mut v = tmp
+                    .delete
+                    .into_iter()
+                    .map(|x| (x, true))
+                    .collect::<Vec<(RawOrder, bool)>>();
+                v.extend(tmp.update.into_iter().map(|x| (x, false)));
+                v.extend(tmp.insert.into_iter().map(|x| (x, false)));
                 v
             }
         }
         MarketType::LinearSwap => {
             if snapshot {
-                let tmp = serde_json::from_value::<LinearOrderbookSnapshot>(ws_msg.data).unwrap();
-                tmp.order_book
+                let tmp = serde_json::from_value::<LinearOrderbookSnapshot>(ws_msg.data)?;
+                tmp.order_book.into_iter().map(|x| (x, false)).collect()
             } else {
-                let tmp = serde_json::from_value::<OrderbookDelta>(ws_msg.data).unwrap();
-                let mut v = Vec::<RawOrder>::new();
-                v.extend(tmp.delete);
-                v.extend(tmp.update);
-                v.extend(tmp.insert);
+                let tmp = serde_json::from_value::<OrderbookDelta>(ws_msg.data)?;
+                let mut v = tmp
+                    .delete
+                    .into_iter()
+                    .map(|x| (x, true))
+                    .collect::<Vec<(RawOrder, bool)>>();
+                v.extend(tmp.update.into_iter().map(|x| (x, false)));
+                v.extend(tmp.insert.into_iter().map(|x| (x, false)));
                 v
             }
         }
-        _ => panic!("Unknown market_type {}", market_type),
+        _ => {
+            return Err(ParseError::UnsupportedMarketType(
+                EXCHANGE_NAME.to_string(),
+                market_type,
+            ))
+        }
     };
 
-    for raw_order in raw_orders.iter() {
-        let order = parse_order(raw_order);
+    for (raw_order, deleted) in raw_orders.iter() {
+        let order = parse_order(raw_order, *deleted)?;
         if raw_order.side == "Buy" {
             orderbook.bids.push(order);
         } else {
@@ -3,9 +3,10 @@ use crypto_market_type::MarketType;
 use crate::Order;
 use crate::{MessageType, OrderBookMsg, TradeMsg, TradeSide};
 
-use chrono::DateTime;
+use crate::error::{ParseError, Result};
+use crate::exchanges::utils::{normalize_pair, original_json, parse_field, parse_rfc3339};
 use serde::{Deserialize, Serialize};
-use serde_json::{Result, Value};
+use serde_json::Value;
 use std::collections::HashMap;
 
 const EXCHANGE_NAME: &str = "coinbase_pro";
@@ -53,62 +54,52 @@ struct OrderbookUpdateMsg {
 }
 
 pub(crate) fn extract_symbol(_market_type: MarketType, msg: &str) -> Option<String> {
-    let ws_msg = serde_json::from_str::<HashMap<String, Value>>(msg).unwrap();
-    let symbol = ws_msg.get("product_id").unwrap().as_str().unwrap();
+    let ws_msg = serde_json::from_str::<HashMap<String, Value>>(msg).ok()?;
+    let symbol = ws_msg.get("product_id")?.as_str()?;
     Some(symbol.to_string())
 }
 
 pub(crate) fn parse_trade(market_type: MarketType, msg: &str) -> Result<Vec<TradeMsg>> {
     let raw_trade = serde_json::from_str::<SpotTradeMsg>(msg)?;
-    let timestamp = DateTime::parse_from_rfc3339(&raw_trade.time).unwrap();
-    let price = raw_trade.price.parse::<f64>().unwrap();
-    let quantity = raw_trade.size.parse::<f64>().unwrap();
+    let timestamp = parse_rfc3339(EXCHANGE_NAME, "time", &raw_trade.time, msg)?;
+    let price = parse_field::<f64>(EXCHANGE_NAME, "price", &raw_trade.price, msg)?;
+    let quantity = parse_field::<f64>(EXCHANGE_NAME, "size", &raw_trade.size, msg)?;
+    let side = parse_field::<TradeSide>(EXCHANGE_NAME, "side", &raw_trade.side, msg)?;
 
     let trade = TradeMsg {
         exchange: EXCHANGE_NAME.to_string(),
         market_type,
         symbol: raw_trade.product_id.clone(),
-        pair: crypto_pair::normalize_pair(&raw_trade.product_id, EXCHANGE_NAME).unwrap(),
+        pair: normalize_pair(&raw_trade.product_id, EXCHANGE_NAME)?,
         msg_type: MessageType::Trade,
         timestamp: timestamp.timestamp_millis(),
+        timestamp_us: Some(timestamp.timestamp_micros()),
         price,
         quantity_base: quantity,
         quantity_quote: price * quantity,
         quantity_contract: None,
-        side: if raw_trade.side == "sell" {
-            TradeSide::Sell
-        } else {
-            TradeSide::Buy
-        },
+        side,
         trade_id: raw_trade.trade_id.to_string(),
-        json: msg.to_string(),
+        json: original_json(msg),
     };
 
     Ok(vec![trade])
 }
 
-fn parse_order(raw_order: &[String; 2]) -> Order {
-    let price = raw_order[0].parse::<f64>().unwrap();
-    let quantity_base = raw_order[1].parse::<f64>().unwrap();
+// Coinbase Pro only has spot markets, sizes are in base currency, so
+// quantity_contract is always None.
+fn parse_order(price: &str, size: &str, msg: &str) -> Result<Order> {
+    let price = parse_field(EXCHANGE_NAME, "price", price, msg)?;
+    let quantity_base = parse_field(EXCHANGE_NAME, "size", size, msg)?;
 
-    Order {
+    Ok(Order {
         price,
         quantity_base,
         quantity_quote: price * quantity_base,
         quantity_contract: None,
-    }
-}
-
-fn parse_change(raw_order: &[String; 3]) -> Order {
-    let price = raw_order[1].parse::<f64>().unwrap();
-    let quantity_base = raw_order[2].parse::<f64>().unwrap();
-
-    Order {
-        price,
-        quantity_base,
-        quantity_quote: price * quantity_base,
-        quantity_contract: None,
-    }
+        order_id: None,
+        order_type: None,
+    })
 }
 
 pub(crate) fn parse_l2(
@@ -116,14 +107,24 @@ pub(crate) fn parse_l2(
     msg: &str,
     timestamp: Option<i64>,
 ) -> Result<Vec<OrderBookMsg>> {
+    // level2 and level2_batch channels have the same snapshot and l2update messages
     let snapshot = {
         let obj = serde_json::from_str::<HashMap<String, Value>>(msg)?;
-        obj.get("type").unwrap().as_str().unwrap() == "snapshot"
+        match obj.get("type").and_then(|x| x.as_str()) {
+            Some("snapshot") => true,
+            Some("l2update") => false,
+            _ => {
+                return Err(ParseError::MalformedField(format!(
+                    "Unknown level2 message {}",
+                    msg
+                )))
+            }
+        }
     };
     if snapshot {
         let orderbook_snapshot = serde_json::from_str::<OrderbookSnapshotMsg>(msg)?;
         let symbol = orderbook_snapshot.product_id;
-        let pair = crypto_pair::normalize_pair(&symbol, EXCHANGE_NAME).unwrap();
+        let pair = normalize_pair(&symbol, EXCHANGE_NAME)?;
 
         let orderbook = OrderBookMsg {
             exchange: EXCHANGE_NAME.to_string(),
@@ -131,21 +132,44 @@ pub(crate) fn parse_l2(
             symbol,
             pair,
             msg_type: MessageType::L2Event,
-            timestamp: timestamp.expect("Coinbase level2 snapshot messages don't have timestamp"),
+            timestamp: timestamp.ok_or_else(|| {
+                ParseError::MalformedField(
+                    "Coinbase level2 snapshot messages don't have timestamp".to_string(),
+                )
+            })?,
+            timestamp_us: None,
             seq_id: None,
             prev_seq_id: None,
-            asks: orderbook_snapshot.asks.iter().map(parse_order).collect(),
-            bids: orderbook_snapshot.bids.iter().map(parse_order).collect(),
+            asks: orderbook_snapshot
+                .asks
+                .iter()
+                .map(|x| parse_order(&x[0], &x[1], msg))
+                .collect::<Result<Vec<Order>>>()?,
+            bids: orderbook_snapshot
+                .bids
+                .iter()
+                .map(|x| parse_order(&x[0], &x[1], msg))
+                .collect::<Result<Vec<Order>>>()?,
             snapshot,
-            json: msg.to_string(),
+            json: original_json(msg),
         };
 
         Ok(vec![orderbook])
     } else {
         let orderbook_updates = serde_json::from_str::<OrderbookUpdateMsg>(msg)?;
         let symbol = orderbook_updates.product_id;
-        let pair = crypto_pair::normalize_pair(&symbol, EXCHANGE_NAME).unwrap();
-        let timestamp = DateTime::parse_from_rfc3339(&orderbook_updates.time).unwrap();
+        let pair = normalize_pair(&symbol, EXCHANGE_NAME)?;
+        let timestamp = parse_rfc3339(EXCHANGE_NAME, "time", &orderbook_updates.time, msg)?;
+
+        // An l2update change is [side, price, size], size "0" means the price level is removed
+        let parse_changes = |side: &str| {
+            orderbook_updates
+                .changes
+                .iter()
+                .filter(|x| x[0] == side)
+                .map(|x| parse_order(&x[1], &x[2], msg))
+                .collect::<Result<Vec<Order>>>()
+        };
 
         let orderbook = OrderBookMsg {
             exchange: EXCHANGE_NAME.to_string(),
@@ -154,22 +178,13 @@ pub(crate) fn parse_l2(
             pair,
             msg_type: MessageType::L2Event,
             timestamp: timestamp.timestamp_millis(),
+            timestamp_us: Some(timestamp.timestamp_micros()),
             seq_id: None,
             prev_seq_id: None,
-            asks: orderbook_updates
-                .changes
-                .iter()
-                .filter(|x| x[0] == "sell")
-                .map(parse_change)
-                .collect(),
-            bids: orderbook_updates
-                .changes
-                .iter()
-                .filter(|x| x[0] == "buy")
-                .map(parse_change)
-                .collect(),
+            asks: parse_changes("sell")?,
+            bids: parse_changes("buy")?,
             snapshot,
-            json: msg.to_string(),
+            json: original_json(msg),
         };
 
         Ok(vec![orderbook])
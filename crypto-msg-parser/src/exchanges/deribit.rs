@@ -1,15 +1,21 @@
 use crypto_market_type::MarketType;
 
-use crate::{MessageType, Order, OrderBookMsg, TradeMsg, TradeSide};
+use crate::{FundingRateMsg, MessageType, Order, OrderBookMsg, TradeMsg, TradeSide};
 
+use crate::error::{ParseError, Result};
 use serde::{Deserialize, Serialize};
-use serde_json::{Result, Value};
+use serde_json::Value;
 use std::collections::HashMap;
 
-use crate::exchanges::utils::calc_quantity_and_volume;
+use crate::exchanges::utils::{
+    calc_quantity_and_volume, normalize_pair, original_json, parse_field, raw_json,
+};
 
 const EXCHANGE_NAME: &str = "deribit";
 
+// 8 hours in milliseconds
+const EIGHT_HOURS: i64 = 8 * 3600 * 1000;
+
 // see https://docs.deribit.com/?javascript#trades-kind-currency-interval
 #[derive(Serialize, Deserialize)]
 struct RawTradeMsg {
@@ -39,6 +45,18 @@ struct RawOrderbookMsg {
     extra: HashMap<String, Value>,
 }
 
+// https://docs.deribit.com/?javascript#perpetual-instrument_name-interval
+// The ticker channel carries funding_8h too, along with instrument_name and mark_price
+#[derive(Serialize, Deserialize)]
+struct RawFundingRateMsg {
+    timestamp: i64,
+    funding_8h: f64,
+    instrument_name: Option<String>,
+    mark_price: Option<f64>,
+    #[serde(flatten)]
+    extra: HashMap<String, Value>,
+}
+
 #[derive(Serialize, Deserialize)]
 struct Params<T: Sized> {
     channel: String,
@@ -52,20 +70,26 @@ struct WebsocketMsg<T: Sized> {
     params: Params<T>,
 }
 
+// e.g., perpetual.BTC-PERPETUAL.raw
+fn channel_to_symbol(channel: &str) -> Option<String> {
+    channel.split('.').nth(1).map(|symbol| symbol.to_string())
+}
+
 pub(crate) fn extract_symbol(_market_type: MarketType, msg: &str) -> Option<String> {
-    let ws_msg = serde_json::from_str::<WebsocketMsg<Value>>(msg).unwrap();
+    let ws_msg = serde_json::from_str::<WebsocketMsg<Value>>(msg).ok()?;
     let data = ws_msg.params.data;
-    if data.is_object() {
-        Some(data["instrument_name"].as_str().unwrap().to_string())
-    } else if data.is_array() {
-        let arr = data.as_array().unwrap();
-        let symbols = arr
-            .iter()
-            .map(|v| v["instrument_name"].as_str().unwrap())
-            .collect::<Vec<&str>>();
-        Some(symbols[0].to_string())
+    if let Some(arr) = data.as_array() {
+        arr.first()?["instrument_name"]
+            .as_str()
+            .map(|symbol| symbol.to_string())
+    } else if data.is_object() {
+        match data["instrument_name"].as_str() {
+            Some(symbol) => Some(symbol.to_string()),
+            // perpetual.BTC-PERPETUAL.raw has no instrument_name in data
+            None => channel_to_symbol(&ws_msg.params.channel),
+        }
     } else {
-        panic!("Unknown message format: {}", msg);
+        None
     }
 }
 
@@ -76,40 +100,41 @@ pub(crate) fn parse_trade(market_type: MarketType, msg: &str) -> Result<Vec<Trad
         .data
         .into_iter()
         .map(|raw_trade| {
-            let pair =
-                crypto_pair::normalize_pair(&raw_trade.instrument_name, EXCHANGE_NAME).unwrap();
+            let pair = normalize_pair(&raw_trade.instrument_name, EXCHANGE_NAME)?;
             let (quantity_base, quantity_quote, quantity_contract) = calc_quantity_and_volume(
                 EXCHANGE_NAME,
                 market_type,
                 &pair,
                 raw_trade.price,
                 raw_trade.amount,
-            );
+            )?;
 
-            TradeMsg {
+            Ok(TradeMsg {
                 exchange: EXCHANGE_NAME.to_string(),
                 market_type,
                 symbol: raw_trade.instrument_name.clone(),
                 pair,
                 msg_type: MessageType::Trade,
                 timestamp: raw_trade.timestamp,
+                timestamp_us: None,
                 price: raw_trade.price,
                 quantity_base,
                 quantity_quote,
                 quantity_contract,
-                side: if raw_trade.direction == "sell" {
-                    TradeSide::Sell
-                } else {
-                    TradeSide::Buy
-                },
+                side: parse_field::<TradeSide>(
+                    EXCHANGE_NAME,
+                    "direction",
+                    &raw_trade.direction,
+                    msg,
+                )?,
                 trade_id: raw_trade.trade_id.to_string(),
-                json: serde_json::to_string(&raw_trade).unwrap(),
-            }
+                json: raw_json(&raw_trade),
+            })
         })
-        .collect();
+        .collect::<Result<Vec<TradeMsg>>>()?;
 
     if trades.len() == 1 {
-        trades[0].json = msg.to_string();
+        trades[0].json = original_json(msg);
     }
     Ok(trades)
 }
@@ -120,21 +145,30 @@ pub(crate) fn parse_l2(market_type: MarketType, msg: &str) -> Result<Vec<OrderBo
     let snapshot = raw_orderbook.type_ == "snapshot";
     let timestamp = raw_orderbook.timestamp;
     let symbol = raw_orderbook.instrument_name;
-    let pair = crypto_pair::normalize_pair(&symbol, EXCHANGE_NAME).unwrap();
-
-    let parse_order = |raw_order: &[Value; 3]| -> Order {
-        let price = raw_order[1].as_f64().unwrap();
-        let quantity = raw_order[2].as_f64().unwrap();
+    let pair = normalize_pair(&symbol, EXCHANGE_NAME)?;
+
+    let parse_order = |raw_order: &[Value; 3]| -> Result<Order> {
+        let (price, quantity) = match (raw_order[1].as_f64(), raw_order[2].as_f64()) {
+            (Some(price), Some(quantity)) => (price, quantity),
+            _ => {
+                return Err(ParseError::MalformedField(format!(
+                    "Invalid order {:?} in {}",
+                    raw_order, msg
+                )))
+            }
+        };
 
         let (quantity_base, quantity_quote, quantity_contract) =
-            calc_quantity_and_volume(EXCHANGE_NAME, market_type, &pair, price, quantity);
+            calc_quantity_and_volume(EXCHANGE_NAME, market_type, &pair, price, quantity)?;
 
-        Order {
+        Ok(Order {
             price,
             quantity_base,
             quantity_quote,
             quantity_contract,
-        }
+            order_id: None,
+            order_type: None,
+        })
     };
 
     let orderbook = OrderBookMsg {
@@ -144,13 +178,56 @@ pub(crate) fn parse_l2(market_type: MarketType, msg: &str) -> Result<Vec<OrderBo
         pair: pair.clone(),
         msg_type: MessageType::L2Event,
         timestamp,
+        timestamp_us: None,
         seq_id: raw_orderbook.change_id,
         prev_seq_id: raw_orderbook.prev_change_id,
-        asks: raw_orderbook.asks.iter().map(|x| parse_order(x)).collect(),
-        bids: raw_orderbook.bids.iter().map(|x| parse_order(x)).collect(),
+        asks: raw_orderbook
+            .asks
+            .iter()
+            .map(parse_order)
+            .collect::<Result<Vec<Order>>>()?,
+        bids: raw_orderbook
+            .bids
+            .iter()
+            .map(parse_order)
+            .collect::<Result<Vec<Order>>>()?,
         snapshot,
-        json: msg.to_string(),
+        json: original_json(msg),
     };
 
     Ok(vec![orderbook])
 }
+
+pub(crate) fn parse_funding_rate(
+    market_type: MarketType,
+    msg: &str,
+) -> Result<Vec<FundingRateMsg>> {
+    let ws_msg = serde_json::from_str::<WebsocketMsg<RawFundingRateMsg>>(msg)?;
+    let raw_funding_rate = ws_msg.params.data;
+    let symbol = match raw_funding_rate.instrument_name.clone() {
+        Some(symbol) => symbol,
+        None => channel_to_symbol(&ws_msg.params.channel)
+            .ok_or_else(|| ParseError::MalformedField(format!("No symbol in message {}", msg)))?,
+    };
+    let pair = normalize_pair(&symbol, EXCHANGE_NAME)?;
+
+    // Deribit funding is continuous, funding_8h is the rate of the last 8 hours,
+    // so it is stamped with the next 8h boundary, like other exchanges
+    let funding_time = (raw_funding_rate.timestamp / EIGHT_HOURS + 1) * EIGHT_HOURS;
+
+    let funding_rate = FundingRateMsg {
+        exchange: EXCHANGE_NAME.to_string(),
+        market_type,
+        symbol,
+        pair,
+        msg_type: MessageType::FundingRate,
+        timestamp: raw_funding_rate.timestamp,
+        funding_rate: raw_funding_rate.funding_8h,
+        funding_time,
+        estimated_rate: None,
+        mark_price: raw_funding_rate.mark_price,
+        json: original_json(msg),
+    };
+
+    Ok(vec![funding_rate])
+}
@@ -1,12 +1,15 @@
-use chrono::DateTime;
 use crypto_market_type::MarketType;
 
 use crate::Order;
 use crate::OrderBookMsg;
 use crate::{MessageType, TradeMsg, TradeSide};
 
+use crate::error::Result;
+use crate::exchanges::utils::{
+    normalize_pair, original_json, parse_field, parse_rfc3339, raw_json,
+};
 use serde::{Deserialize, Serialize};
-use serde_json::{Result, Value};
+use serde_json::Value;
 use std::collections::HashMap;
 
 use super::message::WebsocketMsg;
@@ -63,7 +66,7 @@ struct RawOrderBookUpdateMsg {
 pub(crate) fn parse_trade(market_type: MarketType, msg: &str) -> Result<Vec<TradeMsg>> {
     let ws_msg = serde_json::from_str::<WebsocketMsg<RawTradesMsg>>(msg)?;
     let symbol = ws_msg.id;
-    let pair = crypto_pair::normalize_pair(&symbol, EXCHANGE_NAME).unwrap();
+    let pair = normalize_pair(&symbol, EXCHANGE_NAME)?;
     debug_assert_eq!("v3_trades", ws_msg.channel);
 
     let mut trades: Vec<TradeMsg> = ws_msg
@@ -71,61 +74,47 @@ pub(crate) fn parse_trade(market_type: MarketType, msg: &str) -> Result<Vec<Trad
         .trades
         .into_iter()
         .map(|raw_trade| {
-            let timestamp = DateTime::parse_from_rfc3339(&raw_trade.createdAt)
-                .unwrap()
+            let timestamp = parse_rfc3339(EXCHANGE_NAME, "createdAt", &raw_trade.createdAt, msg)?
                 .timestamp_millis();
-            let price = raw_trade.price.parse::<f64>().unwrap();
-            let size = raw_trade.size.parse::<f64>().unwrap();
-            TradeMsg {
+            let price = parse_field(EXCHANGE_NAME, "price", &raw_trade.price, msg)?;
+            let size = parse_field(EXCHANGE_NAME, "size", &raw_trade.size, msg)?;
+            Ok(TradeMsg {
                 exchange: EXCHANGE_NAME.to_string(),
                 market_type,
                 symbol: symbol.to_string(),
                 pair: pair.to_string(),
                 msg_type: MessageType::Trade,
                 timestamp,
+                timestamp_us: None,
                 price,
                 quantity_base: size,
                 quantity_quote: price * size,
                 quantity_contract: Some(size),
-                side: if raw_trade.side == "SELL" {
-                    TradeSide::Sell
-                } else {
-                    TradeSide::Buy
-                },
+                side: parse_field::<TradeSide>(EXCHANGE_NAME, "side", &raw_trade.side, msg)?,
                 trade_id: timestamp.to_string(),
-                json: serde_json::to_string(&raw_trade).unwrap(),
-            }
+                json: raw_json(&raw_trade),
+            })
         })
-        .collect();
+        .collect::<Result<Vec<TradeMsg>>>()?;
 
     if trades.len() == 1 {
-        trades[0].json = msg.to_string();
+        trades[0].json = original_json(msg);
     }
     Ok(trades)
 }
 
-fn parse_order_update(raw_order: &[String; 2]) -> Order {
-    let price = raw_order[0].parse::<f64>().unwrap();
-    let size = raw_order[1].parse::<f64>().unwrap();
+fn parse_order(price: &str, size: &str, msg: &str) -> Result<Order> {
+    let price = parse_field(EXCHANGE_NAME, "price", price, msg)?;
+    let size = parse_field(EXCHANGE_NAME, "size", size, msg)?;
 
-    Order {
+    Ok(Order {
         price,
         quantity_base: size,
         quantity_quote: price * size,
         quantity_contract: Some(size),
-    }
-}
-
-fn parse_order_snapshot(raw_order: &RawOrder) -> Order {
-    let price = raw_order.price.parse::<f64>().unwrap();
-    let size = raw_order.size.parse::<f64>().unwrap();
-
-    Order {
-        price,
-        quantity_base: size,
-        quantity_quote: price * size,
-        quantity_contract: Some(size),
-    }
+        order_id: None,
+        order_type: None,
+    })
 }
 
 pub(crate) fn parse_l2(
@@ -135,7 +124,7 @@ pub(crate) fn parse_l2(
 ) -> Result<Vec<OrderBookMsg>> {
     let ws_msg = serde_json::from_str::<WebsocketMsg<Value>>(msg)?;
     let symbol = ws_msg.id;
-    let pair = crypto_pair::normalize_pair(&symbol, EXCHANGE_NAME).unwrap();
+    let pair = normalize_pair(&symbol, EXCHANGE_NAME)?;
     let snapshot = ws_msg.type_ == "subscribed";
     debug_assert_eq!("v3_orderbook", ws_msg.channel);
 
@@ -145,15 +134,15 @@ pub(crate) fn parse_l2(
             ws_msg
                 .contents
                 .asks
-                .into_iter()
-                .map(|x| parse_order_snapshot(&x))
-                .collect(),
+                .iter()
+                .map(|x| parse_order(&x.price, &x.size, msg))
+                .collect::<Result<Vec<Order>>>()?,
             ws_msg
                 .contents
                 .bids
-                .into_iter()
-                .map(|x| parse_order_snapshot(&x))
-                .collect(),
+                .iter()
+                .map(|x| parse_order(&x.price, &x.size, msg))
+                .collect::<Result<Vec<Order>>>()?,
         )
     } else {
         let ws_msg = serde_json::from_str::<WebsocketMsg<RawOrderBookUpdateMsg>>(msg)?;
@@ -161,15 +150,15 @@ pub(crate) fn parse_l2(
             ws_msg
                 .contents
                 .asks
-                .into_iter()
-                .map(|x| parse_order_update(&x))
-                .collect(),
+                .iter()
+                .map(|x| parse_order(&x[0], &x[1], msg))
+                .collect::<Result<Vec<Order>>>()?,
             ws_msg
                 .contents
                 .bids
-                .into_iter()
-                .map(|x| parse_order_update(&x))
-                .collect(),
+                .iter()
+                .map(|x| parse_order(&x[0], &x[1], msg))
+                .collect::<Result<Vec<Order>>>()?,
         )
     };
 
@@ -180,12 +169,13 @@ pub(crate) fn parse_l2(
         pair,
         msg_type: MessageType::L2Event,
         timestamp,
+        timestamp_us: None,
         asks,
         bids,
         seq_id: None,
         prev_seq_id: None,
         snapshot,
-        json: msg.to_string(),
+        json: original_json(msg),
     };
 
     Ok(vec![orderbook])
@@ -5,19 +5,23 @@ use crypto_market_type::MarketType;
 
 use crate::{OrderBookMsg, TradeMsg};
 
-use serde_json::{Result, Value};
+use crate::error::{ParseError, Result};
+use serde_json::Value;
 
 use self::message::WebsocketMsg;
 
 pub(crate) fn extract_symbol(_market_type_: MarketType, msg: &str) -> Option<String> {
-    let ws_msg = serde_json::from_str::<WebsocketMsg<Value>>(msg).unwrap();
+    let ws_msg = serde_json::from_str::<WebsocketMsg<Value>>(msg).ok()?;
     Some(ws_msg.id)
 }
 
 pub(crate) fn parse_trade(market_type: MarketType, msg: &str) -> Result<Vec<TradeMsg>> {
     match market_type {
         MarketType::LinearSwap => dydx_swap::parse_trade(market_type, msg),
-        _ => panic!("Unknown market type {}", market_type),
+        _ => Err(ParseError::UnsupportedMarketType(
+            "dydx".to_string(),
+            market_type,
+        )),
     }
 }
 
@@ -28,6 +32,9 @@ pub(crate) fn parse_l2(
 ) -> Result<Vec<OrderBookMsg>> {
     match market_type {
         MarketType::LinearSwap => dydx_swap::parse_l2(market_type, msg, timestamp),
-        _ => panic!("Unknown market type {}", market_type),
+        _ => Err(ParseError::UnsupportedMarketType(
+            "dydx".to_string(),
+            market_type,
+        )),
     }
 }
@@ -5,9 +5,12 @@ use crate::{
     TradeSide,
 };
 
-use chrono::DateTime;
+use crate::error::Result;
+use crate::exchanges::utils::{
+    normalize_pair, original_json, parse_field, parse_rfc3339, raw_json,
+};
 use serde::{Deserialize, Serialize};
-use serde_json::{Result, Value};
+use serde_json::Value;
 use std::collections::HashMap;
 
 const EXCHANGE_NAME: &str = "ftx";
@@ -46,51 +49,48 @@ struct WebsocketMsg<T: Sized> {
 }
 
 pub(crate) fn extract_symbol(_market_type: MarketType, msg: &str) -> Option<String> {
-    let ws_msg = serde_json::from_str::<WebsocketMsg<Value>>(msg).unwrap();
+    let ws_msg = serde_json::from_str::<WebsocketMsg<Value>>(msg).ok()?;
     Some(ws_msg.market)
 }
 
 pub(crate) fn parse_trade(market_type: MarketType, msg: &str) -> Result<Vec<TradeMsg>> {
     let ws_msg = serde_json::from_str::<WebsocketMsg<Vec<RawTradeMsg>>>(msg)?;
     let symbol = ws_msg.market.as_str();
-    let pair = crypto_pair::normalize_pair(symbol, EXCHANGE_NAME).unwrap();
+    let pair = normalize_pair(symbol, EXCHANGE_NAME)?;
 
     let mut trades: Vec<TradeMsg> = ws_msg
         .data
         .into_iter()
         .map(|raw_trade| {
-            let timestamp = DateTime::parse_from_rfc3339(&raw_trade.time).unwrap();
+            let timestamp = parse_rfc3339(EXCHANGE_NAME, "time", &raw_trade.time, msg)?;
             let (quantity_base, quantity_quote, quantity_contract) = calc_quantity_and_volume(
                 EXCHANGE_NAME,
                 market_type,
                 &pair,
                 raw_trade.price,
                 raw_trade.size,
-            );
-            TradeMsg {
+            )?;
+            Ok(TradeMsg {
                 exchange: EXCHANGE_NAME.to_string(),
                 market_type,
                 symbol: symbol.to_string(),
                 pair: pair.clone(),
                 msg_type: MessageType::Trade,
                 timestamp: timestamp.timestamp_millis(),
+                timestamp_us: None,
                 price: raw_trade.price,
                 quantity_base,
                 quantity_quote,
                 quantity_contract,
-                side: if raw_trade.side == "sell" {
-                    TradeSide::Sell
-                } else {
-                    TradeSide::Buy
-                },
+                side: parse_field::<TradeSide>(EXCHANGE_NAME, "side", &raw_trade.side, msg)?,
                 trade_id: raw_trade.id.to_string(),
-                json: serde_json::to_string(&raw_trade).unwrap(),
-            }
+                json: raw_json(&raw_trade),
+            })
         })
-        .collect();
+        .collect::<Result<Vec<TradeMsg>>>()?;
 
     if trades.len() == 1 {
-        trades[0].json = msg.to_string();
+        trades[0].json = original_json(msg);
     }
     Ok(trades)
 }
@@ -99,22 +99,24 @@ pub(crate) fn parse_l2(market_type: MarketType, msg: &str) -> Result<Vec<OrderBo
     let ws_msg = serde_json::from_str::<WebsocketMsg<RawOrderbookMsg>>(msg)?;
     debug_assert_eq!(ws_msg.channel, "orderbook");
     let symbol = ws_msg.market.as_str();
-    let pair = crypto_pair::normalize_pair(symbol, EXCHANGE_NAME).unwrap();
+    let pair = normalize_pair(symbol, EXCHANGE_NAME)?;
     let snapshot = ws_msg.data.action == "partial";
     let timestamp = (ws_msg.data.time * 1000.0) as i64;
 
-    let parse_order = |raw_order: &[f64; 2]| -> Order {
+    let parse_order = |raw_order: &[f64; 2]| -> Result<Order> {
         let price = raw_order[0];
         let quantity = raw_order[1];
         let (quantity_base, quantity_quote, quantity_contract) =
-            calc_quantity_and_volume(EXCHANGE_NAME, market_type, &pair, price, quantity);
+            calc_quantity_and_volume(EXCHANGE_NAME, market_type, &pair, price, quantity)?;
 
-        Order {
+        Ok(Order {
             price,
             quantity_base,
             quantity_quote,
             quantity_contract,
-        }
+            order_id: None,
+            order_type: None,
+        })
     };
 
     let orderbook = OrderBookMsg {
@@ -124,12 +126,23 @@ pub(crate) fn parse_l2(market_type: MarketType, msg: &str) -> Result<Vec<OrderBo
         pair: pair.to_string(),
         msg_type: MessageType::L2Event,
         timestamp,
+        timestamp_us: None,
         seq_id: None,
         prev_seq_id: None,
-        asks: ws_msg.data.asks.iter().map(|x| parse_order(x)).collect(),
-        bids: ws_msg.data.bids.iter().map(|x| parse_order(x)).collect(),
+        asks: ws_msg
+            .data
+            .asks
+            .iter()
+            .map(parse_order)
+            .collect::<Result<Vec<Order>>>()?,
+        bids: ws_msg
+            .data
+            .bids
+            .iter()
+            .map(parse_order)
+            .collect::<Result<Vec<Order>>>()?,
         snapshot,
-        json: msg.to_string(),
+        json: original_json(msg),
     };
 
     Ok(vec![orderbook])
@@ -1,18 +1,19 @@
 use crate::{OrderBookMsg, TradeMsg};
 
-use serde_json::{Result, Value};
+use crate::error::{ParseError, Result};
+use serde_json::Value;
 use std::collections::HashMap;
 
 use super::{gate_spot_20210916, gate_spot_current};
 
 pub(super) fn extract_symbol(msg: &str) -> Option<String> {
-    let json_obj = serde_json::from_str::<HashMap<String, Value>>(msg).unwrap();
+    let json_obj = serde_json::from_str::<HashMap<String, Value>>(msg).ok()?;
     if json_obj.contains_key("params") {
         gate_spot_20210916::extract_symbol(msg)
     } else if json_obj.contains_key("result") {
         gate_spot_current::extract_symbol(msg)
     } else {
-        panic!("Unknown message format: {}", msg);
+        None
     }
 }
 
@@ -24,7 +25,10 @@ pub(super) fn parse_trade(msg: &str) -> Result<Vec<TradeMsg>> {
     } else if json_obj.contains_key("result") {
         gate_spot_current::parse_trade(msg)
     } else {
-        panic!("Unknown message format: {}", msg);
+        Err(ParseError::MalformedField(format!(
+            "Unknown message format: {}",
+            msg
+        )))
     }
 }
 
@@ -36,6 +40,9 @@ pub(crate) fn parse_l2(msg: &str, timestamp: i64) -> Result<Vec<OrderBookMsg>> {
     } else if json_obj.contains_key("result") {
         gate_spot_current::parse_l2(msg)
     } else {
-        panic!("Unknown message format: {}", msg);
+        Err(ParseError::MalformedField(format!(
+            "Unknown message format: {}",
+            msg
+        )))
     }
 }
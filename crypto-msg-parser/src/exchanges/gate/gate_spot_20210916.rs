@@ -2,8 +2,10 @@ use crypto_market_type::MarketType;
 
 use crate::{MessageType, Order, OrderBookMsg, TradeMsg, TradeSide};
 
+use crate::error::{ParseError, Result};
+use crate::exchanges::utils::{normalize_pair, original_json, parse_field, raw_json};
 use serde::{Deserialize, Serialize};
-use serde_json::{Result, Value};
+use serde_json::Value;
 use std::collections::HashMap;
 
 const EXCHANGE_NAME: &str = "gate";
@@ -39,53 +41,59 @@ struct SpotWebsocketMsg {
 }
 
 pub(super) fn extract_symbol(msg: &str) -> Option<String> {
-    let ws_msg = serde_json::from_str::<SpotWebsocketMsg>(msg).unwrap();
-    if ws_msg.method == "trades.update" {
-        Some(ws_msg.params[0].as_str().unwrap().to_string())
+    let ws_msg = serde_json::from_str::<SpotWebsocketMsg>(msg).ok()?;
+    let symbol = if ws_msg.method == "trades.update" {
+        ws_msg.params.first()?.as_str()
     } else if ws_msg.method == "depth.update" {
-        Some(ws_msg.params[2].as_str().unwrap().to_string())
+        ws_msg.params.get(2)?.as_str()
     } else {
-        panic!("Unsupported message format: {}", msg);
-    }
+        None
+    };
+    symbol.map(|s| s.to_string())
 }
 
 #[deprecated(since = "1.3.7", note = "Gate has new data format since 2020-09-16")]
 pub(super) fn parse_trade(msg: &str) -> Result<Vec<TradeMsg>> {
     let ws_msg = serde_json::from_str::<SpotWebsocketMsg>(msg)?;
-    let symbol = ws_msg.params[0].as_str().unwrap();
-    let pair = crypto_pair::normalize_pair(symbol, EXCHANGE_NAME).unwrap();
-    let raw_trades: Vec<SpotTradeMsg> = serde_json::from_value(ws_msg.params[1].clone()).unwrap();
+    if ws_msg.params.len() < 2 {
+        return Err(ParseError::MalformedField(format!(
+            "Expected symbol and trades in params of {}",
+            msg
+        )));
+    }
+    let symbol = ws_msg.params[0]
+        .as_str()
+        .ok_or_else(|| ParseError::MalformedField(format!("No symbol in {}", msg)))?;
+    let pair = normalize_pair(symbol, EXCHANGE_NAME)?;
+    let raw_trades: Vec<SpotTradeMsg> = serde_json::from_value(ws_msg.params[1].clone())?;
 
-    let mut trades: Vec<TradeMsg> = raw_trades
+    let mut trades = raw_trades
         .into_iter()
         .map(|raw_trade| {
-            let price = raw_trade.price.parse::<f64>().unwrap();
-            let quantity = raw_trade.amount.parse::<f64>().unwrap();
+            let price = parse_field(EXCHANGE_NAME, "price", &raw_trade.price, msg)?;
+            let quantity = parse_field(EXCHANGE_NAME, "amount", &raw_trade.amount, msg)?;
 
-            TradeMsg {
+            Ok(TradeMsg {
                 exchange: EXCHANGE_NAME.to_string(),
                 market_type: MarketType::Spot,
                 symbol: symbol.to_string(),
                 pair: pair.clone(),
                 msg_type: MessageType::Trade,
                 timestamp: (raw_trade.time * 1000.0) as i64,
+                timestamp_us: None,
                 price,
                 quantity_base: quantity,
                 quantity_quote: price * quantity,
                 quantity_contract: None,
-                side: if raw_trade.type_ == "sell" {
-                    TradeSide::Sell
-                } else {
-                    TradeSide::Buy
-                },
+                side: parse_field::<TradeSide>(EXCHANGE_NAME, "type", &raw_trade.type_, msg)?,
                 trade_id: raw_trade.id.to_string(),
-                json: serde_json::to_string(&raw_trade).unwrap(),
-            }
+                json: raw_json(&raw_trade),
+            })
         })
-        .collect();
+        .collect::<Result<Vec<TradeMsg>>>()?;
 
     if trades.len() == 1 {
-        trades[0].json = msg.to_string();
+        trades[0].json = original_json(msg);
     }
     Ok(trades)
 }
@@ -93,22 +101,27 @@ pub(super) fn parse_trade(msg: &str) -> Result<Vec<TradeMsg>> {
 #[deprecated(since = "1.3.7", note = "Gate has new data format since 2020-09-16")]
 pub(crate) fn parse_l2(msg: &str, timestamp: i64) -> Result<Vec<OrderBookMsg>> {
     let ws_msg = serde_json::from_str::<SpotWebsocketMsg>(msg)?;
-    debug_assert_eq!(ws_msg.params.len(), 3);
-    let snapshot = ws_msg.params[0].as_bool().unwrap();
-    let symbol = ws_msg.params[2].as_str().unwrap();
-    let pair = crypto_pair::normalize_pair(symbol, EXCHANGE_NAME).unwrap();
-    let raw_orderbook =
-        serde_json::from_value::<SpotOrderbookMsg>(ws_msg.params[1].clone()).unwrap();
+    let (snapshot, symbol) = match ws_msg.params.as_slice() {
+        [snapshot, _, symbol] => (snapshot.as_bool(), symbol.as_str()),
+        _ => (None, None),
+    };
+    let (snapshot, symbol) = snapshot.zip(symbol).ok_or_else(|| {
+        ParseError::MalformedField(format!("Expected [snapshot, depth, symbol] in {}", msg))
+    })?;
+    let pair = normalize_pair(symbol, EXCHANGE_NAME)?;
+    let raw_orderbook = serde_json::from_value::<SpotOrderbookMsg>(ws_msg.params[1].clone())?;
 
-    let parse_order = |raw_order: &[String; 2]| -> Order {
-        let price = raw_order[0].parse::<f64>().unwrap();
-        let quantity_base = raw_order[1].parse::<f64>().unwrap();
-        Order {
+    let parse_order = |raw_order: &[String; 2]| -> Result<Order> {
+        let price = parse_field(EXCHANGE_NAME, "price", &raw_order[0], msg)?;
+        let quantity_base = parse_field(EXCHANGE_NAME, "quantity", &raw_order[1], msg)?;
+        Ok(Order {
             price,
             quantity_base,
             quantity_quote: price * quantity_base,
             quantity_contract: None,
-        }
+            order_id: None,
+            order_type: None,
+        })
     };
 
     let orderbook = OrderBookMsg {
@@ -118,20 +131,25 @@ pub(crate) fn parse_l2(msg: &str, timestamp: i64) -> Result<Vec<OrderBookMsg>> {
         pair,
         msg_type: MessageType::L2Event,
         timestamp,
+        timestamp_us: None,
         seq_id: None,
         prev_seq_id: None,
         asks: if let Some(asks) = raw_orderbook.asks {
-            asks.iter().map(|x| parse_order(x)).collect()
+            asks.iter()
+                .map(parse_order)
+                .collect::<Result<Vec<Order>>>()?
         } else {
             Vec::new()
         },
         bids: if let Some(bids) = raw_orderbook.bids {
-            bids.iter().map(|x| parse_order(x)).collect()
+            bids.iter()
+                .map(parse_order)
+                .collect::<Result<Vec<Order>>>()?
         } else {
             Vec::new()
         },
         snapshot,
-        json: msg.to_string(),
+        json: original_json(msg),
     };
 
     Ok(vec![orderbook])
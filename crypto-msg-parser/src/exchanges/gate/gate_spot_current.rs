@@ -1,9 +1,11 @@
 use crypto_market_type::MarketType;
 
 use super::messages::WebsocketMsg;
+use crate::error::{ParseError, Result};
+use crate::exchanges::utils::{normalize_pair, original_json, parse_field};
 use crate::{MessageType, Order, OrderBookMsg, TradeMsg, TradeSide};
 use serde::{Deserialize, Serialize};
-use serde_json::{Result, Value};
+use serde_json::Value;
 use std::collections::HashMap;
 
 const EXCHANGE_NAME: &str = "gate";
@@ -52,14 +54,15 @@ struct SpotOrderbookSnapshotMsg {
 }
 
 pub(super) fn extract_symbol(msg: &str) -> Option<String> {
-    let ws_msg = serde_json::from_str::<WebsocketMsg<Value>>(msg).unwrap();
-    if ws_msg.channel == "spot.trades" {
-        Some(ws_msg.result["currency_pair"].as_str().unwrap().to_string())
+    let ws_msg = serde_json::from_str::<WebsocketMsg<Value>>(msg).ok()?;
+    let symbol = if ws_msg.channel == "spot.trades" {
+        ws_msg.result["currency_pair"].as_str()
     } else if ws_msg.channel.starts_with("spot.order_book") {
-        Some(ws_msg.result["s"].as_str().unwrap().to_string())
+        ws_msg.result["s"].as_str()
     } else {
-        panic!("Unsupported message format: {}", msg);
-    }
+        None
+    };
+    symbol.map(|s| s.to_string())
 }
 
 pub(super) fn parse_trade(msg: &str) -> Result<Vec<TradeMsg>> {
@@ -68,9 +71,11 @@ pub(super) fn parse_trade(msg: &str) -> Result<Vec<TradeMsg>> {
     debug_assert_eq!(ws_msg.event, "update");
     let result = ws_msg.result;
     let symbol = result.currency_pair;
-    let pair = crypto_pair::normalize_pair(&symbol, EXCHANGE_NAME).unwrap();
-    let price = result.price.parse::<f64>().unwrap();
-    let quantity_base = result.amount.parse::<f64>().unwrap();
+    let pair = normalize_pair(&symbol, EXCHANGE_NAME)?;
+    let price = parse_field(EXCHANGE_NAME, "price", &result.price, msg)?;
+    let quantity_base = parse_field(EXCHANGE_NAME, "amount", &result.amount, msg)?;
+    let create_time_ms: f64 =
+        parse_field(EXCHANGE_NAME, "create_time_ms", &result.create_time_ms, msg)?;
 
     let trade = TradeMsg {
         exchange: EXCHANGE_NAME.to_string(),
@@ -78,32 +83,31 @@ pub(super) fn parse_trade(msg: &str) -> Result<Vec<TradeMsg>> {
         symbol,
         pair,
         msg_type: MessageType::Trade,
-        timestamp: result.create_time_ms.parse::<f64>().unwrap() as i64,
-        price: result.price.parse::<f64>().unwrap(),
+        timestamp: create_time_ms as i64,
+        timestamp_us: None,
+        price,
         quantity_base,
         quantity_quote: price * quantity_base,
         quantity_contract: None,
-        side: if result.side == "sell" {
-            TradeSide::Sell
-        } else {
-            TradeSide::Buy
-        },
+        side: parse_field::<TradeSide>(EXCHANGE_NAME, "side", &result.side, msg)?,
         trade_id: result.id.to_string(),
-        json: msg.to_string(),
+        json: original_json(msg),
     };
 
     Ok(vec![trade])
 }
 
-fn parse_order(raw_order: &[String; 2]) -> Order {
-    let price = raw_order[0].parse::<f64>().unwrap();
-    let quantity_base = raw_order[1].parse::<f64>().unwrap();
-    Order {
+fn parse_order(raw_order: &[String; 2], msg: &str) -> Result<Order> {
+    let price = parse_field(EXCHANGE_NAME, "price", &raw_order[0], msg)?;
+    let quantity_base = parse_field(EXCHANGE_NAME, "quantity", &raw_order[1], msg)?;
+    Ok(Order {
         price,
         quantity_base,
         quantity_quote: price * quantity_base,
         quantity_contract: None,
-    }
+        order_id: None,
+        order_type: None,
+    })
 }
 
 pub(crate) fn parse_l2(msg: &str) -> Result<Vec<OrderBookMsg>> {
@@ -113,7 +117,10 @@ pub(crate) fn parse_l2(msg: &str) -> Result<Vec<OrderBookMsg>> {
     } else if ws_msg.channel == "spot.order_book" {
         parse_l2_snapshot(msg)
     } else {
-        panic!("Unknown message format: {}", msg);
+        Err(ParseError::MalformedField(format!(
+            "Unknown message format: {}",
+            msg
+        )))
     }
 }
 
@@ -122,7 +129,7 @@ fn parse_l2_update(msg: &str) -> Result<Vec<OrderBookMsg>> {
     debug_assert_eq!(ws_msg.channel, "spot.order_book_update");
     let result = ws_msg.result;
     let symbol = result.s;
-    let pair = crypto_pair::normalize_pair(&symbol, EXCHANGE_NAME).unwrap();
+    let pair = normalize_pair(&symbol, EXCHANGE_NAME)?;
 
     let orderbook = OrderBookMsg {
         exchange: EXCHANGE_NAME.to_string(),
@@ -131,20 +138,25 @@ fn parse_l2_update(msg: &str) -> Result<Vec<OrderBookMsg>> {
         pair,
         msg_type: MessageType::L2Event,
         timestamp: result.t,
+        timestamp_us: None,
         seq_id: Some(result.u as u64),
-        prev_seq_id: None,
+        prev_seq_id: (result.U as u64).checked_sub(1),
         asks: if let Some(asks) = result.a {
-            asks.iter().map(parse_order).collect()
+            asks.iter()
+                .map(|x| parse_order(x, msg))
+                .collect::<Result<Vec<Order>>>()?
         } else {
             Vec::new()
         },
         bids: if let Some(bids) = result.b {
-            bids.iter().map(parse_order).collect()
+            bids.iter()
+                .map(|x| parse_order(x, msg))
+                .collect::<Result<Vec<Order>>>()?
         } else {
             Vec::new()
         },
         snapshot: ws_msg.event == "all",
-        json: msg.to_string(),
+        json: original_json(msg),
     };
 
     Ok(vec![orderbook])
@@ -156,18 +168,7 @@ fn parse_l2_snapshot(msg: &str) -> Result<Vec<OrderBookMsg>> {
     debug_assert_eq!(ws_msg.event, "update");
     let result = ws_msg.result;
     let symbol = result.s;
-    let pair = crypto_pair::normalize_pair(&symbol, EXCHANGE_NAME).unwrap();
-
-    let parse_order = |raw_order: &[String; 2]| -> Order {
-        let price = raw_order[0].parse::<f64>().unwrap();
-        let quantity_base = raw_order[1].parse::<f64>().unwrap();
-        Order {
-            price,
-            quantity_base,
-            quantity_quote: price * quantity_base,
-            quantity_contract: None,
-        }
-    };
+    let pair = normalize_pair(&symbol, EXCHANGE_NAME)?;
 
     let orderbook = OrderBookMsg {
         exchange: EXCHANGE_NAME.to_string(),
@@ -176,20 +177,25 @@ fn parse_l2_snapshot(msg: &str) -> Result<Vec<OrderBookMsg>> {
         pair,
         msg_type: MessageType::L2Event,
         timestamp: result.t,
-        seq_id: None,
+        timestamp_us: None,
+        seq_id: Some(result.lastUpdateId as u64),
         prev_seq_id: None,
         asks: if let Some(asks) = result.asks {
-            asks.iter().map(|x| parse_order(x)).collect()
+            asks.iter()
+                .map(|x| parse_order(x, msg))
+                .collect::<Result<Vec<Order>>>()?
         } else {
             Vec::new()
         },
         bids: if let Some(bids) = result.bids {
-            bids.iter().map(|x| parse_order(x)).collect()
+            bids.iter()
+                .map(|x| parse_order(x, msg))
+                .collect::<Result<Vec<Order>>>()?
         } else {
             Vec::new()
         },
         snapshot: true,
-        json: msg.to_string(),
+        json: original_json(msg),
     };
 
     Ok(vec![orderbook])
@@ -1,12 +1,15 @@
 use crypto_market_type::MarketType;
 
-use super::super::utils::calc_quantity_and_volume;
+use super::super::utils::{
+    calc_quantity_and_volume, normalize_pair, original_json, parse_field, raw_json,
+};
 use super::messages::WebsocketMsg;
 
 use crate::{MessageType, Order, OrderBookMsg, TradeMsg, TradeSide};
 
+use crate::error::{ParseError, Result};
 use serde::{Deserialize, Serialize};
-use serde_json::{Result, Value};
+use serde_json::Value;
 use std::{cell::RefCell, collections::HashMap};
 
 const EXCHANGE_NAME: &str = "gate";
@@ -61,40 +64,28 @@ struct SwapTradeMsg {
 }
 
 pub(super) fn extract_symbol(_market_type_: MarketType, msg: &str) -> Option<String> {
-    let ws_msg = serde_json::from_str::<WebsocketMsg<Value>>(msg).unwrap();
+    let ws_msg = serde_json::from_str::<WebsocketMsg<Value>>(msg).ok()?;
     let result = ws_msg.result;
-    if ws_msg.channel == "futures.trades" {
-        let symbols = result
-            .as_array()
-            .unwrap()
-            .iter()
-            .map(|trade_msg| trade_msg["contract"].as_str().unwrap())
-            .collect::<Vec<&str>>();
-        Some(symbols[0].to_string())
+    let symbol = if ws_msg.channel == "futures.trades" {
+        result.as_array()?.first()?["contract"].as_str()
     } else if ws_msg.channel == "futures.order_book" {
         if ws_msg.event == "all" {
-            Some(result["contract"].as_str().unwrap().to_string())
+            result["contract"].as_str()
         } else {
             debug_assert_eq!(ws_msg.event, "update");
-            let arr = result.as_array().unwrap();
-            let symbols = arr
-                .iter()
-                .map(|x| x.as_object().unwrap())
-                .map(|x| {
-                    if x.contains_key("contract") {
-                        x["contract"].as_str().unwrap()
-                    } else {
-                        x["c"].as_str().unwrap()
-                    }
-                })
-                .collect::<Vec<&str>>();
-            Some(symbols[0].to_string())
+            let x = result.as_array()?.first()?.as_object()?;
+            if x.contains_key("contract") {
+                x["contract"].as_str()
+            } else {
+                x["c"].as_str()
+            }
         }
     } else if ws_msg.channel == "futures.order_book_update" {
-        Some(result["s"].as_str().unwrap().to_string())
+        result["s"].as_str()
     } else {
-        panic!("Unknown message format: {}", msg);
-    }
+        None
+    };
+    symbol.map(|s| s.to_string())
 }
 
 pub(super) fn parse_trade(market_type: MarketType, msg: &str) -> Result<Vec<TradeMsg>> {
@@ -107,8 +98,8 @@ pub(super) fn parse_trade(market_type: MarketType, msg: &str) -> Result<Vec<Trad
                 .into_iter()
                 .map(|raw_trade| {
                     let symbol = raw_trade.contract.as_str();
-                    let pair = crypto_pair::normalize_pair(symbol, EXCHANGE_NAME).unwrap();
-                    let price = raw_trade.price.parse::<f64>().unwrap();
+                    let pair = normalize_pair(symbol, EXCHANGE_NAME)?;
+                    let price = parse_field(EXCHANGE_NAME, "price", &raw_trade.price, msg)?;
                     let quantity = f64::abs(raw_trade.size);
                     let (quantity_base, quantity_quote, quantity_contract) =
                         calc_quantity_and_volume(
@@ -117,15 +108,16 @@ pub(super) fn parse_trade(market_type: MarketType, msg: &str) -> Result<Vec<Trad
                             &pair,
                             price,
                             quantity,
-                        );
+                        )?;
 
-                    TradeMsg {
+                    Ok(TradeMsg {
                         exchange: EXCHANGE_NAME.to_string(),
                         market_type,
                         symbol: symbol.to_string(),
                         pair,
                         msg_type: MessageType::Trade,
                         timestamp: raw_trade.create_time * 1000,
+                        timestamp_us: None,
                         price,
                         quantity_base,
                         quantity_quote,
@@ -136,12 +128,12 @@ pub(super) fn parse_trade(market_type: MarketType, msg: &str) -> Result<Vec<Trad
                             TradeSide::Buy
                         },
                         trade_id: raw_trade.id.to_string(),
-                        json: serde_json::to_string(&raw_trade).unwrap(),
-                    }
+                        json: raw_json(&raw_trade),
+                    })
                 })
-                .collect();
+                .collect::<Result<Vec<TradeMsg>>>()?;
             if trades.len() == 1 {
-                trades[0].json = msg.to_string();
+                trades[0].json = original_json(msg);
             }
             Ok(trades)
         }
@@ -153,8 +145,8 @@ pub(super) fn parse_trade(market_type: MarketType, msg: &str) -> Result<Vec<Trad
                 .into_iter()
                 .map(|raw_trade| {
                     let symbol = raw_trade.contract.as_str();
-                    let pair = crypto_pair::normalize_pair(symbol, EXCHANGE_NAME).unwrap();
-                    let price = raw_trade.price.parse::<f64>().unwrap();
+                    let pair = normalize_pair(symbol, EXCHANGE_NAME)?;
+                    let price = parse_field(EXCHANGE_NAME, "price", &raw_trade.price, msg)?;
                     let quantity = f64::abs(raw_trade.size);
                     let (quantity_base, quantity_quote, quantity_contract) =
                         calc_quantity_and_volume(
@@ -163,15 +155,16 @@ pub(super) fn parse_trade(market_type: MarketType, msg: &str) -> Result<Vec<Trad
                             &pair,
                             price,
                             quantity,
-                        );
+                        )?;
 
-                    TradeMsg {
+                    Ok(TradeMsg {
                         exchange: EXCHANGE_NAME.to_string(),
                         market_type,
                         symbol: symbol.to_string(),
                         pair,
                         msg_type: MessageType::Trade,
                         timestamp: raw_trade.create_time_ms,
+                        timestamp_us: None,
                         price,
                         quantity_base,
                         quantity_quote,
@@ -182,16 +175,19 @@ pub(super) fn parse_trade(market_type: MarketType, msg: &str) -> Result<Vec<Trad
                             TradeSide::Buy
                         },
                         trade_id: raw_trade.id.to_string(),
-                        json: serde_json::to_string(&raw_trade).unwrap(),
-                    }
+                        json: raw_json(&raw_trade),
+                    })
                 })
-                .collect();
+                .collect::<Result<Vec<TradeMsg>>>()?;
             if trades.len() == 1 {
-                trades[0].json = msg.to_string();
+                trades[0].json = original_json(msg);
             }
             Ok(trades)
         }
-        _ => panic!("Unknown market type {}", market_type),
+        _ => Err(ParseError::UnsupportedMarketType(
+            EXCHANGE_NAME.to_string(),
+            market_type,
+        )),
     }
 }
 
@@ -206,27 +202,31 @@ fn parse_l2_legacy(market_type: MarketType, msg: &str) -> Result<Vec<OrderBookMs
     let snapshot = ws_msg.event == "all";
 
     let orderbook = if snapshot {
-        let raw_orderbook = serde_json::from_value::<RawOrderbookSnapshot>(ws_msg.result).unwrap();
+        let raw_orderbook = serde_json::from_value::<RawOrderbookSnapshot>(ws_msg.result)?;
         let symbol = raw_orderbook.contract;
-        let pair = crypto_pair::normalize_pair(&symbol, EXCHANGE_NAME).unwrap();
+        let pair = normalize_pair(&symbol, EXCHANGE_NAME)?;
         let timestamp = if market_type != MarketType::LinearFuture {
-            raw_orderbook.t.unwrap()
+            raw_orderbook
+                .t
+                .ok_or_else(|| ParseError::MalformedField(format!("No t in {}", msg)))?
         } else {
             ws_msg.time * 1000
         };
 
-        let parse_order = |raw_order: &RawOrderLegacy| -> Order {
-            let price = raw_order.p.parse::<f64>().unwrap();
+        let parse_order = |raw_order: &RawOrderLegacy| -> Result<Order> {
+            let price = parse_field(EXCHANGE_NAME, "p", &raw_order.p, msg)?;
             let quantity = raw_order.s;
 
             let (quantity_base, quantity_quote, quantity_contract) =
-                calc_quantity_and_volume(EXCHANGE_NAME, market_type, &pair, price, quantity);
-            Order {
+                calc_quantity_and_volume(EXCHANGE_NAME, market_type, &pair, price, quantity)?;
+            Ok(Order {
                 price,
                 quantity_base,
                 quantity_quote,
                 quantity_contract,
-            }
+                order_id: None,
+                order_type: None,
+            })
         };
 
         OrderBookMsg {
@@ -236,49 +236,62 @@ fn parse_l2_legacy(market_type: MarketType, msg: &str) -> Result<Vec<OrderBookMs
             pair: pair.to_string(),
             msg_type: MessageType::L2Event,
             timestamp,
-            asks: raw_orderbook.asks.iter().map(|x| parse_order(x)).collect(),
-            bids: raw_orderbook.bids.iter().map(|x| parse_order(x)).collect(),
+            timestamp_us: None,
+            asks: raw_orderbook
+                .asks
+                .iter()
+                .map(&parse_order)
+                .collect::<Result<Vec<Order>>>()?,
+            bids: raw_orderbook
+                .bids
+                .iter()
+                .map(&parse_order)
+                .collect::<Result<Vec<Order>>>()?,
             seq_id: None,
             prev_seq_id: None,
             snapshot,
-            json: msg.to_string(),
+            json: original_json(msg),
         }
     } else {
-        let raw_orderbook = serde_json::from_value::<Vec<RawOrderLegacy>>(ws_msg.result).unwrap();
-        let symbol = if market_type == MarketType::LinearFuture {
-            raw_orderbook[0].c.clone().unwrap()
-        } else {
-            raw_orderbook[0].contract.clone().unwrap()
-        };
-        let pair = crypto_pair::normalize_pair(&symbol, EXCHANGE_NAME).unwrap();
+        let raw_orderbook = serde_json::from_value::<Vec<RawOrderLegacy>>(ws_msg.result)?;
+        let symbol = raw_orderbook
+            .first()
+            .and_then(|x| {
+                if market_type == MarketType::LinearFuture {
+                    x.c.clone()
+                } else {
+                    x.contract.clone()
+                }
+            })
+            .ok_or_else(|| ParseError::MalformedField(format!("No contract in {}", msg)))?;
+        let pair = normalize_pair(&symbol, EXCHANGE_NAME)?;
         let timestamp = ws_msg.time * 1000;
 
-        let parse_order = |raw_order: &RawOrderLegacy| -> Order {
-            let price = raw_order.p.parse::<f64>().unwrap();
+        let parse_order = |raw_order: &RawOrderLegacy| -> Result<Order> {
+            let price = parse_field(EXCHANGE_NAME, "p", &raw_order.p, msg)?;
             let quantity = f64::abs(raw_order.s);
 
             let (quantity_base, quantity_quote, quantity_contract) =
-                calc_quantity_and_volume(EXCHANGE_NAME, market_type, &pair, price, quantity);
-            Order {
+                calc_quantity_and_volume(EXCHANGE_NAME, market_type, &pair, price, quantity)?;
+            Ok(Order {
                 price,
                 quantity_base,
                 quantity_quote,
                 quantity_contract,
-            }
+                order_id: None,
+                order_type: None,
+            })
         };
 
-        PRICE_HASHMAP.with(|slf| {
+        PRICE_HASHMAP.with(|slf| -> Result<OrderBookMsg> {
             let mut tmp = slf.borrow_mut();
-            if !tmp.contains_key(&symbol) {
-                tmp.insert(symbol.clone(), HashMap::new());
-            }
-            let price_map = tmp.get_mut(&symbol).unwrap();
+            let price_map = tmp.entry(symbol.clone()).or_default();
 
             let mut asks: Vec<Order> = Vec::new();
             let mut bids: Vec<Order> = Vec::new();
             for x in raw_orderbook.iter() {
                 let price = x.p.clone();
-                let order = parse_order(x);
+                let order = parse_order(x)?;
                 if x.s < 0.0 {
                     asks.push(order);
                     price_map.insert(price, true);
@@ -294,21 +307,22 @@ fn parse_l2_legacy(market_type: MarketType, msg: &str) -> Result<Vec<OrderBookMs
                 }
             }
 
-            OrderBookMsg {
+            Ok(OrderBookMsg {
                 exchange: EXCHANGE_NAME.to_string(),
                 market_type,
                 symbol,
                 pair: pair.to_string(),
                 msg_type: MessageType::L2Event,
                 timestamp,
+                timestamp_us: None,
                 seq_id: None,
                 prev_seq_id: None,
                 asks,
                 bids,
                 snapshot,
-                json: msg.to_string(),
-            }
-        })
+                json: original_json(msg),
+            })
+        })?
     };
 
     Ok(vec![orderbook])
@@ -333,18 +347,25 @@ struct OrderbookUpdateMsg {
     pub extra: HashMap<String, Value>,
 }
 
-fn parse_order(market_type: MarketType, raw_order: &RawOrderNew, pair: &str) -> Order {
-    let price = raw_order.p.parse::<f64>().unwrap();
+fn parse_order(
+    market_type: MarketType,
+    raw_order: &RawOrderNew,
+    pair: &str,
+    msg: &str,
+) -> Result<Order> {
+    let price = parse_field(EXCHANGE_NAME, "p", &raw_order.p, msg)?;
     let quantity = raw_order.s;
 
     let (quantity_base, quantity_quote, quantity_contract) =
-        calc_quantity_and_volume(EXCHANGE_NAME, market_type, pair, price, quantity);
-    Order {
+        calc_quantity_and_volume(EXCHANGE_NAME, market_type, pair, price, quantity)?;
+    Ok(Order {
         price,
         quantity_base,
         quantity_quote,
         quantity_contract,
-    }
+        order_id: None,
+        order_type: None,
+    })
 }
 
 fn parse_l2_update(market_type: MarketType, msg: &str) -> Result<Vec<OrderBookMsg>> {
@@ -352,7 +373,7 @@ fn parse_l2_update(market_type: MarketType, msg: &str) -> Result<Vec<OrderBookMs
     debug_assert_eq!(ws_msg.channel, "futures.order_book_update");
     let result = ws_msg.result;
     let symbol = result.s;
-    let pair = crypto_pair::normalize_pair(&symbol, EXCHANGE_NAME).unwrap();
+    let pair = normalize_pair(&symbol, EXCHANGE_NAME)?;
 
     let orderbook = OrderBookMsg {
         exchange: EXCHANGE_NAME.to_string(),
@@ -361,20 +382,21 @@ fn parse_l2_update(market_type: MarketType, msg: &str) -> Result<Vec<OrderBookMs
         pair: pair.clone(),
         msg_type: MessageType::L2Event,
         timestamp: result.t,
+        timestamp_us: None,
         seq_id: result.extra.get("u").and_then(|v| v.as_u64()),
         prev_seq_id: None,
         asks: result
             .a
             .iter()
-            .map(|x| parse_order(market_type, x, &pair))
-            .collect(),
+            .map(|x| parse_order(market_type, x, &pair, msg))
+            .collect::<Result<Vec<Order>>>()?,
         bids: result
             .b
             .iter()
-            .map(|x| parse_order(market_type, x, &pair))
-            .collect(),
+            .map(|x| parse_order(market_type, x, &pair, msg))
+            .collect::<Result<Vec<Order>>>()?,
         snapshot: ws_msg.event == "all",
-        json: msg.to_string(),
+        json: original_json(msg),
     };
 
     Ok(vec![orderbook])
@@ -387,6 +409,9 @@ pub(crate) fn parse_l2(market_type: MarketType, msg: &str) -> Result<Vec<OrderBo
     } else if ws_msg.channel == "futures.order_book_update" {
         parse_l2_update(market_type, msg)
     } else {
-        panic!("Unknown channel {}", ws_msg.channel);
+        Err(ParseError::MalformedField(format!(
+            "Unknown channel {}",
+            ws_msg.channel
+        )))
     }
 }
@@ -8,7 +8,7 @@ use crypto_market_type::MarketType;
 
 use crate::{OrderBookMsg, TradeMsg};
 
-use serde_json::Result;
+use crate::error::{ParseError, Result};
 
 pub(crate) fn extract_symbol(market_type: MarketType, msg: &str) -> Option<String> {
     if market_type == MarketType::Spot {
@@ -34,7 +34,11 @@ pub(crate) fn parse_l2(
     if market_type == MarketType::Spot {
         gate_spot::parse_l2(
             msg,
-            timestamp.expect("Gate spot orderbook messages don't have timestamp"),
+            timestamp.ok_or_else(|| {
+                ParseError::MalformedField(
+                    "Gate spot orderbook messages don't have timestamp".to_string(),
+                )
+            })?,
         )
     } else {
         gate_swap::parse_l2(market_type, msg)
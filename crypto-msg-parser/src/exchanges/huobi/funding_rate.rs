@@ -1,9 +1,11 @@
 use crypto_market_type::MarketType;
 
+use crate::exchanges::utils::{normalize_pair, original_json, parse_field, raw_json};
 use crate::{FundingRateMsg, MessageType};
 
+use crate::error::Result;
 use serde::{Deserialize, Serialize};
-use serde_json::{Result, Value};
+use serde_json::Value;
 use std::collections::HashMap;
 
 #[derive(Serialize, Deserialize)]
@@ -36,21 +38,34 @@ pub(crate) fn parse_funding_rate(
     let mut funding_rates: Vec<FundingRateMsg> = ws_msg
         .data
         .into_iter()
-        .map(|raw_msg| FundingRateMsg {
-            exchange: "huobi".to_string(),
-            market_type,
-            symbol: raw_msg.contract_code.clone(),
-            pair: crypto_pair::normalize_pair(&raw_msg.contract_code, "huobi").unwrap(),
-            msg_type: MessageType::FundingRate,
-            timestamp: raw_msg.funding_time.parse::<i64>().unwrap(),
-            funding_rate: raw_msg.funding_rate.parse::<f64>().unwrap(),
-            funding_time: raw_msg.settlement_time.parse::<i64>().unwrap(),
-            estimated_rate: Some(raw_msg.estimated_rate.parse::<f64>().unwrap()),
-            json: serde_json::to_string(&raw_msg).unwrap(),
+        .map(|raw_msg| {
+            Ok(FundingRateMsg {
+                exchange: "huobi".to_string(),
+                market_type,
+                symbol: raw_msg.contract_code.clone(),
+                pair: normalize_pair(&raw_msg.contract_code, "huobi")?,
+                msg_type: MessageType::FundingRate,
+                timestamp: parse_field("huobi", "funding_time", &raw_msg.funding_time, msg)?,
+                funding_rate: parse_field("huobi", "funding_rate", &raw_msg.funding_rate, msg)?,
+                funding_time: parse_field(
+                    "huobi",
+                    "settlement_time",
+                    &raw_msg.settlement_time,
+                    msg,
+                )?,
+                estimated_rate: Some(parse_field(
+                    "huobi",
+                    "estimated_rate",
+                    &raw_msg.estimated_rate,
+                    msg,
+                )?),
+                mark_price: None,
+                json: raw_json(&raw_msg),
+            })
         })
-        .collect();
+        .collect::<Result<Vec<FundingRateMsg>>>()?;
     if funding_rates.len() == 1 {
-        funding_rates[0].json = msg.to_string();
+        funding_rates[0].json = original_json(msg);
     }
     Ok(funding_rates)
 }
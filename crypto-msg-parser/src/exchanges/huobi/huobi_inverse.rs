@@ -5,11 +5,13 @@ use crate::{
     TradeSide,
 };
 
+use crate::error::Result;
+use crate::exchanges::utils::{normalize_pair, original_json, parse_field, raw_json};
 use serde::{Deserialize, Serialize};
-use serde_json::{Result, Value};
+use serde_json::Value;
 use std::collections::HashMap;
 
-use super::message::WebsocketMsg;
+use super::message::{channel_symbol, WebsocketMsg};
 
 const EXCHANGE_NAME: &str = "huobi";
 
@@ -53,11 +55,8 @@ struct TradeTick {
 pub(crate) fn parse_trade(market_type: MarketType, msg: &str) -> Result<Vec<TradeMsg>> {
     let ws_msg = serde_json::from_str::<WebsocketMsg<TradeTick>>(msg)?;
 
-    let symbol = {
-        let v: Vec<&str> = ws_msg.ch.split('.').collect();
-        v[1]
-    };
-    let pair = crypto_pair::normalize_pair(symbol, EXCHANGE_NAME).unwrap();
+    let symbol = channel_symbol(&ws_msg.ch)?;
+    let pair = normalize_pair(symbol, EXCHANGE_NAME)?;
 
     let mut trades: Vec<TradeMsg> = ws_msg
         .tick
@@ -70,57 +69,58 @@ pub(crate) fn parse_trade(market_type: MarketType, msg: &str) -> Result<Vec<Trad
                 &pair,
                 raw_trade.price,
                 raw_trade.amount,
-            );
-            TradeMsg {
+            )?;
+            Ok(TradeMsg {
                 exchange: EXCHANGE_NAME.to_string(),
                 market_type,
                 symbol: symbol.to_string(),
                 pair: pair.to_string(),
                 msg_type: MessageType::Trade,
                 timestamp: raw_trade.ts,
+                timestamp_us: None,
                 price: raw_trade.price,
                 quantity_base: raw_trade.quantity,
                 quantity_quote,
                 quantity_contract: Some(raw_trade.amount),
-                side: if raw_trade.direction == "sell" {
-                    TradeSide::Sell
-                } else {
-                    TradeSide::Buy
-                },
+                side: parse_field::<TradeSide>(
+                    EXCHANGE_NAME,
+                    "direction",
+                    &raw_trade.direction,
+                    msg,
+                )?,
                 trade_id: raw_trade.id.to_string(),
-                json: serde_json::to_string(&raw_trade).unwrap(),
-            }
+                json: raw_json(&raw_trade),
+            })
         })
-        .collect();
+        .collect::<Result<Vec<TradeMsg>>>()?;
 
     if trades.len() == 1 {
-        trades[0].json = msg.to_string();
+        trades[0].json = original_json(msg);
     }
     Ok(trades)
 }
 
 pub(crate) fn parse_l2(market_type: MarketType, msg: &str) -> Result<Vec<OrderBookMsg>> {
     let ws_msg = serde_json::from_str::<WebsocketMsg<InverseOrderbookMsg>>(msg)?;
-    let symbol = {
-        let v: Vec<&str> = ws_msg.tick.ch.split('.').collect();
-        v[1]
-    };
-    let pair = crypto_pair::normalize_pair(symbol, EXCHANGE_NAME).unwrap();
+    let symbol = channel_symbol(&ws_msg.tick.ch)?;
+    let pair = normalize_pair(symbol, EXCHANGE_NAME)?;
     let timestamp = ws_msg.tick.ts;
     let snapshot = ws_msg.tick.event == "snapshot";
 
-    let parse_order = |raw_order: &[f64; 2]| -> Order {
+    let parse_order = |raw_order: &[f64; 2]| -> Result<Order> {
         let price = raw_order[0];
         let quantity = raw_order[1];
 
         let (quantity_base, quantity_quote, quantity_contract) =
-            calc_quantity_and_volume(EXCHANGE_NAME, market_type, &pair, price, quantity);
-        Order {
+            calc_quantity_and_volume(EXCHANGE_NAME, market_type, &pair, price, quantity)?;
+        Ok(Order {
             price,
             quantity_base,
             quantity_quote,
             quantity_contract,
-        }
+            order_id: None,
+            order_type: None,
+        })
     };
 
     let orderbook = OrderBookMsg {
@@ -130,12 +130,23 @@ pub(crate) fn parse_l2(market_type: MarketType, msg: &str) -> Result<Vec<OrderBo
         pair: pair.to_string(),
         msg_type: MessageType::L2Event,
         timestamp,
+        timestamp_us: None,
         seq_id: Some(ws_msg.tick.mrid),
         prev_seq_id: None,
-        asks: ws_msg.tick.asks.iter().map(|x| parse_order(x)).collect(),
-        bids: ws_msg.tick.bids.iter().map(|x| parse_order(x)).collect(),
+        asks: ws_msg
+            .tick
+            .asks
+            .iter()
+            .map(parse_order)
+            .collect::<Result<Vec<Order>>>()?,
+        bids: ws_msg
+            .tick
+            .bids
+            .iter()
+            .map(parse_order)
+            .collect::<Result<Vec<Order>>>()?,
         snapshot,
-        json: msg.to_string(),
+        json: original_json(msg),
     };
 
     Ok(vec![orderbook])
@@ -2,11 +2,13 @@ use crypto_market_type::MarketType;
 
 use crate::{MessageType, TradeMsg, TradeSide};
 
+use crate::error::Result;
+use crate::exchanges::utils::{normalize_pair, original_json, parse_field, raw_json};
 use serde::{Deserialize, Serialize};
-use serde_json::{Result, Value};
+use serde_json::Value;
 use std::collections::HashMap;
 
-use super::message::WebsocketMsg;
+use super::message::{channel_symbol, WebsocketMsg};
 
 const EXCHANGE_NAME: &str = "huobi";
 
@@ -36,39 +38,40 @@ struct TradeTick {
 pub(crate) fn parse_trade(market_type: MarketType, msg: &str) -> Result<Vec<TradeMsg>> {
     let ws_msg = serde_json::from_str::<WebsocketMsg<TradeTick>>(msg)?;
 
-    let symbol = {
-        let v: Vec<&str> = ws_msg.ch.split('.').collect();
-        v[1]
-    };
-    let pair = crypto_pair::normalize_pair(symbol, EXCHANGE_NAME).unwrap();
+    let symbol = channel_symbol(&ws_msg.ch)?;
+    let pair = normalize_pair(symbol, EXCHANGE_NAME)?;
 
     let mut trades: Vec<TradeMsg> = ws_msg
         .tick
         .data
         .into_iter()
-        .map(|raw_trade| TradeMsg {
-            exchange: EXCHANGE_NAME.to_string(),
-            market_type,
-            symbol: symbol.to_string(),
-            pair: pair.to_string(),
-            msg_type: MessageType::Trade,
-            timestamp: raw_trade.ts,
-            price: raw_trade.price,
-            quantity_base: raw_trade.quantity,
-            quantity_quote: raw_trade.trade_turnover,
-            quantity_contract: Some(raw_trade.amount),
-            side: if raw_trade.direction == "sell" {
-                TradeSide::Sell
-            } else {
-                TradeSide::Buy
-            },
-            trade_id: raw_trade.id.to_string(),
-            json: serde_json::to_string(&raw_trade).unwrap(),
+        .map(|raw_trade| {
+            Ok(TradeMsg {
+                exchange: EXCHANGE_NAME.to_string(),
+                market_type,
+                symbol: symbol.to_string(),
+                pair: pair.to_string(),
+                msg_type: MessageType::Trade,
+                timestamp: raw_trade.ts,
+                timestamp_us: None,
+                price: raw_trade.price,
+                quantity_base: raw_trade.quantity,
+                quantity_quote: raw_trade.trade_turnover,
+                quantity_contract: Some(raw_trade.amount),
+                side: parse_field::<TradeSide>(
+                    EXCHANGE_NAME,
+                    "direction",
+                    &raw_trade.direction,
+                    msg,
+                )?,
+                trade_id: raw_trade.id.to_string(),
+                json: raw_json(&raw_trade),
+            })
         })
-        .collect();
+        .collect::<Result<Vec<TradeMsg>>>()?;
 
     if trades.len() == 1 {
-        trades[0].json = msg.to_string();
+        trades[0].json = original_json(msg);
     }
     Ok(trades)
 }
@@ -2,11 +2,13 @@ use crypto_market_type::MarketType;
 
 use crate::{MessageType, Order, OrderBookMsg, TradeMsg, TradeSide};
 
+use crate::error::Result;
+use crate::exchanges::utils::{normalize_pair, original_json, parse_field, raw_json};
 use serde::{Deserialize, Serialize};
-use serde_json::{Result, Value};
+use serde_json::Value;
 use std::collections::HashMap;
 
-use super::message::WebsocketMsg;
+use super::message::{channel_symbol, WebsocketMsg};
 
 const EXCHANGE_NAME: &str = "huobi";
 
@@ -47,50 +49,48 @@ struct TradeTick {
 pub(super) fn parse_trade(msg: &str) -> Result<Vec<TradeMsg>> {
     let ws_msg = serde_json::from_str::<WebsocketMsg<TradeTick>>(msg)?;
 
-    let symbol = {
-        let v: Vec<&str> = ws_msg.ch.split('.').collect();
-        v[1]
-    };
-    let pair = crypto_pair::normalize_pair(symbol, EXCHANGE_NAME).unwrap();
+    let symbol = channel_symbol(&ws_msg.ch)?;
+    let pair = normalize_pair(symbol, EXCHANGE_NAME)?;
 
     let mut trades: Vec<TradeMsg> = ws_msg
         .tick
         .data
         .into_iter()
-        .map(|raw_trade| TradeMsg {
-            exchange: EXCHANGE_NAME.to_string(),
-            market_type: MarketType::Spot,
-            symbol: symbol.to_string(),
-            pair: pair.to_string(),
-            msg_type: MessageType::Trade,
-            timestamp: raw_trade.ts,
-            price: raw_trade.price,
-            quantity_base: raw_trade.amount,
-            quantity_quote: raw_trade.price * raw_trade.amount,
-            quantity_contract: None,
-            side: if raw_trade.direction == "sell" {
-                TradeSide::Sell
-            } else {
-                TradeSide::Buy
-            },
-            trade_id: raw_trade.tradeId.to_string(),
-            json: serde_json::to_string(&raw_trade).unwrap(),
+        .map(|raw_trade| {
+            Ok(TradeMsg {
+                exchange: EXCHANGE_NAME.to_string(),
+                market_type: MarketType::Spot,
+                symbol: symbol.to_string(),
+                pair: pair.to_string(),
+                msg_type: MessageType::Trade,
+                timestamp: raw_trade.ts,
+                timestamp_us: None,
+                price: raw_trade.price,
+                quantity_base: raw_trade.amount,
+                quantity_quote: raw_trade.price * raw_trade.amount,
+                quantity_contract: None,
+                side: parse_field::<TradeSide>(
+                    EXCHANGE_NAME,
+                    "direction",
+                    &raw_trade.direction,
+                    msg,
+                )?,
+                trade_id: raw_trade.tradeId.to_string(),
+                json: raw_json(&raw_trade),
+            })
         })
-        .collect();
+        .collect::<Result<Vec<TradeMsg>>>()?;
 
     if trades.len() == 1 {
-        trades[0].json = msg.to_string();
+        trades[0].json = original_json(msg);
     }
     Ok(trades)
 }
 
 pub(crate) fn parse_l2(msg: &str) -> Result<Vec<OrderBookMsg>> {
     let ws_msg = serde_json::from_str::<WebsocketMsg<SpotOrderbookMsg>>(msg)?;
-    let symbol = {
-        let v: Vec<&str> = ws_msg.ch.split('.').collect();
-        v[1]
-    };
-    let pair = crypto_pair::normalize_pair(symbol, EXCHANGE_NAME).unwrap();
+    let symbol = channel_symbol(&ws_msg.ch)?;
+    let pair = normalize_pair(symbol, EXCHANGE_NAME)?;
     let timestamp = ws_msg.ts;
 
     let parse_order = |raw_order: &[f64; 2]| -> Order {
@@ -102,6 +102,8 @@ pub(crate) fn parse_l2(msg: &str) -> Result<Vec<OrderBookMsg>> {
             quantity_base,
             quantity_quote: price * quantity_base,
             quantity_contract: None,
+            order_id: None,
+            order_type: None,
         }
     };
 
@@ -112,6 +114,7 @@ pub(crate) fn parse_l2(msg: &str) -> Result<Vec<OrderBookMsg>> {
         pair,
         msg_type: MessageType::L2Event,
         timestamp,
+        timestamp_us: None,
         seq_id: Some(ws_msg.tick.seq_num),
         prev_seq_id: Some(ws_msg.tick.prev_seq_num),
         asks: ws_msg
@@ -129,7 +132,7 @@ pub(crate) fn parse_l2(msg: &str) -> Result<Vec<OrderBookMsg>> {
             .map(|x| parse_order(&x))
             .collect(),
         snapshot: false,
-        json: msg.to_string(),
+        json: original_json(msg),
     };
 
     Ok(vec![orderbook])
@@ -1,8 +1,17 @@
 use serde::{Deserialize, Serialize};
 
+use crate::error::{ParseError, Result};
+
 #[derive(Serialize, Deserialize)]
 pub(super) struct WebsocketMsg<T: Sized> {
     pub ch: String,
     pub ts: i64,
     pub tick: T,
 }
+
+// The symbol is the second part of a channel, e.g., market.BTC-USD.trade.detail
+pub(super) fn channel_symbol(ch: &str) -> Result<&str> {
+    ch.split('.')
+        .nth(1)
+        .ok_or_else(|| ParseError::MalformedField(format!("No symbol in channel {}", ch)))
+}
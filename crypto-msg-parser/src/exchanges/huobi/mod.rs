@@ -8,14 +8,15 @@ use crypto_market_type::MarketType;
 
 use crate::{FundingRateMsg, OrderBookMsg, TradeMsg};
 
-use serde_json::{Result, Value};
+use crate::error::{ParseError, Result};
+use serde_json::Value;
 
 use message::WebsocketMsg;
 
 pub(crate) fn extract_symbol(_market_type_: MarketType, msg: &str) -> Option<String> {
-    let ws_msg = serde_json::from_str::<WebsocketMsg<Value>>(msg).unwrap();
-    let v = ws_msg.ch.split('.').collect::<Vec<&str>>();
-    Some(v[1].to_string())
+    let ws_msg = serde_json::from_str::<WebsocketMsg<Value>>(msg).ok()?;
+    let symbol = message::channel_symbol(&ws_msg.ch).ok()?;
+    Some(symbol.to_string())
 }
 
 pub(crate) fn parse_trade(market_type: MarketType, msg: &str) -> Result<Vec<TradeMsg>> {
@@ -27,7 +28,10 @@ pub(crate) fn parse_trade(market_type: MarketType, msg: &str) -> Result<Vec<Trad
         MarketType::LinearFuture | MarketType::LinearSwap | MarketType::EuropeanOption => {
             huobi_linear::parse_trade(market_type, msg)
         }
-        _ => panic!("Unknown market type {}", market_type),
+        _ => Err(ParseError::UnsupportedMarketType(
+            "huobi".to_string(),
+            market_type,
+        )),
     }
 }
 
@@ -38,7 +42,10 @@ pub(crate) fn parse_funding_rate(
     if market_type == MarketType::InverseSwap || market_type == MarketType::LinearSwap {
         funding_rate::parse_funding_rate(market_type, msg)
     } else {
-        panic!("Huobi {} does NOT have funding rates", market_type);
+        Err(ParseError::UnsupportedMarketType(
+            "huobi".to_string(),
+            market_type,
+        ))
     }
 }
 
@@ -51,6 +58,9 @@ pub(crate) fn parse_l2(market_type: MarketType, msg: &str) -> Result<Vec<OrderBo
         MarketType::LinearFuture | MarketType::LinearSwap | MarketType::EuropeanOption => {
             huobi_inverse::parse_l2(market_type, msg)
         }
-        _ => panic!("Unknown market type {}", market_type),
+        _ => Err(ParseError::UnsupportedMarketType(
+            "huobi".to_string(),
+            market_type,
+        )),
     }
 }
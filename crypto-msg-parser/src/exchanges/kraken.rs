@@ -2,8 +2,10 @@ use crypto_market_type::MarketType;
 
 use crate::{MessageType, Order, OrderBookMsg, TradeMsg, TradeSide};
 
+use crate::error::{ParseError, Result};
+use crate::exchanges::utils::{normalize_pair, original_json, parse_field, raw_json};
 use serde::{Deserialize, Serialize};
-use serde_json::{Result, Value};
+use serde_json::Value;
 use std::collections::HashMap;
 
 const EXCHANGE_NAME: &str = "kraken";
@@ -30,9 +32,9 @@ struct SpotTradeMsg {
 #[derive(Serialize, Deserialize)]
 struct OrderbookSnapshot {
     #[serde(rename = "as")]
-    asks: Vec<[String; 3]>,
+    asks: Vec<Vec<String>>,
     #[serde(rename = "bs")]
-    bids: Vec<[String; 3]>,
+    bids: Vec<Vec<String>>,
     #[serde(flatten)]
     extra: HashMap<String, Value>,
 }
@@ -47,51 +49,77 @@ struct OrderbookUpdate {
 }
 
 pub(crate) fn extract_symbol(_market_type_: MarketType, msg: &str) -> Option<String> {
-    let arr = serde_json::from_str::<Vec<Value>>(msg).unwrap();
-    let symbol = arr[arr.len() - 1].as_str().unwrap();
-    Some(symbol.to_string())
+    let arr = serde_json::from_str::<Vec<Value>>(msg).ok()?;
+    arr.last()?.as_str().map(|symbol| symbol.to_string())
+}
+
+// The symbol is the last element of a data message
+fn get_symbol<'a>(arr: &'a [Value], msg: &str) -> Result<&'a str> {
+    arr.last()
+        .and_then(|x| x.as_str())
+        .ok_or_else(|| ParseError::MalformedField(format!("No symbol in {}", msg)))
+}
+
+// Kraken timestamps are seconds with fractions
+fn parse_time(value: &str, msg: &str) -> Result<i64> {
+    let seconds: f64 = parse_field(EXCHANGE_NAME, "time", value, msg)?;
+    Ok((seconds * 1000.0) as i64)
+}
+
+// Checks that a trade or order has at least `len` fields
+fn check_len(raw: &[String], len: usize, msg: &str) -> Result<()> {
+    if raw.len() < len {
+        Err(ParseError::MalformedField(format!(
+            "Expected {} fields in {:?} of {}",
+            len, raw, msg
+        )))
+    } else {
+        Ok(())
+    }
 }
 
 pub(crate) fn parse_trade(market_type: MarketType, msg: &str) -> Result<Vec<TradeMsg>> {
     let arr = serde_json::from_str::<Vec<Value>>(msg)?;
-    debug_assert_eq!(arr[2].as_str().unwrap(), "trade");
-    debug_assert_eq!(arr.len(), 4);
-    let symbol = arr[arr.len() - 1].as_str().unwrap();
-    let pair = crypto_pair::normalize_pair(symbol, EXCHANGE_NAME).unwrap();
-    let raw_trades: Vec<Vec<String>> = serde_json::from_value(arr[1].clone()).unwrap();
+    if arr.len() != 4 || arr[2].as_str() != Some("trade") {
+        return Err(ParseError::MalformedField(format!(
+            "Unknown message format {}",
+            msg
+        )));
+    }
+    let symbol = get_symbol(&arr, msg)?;
+    let pair = normalize_pair(symbol, EXCHANGE_NAME)?;
+    let raw_trades: Vec<Vec<String>> = serde_json::from_value(arr[1].clone())?;
 
     // trade format https://docs.kraken.com/websockets/#message-trade
-    let mut trades: Vec<TradeMsg> = raw_trades
+    let mut trades = raw_trades
         .into_iter()
         .map(|raw_trade| {
-            let price = raw_trade[0].parse::<f64>().unwrap();
-            let quantity = raw_trade[1].parse::<f64>().unwrap();
-            let timestamp = (raw_trade[2].parse::<f64>().unwrap() * 1000.0) as i64;
+            check_len(&raw_trade, 4, msg)?;
+            let price = parse_field(EXCHANGE_NAME, "price", &raw_trade[0], msg)?;
+            let quantity = parse_field(EXCHANGE_NAME, "volume", &raw_trade[1], msg)?;
+            let timestamp = parse_time(&raw_trade[2], msg)?;
 
-            TradeMsg {
+            Ok(TradeMsg {
                 exchange: EXCHANGE_NAME.to_string(),
                 market_type,
                 symbol: symbol.to_string(),
                 pair: pair.clone(),
                 msg_type: MessageType::Trade,
                 timestamp,
+                timestamp_us: None,
                 price,
                 quantity_base: quantity,
                 quantity_quote: price * quantity,
                 quantity_contract: None,
-                side: if raw_trade[3] == "s" {
-                    TradeSide::Sell
-                } else {
-                    TradeSide::Buy
-                },
+                side: parse_field::<TradeSide>(EXCHANGE_NAME, "side", &raw_trade[3], msg)?,
                 trade_id: timestamp.to_string(),
-                json: serde_json::to_string(&raw_trade).unwrap(),
-            }
+                json: raw_json(&raw_trade),
+            })
         })
-        .collect();
+        .collect::<Result<Vec<TradeMsg>>>()?;
 
     if trades.len() == 1 {
-        trades[0].json = msg.to_string();
+        trades[0].json = original_json(msg);
     }
     Ok(trades)
 }
@@ -99,128 +127,98 @@ pub(crate) fn parse_trade(market_type: MarketType, msg: &str) -> Result<Vec<Trad
 pub(crate) fn parse_l2(market_type: MarketType, msg: &str) -> Result<Vec<OrderBookMsg>> {
     debug_assert_eq!(market_type, MarketType::Spot);
     let arr = serde_json::from_str::<Vec<Value>>(msg)?;
-    debug_assert_eq!(arr[arr.len() - 2].as_str().unwrap(), "book-25");
-    let symbol = arr[arr.len() - 1].as_str().unwrap().to_string();
-    let pair = crypto_pair::normalize_pair(&symbol, EXCHANGE_NAME).unwrap();
-    let snapshot = arr[1].as_object().unwrap().contains_key("as");
+    if arr.len() < 4
+        || !arr[arr.len() - 2]
+            .as_str()
+            .unwrap_or_default()
+            .starts_with("book")
+    {
+        return Err(ParseError::MalformedField(format!(
+            "Unknown message format {}",
+            msg
+        )));
+    }
+    let symbol = get_symbol(&arr, msg)?.to_string();
+    let pair = normalize_pair(&symbol, EXCHANGE_NAME)?;
+    let snapshot = arr[1].get("as").is_some();
 
-    let parse_order = |raw_order: &[String]| -> Order {
-        let price = raw_order[0].parse::<f64>().unwrap();
-        let quantity_base = raw_order[1].parse::<f64>().unwrap();
+    // returns the order and its timestamp
+    let parse_order = |raw_order: &[String]| -> Result<(Order, i64)> {
+        check_len(raw_order, 3, msg)?;
+        let price = parse_field(EXCHANGE_NAME, "price", &raw_order[0], msg)?;
+        let quantity_base = parse_field(EXCHANGE_NAME, "volume", &raw_order[1], msg)?;
 
-        Order {
+        let order = Order {
             price,
             quantity_base,
             quantity_quote: price * quantity_base,
             quantity_contract: None,
-        }
+            order_id: None,
+            order_type: None,
+        };
+        Ok((order, parse_time(&raw_order[2], msg)?))
     };
 
-    let orderbooks = if snapshot {
-        let orderbook_snapshot = serde_json::from_value::<OrderbookSnapshot>(arr[1].clone())?;
-
-        let timestamp = {
-            let mut timestamps: Vec<i64> = Vec::new();
-            for ask in orderbook_snapshot.asks.iter() {
-                let t = (ask[2].parse::<f64>().unwrap() * 1000.0) as i64;
-                timestamps.push(t);
-            }
-            for bid in orderbook_snapshot.bids.iter() {
-                let t = (bid[2].parse::<f64>().unwrap() * 1000.0) as i64;
-                timestamps.push(t);
-            }
-            if timestamps.is_empty() {
-                None
+    let mut asks: Vec<Order> = Vec::new();
+    let mut bids: Vec<Order> = Vec::new();
+    let mut timestamp: Option<i64> = None;
+    let mut process_orders = |raw_orders: &[Vec<String>], is_ask: bool| -> Result<()> {
+        for raw_order in raw_orders.iter() {
+            let (order, t) = parse_order(raw_order)?;
+            if is_ask {
+                asks.push(order);
             } else {
-                let max = *timestamps.iter().max().unwrap();
-                Some(max)
+                bids.push(order);
             }
-        };
-
-        if let Some(timestamp) = timestamp {
-            vec![OrderBookMsg {
-                exchange: EXCHANGE_NAME.to_string(),
-                market_type: MarketType::Spot,
-                symbol,
-                pair,
-                msg_type: MessageType::L2Event,
-                timestamp,
-                seq_id: None,
-                prev_seq_id: None,
-                asks: orderbook_snapshot
-                    .asks
-                    .iter()
-                    .map(|x| parse_order(x))
-                    .collect(),
-                bids: orderbook_snapshot
-                    .bids
-                    .iter()
-                    .map(|x| parse_order(x))
-                    .collect(),
-                snapshot,
-                json: msg.to_string(),
-            }]
-        } else {
-            vec![]
+            timestamp = Some(timestamp.map_or(t, |x| x.max(t)));
         }
+        Ok(())
+    };
+
+    if snapshot {
+        let orderbook_snapshot = serde_json::from_value::<OrderbookSnapshot>(arr[1].clone())?;
+        process_orders(&orderbook_snapshot.asks, true)?;
+        process_orders(&orderbook_snapshot.bids, false)?;
     } else {
-        let mut asks: Vec<Order> = Vec::new();
-        let mut bids: Vec<Order> = Vec::new();
-        let mut timestamps: Vec<i64> = Vec::new();
-        let mut process_update = |update: OrderbookUpdate| {
+        let updates = match arr.len() {
+            4 => &arr[1..2],
+            5 => &arr[1..3],
+            _ => {
+                return Err(ParseError::MalformedField(format!(
+                    "Unknown message format {}",
+                    msg
+                )))
+            }
+        };
+        for update in updates {
+            let update = serde_json::from_value::<OrderbookUpdate>(update.clone())?;
             if let Some(a) = update.a {
-                for raw_order in a.iter() {
-                    let order = parse_order(raw_order);
-                    asks.push(order);
-                    let t = (raw_order[2].parse::<f64>().unwrap() * 1000.0) as i64;
-                    timestamps.push(t);
-                }
+                process_orders(&a, true)?;
             }
             if let Some(b) = update.b {
-                for raw_order in b.iter() {
-                    let order = parse_order(raw_order);
-                    bids.push(order);
-                    let t = (raw_order[2].parse::<f64>().unwrap() * 1000.0) as i64;
-                    timestamps.push(t);
-                }
+                process_orders(&b, false)?;
             }
-        };
-        if arr.len() == 4 {
-            let update = serde_json::from_value::<OrderbookUpdate>(arr[1].clone())?;
-            process_update(update);
-        } else if arr.len() == 5 {
-            let update = serde_json::from_value::<OrderbookUpdate>(arr[1].clone())?;
-            process_update(update);
-            let update = serde_json::from_value::<OrderbookUpdate>(arr[2].clone())?;
-            process_update(update);
-        } else {
-            panic!("Unknown message format {}", msg);
-        };
-
-        let timestamp = if timestamps.is_empty() {
-            None
-        } else {
-            let max = *timestamps.iter().max().unwrap();
-            Some(max)
-        };
-        if let Some(timestamp) = timestamp {
-            vec![OrderBookMsg {
-                exchange: EXCHANGE_NAME.to_string(),
-                market_type: MarketType::Spot,
-                symbol,
-                pair,
-                msg_type: MessageType::L2Event,
-                timestamp,
-                seq_id: None,
-                prev_seq_id: None,
-                asks,
-                bids,
-                snapshot,
-                json: msg.to_string(),
-            }]
-        } else {
-            vec![]
         }
+    }
+
+    let orderbooks = if let Some(timestamp) = timestamp {
+        vec![OrderBookMsg {
+            exchange: EXCHANGE_NAME.to_string(),
+            market_type: MarketType::Spot,
+            symbol,
+            pair,
+            msg_type: MessageType::L2Event,
+            timestamp,
+            timestamp_us: None,
+            seq_id: None,
+            prev_seq_id: None,
+            asks,
+            bids,
+            snapshot,
+            json: original_json(msg),
+        }]
+    } else {
+        vec![]
     };
 
     Ok(orderbooks)
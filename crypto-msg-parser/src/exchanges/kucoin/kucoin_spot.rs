@@ -2,8 +2,10 @@ use crypto_market_type::MarketType;
 
 use crate::{MessageType, Order, OrderBookMsg, TradeMsg, TradeSide};
 
+use crate::error::Result;
+use crate::exchanges::utils::{normalize_pair, original_json, parse_field};
 use serde::{Deserialize, Serialize};
-use serde_json::{Result, Value};
+use serde_json::Value;
 use std::collections::HashMap;
 
 use super::message::WebsocketMsg;
@@ -45,27 +47,25 @@ pub(super) fn parse_trade(msg: &str) -> Result<Vec<TradeMsg>> {
     debug_assert_eq!(ws_msg.subject, "trade.l3match");
     debug_assert!(ws_msg.topic.starts_with("/market/match:"));
     let raw_trade = ws_msg.data;
-    let price = raw_trade.price.parse::<f64>().unwrap();
-    let quantity = raw_trade.size.parse::<f64>().unwrap();
+    let price = parse_field(EXCHANGE_NAME, "price", &raw_trade.price, msg)?;
+    let quantity = parse_field(EXCHANGE_NAME, "size", &raw_trade.size, msg)?;
+    let time: i64 = parse_field(EXCHANGE_NAME, "time", &raw_trade.time, msg)?; // nanoseconds
 
     let trade = TradeMsg {
         exchange: EXCHANGE_NAME.to_string(),
         market_type: MarketType::Spot,
         symbol: raw_trade.symbol.clone(),
-        pair: crypto_pair::normalize_pair(&raw_trade.symbol, EXCHANGE_NAME).unwrap(),
+        pair: normalize_pair(&raw_trade.symbol, EXCHANGE_NAME)?,
         msg_type: MessageType::Trade,
-        timestamp: raw_trade.time.parse::<i64>().unwrap() / 1000000,
+        timestamp: time / 1000000,
+        timestamp_us: Some(time / 1000),
         price,
         quantity_base: quantity,
         quantity_quote: price * quantity,
         quantity_contract: None,
-        side: if raw_trade.side == "sell" {
-            TradeSide::Sell
-        } else {
-            TradeSide::Buy
-        },
+        side: parse_field::<TradeSide>(EXCHANGE_NAME, "side", &raw_trade.side, msg)?,
         trade_id: raw_trade.sequence.to_string(),
-        json: msg.to_string(),
+        json: original_json(msg),
     };
 
     Ok(vec![trade])
@@ -76,18 +76,20 @@ pub(crate) fn parse_l2(msg: &str, timestamp: i64) -> Result<Vec<OrderBookMsg>> {
     debug_assert_eq!(ws_msg.subject, "trade.l2update");
     debug_assert!(ws_msg.topic.starts_with("/market/level2:"));
     let symbol = ws_msg.data.symbol;
-    let pair = crypto_pair::normalize_pair(&symbol, EXCHANGE_NAME).unwrap();
+    let pair = normalize_pair(&symbol, EXCHANGE_NAME)?;
 
-    let parse_order = |raw_order: &[String; 3]| -> Order {
-        let price = raw_order[0].parse::<f64>().unwrap();
-        let quantity_base = raw_order[1].parse::<f64>().unwrap();
+    let parse_order = |raw_order: &[String; 3]| -> Result<Order> {
+        let price = parse_field(EXCHANGE_NAME, "price", &raw_order[0], msg)?;
+        let quantity_base = parse_field(EXCHANGE_NAME, "size", &raw_order[1], msg)?;
 
-        Order {
+        Ok(Order {
             price,
             quantity_base,
             quantity_quote: price * quantity_base,
             quantity_contract: None,
-        }
+            order_id: None,
+            order_type: None,
+        })
     };
 
     let orderbook = OrderBookMsg {
@@ -97,24 +99,25 @@ pub(crate) fn parse_l2(msg: &str, timestamp: i64) -> Result<Vec<OrderBookMsg>> {
         pair,
         msg_type: MessageType::L2Event,
         timestamp,
-        seq_id: Some(ws_msg.data.sequenceStart as u64),
-        prev_seq_id: None,
+        timestamp_us: None,
+        seq_id: Some(ws_msg.data.sequenceEnd as u64),
+        prev_seq_id: (ws_msg.data.sequenceStart as u64).checked_sub(1),
         asks: ws_msg
             .data
             .changes
             .asks
             .iter()
-            .map(|x| parse_order(x))
-            .collect(),
+            .map(parse_order)
+            .collect::<Result<Vec<Order>>>()?,
         bids: ws_msg
             .data
             .changes
             .bids
             .iter()
-            .map(|x| parse_order(x))
-            .collect(),
+            .map(parse_order)
+            .collect::<Result<Vec<Order>>>()?,
         snapshot: false,
-        json: msg.to_string(),
+        json: original_json(msg),
     };
 
     Ok(vec![orderbook])
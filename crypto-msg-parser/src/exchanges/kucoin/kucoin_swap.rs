@@ -5,8 +5,10 @@ use crate::{
     MessageType, Order, OrderBookMsg, TradeMsg, TradeSide,
 };
 
+use crate::error::{ParseError, Result};
+use crate::exchanges::utils::{normalize_pair, original_json, parse_field};
 use serde::{Deserialize, Serialize};
-use serde_json::{Result, Value};
+use serde_json::Value;
 use std::collections::HashMap;
 
 const EXCHANGE_NAME: &str = "kucoin";
@@ -36,7 +38,7 @@ struct ContractTradeMsg {
     side: String, // buy, sell
     size: f64,
     price: f64,
-    ts: i64,
+    ts: i64, // nanoseconds
     #[serde(flatten)]
     extra: HashMap<String, Value>,
 }
@@ -56,14 +58,14 @@ pub(crate) fn parse_trade(market_type: MarketType, msg: &str) -> Result<Vec<Trad
     debug_assert_eq!(ws_msg.subject, "match");
     debug_assert!(ws_msg.topic.starts_with("/contractMarket/execution:"));
     let raw_trade = ws_msg.data;
-    let pair = crypto_pair::normalize_pair(&raw_trade.symbol, EXCHANGE_NAME).unwrap();
+    let pair = normalize_pair(&raw_trade.symbol, EXCHANGE_NAME)?;
     let (quantity_base, quantity_quote, quantity_contract) = calc_quantity_and_volume(
         EXCHANGE_NAME,
         market_type,
         &pair,
         raw_trade.price,
         raw_trade.size,
-    );
+    )?;
 
     let trade = TradeMsg {
         exchange: EXCHANGE_NAME.to_string(),
@@ -72,17 +74,14 @@ pub(crate) fn parse_trade(market_type: MarketType, msg: &str) -> Result<Vec<Trad
         pair,
         msg_type: MessageType::Trade,
         timestamp: raw_trade.ts / 1000000,
+        timestamp_us: Some(raw_trade.ts / 1000),
         price: raw_trade.price,
         quantity_base,
         quantity_quote,
         quantity_contract,
-        side: if raw_trade.side == "sell" {
-            TradeSide::Sell
-        } else {
-            TradeSide::Buy
-        },
+        side: parse_field::<TradeSide>(EXCHANGE_NAME, "side", &raw_trade.side, msg)?,
         trade_id: raw_trade.sequence.to_string(),
-        json: msg.to_string(),
+        json: original_json(msg),
     };
 
     Ok(vec![trade])
@@ -95,21 +94,30 @@ pub(crate) fn parse_l2(market_type: MarketType, msg: &str) -> Result<Vec<OrderBo
     let symbol = ws_msg
         .topic
         .strip_prefix("/contractMarket/level2:")
-        .unwrap();
-    let pair = crypto_pair::normalize_pair(symbol, EXCHANGE_NAME).unwrap();
+        .ok_or_else(|| ParseError::MalformedField(format!("Unknown topic in {}", msg)))?;
+    let pair = normalize_pair(symbol, EXCHANGE_NAME)?;
 
+    // change is price,side,quantity
     let raw_order: Vec<&str> = ws_msg.data.change.split(',').collect();
+    if raw_order.len() != 3 {
+        return Err(ParseError::MalformedField(format!(
+            "Invalid change in {}",
+            msg
+        )));
+    }
     let order: Order = {
-        let price = raw_order[0].parse::<f64>().unwrap();
-        let quantity = raw_order[2].parse::<f64>().unwrap();
+        let price = parse_field(EXCHANGE_NAME, "price", raw_order[0], msg)?;
+        let quantity = parse_field(EXCHANGE_NAME, "quantity", raw_order[2], msg)?;
 
         let (quantity_base, quantity_quote, quantity_contract) =
-            calc_quantity_and_volume(EXCHANGE_NAME, market_type, &pair, price, quantity);
+            calc_quantity_and_volume(EXCHANGE_NAME, market_type, &pair, price, quantity)?;
         Order {
             price,
             quantity_base,
             quantity_quote,
             quantity_contract,
+            order_id: None,
+            order_type: None,
         }
     };
 
@@ -128,12 +136,14 @@ pub(crate) fn parse_l2(market_type: MarketType, msg: &str) -> Result<Vec<OrderBo
         pair,
         msg_type: MessageType::L2Event,
         timestamp: ws_msg.data.timestamp,
+        timestamp_us: None,
         seq_id: Some(ws_msg.data.sequence as u64),
-        prev_seq_id: None,
+        // Each message carries exactly one change, so sequence increases by 1
+        prev_seq_id: (ws_msg.data.sequence as u64).checked_sub(1),
         asks,
         bids,
         snapshot: false,
-        json: msg.to_string(),
+        json: original_json(msg),
     };
 
     Ok(vec![orderbook])
@@ -6,14 +6,14 @@ use crypto_market_type::MarketType;
 
 use crate::{OrderBookMsg, TradeMsg};
 
-use serde_json::{Result, Value};
+use crate::error::{ParseError, Result};
+use serde_json::Value;
 
 use self::message::WebsocketMsg;
 
 pub(crate) fn extract_symbol(_market_type: MarketType, msg: &str) -> Option<String> {
-    let ws_msg = serde_json::from_str::<WebsocketMsg<Value>>(msg).unwrap();
-    let pos = ws_msg.topic.rfind(':').unwrap();
-    let symbol = &ws_msg.topic[pos + 1..];
+    let ws_msg = serde_json::from_str::<WebsocketMsg<Value>>(msg).ok()?;
+    let (_, symbol) = ws_msg.topic.rsplit_once(':')?;
     Some(symbol.to_string())
 }
 
@@ -31,7 +31,11 @@ pub(crate) fn parse_l2(
     timestamp: Option<i64>,
 ) -> Result<Vec<OrderBookMsg>> {
     if market_type == MarketType::Spot {
-        kucoin_spot::parse_l2(msg, timestamp.unwrap())
+        // spot level2 messages don't carry a timestamp
+        let timestamp = timestamp.ok_or_else(|| {
+            ParseError::MalformedField("kucoin spot level2 messages need a timestamp".to_string())
+        })?;
+        kucoin_spot::parse_l2(msg, timestamp)
     } else {
         kucoin_swap::parse_l2(market_type, msg)
     }
@@ -1,3 +1,4 @@
+mod registry;
 mod utils;
 
 pub(super) mod binance;
@@ -19,3 +20,5 @@ pub(super) mod kucoin;
 pub(super) mod mxc;
 pub(super) mod okex;
 pub(super) mod zbg;
+
+pub(crate) use registry::{lookup, ParseFn};
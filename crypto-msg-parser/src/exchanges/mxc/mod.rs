@@ -7,28 +7,33 @@ use crypto_market_type::MarketType;
 
 use crate::{OrderBookMsg, TradeMsg};
 
-use serde_json::{Result, Value};
+use crate::error::{ParseError, Result};
+use serde_json::Value;
+
+// crypto-ws-client strips the socket.io `42` prefix of MXC spot messages,
+// but raw frames captured by other tools still carry it.
+fn strip_socketio_prefix(msg: &str) -> &str {
+    msg.strip_prefix("42").unwrap_or(msg)
+}
 
 pub(crate) fn extract_symbol(market_type_: MarketType, msg: &str) -> Option<String> {
     if market_type_ == MarketType::Spot {
-        let arr = serde_json::from_str::<Vec<Value>>(msg).unwrap();
-        Some(arr[1]["symbol"].as_str().unwrap().to_string())
+        let arr = serde_json::from_str::<Vec<Value>>(strip_socketio_prefix(msg)).ok()?;
+        arr.get(1)?["symbol"]
+            .as_str()
+            .map(|symbol| symbol.to_string())
     } else {
-        let json_obj = serde_json::from_str::<HashMap<String, Value>>(msg).unwrap();
-        Some(
-            json_obj
-                .get("symbol")
-                .unwrap()
-                .as_str()
-                .unwrap()
-                .to_string(),
-        )
+        let json_obj = serde_json::from_str::<HashMap<String, Value>>(msg).ok()?;
+        json_obj
+            .get("symbol")?
+            .as_str()
+            .map(|symbol| symbol.to_string())
     }
 }
 
 pub(crate) fn parse_trade(market_type: MarketType, msg: &str) -> Result<Vec<TradeMsg>> {
     if market_type == MarketType::Spot {
-        mxc_spot::parse_trade(msg)
+        mxc_spot::parse_trade(strip_socketio_prefix(msg))
     } else {
         mxc_swap::parse_trade(market_type, msg)
     }
@@ -41,8 +46,12 @@ pub(crate) fn parse_l2(
 ) -> Result<Vec<OrderBookMsg>> {
     if market_type == MarketType::Spot {
         mxc_spot::parse_l2(
-            msg,
-            timestamp.expect("MXC Spot orderbook messages don't have timestamp"),
+            strip_socketio_prefix(msg),
+            timestamp.ok_or_else(|| {
+                ParseError::MalformedField(
+                    "MXC Spot orderbook messages don't have timestamp".to_string(),
+                )
+            })?,
         )
     } else {
         mxc_swap::parse_l2(market_type, msg)
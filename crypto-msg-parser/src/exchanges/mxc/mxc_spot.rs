@@ -2,8 +2,10 @@ use crypto_market_type::MarketType;
 
 use crate::{MessageType, Order, OrderBookMsg, TradeMsg, TradeSide};
 
+use crate::error::{ParseError, Result};
+use crate::exchanges::utils::{normalize_pair, original_json, parse_field, raw_json};
 use serde::{Deserialize, Serialize};
-use serde_json::{Result, Value};
+use serde_json::Value;
 use std::collections::HashMap;
 
 const EXCHANGE_NAME: &str = "mxc";
@@ -41,30 +43,29 @@ struct WebsocketMsg<T: Sized> {
 }
 
 pub(super) fn parse_trade(msg: &str) -> Result<Vec<TradeMsg>> {
-    let arr = serde_json::from_str::<Vec<Value>>(msg)?;
-    assert_eq!(arr.len(), 2);
-    let ws_msg: WebsocketMsg<PushSymbolData> = serde_json::from_value(arr[1].clone())?;
-    if ws_msg.data.deals.is_none() {
+    let ws_msg = parse_push_symbol_data(msg)?;
+    let raw_trades = if let Some(deals) = ws_msg.data.deals {
+        deals
+    } else {
         return Ok(Vec::new());
-    }
-
-    let raw_trades = ws_msg.data.deals.unwrap();
+    };
     let symbol = ws_msg.symbol.as_str();
-    let pair = crypto_pair::normalize_pair(symbol, EXCHANGE_NAME).unwrap();
+    let pair = normalize_pair(symbol, EXCHANGE_NAME)?;
 
     let mut trades: Vec<TradeMsg> = raw_trades
         .into_iter()
         .map(|raw_trade| {
-            let price = raw_trade.p.parse::<f64>().unwrap();
-            let quantity = raw_trade.q.parse::<f64>().unwrap();
+            let price = parse_field(EXCHANGE_NAME, "p", &raw_trade.p, msg)?;
+            let quantity = parse_field(EXCHANGE_NAME, "q", &raw_trade.q, msg)?;
 
-            TradeMsg {
+            Ok(TradeMsg {
                 exchange: EXCHANGE_NAME.to_string(),
                 market_type: MarketType::Spot,
                 symbol: symbol.to_string(),
                 pair: pair.clone(),
                 msg_type: MessageType::Trade,
                 timestamp: raw_trade.t,
+                timestamp_us: None,
                 price,
                 quantity_base: quantity,
                 quantity_quote: price * quantity,
@@ -75,40 +76,52 @@ pub(super) fn parse_trade(msg: &str) -> Result<Vec<TradeMsg>> {
                     TradeSide::Buy
                 },
                 trade_id: raw_trade.t.to_string(),
-                json: serde_json::to_string(&raw_trade).unwrap(),
-            }
+                json: raw_json(&raw_trade),
+            })
         })
-        .collect();
+        .collect::<Result<Vec<TradeMsg>>>()?;
 
     if trades.len() == 1 {
-        trades[0].json = msg.to_string();
+        trades[0].json = original_json(msg);
     }
     Ok(trades)
 }
 
-fn parse_order(raw_order: &RawOrder) -> Order {
-    let price = raw_order.p.parse::<f64>().unwrap();
-    let quantity_base = raw_order.q.parse::<f64>().unwrap();
-    let quantity_quote = raw_order.a.parse::<f64>().unwrap();
+fn parse_order(raw_order: &RawOrder, msg: &str) -> Result<Order> {
+    let price = parse_field(EXCHANGE_NAME, "p", &raw_order.p, msg)?;
+    let quantity_base = parse_field(EXCHANGE_NAME, "q", &raw_order.q, msg)?;
+    let quantity_quote = parse_field(EXCHANGE_NAME, "a", &raw_order.a, msg)?;
 
-    Order {
+    Ok(Order {
         price,
         quantity_base,
         quantity_quote,
         quantity_contract: None,
+        order_id: None,
+        order_type: None,
+    })
+}
+
+// Messages are ["push.symbol", {...}]
+fn parse_push_symbol_data(msg: &str) -> Result<WebsocketMsg<PushSymbolData>> {
+    let arr = serde_json::from_str::<Vec<Value>>(msg)?;
+    if arr.len() != 2 {
+        return Err(ParseError::MalformedField(format!(
+            "Unknown message format {}",
+            msg
+        )));
     }
+    Ok(serde_json::from_value(arr[1].clone())?)
 }
 
 pub(crate) fn parse_l2(msg: &str, timestamp: i64) -> Result<Vec<OrderBookMsg>> {
-    let arr = serde_json::from_str::<Vec<Value>>(msg)?;
-    assert_eq!(arr.len(), 2);
-    let ws_msg: WebsocketMsg<PushSymbolData> = serde_json::from_value(arr[1].clone())?;
+    let ws_msg = parse_push_symbol_data(msg)?;
     if ws_msg.data.asks.is_none() && ws_msg.data.bids.is_none() {
         return Ok(Vec::new());
     }
 
     let symbol = ws_msg.symbol.as_str();
-    let pair = crypto_pair::normalize_pair(symbol, EXCHANGE_NAME).unwrap();
+    let pair = normalize_pair(symbol, EXCHANGE_NAME)?;
 
     let orderbook = OrderBookMsg {
         exchange: EXCHANGE_NAME.to_string(),
@@ -117,20 +130,25 @@ pub(crate) fn parse_l2(msg: &str, timestamp: i64) -> Result<Vec<OrderBookMsg>> {
         pair,
         msg_type: MessageType::L2Event,
         timestamp,
+        timestamp_us: None,
         seq_id: None,
         prev_seq_id: None,
         asks: if let Some(asks) = ws_msg.data.asks {
-            asks.iter().map(parse_order).collect::<Vec<Order>>()
+            asks.iter()
+                .map(|x| parse_order(x, msg))
+                .collect::<Result<Vec<Order>>>()?
         } else {
             Vec::new()
         },
         bids: if let Some(bids) = ws_msg.data.bids {
-            bids.iter().map(parse_order).collect::<Vec<Order>>()
+            bids.iter()
+                .map(|x| parse_order(x, msg))
+                .collect::<Result<Vec<Order>>>()?
         } else {
             Vec::new()
         },
         snapshot: false,
-        json: msg.to_string(),
+        json: original_json(msg),
     };
 
     Ok(vec![orderbook])
@@ -1,10 +1,11 @@
 use crypto_market_type::MarketType;
 
-use super::super::utils::calc_quantity_and_volume;
+use super::super::utils::{calc_quantity_and_volume, normalize_pair, original_json};
 use crate::{MessageType, Order, OrderBookMsg, TradeMsg, TradeSide};
 
+use crate::error::Result;
 use serde::{Deserialize, Serialize};
-use serde_json::{Result, Value};
+use serde_json::Value;
 use std::collections::HashMap;
 
 const EXCHANGE_NAME: &str = "mxc";
@@ -42,11 +43,11 @@ struct WebsocketMsg<T: Sized> {
 pub(super) fn parse_trade(market_type: MarketType, msg: &str) -> Result<Vec<TradeMsg>> {
     let ws_msg = serde_json::from_str::<WebsocketMsg<RawTradeMsg>>(msg)?;
     let symbol = ws_msg.symbol.as_str();
-    let pair = crypto_pair::normalize_pair(symbol, EXCHANGE_NAME).unwrap();
+    let pair = normalize_pair(symbol, EXCHANGE_NAME)?;
     let raw_trade = ws_msg.data;
 
     let (quantity_base, quantity_quote, _) =
-        calc_quantity_and_volume(EXCHANGE_NAME, market_type, &pair, raw_trade.p, raw_trade.v);
+        calc_quantity_and_volume(EXCHANGE_NAME, market_type, &pair, raw_trade.p, raw_trade.v)?;
 
     let trade = TradeMsg {
         exchange: EXCHANGE_NAME.to_string(),
@@ -55,6 +56,7 @@ pub(super) fn parse_trade(market_type: MarketType, msg: &str) -> Result<Vec<Trad
         pair,
         msg_type: MessageType::Trade,
         timestamp: raw_trade.t,
+        timestamp_us: None,
         price: raw_trade.p,
         quantity_base,
         quantity_quote,
@@ -65,7 +67,7 @@ pub(super) fn parse_trade(market_type: MarketType, msg: &str) -> Result<Vec<Trad
             TradeSide::Buy
         },
         trade_id: raw_trade.t.to_string(),
-        json: msg.to_string(),
+        json: original_json(msg),
     };
 
     Ok(vec![trade])
@@ -74,19 +76,21 @@ pub(super) fn parse_trade(market_type: MarketType, msg: &str) -> Result<Vec<Trad
 pub(crate) fn parse_l2(market_type: MarketType, msg: &str) -> Result<Vec<OrderBookMsg>> {
     let ws_msg = serde_json::from_str::<WebsocketMsg<RawOrderbookMsg>>(msg)?;
     let symbol = ws_msg.symbol.as_str();
-    let pair = crypto_pair::normalize_pair(symbol, EXCHANGE_NAME).unwrap();
+    let pair = normalize_pair(symbol, EXCHANGE_NAME)?;
 
-    let parse_order = |raw_order: &[f64; 3]| -> Order {
+    let parse_order = |raw_order: &[f64; 3]| -> Result<Order> {
         let price = raw_order[0];
         let quantity = raw_order[1];
         let (quantity_base, quantity_quote, quantity_contract) =
-            calc_quantity_and_volume(EXCHANGE_NAME, market_type, &pair, price, quantity);
-        Order {
+            calc_quantity_and_volume(EXCHANGE_NAME, market_type, &pair, price, quantity)?;
+        Ok(Order {
             price,
             quantity_base,
             quantity_quote,
             quantity_contract,
-        }
+            order_id: None,
+            order_type: None,
+        })
     };
 
     let orderbook = OrderBookMsg {
@@ -96,22 +100,23 @@ pub(crate) fn parse_l2(market_type: MarketType, msg: &str) -> Result<Vec<OrderBo
         pair: pair.to_string(),
         msg_type: MessageType::L2Event,
         timestamp: ws_msg.ts,
+        timestamp_us: None,
         seq_id: None,
         prev_seq_id: None,
         asks: ws_msg
             .data
             .asks
             .iter()
-            .map(|x| parse_order(x))
-            .collect::<Vec<Order>>(),
+            .map(parse_order)
+            .collect::<Result<Vec<Order>>>()?,
         bids: ws_msg
             .data
             .bids
             .iter()
-            .map(|x| parse_order(x))
-            .collect::<Vec<Order>>(),
+            .map(parse_order)
+            .collect::<Result<Vec<Order>>>()?,
         snapshot: false,
-        json: msg.to_string(),
+        json: original_json(msg),
     };
 
     Ok(vec![orderbook])
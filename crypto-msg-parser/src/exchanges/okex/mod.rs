@@ -0,0 +1,143 @@
+mod okex_v3;
+mod okex_v5;
+
+use crypto_market_type::MarketType;
+
+use crate::{
+    BboMsg, FundingRateMsg, IndexPriceMsg, OpenInterestMsg, OrderBookMsg, ParseOptions, TradeMsg,
+};
+
+use super::registry::ExchangeParser;
+use super::utils::normalize_pair;
+use crate::error::{ParseError, Result};
+use serde_json::Value;
+
+const EXCHANGE_NAME: &str = "okex";
+
+pub(crate) const PARSER: ExchangeParser = ExchangeParser {
+    name: "okex",
+    extract_symbol,
+    parse_trade,
+    parse_l2: |market_type, msg, _timestamp| parse_l2(market_type, msg),
+    parse_trade_with_options: Some(parse_trade_with_options),
+    parse_l2_with_options: Some(|market_type, msg, _timestamp, options| {
+        parse_l2_with_options(market_type, msg, options)
+    }),
+    parse_l2_snapshot: Some(parse_l2_snapshot),
+    parse_bbo: Some(parse_bbo),
+    parse_ticker: None,
+    parse_funding_rate: Some(parse_funding_rate),
+    parse_mark_price: None,
+    parse_index_price: Some(parse_index_price),
+    parse_liquidation: None,
+    parse_open_interest: Some(parse_open_interest),
+    parse_candlestick_history: None,
+};
+
+// okex sends empty strings for deleted levels in some channels, treat them as 0
+fn parse_f64(s: &str) -> Result<f64> {
+    if s.is_empty() {
+        Ok(0.0)
+    } else {
+        s.parse::<f64>()
+            .map_err(|_| ParseError::MalformedField(format!("invalid number {:?}", s)))
+    }
+}
+
+// v5 messages carry an `arg` field, while v3 messages carry a `table` field.
+//
+// The message is parsed only once, v3 and v5 parsers deserialize their typed
+// structs from the returned value instead of parsing the text again.
+fn parse_json(msg: &str) -> Result<(Value, bool)> {
+    let json = serde_json::from_str::<Value>(msg)?;
+    let v5 = json.get("arg").is_some();
+    Ok((json, v5))
+}
+
+// Spot quantities don't depend on the pair, so spot messages only normalize
+// it if ParseOptions::normalize_pair is set, contracts need it for contract values.
+fn pair_with(symbol: &str, market_type: MarketType, options: &ParseOptions) -> Result<String> {
+    if options.normalize_pair || market_type != MarketType::Spot {
+        normalize_pair(symbol, EXCHANGE_NAME)
+    } else {
+        Ok(String::new())
+    }
+}
+
+pub(crate) fn extract_symbol(market_type: MarketType, msg: &str) -> Option<String> {
+    let (json, v5) = parse_json(msg).ok()?;
+    if v5 {
+        okex_v5::extract_symbol(json)
+    } else {
+        okex_v3::extract_symbol(market_type, json)
+    }
+}
+
+pub(crate) fn parse_trade(market_type: MarketType, msg: &str) -> Result<Vec<TradeMsg>> {
+    parse_trade_with_options(market_type, msg, &ParseOptions::default())
+}
+
+fn parse_trade_with_options(
+    market_type: MarketType,
+    msg: &str,
+    options: &ParseOptions,
+) -> Result<Vec<TradeMsg>> {
+    let (json, v5) = parse_json(msg)?;
+    if v5 {
+        okex_v5::parse_trade(market_type, msg, json, options)
+    } else {
+        okex_v3::parse_trade(market_type, msg, json, options)
+    }
+}
+
+pub(crate) fn parse_funding_rate(
+    market_type: MarketType,
+    msg: &str,
+) -> Result<Vec<FundingRateMsg>> {
+    okex_v3::parse_funding_rate(market_type, msg)
+}
+
+pub(crate) fn parse_index_price(market_type: MarketType, msg: &str) -> Result<Vec<IndexPriceMsg>> {
+    okex_v3::parse_index_price(market_type, msg)
+}
+
+// okex tickers always have timestamp
+pub(crate) fn parse_bbo(
+    market_type: MarketType,
+    msg: &str,
+    _timestamp: Option<i64>,
+) -> Result<Vec<BboMsg>> {
+    okex_v3::parse_bbo(market_type, msg)
+}
+
+pub(crate) fn parse_l2(market_type: MarketType, msg: &str) -> Result<Vec<OrderBookMsg>> {
+    parse_l2_with_options(market_type, msg, &ParseOptions::default())
+}
+
+fn parse_l2_with_options(
+    market_type: MarketType,
+    msg: &str,
+    options: &ParseOptions,
+) -> Result<Vec<OrderBookMsg>> {
+    let (json, v5) = parse_json(msg)?;
+    if v5 {
+        okex_v5::parse_l2(market_type, msg, json, options)
+    } else {
+        okex_v3::parse_l2(market_type, msg, json, options)
+    }
+}
+
+pub(crate) fn parse_l2_snapshot(
+    market_type: MarketType,
+    symbol: &str,
+    msg: &str,
+) -> Result<Vec<OrderBookMsg>> {
+    okex_v3::parse_l2_snapshot(market_type, symbol, msg)
+}
+
+pub(crate) fn parse_open_interest(
+    market_type: MarketType,
+    msg: &str,
+) -> Result<Vec<OpenInterestMsg>> {
+    okex_v3::parse_open_interest(market_type, msg)
+}
@@ -0,0 +1,481 @@
+use crypto_market_type::MarketType;
+
+use super::super::utils::{
+    calc_open_interest_value, calc_quantity_and_volume, normalize_pair, original_json,
+    original_json_with, parse_field, parse_rfc3339, raw_json, raw_json_with,
+};
+use super::{pair_with, parse_f64};
+use crate::{
+    BboMsg, FundingRateMsg, IndexPriceMsg, MessageType, OpenInterestMsg, OrderBookMsg, TradeMsg,
+    TradeSide,
+};
+use crate::{Order, ParseOptions};
+
+use crate::error::{ParseError, Result};
+use chrono::prelude::*;
+use chrono::DateTime;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+
+const EXCHANGE_NAME: &str = "okex";
+
+// https://www.okex.com/docs/en/#spot_ws-trade
+// https://www.okex.com/docs/en/#futures_ws-trade
+// https://www.okex.com/docs/en/#ws_swap-trade
+// https://www.okex.com/docs/en/#option_ws-trade
+#[derive(Serialize, Deserialize)]
+struct RawTradeMsg {
+    instrument_id: String,
+    trade_id: String,
+    price: String,
+    size: Option<String>,
+    qty: Option<String>,
+    trade_side: Option<String>, // buy, sell, for option/trades only
+    side: Option<String>,       // buy, sell, for other
+    timestamp: String,
+    #[serde(flatten)]
+    extra: HashMap<String, Value>,
+}
+
+// https://www.okex.com/docs/en/#spot_ws-full_depth
+// https://www.okex.com/docs/en/#futures_ws-full_depth
+// https://www.okex.com/docs/en/#ws_swap-full_depth
+// https://www.okex.com/docs/en/#option_ws-full_depth
+#[derive(Serialize, Deserialize)]
+struct RawOrderbookMsg {
+    instrument_id: String,
+    timestamp: String,
+    asks: Vec<[String; 4]>,
+    bids: Vec<[String; 4]>,
+    #[serde(flatten)]
+    extra: HashMap<String, Value>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct RawFundingRateMsg {
+    estimated_rate: String,
+    funding_rate: String,
+    funding_time: String,
+    instrument_id: String,
+    settlement_time: String,
+    #[serde(flatten)]
+    extra: HashMap<String, Value>,
+}
+
+// https://www.okex.com/docs/en/#index_ws-ticker
+#[derive(Serialize, Deserialize)]
+struct RawIndexTickerMsg {
+    instrument_id: String,
+    last: String,
+    timestamp: String,
+    #[serde(flatten)]
+    extra: HashMap<String, Value>,
+}
+
+// https://www.okex.com/docs/en/#spot_ws-ticker
+// https://www.okex.com/docs/en/#futures_ws-ticker
+// https://www.okex.com/docs/en/#ws_swap-ticker
+#[derive(Serialize, Deserialize)]
+struct RawTickerMsg {
+    instrument_id: String,
+    best_bid: String,
+    best_bid_size: String,
+    best_ask: String,
+    best_ask_size: String,
+    timestamp: String,
+    #[serde(flatten)]
+    extra: HashMap<String, Value>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct WebsocketMsg<T: Sized> {
+    table: String,
+    data: Vec<T>,
+    action: Option<String>, // partial, update
+    #[serde(flatten)]
+    extra: HashMap<String, Value>,
+}
+
+pub(super) fn extract_symbol(_market_type: MarketType, json: Value) -> Option<String> {
+    // event messages such as login and subscribe responses carry no symbol
+    let ws_msg = serde_json::from_value::<WebsocketMsg<Value>>(json).ok()?;
+    let symbol = ws_msg.data.first()?["instrument_id"].as_str()?;
+    Some(symbol.to_string())
+}
+
+pub(super) fn parse_trade(
+    market_type: MarketType,
+    msg: &str,
+    json: Value,
+    options: &ParseOptions,
+) -> Result<Vec<TradeMsg>> {
+    let ws_msg = serde_json::from_value::<WebsocketMsg<RawTradeMsg>>(json)?;
+    if ws_msg.data.is_empty() {
+        return Ok(Vec::new());
+    }
+    let option_trades = ws_msg.table.as_str() == "option/trades";
+    let mut trades = ws_msg
+        .data
+        .into_iter()
+        .map(|raw_trade| {
+            let timestamp = parse_rfc3339(EXCHANGE_NAME, "timestamp", &raw_trade.timestamp, msg)?;
+            let price = parse_f64(&raw_trade.price)?;
+            let size = if let Some(qty) = raw_trade.qty.as_ref() {
+                parse_f64(qty)?
+            } else if let Some(size) = raw_trade.size.as_ref() {
+                parse_f64(size)?
+            } else {
+                return Err(ParseError::MalformedField(
+                    "qty and size are both missing".to_string(),
+                ));
+            };
+            let side = if option_trades {
+                raw_trade.trade_side.clone()
+            } else {
+                raw_trade.side.clone()
+            }
+            .ok_or_else(|| ParseError::MalformedField(format!("No side in {}", msg)))?;
+            let pair = pair_with(&raw_trade.instrument_id, market_type, options)?;
+            let (quantity_base, quantity_quote, _) =
+                calc_quantity_and_volume(EXCHANGE_NAME, market_type, &pair, price, size)?;
+
+            Ok(TradeMsg {
+                exchange: EXCHANGE_NAME.to_string(),
+                market_type,
+                symbol: raw_trade.instrument_id.clone(),
+                pair,
+                msg_type: MessageType::Trade,
+                timestamp: timestamp.timestamp_millis(),
+                timestamp_us: None,
+                price,
+                quantity_base,
+                quantity_quote,
+                quantity_contract: if market_type == MarketType::Spot {
+                    None
+                } else {
+                    Some(size)
+                },
+                side: parse_field::<TradeSide>(EXCHANGE_NAME, "side", &side, msg)?,
+                trade_id: raw_trade.trade_id.to_string(),
+                json: raw_json_with(&raw_trade, options),
+            })
+        })
+        .collect::<Result<Vec<TradeMsg>>>()?;
+
+    if trades.len() == 1 {
+        trades[0].json = original_json_with(msg, options);
+    }
+    Ok(trades)
+}
+
+pub(super) fn parse_funding_rate(
+    market_type: MarketType,
+    msg: &str,
+) -> Result<Vec<FundingRateMsg>> {
+    let ws_msg = serde_json::from_str::<WebsocketMsg<RawFundingRateMsg>>(msg)?;
+    if ws_msg.data.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let mut rates = ws_msg
+        .data
+        .into_iter()
+        .map(|raw_msg| {
+            let funding_time =
+                parse_rfc3339(EXCHANGE_NAME, "funding_time", &raw_msg.funding_time, msg)?;
+            Ok(FundingRateMsg {
+                exchange: EXCHANGE_NAME.to_string(),
+                market_type,
+                symbol: raw_msg.instrument_id.clone(),
+                pair: normalize_pair(&raw_msg.instrument_id, EXCHANGE_NAME)?,
+                msg_type: MessageType::FundingRate,
+                timestamp: Utc::now().timestamp_millis(),
+                funding_rate: parse_f64(&raw_msg.funding_rate)?,
+                funding_time: funding_time.timestamp_millis(),
+                estimated_rate: Some(parse_f64(&raw_msg.estimated_rate)?),
+                mark_price: None,
+                json: raw_json(&raw_msg),
+            })
+        })
+        .collect::<Result<Vec<FundingRateMsg>>>()?;
+
+    if rates.len() == 1 {
+        rates[0].json = original_json(msg);
+    }
+    Ok(rates)
+}
+
+pub(super) fn parse_index_price(market_type: MarketType, msg: &str) -> Result<Vec<IndexPriceMsg>> {
+    let ws_msg = serde_json::from_str::<WebsocketMsg<RawIndexTickerMsg>>(msg)?;
+    if ws_msg.table != "index/ticker" {
+        return Err(ParseError::MalformedField(format!(
+            "Unknown index price message {}",
+            msg
+        )));
+    }
+
+    let mut index_prices = ws_msg
+        .data
+        .into_iter()
+        .map(|raw_msg| {
+            let timestamp = parse_rfc3339(EXCHANGE_NAME, "timestamp", &raw_msg.timestamp, msg)?;
+            Ok(IndexPriceMsg {
+                exchange: EXCHANGE_NAME.to_string(),
+                market_type,
+                symbol: raw_msg.instrument_id.clone(),
+                pair: normalize_pair(&raw_msg.instrument_id, EXCHANGE_NAME)?,
+                msg_type: MessageType::IndexPrice,
+                timestamp: timestamp.timestamp_millis(),
+                index_price: parse_f64(&raw_msg.last)?,
+                json: raw_json(&raw_msg),
+            })
+        })
+        .collect::<Result<Vec<IndexPriceMsg>>>()?;
+
+    if index_prices.len() == 1 {
+        index_prices[0].json = original_json(msg);
+    }
+    Ok(index_prices)
+}
+
+pub(super) fn parse_bbo(market_type: MarketType, msg: &str) -> Result<Vec<BboMsg>> {
+    let ws_msg = serde_json::from_str::<WebsocketMsg<RawTickerMsg>>(msg)?;
+    if ws_msg.data.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let mut bbos = ws_msg
+        .data
+        .into_iter()
+        .map(|raw_ticker| {
+            let timestamp = parse_rfc3339(EXCHANGE_NAME, "timestamp", &raw_ticker.timestamp, msg)?;
+            let pair = normalize_pair(&raw_ticker.instrument_id, EXCHANGE_NAME)?;
+            let bid_price = parse_f64(&raw_ticker.best_bid)?;
+            let ask_price = parse_f64(&raw_ticker.best_ask)?;
+            let (bid_quantity, _, _) = calc_quantity_and_volume(
+                EXCHANGE_NAME,
+                market_type,
+                &pair,
+                bid_price,
+                parse_f64(&raw_ticker.best_bid_size)?,
+            )?;
+            let (ask_quantity, _, _) = calc_quantity_and_volume(
+                EXCHANGE_NAME,
+                market_type,
+                &pair,
+                ask_price,
+                parse_f64(&raw_ticker.best_ask_size)?,
+            )?;
+
+            Ok(BboMsg {
+                exchange: EXCHANGE_NAME.to_string(),
+                market_type,
+                symbol: raw_ticker.instrument_id.clone(),
+                pair,
+                msg_type: MessageType::BBO,
+                timestamp: timestamp.timestamp_millis(),
+                bid_price,
+                bid_quantity,
+                ask_price,
+                ask_quantity,
+                synthetic: false,
+                json: raw_json(&raw_ticker),
+            })
+        })
+        .collect::<Result<Vec<BboMsg>>>()?;
+
+    if bbos.len() == 1 {
+        bbos[0].json = original_json(msg);
+    }
+    Ok(bbos)
+}
+
+pub(super) fn parse_l2(
+    market_type: MarketType,
+    msg: &str,
+    json: Value,
+    options: &ParseOptions,
+) -> Result<Vec<OrderBookMsg>> {
+    let ws_msg = serde_json::from_value::<WebsocketMsg<RawOrderbookMsg>>(json)?;
+    if ws_msg.data.is_empty() {
+        return Ok(Vec::new());
+    }
+    // depth_l2_tbt and depth send partial then update, depth5 has no action
+    // field and is always a snapshot
+    let snapshot = ws_msg.action.as_deref() != Some("update");
+    debug_assert_eq!(ws_msg.data.len(), 1);
+
+    let mut orderbooks = ws_msg
+        .data
+        .iter()
+        .map(|raw_orderbook| {
+            let symbol = raw_orderbook.instrument_id.clone();
+            let pair = pair_with(&symbol, market_type, options)?;
+            let timestamp =
+                parse_rfc3339(EXCHANGE_NAME, "timestamp", &raw_orderbook.timestamp, msg)?;
+
+            let parse_order = |raw_order: &[String; 4]| -> Result<Order> {
+                let price = parse_f64(&raw_order[0])?;
+                let quantity = parse_f64(&raw_order[1])?;
+                let (quantity_base, quantity_quote, quantity_contract) =
+                    calc_quantity_and_volume(EXCHANGE_NAME, market_type, &pair, price, quantity)?;
+
+                Ok(Order {
+                    price,
+                    quantity_base,
+                    quantity_quote,
+                    quantity_contract,
+                    order_id: None,
+                    order_type: None,
+                })
+            };
+
+            Ok(OrderBookMsg {
+                exchange: EXCHANGE_NAME.to_string(),
+                market_type,
+                symbol,
+                pair: pair.clone(),
+                msg_type: MessageType::L2Event,
+                timestamp: timestamp.timestamp_millis(),
+                timestamp_us: None,
+                seq_id: None,
+                prev_seq_id: None,
+                asks: raw_orderbook
+                    .asks
+                    .iter()
+                    .map(&parse_order)
+                    .collect::<Result<Vec<Order>>>()?,
+                bids: raw_orderbook
+                    .bids
+                    .iter()
+                    .map(&parse_order)
+                    .collect::<Result<Vec<Order>>>()?,
+                snapshot,
+                json: raw_json_with(raw_orderbook, options),
+            })
+        })
+        .collect::<Result<Vec<OrderBookMsg>>>()?;
+
+    if orderbooks.len() == 1 {
+        orderbooks[0].json = original_json_with(msg, options);
+    }
+    Ok(orderbooks)
+}
+
+// e.g., https://www.okex.com/api/spot/v3/instruments/BTC-USDT/book?size=200
+// and https://www.okex.com/api/swap/v3/instruments/BTC-USDT-SWAP/depth?size=200
+// Each level is [price, size, num_orders] in spot, with extra fields in contracts
+#[derive(Serialize, Deserialize)]
+struct RawL2SnapshotMsg {
+    asks: Vec<Vec<String>>,
+    bids: Vec<Vec<String>>,
+    timestamp: Option<String>, // spot and futures
+    time: Option<String>,      // swap
+    #[serde(flatten)]
+    extra: HashMap<String, Value>,
+}
+
+// okex v3 has no sequence numbers, so seq_id is None
+pub(super) fn parse_l2_snapshot(
+    market_type: MarketType,
+    symbol: &str,
+    msg: &str,
+) -> Result<Vec<OrderBookMsg>> {
+    let raw_snapshot = serde_json::from_str::<RawL2SnapshotMsg>(msg)?;
+    let pair = normalize_pair(symbol, EXCHANGE_NAME)?;
+    let timestamp = match raw_snapshot
+        .timestamp
+        .as_ref()
+        .or(raw_snapshot.time.as_ref())
+    {
+        Some(time) => DateTime::parse_from_rfc3339(time)
+            .map_err(|_| ParseError::MalformedField(format!("invalid timestamp {}", time)))?
+            .timestamp_millis(),
+        None => Utc::now().timestamp_millis(),
+    };
+
+    let parse_order = |raw_order: &Vec<String>| -> Result<Order> {
+        if raw_order.len() < 2 {
+            return Err(ParseError::MalformedField(format!(
+                "invalid level {:?}",
+                raw_order
+            )));
+        }
+        let price = parse_f64(&raw_order[0])?;
+        let quantity = parse_f64(&raw_order[1])?;
+        let (quantity_base, quantity_quote, quantity_contract) =
+            calc_quantity_and_volume(EXCHANGE_NAME, market_type, &pair, price, quantity)?;
+        Ok(Order {
+            price,
+            quantity_base,
+            quantity_quote,
+            quantity_contract,
+            order_id: None,
+            order_type: None,
+        })
+    };
+
+    let orderbook = OrderBookMsg {
+        exchange: EXCHANGE_NAME.to_string(),
+        market_type,
+        symbol: symbol.to_string(),
+        pair: pair.clone(),
+        msg_type: MessageType::L2Snapshot,
+        timestamp,
+        timestamp_us: None,
+        seq_id: None,
+        prev_seq_id: None,
+        asks: raw_snapshot
+            .asks
+            .iter()
+            .map(&parse_order)
+            .collect::<Result<Vec<Order>>>()?,
+        bids: raw_snapshot
+            .bids
+            .iter()
+            .map(&parse_order)
+            .collect::<Result<Vec<Order>>>()?,
+        snapshot: true,
+        json: original_json(msg),
+    };
+    Ok(vec![orderbook])
+}
+
+// https://www.okex.com/docs/en/#futures-open_interest
+// https://www.okex.com/docs/en/#swap-swap---open_interest
+#[derive(Serialize, Deserialize)]
+struct RawOpenInterestMsg {
+    instrument_id: String,
+    amount: String,
+    timestamp: String,
+    #[serde(flatten)]
+    extra: HashMap<String, Value>,
+}
+
+pub(super) fn parse_open_interest(
+    market_type: MarketType,
+    msg: &str,
+) -> Result<Vec<OpenInterestMsg>> {
+    let raw_msg = serde_json::from_str::<RawOpenInterestMsg>(msg)?;
+    let timestamp = parse_rfc3339(EXCHANGE_NAME, "timestamp", &raw_msg.timestamp, msg)?;
+    let pair = normalize_pair(&raw_msg.instrument_id, EXCHANGE_NAME)?;
+    let open_interest = parse_f64(&raw_msg.amount)?;
+    let open_interest_msg = OpenInterestMsg {
+        exchange: EXCHANGE_NAME.to_string(),
+        market_type,
+        symbol: raw_msg.instrument_id,
+        open_interest_value: calc_open_interest_value(
+            EXCHANGE_NAME,
+            market_type,
+            &pair,
+            open_interest,
+        ),
+        pair,
+        msg_type: MessageType::OpenInterest,
+        timestamp: timestamp.timestamp_millis(),
+        open_interest,
+        json: original_json(msg),
+    };
+    Ok(vec![open_interest_msg])
+}
@@ -0,0 +1,191 @@
+use crypto_market_type::MarketType;
+
+use super::super::utils::{
+    calc_quantity_and_volume, original_json_with, parse_field, raw_json_with,
+};
+use super::{pair_with, parse_f64};
+use crate::{MessageType, OrderBookMsg, TradeMsg, TradeSide};
+use crate::{Order, ParseOptions};
+
+use crate::error::Result;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+
+const EXCHANGE_NAME: &str = "okex";
+
+// https://www.okex.com/docs-v5/en/#websocket-api-public-channels-trades-channel
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawTradeMsg {
+    inst_id: String,
+    trade_id: String,
+    px: String,
+    sz: String,
+    side: String, // buy, sell
+    ts: String,
+    #[serde(flatten)]
+    extra: HashMap<String, Value>,
+}
+
+// https://www.okex.com/docs-v5/en/#websocket-api-public-channels-order-book-channel
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawOrderbookMsg {
+    asks: Vec<[String; 4]>,
+    bids: Vec<[String; 4]>,
+    ts: String,
+    seq_id: Option<i64>,
+    prev_seq_id: Option<i64>, // -1 in snapshots
+    #[serde(flatten)]
+    extra: HashMap<String, Value>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Arg {
+    channel: String,
+    inst_id: String,
+    #[serde(flatten)]
+    extra: HashMap<String, Value>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct WebsocketMsg<T: Sized> {
+    arg: Arg,
+    data: Vec<T>,
+    action: Option<String>, // snapshot, update
+    #[serde(flatten)]
+    extra: HashMap<String, Value>,
+}
+
+pub(super) fn extract_symbol(json: Value) -> Option<String> {
+    // event messages such as login and subscribe responses carry no symbol
+    let ws_msg = serde_json::from_value::<WebsocketMsg<Value>>(json).ok()?;
+    Some(ws_msg.arg.inst_id)
+}
+
+pub(super) fn parse_trade(
+    market_type: MarketType,
+    msg: &str,
+    json: Value,
+    options: &ParseOptions,
+) -> Result<Vec<TradeMsg>> {
+    let ws_msg = serde_json::from_value::<WebsocketMsg<RawTradeMsg>>(json)?;
+    if ws_msg.data.is_empty() {
+        return Ok(Vec::new());
+    }
+    let mut trades = ws_msg
+        .data
+        .into_iter()
+        .map(|raw_trade| {
+            let timestamp = parse_field(EXCHANGE_NAME, "ts", &raw_trade.ts, msg)?;
+            let price = parse_f64(&raw_trade.px)?;
+            let size = parse_f64(&raw_trade.sz)?;
+            let pair = pair_with(&raw_trade.inst_id, market_type, options)?;
+            let (quantity_base, quantity_quote, _) =
+                calc_quantity_and_volume(EXCHANGE_NAME, market_type, &pair, price, size)?;
+
+            Ok(TradeMsg {
+                exchange: EXCHANGE_NAME.to_string(),
+                market_type,
+                symbol: raw_trade.inst_id.clone(),
+                pair,
+                msg_type: MessageType::Trade,
+                timestamp,
+                timestamp_us: None,
+                price,
+                quantity_base,
+                quantity_quote,
+                quantity_contract: if market_type == MarketType::Spot {
+                    None
+                } else {
+                    Some(size)
+                },
+                side: parse_field::<TradeSide>(EXCHANGE_NAME, "side", &raw_trade.side, msg)?,
+                trade_id: raw_trade.trade_id.clone(),
+                json: raw_json_with(&raw_trade, options),
+            })
+        })
+        .collect::<Result<Vec<TradeMsg>>>()?;
+
+    if trades.len() == 1 {
+        trades[0].json = original_json_with(msg, options);
+    }
+    Ok(trades)
+}
+
+pub(super) fn parse_l2(
+    market_type: MarketType,
+    msg: &str,
+    json: Value,
+    options: &ParseOptions,
+) -> Result<Vec<OrderBookMsg>> {
+    let ws_msg = serde_json::from_value::<WebsocketMsg<RawOrderbookMsg>>(json)?;
+    if ws_msg.data.is_empty() {
+        return Ok(Vec::new());
+    }
+    // books, books50-l2-tbt and books-l2-tbt send snapshot then update,
+    // books5 and bbo-tbt have no action field, they are always snapshots
+    let snapshot = ws_msg.action.as_deref() != Some("update");
+    debug_assert_eq!(ws_msg.data.len(), 1);
+
+    let symbol = ws_msg.arg.inst_id.as_str();
+    let pair = pair_with(symbol, market_type, options)?;
+
+    let mut orderbooks = ws_msg
+        .data
+        .iter()
+        .map(|raw_orderbook| {
+            let timestamp = parse_field(EXCHANGE_NAME, "ts", &raw_orderbook.ts, msg)?;
+
+            let parse_order = |raw_order: &[String; 4]| -> Result<Order> {
+                let price = parse_f64(&raw_order[0])?;
+                let quantity = parse_f64(&raw_order[1])?;
+                let (quantity_base, quantity_quote, quantity_contract) =
+                    calc_quantity_and_volume(EXCHANGE_NAME, market_type, &pair, price, quantity)?;
+
+                Ok(Order {
+                    price,
+                    quantity_base,
+                    quantity_quote,
+                    quantity_contract,
+                    order_id: None,
+                    order_type: None,
+                })
+            };
+
+            Ok(OrderBookMsg {
+                exchange: EXCHANGE_NAME.to_string(),
+                market_type,
+                symbol: symbol.to_string(),
+                pair: pair.clone(),
+                msg_type: MessageType::L2Event,
+                timestamp,
+                timestamp_us: None,
+                seq_id: raw_orderbook.seq_id.map(|x| x as u64),
+                prev_seq_id: raw_orderbook
+                    .prev_seq_id
+                    .filter(|x| *x >= 0)
+                    .map(|x| x as u64),
+                asks: raw_orderbook
+                    .asks
+                    .iter()
+                    .map(&parse_order)
+                    .collect::<Result<Vec<Order>>>()?,
+                bids: raw_orderbook
+                    .bids
+                    .iter()
+                    .map(&parse_order)
+                    .collect::<Result<Vec<Order>>>()?,
+                snapshot,
+                json: raw_json_with(raw_orderbook, options),
+            })
+        })
+        .collect::<Result<Vec<OrderBookMsg>>>()?;
+
+    if orderbooks.len() == 1 {
+        orderbooks[0].json = original_json_with(msg, options);
+    }
+    Ok(orderbooks)
+}
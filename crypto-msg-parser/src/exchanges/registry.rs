@@ -0,0 +1,54 @@
+// Parser functions of exchanges, so that the dispatch functions in lib.rs
+// look them up instead of matching on exchange names one by one.
+//
+// An exchange declares its `ExchangeParser` in its own module and is added
+// to `PARSERS`, fields are None for message types it doesn't support.
+use crypto_market_type::MarketType;
+
+use crate::error::Result;
+use crate::{
+    BboMsg, FundingRateMsg, IndexPriceMsg, KlineMsg, LiquidationMsg, MarkPriceMsg, OpenInterestMsg,
+    OrderBookMsg, ParseOptions, TickerMsg, TradeMsg,
+};
+
+pub(crate) type ParseFn<T> = fn(MarketType, &str) -> Result<Vec<T>>;
+// (market_type, symbol, msg)
+type ParseSnapshotFn = fn(MarketType, &str, &str) -> Result<Vec<OrderBookMsg>>;
+// (market_type, symbol, interval in seconds, msg)
+type ParseCandlestickHistoryFn = fn(MarketType, &str, usize, &str) -> Result<Vec<KlineMsg>>;
+type ParseTradeWithOptionsFn = fn(MarketType, &str, &ParseOptions) -> Result<Vec<TradeMsg>>;
+// (market_type, msg, timestamp), the timestamp is for BBO messages without one
+type ParseBboFn = fn(MarketType, &str, Option<i64>) -> Result<Vec<BboMsg>>;
+// (market_type, msg, timestamp, options)
+type ParseL2WithOptionsFn =
+    fn(MarketType, &str, Option<i64>, &ParseOptions) -> Result<Vec<OrderBookMsg>>;
+
+pub(crate) struct ExchangeParser {
+    pub name: &'static str,
+    pub extract_symbol: fn(MarketType, &str) -> Option<String>,
+    pub parse_trade: ParseFn<TradeMsg>,
+    // the timestamp is for exchanges whose orderbook messages don't have one
+    pub parse_l2: fn(MarketType, &str, Option<i64>) -> Result<Vec<OrderBookMsg>>,
+    // parsers skipping the work of fields deselected by ParseOptions, if None
+    // the fields are filled in and cleared afterwards
+    pub parse_trade_with_options: Option<ParseTradeWithOptionsFn>,
+    pub parse_l2_with_options: Option<ParseL2WithOptionsFn>,
+    pub parse_l2_snapshot: Option<ParseSnapshotFn>,
+    pub parse_bbo: Option<ParseBboFn>,
+    pub parse_ticker: Option<ParseFn<TickerMsg>>,
+    pub parse_funding_rate: Option<ParseFn<FundingRateMsg>>,
+    pub parse_mark_price: Option<ParseFn<MarkPriceMsg>>,
+    pub parse_index_price: Option<ParseFn<IndexPriceMsg>>,
+    pub parse_liquidation: Option<ParseFn<LiquidationMsg>>,
+    pub parse_open_interest: Option<ParseFn<OpenInterestMsg>>,
+    pub parse_candlestick_history: Option<ParseCandlestickHistoryFn>,
+}
+
+const PARSERS: &[&ExchangeParser] = &[&super::binance::PARSER, &super::okex::PARSER];
+
+pub(crate) fn lookup(exchange: &str) -> Option<&'static ExchangeParser> {
+    PARSERS
+        .iter()
+        .find(|parser| parser.name == exchange)
+        .copied()
+}
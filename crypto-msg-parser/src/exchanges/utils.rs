@@ -1,29 +1,109 @@
-use std::time::Duration;
+use std::str::FromStr;
 
+use chrono::{DateTime, FixedOffset};
 use crypto_market_type::MarketType;
-use reqwest::{header, Result};
 
-pub(super) fn http_get(url: &str) -> Result<String> {
-    let mut headers = header::HeaderMap::new();
-    headers.insert(
-        header::CONTENT_TYPE,
-        header::HeaderValue::from_static("application/json"),
-    );
+use crate::error::ParseError;
+use crate::ParseOptions;
 
-    let client = reqwest::blocking::Client::builder()
-         .default_headers(headers)
-         .timeout(Duration::from_secs(10))
-         .user_agent("Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/87.0.4280.88 Safari/537.36")
-         .gzip(true)
-         .build()?;
-    let response = client.get(url).send()?;
+// The `json` field of a message parsed from `msg`
+pub(super) fn original_json(msg: &str) -> String {
+    msg.to_string()
+}
+
+// The `json` field of a message parsed from one element of a raw message
+pub(super) fn raw_json<T: serde::Serialize + ?Sized>(raw_msg: &T) -> String {
+    serde_json::to_string(raw_msg).unwrap_or_default()
+}
+
+// original_json(), empty if ParseOptions::keep_json is false
+pub(super) fn original_json_with(msg: &str, options: &ParseOptions) -> String {
+    if options.keep_json {
+        original_json(msg)
+    } else {
+        String::new()
+    }
+}
 
-    match response.error_for_status() {
-        Ok(resp) => Ok(resp.text()?),
-        Err(error) => Err(error),
+// raw_json(), empty if ParseOptions::keep_json is false
+pub(super) fn raw_json_with<T: serde::Serialize + ?Sized>(
+    raw_msg: &T,
+    options: &ParseOptions,
+) -> String {
+    if options.keep_json {
+        raw_json(raw_msg)
+    } else {
+        String::new()
     }
 }
 
+// Formats a candlestick interval in seconds as `KlineMsg::period`, e.g.,
+// 60 is 1m, 3600 is 1H and 30 days is 1M
+pub(super) fn interval_to_period(interval: usize) -> String {
+    let units = [
+        (30 * 86400, "M"),
+        (7 * 86400, "W"),
+        (86400, "D"),
+        (3600, "H"),
+        (60, "m"),
+    ];
+    match units
+        .iter()
+        .find(|(secs, _)| interval / secs * secs == interval)
+    {
+        Some((secs, unit)) => format!("{}{}", interval / secs, unit),
+        None => format!("{}s", interval),
+    }
+}
+
+// Raw messages longer than this are truncated in ParseError::InvalidField
+const MAX_RAW_MSG_LEN: usize = 256;
+
+// Creates a ParseError::InvalidField, truncating the raw message
+pub(super) fn invalid_field(exchange: &str, field: &str, value: &str, msg: &str) -> ParseError {
+    let raw_msg = match msg.char_indices().nth(MAX_RAW_MSG_LEN) {
+        Some((pos, _)) => format!("{}...", &msg[..pos]),
+        None => msg.to_string(),
+    };
+    ParseError::InvalidField {
+        exchange: exchange.to_string(),
+        field: field.to_string(),
+        value: value.to_string(),
+        raw_msg,
+    }
+}
+
+// Parses a field, fails with ParseError::InvalidField
+pub(super) fn parse_field<T: FromStr>(
+    exchange: &str,
+    field: &str,
+    value: &str,
+    msg: &str,
+) -> std::result::Result<T, ParseError> {
+    value
+        .parse::<T>()
+        .map_err(|_| invalid_field(exchange, field, value, msg))
+}
+
+// Parses an RFC 3339 timestamp field, fails with ParseError::InvalidField
+pub(super) fn parse_rfc3339(
+    exchange: &str,
+    field: &str,
+    value: &str,
+    msg: &str,
+) -> std::result::Result<DateTime<FixedOffset>, ParseError> {
+    DateTime::parse_from_rfc3339(value).map_err(|_| invalid_field(exchange, field, value, msg))
+}
+
+// normalize_pair_cached() that fails with ParseError::UnknownSymbol instead of None
+pub(super) fn normalize_pair(
+    symbol: &str,
+    exchange: &str,
+) -> std::result::Result<String, ParseError> {
+    crypto_pair::normalize_pair_cached(symbol, exchange)
+        .ok_or_else(|| ParseError::UnknownSymbol(symbol.to_string()))
+}
+
 // returns (quantity_base, quantity_quote, quantity_contract)
 pub(super) fn calc_quantity_and_volume(
     exchange: &str,
@@ -31,11 +111,26 @@ pub(super) fn calc_quantity_and_volume(
     pair: &str,
     price: f64,
     quantity: f64,
-) -> (f64, f64, Option<f64>) {
-    let contract_value =
-        crypto_contract_value::get_contract_value(exchange, market_type, pair).unwrap() as f64;
+) -> std::result::Result<(f64, f64, Option<f64>), ParseError> {
+    let contract_value = crypto_contract_value::get_contract_value(exchange, market_type, pair)
+        .ok_or_else(|| {
+            ParseError::MissingContractValue(exchange.to_string(), market_type, pair.to_string())
+        })?;
 
-    match market_type {
+    // a size of 0 deletes a price level, keep it as 0 instead of computing
+    // 0 / price, which would be NaN for a 0 price
+    if quantity == 0.0 {
+        return match market_type {
+            MarketType::Spot => Ok((0.0, 0.0, None)),
+            MarketType::Unknown => Err(ParseError::UnsupportedMarketType(
+                exchange.to_string(),
+                market_type,
+            )),
+            _ => Ok((0.0, 0.0, Some(0.0))),
+        };
+    }
+
+    let quantities = match market_type {
         MarketType::Spot => (quantity, quantity * price, None),
         MarketType::InverseSwap | MarketType::InverseFuture => {
             let quantity_quote = quantity * contract_value;
@@ -54,6 +149,91 @@ pub(super) fn calc_quantity_and_volume(
             let quantity_base = quantity * contract_value;
             (quantity_base, quantity_base * price, Some(quantity))
         }
-        _ => panic!("Unknown market_type {}", market_type),
+        _ => {
+            return Err(ParseError::UnsupportedMarketType(
+                exchange.to_string(),
+                market_type,
+            ))
+        }
+    };
+    Ok(quantities)
+}
+
+// returns the notional value of open interest in quote currency, only inverse markets are supported
+pub(super) fn calc_open_interest_value(
+    exchange: &str,
+    market_type: MarketType,
+    pair: &str,
+    open_interest: f64,
+) -> Option<f64> {
+    match market_type {
+        MarketType::InverseSwap | MarketType::InverseFuture => {
+            crypto_contract_value::get_contract_value(exchange, market_type, pair)
+                .map(|contract_value| open_interest * contract_value)
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        calc_quantity_and_volume, interval_to_period, invalid_field, parse_field, parse_rfc3339,
+        MAX_RAW_MSG_LEN,
+    };
+    use crate::ParseError;
+    use crypto_market_type::MarketType;
+
+    #[test]
+    fn invalid_field_context() {
+        let msg = r#"{"time":"yesterday"}"#;
+        let err = parse_rfc3339("coinbase_pro", "time", "yesterday", msg)
+            .err()
+            .unwrap();
+        assert_eq!(
+            r#"Invalid time "yesterday" from coinbase_pro, raw message: {"time":"yesterday"}"#,
+            err.to_string()
+        );
+        assert!(parse_field::<f64>("coinbase_pro", "price", "1.5", msg).is_ok());
+    }
+
+    #[test]
+    fn truncate_raw_msg() {
+        let msg = "é".repeat(MAX_RAW_MSG_LEN + 10);
+        if let ParseError::InvalidField { raw_msg, .. } = invalid_field("bitmex", "price", "", &msg)
+        {
+            assert_eq!(MAX_RAW_MSG_LEN + 3, raw_msg.chars().count());
+            assert!(raw_msg.ends_with("..."));
+        } else {
+            panic!("Expected ParseError::InvalidField");
+        }
+    }
+
+    #[test]
+    fn zero_quantity() {
+        assert_eq!(
+            (0.0, 0.0, None),
+            calc_quantity_and_volume("okex", MarketType::Spot, "BTC/USDT", 38886.3, 0.0).unwrap()
+        );
+        assert_eq!(
+            (0.0, 0.0, Some(0.0)),
+            calc_quantity_and_volume("okex", MarketType::InverseSwap, "BTC/USD", 0.0, 0.0).unwrap()
+        );
+        assert_eq!(
+            (0.0, 0.0, Some(0.0)),
+            calc_quantity_and_volume("okex", MarketType::LinearSwap, "BTC/USDT", 47301.5, 0.0)
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn period() {
+        assert_eq!("10s", interval_to_period(10));
+        assert_eq!("1m", interval_to_period(60));
+        assert_eq!("15m", interval_to_period(900));
+        assert_eq!("4H", interval_to_period(14400));
+        assert_eq!("3D", interval_to_period(259200));
+        assert_eq!("2W", interval_to_period(1209600));
+        assert_eq!("1M", interval_to_period(2592000));
     }
 }
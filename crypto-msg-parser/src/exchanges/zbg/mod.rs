@@ -5,7 +5,7 @@ use crypto_market_type::MarketType;
 
 use crate::{OrderBookMsg, TradeMsg};
 
-use serde_json::Result;
+use crate::error::Result;
 
 pub(crate) fn extract_symbol(market_type: MarketType, msg: &str) -> Option<String> {
     if market_type == MarketType::Spot {
@@ -2,23 +2,28 @@ use crypto_market_type::MarketType;
 
 use crate::{MessageType, Order, OrderBookMsg, TradeMsg, TradeSide};
 
+use crate::error::{ParseError, Result};
+use crate::exchanges::utils::{normalize_pair, original_json, parse_field, raw_json};
 use serde::{Deserialize, Serialize};
-use serde_json::{Result, Value};
+use serde_json::Value;
 
 const EXCHANGE_NAME: &str = "zbg";
 
+// Spot data messages are arrays of strings, e.g. [T, symbol-id, timestamp, symbol, ...],
+// while pong and subscription responses are JSON objects
 pub(super) fn extract_symbol(msg: &str) -> Option<String> {
-    if let Ok(list) = serde_json::from_str::<Vec<Vec<Value>>>(msg) {
-        if msg.starts_with(r#"[["T","#) || msg.starts_with(r#"["T","#) {
-            Some(list[0][3].as_str().unwrap().to_string())
-        } else {
-            Some(list[0][2].as_str().unwrap().to_string())
-        }
-    } else if let Ok(list) = serde_json::from_str::<Vec<Value>>(msg) {
-        Some(list[3].as_str().unwrap().to_string())
+    let first = if let Ok(list) = serde_json::from_str::<Vec<Vec<Value>>>(msg) {
+        list.into_iter().next()?
     } else {
-        None
-    }
+        serde_json::from_str::<Vec<Value>>(msg).ok()?
+    };
+    // the symbol precedes the timestamp only in snapshots
+    let index = if first.first()?.as_str()? == "AE" {
+        2
+    } else {
+        3
+    };
+    first.get(index)?.as_str().map(|x| x.to_string())
 }
 
 // https://zbgapi.github.io/docs/spot/v1/en/#market-trade
@@ -30,43 +35,53 @@ pub(super) fn parse_trade(msg: &str) -> Result<Vec<TradeMsg>> {
         let tmp = serde_json::from_str::<Vec<String>>(msg)?;
         vec![tmp]
     } else {
-        panic!("Invalid trade msg {}", msg);
+        return Err(ParseError::MalformedField(format!(
+            "Invalid trade msg {}",
+            msg
+        )));
     };
 
-    let mut trades: Vec<TradeMsg> = arr
+    let mut trades = arr
         .into_iter()
         .map(|raw_trade| {
-            assert_eq!(raw_trade[0], "T");
-            let timestamp = raw_trade[2].parse::<i64>().unwrap() * 1000;
+            if raw_trade.len() < 7 || raw_trade[0] != "T" {
+                return Err(ParseError::MalformedField(format!(
+                    "Invalid trade msg {}",
+                    msg
+                )));
+            }
+            let timestamp =
+                parse_field::<i64>(EXCHANGE_NAME, "timestamp", &raw_trade[2], msg)? * 1000;
             let symbol = raw_trade[3].as_str();
             let side = if raw_trade[4] == "ask" {
                 TradeSide::Sell
             } else {
                 TradeSide::Buy
             };
-            let price = raw_trade[5].parse::<f64>().unwrap();
-            let quantity = raw_trade[6].parse::<f64>().unwrap();
+            let price = parse_field(EXCHANGE_NAME, "price", &raw_trade[5], msg)?;
+            let quantity = parse_field(EXCHANGE_NAME, "quantity", &raw_trade[6], msg)?;
 
-            TradeMsg {
+            Ok(TradeMsg {
                 exchange: EXCHANGE_NAME.to_string(),
                 market_type: MarketType::Spot,
                 symbol: symbol.to_string(),
-                pair: crypto_pair::normalize_pair(symbol, EXCHANGE_NAME).unwrap(),
+                pair: normalize_pair(symbol, EXCHANGE_NAME)?,
                 msg_type: MessageType::Trade,
                 timestamp,
+                timestamp_us: None,
                 price,
                 quantity_base: quantity,
                 quantity_quote: price * quantity,
                 quantity_contract: None,
                 side,
                 trade_id: timestamp.to_string(),
-                json: serde_json::to_string(&raw_trade).unwrap(),
-            }
+                json: raw_json(&raw_trade),
+            })
         })
-        .collect();
+        .collect::<Result<Vec<TradeMsg>>>()?;
 
     if trades.len() == 1 {
-        trades[0].json = msg.to_string();
+        trades[0].json = original_json(msg);
     }
     Ok(trades)
 }
@@ -88,106 +103,106 @@ pub(crate) fn parse_l2(msg: &str) -> Result<Vec<OrderBookMsg>> {
     let orderbooks = if snapshot {
         let arr = serde_json::from_str::<Vec<Vec<Value>>>(msg)?;
 
-        let parse_order = |raw_order: &[Value; 2]| -> Order {
-            if raw_order[0].is_string() {
-                let price = raw_order[0].as_str().unwrap().parse::<f64>().unwrap();
-                let quantity_base = raw_order[1].as_str().unwrap().parse::<f64>().unwrap();
-
-                Order {
-                    price,
-                    quantity_base,
-                    quantity_quote: price * quantity_base,
-                    quantity_contract: None,
-                }
-            } else if raw_order[0].is_f64() {
-                let price = raw_order[0].as_f64().unwrap();
-                let quantity_base = raw_order[1].as_f64().unwrap();
-
-                Order {
-                    price,
-                    quantity_base,
-                    quantity_quote: price * quantity_base,
-                    quantity_contract: None,
-                }
-            } else {
-                panic!("Unknown format {}", msg);
+        // prices and quantities are either strings or numbers
+        let parse_number = |field: &str, value: &Value| -> Result<f64> {
+            match value {
+                Value::String(s) => parse_field(EXCHANGE_NAME, field, s, msg),
+                _ => value.as_f64().ok_or_else(|| {
+                    ParseError::MalformedField(format!("Invalid {} {} in {}", field, value, msg))
+                }),
             }
         };
+        let parse_order = |raw_order: &[Value; 2]| -> Result<Order> {
+            let price = parse_number("price", &raw_order[0])?;
+            let quantity_base = parse_number("quantity", &raw_order[1])?;
+
+            Ok(Order {
+                price,
+                quantity_base,
+                quantity_quote: price * quantity_base,
+                quantity_contract: None,
+                order_id: None,
+                order_type: None,
+            })
+        };
+        let parse_orders = |side: &str, raw_orders: Option<&Value>| -> Result<Vec<Order>> {
+            let raw_orders = raw_orders
+                .and_then(|x| x.get(side))
+                .ok_or_else(|| ParseError::MalformedField(format!("No {} in {}", side, msg)))?;
+            serde_json::from_value::<Vec<[Value; 2]>>(raw_orders.clone())?
+                .iter()
+                .map(parse_order)
+                .collect()
+        };
 
         let mut v = arr
             .iter()
             .map(|raw_orderbook| {
-                let symbol = raw_orderbook[2].as_str().unwrap();
-                let pair = crypto_pair::normalize_pair(symbol, EXCHANGE_NAME).unwrap();
-                let timestamp = raw_orderbook[3].as_str().unwrap().parse::<i64>().unwrap() * 1000;
-
-                let asks = serde_json::from_value::<Vec<[Value; 2]>>(
-                    raw_orderbook[4]
-                        .as_object()
-                        .unwrap()
-                        .get("asks")
-                        .unwrap()
-                        .clone(),
-                )
-                .unwrap()
-                .iter()
-                .map(|x| parse_order(x))
-                .collect::<Vec<Order>>();
-                let bids = serde_json::from_value::<Vec<[Value; 2]>>(
-                    raw_orderbook[5]
-                        .as_object()
-                        .unwrap()
-                        .get("bids")
-                        .unwrap()
-                        .clone(),
-                )
-                .unwrap()
-                .iter()
-                .map(|x| parse_order(x))
-                .collect::<Vec<Order>>();
-
-                OrderBookMsg {
+                let symbol = raw_orderbook
+                    .get(2)
+                    .and_then(|x| x.as_str())
+                    .ok_or_else(|| ParseError::MalformedField(format!("No symbol in {}", msg)))?;
+                let pair = normalize_pair(symbol, EXCHANGE_NAME)?;
+                let timestamp = raw_orderbook
+                    .get(3)
+                    .and_then(|x| x.as_str())
+                    .ok_or_else(|| {
+                        ParseError::MalformedField(format!("No timestamp in {}", msg))
+                    })?;
+                let timestamp =
+                    parse_field::<i64>(EXCHANGE_NAME, "timestamp", timestamp, msg)? * 1000;
+
+                let asks = parse_orders("asks", raw_orderbook.get(4))?;
+                let bids = parse_orders("bids", raw_orderbook.get(5))?;
+
+                Ok(OrderBookMsg {
                     exchange: EXCHANGE_NAME.to_string(),
                     market_type: MarketType::Spot,
                     symbol: symbol.to_string(),
                     pair,
                     msg_type: MessageType::L2Event,
                     timestamp,
+                    timestamp_us: None,
                     seq_id: None,
                     prev_seq_id: None,
                     asks,
                     bids,
                     snapshot,
-                    json: serde_json::to_string(raw_orderbook)
-                        .unwrap()
-                        .as_str()
-                        .to_string(),
-                }
+                    json: raw_json(raw_orderbook),
+                })
             })
-            .collect::<Vec<OrderBookMsg>>();
+            .collect::<Result<Vec<OrderBookMsg>>>()?;
 
         if v.len() == 1 {
-            v[0].json = msg.to_string();
+            v[0].json = original_json(msg);
         }
         v
     } else {
         let arr = serde_json::from_str::<Vec<String>>(msg)?;
+        if arr.len() < 7 {
+            return Err(ParseError::MalformedField(format!(
+                "Invalid orderbook update {}",
+                msg
+            )));
+        }
         let symbol = arr[3].clone();
-        let pair = crypto_pair::normalize_pair(&symbol, EXCHANGE_NAME).unwrap();
-        let timestamp = arr[2].parse::<i64>().unwrap() * 1000;
+        let pair = normalize_pair(&symbol, EXCHANGE_NAME)?;
+        let timestamp = parse_field::<i64>(EXCHANGE_NAME, "timestamp", &arr[2], msg)? * 1000;
 
         let mut asks: Vec<Order> = Vec::new();
         let mut bids: Vec<Order> = Vec::new();
 
         let order: Order = {
-            let price = arr[5].parse::<f64>().unwrap();
-            let quantity_base = arr[6].parse::<f64>().unwrap();
+            let price = parse_field(EXCHANGE_NAME, "price", &arr[5], msg)?;
+            let quantity_base = parse_field(EXCHANGE_NAME, "quantity", &arr[6], msg)?;
 
             Order {
                 price,
                 quantity_base,
                 quantity_quote: quantity_base * price,
                 quantity_contract: None,
+                order_id: None,
+                order_type: None,
             }
         };
 
@@ -204,12 +219,13 @@ pub(crate) fn parse_l2(msg: &str) -> Result<Vec<OrderBookMsg>> {
             pair,
             msg_type: MessageType::L2Event,
             timestamp,
+            timestamp_us: None,
             seq_id: None,
             prev_seq_id: None,
             asks,
             bids,
             snapshot,
-            json: msg.to_string(),
+            json: original_json(msg),
         };
         vec![orderbook]
     };
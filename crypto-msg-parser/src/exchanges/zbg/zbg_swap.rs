@@ -1,11 +1,13 @@
 use crypto_market_type::MarketType;
 
-use super::super::utils::http_get;
+use super::super::utils::{normalize_pair, original_json, parse_field};
 use crate::{MessageType, Order, OrderBookMsg, TradeMsg, TradeSide};
+use crypto_pair::http_get;
 
+use crate::error::{ParseError, Result};
 use lazy_static::lazy_static;
 use serde::{Deserialize, Serialize};
-use serde_json::{Result, Value};
+use serde_json::Value;
 use std::collections::{BTreeMap, HashMap};
 
 const EXCHANGE_NAME: &str = "zbg";
@@ -66,6 +68,9 @@ impl SwapContractInfo {
 
 // See https://zbgapi.github.io/docs/future/v1/en/#public-get-contracts
 fn fetch_swap_contracts() -> BTreeMap<i64, SwapContractInfo> {
+    if cfg!(feature = "offline") {
+        return BTreeMap::new();
+    }
     #[derive(Serialize, Deserialize)]
     #[allow(non_snake_case)]
     struct SwapMarket {
@@ -99,17 +104,22 @@ fn fetch_swap_contracts() -> BTreeMap<i64, SwapContractInfo> {
     }
 
     let mut mapping = BTreeMap::<i64, SwapContractInfo>::new();
-    if let Ok(txt) = http_get("https://www.zbg.com/exchange/api/v1/future/common/contracts") {
-        let resp = serde_json::from_str::<Response>(&txt).unwrap();
-        let swap_markets = resp.datas;
-
-        for swap_market in swap_markets.iter() {
-            let contract_info = SwapContractInfo {
-                symbol: swap_market.symbol.clone(),
-                contract_id: swap_market.contractId,
-                contract_unit: swap_market.contractUnit.parse::<f64>().unwrap(),
-            };
-            mapping.insert(contract_info.contract_id, contract_info);
+    if let Some(resp) = http_get(
+        EXCHANGE_NAME,
+        "https://www.zbg.com/exchange/api/v1/future/common/contracts",
+    )
+    .ok()
+    .and_then(|txt| serde_json::from_str::<Response>(&txt).ok())
+    {
+        for swap_market in resp.datas.iter() {
+            if let Ok(contract_unit) = swap_market.contractUnit.parse::<f64>() {
+                let contract_info = SwapContractInfo {
+                    symbol: swap_market.symbol.clone(),
+                    contract_id: swap_market.contractId,
+                    contract_unit,
+                };
+                mapping.insert(contract_info.contract_id, contract_info);
+            }
         }
     }
 
@@ -138,65 +148,85 @@ struct RawOrderbookMsg {
     extra: HashMap<String, Value>,
 }
 
+// Swap messages carry a contract ID instead of a symbol
+fn get_contract_info(contract_id: i64) -> Result<&'static SwapContractInfo> {
+    SWAP_CONTRACT_MAP
+        .get(&contract_id)
+        .ok_or_else(|| ParseError::UnknownSymbol(format!("contractId {}", contract_id)))
+}
+
 pub(super) fn extract_symbol(_market_type: MarketType, msg: &str) -> Option<String> {
-    let ws_msg = serde_json::from_str::<Vec<Value>>(msg).unwrap();
-    let contract_id = ws_msg[1]["contractId"].as_i64().unwrap();
-    let contract_info = SWAP_CONTRACT_MAP.get(&contract_id).unwrap();
-    let symbol = contract_info.symbol.as_str();
-    Some(symbol.to_string())
+    let ws_msg = serde_json::from_str::<Vec<Value>>(msg).ok()?;
+    let contract_id = ws_msg.get(1)?["contractId"].as_i64()?;
+    let contract_info = get_contract_info(contract_id).ok()?;
+    Some(contract_info.symbol.clone())
 }
 
 fn calc_quantity_and_volume(
     market_type: MarketType,
-    contract_id: i64,
+    contract_unit: f64,
     price: f64,
     size: f64,
-) -> (f64, f64) {
+) -> Result<(f64, f64)> {
     match market_type {
         MarketType::InverseSwap => {
-            let contract_unit = SWAP_CONTRACT_MAP.get(&contract_id).unwrap().contract_unit;
             let volume = size * contract_unit;
 
-            (volume / price, volume)
+            Ok((volume / price, volume))
         }
         MarketType::LinearSwap => {
-            let contract_unit = SWAP_CONTRACT_MAP.get(&contract_id).unwrap().contract_unit;
             let quantity = size * contract_unit;
 
-            (quantity, quantity * price)
+            Ok((quantity, quantity * price))
         }
-        _ => panic!("Unknown market_type {}", market_type),
+        _ => Err(ParseError::UnsupportedMarketType(
+            EXCHANGE_NAME.to_string(),
+            market_type,
+        )),
     }
 }
 
+// Checks the channel of a `[channel, data]` message and returns data
+fn extract_data(channel: &str, msg: &str) -> Result<Value> {
+    let mut ws_msg = serde_json::from_str::<Vec<Value>>(msg)?;
+    if ws_msg.len() != 2 || ws_msg[0].as_str() != Some(channel) {
+        return Err(ParseError::MalformedField(format!(
+            "Expected a {} message, got {}",
+            channel, msg
+        )));
+    }
+    Ok(ws_msg.swap_remove(1))
+}
+
 pub(super) fn parse_trade(market_type: MarketType, msg: &str) -> Result<Vec<TradeMsg>> {
-    let ws_msg = serde_json::from_str::<Vec<Value>>(msg)?;
-    assert_eq!(ws_msg[0].as_str().unwrap(), "future_tick");
-    let raw_trade: RawTradeMsg = serde_json::from_value(ws_msg[1].clone()).unwrap();
+    let raw_trade: RawTradeMsg = serde_json::from_value(extract_data("future_tick", msg)?)?;
 
-    let contract_info = SWAP_CONTRACT_MAP.get(&raw_trade.contractId).unwrap();
+    let contract_info = get_contract_info(raw_trade.contractId)?;
     let symbol = contract_info.symbol.as_str();
-    let pair = crypto_pair::normalize_pair(symbol, EXCHANGE_NAME).unwrap();
-
-    let timestamp = raw_trade.trades[0].as_i64().unwrap();
-    let price = raw_trade.trades[1]
-        .as_str()
-        .unwrap()
-        .parse::<f64>()
-        .unwrap();
-    let size = raw_trade.trades[2]
-        .as_str()
-        .unwrap()
-        .parse::<f64>()
-        .unwrap();
-    let side = if raw_trade.trades[3].as_i64().unwrap() == -1 {
+    let pair = normalize_pair(symbol, EXCHANGE_NAME)?;
+
+    // [timestamp, price, size, side]
+    let (timestamp, price, size, side) = match raw_trade.trades.as_slice() {
+        [timestamp, price, size, side, ..] => (
+            timestamp.as_i64(),
+            price.as_str(),
+            size.as_str(),
+            side.as_i64(),
+        ),
+        _ => (None, None, None, None),
+    };
+    let malformed = || ParseError::MalformedField(format!("Invalid trades in {}", msg));
+    let timestamp = timestamp.ok_or_else(malformed)?;
+    let price = parse_field(EXCHANGE_NAME, "price", price.ok_or_else(malformed)?, msg)?;
+    let size = parse_field(EXCHANGE_NAME, "size", size.ok_or_else(malformed)?, msg)?;
+    let side = if side.ok_or_else(malformed)? == -1 {
         TradeSide::Sell
     } else {
         TradeSide::Buy
     };
 
     let (quantity_base, quantity_quote) =
-        calc_quantity_and_volume(market_type, contract_info.contract_id, price, size);
+        calc_quantity_and_volume(market_type, contract_info.contract_unit, price, size)?;
 
     let trade = TradeMsg {
         exchange: EXCHANGE_NAME.to_string(),
@@ -205,39 +235,41 @@ pub(super) fn parse_trade(market_type: MarketType, msg: &str) -> Result<Vec<Trad
         pair,
         msg_type: MessageType::Trade,
         timestamp: timestamp / 1000,
+        timestamp_us: None,
         price,
         quantity_base,
         quantity_quote,
         quantity_contract: Some(size),
         side,
         trade_id: timestamp.to_string(),
-        json: msg.to_string(),
+        json: original_json(msg),
     };
 
     Ok(vec![trade])
 }
 
 pub(crate) fn parse_l2(market_type: MarketType, msg: &str) -> Result<Vec<OrderBookMsg>> {
-    let ws_msg = serde_json::from_str::<Vec<Value>>(msg)?;
-    assert_eq!(ws_msg[0].as_str().unwrap(), "future_snapshot_depth");
-    let raw_orderbook: RawOrderbookMsg = serde_json::from_value(ws_msg[1].clone()).unwrap();
+    let raw_orderbook: RawOrderbookMsg =
+        serde_json::from_value(extract_data("future_snapshot_depth", msg)?)?;
 
-    let contract_info = SWAP_CONTRACT_MAP.get(&raw_orderbook.contractId).unwrap();
+    let contract_info = get_contract_info(raw_orderbook.contractId)?;
     let symbol = contract_info.symbol.as_str();
-    let pair = crypto_pair::normalize_pair(symbol, EXCHANGE_NAME).unwrap();
+    let pair = normalize_pair(symbol, EXCHANGE_NAME)?;
 
-    let parse_order = |raw_order: &[String; 2]| -> Order {
-        let price = raw_order[0].parse::<f64>().unwrap();
-        let quantity = raw_order[1].parse::<f64>().unwrap();
+    let parse_order = |raw_order: &[String; 2]| -> Result<Order> {
+        let price = parse_field(EXCHANGE_NAME, "price", &raw_order[0], msg)?;
+        let quantity = parse_field(EXCHANGE_NAME, "quantity", &raw_order[1], msg)?;
         let (quantity_base, quantity_quote) =
-            calc_quantity_and_volume(market_type, contract_info.contract_id, price, quantity);
+            calc_quantity_and_volume(market_type, contract_info.contract_unit, price, quantity)?;
 
-        Order {
+        Ok(Order {
             price,
             quantity_base,
             quantity_quote,
             quantity_contract: Some(quantity),
-        }
+            order_id: None,
+            order_type: None,
+        })
     };
 
     let orderbook = OrderBookMsg {
@@ -247,20 +279,21 @@ pub(crate) fn parse_l2(market_type: MarketType, msg: &str) -> Result<Vec<OrderBo
         pair,
         msg_type: MessageType::L2Event,
         timestamp: raw_orderbook.time / 1000,
+        timestamp_us: None,
         seq_id: None,
         prev_seq_id: None,
         asks: raw_orderbook
             .asks
             .iter()
-            .map(|x| parse_order(x))
-            .collect::<Vec<Order>>(),
+            .map(&parse_order)
+            .collect::<Result<Vec<Order>>>()?,
         bids: raw_orderbook
             .bids
             .iter()
-            .map(|x| parse_order(x))
-            .collect::<Vec<Order>>(),
+            .map(&parse_order)
+            .collect::<Result<Vec<Order>>>()?,
         snapshot: false,
-        json: msg.to_string(),
+        json: original_json(msg),
     };
 
     Ok(vec![orderbook])
@@ -1,18 +1,36 @@
+mod bitfinex_channels;
+mod book;
+mod compact;
+mod dedup;
+mod error;
 pub mod exchanges;
 mod msg;
+mod options;
 mod order;
+mod seq_gap;
+mod topk;
 
+pub use bitfinex_channels::BitfinexChannels;
+pub use book::{OrderBookManager, ResyncRequest, SnapshotCadence};
+pub use compact::OrderBookCompactor;
+pub use dedup::TradeDeduplicator;
+pub use error::ParseError;
 pub use msg::*;
-pub use order::Order;
+pub use options::ParseOptions;
+pub use order::{Order, OrderUpdateType};
+pub use seq_gap::SeqGapDetector;
+pub use topk::TopKTracker;
 
-pub use crypto_market_type::MarketType;
+pub use crypto_market_type::{MarketType, MessageType};
 
-use serde_json::Result;
+use error::Result;
 
 /// Extract the symbol from the message.
 pub fn extract_symbol(exchange: &str, market_type: MarketType, msg: &str) -> Option<String> {
+    if let Some(parser) = exchanges::lookup(exchange) {
+        return (parser.extract_symbol)(market_type, msg);
+    }
     match exchange {
-        "binance" => exchanges::binance::extract_symbol(market_type, msg),
         "bitfinex" => exchanges::bitfinex::extract_symbol(market_type, msg),
         "bitget" => exchanges::bitget::extract_symbol(market_type, msg),
         "bithumb" => exchanges::bithumb::extract_symbol(market_type, msg),
@@ -29,7 +47,6 @@ pub fn extract_symbol(exchange: &str, market_type: MarketType, msg: &str) -> Opt
         "kraken" => exchanges::kraken::extract_symbol(market_type, msg),
         "kucoin" => exchanges::kucoin::extract_symbol(market_type, msg),
         "mxc" => exchanges::mxc::extract_symbol(market_type, msg),
-        "okex" => exchanges::okex::extract_symbol(market_type, msg),
         "zbg" => exchanges::zbg::extract_symbol(market_type, msg),
         _ => panic!("Unknown exchange {}", exchange),
     }
@@ -37,8 +54,10 @@ pub fn extract_symbol(exchange: &str, market_type: MarketType, msg: &str) -> Opt
 
 /// Parse trade messages.
 pub fn parse_trade(exchange: &str, market_type: MarketType, msg: &str) -> Result<Vec<TradeMsg>> {
+    if let Some(parser) = exchanges::lookup(exchange) {
+        return (parser.parse_trade)(market_type, msg);
+    }
     match exchange {
-        "binance" => exchanges::binance::parse_trade(market_type, msg),
         "bitfinex" => exchanges::bitfinex::parse_trade(market_type, msg),
         "bitget" => exchanges::bitget::parse_trade(market_type, msg),
         "bithumb" => exchanges::bithumb::parse_trade(market_type, msg),
@@ -55,10 +74,38 @@ pub fn parse_trade(exchange: &str, market_type: MarketType, msg: &str) -> Result
         "kraken" => exchanges::kraken::parse_trade(market_type, msg),
         "kucoin" => exchanges::kucoin::parse_trade(market_type, msg),
         "mxc" => exchanges::mxc::parse_trade(market_type, msg),
-        "okex" => exchanges::okex::parse_trade(market_type, msg),
         "zbg" => exchanges::zbg::parse_trade(market_type, msg),
-        _ => panic!("Unknown exchange {}", exchange),
+        _ => Err(ParseError::UnsupportedExchange(exchange.to_string())),
+    }
+}
+
+/// Parse trade messages, populating only fields selected by `options`.
+///
+/// Exchanges with option-aware parsers, such as okex, skip the work of
+/// deselected fields, others fill them in and clear them afterwards.
+pub fn parse_trade_with_options(
+    exchange: &str,
+    market_type: MarketType,
+    msg: &str,
+    options: &ParseOptions,
+) -> Result<Vec<TradeMsg>> {
+    let mut trades =
+        match exchanges::lookup(exchange).and_then(|parser| parser.parse_trade_with_options) {
+            Some(parse) => parse(market_type, msg, options)?,
+            None => parse_trade(exchange, market_type, msg)?,
+        };
+    for trade in trades.iter_mut() {
+        if !options.keep_json {
+            trade.json = String::new();
+        }
+        if !options.normalize_pair {
+            trade.pair = String::new();
+        }
+        if !options.compute_quote {
+            trade.quantity_quote = 0.0;
+        }
     }
+    Ok(trades)
 }
 
 /// Parse level2 orderbook messages.
@@ -68,73 +115,265 @@ pub fn parse_l2(
     msg: &str,
     timestamp: Option<i64>,
 ) -> Result<Vec<OrderBookMsg>> {
-    let ret = match exchange {
-        "binance" => exchanges::binance::parse_l2(market_type, msg),
-        "bitfinex" => exchanges::bitfinex::parse_l2(
-            market_type,
-            msg,
-            timestamp.expect("Bitfinex orderbook messages doesn't have timestamp"),
-        ),
-        "bitget" => exchanges::bitget::parse_l2(market_type, msg),
-        "bithumb" => exchanges::bithumb::parse_l2(market_type, msg),
-        "bitmex" => exchanges::bitmex::parse_l2(
-            market_type,
-            msg,
-            timestamp.expect("BitMEX orderbook messages don't have timestamp"),
-        ),
-        "bitstamp" => exchanges::bitstamp::parse_l2(market_type, msg),
-        "bitz" => exchanges::bitz::parse_l2(market_type, msg),
-        "bybit" => exchanges::bybit::parse_l2(market_type, msg),
-        "coinbase_pro" => exchanges::coinbase_pro::parse_l2(market_type, msg, timestamp),
-        "deribit" => exchanges::deribit::parse_l2(market_type, msg),
-        "dydx" => exchanges::dydx::parse_l2(
-            market_type,
-            msg,
-            timestamp.expect("dYdX orderbook messages don't have timestamp"),
-        ),
-        "ftx" => exchanges::ftx::parse_l2(market_type, msg),
-        "gate" => exchanges::gate::parse_l2(market_type, msg, timestamp),
-        "huobi" => exchanges::huobi::parse_l2(market_type, msg),
-        "kraken" => exchanges::kraken::parse_l2(market_type, msg),
-        "kucoin" => exchanges::kucoin::parse_l2(market_type, msg, timestamp),
-        "mxc" => exchanges::mxc::parse_l2(market_type, msg, timestamp),
-        "okex" => exchanges::okex::parse_l2(market_type, msg),
-        "zbg" => exchanges::zbg::parse_l2(market_type, msg),
-        _ => panic!("Unknown exchange {}", exchange),
-    };
-    match ret {
-        Ok(mut orderbooks) => {
-            for orderbook in orderbooks.iter_mut() {
-                if orderbook.snapshot {
-                    // sorted in ascending order by price
-                    orderbook
-                        .asks
-                        .sort_by(|a, b| a.price.partial_cmp(&b.price).unwrap());
-                    // sorted in descending order by price
-                    orderbook
-                        .bids
-                        .sort_by(|a, b| b.price.partial_cmp(&a.price).unwrap());
-                }
+    let mut orderbooks = if let Some(parser) = exchanges::lookup(exchange) {
+        (parser.parse_l2)(market_type, msg, timestamp)
+    } else {
+        match exchange {
+            "bitfinex" => exchanges::bitfinex::parse_l2(
+                market_type,
+                msg,
+                timestamp.ok_or_else(|| {
+                    ParseError::MalformedField(
+                        "Bitfinex orderbook messages doesn't have timestamp".to_string(),
+                    )
+                })?,
+            ),
+            "bitget" => exchanges::bitget::parse_l2(market_type, msg),
+            "bithumb" => exchanges::bithumb::parse_l2(market_type, msg),
+            "bitmex" => exchanges::bitmex::parse_l2(
+                market_type,
+                msg,
+                timestamp.ok_or_else(|| {
+                    ParseError::MalformedField(
+                        "BitMEX orderbook messages don't have timestamp".to_string(),
+                    )
+                })?,
+            ),
+            "bitstamp" => exchanges::bitstamp::parse_l2(market_type, msg),
+            "bitz" => exchanges::bitz::parse_l2(market_type, msg),
+            "bybit" => exchanges::bybit::parse_l2(market_type, msg),
+            "coinbase_pro" => exchanges::coinbase_pro::parse_l2(market_type, msg, timestamp),
+            "deribit" => exchanges::deribit::parse_l2(market_type, msg),
+            "dydx" => exchanges::dydx::parse_l2(
+                market_type,
+                msg,
+                timestamp.ok_or_else(|| {
+                    ParseError::MalformedField(
+                        "dYdX orderbook messages don't have timestamp".to_string(),
+                    )
+                })?,
+            ),
+            "ftx" => exchanges::ftx::parse_l2(market_type, msg),
+            "gate" => exchanges::gate::parse_l2(market_type, msg, timestamp),
+            "huobi" => exchanges::huobi::parse_l2(market_type, msg),
+            "kraken" => exchanges::kraken::parse_l2(market_type, msg),
+            "kucoin" => exchanges::kucoin::parse_l2(market_type, msg, timestamp),
+            "mxc" => exchanges::mxc::parse_l2(market_type, msg, timestamp),
+            "zbg" => exchanges::zbg::parse_l2(market_type, msg),
+            _ => Err(ParseError::UnsupportedExchange(exchange.to_string())),
+        }
+    }?;
+    sort_snapshots(&mut orderbooks);
+    Ok(orderbooks)
+}
+
+fn sort_snapshots(orderbooks: &mut [OrderBookMsg]) {
+    for orderbook in orderbooks.iter_mut() {
+        if orderbook.snapshot {
+            // sorted in ascending order by price
+            orderbook.asks.sort_by(|a, b| a.price.total_cmp(&b.price));
+            // sorted in descending order by price
+            orderbook.bids.sort_by(|a, b| b.price.total_cmp(&a.price));
+        }
+    }
+}
+
+/// Parse level2 orderbook messages, populating only fields selected by `options`.
+///
+/// See `parse_trade_with_options()`.
+pub fn parse_l2_with_options(
+    exchange: &str,
+    market_type: MarketType,
+    msg: &str,
+    timestamp: Option<i64>,
+    options: &ParseOptions,
+) -> Result<Vec<OrderBookMsg>> {
+    let mut orderbooks =
+        match exchanges::lookup(exchange).and_then(|parser| parser.parse_l2_with_options) {
+            Some(parse) => {
+                let mut orderbooks = parse(market_type, msg, timestamp, options)?;
+                sort_snapshots(&mut orderbooks);
+                orderbooks
+            }
+            None => parse_l2(exchange, market_type, msg, timestamp)?,
+        };
+    for orderbook in orderbooks.iter_mut() {
+        if !options.keep_json {
+            orderbook.json = String::new();
+        }
+        if !options.normalize_pair {
+            orderbook.pair = String::new();
+        }
+        if !options.compute_quote {
+            for order in orderbook.asks.iter_mut().chain(orderbook.bids.iter_mut()) {
+                order.quantity_quote = 0.0;
             }
-            Ok(orderbooks)
         }
-        Err(_) => ret,
+    }
+    Ok(orderbooks)
+}
+
+/// Parse level2 orderbook snapshots fetched by RESTful APIs.
+///
+/// RESTful responses may not contain the symbol, so it has to be passed in.
+/// If the exchange has sequence numbers, `seq_id` is the last update ID of
+/// the snapshot, so that level2 events with `seq_id` not greater than it can
+/// be dropped when applying them on top of the snapshot.
+pub fn parse_l2_snapshot(
+    exchange: &str,
+    market_type: MarketType,
+    symbol: &str,
+    msg: &str,
+) -> Result<Vec<OrderBookMsg>> {
+    let ret = match exchanges::lookup(exchange).and_then(|parser| parser.parse_l2_snapshot) {
+        Some(func) => func(market_type, symbol, msg),
+        None => Err(ParseError::UnsupportedExchange(exchange.to_string())),
+    };
+    ret.map(|mut orderbooks| {
+        for orderbook in orderbooks.iter_mut() {
+            orderbook.asks.sort_by(|a, b| a.price.total_cmp(&b.price));
+            orderbook.bids.sort_by(|a, b| b.price.total_cmp(&a.price));
+        }
+        orderbooks
+    })
+}
+
+/// Parse BBO(best bid & offer) messages.
+///
+/// `timestamp` is used only if the message has none, e.g., binance spot
+/// `bookTicker`, usually it is the time the message was received.
+pub fn parse_bbo(
+    exchange: &str,
+    market_type: MarketType,
+    msg: &str,
+    timestamp: Option<i64>,
+) -> Result<Vec<BboMsg>> {
+    match exchanges::lookup(exchange).and_then(|parser| parser.parse_bbo) {
+        Some(func) => func(market_type, msg, timestamp),
+        None => Err(ParseError::UnsupportedExchange(exchange.to_string())),
     }
 }
 
+/// Parse 24hr rolling window ticker messages, e.g., binance `@ticker`.
+pub fn parse_ticker(exchange: &str, market_type: MarketType, msg: &str) -> Result<Vec<TickerMsg>> {
+    call_registered(
+        exchange,
+        exchanges::lookup(exchange).and_then(|parser| parser.parse_ticker),
+        market_type,
+        msg,
+    )
+}
+
 /// Parse funding rate messages.
 pub fn parse_funding_rate(
     exchange: &str,
     market_type: MarketType,
     msg: &str,
 ) -> Result<Vec<FundingRateMsg>> {
+    if let Some(parser) = exchanges::lookup(exchange) {
+        return call_registered(exchange, parser.parse_funding_rate, market_type, msg);
+    }
     let func = match exchange {
-        "binance" => exchanges::binance::parse_funding_rate,
         "bitget" => exchanges::bitget::parse_funding_rate,
         "bitmex" => exchanges::bitmex::parse_funding_rate,
+        "deribit" => exchanges::deribit::parse_funding_rate,
         "huobi" => exchanges::huobi::parse_funding_rate,
-        "okex" => exchanges::okex::parse_funding_rate,
-        _ => panic!("{} does NOT have perpetual swap market", exchange),
+        _ => return Err(ParseError::UnsupportedExchange(exchange.to_string())),
+    };
+    func(market_type, msg)
+}
+
+/// Parse mark price messages, e.g., binance `markPriceUpdate`.
+pub fn parse_mark_price(
+    exchange: &str,
+    market_type: MarketType,
+    msg: &str,
+) -> Result<Vec<MarkPriceMsg>> {
+    call_registered(
+        exchange,
+        exchanges::lookup(exchange).and_then(|parser| parser.parse_mark_price),
+        market_type,
+        msg,
+    )
+}
+
+/// Parse index price messages, e.g., okex `index/ticker`, binance `@indexPrice` and
+/// bitmex `.BXBT`.
+///
+/// Binance `markPriceUpdate` messages carry the index price as well.
+pub fn parse_index_price(
+    exchange: &str,
+    market_type: MarketType,
+    msg: &str,
+) -> Result<Vec<IndexPriceMsg>> {
+    if let Some(parser) = exchanges::lookup(exchange) {
+        return call_registered(exchange, parser.parse_index_price, market_type, msg);
+    }
+    let func = match exchange {
+        "bitmex" => exchanges::bitmex::parse_index_price,
+        _ => return Err(ParseError::UnsupportedExchange(exchange.to_string())),
     };
     func(market_type, msg)
 }
+
+/// Parse liquidation messages, e.g., binance `forceOrder` and bitmex `liquidation`.
+pub fn parse_liquidation(
+    exchange: &str,
+    market_type: MarketType,
+    msg: &str,
+) -> Result<Vec<LiquidationMsg>> {
+    if let Some(parser) = exchanges::lookup(exchange) {
+        return call_registered(exchange, parser.parse_liquidation, market_type, msg);
+    }
+    match exchange {
+        "bitmex" => exchanges::bitmex::parse_liquidation(market_type, msg),
+        _ => Err(ParseError::UnsupportedExchange(exchange.to_string())),
+    }
+}
+
+/// Parse open interest messages fetched by RESTful APIs.
+pub fn parse_open_interest(
+    exchange: &str,
+    market_type: MarketType,
+    msg: &str,
+) -> Result<Vec<OpenInterestMsg>> {
+    if let Some(parser) = exchanges::lookup(exchange) {
+        return call_registered(exchange, parser.parse_open_interest, market_type, msg);
+    }
+    match exchange {
+        "bitmex" => exchanges::bitmex::parse_open_interest(market_type, msg),
+        _ => Err(ParseError::UnsupportedExchange(exchange.to_string())),
+    }
+}
+
+/// Parse candlesticks fetched by RESTful APIs.
+///
+/// RESTful responses contain neither the symbol nor the interval, so they
+/// have to be passed in, `interval` is in seconds. The `timestamp` of each
+/// candlestick is its open time.
+pub fn parse_candlestick_history(
+    exchange: &str,
+    market_type: MarketType,
+    symbol: &str,
+    interval: usize,
+    msg: &str,
+) -> Result<Vec<KlineMsg>> {
+    match exchanges::lookup(exchange).and_then(|parser| parser.parse_candlestick_history) {
+        Some(func) => func(market_type, symbol, interval, msg),
+        None => Err(ParseError::UnsupportedExchange(exchange.to_string())),
+    }
+}
+
+// Calls a function registered by the exchange, None means the exchange
+// doesn't support this message type
+fn call_registered<T>(
+    exchange: &str,
+    func: Option<exchanges::ParseFn<T>>,
+    market_type: MarketType,
+    msg: &str,
+) -> Result<Vec<T>> {
+    match func {
+        Some(func) => func(market_type, msg),
+        None => Err(ParseError::UnsupportedExchange(exchange.to_string())),
+    }
+}
@@ -1,29 +1,10 @@
-use crypto_market_type::MarketType;
+use crypto_market_type::{MarketType, MessageType};
 use serde::{Deserialize, Serialize};
-use strum_macros::{Display, EnumString};
+use std::str::FromStr;
+use strum_macros::Display;
 
 use crate::order::Order;
 
-/// The type of a message
-///
-/// Copied from crypto_crawler::MessageType
-#[derive(Copy, Clone, PartialEq, Serialize, Deserialize, Display, Debug, EnumString)]
-#[serde(rename_all = "snake_case")]
-#[strum(serialize_all = "snake_case")]
-pub enum MessageType {
-    Trade,
-    L2Event,
-    L2Snapshot,
-    L3Event,
-    L3Snapshot,
-    #[serde(rename = "bbo")]
-    #[allow(clippy::upper_case_acronyms)]
-    BBO,
-    Ticker,
-    Candlestick,
-    FundingRate,
-}
-
 macro_rules! add_common_fields {
     (
         $(#[$outer:meta])*
@@ -66,7 +47,7 @@ add_common_fields!(
 );
 
 /// Which side is taker
-#[derive(Copy, Clone, PartialEq, Serialize, Deserialize, Display, Debug, EnumString)]
+#[derive(Copy, Clone, PartialEq, Serialize, Deserialize, Display, Debug)]
 #[serde(rename_all = "snake_case")]
 #[strum(serialize_all = "snake_case")]
 pub enum TradeSide {
@@ -76,6 +57,20 @@ pub enum TradeSide {
     Sell,
 }
 
+impl FromStr for TradeSide {
+    type Err = strum::ParseError;
+
+    /// Parses sides emitted by exchanges, i.e., `buy`, `sell`, `b` and `s`,
+    /// case-insensitively.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "buy" | "b" => Ok(TradeSide::Buy),
+            "sell" | "s" => Ok(TradeSide::Sell),
+            _ => Err(strum::ParseError::VariantNotFound),
+        }
+    }
+}
+
 /// Realtime trade message.
 #[derive(Serialize, Deserialize)]
 pub struct TradeMsg {
@@ -91,6 +86,10 @@ pub struct TradeMsg {
     pub msg_type: MessageType,
     /// Unix timestamp, in milliseconds
     pub timestamp: i64,
+    /// Unix timestamp in microseconds, only for exchanges with sub-millisecond
+    /// timestamps, i.e., bitstamp, coinbase_pro and kucoin
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timestamp_us: Option<i64>,
 
     /// price
     pub price: f64,
@@ -110,7 +109,7 @@ pub struct TradeMsg {
 }
 
 /// Level2 orderbook message.
-#[derive(Serialize, Deserialize)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct OrderBookMsg {
     /// The exchange name, unique for each exchage
     pub exchange: String,
@@ -124,9 +123,15 @@ pub struct OrderBookMsg {
     pub msg_type: MessageType,
     /// Unix timestamp, in milliseconds
     pub timestamp: i64,
-    /// The sequence ID for this update (not all exchanges provide this information)
+    /// Unix timestamp in microseconds, only for exchanges with sub-millisecond
+    /// timestamps, i.e., bitstamp, coinbase_pro and kucoin
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timestamp_us: Option<i64>,
+    /// The sequence ID for this update, i.e., the last update ID it contains (not all exchanges provide this information)
     pub seq_id: Option<u64>,
-    /// The sequence ID for the previous update (not all exchanges provide this information)
+    /// The `seq_id` of the previous update if no update is missing in between (not all exchanges provide this information)
+    ///
+    /// See `SeqGapDetector`.
     pub prev_seq_id: Option<u64>,
 
     /// sorted in ascending order by price if snapshot=true, otherwise not sorted
@@ -140,6 +145,30 @@ pub struct OrderBookMsg {
     pub json: String,
 }
 
+impl OrderBookMsg {
+    /// Returns true if the best bid is at or above the best ask.
+    ///
+    /// A crossed snapshot or maintained book indicates a parsing bug or a
+    /// dropped update. Levels with 0 quantity are ignored, and a book with an
+    /// empty side is never crossed. Incremental updates contain only changed
+    /// levels, so the result is only meaningful for whole books.
+    pub fn is_crossed(&self) -> bool {
+        let best_bid = self
+            .bids
+            .iter()
+            .filter(|order| order.quantity_base > 0.0)
+            .map(|order| order.price)
+            .fold(f64::NEG_INFINITY, f64::max);
+        let best_ask = self
+            .asks
+            .iter()
+            .filter(|order| order.quantity_base > 0.0)
+            .map(|order| order.price)
+            .fold(f64::INFINITY, f64::min);
+        best_bid >= best_ask
+    }
+}
+
 /// Funding rate message.
 #[derive(Serialize, Deserialize)]
 pub struct FundingRateMsg {
@@ -163,6 +192,9 @@ pub struct FundingRateMsg {
     // Estimated funding rate between [funding_time-h, funding_time], it will be static after funding_time
     #[serde(skip_serializing_if = "Option::is_none")]
     pub estimated_rate: Option<f64>,
+    /// Mark price, available if the funding rate is carried by a ticker or mark price message
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mark_price: Option<f64>,
     /// the original JSON message
     pub json: String,
 }
@@ -178,6 +210,8 @@ add_common_fields!(
         volume: f64,
 
         quote_volume: f64,
+        /// Price change percent of the window, e.g., binance `P`
+        price_change_percent: Option<f64>,
 
         last_quantity: Option<f64>,
 
@@ -194,12 +228,109 @@ add_common_fields!(
 );
 
 add_common_fields!(
+    /// BBO(best bid & offer) message.
     #[derive(Serialize, Deserialize)]
     struct BboMsg {
+        /// best bid price
         bid_price: f64,
+        /// Number of base coins at the best bid
         bid_quantity: f64,
+        /// best ask price
         ask_price: f64,
+        /// Number of base coins at the best ask
         ask_quantity: f64,
+        /// True if derived from a level2 snapshot rather than received from a BBO channel
+        #[serde(default)]
+        synthetic: bool,
+    }
+);
+
+impl BboMsg {
+    /// Derives a synthetic BBO message from the best levels of a level2 snapshot.
+    ///
+    /// Returns `None` if `orderbook` is an incremental update, which doesn't
+    /// carry the whole top of book, or if either side is empty.
+    /// `json` is the original JSON message of `orderbook`.
+    pub fn from_snapshot(orderbook: &OrderBookMsg) -> Option<Self> {
+        if !orderbook.snapshot {
+            return None;
+        }
+        let best_bid = orderbook
+            .bids
+            .iter()
+            .max_by(|a, b| a.price.total_cmp(&b.price))?;
+        let best_ask = orderbook
+            .asks
+            .iter()
+            .min_by(|a, b| a.price.total_cmp(&b.price))?;
+        Some(BboMsg {
+            exchange: orderbook.exchange.clone(),
+            market_type: orderbook.market_type,
+            symbol: orderbook.symbol.clone(),
+            pair: orderbook.pair.clone(),
+            msg_type: MessageType::BBO,
+            timestamp: orderbook.timestamp,
+            json: orderbook.json.clone(),
+            bid_price: best_bid.price,
+            bid_quantity: best_bid.quantity_base,
+            ask_price: best_ask.price,
+            ask_quantity: best_ask.quantity_base,
+            synthetic: true,
+        })
+    }
+}
+
+add_common_fields!(
+    /// Open interest message, polled from RESTful APIs.
+    #[derive(Serialize, Deserialize)]
+    struct OpenInterestMsg {
+        /// Number of outstanding contracts, which is the number of base coins for spot-like contracts such as binance linear markets
+        open_interest: f64,
+        /// Notional value of outstanding contracts in quote currency, only available in inverse markets
+        open_interest_value: Option<f64>,
+    }
+);
+
+add_common_fields!(
+    /// Liquidation message, i.e., a forced order placed by the liquidation engine.
+    #[derive(Serialize, Deserialize)]
+    struct LiquidationMsg {
+        /// Side of the liquidation order, `Sell` means a long position was liquidated
+        side: TradeSide,
+        /// Liquidation price
+        price: f64,
+        /// Number of base coins
+        quantity_base: f64,
+        /// Number of quote coins(mostly USDT)
+        quantity_quote: f64,
+        /// Number of contracts
+        quantity_contract: Option<f64>,
+    }
+);
+
+add_common_fields!(
+    /// Mark price message, e.g., binance `markPriceUpdate`.
+    #[derive(Serialize, Deserialize)]
+    struct MarkPriceMsg {
+        /// Mark price, which is used to calculate unrealized PnL and liquidations
+        mark_price: f64,
+        /// Index price, see [`MessageType::IndexPrice`]
+        index_price: Option<f64>,
+        /// Estimated settle price, only useful in the last hour before the settlement starts
+        estimated_settle_price: Option<f64>,
+    }
+);
+
+add_common_fields!(
+    /// Index price message, e.g., okex `index/ticker`, binance `indexPriceUpdate`
+    /// and bitmex `.BXBT`.
+    ///
+    /// `symbol` is the index, which may differ from trading symbols, e.g.,
+    /// binance `BTCUSD` is the index of `BTCUSD_PERP` and `BTCUSD_210625`.
+    #[derive(Serialize, Deserialize)]
+    struct IndexPriceMsg {
+        /// Index price, see [`MessageType::IndexPrice`]
+        index_price: f64,
     }
 );
 
@@ -218,3 +349,29 @@ add_common_fields!(
         quote_volume: Option<f64>,
     }
 );
+
+#[cfg(test)]
+mod tests {
+    use super::TradeSide;
+    use std::str::FromStr;
+
+    #[test]
+    fn trade_side_from_str() {
+        let cases = [
+            ("buy", TradeSide::Buy),   // okex, coinbase_pro, kucoin, huobi, deribit, ftx
+            ("sell", TradeSide::Sell), // okex, coinbase_pro, kucoin, huobi, deribit, ftx
+            ("Buy", TradeSide::Buy),   // bitmex, bybit
+            ("Sell", TradeSide::Sell), // bitmex, bybit
+            ("BUY", TradeSide::Buy),   // dydx, binance
+            ("SELL", TradeSide::Sell), // dydx, binance
+            ("b", TradeSide::Buy),     // kraken
+            ("s", TradeSide::Sell),    // kraken
+        ];
+        for (text, side) in cases.iter() {
+            assert_eq!(*side, TradeSide::from_str(text).unwrap());
+        }
+
+        assert!(TradeSide::from_str("").is_err());
+        assert!(TradeSide::from_str("ask").is_err());
+    }
+}
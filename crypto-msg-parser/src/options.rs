@@ -0,0 +1,58 @@
+/// Selects which fields are populated, see `parse_trade_with_options()`.
+///
+/// The default populates all fields, which is what `parse_trade()` and
+/// `parse_l2()` do.
+///
+/// ## Example
+///
+/// ```
+/// use crypto_msg_parser::{parse_trade_with_options, MarketType, ParseOptions};
+///
+/// let options = ParseOptions::new().keep_json(false);
+/// let msg = r#"{"table":"spot/trade","data":[{"side":"sell","trade_id":"161659503","price":"56000.1","size":"0.00020621","instrument_id":"BTC-USDT","timestamp":"2021-03-22T01:16:28.687Z"}]}"#;
+/// let trades = parse_trade_with_options("okex", MarketType::Spot, msg, &options).unwrap();
+/// assert!(trades[0].json.is_empty());
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct ParseOptions {
+    /// Fill in `quantity_quote`, otherwise it is 0
+    pub compute_quote: bool,
+    /// Fill in `pair`, otherwise it is empty
+    pub normalize_pair: bool,
+    /// Keep the original message in `json`, otherwise it is empty
+    pub keep_json: bool,
+}
+
+impl Default for ParseOptions {
+    fn default() -> Self {
+        ParseOptions {
+            compute_quote: true,
+            normalize_pair: true,
+            keep_json: true,
+        }
+    }
+}
+
+impl ParseOptions {
+    /// Creates options populating all fields.
+    pub fn new() -> Self {
+        ParseOptions::default()
+    }
+
+    pub fn compute_quote(mut self, compute_quote: bool) -> Self {
+        self.compute_quote = compute_quote;
+        self
+    }
+
+    pub fn normalize_pair(mut self, normalize_pair: bool) -> Self {
+        self.normalize_pair = normalize_pair;
+        self
+    }
+
+    /// Dropping `json` saves serializing or copying the message, which is
+    /// the most expensive part of parsing small messages.
+    pub fn keep_json(mut self, keep_json: bool) -> Self {
+        self.keep_json = keep_json;
+        self
+    }
+}
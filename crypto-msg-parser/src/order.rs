@@ -2,7 +2,24 @@ use serde::de::{Deserializer, SeqAccess, Visitor};
 use serde::ser::{SerializeSeq, Serializer};
 use serde::{Deserialize, Serialize};
 
+/// How a level3 order changes the orderbook.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OrderUpdateType {
+    /// A new order is placed
+    Add,
+    /// The quantity of an existing order changed, e.g., partially filled
+    Update,
+    /// The order is filled or canceled
+    Delete,
+}
+
 /// An order in the orderbook asks or bids array.
+///
+/// Level2 orders are serialized as `[price, quantity_base, quantity_quote]`,
+/// followed by `quantity_contract` if present. Level3 orders append
+/// `order_id` and `order_type` as strings, with `null` for a missing
+/// `order_id` if only `order_type` is present.
 #[derive(Clone)]
 pub struct Order {
     /// price
@@ -13,6 +30,10 @@ pub struct Order {
     pub quantity_quote: f64,
     /// Number of contracts, always None for Spot
     pub quantity_contract: Option<f64>,
+    /// Exchange-specific order ID, only available in level3 orderbooks
+    pub order_id: Option<String>,
+    /// Add, update or delete, only available in level3 orderbooks
+    pub order_type: Option<OrderUpdateType>,
 }
 
 impl Serialize for Order {
@@ -20,11 +41,13 @@ impl Serialize for Order {
     where
         S: Serializer,
     {
-        let len: usize = if self.quantity_contract.is_some() {
-            4
-        } else {
-            3
-        };
+        let len: usize = 3
+            + self.quantity_contract.is_some() as usize
+            + if self.order_type.is_some() {
+                2
+            } else {
+                self.order_id.is_some() as usize
+            };
         let mut seq = serializer.serialize_seq(Some(len))?;
         seq.serialize_element(&self.price)?;
         seq.serialize_element(&self.quantity_base)?;
@@ -32,11 +55,25 @@ impl Serialize for Order {
         if let Some(qc) = self.quantity_contract {
             seq.serialize_element(&qc)?;
         }
+        if self.order_id.is_some() || self.order_type.is_some() {
+            seq.serialize_element(&self.order_id)?;
+        }
+        if let Some(order_type) = self.order_type {
+            seq.serialize_element(&order_type)?;
+        }
 
         seq.end()
     }
 }
 
+// Level2 orders only have numbers, level3 orders have trailing strings
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum OrderElement {
+    Number(f64),
+    Text(Option<String>),
+}
+
 struct OrderVisitor;
 
 impl<'de> Visitor<'de> for OrderVisitor {
@@ -51,16 +88,34 @@ impl<'de> Visitor<'de> for OrderVisitor {
         V: SeqAccess<'de>,
     {
         let mut vec = Vec::<f64>::new();
+        let mut texts = Vec::<Option<String>>::new();
 
-        while let Some(elem) = visitor.next_element()? {
-            vec.push(elem);
+        while let Some(elem) = visitor.next_element::<OrderElement>()? {
+            match elem {
+                OrderElement::Number(x) => vec.push(x),
+                OrderElement::Text(x) => texts.push(x),
+            }
+        }
+        if vec.len() < 3 {
+            return Err(serde::de::Error::invalid_length(vec.len(), &self));
         }
+        let mut texts = texts.into_iter();
+        let order_id = texts.next().flatten();
+        let order_type = match texts.next().flatten() {
+            Some(x) => Some(
+                serde_json::from_value::<OrderUpdateType>(serde_json::Value::String(x))
+                    .map_err(serde::de::Error::custom)?,
+            ),
+            None => None,
+        };
 
         let order = Order {
             price: vec[0],
             quantity_base: vec[1],
             quantity_quote: vec[2],
             quantity_contract: if vec.len() == 4 { Some(vec[3]) } else { None },
+            order_id,
+            order_type,
         };
 
         Ok(order)
@@ -78,7 +133,7 @@ impl<'de> Deserialize<'de> for Order {
 
 #[cfg(test)]
 mod tests {
-    use crate::order::Order;
+    use crate::order::{Order, OrderUpdateType};
 
     #[test]
     fn order_serialize() {
@@ -87,11 +142,50 @@ mod tests {
             quantity_base: 1.7,
             quantity_quote: 59999.8 * 1.7,
             quantity_contract: Some(1.7),
+            order_id: None,
+            order_type: None,
         };
         let text = serde_json::to_string(&order).unwrap();
         assert_eq!(text.as_str(), "[59999.8,1.7,101999.66,1.7]");
     }
 
+    #[test]
+    fn l3_order_serde() {
+        let order = Order {
+            price: 59999.8,
+            quantity_base: 1.7,
+            quantity_quote: 59999.8 * 1.7,
+            quantity_contract: None,
+            order_id: Some("d50ec984-77a8-460a-b958-66f114b0de9b".to_string()),
+            order_type: Some(OrderUpdateType::Add),
+        };
+        let text = serde_json::to_string(&order).unwrap();
+        assert_eq!(
+            text.as_str(),
+            r#"[59999.8,1.7,101999.66,"d50ec984-77a8-460a-b958-66f114b0de9b","add"]"#
+        );
+        let actual = serde_json::from_str::<Order>(&text).unwrap();
+        assert_eq!(actual.quantity_contract, None);
+        assert_eq!(actual.order_id, order.order_id);
+        assert_eq!(actual.order_type, Some(OrderUpdateType::Add));
+
+        let order = Order {
+            order_id: None,
+            order_type: Some(OrderUpdateType::Delete),
+            quantity_contract: Some(1.7),
+            ..order
+        };
+        let text = serde_json::to_string(&order).unwrap();
+        assert_eq!(
+            text.as_str(),
+            r#"[59999.8,1.7,101999.66,1.7,null,"delete"]"#
+        );
+        let actual = serde_json::from_str::<Order>(&text).unwrap();
+        assert_eq!(actual.quantity_contract, Some(1.7));
+        assert_eq!(actual.order_id, None);
+        assert_eq!(actual.order_type, Some(OrderUpdateType::Delete));
+    }
+
     #[test]
     fn order_deserialize() {
         let expected = Order {
@@ -99,11 +193,15 @@ mod tests {
             quantity_base: 1.7,
             quantity_quote: 59999.8 * 1.7,
             quantity_contract: Some(1.7),
+            order_id: None,
+            order_type: None,
         };
         let actual = serde_json::from_str::<Order>("[59999.8,1.7,101999.66,1.7]").unwrap();
         assert_eq!(expected.price, actual.price);
         assert_eq!(expected.quantity_base, actual.quantity_base);
         assert_eq!(expected.quantity_quote, actual.quantity_quote);
         assert_eq!(expected.quantity_contract, actual.quantity_contract);
+        assert_eq!(expected.order_id, actual.order_id);
+        assert_eq!(expected.order_type, actual.order_type);
     }
 }
@@ -0,0 +1,64 @@
+use crate::{MarketType, OrderBookMsg};
+use std::collections::HashMap;
+
+/// Detects missing level2 updates by checking `seq_id` and `prev_seq_id`.
+///
+/// An update is contiguous if its `prev_seq_id` is not greater than the
+/// `seq_id` of the last accepted message. Updates overlapping with the last
+/// accepted message are allowed, which is what Binance does right after a
+/// snapshot, i.e., `U <= lastUpdateId + 1 <= u`.
+///
+/// Once a gap is detected, the orderbook of the symbol should be rebuilt
+/// from a fresh snapshot.
+#[derive(Default)]
+pub struct SeqGapDetector {
+    // the seq_id of the last accepted message of each symbol
+    last_seq_ids: HashMap<(String, MarketType, String), u64>,
+}
+
+impl SeqGapDetector {
+    pub fn new() -> Self {
+        SeqGapDetector::default()
+    }
+
+    /// Checks a message and returns true if some updates before it are missing.
+    ///
+    /// Snapshots always reset the sequence of the symbol. Messages without
+    /// `seq_id` can't be checked and never report a gap, and neither do
+    /// stale messages whose `seq_id` is not greater than the last one.
+    pub fn check(&mut self, msg: &OrderBookMsg) -> bool {
+        let seq_id = match msg.seq_id {
+            Some(seq_id) => seq_id,
+            None => return false,
+        };
+        let key = (msg.exchange.clone(), msg.market_type, msg.symbol.clone());
+        if msg.snapshot {
+            self.last_seq_ids.insert(key, seq_id);
+            return false;
+        }
+        match self.last_seq_ids.get_mut(&key) {
+            Some(last_seq_id) => {
+                if seq_id <= *last_seq_id {
+                    // stale message, already covered by the last one
+                    return false;
+                }
+                let gap = match msg.prev_seq_id {
+                    Some(prev_seq_id) => prev_seq_id > *last_seq_id,
+                    None => false,
+                };
+                *last_seq_id = seq_id;
+                gap
+            }
+            None => {
+                self.last_seq_ids.insert(key, seq_id);
+                false
+            }
+        }
+    }
+
+    /// Forgets the sequence of a symbol, e.g., after reconnecting.
+    pub fn reset(&mut self, exchange: &str, market_type: MarketType, symbol: &str) {
+        self.last_seq_ids
+            .remove(&(exchange.to_string(), market_type, symbol.to_string()));
+    }
+}
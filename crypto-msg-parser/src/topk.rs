@@ -0,0 +1,111 @@
+use crate::{MarketType, OrderBookMsg};
+use std::collections::HashMap;
+
+/// Keeps the latest level2 top-k snapshot of each symbol and emits them at a throttled rate.
+///
+/// Top-k snapshot channels, such as OKEx `depth5`, Bybit `orderBookL2_25` and
+/// Bitstamp `order_book`, push full replacements, so the latest snapshot is
+/// always a complete top-k view and no orderbook reconstruction is needed.
+///
+/// Throttling is based on the `timestamp` of snapshots, so the same input
+/// always produces the same output, no matter it is realtime or replayed.
+pub struct TopKTracker {
+    depth: usize,
+    interval: i64,
+    books: HashMap<(String, MarketType, String), TopKEntry>,
+}
+
+struct TopKEntry {
+    latest: OrderBookMsg,
+    // timestamp of the last emitted snapshot
+    last_emitted: Option<i64>,
+    // true if latest has not been emitted yet
+    pending: bool,
+}
+
+impl TopKTracker {
+    /// Creates a tracker.
+    ///
+    /// # Arguments
+    ///
+    /// * `depth` - Keep at most `depth` levels on each side
+    /// * `interval` - Emit at most one snapshot per symbol every `interval` milliseconds
+    pub fn new(depth: usize, interval: u64) -> Self {
+        TopKTracker {
+            depth,
+            interval: interval as i64,
+            books: HashMap::new(),
+        }
+    }
+
+    /// Updates the latest snapshot of the symbol.
+    ///
+    /// Returns the truncated snapshot if at least `interval` milliseconds have
+    /// passed since the last emission of this symbol, otherwise `None`.
+    ///
+    /// Incremental updates, i.e., `snapshot == false`, are ignored, so are
+    /// levels with a NaN price.
+    pub fn update(&mut self, mut msg: OrderBookMsg) -> Option<OrderBookMsg> {
+        if !msg.snapshot {
+            return None;
+        }
+        // a NaN level would sort to the top of bids
+        msg.asks.retain(|order| !order.price.is_nan());
+        msg.bids.retain(|order| !order.price.is_nan());
+        msg.asks.sort_by(|a, b| a.price.total_cmp(&b.price));
+        msg.bids.sort_by(|a, b| b.price.total_cmp(&a.price));
+        msg.asks.truncate(self.depth);
+        msg.bids.truncate(self.depth);
+
+        let key = (msg.exchange.clone(), msg.market_type, msg.symbol.clone());
+        let timestamp = msg.timestamp;
+        let entry = self.books.entry(key).or_insert_with(|| TopKEntry {
+            latest: msg.clone(),
+            last_emitted: None,
+            pending: true,
+        });
+        if timestamp < entry.latest.timestamp {
+            // out-of-order snapshot, older than what we have
+            return None;
+        }
+        entry.latest = msg;
+        entry.pending = true;
+
+        let due = match entry.last_emitted {
+            Some(last_emitted) => timestamp - last_emitted >= self.interval,
+            None => true,
+        };
+        if due {
+            entry.last_emitted = Some(timestamp);
+            entry.pending = false;
+            Some(entry.latest.clone())
+        } else {
+            None
+        }
+    }
+
+    /// Returns the latest snapshot of the symbol, emitted or not.
+    pub fn latest(
+        &self,
+        exchange: &str,
+        market_type: MarketType,
+        symbol: &str,
+    ) -> Option<&OrderBookMsg> {
+        self.books
+            .get(&(exchange.to_string(), market_type, symbol.to_string()))
+            .map(|entry| &entry.latest)
+    }
+
+    /// Emits all snapshots which were throttled and have not been emitted yet.
+    pub fn flush(&mut self) -> Vec<OrderBookMsg> {
+        self.books
+            .values_mut()
+            .filter(|entry| entry.pending)
+            .map(|entry| {
+                entry.last_emitted = Some(entry.latest.timestamp);
+                entry.pending = false;
+                entry.latest.clone()
+            })
+            .collect()
+    }
+}
@@ -21,6 +21,44 @@ mod trade {
         assert_eq!(trade.quantity_quote, 0.00035600 * 58942.01);
         assert_eq!(trade.quantity_contract, None);
         assert_eq!(trade.side, TradeSide::Buy);
+        assert_eq!(trade.trade_id, "640283266");
+    }
+
+    #[test]
+    fn spot_trade() {
+        let raw_msg = r#"{"stream":"btcusdt@trade","data":{"e":"trade","E":1616176861895,"s":"BTCUSDT","t":716849523,"p":"58942.01000000","q":"0.00035600","b":5530297405,"a":5530297420,"T":1616176861893,"m":true,"M":true}}"#;
+        let trade = &parse_trade("binance", MarketType::Spot, raw_msg).unwrap()[0];
+
+        crate::utils::check_trade_fields(
+            "binance",
+            MarketType::Spot,
+            "BTC/USDT".to_string(),
+            extract_symbol("binance", MarketType::Spot, raw_msg).unwrap(),
+            trade,
+        );
+
+        assert_eq!(trade.timestamp, 1616176861893);
+        assert_eq!(trade.price, 58942.01);
+        assert_eq!(trade.quantity_base, 0.00035600);
+        assert_eq!(trade.side, TradeSide::Sell);
+        assert_eq!(trade.trade_id, "716849523");
+    }
+
+    #[test]
+    fn spot_raw_stream() {
+        let agg_trade = r#"{"e":"aggTrade","E":1616176861895,"s":"BTCUSDT","a":640283266,"p":"58942.01000000","q":"0.00035600","f":716849523,"l":716849523,"T":1616176861893,"m":false,"M":true}"#;
+        let trade = &parse_trade("binance", MarketType::Spot, agg_trade).unwrap()[0];
+        assert_eq!(
+            "BTCUSDT",
+            extract_symbol("binance", MarketType::Spot, agg_trade).unwrap()
+        );
+        assert_eq!(trade.side, TradeSide::Buy);
+        assert_eq!(trade.trade_id, "640283266");
+
+        let raw_trade = r#"{"e":"trade","E":1616176861895,"s":"BTCUSDT","t":716849523,"p":"58942.01000000","q":"0.00035600","b":5530297405,"a":5530297420,"T":1616176861893,"m":false,"M":true}"#;
+        let trade = &parse_trade("binance", MarketType::Spot, raw_trade).unwrap()[0];
+        assert_eq!(trade.side, TradeSide::Buy);
+        assert_eq!(trade.trade_id, "716849523");
     }
 
     #[test]
@@ -125,6 +163,30 @@ mod trade {
         assert_eq!(trades[0].quantity_quote, 0.0001 * 4842.24);
         assert_eq!(trades[0].quantity_contract, Some(0.0001));
     }
+    #[test]
+    fn european_option() {
+        let raw_msg = r#"{"stream":"BTC-220930-18000-C@trade","data":{"e":"trade","E":1661400364102,"s":"BTC-220930-18000-C","t":"315","p":"4842.2","q":"0.2","b":4612047757752932782,"a":4612057653433061439,"T":1661400364092,"S":"-1","X":"TAKER"}}"#;
+        let trades = &parse_trade("binance", MarketType::EuropeanOption, raw_msg).unwrap();
+
+        assert_eq!(trades.len(), 1);
+        let trade = &trades[0];
+
+        crate::utils::check_trade_fields(
+            "binance",
+            MarketType::EuropeanOption,
+            "BTC/USDT".to_string(),
+            extract_symbol("binance", MarketType::EuropeanOption, raw_msg).unwrap(),
+            trade,
+        );
+
+        assert_eq!(trade.timestamp, 1661400364092);
+        assert_eq!(trade.trade_id, "315");
+        assert_eq!(trade.price, 4842.2);
+        assert_eq!(trade.quantity_base, 0.2);
+        assert_eq!(trade.quantity_quote, 0.2 * 4842.2);
+        assert_eq!(trade.quantity_contract, Some(0.2));
+        assert_eq!(trade.side, TradeSide::Sell);
+    }
 }
 
 #[cfg(test)]
@@ -181,6 +243,8 @@ mod funding_rate {
         assert_eq!(funding_rates[0].pair, "BTC/USDT".to_string());
         assert_eq!(funding_rates[0].funding_rate, 0.00058455);
         assert_eq!(funding_rates[0].funding_time, 1617321600000);
+        assert_eq!(funding_rates[0].mark_price, Some(58940.14924532));
+        assert_eq!(funding_rates[0].timestamp, 1617308820003);
 
         let raw_msg = r#"{"stream":"!markPrice@arr","data":[{"e":"markPriceUpdate","E":1617309024002,"s":"BTCUSDT","p":"59022.53514719","P":"58902.34482833","i":"58936.68384000","r":"0.00058959","T":1617321600000},{"e":"markPriceUpdate","E":1617309024002,"s":"ETHUSDT","p":"1981.15704420","P":"1974.79557094","i":"1978.08197502","r":"0.00059142","T":1617321600000}]}"#;
         let funding_rates =
@@ -202,6 +266,98 @@ mod funding_rate {
     }
 }
 
+#[cfg(test)]
+mod mark_price {
+    use crypto_msg_parser::{parse_funding_rate, parse_mark_price, MarketType, MessageType};
+
+    #[test]
+    fn linear_swap() {
+        let raw_msg = r#"{"stream":"btcusdt@markPrice","data":{"e":"markPriceUpdate","E":1617308820003,"s":"BTCUSDT","p":"58940.14924532","P":"58905.14663658","i":"58857.26693664","r":"0.00058455","T":1617321600000}}"#;
+        let mark_prices = &parse_mark_price("binance", MarketType::LinearSwap, raw_msg).unwrap();
+
+        assert_eq!(mark_prices.len(), 1);
+        let mark_price = &mark_prices[0];
+        assert_eq!(mark_price.exchange, "binance");
+        assert_eq!(mark_price.market_type, MarketType::LinearSwap);
+        assert_eq!(mark_price.msg_type, MessageType::MarkPrice);
+        assert_eq!(mark_price.symbol, "BTCUSDT");
+        assert_eq!(mark_price.pair, "BTC/USDT");
+        assert_eq!(mark_price.timestamp, 1617308820003);
+        assert_eq!(mark_price.mark_price, 58940.14924532);
+        assert_eq!(mark_price.index_price, Some(58857.26693664));
+        assert_eq!(mark_price.estimated_settle_price, Some(58905.14663658));
+        assert_eq!(mark_price.json, raw_msg);
+
+        // the same message carries the funding rate
+        let funding_rates =
+            &parse_funding_rate("binance", MarketType::LinearSwap, raw_msg).unwrap();
+        assert_eq!(funding_rates.len(), 1);
+        crate::utils::check_funding_rate_fields(
+            "binance",
+            MarketType::LinearSwap,
+            &funding_rates[0],
+        );
+        assert_eq!(funding_rates[0].funding_rate, 0.00058455);
+        assert_eq!(funding_rates[0].funding_time, 1617321600000);
+        assert_eq!(funding_rates[0].mark_price, Some(58940.14924532));
+    }
+
+    #[test]
+    fn inverse_swap_all() {
+        let raw_msg = r#"{"stream":"!markPrice@arr","data":[{"e":"markPriceUpdate","E":1617309501002,"s":"BTCUSD_PERP","p":"59003.37984561","P":"58896.41602208","r":"0.00073684","T":1617321600000},{"e":"markPriceUpdate","E":1617309501002,"s":"ETHUSD_PERP","p":"1981.89000000","P":"1975.18948029","r":"0.00100944","T":1617321600000}]}"#;
+        let mark_prices = &parse_mark_price("binance", MarketType::InverseSwap, raw_msg).unwrap();
+
+        assert_eq!(mark_prices.len(), 2);
+        assert_eq!(mark_prices[0].pair, "BTC/USD");
+        assert_eq!(mark_prices[0].mark_price, 59003.37984561);
+        // inverse markets don't have the index price field
+        assert_eq!(mark_prices[0].index_price, None);
+        assert_eq!(mark_prices[1].pair, "ETH/USD");
+        assert_eq!(mark_prices[1].mark_price, 1981.89);
+    }
+
+    #[test]
+    fn spot_unsupported() {
+        let raw_msg = r#"{"stream":"btcusdt@markPrice","data":{"e":"markPriceUpdate","E":1617308820003,"s":"BTCUSDT","p":"58940.14924532","P":"58905.14663658","i":"58857.26693664","r":"0.00058455","T":1617321600000}}"#;
+        assert!(parse_mark_price("binance", MarketType::Spot, raw_msg).is_err());
+    }
+}
+
+#[cfg(test)]
+mod index_price {
+    use crypto_msg_parser::{parse_index_price, MarketType, MessageType};
+
+    #[test]
+    fn inverse_swap() {
+        let raw_msg = r#"{"stream":"btcusd@indexPrice","data":{"e":"indexPriceUpdate","E":1617309501002,"i":"BTCUSD","p":"58896.41602208"}}"#;
+        let index_prices = &parse_index_price("binance", MarketType::InverseSwap, raw_msg).unwrap();
+
+        assert_eq!(index_prices.len(), 1);
+        let index_price = &index_prices[0];
+        assert_eq!(index_price.exchange, "binance");
+        assert_eq!(index_price.market_type, MarketType::InverseSwap);
+        assert_eq!(index_price.msg_type, MessageType::IndexPrice);
+        assert_eq!(index_price.symbol, "BTCUSD");
+        assert_eq!(index_price.pair, "BTC/USD");
+        assert_eq!(index_price.timestamp, 1617309501002);
+        assert_eq!(index_price.index_price, 58896.41602208);
+        assert_eq!(index_price.json, raw_msg);
+    }
+
+    #[test]
+    fn linear_swap_mark_price() {
+        let raw_msg = r#"{"stream":"!markPrice@arr","data":[{"e":"markPriceUpdate","E":1617308820003,"s":"BTCUSDT","p":"58940.14924532","P":"58905.14663658","i":"58857.26693664","r":"0.00058455","T":1617321600000},{"e":"markPriceUpdate","E":1617308820003,"s":"ETHUSDT","p":"1981.89000000","P":"1975.18948029","r":"0.00100944","T":1617321600000}]}"#;
+        let index_prices = &parse_index_price("binance", MarketType::LinearSwap, raw_msg).unwrap();
+
+        // events without the i field are skipped
+        assert_eq!(index_prices.len(), 1);
+        assert_eq!(index_prices[0].symbol, "BTCUSDT");
+        assert_eq!(index_prices[0].pair, "BTC/USDT");
+        assert_eq!(index_prices[0].index_price, 58857.26693664);
+        assert_eq!(index_prices[0].json, raw_msg);
+    }
+}
+
 #[cfg(test)]
 mod l2_orderbook {
     use crypto_msg_parser::{extract_symbol, parse_l2, MarketType};
@@ -261,6 +417,8 @@ mod l2_orderbook {
         );
 
         assert_eq!(orderbook.timestamp, 1622368000234);
+        assert_eq!(orderbook.seq_id, Some(127531214406));
+        assert_eq!(orderbook.prev_seq_id, Some(127531213513));
 
         assert_eq!(orderbook.bids[0].price, 35943.8);
         assert_eq!(orderbook.bids[0].quantity_base, 6000.0 / 35943.8);
@@ -406,5 +564,346 @@ mod l2_orderbook {
     }
 
     #[test]
-    fn option() {}
+    fn option() {
+        let raw_msg = r#"{"stream":"BTC-220930-40000-C@depth10@100ms","data":{"e":"depth","E":1661400364102,"T":1661400364092,"s":"BTC-220930-40000-C","u":1528,"pu":1527,"b":[["5.0","12.5"],["4.5","20.0"]],"a":[["10.0","3.0"],["15.0","8.25"]]}}"#;
+        let orderbook = &parse_l2("binance", MarketType::EuropeanOption, raw_msg, None).unwrap()[0];
+
+        assert_eq!(orderbook.asks.len(), 2);
+        assert_eq!(orderbook.bids.len(), 2);
+        assert!(orderbook.snapshot);
+
+        crate::utils::check_orderbook_fields(
+            "binance",
+            MarketType::EuropeanOption,
+            "BTC/USDT".to_string(),
+            extract_symbol("binance", MarketType::EuropeanOption, raw_msg).unwrap(),
+            orderbook,
+        );
+
+        assert_eq!(orderbook.timestamp, 1661400364092);
+        assert_eq!(orderbook.seq_id, Some(1528));
+        assert_eq!(orderbook.prev_seq_id, Some(1527));
+
+        assert_eq!(orderbook.bids[0].price, 5.0);
+        assert_eq!(orderbook.bids[0].quantity_base, 12.5);
+        assert_eq!(orderbook.bids[0].quantity_quote, 5.0 * 12.5);
+        assert_eq!(orderbook.bids[0].quantity_contract, Some(12.5));
+
+        assert_eq!(orderbook.asks[1].price, 15.0);
+        assert_eq!(orderbook.asks[1].quantity_base, 8.25);
+        assert_eq!(orderbook.asks[1].quantity_contract, Some(8.25));
+    }
+}
+
+#[cfg(test)]
+mod bbo {
+    use crypto_msg_parser::{extract_symbol, parse_bbo, MarketType};
+
+    #[test]
+    fn spot() {
+        let raw_msg = r#"{"stream":"btcusdt@bookTicker","data":{"u":11964419009,"s":"BTCUSDT","b":"47186.47000000","B":"0.70730000","a":"47186.48000000","A":"0.12549000"}}"#;
+        assert!(parse_bbo("binance", MarketType::Spot, raw_msg, None).is_err());
+        let bbo = &parse_bbo("binance", MarketType::Spot, raw_msg, Some(1633161238000)).unwrap()[0];
+
+        crate::utils::check_bbo_fields(
+            "binance",
+            MarketType::Spot,
+            "BTC/USDT".to_string(),
+            extract_symbol("binance", MarketType::Spot, raw_msg).unwrap(),
+            bbo,
+        );
+
+        assert_eq!(bbo.timestamp, 1633161238000);
+        assert_eq!(bbo.bid_price, 47186.47);
+        assert_eq!(bbo.bid_quantity, 0.7073);
+        assert_eq!(bbo.ask_price, 47186.48);
+        assert_eq!(bbo.ask_quantity, 0.12549);
+    }
+
+    #[test]
+    fn linear_swap() {
+        let raw_msg = r#"{"stream":"btcusdt@bookTicker","data":{"e":"bookTicker","u":1003423862340,"s":"BTCUSDT","b":"47160.78","B":"2.494","a":"47160.79","A":"3.126","T":1633161238541,"E":1633161238546}}"#;
+        let bbo = &parse_bbo("binance", MarketType::LinearSwap, raw_msg, Some(0)).unwrap()[0];
+
+        crate::utils::check_bbo_fields(
+            "binance",
+            MarketType::LinearSwap,
+            "BTC/USDT".to_string(),
+            extract_symbol("binance", MarketType::LinearSwap, raw_msg).unwrap(),
+            bbo,
+        );
+
+        assert_eq!(bbo.timestamp, 1633161238541);
+        assert_eq!(bbo.bid_price, 47160.78);
+        assert_eq!(bbo.bid_quantity, 2.494);
+        assert_eq!(bbo.ask_price, 47160.79);
+        assert_eq!(bbo.ask_quantity, 3.126);
+    }
+}
+
+#[cfg(test)]
+mod open_interest {
+    use crypto_msg_parser::{parse_open_interest, MarketType, MessageType};
+
+    #[test]
+    fn inverse_swap() {
+        let raw_msg = r#"{"symbol":"BTCUSD_PERP","pair":"BTCUSD","openInterest":"3197684","contractType":"PERPETUAL","time":1626330013316}"#;
+        let open_interests =
+            &parse_open_interest("binance", MarketType::InverseSwap, raw_msg).unwrap();
+
+        assert_eq!(open_interests.len(), 1);
+        let open_interest = &open_interests[0];
+        assert_eq!(open_interest.exchange, "binance");
+        assert_eq!(open_interest.market_type, MarketType::InverseSwap);
+        assert_eq!(open_interest.msg_type, MessageType::OpenInterest);
+        assert_eq!(open_interest.symbol, "BTCUSD_PERP");
+        assert_eq!(open_interest.pair, "BTC/USD");
+        assert_eq!(open_interest.timestamp, 1626330013316);
+        assert_eq!(open_interest.open_interest, 3197684.0);
+        assert_eq!(open_interest.open_interest_value, Some(319768400.0));
+    }
+
+    #[test]
+    fn linear_swap() {
+        let raw_msg = r#"{"symbol":"BTCUSDT","openInterest":"27483.594","time":1626330013537}"#;
+        let open_interests =
+            &parse_open_interest("binance", MarketType::LinearSwap, raw_msg).unwrap();
+
+        assert_eq!(open_interests.len(), 1);
+        let open_interest = &open_interests[0];
+        assert_eq!(open_interest.market_type, MarketType::LinearSwap);
+        assert_eq!(open_interest.pair, "BTC/USDT");
+        assert_eq!(open_interest.timestamp, 1626330013537);
+        assert_eq!(open_interest.open_interest, 27483.594);
+        assert_eq!(open_interest.open_interest_value, None);
+    }
+
+    #[test]
+    fn malformed() {
+        let raw_msg = r#"{"symbol":"BTCUSDT","openInterest":"N/A","time":1626330013537}"#;
+        assert!(parse_open_interest("binance", MarketType::LinearSwap, raw_msg).is_err());
+
+        let raw_msg = r#"{"symbol":"","openInterest":"27483.594","time":1626330013537}"#;
+        assert!(parse_open_interest("binance", MarketType::LinearSwap, raw_msg).is_err());
+    }
+}
+
+#[cfg(test)]
+mod liquidation {
+    use crypto_msg_parser::{parse_liquidation, MarketType, MessageType, TradeSide};
+
+    #[test]
+    fn inverse_swap() {
+        let raw_msg = r#"{"stream":"!forceOrder@arr","data":{"e":"forceOrder","E":1626330013525,"o":{"s":"BTCUSD_PERP","ps":"BTCUSD","S":"BUY","o":"LIMIT","f":"IOC","q":"10","p":"32965.3","ap":"32816.5","X":"FILLED","l":"10","z":"10","T":1626330013518}}}"#;
+        let liquidations = &parse_liquidation("binance", MarketType::InverseSwap, raw_msg).unwrap();
+
+        assert_eq!(liquidations.len(), 1);
+        let liquidation = &liquidations[0];
+        assert_eq!(liquidation.exchange, "binance");
+        assert_eq!(liquidation.market_type, MarketType::InverseSwap);
+        assert_eq!(liquidation.msg_type, MessageType::Liquidation);
+        assert_eq!(liquidation.symbol, "BTCUSD_PERP");
+        assert_eq!(liquidation.pair, "BTC/USD");
+        assert_eq!(liquidation.timestamp, 1626330013518);
+        assert_eq!(liquidation.side, TradeSide::Buy);
+        assert_eq!(liquidation.price, 32816.5);
+        assert_eq!(liquidation.quantity_contract, Some(10.0));
+        assert_eq!(liquidation.quantity_quote, 1000.0);
+        assert_eq!(liquidation.quantity_base, 1000.0 / 32816.5);
+    }
+
+    #[test]
+    fn linear_swap() {
+        let raw_msg = r#"{"stream":"!forceOrder@arr","data":{"e":"forceOrder","E":1626330013600,"o":{"s":"BTCUSDT","S":"SELL","o":"LIMIT","f":"IOC","q":"0.014","p":"32710.26","ap":"32785.74","X":"FILLED","l":"0.014","z":"0.014","T":1626330013597}}}"#;
+        let liquidations = &parse_liquidation("binance", MarketType::LinearSwap, raw_msg).unwrap();
+
+        assert_eq!(liquidations.len(), 1);
+        let liquidation = &liquidations[0];
+        assert_eq!(liquidation.market_type, MarketType::LinearSwap);
+        assert_eq!(liquidation.pair, "BTC/USDT");
+        assert_eq!(liquidation.timestamp, 1626330013597);
+        assert_eq!(liquidation.side, TradeSide::Sell);
+        assert_eq!(liquidation.price, 32785.74);
+        assert_eq!(liquidation.quantity_base, 0.014);
+        assert_eq!(liquidation.quantity_contract, Some(0.014));
+    }
+}
+
+#[cfg(test)]
+mod l2_snapshot {
+    use crypto_msg_parser::{parse_l2_snapshot, MarketType, MessageType};
+
+    #[test]
+    fn spot() {
+        let raw_msg = r#"{"lastUpdateId":11294093726,"bids":[["35743.87000000","0.00001500"],["35743.98000000","0.10000000"]],"asks":[["35744.00000000","0.24000000"],["35743.99000000","1.00000000"]]}"#;
+        let orderbook =
+            &parse_l2_snapshot("binance", MarketType::Spot, "BTCUSDT", raw_msg).unwrap()[0];
+
+        assert_eq!(orderbook.msg_type, MessageType::L2Snapshot);
+        assert!(orderbook.snapshot);
+        assert_eq!(orderbook.symbol, "BTCUSDT");
+        assert_eq!(orderbook.pair, "BTC/USDT");
+        assert_eq!(orderbook.seq_id, Some(11294093726));
+        assert_eq!(orderbook.prev_seq_id, None);
+
+        // sorted, best prices first
+        assert_eq!(orderbook.bids[0].price, 35743.98);
+        assert_eq!(orderbook.asks[0].price, 35743.99);
+        assert_eq!(orderbook.asks[0].quantity_base, 1.0);
+        assert_eq!(orderbook.asks[0].quantity_contract, None);
+    }
+
+    #[test]
+    fn inverse_swap() {
+        let raw_msg = r#"{"lastUpdateId":241838385961,"E":1625097804231,"T":1625097804224,"symbol":"BTCUSD_PERP","pair":"BTCUSD","bids":[["35000.0","10"]],"asks":[["35000.1","20"]]}"#;
+        let orderbook =
+            &parse_l2_snapshot("binance", MarketType::InverseSwap, "BTCUSD_PERP", raw_msg).unwrap()
+                [0];
+
+        assert_eq!(orderbook.pair, "BTC/USD");
+        assert_eq!(orderbook.timestamp, 1625097804224);
+        assert_eq!(orderbook.seq_id, Some(241838385961));
+        assert_eq!(orderbook.asks[0].quantity_contract, Some(20.0));
+        assert_eq!(orderbook.asks[0].quantity_quote, 20.0 * 100.0);
+    }
+}
+
+#[cfg(test)]
+mod ticker {
+    use crypto_msg_parser::{extract_symbol, parse_ticker, MarketType, MessageType};
+
+    #[test]
+    fn spot() {
+        let raw_msg = r#"{"stream":"btcusdt@ticker","data":{"e":"24hrTicker","E":1633161238546,"s":"BTCUSDT","p":"1325.48000000","P":"2.891","w":"46752.77584404","x":"45838.58000000","c":"47164.06000000","Q":"0.00212000","b":"47164.05000000","B":"0.86911000","a":"47164.06000000","A":"0.54402000","o":"45838.58000000","h":"48398.00000000","l":"45664.52000000","v":"39858.43717000","q":"1863496432.04458240","O":1633074838546,"C":1633161238546,"F":1072590880,"L":1073740316,"n":1149437}}"#;
+        assert_eq!(
+            "BTCUSDT",
+            extract_symbol("binance", MarketType::Spot, raw_msg).unwrap()
+        );
+        let ticker = &parse_ticker("binance", MarketType::Spot, raw_msg).unwrap()[0];
+
+        assert_eq!(ticker.exchange, "binance");
+        assert_eq!(ticker.market_type, MarketType::Spot);
+        assert_eq!(ticker.msg_type, MessageType::Ticker);
+        assert_eq!(ticker.symbol, "BTCUSDT");
+        assert_eq!(ticker.pair, "BTC/USDT");
+        assert_eq!(ticker.timestamp, 1633161238546);
+
+        assert_eq!(ticker.open, 45838.58);
+        assert_eq!(ticker.high, 48398.0);
+        assert_eq!(ticker.low, 45664.52);
+        assert_eq!(ticker.close, 47164.06);
+        assert_eq!(ticker.volume, 39858.43717);
+        assert_eq!(ticker.quote_volume, 1863496432.0445824);
+        assert_eq!(ticker.price_change_percent, Some(2.891));
+        assert_eq!(ticker.last_quantity, Some(0.00212));
+        assert_eq!(ticker.best_bid_price, Some(47164.05));
+        assert_eq!(ticker.best_ask_quantity, Some(0.54402));
+    }
+
+    #[test]
+    fn linear_swap() {
+        let raw_msg = r#"{"stream":"btcusdt@ticker","data":{"e":"24hrTicker","E":1633161238600,"s":"BTCUSDT","p":"1318.93","P":"2.878","w":"46733.45","c":"47150.01","Q":"0.010","o":"45831.08","h":"48380.00","l":"45643.00","v":"412586.372","q":"19281546930.76","O":1633074780000,"C":1633161238592,"F":1367453069,"L":1372069381,"n":4616171}}"#;
+        let ticker = &parse_ticker("binance", MarketType::LinearSwap, raw_msg).unwrap()[0];
+
+        assert_eq!(ticker.market_type, MarketType::LinearSwap);
+        assert_eq!(ticker.symbol, "BTCUSDT");
+        assert_eq!(ticker.pair, "BTC/USDT");
+        assert_eq!(ticker.timestamp, 1633161238600);
+
+        assert_eq!(ticker.open, 45831.08);
+        assert_eq!(ticker.high, 48380.0);
+        assert_eq!(ticker.low, 45643.0);
+        assert_eq!(ticker.close, 47150.01);
+        assert_eq!(ticker.volume, 412586.372);
+        assert_eq!(ticker.quote_volume, 19281546930.76);
+        assert_eq!(ticker.price_change_percent, Some(2.878));
+        assert_eq!(ticker.last_quantity, Some(0.01));
+        assert_eq!(ticker.best_bid_price, None);
+    }
+
+    #[test]
+    fn inverse_swap() {
+        let raw_msg = r#"{"stream":"btcusd_perp@ticker","data":{"e":"24hrTicker","E":1633161238612,"s":"BTCUSD_PERP","ps":"BTCUSD","p":"1316.1","P":"2.871","w":"46735.60311254","c":"47150.1","Q":"2","o":"45834.0","h":"48382.9","l":"45647.1","v":"5246328","q":"11225.68312594","O":1633074780000,"C":1633161238610,"F":406476125,"L":407331738,"n":855614}}"#;
+        let ticker = &parse_ticker("binance", MarketType::InverseSwap, raw_msg).unwrap()[0];
+
+        assert_eq!(ticker.market_type, MarketType::InverseSwap);
+        assert_eq!(ticker.symbol, "BTCUSD_PERP");
+        assert_eq!(ticker.pair, "BTC/USD");
+        assert_eq!(ticker.timestamp, 1633161238612);
+
+        assert_eq!(ticker.close, 47150.1);
+        // v is in contracts of 100 USD, q is in BTC
+        assert_eq!(ticker.volume, 11225.68312594);
+        assert_eq!(ticker.quote_volume, 5246328.0 * 100.0);
+        assert_eq!(ticker.last_quantity, Some(2.0 * 100.0 / 47150.1));
+    }
+
+    #[test]
+    fn invalid_field() {
+        let raw_msg = r#"{"stream":"btcusdt@ticker","data":{"e":"24hrTicker","E":1633161238600,"s":"BTCUSDT","p":"1318.93","P":"2.878","w":"46733.45","c":"NaN?","Q":"0.010","o":"45831.08","h":"48380.00","l":"45643.00","v":"412586.372","q":"19281546930.76","O":1633074780000,"C":1633161238592,"F":1367453069,"L":1372069381,"n":4616171}}"#;
+        assert!(parse_ticker("binance", MarketType::LinearSwap, raw_msg).is_err());
+    }
+}
+
+#[cfg(test)]
+mod candlestick_history {
+    use crypto_msg_parser::{parse_candlestick_history, MarketType, MessageType};
+
+    #[test]
+    fn spot() {
+        let raw_msg = r#"[[1633046400000,"43820.01000000","43934.00000000","43776.07000000","43833.51000000","133.12545000",1633046459999,"5837158.56632230",3327,"59.47386000","2607792.76005290","0"],[1633046460000,"43833.51000000","43900.00000000","43833.50000000","43888.88000000","66.24658000",1633046519999,"2906936.68432570",1877,"39.80522000","1746761.05017150","0"]]"#;
+        let klines =
+            parse_candlestick_history("binance", MarketType::Spot, "BTCUSDT", 60, raw_msg).unwrap();
+
+        assert_eq!(klines.len(), 2);
+        let kline = &klines[0];
+        assert_eq!(kline.exchange, "binance");
+        assert_eq!(kline.msg_type, MessageType::Candlestick);
+        assert_eq!(kline.symbol, "BTCUSDT");
+        assert_eq!(kline.pair, "BTC/USDT");
+        assert_eq!(kline.timestamp, 1633046400000);
+        assert_eq!(kline.period, "1m");
+        assert_eq!(kline.open, 43820.01);
+        assert_eq!(kline.high, 43934.0);
+        assert_eq!(kline.low, 43776.07);
+        assert_eq!(kline.close, 43833.51);
+        assert_eq!(kline.volume, 133.12545);
+        assert_eq!(kline.quote_volume, Some(5837158.5663223));
+        assert_eq!(klines[1].timestamp, 1633046460000);
+    }
+
+    #[test]
+    fn linear_swap() {
+        let raw_msg = r#"[[1633046400000,"43800.00","44155.00","43622.03","43934.94","21754.571",1633049999999,"955290393.80320",218207,"11176.807","490827051.64660","0"]]"#;
+        let kline =
+            &parse_candlestick_history("binance", MarketType::LinearSwap, "BTCUSDT", 3600, raw_msg)
+                .unwrap()[0];
+
+        assert_eq!(kline.market_type, MarketType::LinearSwap);
+        assert_eq!(kline.period, "1H");
+        assert_eq!(kline.close, 43934.94);
+        assert_eq!(kline.volume, 21754.571);
+    }
+
+    #[test]
+    fn empty() {
+        assert!(
+            parse_candlestick_history("binance", MarketType::Spot, "BTCUSDT", 60, "[]")
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn unsupported() {
+        assert!(parse_candlestick_history(
+            "binance",
+            MarketType::InverseSwap,
+            "BTCUSD_PERP",
+            60,
+            "[]"
+        )
+        .is_err());
+        assert!(parse_candlestick_history("okex", MarketType::Spot, "BTC-USDT", 60, "[]").is_err());
+    }
 }
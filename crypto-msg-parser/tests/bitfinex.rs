@@ -266,3 +266,80 @@ mod l2_orderbook {
         assert_eq!(orderbook.bids[0].quantity_contract.unwrap(), 2.27726294);
     }
 }
+
+#[cfg(test)]
+mod channels {
+    use crypto_msg_parser::{
+        extract_symbol, parse_l2, parse_trade, BitfinexChannels, MarketType, TradeSide,
+    };
+
+    #[test]
+    fn subscribed_then_trade() {
+        let mut channels = BitfinexChannels::new();
+        assert_eq!(
+            None,
+            channels.process(r#"{"event":"info","version":2,"serverId":"4b5d7ff0-9d0f-4f6b-a33c-2ba5a0d5e4a8","platform":{"status":1}}"#)
+        );
+        assert_eq!(
+            None,
+            channels.process(r#"{"event":"subscribed","channel":"trades","chanId":17470,"symbol":"tBTCUST","pair":"BTCUST"}"#)
+        );
+        assert_eq!(
+            Some(r#"{"channel":"trades","symbol":"tBTCUST"}"#),
+            channels.get(17470)
+        );
+        assert_eq!(None, channels.process(r#"[17470,"hb"]"#));
+
+        let raw_msg = r#"[17470,"te",[637771130,1615232733897,-0.11546588,51350]]"#;
+        assert!(parse_trade("bitfinex", MarketType::Spot, raw_msg).is_err());
+
+        let msg = channels.process(raw_msg).unwrap();
+        let trade = &parse_trade("bitfinex", MarketType::Spot, &msg).unwrap()[0];
+
+        crate::utils::check_trade_fields(
+            "bitfinex",
+            MarketType::Spot,
+            "BTC/USDT".to_string(),
+            extract_symbol("bitfinex", MarketType::Spot, &msg).unwrap(),
+            trade,
+        );
+        assert_eq!(trade.trade_id, "637771130");
+        assert_eq!(trade.timestamp, 1615232733897);
+        assert_eq!(trade.price, 51350.0);
+        assert_eq!(trade.quantity_base, 0.11546588);
+        assert_eq!(trade.side, TradeSide::Sell);
+
+        // data of a channel after unsubscribing is dropped
+        assert_eq!(
+            None,
+            channels.process(r#"{"event":"unsubscribed","status":"OK","chanId":17470}"#)
+        );
+        assert_eq!(None, channels.process(raw_msg));
+    }
+
+    #[test]
+    fn subscribed_then_book() {
+        let mut channels = BitfinexChannels::new();
+        channels.process(r#"{"event":"subscribed","channel":"book","chanId":224,"symbol":"tBTCUST","prec":"P0","freq":"F0","len":"25","pair":"BTCUST"}"#);
+
+        let msg = channels
+            .process(r#"[224,[[34668,1,0.0245],[34667,2,0.5],[34670,1,-0.01]]]"#)
+            .unwrap();
+        let orderbook =
+            &parse_l2("bitfinex", MarketType::Spot, &msg, Some(1622766183140)).unwrap()[0];
+        assert!(orderbook.snapshot);
+        assert_eq!(orderbook.symbol, "tBTCUST");
+        assert_eq!(orderbook.pair, "BTC/USDT");
+        assert_eq!(orderbook.bids.len(), 2);
+        assert_eq!(orderbook.asks.len(), 1);
+        assert_eq!(orderbook.asks[0].price, 34670.0);
+        assert_eq!(orderbook.asks[0].quantity_base, 0.01);
+
+        let msg = channels.process(r#"[224,[34668,0,1]]"#).unwrap();
+        let orderbook =
+            &parse_l2("bitfinex", MarketType::Spot, &msg, Some(1622766183240)).unwrap()[0];
+        assert!(!orderbook.snapshot);
+        assert_eq!(orderbook.bids[0].price, 34668.0);
+        assert_eq!(orderbook.bids[0].quantity_base, 0.0);
+    }
+}
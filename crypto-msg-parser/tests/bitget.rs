@@ -77,7 +77,7 @@ mod trade {
 
 #[cfg(test)]
 mod funding_rate {
-    use crypto_msg_parser::{parse_funding_rate, MarketType};
+    use crypto_msg_parser::{extract_symbol, parse_funding_rate, MarketType, ParseError};
 
     #[test]
     fn inverse_swap() {
@@ -111,6 +111,89 @@ mod funding_rate {
         assert_eq!(funding_rates[0].funding_rate, 0.000106539854);
         assert_eq!(funding_rates[0].funding_time, 1617346800000);
     }
+
+    #[test]
+    fn inverse_swap_unaligned() {
+        let raw_msg = r#"{"data":[{"funding_rate":"0.000258514264","funding_time":"1617346800012","instrument_id":"btcusd"}],"table":"swap/funding_rate"}"#;
+        let funding_rates =
+            &parse_funding_rate("bitget", MarketType::InverseSwap, raw_msg).unwrap();
+
+        assert_eq!(funding_rates.len(), 1);
+        crate::utils::check_funding_rate_fields(
+            "bitget",
+            MarketType::InverseSwap,
+            &funding_rates[0],
+        );
+        assert_eq!(funding_rates[0].funding_time, 1617346800000);
+    }
+
+    #[test]
+    fn linear_swap_unaligned() {
+        let raw_msg = r#"{"data":[{"funding_rate":"0.000106539854","funding_time":"1617346799995","instrument_id":"cmt_btcusdt"}],"table":"swap/funding_rate"}"#;
+        let funding_rates = &parse_funding_rate("bitget", MarketType::LinearSwap, raw_msg).unwrap();
+
+        assert_eq!(funding_rates.len(), 1);
+        crate::utils::check_funding_rate_fields(
+            "bitget",
+            MarketType::LinearSwap,
+            &funding_rates[0],
+        );
+        assert_eq!(funding_rates[0].funding_time, 1617346800000);
+    }
+
+    #[test]
+    fn linear_swap_ticker() {
+        let raw_msg = r#"{"action":"snapshot","arg":{"instType":"mc","channel":"ticker","instId":"BTCUSDT"},"data":[{"instId":"BTCUSDT","last":"41243.50","bestAsk":"41243.5","bestBid":"41243","high24h":"41984.00","low24h":"40777.50","priceChangePercent":"-0.01106","capitalRate":"0.000100","nextSettleTime":1648540800000,"systemTime":1648525466453,"markPrice":"41245.03","indexPrice":"41268.49","holding":"49296.451","baseVolume":"56071.316","quoteVolume":"2318005459.474","openUtc":"41512.0000000000000000","chgUTC":"-0.00647","symbolType":1,"symbolId":"BTCUSDT_UMCBL","deliveryPrice":"0","bidSz":"10.282","askSz":"2.049"}]}"#;
+        let funding_rates = &parse_funding_rate("bitget", MarketType::LinearSwap, raw_msg).unwrap();
+
+        assert_eq!(funding_rates.len(), 1);
+
+        for rate in funding_rates.iter() {
+            crate::utils::check_funding_rate_fields("bitget", MarketType::LinearSwap, rate);
+        }
+
+        assert_eq!(
+            "BTCUSDT",
+            extract_symbol("bitget", MarketType::LinearSwap, raw_msg).unwrap()
+        );
+        assert_eq!(funding_rates[0].pair, "BTC/USDT".to_string());
+        assert_eq!(funding_rates[0].funding_rate, 0.0001);
+        assert_eq!(funding_rates[0].funding_time, 1648540800000);
+        assert_eq!(funding_rates[0].mark_price, Some(41245.03));
+        assert_eq!(funding_rates[0].timestamp, 1648525466453);
+    }
+
+    #[test]
+    fn inverse_swap_ticker() {
+        let raw_msg = r#"{"action":"snapshot","arg":{"instType":"mc","channel":"ticker","instId":"BTCUSD"},"data":[{"instId":"BTCUSD","last":"41236.50","bestAsk":"41237","bestBid":"41236.5","high24h":"41982.00","low24h":"40770.00","priceChangePercent":"-0.01113","capitalRate":"0.000100","nextSettleTime":1648540800000,"systemTime":1648525466453,"markPrice":"41239.98","indexPrice":"41268.49","holding":"1035.468","baseVolume":"1840.213","quoteVolume":"75850826.871","openUtc":"41505.5000000000000000","chgUTC":"-0.00649","symbolType":1,"symbolId":"BTCUSD_DMCBL","deliveryPrice":"0","bidSz":"0.745","askSz":"1.104"}]}"#;
+        let funding_rates =
+            &parse_funding_rate("bitget", MarketType::InverseSwap, raw_msg).unwrap();
+
+        assert_eq!(funding_rates.len(), 1);
+
+        for rate in funding_rates.iter() {
+            crate::utils::check_funding_rate_fields("bitget", MarketType::InverseSwap, rate);
+        }
+
+        assert_eq!(
+            "BTCUSD",
+            extract_symbol("bitget", MarketType::InverseSwap, raw_msg).unwrap()
+        );
+        assert_eq!(funding_rates[0].pair, "BTC/USD".to_string());
+        assert_eq!(funding_rates[0].funding_rate, 0.0001);
+        assert_eq!(funding_rates[0].funding_time, 1648540800000);
+        assert_eq!(funding_rates[0].mark_price, Some(41239.98));
+    }
+
+    #[test]
+    fn ticker_with_unknown_quote() {
+        // an inverse symbol doesn't end with USDT
+        let raw_msg = r#"{"action":"snapshot","arg":{"instType":"mc","channel":"ticker","instId":"BTCUSD"},"data":[{"instId":"BTCUSD","last":"41236.50","bestAsk":"41237","bestBid":"41236.5","high24h":"41982.00","low24h":"40770.00","priceChangePercent":"-0.01113","capitalRate":"0.000100","nextSettleTime":1648540800000,"systemTime":1648525466453,"markPrice":"41239.98","indexPrice":"41268.49","holding":"1035.468","baseVolume":"1840.213","quoteVolume":"75850826.871","openUtc":"41505.5000000000000000","chgUTC":"-0.00649","symbolType":1,"symbolId":"BTCUSD_DMCBL","deliveryPrice":"0","bidSz":"0.745","askSz":"1.104"}]}"#;
+        assert!(matches!(
+            parse_funding_rate("bitget", MarketType::LinearSwap, raw_msg),
+            Err(ParseError::UnknownSymbol(symbol)) if symbol == "BTCUSD"
+        ));
+    }
 }
 
 #[cfg(test)]
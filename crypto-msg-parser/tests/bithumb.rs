@@ -101,3 +101,46 @@ fn l2_orderbook_update() {
     assert_eq!(orderbook.bids[0].quantity_base, 0.015396);
     assert_eq!(orderbook.bids[0].quantity_quote, 34613.44 * 0.015396);
 }
+
+#[test]
+fn krw_trade() {
+    let raw_msg = r#"{"code":"00007","data":{"p":"47390000.0000000000","symbol":"BTC-KRW","ver":"3471128","s":"sell","t":"1626330013","v":"0.0021"},"topic":"TRADE","timestamp":1626330013405}"#;
+    let trades = &parse_trade("bithumb", MarketType::Spot, raw_msg).unwrap();
+
+    assert_eq!(trades.len(), 1);
+    let trade = &trades[0];
+
+    crate::utils::check_trade_fields(
+        "bithumb",
+        MarketType::Spot,
+        "BTC/KRW".to_string(),
+        extract_symbol("bithumb", MarketType::Spot, raw_msg).unwrap(),
+        trade,
+    );
+
+    assert_eq!(trade.price, 47390000.0);
+    assert_eq!(trade.quantity_base, 0.0021);
+    assert_eq!(trade.side, TradeSide::Sell);
+}
+
+#[test]
+fn krw_l2_orderbook_update() {
+    let raw_msg = r#"{"code":"00007","data":{"symbol":"BTC-KRW","b":[["47380000.0000000000","0.053"]],"ver":"8827402","s":[["47400000.0000000000","0.1201"]]},"topic":"ORDERBOOK","timestamp":1626330013537}"#;
+    let orderbook = &parse_l2("bithumb", MarketType::Spot, raw_msg, None).unwrap()[0];
+
+    assert_eq!(orderbook.asks.len(), 1);
+    assert_eq!(orderbook.bids.len(), 1);
+
+    crate::utils::check_orderbook_fields(
+        "bithumb",
+        MarketType::Spot,
+        "BTC/KRW".to_string(),
+        extract_symbol("bithumb", MarketType::Spot, raw_msg).unwrap(),
+        orderbook,
+    );
+
+    assert_eq!(orderbook.bids[0].price, 47380000.0);
+    assert_eq!(orderbook.bids[0].quantity_quote, 47380000.0 * 0.053);
+    assert_eq!(orderbook.asks[0].price, 47400000.0);
+    assert_eq!(orderbook.asks[0].quantity_base, 0.1201);
+}
@@ -339,3 +339,149 @@ mod l2_orderbook {
         assert_eq!(orderbook.bids[0].quantity_contract.unwrap(), 0.0);
     }
 }
+
+#[cfg(test)]
+mod open_interest {
+    use crypto_msg_parser::{parse_open_interest, MarketType, MessageType};
+
+    #[test]
+    fn all() {
+        let raw_msg = r#"[{"symbol":".BXBT","timestamp":"2021-07-15T06:20:00.000Z","openInterest":null,"openValue":0},{"symbol":"XBTUSD","timestamp":"2021-07-15T06:20:13.405Z","openInterest":585218200,"openValue":1819617223560},{"symbol":"ETHUSD","timestamp":"2021-07-15T06:20:13.405Z","openInterest":1543650,"openValue":302522823500}]"#;
+        let open_interests = &parse_open_interest("bitmex", MarketType::Unknown, raw_msg).unwrap();
+
+        assert_eq!(open_interests.len(), 2);
+        for open_interest in open_interests.iter() {
+            assert_eq!(open_interest.exchange, "bitmex");
+            assert_eq!(open_interest.msg_type, MessageType::OpenInterest);
+            assert_eq!(open_interest.timestamp, 1626330013405);
+        }
+
+        assert_eq!(open_interests[0].symbol, "XBTUSD");
+        assert_eq!(open_interests[0].market_type, MarketType::InverseSwap);
+        assert_eq!(open_interests[0].pair, "BTC/USD");
+        assert_eq!(open_interests[0].open_interest, 585218200.0);
+        assert_eq!(open_interests[0].open_interest_value, Some(585218200.0));
+
+        assert_eq!(open_interests[1].symbol, "ETHUSD");
+        assert_eq!(open_interests[1].market_type, MarketType::QuantoSwap);
+        assert_eq!(open_interests[1].open_interest_value, None);
+    }
+}
+
+#[cfg(test)]
+mod index_price {
+    use crypto_msg_parser::{parse_index_price, MarketType, MessageType};
+
+    #[test]
+    fn bxbt() {
+        let raw_msg = r#"{"table":"instrument","action":"update","data":[{"symbol":".BXBT","lastPrice":58857.27,"timestamp":"2021-04-01T20:27:00.000Z"},{"symbol":"XBTUSD","lastPrice":58940.5,"timestamp":"2021-04-01T20:27:00.000Z"},{"symbol":".BETH","markPrice":1975.2,"timestamp":"2021-04-01T20:27:00.000Z"}]}"#;
+        let index_prices = &parse_index_price("bitmex", MarketType::Unknown, raw_msg).unwrap();
+
+        // XBTUSD is a contract, and .BETH has no lastPrice in this update
+        assert_eq!(index_prices.len(), 1);
+        let index_price = &index_prices[0];
+        assert_eq!(index_price.exchange, "bitmex");
+        assert_eq!(index_price.market_type, MarketType::Unknown);
+        assert_eq!(index_price.msg_type, MessageType::IndexPrice);
+        assert_eq!(index_price.symbol, ".BXBT");
+        assert_eq!(index_price.pair, "BTC/USD");
+        assert_eq!(index_price.timestamp, 1617308820000);
+        assert_eq!(index_price.index_price, 58857.27);
+        assert_eq!(index_price.json, raw_msg);
+    }
+
+    #[test]
+    fn usdt_index() {
+        let raw_msg = r#"{"table":"instrument","action":"update","data":[{"symbol":".BXBTUSDT","lastPrice":58871.12,"timestamp":"2021-04-01T20:27:00.000Z"}]}"#;
+        let index_prices = &parse_index_price("bitmex", MarketType::Unknown, raw_msg).unwrap();
+        assert_eq!(index_prices[0].pair, "BTC/USDT");
+    }
+}
+
+#[cfg(test)]
+mod liquidation {
+    use crypto_msg_parser::{parse_liquidation, MarketType, MessageType, TradeSide};
+
+    #[test]
+    fn insert() {
+        let raw_msg = r#"{"table":"liquidation","action":"insert","data":[{"orderID":"05f91ad4-dbba-4a14-b9f4-b8a8e3ec2fe8","symbol":"XBTUSD","side":"Sell","price":32500,"leavesQty":1300}]}"#;
+        let liquidations = &parse_liquidation("bitmex", MarketType::Unknown, raw_msg).unwrap();
+
+        assert_eq!(liquidations.len(), 1);
+        let liquidation = &liquidations[0];
+        assert_eq!(liquidation.exchange, "bitmex");
+        assert_eq!(liquidation.market_type, MarketType::InverseSwap);
+        assert_eq!(liquidation.msg_type, MessageType::Liquidation);
+        assert_eq!(liquidation.symbol, "XBTUSD");
+        assert_eq!(liquidation.pair, "BTC/USD");
+        assert_eq!(liquidation.side, TradeSide::Sell);
+        assert_eq!(liquidation.price, 32500.0);
+        assert_eq!(liquidation.quantity_contract, Some(1300.0));
+        assert_eq!(liquidation.quantity_quote, 1300.0);
+        assert_eq!(liquidation.quantity_base, 0.04);
+        assert_eq!(liquidation.json, raw_msg);
+    }
+
+    #[test]
+    fn update() {
+        let raw_msg = r#"{"table":"liquidation","action":"update","data":[{"orderID":"05f91ad4-dbba-4a14-b9f4-b8a8e3ec2fe8","symbol":"XBTUSD","leavesQty":300}]}"#;
+        let liquidations = parse_liquidation("bitmex", MarketType::Unknown, raw_msg).unwrap();
+        assert!(liquidations.is_empty());
+    }
+}
+
+#[cfg(test)]
+mod multiplex {
+    use crypto_msg_parser::exchanges::bitmex::get_msg_type;
+    use crypto_msg_parser::{
+        extract_symbol, parse_funding_rate, parse_l2, parse_trade, MarketType, MessageType,
+    };
+
+    #[test]
+    fn interleaved_tables() {
+        // one connection subscribed to trade:XBTUSD,orderBookL2:XBTUSD,funding:XBTUSD
+        let messages = [
+            r#"{"table":"orderBookL2","action":"partial","data":[{"symbol":"XBTUSD","id":8796381000,"side":"Sell","size":49900,"price":36190},{"symbol":"XBTUSD","id":8796385500,"side":"Buy","size":136,"price":36145}]}"#,
+            r#"{"table":"trade","action":"insert","data":[{"timestamp":"2021-03-12T02:00:04.608Z","symbol":"XBTUSD","side":"Sell","size":900,"price":56927,"tickDirection":"MinusTick","trdMatchID":"d1b82d61-d902-349c-936c-2588b8204aff","grossValue":1581300,"homeNotional":0.015813,"foreignNotional":900}]}"#,
+            r#"{"table":"orderBookL2","action":"update","data":[{"symbol":"XBTUSD","id":8796381000,"side":"Sell","size":36760}]}"#,
+            r#"{"table":"funding","action":"insert","data":[{"timestamp":"2021-04-01T20:00:00.000Z","symbol":"XBTUSD","fundingInterval":"2000-01-01T08:00:00.000Z","fundingRate":0.000817,"fundingRateDaily":0.002451}]}"#,
+            r#"{"table":"trade","action":"insert","data":[{"timestamp":"2021-03-12T02:00:05.608Z","symbol":"XBTUSD","side":"Buy","size":100,"price":56928,"tickDirection":"PlusTick","trdMatchID":"e1b82d61-d902-349c-936c-2588b8204aff","grossValue":175660,"homeNotional":0.0017566,"foreignNotional":100}]}"#,
+        ];
+
+        let mut num_trades = 0;
+        let mut num_orderbooks = 0;
+        let mut num_funding_rates = 0;
+        for msg in messages {
+            assert_eq!(
+                "XBTUSD",
+                extract_symbol("bitmex", MarketType::Unknown, msg).unwrap()
+            );
+            match get_msg_type(msg) {
+                Some(MessageType::Trade) => {
+                    let trades = parse_trade("bitmex", MarketType::Unknown, msg).unwrap();
+                    assert_eq!(MessageType::Trade, trades[0].msg_type);
+                    num_trades += trades.len();
+                }
+                Some(MessageType::L2Event) => {
+                    let orderbooks = parse_l2("bitmex", MarketType::Unknown, msg, Some(0)).unwrap();
+                    assert_eq!(MarketType::InverseSwap, orderbooks[0].market_type);
+                    num_orderbooks += orderbooks.len();
+                }
+                Some(MessageType::FundingRate) => {
+                    let rates = parse_funding_rate("bitmex", MarketType::Unknown, msg).unwrap();
+                    assert_eq!(0.000817, rates[0].funding_rate);
+                    num_funding_rates += rates.len();
+                }
+                msg_type => panic!("Unexpected {:?} message {}", msg_type, msg),
+            }
+        }
+        assert_eq!(2, num_trades);
+        assert_eq!(2, num_orderbooks);
+        assert_eq!(1, num_funding_rates);
+
+        // messages of other tables are refused
+        assert!(parse_trade("bitmex", MarketType::Unknown, messages[0]).is_err());
+        assert!(parse_l2("bitmex", MarketType::Unknown, messages[1], Some(0)).is_err());
+        assert!(parse_funding_rate("bitmex", MarketType::Unknown, messages[1]).is_err());
+    }
+}
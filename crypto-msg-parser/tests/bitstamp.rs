@@ -1,6 +1,8 @@
 mod utils;
 
-use crypto_msg_parser::{extract_symbol, parse_l2, parse_trade, MarketType, TradeSide};
+use crypto_msg_parser::{
+    extract_symbol, parse_l2, parse_trade, BboMsg, MarketType, MessageType, TradeSide,
+};
 
 #[test]
 fn trade() {
@@ -19,6 +21,32 @@ fn trade() {
     assert_eq!(trade.side, TradeSide::Buy);
 }
 
+#[test]
+fn trade_sell() {
+    let raw_msg = r#"{"channel": "live_trades_ethusd", "data": {"amount": 0.5, "amount_str": "0.50000000", "buy_order_id": 1341285759094785, "id": 158457580, "microtimestamp": "1616297318190000", "price": 1802.5, "price_str": "1802.50", "sell_order_id": 1341285698236417, "timestamp": "1616297318", "type": 1}, "event": "trade"}"#;
+    let trade = &parse_trade("bitstamp", MarketType::Spot, raw_msg).unwrap()[0];
+
+    crate::utils::check_trade_fields(
+        "bitstamp",
+        MarketType::Spot,
+        "ETH/USD".to_string(),
+        extract_symbol("bitstamp", MarketType::Spot, raw_msg).unwrap(),
+        trade,
+    );
+
+    assert_eq!("ethusd", trade.symbol);
+    assert_eq!(trade.timestamp, 1616297318190);
+    assert_eq!(trade.timestamp_us, Some(1616297318190000));
+    assert_eq!(trade.side, TradeSide::Sell);
+    assert_eq!(trade.trade_id, "158457580");
+}
+
+#[test]
+fn trade_unknown_type() {
+    let raw_msg = r#"{"channel": "live_trades_btcusd", "data": {"amount": 1e-08, "amount_str": "1E-8", "buy_order_id": 1341285759094784, "id": 158457579, "microtimestamp": "1616297318187000", "price": 57748.8, "price_str": "57748.80", "sell_order_id": 1341285698236416, "timestamp": "1616297318", "type": 2}, "event": "trade"}"#;
+    assert!(parse_trade("bitstamp", MarketType::Spot, raw_msg).is_err());
+}
+
 #[test]
 fn l2_orderbook_update() {
     let raw_msg = r#"{"data":{"timestamp":"1622520011","microtimestamp":"1622520011989838","bids":[["36653.62","0.75000000"]],"asks":[["36665.20","0.00000000"],["36669.76","0.75000000"]]},"channel":"diff_order_book_btcusd","event":"data"}"#;
@@ -37,6 +65,7 @@ fn l2_orderbook_update() {
     );
 
     assert_eq!(orderbook.timestamp, 1622520011989);
+    assert_eq!(orderbook.timestamp_us, Some(1622520011989838));
 
     assert_eq!(orderbook.bids[0].price, 36653.62);
     assert_eq!(orderbook.bids[0].quantity_base, 0.75);
@@ -50,3 +79,70 @@ fn l2_orderbook_update() {
     assert_eq!(orderbook.asks[1].quantity_base, 0.75);
     assert_eq!(orderbook.asks[1].quantity_quote, 36669.76 * 0.75);
 }
+
+#[test]
+fn l2_orderbook_snapshot() {
+    let raw_msg = r#"{"data":{"timestamp":"1622520012","microtimestamp":"1622520012035474","bids":[["36653.62","0.75000000"],["36655.00","0.10000000"]],"asks":[["36669.76","0.75000000"],["36665.20","0.20000000"]]},"channel":"order_book_btcusd","event":"data"}"#;
+    let orderbook = &parse_l2("bitstamp", MarketType::Spot, raw_msg, None).unwrap()[0];
+
+    assert_eq!(orderbook.asks.len(), 2);
+    assert_eq!(orderbook.bids.len(), 2);
+    assert!(orderbook.snapshot);
+
+    crate::utils::check_orderbook_fields(
+        "bitstamp",
+        MarketType::Spot,
+        "BTC/USD".to_string(),
+        extract_symbol("bitstamp", MarketType::Spot, raw_msg).unwrap(),
+        orderbook,
+    );
+
+    assert_eq!(orderbook.timestamp, 1622520012035);
+    assert_eq!(orderbook.timestamp_us, Some(1622520012035474));
+
+    // snapshots are sorted, best prices first
+    assert_eq!(orderbook.bids[0].price, 36655.0);
+    assert_eq!(orderbook.bids[1].price, 36653.62);
+    assert_eq!(orderbook.asks[0].price, 36665.2);
+    assert_eq!(orderbook.asks[1].price, 36669.76);
+}
+
+#[test]
+fn bbo_from_snapshot() {
+    let raw_msg = r#"{"data":{"timestamp":"1622520012","microtimestamp":"1622520012035474","bids":[["36653.62","0.75000000"],["36655.00","0.10000000"]],"asks":[["36669.76","0.75000000"],["36665.20","0.20000000"]]},"channel":"order_book_btcusd","event":"data"}"#;
+    let orderbook = &parse_l2("bitstamp", MarketType::Spot, raw_msg, None).unwrap()[0];
+    let bbo = BboMsg::from_snapshot(orderbook).unwrap();
+
+    assert!(bbo.synthetic);
+    assert_eq!(MessageType::BBO, bbo.msg_type);
+    assert_eq!("BTC/USD", bbo.pair);
+    assert_eq!(1622520012035, bbo.timestamp);
+    assert_eq!(36655.0, bbo.bid_price);
+    assert_eq!(0.1, bbo.bid_quantity);
+    assert_eq!(36665.2, bbo.ask_price);
+    assert_eq!(0.2, bbo.ask_quantity);
+
+    let update = r#"{"data":{"timestamp":"1622520011","microtimestamp":"1622520011989838","bids":[["36653.62","0.75000000"]],"asks":[["36665.20","0.00000000"],["36669.76","0.75000000"]]},"channel":"diff_order_book_btcusd","event":"data"}"#;
+    let orderbook = &parse_l2("bitstamp", MarketType::Spot, update, None).unwrap()[0];
+    assert!(BboMsg::from_snapshot(orderbook).is_none());
+}
+
+#[test]
+fn extract_symbol_from_channels() {
+    assert_eq!(
+        Some("btcusd".to_string()),
+        extract_symbol(
+            "bitstamp",
+            MarketType::Spot,
+            r#"{"channel":"live_orders_btcusd","event":"order_created","data":{}}"#
+        )
+    );
+    assert_eq!(
+        None,
+        extract_symbol(
+            "bitstamp",
+            MarketType::Spot,
+            r#"{"channel":"unknown_btcusd","event":"data","data":{}}"#
+        )
+    );
+}
@@ -307,4 +307,27 @@ mod l2_orderbook {
         assert_eq!(orderbook.bids[0].quantity_quote, 36381.5 * 6.906);
         assert_eq!(orderbook.bids[0].quantity_contract.unwrap(), 6.906);
     }
+
+    #[test]
+    fn linear_swap_delta_actions() {
+        let raw_msg = r#"{"topic":"orderBookL2_25.BTCUSDT","type":"delta","data":{"delete":[{"price":"36397.50","symbol":"BTCUSDT","id":"363975000","side":"Sell","size":1.5}],"update":[{"price":"36381.50","symbol":"BTCUSDT","id":"363815000","side":"Buy","size":6.906}],"insert":[{"price":"36380.00","symbol":"BTCUSDT","id":"363800000","side":"Buy","size":2.5}]},"cross_seq":"5737704047","timestamp_e6":"1622544088904367"}"#;
+        let orderbook = &parse_l2("bybit", MarketType::LinearSwap, raw_msg, None).unwrap()[0];
+
+        assert!(!orderbook.snapshot);
+        assert_eq!(orderbook.asks.len(), 1);
+        assert_eq!(orderbook.bids.len(), 2);
+
+        // deleted levels are zero even if the exchange sends a size
+        assert_eq!(orderbook.asks[0].price, 36397.5);
+        assert_eq!(orderbook.asks[0].quantity_base, 0.0);
+        assert_eq!(orderbook.asks[0].quantity_contract.unwrap(), 0.0);
+
+        assert_eq!(orderbook.bids[0].price, 36381.5);
+        assert_eq!(orderbook.bids[0].quantity_base, 6.906);
+        assert_eq!(orderbook.bids[1].price, 36380.0);
+        assert_eq!(orderbook.bids[1].quantity_base, 2.5);
+
+        let unknown_type = raw_msg.replace(r#""type":"delta""#, r#""type":"partial""#);
+        assert!(parse_l2("bybit", MarketType::LinearSwap, &unknown_type, None).is_err());
+    }
 }
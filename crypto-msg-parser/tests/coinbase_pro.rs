@@ -1,7 +1,7 @@
 mod utils;
 
 use chrono::prelude::*;
-use crypto_msg_parser::{extract_symbol, parse_l2, parse_trade, MarketType, TradeSide};
+use crypto_msg_parser::{extract_symbol, parse_l2, parse_trade, MarketType, ParseError, TradeSide};
 
 #[test]
 fn trade() {
@@ -18,6 +18,31 @@ fn trade() {
 
     assert_eq!(trade.quantity_base, 0.00031874);
     assert_eq!(trade.side, TradeSide::Sell);
+    assert_eq!(trade.timestamp, 1616298447112);
+    assert_eq!(trade.timestamp_us, Some(1616298447112041));
+}
+
+#[test]
+fn trade_invalid_time() {
+    let raw_msg = r#"{"type":"last_match","trade_id":147587438,"maker_order_id":"3dbaddb1-3dcf-4511-b81c-89450a56deb4","taker_order_id":"421f3aaa-dfdd-4192-805a-bb73462ea6db","side":"sell","size":"0.00031874","price":"57786.82","product_id":"BTC-USD","sequence":22962703070,"time":"1616298447112"}"#;
+    let err = parse_trade("coinbase_pro", MarketType::Spot, raw_msg)
+        .err()
+        .unwrap();
+
+    if let ParseError::InvalidField {
+        exchange,
+        field,
+        value,
+        raw_msg: truncated,
+    } = err
+    {
+        assert_eq!("coinbase_pro", exchange);
+        assert_eq!("time", field);
+        assert_eq!("1616298447112", value);
+        assert!(truncated.starts_with(r#"{"type":"last_match""#));
+    } else {
+        panic!("Expected ParseError::InvalidField");
+    }
 }
 
 #[test]
@@ -78,8 +103,116 @@ fn l2_orderbook_update() {
     );
 
     assert_eq!(orderbook.timestamp, 1622624529048);
+    assert_eq!(orderbook.timestamp_us, Some(1622624529048568));
 
     assert_eq!(orderbook.bids[0].price, 37378.26);
     assert_eq!(orderbook.bids[0].quantity_base, 0.0246);
     assert_eq!(orderbook.bids[0].quantity_quote, 37378.26 * 0.0246);
 }
+
+#[test]
+fn l2_orderbook_update_buy_and_sell() {
+    let raw_msg = r#"{"type":"l2update","product_id":"BTC-USD","changes":[["buy","37378.26","0.02460000"],["sell","37380.01","0.00000000"],["sell","37381.5","0.15000000"]],"time":"2021-06-02T09:02:09.048568Z"}"#;
+    let orderbook = &parse_l2("coinbase_pro", MarketType::Spot, raw_msg, None).unwrap()[0];
+
+    assert_eq!(orderbook.asks.len(), 2);
+    assert_eq!(orderbook.bids.len(), 1);
+    assert!(!orderbook.snapshot);
+
+    crate::utils::check_orderbook_fields(
+        "coinbase_pro",
+        MarketType::Spot,
+        "BTC/USD".to_string(),
+        extract_symbol("coinbase_pro", MarketType::Spot, raw_msg).unwrap(),
+        orderbook,
+    );
+
+    assert_eq!(orderbook.bids[0].price, 37378.26);
+    assert_eq!(orderbook.bids[0].quantity_base, 0.0246);
+    assert_eq!(orderbook.bids[0].quantity_contract, None);
+
+    // size "0" removes the price level
+    assert_eq!(orderbook.asks[0].price, 37380.01);
+    assert_eq!(orderbook.asks[0].quantity_base, 0.0);
+    assert_eq!(orderbook.asks[0].quantity_quote, 0.0);
+    assert_eq!(orderbook.asks[0].quantity_contract, None);
+
+    assert_eq!(orderbook.asks[1].price, 37381.5);
+    assert_eq!(orderbook.asks[1].quantity_base, 0.15);
+    assert_eq!(orderbook.asks[1].quantity_quote, 37381.5 * 0.15);
+}
+
+#[test]
+fn l2_orderbook_update_buy_removal() {
+    let raw_msg = r#"{"type":"l2update","product_id":"BTC-USD","changes":[["buy","37378.26","0"]],"time":"2021-06-02T09:02:09.048568Z"}"#;
+    let orderbook = &parse_l2("coinbase_pro", MarketType::Spot, raw_msg, None).unwrap()[0];
+
+    assert_eq!(orderbook.asks.len(), 0);
+    assert_eq!(orderbook.bids.len(), 1);
+    assert_eq!(orderbook.bids[0].price, 37378.26);
+    assert_eq!(orderbook.bids[0].quantity_base, 0.0);
+    assert_eq!(orderbook.bids[0].quantity_quote, 0.0);
+}
+
+#[test]
+fn l2_orderbook_snapshot_one_sided() {
+    let raw_msg = r#"{"type":"snapshot","product_id":"BTC-USD","asks":[],"bids":[["37209.96","0.04016376"]]}"#;
+    let orderbook = &parse_l2(
+        "coinbase_pro",
+        MarketType::Spot,
+        raw_msg,
+        Some(Utc::now().timestamp_millis()),
+    )
+    .unwrap()[0];
+
+    assert!(orderbook.snapshot);
+    assert_eq!(orderbook.asks.len(), 0);
+    assert_eq!(orderbook.bids.len(), 1);
+    assert_eq!(orderbook.bids[0].quantity_contract, None);
+
+    let raw_msg = r#"{"type":"snapshot","product_id":"BTC-USD","asks":[["37212.77","0.05724592"]],"bids":[]}"#;
+    let orderbook = &parse_l2(
+        "coinbase_pro",
+        MarketType::Spot,
+        raw_msg,
+        Some(Utc::now().timestamp_millis()),
+    )
+    .unwrap()[0];
+
+    assert!(orderbook.snapshot);
+    assert_eq!(orderbook.asks.len(), 1);
+    assert_eq!(orderbook.bids.len(), 0);
+}
+
+#[test]
+fn l2_orderbook_batch_update() {
+    // level2_batch pushes l2update messages with changes of 50 milliseconds
+    let raw_msg = r#"{"type":"l2update","product_id":"ETH-USD","changes":[["sell","2648.31","0.37800000"],["buy","2647.52","0.00000000"],["sell","2648.95","0.00000000"],["buy","2647.36","1.20000000"],["buy","2646.12","0.75612000"]],"time":"2021-06-02T09:02:09.098152Z"}"#;
+    let orderbook = &parse_l2("coinbase_pro", MarketType::Spot, raw_msg, None).unwrap()[0];
+
+    assert!(!orderbook.snapshot);
+    assert_eq!(orderbook.symbol, "ETH-USD");
+    assert_eq!(orderbook.pair, "ETH/USD");
+    assert_eq!(orderbook.timestamp, 1622624529098);
+    assert_eq!(orderbook.timestamp_us, Some(1622624529098152));
+
+    assert_eq!(orderbook.asks.len(), 2);
+    assert_eq!(orderbook.asks[0].price, 2648.31);
+    assert_eq!(orderbook.asks[0].quantity_base, 0.378);
+    assert_eq!(orderbook.asks[1].price, 2648.95);
+    assert_eq!(orderbook.asks[1].quantity_base, 0.0);
+
+    assert_eq!(orderbook.bids.len(), 3);
+    assert_eq!(orderbook.bids[0].price, 2647.52);
+    assert_eq!(orderbook.bids[0].quantity_base, 0.0);
+    assert_eq!(orderbook.bids[1].price, 2647.36);
+    assert_eq!(orderbook.bids[1].quantity_base, 1.2);
+    assert_eq!(orderbook.bids[2].price, 2646.12);
+    assert_eq!(orderbook.bids[2].quantity_base, 0.75612);
+}
+
+#[test]
+fn l2_orderbook_unknown_type() {
+    let raw_msg = r#"{"type":"subscriptions","channels":[{"name":"level2_batch","product_ids":["BTC-USD"]}]}"#;
+    assert!(parse_l2("coinbase_pro", MarketType::Spot, raw_msg, None).is_err());
+}
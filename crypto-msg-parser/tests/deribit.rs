@@ -363,3 +363,52 @@ mod l2_orderbook {
         assert_eq!(orderbook.asks[2].quantity_contract.unwrap(), 0.5);
     }
 }
+
+#[cfg(test)]
+mod funding_rate {
+    use crypto_msg_parser::{extract_symbol, parse_funding_rate, MarketType};
+
+    #[test]
+    fn inverse_swap() {
+        let raw_msg = r#"{"jsonrpc":"2.0","method":"subscription","params":{"channel":"perpetual.BTC-PERPETUAL.raw","data":{"timestamp":1616321478553,"interest":0.0000071,"index_price":56173.74,"funding_8h":0.00022934}}}"#;
+        let funding_rates =
+            &parse_funding_rate("deribit", MarketType::InverseSwap, raw_msg).unwrap();
+
+        assert_eq!(funding_rates.len(), 1);
+
+        for rate in funding_rates.iter() {
+            crate::utils::check_funding_rate_fields("deribit", MarketType::InverseSwap, rate);
+        }
+
+        assert_eq!(
+            "BTC-PERPETUAL",
+            extract_symbol("deribit", MarketType::InverseSwap, raw_msg).unwrap()
+        );
+        assert_eq!(funding_rates[0].symbol, "BTC-PERPETUAL");
+        assert_eq!(funding_rates[0].pair, "BTC/USD");
+        assert_eq!(funding_rates[0].timestamp, 1616321478553);
+        assert_eq!(funding_rates[0].funding_rate, 0.00022934);
+        assert_eq!(funding_rates[0].funding_time, 1616342400000);
+        assert_eq!(funding_rates[0].mark_price, None);
+    }
+
+    #[test]
+    fn inverse_swap_ticker() {
+        let raw_msg = r#"{"jsonrpc":"2.0","method":"subscription","params":{"channel":"ticker.ETH-PERPETUAL.raw","data":{"timestamp":1616342400000,"state":"open","settlement_price":1805.46,"open_interest":31583598,"min_price":1754.95,"max_price":1808.45,"mark_price":1781.7,"last_price":1781.55,"interest_value":0.0006262,"instrument_name":"ETH-PERPETUAL","index_price":1781.26,"funding_8h":-0.00001226,"current_funding":0.0,"best_bid_price":1781.55,"best_bid_amount":20,"best_ask_price":1781.6,"best_ask_amount":1}}}"#;
+        let funding_rates =
+            &parse_funding_rate("deribit", MarketType::InverseSwap, raw_msg).unwrap();
+
+        assert_eq!(funding_rates.len(), 1);
+        crate::utils::check_funding_rate_fields(
+            "deribit",
+            MarketType::InverseSwap,
+            &funding_rates[0],
+        );
+
+        assert_eq!(funding_rates[0].pair, "ETH/USD");
+        assert_eq!(funding_rates[0].funding_rate, -0.00001226);
+        // exactly on the boundary, the next one is 8 hours later
+        assert_eq!(funding_rates[0].funding_time, 1616371200000);
+        assert_eq!(funding_rates[0].mark_price, Some(1781.7));
+    }
+}
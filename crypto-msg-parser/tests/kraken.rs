@@ -1,6 +1,6 @@
 mod utils;
 
-use crypto_msg_parser::{extract_symbol, parse_l2, parse_trade, MarketType, TradeSide};
+use crypto_msg_parser::{extract_symbol, parse_l2, parse_trade, MarketType, ParseError, TradeSide};
 
 #[test]
 fn trade() {
@@ -20,6 +20,29 @@ fn trade() {
     assert_eq!(trade.side, TradeSide::Buy);
 }
 
+#[test]
+fn trade_invalid_side() {
+    let raw_msg =
+        r#"[321,[["57126.70000","0.02063928","1616333924.737428","x","m",""]],"trade","XBT/USD"]"#;
+    let err = parse_trade("kraken", MarketType::Spot, raw_msg)
+        .err()
+        .unwrap();
+
+    if let ParseError::InvalidField {
+        exchange,
+        field,
+        value,
+        ..
+    } = err
+    {
+        assert_eq!("kraken", exchange);
+        assert_eq!("side", field);
+        assert_eq!("x", value);
+    } else {
+        panic!("Expected ParseError::InvalidField");
+    }
+}
+
 #[test]
 fn l2_orderbook_snapshot() {
     let raw_msg = r#"[6304,{"as":[],"bs":[]},"book-25","PERP/EUR"]"#;
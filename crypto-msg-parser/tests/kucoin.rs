@@ -21,6 +21,8 @@ mod trade {
             trade,
         );
 
+        assert_eq!(trade.timestamp, 1616362370760);
+        assert_eq!(trade.timestamp_us, Some(1616362370760468));
         assert_eq!(trade.quantity_base, 0.00013064);
         assert_eq!(trade.quantity_contract, None);
         assert_eq!(trade.side, TradeSide::Buy);
@@ -56,6 +58,8 @@ mod trade {
         ));
         assert_eq!(trade.quantity_contract, Some(16.0));
         assert_eq!(trade.side, TradeSide::Buy);
+        assert_eq!(trade.timestamp, 1616362645429);
+        assert_eq!(trade.timestamp_us, Some(1616362645429686));
     }
 
     #[test]
@@ -130,6 +134,10 @@ mod l2_orderbook {
         assert_eq!(orderbook.bids[0].price, 39272.0);
         assert_eq!(orderbook.bids[0].quantity_base, 0.0530867);
         assert_eq!(orderbook.bids[0].quantity_quote, 39272.0 * 0.0530867);
+        assert_eq!(orderbook.bids[0].quantity_contract, None);
+        assert_eq!(orderbook.prev_seq_id, Some(1617071937789));
+
+        assert!(parse_l2("kucoin", MarketType::Spot, raw_msg, None).is_err());
     }
 
     #[test]
@@ -25,6 +25,26 @@ mod trade {
         assert_eq!(trade.side, TradeSide::Buy);
     }
 
+    #[test]
+    fn spot_with_socketio_prefix() {
+        let raw_msg = r#"42["push.symbol",{"symbol":"BTC_USDT","data":{"deals":[{"t":1616373554541,"p":"57005.89","q":"0.007811","T":2}]}}]"#;
+        let trades = &parse_trade("mxc", MarketType::Spot, raw_msg).unwrap();
+
+        assert_eq!(trades.len(), 1);
+        let trade = &trades[0];
+
+        crate::utils::check_trade_fields(
+            "mxc",
+            MarketType::Spot,
+            "BTC/USDT".to_string(),
+            extract_symbol("mxc", MarketType::Spot, raw_msg).unwrap(),
+            trade,
+        );
+
+        assert_eq!(trade.price, 57005.89);
+        assert_eq!(trade.side, TradeSide::Sell);
+    }
+
     #[test]
     fn linear_swap() {
         let raw_msg = r#"{"channel":"push.deal","data":{"M":1,"O":3,"T":2,"p":57602,"t":1616370338806,"v":14},"symbol":"BTC_USDT","ts":1616370338806}"#;
@@ -118,6 +138,32 @@ mod l2_orderbook {
         assert_eq!(orderbook.bids[0].quantity_quote, 1908.06663);
     }
 
+    #[test]
+    fn spot_update_with_socketio_prefix() {
+        let raw_msg = r#"42["push.symbol",{"symbol":"BTC_USDT","data":{"asks":[{"p":"38941.81","q":"0.012000","a":"467.30172"}]}}]"#;
+        let orderbook = &parse_l2(
+            "mxc",
+            MarketType::Spot,
+            raw_msg,
+            Some(Utc::now().timestamp_millis()),
+        )
+        .unwrap()[0];
+
+        assert_eq!(orderbook.asks.len(), 1);
+        assert!(orderbook.bids.is_empty());
+
+        crate::utils::check_orderbook_fields(
+            "mxc",
+            MarketType::Spot,
+            "BTC/USDT".to_string(),
+            extract_symbol("mxc", MarketType::Spot, raw_msg).unwrap(),
+            orderbook,
+        );
+
+        assert_eq!(orderbook.asks[0].price, 38941.81);
+        assert_eq!(orderbook.asks[0].quantity_base, 0.012);
+    }
+
     #[test]
     fn linear_swap_update() {
         let raw_msg = r#"{"channel":"push.depth","data":{"asks":[[38704.5,138686,1]],"bids":[],"version":2427341830},"symbol":"BTC_USDT","ts":1622722473816}"#;
@@ -215,6 +215,34 @@ mod funding_rate {
     }
 }
 
+#[cfg(test)]
+mod index_price {
+    use crypto_msg_parser::{parse_index_price, MarketType, MessageType};
+
+    #[test]
+    fn index_ticker() {
+        let raw_msg = r#"{"table":"index/ticker","data":[{"last":"58857.3","open_24h":"58713.1","high_24h":"59849.2","low_24h":"57942.5","instrument_id":"BTC-USD","timestamp":"2021-04-01T20:27:00.003Z"},{"last":"1975.21","open_24h":"1918.62","high_24h":"1990.8","low_24h":"1899.75","instrument_id":"ETH-USDT","timestamp":"2021-04-01T20:27:00.012Z"}]}"#;
+        let index_prices = &parse_index_price("okex", MarketType::InverseSwap, raw_msg).unwrap();
+
+        assert_eq!(index_prices.len(), 2);
+        assert_eq!(index_prices[0].exchange, "okex");
+        assert_eq!(index_prices[0].market_type, MarketType::InverseSwap);
+        assert_eq!(index_prices[0].msg_type, MessageType::IndexPrice);
+        assert_eq!(index_prices[0].symbol, "BTC-USD");
+        assert_eq!(index_prices[0].pair, "BTC/USD");
+        assert_eq!(index_prices[0].timestamp, 1617308820003);
+        assert_eq!(index_prices[0].index_price, 58857.3);
+        assert_eq!(index_prices[1].pair, "ETH/USDT");
+        assert_eq!(index_prices[1].index_price, 1975.21);
+    }
+
+    #[test]
+    fn other_table() {
+        let raw_msg = r#"{"table":"swap/ticker","data":[{"last":"58857.3","instrument_id":"BTC-USD-SWAP","timestamp":"2021-04-01T20:27:00.003Z"}]}"#;
+        assert!(parse_index_price("okex", MarketType::InverseSwap, raw_msg).is_err());
+    }
+}
+
 #[cfg(test)]
 mod l2_orderbook {
     use crypto_msg_parser::{extract_symbol, parse_l2, MarketType};
@@ -360,3 +388,449 @@ mod l2_orderbook {
         assert_eq!(orderbook.asks[0].quantity_contract.unwrap(), 906.0);
     }
 }
+
+#[cfg(test)]
+mod bbo {
+    use crypto_msg_parser::{extract_symbol, parse_bbo, MarketType};
+    use float_cmp::approx_eq;
+
+    #[test]
+    fn spot() {
+        let raw_msg = r#"{"table":"spot/ticker","data":[{"last":"47305.8","open_24h":"47682.7","best_bid":"47305.7","high_24h":"48325.1","low_24h":"46900","open_utc0":"47939.9","open_utc8":"47712.3","base_volume_24h":"8026.07289545","quote_volume_24h":"381716596.49432264","best_ask":"47305.8","instrument_id":"BTC-USDT","timestamp":"2021-10-02T07:56:30.122Z","best_bid_size":"1.27376622","best_ask_size":"0.15263413","last_qty":"0.00134576"}]}"#;
+        let bbo = &parse_bbo("okex", MarketType::Spot, raw_msg, None).unwrap()[0];
+
+        crate::utils::check_bbo_fields(
+            "okex",
+            MarketType::Spot,
+            "BTC/USDT".to_string(),
+            extract_symbol("okex", MarketType::Spot, raw_msg).unwrap(),
+            bbo,
+        );
+
+        assert_eq!(bbo.timestamp, 1633161390122);
+        assert_eq!(bbo.bid_price, 47305.7);
+        assert_eq!(bbo.bid_quantity, 1.27376622);
+        assert_eq!(bbo.ask_price, 47305.8);
+        assert_eq!(bbo.ask_quantity, 0.15263413);
+    }
+
+    #[test]
+    fn linear_swap() {
+        let raw_msg = r#"{"table":"swap/ticker","data":[{"last":"47291.9","open_24h":"47661.2","best_bid":"47291.9","high_24h":"48300","low_24h":"46880","volume_24h":"1934532","volume_token_24h":"19345.32","best_ask":"47292","open_interest":"1047530","instrument_id":"BTC-USDT-SWAP","timestamp":"2021-10-02T07:56:31.237Z","best_bid_size":"152","best_ask_size":"67","last_qty":"5"}]}"#;
+        let bbo = &parse_bbo("okex", MarketType::LinearSwap, raw_msg, None).unwrap()[0];
+
+        crate::utils::check_bbo_fields(
+            "okex",
+            MarketType::LinearSwap,
+            "BTC/USDT".to_string(),
+            extract_symbol("okex", MarketType::LinearSwap, raw_msg).unwrap(),
+            bbo,
+        );
+
+        assert_eq!(bbo.timestamp, 1633161391237);
+        assert_eq!(bbo.bid_price, 47291.9);
+        assert!(approx_eq!(f64, bbo.bid_quantity, 1.52, epsilon = 0.0000001));
+        assert_eq!(bbo.ask_price, 47292.0);
+        assert!(approx_eq!(f64, bbo.ask_quantity, 0.67, epsilon = 0.0000001));
+    }
+}
+
+#[cfg(test)]
+mod empty_data {
+    use crypto_msg_parser::{
+        extract_symbol, parse_bbo, parse_funding_rate, parse_l2, parse_trade, MarketType,
+    };
+
+    #[test]
+    fn empty_data() {
+        let raw_msg = r#"{"table":"spot/depth_l2_tbt","data":[]}"#;
+        assert!(parse_trade("okex", MarketType::Spot, raw_msg)
+            .unwrap()
+            .is_empty());
+        assert!(parse_l2("okex", MarketType::Spot, raw_msg, None)
+            .unwrap()
+            .is_empty());
+        assert!(parse_bbo("okex", MarketType::Spot, raw_msg, None)
+            .unwrap()
+            .is_empty());
+        assert!(parse_funding_rate("okex", MarketType::LinearSwap, raw_msg)
+            .unwrap()
+            .is_empty());
+        assert_eq!(None, extract_symbol("okex", MarketType::Spot, raw_msg));
+    }
+
+    #[test]
+    fn subscribe_response() {
+        let raw_msg = r#"{"event":"subscribe","channel":"spot/trade:BTC-USDT"}"#;
+        assert_eq!(None, extract_symbol("okex", MarketType::Spot, raw_msg));
+        assert!(parse_trade("okex", MarketType::Spot, raw_msg).is_err());
+    }
+}
+
+#[cfg(test)]
+mod v5 {
+    use crypto_msg_parser::{extract_symbol, parse_l2, parse_trade, MarketType, TradeSide};
+    use float_cmp::approx_eq;
+
+    #[test]
+    fn spot_trade() {
+        let raw_msg = r#"{"arg":{"channel":"trades","instId":"BTC-USDT"},"data":[{"instId":"BTC-USDT","tradeId":"130639474","px":"42219.9","sz":"0.12060306","side":"buy","ts":"1630048897897"}]}"#;
+        let trades = &parse_trade("okex", MarketType::Spot, raw_msg).unwrap();
+
+        assert_eq!(trades.len(), 1);
+        let trade = &trades[0];
+
+        crate::utils::check_trade_fields(
+            "okex",
+            MarketType::Spot,
+            "BTC/USDT".to_string(),
+            extract_symbol("okex", MarketType::Spot, raw_msg).unwrap(),
+            trade,
+        );
+
+        assert_eq!(trade.timestamp, 1630048897897);
+        assert_eq!(trade.price, 42219.9);
+        assert_eq!(trade.quantity_base, 0.12060306);
+        assert_eq!(trade.quantity_contract, None);
+        assert_eq!(trade.side, TradeSide::Buy);
+        assert_eq!(trade.trade_id, "130639474");
+    }
+
+    #[test]
+    fn linear_swap_trade() {
+        let raw_msg = r#"{"arg":{"channel":"trades","instId":"BTC-USDT-SWAP"},"data":[{"instId":"BTC-USDT-SWAP","tradeId":"165233366","px":"47313.1","sz":"3","side":"sell","ts":"1630049009785"}]}"#;
+        let trades = &parse_trade("okex", MarketType::LinearSwap, raw_msg).unwrap();
+
+        assert_eq!(trades.len(), 1);
+        let trade = &trades[0];
+
+        crate::utils::check_trade_fields(
+            "okex",
+            MarketType::LinearSwap,
+            "BTC/USDT".to_string(),
+            extract_symbol("okex", MarketType::LinearSwap, raw_msg).unwrap(),
+            trade,
+        );
+
+        assert!(approx_eq!(
+            f64,
+            trade.quantity_base,
+            0.01 * 3.0,
+            epsilon = 0.000000001
+        ));
+        assert_eq!(trade.quantity_contract, Some(3.0));
+        assert_eq!(trade.side, TradeSide::Sell);
+    }
+
+    #[test]
+    fn spot_l2_snapshot() {
+        let raw_msg = r#"{"arg":{"channel":"books","instId":"BTC-USDT"},"action":"snapshot","data":[{"asks":[["47269.9","0.39441932","0","6"],["47270","0.01","0","1"]],"bids":[["47269.8","0.60558097","0","7"],["47269.7","0.00021156","0","1"]],"ts":"1630049128469","checksum":-1303286658}]}"#;
+        let orderbook = &parse_l2("okex", MarketType::Spot, raw_msg, None).unwrap()[0];
+
+        assert_eq!(orderbook.asks.len(), 2);
+        assert_eq!(orderbook.bids.len(), 2);
+        assert!(orderbook.snapshot);
+
+        crate::utils::check_orderbook_fields(
+            "okex",
+            MarketType::Spot,
+            "BTC/USDT".to_string(),
+            extract_symbol("okex", MarketType::Spot, raw_msg).unwrap(),
+            orderbook,
+        );
+
+        assert_eq!(orderbook.timestamp, 1630049128469);
+        assert_eq!(orderbook.bids[0].price, 47269.8);
+        assert_eq!(orderbook.bids[0].quantity_base, 0.60558097);
+        assert_eq!(orderbook.asks[0].price, 47269.9);
+        assert_eq!(orderbook.asks[0].quantity_base, 0.39441932);
+    }
+
+    #[test]
+    fn linear_swap_l2_update() {
+        let raw_msg = r#"{"arg":{"channel":"books","instId":"BTC-USDT-SWAP"},"action":"update","data":[{"asks":[["47315.3","12","0","2"]],"bids":[["47301.5","0","0","0"]],"ts":"1630049160372","checksum":-618466306}]}"#;
+        let orderbook = &parse_l2("okex", MarketType::LinearSwap, raw_msg, None).unwrap()[0];
+
+        assert_eq!(orderbook.asks.len(), 1);
+        assert_eq!(orderbook.bids.len(), 1);
+        assert!(!orderbook.snapshot);
+
+        crate::utils::check_orderbook_fields(
+            "okex",
+            MarketType::LinearSwap,
+            "BTC/USDT".to_string(),
+            extract_symbol("okex", MarketType::LinearSwap, raw_msg).unwrap(),
+            orderbook,
+        );
+
+        assert_eq!(orderbook.timestamp, 1630049160372);
+        assert_eq!(orderbook.bids[0].quantity_base, 0.0);
+        assert_eq!(orderbook.asks[0].quantity_contract, Some(12.0));
+        assert!(approx_eq!(
+            f64,
+            orderbook.asks[0].quantity_base,
+            0.01 * 12.0,
+            epsilon = 0.000000001
+        ));
+    }
+
+    #[test]
+    fn subscribe_response() {
+        let raw_msg = r#"{"event":"subscribe","arg":{"channel":"trades","instId":"BTC-USDT"}}"#;
+        assert_eq!(None, extract_symbol("okex", MarketType::Spot, raw_msg));
+    }
+}
+
+#[cfg(test)]
+mod numeric_strings {
+    use crypto_msg_parser::{parse_l2, parse_trade, MarketType};
+
+    #[test]
+    fn empty_size_in_update() {
+        let raw_msg = r#"{"table":"spot/depth_l2_tbt","action":"update","data":[{"instrument_id":"BTC-USDT","asks":[["38888.7","4.14263198","0","12"]],"bids":[["38886.3","","0","0"]],"timestamp":"2021-06-03T12:40:09.962Z","checksum":976527820}]}"#;
+        let orderbook = &parse_l2("okex", MarketType::Spot, raw_msg, None).unwrap()[0];
+
+        assert_eq!(orderbook.bids.len(), 1);
+        assert_eq!(orderbook.bids[0].price, 38886.3);
+        assert_eq!(orderbook.bids[0].quantity_base, 0.0);
+        assert_eq!(orderbook.bids[0].quantity_quote, 0.0);
+        assert_eq!(orderbook.asks[0].quantity_base, 4.14263198);
+    }
+
+    #[test]
+    fn empty_size_in_v5_update() {
+        let raw_msg = r#"{"arg":{"channel":"books","instId":"BTC-USDT"},"action":"update","data":[{"asks":[["47315.3","","0","0"]],"bids":[],"ts":"1630049160372","checksum":-618466306}]}"#;
+        let orderbook = &parse_l2("okex", MarketType::Spot, raw_msg, None).unwrap()[0];
+
+        assert_eq!(orderbook.asks.len(), 1);
+        assert_eq!(orderbook.asks[0].quantity_base, 0.0);
+    }
+
+    #[test]
+    fn malformed_number() {
+        let raw_msg = r#"{"table":"spot/depth_l2_tbt","action":"update","data":[{"instrument_id":"BTC-USDT","asks":[["38888.7","abc","0","12"]],"bids":[],"timestamp":"2021-06-03T12:40:09.962Z","checksum":976527820}]}"#;
+        assert!(parse_l2("okex", MarketType::Spot, raw_msg, None).is_err());
+
+        let raw_msg = r#"{"table":"spot/trade","data":[{"side":"sell","trade_id":"161659503","price":"56,593.6","size":"0.00020621","instrument_id":"BTC-USDT","timestamp":"2021-03-22T01:16:28.687Z"}]}"#;
+        assert!(parse_trade("okex", MarketType::Spot, raw_msg).is_err());
+    }
+}
+
+#[cfg(test)]
+mod delete_level {
+    use crypto_msg_parser::{parse_l2, MarketType, Order};
+
+    // A size of "0" deletes a level, it must be kept as a 0-quantity order
+    fn assert_deleted(order: &Order, price: f64, contract: Option<f64>) {
+        assert_eq!(order.price, price);
+        assert_eq!(order.quantity_base, 0.0);
+        assert_eq!(order.quantity_quote, 0.0);
+        assert_eq!(order.quantity_contract, contract);
+    }
+
+    #[test]
+    fn spot_update() {
+        let raw_msg = r#"{"table":"spot/depth_l2_tbt","action":"update","data":[{"instrument_id":"BTC-USDT","asks":[["38930","0","0","0"]],"bids":[["38929.9","0","0","0"],["38925.7","0.00062109","0","2"]],"timestamp":"2021-06-03T12:40:09.962Z","checksum":976527820}]}"#;
+        let orderbook = &parse_l2("okex", MarketType::Spot, raw_msg, None).unwrap()[0];
+
+        assert!(!orderbook.snapshot);
+        assert_eq!(orderbook.asks.len(), 1);
+        assert_eq!(orderbook.bids.len(), 2);
+        assert_deleted(&orderbook.asks[0], 38930.0, None);
+        assert_deleted(&orderbook.bids[0], 38929.9, None);
+        assert_eq!(orderbook.bids[1].quantity_base, 0.00062109);
+    }
+
+    #[test]
+    fn inverse_swap_update() {
+        let raw_msg = r#"{"table":"swap/depth_l2_tbt","action":"update","data":[{"instrument_id":"BTC-USD-SWAP","asks":[["39167.2","0","0","0"]],"bids":[["39167.1","0","0","0"]],"timestamp":"2021-06-03T13:14:24.831Z","checksum":-1582320415}]}"#;
+        let orderbook = &parse_l2("okex", MarketType::InverseSwap, raw_msg, None).unwrap()[0];
+
+        assert!(!orderbook.snapshot);
+        assert_deleted(&orderbook.asks[0], 39167.2, Some(0.0));
+        assert_deleted(&orderbook.bids[0], 39167.1, Some(0.0));
+    }
+
+    #[test]
+    fn v5_spot_update() {
+        let raw_msg = r#"{"arg":{"channel":"books","instId":"BTC-USDT"},"action":"update","data":[{"asks":[["47315.3","0","0","0"]],"bids":[],"ts":"1630049160372","checksum":-618466306}]}"#;
+        let orderbook = &parse_l2("okex", MarketType::Spot, raw_msg, None).unwrap()[0];
+
+        assert!(!orderbook.snapshot);
+        assert_eq!(orderbook.asks.len(), 1);
+        assert!(orderbook.bids.is_empty());
+        assert_deleted(&orderbook.asks[0], 47315.3, None);
+    }
+
+    #[test]
+    fn v5_linear_swap_update() {
+        let raw_msg = r#"{"arg":{"channel":"books","instId":"BTC-USDT-SWAP"},"action":"update","data":[{"asks":[["47315.3","0","0","0"]],"bids":[["47301.5","0","0","0"]],"ts":"1630049160372","checksum":-618466306}]}"#;
+        let orderbook = &parse_l2("okex", MarketType::LinearSwap, raw_msg, None).unwrap()[0];
+
+        assert!(!orderbook.snapshot);
+        assert_deleted(&orderbook.asks[0], 47315.3, Some(0.0));
+        assert_deleted(&orderbook.bids[0], 47301.5, Some(0.0));
+    }
+}
+
+#[cfg(test)]
+mod open_interest {
+    use crypto_msg_parser::{parse_open_interest, MarketType, MessageType};
+
+    #[test]
+    fn inverse_swap() {
+        let raw_msg = r#"{"instrument_id":"BTC-USD-SWAP","amount":"2493578","timestamp":"2021-07-15T06:20:13.405Z"}"#;
+        let open_interests =
+            &parse_open_interest("okex", MarketType::InverseSwap, raw_msg).unwrap();
+
+        assert_eq!(open_interests.len(), 1);
+        let open_interest = &open_interests[0];
+        assert_eq!(open_interest.exchange, "okex");
+        assert_eq!(open_interest.market_type, MarketType::InverseSwap);
+        assert_eq!(open_interest.msg_type, MessageType::OpenInterest);
+        assert_eq!(open_interest.symbol, "BTC-USD-SWAP");
+        assert_eq!(open_interest.pair, "BTC/USD");
+        assert_eq!(open_interest.timestamp, 1626330013405);
+        assert_eq!(open_interest.open_interest, 2493578.0);
+        assert_eq!(open_interest.open_interest_value, Some(249357800.0));
+    }
+
+    #[test]
+    fn linear_future() {
+        let raw_msg = r#"{"instrument_id":"BTC-USDT-210924","amount":"1037514","timestamp":"2021-07-15T06:20:13.405Z"}"#;
+        let open_interests =
+            &parse_open_interest("okex", MarketType::LinearFuture, raw_msg).unwrap();
+
+        assert_eq!(open_interests.len(), 1);
+        assert_eq!(open_interests[0].pair, "BTC/USDT");
+        assert_eq!(open_interests[0].open_interest, 1037514.0);
+        assert_eq!(open_interests[0].open_interest_value, None);
+    }
+}
+
+#[cfg(test)]
+mod l2_snapshot {
+    use crypto_msg_parser::{parse_l2_snapshot, MarketType, MessageType};
+
+    #[test]
+    fn spot() {
+        let raw_msg = r#"{"asks":[["35000.2","0.5","1"],["35000.1","0.1","2"]],"bids":[["34999.9","2","3"]],"timestamp":"2021-06-30T23:59:59.123Z"}"#;
+        let orderbook =
+            &parse_l2_snapshot("okex", MarketType::Spot, "BTC-USDT", raw_msg).unwrap()[0];
+
+        assert_eq!(orderbook.msg_type, MessageType::L2Snapshot);
+        assert!(orderbook.snapshot);
+        assert_eq!(orderbook.pair, "BTC/USDT");
+        assert_eq!(orderbook.timestamp, 1625097599123);
+        assert_eq!(orderbook.seq_id, None);
+        assert_eq!(orderbook.asks[0].price, 35000.1);
+        assert_eq!(orderbook.bids[0].quantity_base, 2.0);
+    }
+
+    #[test]
+    fn linear_swap() {
+        let raw_msg = r#"{"asks":[["35000.1","10","0","2"]],"bids":[["34999.9","20","0","3"]],"time":"2021-06-30T23:59:59.123Z"}"#;
+        let orderbook = &parse_l2_snapshot(
+            "okex",
+            MarketType::LinearSwap,
+            "BTC-USDT-SWAP",
+            raw_msg,
+        )
+        .unwrap()[0];
+
+        assert_eq!(orderbook.pair, "BTC/USDT");
+        assert_eq!(orderbook.timestamp, 1625097599123);
+        assert_eq!(orderbook.asks[0].quantity_contract, Some(10.0));
+        assert_eq!(orderbook.asks[0].quantity_base, 10.0 * 0.01);
+    }
+}
+
+#[cfg(test)]
+mod deep_books {
+    use crypto_msg_parser::{parse_l2, MarketType};
+
+    // `num_levels` levels on each side, asks upwards and bids downwards from 40000
+    fn levels(num_levels: usize) -> (String, String) {
+        let asks = (0..num_levels)
+            .map(|i| format!(r#"["{}","0.{}","0","1"]"#, 40000 + i + 1, i + 1))
+            .collect::<Vec<String>>()
+            .join(",");
+        let bids = (0..num_levels)
+            .map(|i| format!(r#"["{}","0.{}","0","1"]"#, 40000 - i - 1, i + 1))
+            .collect::<Vec<String>>()
+            .join(",");
+        (asks, bids)
+    }
+
+    #[test]
+    fn v3_depth_l2_tbt_partial() {
+        let (asks, bids) = levels(200);
+        let raw_msg = format!(
+            r#"{{"table":"spot/depth_l2_tbt","action":"partial","data":[{{"instrument_id":"BTC-USDT","asks":[{}],"bids":[{}],"timestamp":"2021-06-03T12:39:11.253Z","checksum":0}}]}}"#,
+            asks, bids
+        );
+        let orderbook = &parse_l2("okex", MarketType::Spot, &raw_msg, None).unwrap()[0];
+
+        assert!(orderbook.snapshot);
+        assert_eq!(orderbook.asks.len(), 200);
+        assert_eq!(orderbook.bids.len(), 200);
+        assert_eq!(orderbook.asks[0].price, 40001.0);
+        assert_eq!(orderbook.asks[199].price, 40200.0);
+        assert_eq!(orderbook.bids[0].price, 39999.0);
+        assert_eq!(orderbook.bids[199].price, 39800.0);
+    }
+
+    #[test]
+    fn v3_depth5_without_action() {
+        let (asks, bids) = levels(5);
+        let raw_msg = format!(
+            r#"{{"table":"spot/depth5","data":[{{"instrument_id":"BTC-USDT","asks":[{}],"bids":[{}],"timestamp":"2021-06-03T12:39:11.253Z"}}]}}"#,
+            asks, bids
+        );
+        let orderbook = &parse_l2("okex", MarketType::Spot, &raw_msg, None).unwrap()[0];
+
+        assert!(orderbook.snapshot);
+        assert_eq!(orderbook.asks.len(), 5);
+        assert_eq!(orderbook.bids.len(), 5);
+    }
+
+    #[test]
+    fn v5_books_l2_tbt_snapshot() {
+        let (asks, bids) = levels(400);
+        let raw_msg = format!(
+            r#"{{"arg":{{"channel":"books-l2-tbt","instId":"BTC-USDT"}},"action":"snapshot","data":[{{"asks":[{}],"bids":[{}],"ts":"1630049128469","checksum":0}}]}}"#,
+            asks, bids
+        );
+        let orderbook = &parse_l2("okex", MarketType::Spot, &raw_msg, None).unwrap()[0];
+
+        assert!(orderbook.snapshot);
+        assert_eq!(orderbook.asks.len(), 400);
+        assert_eq!(orderbook.bids.len(), 400);
+        assert_eq!(orderbook.asks[0].price, 40001.0);
+        assert_eq!(orderbook.asks[399].price, 40400.0);
+        assert_eq!(orderbook.bids[0].price, 39999.0);
+        assert_eq!(orderbook.bids[399].price, 39600.0);
+    }
+
+    #[test]
+    fn v5_books50_l2_tbt() {
+        let (asks, bids) = levels(50);
+        let raw_msg = format!(
+            r#"{{"arg":{{"channel":"books50-l2-tbt","instId":"BTC-USDT-SWAP"}},"action":"snapshot","data":[{{"asks":[{}],"bids":[{}],"ts":"1630049128469","checksum":0}}]}}"#,
+            asks, bids
+        );
+        let orderbook = &parse_l2("okex", MarketType::LinearSwap, &raw_msg, None).unwrap()[0];
+
+        assert!(orderbook.snapshot);
+        assert_eq!(orderbook.asks.len(), 50);
+        assert_eq!(orderbook.bids.len(), 50);
+
+        let raw_msg = r#"{"arg":{"channel":"books50-l2-tbt","instId":"BTC-USDT-SWAP"},"action":"update","data":[{"asks":[["40001","0","0","0"]],"bids":[],"ts":"1630049128479","checksum":0}]}"#;
+        let orderbook = &parse_l2("okex", MarketType::LinearSwap, raw_msg, None).unwrap()[0];
+
+        assert!(!orderbook.snapshot);
+        assert_eq!(orderbook.asks.len(), 1);
+        assert_eq!(orderbook.asks[0].quantity_base, 0.0);
+        assert_eq!(orderbook.bids.len(), 0);
+    }
+}
@@ -0,0 +1,229 @@
+mod utils;
+
+use crypto_msg_parser::{Order, OrderBookCompactor, OrderBookMsg};
+use utils::{l2_msg, order};
+
+fn update(
+    symbol: &str,
+    timestamp: i64,
+    seq_id: u64,
+    asks: Vec<Order>,
+    bids: Vec<Order>,
+    snapshot: bool,
+) -> OrderBookMsg {
+    OrderBookMsg {
+        symbol: symbol.to_string(),
+        timestamp,
+        json: format!("raw {}", seq_id),
+        ..l2_msg(seq_id, seq_id - 1, snapshot, asks, bids)
+    }
+}
+
+#[test]
+fn merge_within_window() {
+    let mut compactor = OrderBookCompactor::new(50);
+
+    assert!(compactor
+        .update(update(
+            "BTCUSDT",
+            1000,
+            11,
+            vec![order(100.1, 1.0)],
+            vec![order(99.9, 1.0)],
+            false
+        ))
+        .is_empty());
+    assert!(compactor
+        .update(update(
+            "BTCUSDT",
+            1020,
+            12,
+            vec![order(100.1, 2.0), order(100.2, 1.0)],
+            vec![],
+            false
+        ))
+        .is_empty());
+    // the bid level is removed, the removal must survive merging
+    assert!(compactor
+        .update(update(
+            "BTCUSDT",
+            1049,
+            13,
+            vec![],
+            vec![order(99.9, 0.0)],
+            false
+        ))
+        .is_empty());
+
+    let emitted = compactor.update(update(
+        "BTCUSDT",
+        1050,
+        14,
+        vec![order(100.3, 1.0)],
+        vec![],
+        false,
+    ));
+    assert_eq!(1, emitted.len());
+    let merged = &emitted[0];
+    assert!(!merged.snapshot);
+    assert_eq!(1049, merged.timestamp);
+    assert_eq!(Some(13), merged.seq_id);
+    assert_eq!(Some(10), merged.prev_seq_id);
+    assert!(merged.json.is_empty());
+
+    assert_eq!(2, merged.asks.len());
+    assert_eq!(100.1, merged.asks[0].price);
+    assert_eq!(2.0, merged.asks[0].quantity_base);
+    assert_eq!(100.2, merged.asks[1].price);
+    assert_eq!(1, merged.bids.len());
+    assert_eq!(99.9, merged.bids[0].price);
+    assert_eq!(0.0, merged.bids[0].quantity_base);
+
+    let flushed = compactor.flush();
+    assert_eq!(1, flushed.len());
+    assert_eq!(1050, flushed[0].timestamp);
+    assert_eq!("raw 14", flushed[0].json);
+    assert!(compactor.flush().is_empty());
+}
+
+#[test]
+fn snapshot_boundary() {
+    let mut compactor = OrderBookCompactor::new(50);
+
+    assert!(compactor
+        .update(update(
+            "BTCUSDT",
+            1000,
+            11,
+            vec![order(100.1, 1.0)],
+            vec![],
+            false
+        ))
+        .is_empty());
+
+    let emitted = compactor.update(update(
+        "BTCUSDT",
+        1010,
+        12,
+        vec![order(100.2, 1.0)],
+        vec![order(99.9, 1.0)],
+        true,
+    ));
+    assert_eq!(2, emitted.len());
+    assert!(!emitted[0].snapshot);
+    assert_eq!(1000, emitted[0].timestamp);
+    assert!(emitted[1].snapshot);
+    assert_eq!(1010, emitted[1].timestamp);
+
+    // updates after the snapshot start a new window
+    assert!(compactor
+        .update(update(
+            "BTCUSDT",
+            1020,
+            13,
+            vec![order(100.2, 2.0)],
+            vec![],
+            false
+        ))
+        .is_empty());
+    let flushed = compactor.flush();
+    assert_eq!(1, flushed.len());
+    assert_eq!(Some(13), flushed[0].seq_id);
+}
+
+#[test]
+fn symbols_are_independent() {
+    let mut compactor = OrderBookCompactor::new(50);
+
+    assert!(compactor
+        .update(update(
+            "BTCUSDT",
+            1000,
+            11,
+            vec![order(100.1, 1.0)],
+            vec![],
+            false
+        ))
+        .is_empty());
+    assert!(compactor
+        .update(update(
+            "ETHUSDT",
+            1100,
+            21,
+            vec![order(10.1, 1.0)],
+            vec![],
+            false
+        ))
+        .is_empty());
+
+    let flushed = compactor.flush();
+    assert_eq!(2, flushed.len());
+    assert_eq!("BTCUSDT", flushed[0].symbol);
+    assert_eq!("ETHUSDT", flushed[1].symbol);
+}
+
+#[test]
+fn tick() {
+    let mut compactor = OrderBookCompactor::new(50);
+
+    assert!(compactor
+        .update(update(
+            "BTCUSDT",
+            1000,
+            11,
+            vec![order(100.1, 1.0)],
+            vec![],
+            false
+        ))
+        .is_empty());
+    assert!(compactor
+        .update(update(
+            "ETHUSDT",
+            1030,
+            21,
+            vec![order(3000.1, 1.0)],
+            vec![],
+            false
+        ))
+        .is_empty());
+
+    // no more messages arrive, the windows close by time only
+    assert!(compactor.tick(1049).is_empty());
+    let emitted = compactor.tick(1050);
+    assert_eq!(1, emitted.len());
+    assert_eq!("BTCUSDT", emitted[0].symbol);
+    assert_eq!(Some(11), emitted[0].seq_id);
+
+    let emitted = compactor.tick(1080);
+    assert_eq!(1, emitted.len());
+    assert_eq!("ETHUSDT", emitted[0].symbol);
+    assert!(compactor.flush().is_empty());
+}
+
+#[test]
+fn nan_price() {
+    let mut compactor = OrderBookCompactor::new(50);
+    compactor.update(update(
+        "BTCUSDT",
+        1000,
+        11,
+        vec![order(f64::NAN, 1.0), order(100.1, 1.0)],
+        vec![order(f64::NAN, 1.0), order(99.9, 1.0)],
+        false,
+    ));
+    compactor.update(update(
+        "BTCUSDT",
+        1010,
+        12,
+        vec![order(100.2, 1.0)],
+        vec![],
+        false,
+    ));
+
+    // sorting doesn't panic
+    let emitted = compactor.flush();
+    assert_eq!(1, emitted.len());
+    assert_eq!(3, emitted[0].asks.len());
+    assert_eq!(100.1, emitted[0].asks[0].price);
+    assert_eq!(2, emitted[0].bids.len());
+}
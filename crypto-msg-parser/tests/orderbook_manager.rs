@@ -0,0 +1,380 @@
+mod utils;
+
+use crypto_msg_parser::{
+    MarketType, Order, OrderBookManager, OrderBookMsg, ResyncRequest, SnapshotCadence,
+};
+use utils::{l2_msg, order};
+
+fn levels(orders: &[Order]) -> Vec<(f64, f64)> {
+    orders.iter().map(|x| (x.price, x.quantity_base)).collect()
+}
+
+#[test]
+fn apply_updates() {
+    let mut manager = OrderBookManager::new();
+    // no book to apply to
+    assert!(!manager.update(l2_msg(90, 80, false, vec![order(101.0, 1.0)], vec![])));
+    assert!(manager.update(l2_msg(
+        100,
+        0,
+        true,
+        vec![order(101.0, 1.0), order(102.0, 2.0)],
+        vec![order(99.0, 1.0)]
+    )));
+    // overlaps with the snapshot
+    assert!(manager.update(l2_msg(
+        105,
+        98,
+        false,
+        vec![order(101.0, 0.0), order(103.0, 3.0)],
+        vec![order(98.0, 2.0)]
+    )));
+    // stale
+    assert!(!manager.update(l2_msg(104, 101, false, vec![order(101.0, 5.0)], vec![])));
+
+    let book = manager.get("binance", MarketType::Spot, "BTCUSDT").unwrap();
+    assert!(book.snapshot);
+    assert_eq!(book.seq_id, Some(105));
+    assert_eq!(vec![(102.0, 2.0), (103.0, 3.0)], levels(&book.asks));
+    assert_eq!(vec![(99.0, 1.0), (98.0, 2.0)], levels(&book.bids));
+}
+
+#[test]
+fn resync_on_gap() {
+    let (tx, rx) = std::sync::mpsc::channel();
+    let mut manager = OrderBookManager::new().on_resync(tx);
+    manager.update(l2_msg(
+        100,
+        0,
+        true,
+        vec![order(101.0, 1.0)],
+        vec![order(99.0, 1.0)],
+    ));
+
+    // updates between 100 and 110 are missing
+    assert!(!manager.update(l2_msg(120, 110, false, vec![order(101.0, 0.0)], vec![])));
+    assert_eq!(
+        vec![ResyncRequest {
+            exchange: "binance".to_string(),
+            market_type: MarketType::Spot,
+            symbol: "BTCUSDT".to_string(),
+        }],
+        rx.try_iter().collect::<Vec<ResyncRequest>>()
+    );
+    // out of sync until a new snapshot arrives
+    assert!(manager
+        .get("binance", MarketType::Spot, "BTCUSDT")
+        .is_none());
+    assert!(!manager.update(l2_msg(130, 120, false, vec![order(104.0, 4.0)], vec![])));
+    assert!(!manager.update(l2_msg(140, 130, false, vec![], vec![order(98.0, 2.0)])));
+
+    // updates up to 130 are covered by the snapshot, 140 is applied on top of it
+    assert!(manager.update(l2_msg(
+        130,
+        0,
+        true,
+        vec![order(102.0, 2.0), order(104.0, 4.0)],
+        vec![order(99.0, 1.0)]
+    )));
+    let book = manager.get("binance", MarketType::Spot, "BTCUSDT").unwrap();
+    assert_eq!(book.seq_id, Some(140));
+    assert_eq!(vec![(102.0, 2.0), (104.0, 4.0)], levels(&book.asks));
+    assert_eq!(vec![(99.0, 1.0), (98.0, 2.0)], levels(&book.bids));
+    // the update that revealed the gap is covered by the snapshot
+    assert!(rx.try_recv().is_err());
+}
+
+#[test]
+fn resync_with_older_snapshot() {
+    let (tx, rx) = std::sync::mpsc::channel();
+    let mut manager = OrderBookManager::new().on_resync(tx);
+    manager.update(l2_msg(100, 0, true, vec![order(101.0, 1.0)], vec![]));
+    assert!(!manager.update(l2_msg(120, 110, false, vec![order(102.0, 2.0)], vec![])));
+    assert!(!manager.update(l2_msg(130, 120, false, vec![order(103.0, 3.0)], vec![])));
+    assert_eq!(1, rx.try_iter().count());
+
+    // updates between 105 and 110 are still missing
+    assert!(manager.update(l2_msg(105, 0, true, vec![order(101.0, 1.0)], vec![])));
+    assert_eq!(1, rx.try_iter().count());
+    assert!(manager
+        .get("binance", MarketType::Spot, "BTCUSDT")
+        .is_none());
+
+    // covers the update that revealed the gap
+    assert!(manager.update(l2_msg(110, 0, true, vec![order(101.0, 1.0)], vec![])));
+    let book = manager.get("binance", MarketType::Spot, "BTCUSDT").unwrap();
+    assert_eq!(book.seq_id, Some(130));
+    assert_eq!(
+        vec![(101.0, 1.0), (102.0, 2.0), (103.0, 3.0)],
+        levels(&book.asks)
+    );
+}
+
+#[test]
+fn resync_timeout() {
+    let (tx, rx) = std::sync::mpsc::channel();
+    let mut manager = OrderBookManager::new().on_resync(tx).resync_timeout(20);
+    manager.update(l2_msg(100, 0, true, vec![order(101.0, 1.0)], vec![]));
+    manager.update(l2_msg(120, 110, false, vec![], vec![]));
+    manager.update(l2_msg(130, 120, false, vec![], vec![]));
+    assert_eq!(1, rx.try_iter().count());
+    // timestamps of l2_msg() grow with seq_id, no snapshot 20ms after the request
+    manager.update(l2_msg(140, 130, false, vec![], vec![]));
+    assert_eq!(1, rx.try_iter().count());
+    manager.update(l2_msg(150, 140, false, vec![], vec![]));
+    assert_eq!(0, rx.try_iter().count());
+}
+
+#[test]
+fn max_buffered() {
+    let (tx, rx) = std::sync::mpsc::channel();
+    let mut manager = OrderBookManager::new().on_resync(tx).max_buffered(2);
+    manager.update(l2_msg(100, 0, true, vec![order(101.0, 1.0)], vec![]));
+    manager.update(l2_msg(120, 110, false, vec![], vec![]));
+    manager.update(l2_msg(121, 120, false, vec![], vec![]));
+    assert_eq!(1, rx.try_iter().count());
+
+    // the book is dropped and requested again
+    manager.update(l2_msg(122, 121, false, vec![], vec![]));
+    assert_eq!(1, rx.try_iter().count());
+    assert!(!manager.update(l2_msg(123, 122, false, vec![order(102.0, 2.0)], vec![])));
+
+    assert!(manager.update(l2_msg(123, 0, true, vec![order(103.0, 3.0)], vec![])));
+    let book = manager.get("binance", MarketType::Spot, "BTCUSDT").unwrap();
+    assert_eq!(vec![(103.0, 3.0)], levels(&book.asks));
+}
+
+#[test]
+fn binance_snapshot_sync() {
+    use crypto_msg_parser::{parse_l2, parse_l2_snapshot};
+
+    let mut manager = OrderBookManager::new();
+    let raw_msg = r#"{"lastUpdateId":127531213600,"E":1622368000100,"T":1622368000090,"bids":[["35943.8","10"]],"asks":[["36038.3","5"]]}"#;
+    let snapshot = parse_l2_snapshot(
+        "binance",
+        MarketType::InverseFuture,
+        "BTCUSD_210625",
+        raw_msg,
+    )
+    .unwrap();
+    assert!(manager.update(snapshot.into_iter().next().unwrap()));
+
+    // U <= lastUpdateId <= u
+    let raw_msg = r#"{"stream":"btcusd_210625@depth@100ms","data":{"e":"depthUpdate","E":1622368000245,"T":1622368000234,"s":"BTCUSD_210625","ps":"BTCUSD","U":127531213513,"u":127531213607,"pu":127531213500,"b":[["35943.8","60"]],"a":[]}}"#;
+    let orderbook = parse_l2("binance", MarketType::InverseFuture, raw_msg, None).unwrap();
+    assert!(manager.update(orderbook.into_iter().next().unwrap()));
+
+    // pu equals u of the previous event
+    let raw_msg = r#"{"stream":"btcusd_210625@depth@100ms","data":{"e":"depthUpdate","E":1622368000345,"T":1622368000334,"s":"BTCUSD_210625","ps":"BTCUSD","U":127531213610,"u":127531214406,"pu":127531213607,"b":[],"a":[["36038.3","0"],["36038.4","21"]]}}"#;
+    let orderbook = parse_l2("binance", MarketType::InverseFuture, raw_msg, None).unwrap();
+    assert!(manager.update(orderbook.into_iter().next().unwrap()));
+
+    let book = manager
+        .get("binance", MarketType::InverseFuture, "BTCUSD_210625")
+        .unwrap();
+    assert_eq!(book.seq_id, Some(127531214406));
+    assert_eq!(
+        vec![36038.4],
+        book.asks.iter().map(|x| x.price).collect::<Vec<f64>>()
+    );
+    assert_eq!(
+        vec![35943.8],
+        book.bids.iter().map(|x| x.price).collect::<Vec<f64>>()
+    );
+}
+
+#[test]
+fn top_n_snapshot() {
+    let mut manager = OrderBookManager::new();
+    assert!(manager
+        .snapshot("binance", MarketType::Spot, "BTCUSDT", 2)
+        .is_none());
+    manager.update(l2_msg(
+        100,
+        0,
+        true,
+        vec![order(103.0, 3.0), order(101.0, 1.0), order(102.0, 2.0)],
+        vec![order(98.0, 2.0), order(99.0, 1.0), order(97.0, 3.0)],
+    ));
+    manager.update(l2_msg(101, 100, false, vec![order(101.0, 0.0)], vec![]));
+
+    let book = manager
+        .snapshot("binance", MarketType::Spot, "BTCUSDT", 2)
+        .unwrap();
+    assert!(book.snapshot);
+    assert_eq!(book.seq_id, Some(101));
+    assert_eq!(vec![(102.0, 2.0), (103.0, 3.0)], levels(&book.asks));
+    assert_eq!(vec![(99.0, 1.0), (98.0, 2.0)], levels(&book.bids));
+}
+
+#[test]
+fn snapshot_every_updates() {
+    let (tx, rx) = std::sync::mpsc::channel();
+    let mut manager = OrderBookManager::new().on_snapshot(tx, 1, SnapshotCadence::EveryUpdates(2));
+    manager.update(l2_msg(
+        100,
+        0,
+        true,
+        vec![order(101.0, 1.0)],
+        vec![order(99.0, 1.0)],
+    ));
+    assert!(rx.try_recv().is_err());
+    manager.update(l2_msg(101, 100, false, vec![order(100.5, 1.0)], vec![]));
+    manager.update(l2_msg(102, 101, false, vec![], vec![order(99.5, 1.0)]));
+    // stale updates don't count
+    manager.update(l2_msg(102, 101, false, vec![], vec![order(99.6, 1.0)]));
+    manager.update(l2_msg(103, 102, false, vec![], vec![order(99.0, 0.0)]));
+
+    let snapshots = rx.try_iter().collect::<Vec<OrderBookMsg>>();
+    assert_eq!(2, snapshots.len());
+    assert!(snapshots.iter().all(|x| x.snapshot));
+    assert_eq!(Some(101), snapshots[0].seq_id);
+    assert_eq!(vec![(100.5, 1.0)], levels(&snapshots[0].asks));
+    assert_eq!(vec![(99.0, 1.0)], levels(&snapshots[0].bids));
+    assert_eq!(Some(103), snapshots[1].seq_id);
+    assert_eq!(vec![(99.5, 1.0)], levels(&snapshots[1].bids));
+}
+
+#[test]
+fn snapshot_interval() {
+    let (tx, rx) = std::sync::mpsc::channel();
+    let mut manager = OrderBookManager::new().on_snapshot(tx, 5, SnapshotCadence::Interval(10));
+    manager.update(l2_msg(
+        100,
+        0,
+        true,
+        vec![order(101.0, 1.0)],
+        vec![order(99.0, 1.0)],
+    ));
+    // timestamps of l2_msg() grow with seq_id
+    manager.update(l2_msg(105, 100, false, vec![order(102.0, 2.0)], vec![]));
+    manager.update(l2_msg(111, 105, false, vec![order(103.0, 3.0)], vec![]));
+
+    let snapshots = rx.try_iter().collect::<Vec<OrderBookMsg>>();
+    assert_eq!(
+        vec![Some(100), Some(111)],
+        snapshots
+            .iter()
+            .map(|x| x.seq_id)
+            .collect::<Vec<Option<u64>>>()
+    );
+    assert_eq!(3, snapshots[1].asks.len());
+}
+
+#[test]
+fn okex_v5_checksum() {
+    use crypto_msg_parser::parse_l2;
+
+    let (tx, rx) = std::sync::mpsc::channel();
+    let mut manager = OrderBookManager::new().on_resync(tx);
+
+    let raw_msg = r#"{"arg":{"channel":"books","instId":"BTC-USDT"},"action":"snapshot","data":[{"asks":[["47269.9","0.39441932","0","6"],["47270","0.01","0","1"]],"bids":[["47269.8","0.60558097","0","7"],["47269.7","0.00021156","0","1"]],"ts":"1630049128469","checksum":-1039000099}]}"#;
+    let snapshot = parse_l2("okex", MarketType::Spot, raw_msg, None).unwrap();
+    assert!(manager.update(snapshot.into_iter().next().unwrap()));
+
+    // the checksum covers the whole book after applying the update, and the
+    // original strings, e.g., 47271.0 instead of 47271
+    let raw_msg = r#"{"arg":{"channel":"books","instId":"BTC-USDT"},"action":"update","data":[{"asks":[["47269.9","0","0","0"],["47271.0","1.5","0","2"]],"bids":[["47269.8","0.5","0","3"]],"ts":"1630049128569","checksum":-462313391}]}"#;
+    let orderbook = parse_l2("okex", MarketType::Spot, raw_msg, None).unwrap();
+    assert!(manager.update(orderbook.into_iter().next().unwrap()));
+    assert!(rx.try_recv().is_err());
+
+    let book = manager.get("okex", MarketType::Spot, "BTC-USDT").unwrap();
+    assert_eq!(vec![(47270.0, 0.01), (47271.0, 1.5)], levels(&book.asks));
+    assert_eq!(
+        vec![(47269.8, 0.5), (47269.7, 0.00021156)],
+        levels(&book.bids)
+    );
+
+    // mismatch
+    let raw_msg = r#"{"arg":{"channel":"books","instId":"BTC-USDT"},"action":"update","data":[{"asks":[["47270","0.02","0","1"]],"bids":[],"ts":"1630049128669","checksum":123456}]}"#;
+    let orderbook = parse_l2("okex", MarketType::Spot, raw_msg, None).unwrap();
+    assert!(!manager.update(orderbook.into_iter().next().unwrap()));
+    assert_eq!(
+        ResyncRequest {
+            exchange: "okex".to_string(),
+            market_type: MarketType::Spot,
+            symbol: "BTC-USDT".to_string(),
+        },
+        rx.try_recv().unwrap()
+    );
+    assert!(manager.get("okex", MarketType::Spot, "BTC-USDT").is_none());
+
+    // a new snapshot after resubscribing brings the book back in sync
+    let raw_msg = r#"{"arg":{"channel":"books","instId":"BTC-USDT"},"action":"snapshot","data":[{"asks":[["47269.9","0.39441932","0","6"],["47270","0.01","0","1"]],"bids":[["47269.8","0.60558097","0","7"],["47269.7","0.00021156","0","1"]],"ts":"1630049128769","checksum":-1039000099}]}"#;
+    let snapshot = parse_l2("okex", MarketType::Spot, raw_msg, None).unwrap();
+    assert!(manager.update(snapshot.into_iter().next().unwrap()));
+    assert!(manager.get("okex", MarketType::Spot, "BTC-USDT").is_some());
+}
+
+#[test]
+fn crossed_book() {
+    let mut orderbook = l2_msg(
+        100,
+        0,
+        true,
+        vec![order(101.0, 1.0), order(102.0, 2.0)],
+        vec![order(99.0, 1.0)],
+    );
+    assert!(!orderbook.is_crossed());
+    // unsorted, the best bid is at the best ask
+    orderbook.bids = vec![order(98.0, 1.0), order(101.0, 1.0)];
+    assert!(orderbook.is_crossed());
+    // removed levels don't count
+    orderbook.bids[1].quantity_base = 0.0;
+    assert!(!orderbook.is_crossed());
+    orderbook.asks.clear();
+    assert!(!orderbook.is_crossed());
+}
+
+#[test]
+fn resync_on_crossed() {
+    let (tx, rx) = std::sync::mpsc::channel();
+    let mut manager = OrderBookManager::new().on_resync(tx).check_crossed();
+    manager.update(l2_msg(
+        100,
+        0,
+        true,
+        vec![order(101.0, 1.0)],
+        vec![order(99.0, 1.0)],
+    ));
+
+    // a bid above the best ask, e.g., because the ask removal was dropped
+    assert!(!manager.update(l2_msg(110, 100, false, vec![], vec![order(102.0, 1.0)])));
+    assert_eq!(1, rx.try_iter().count());
+    assert!(manager
+        .get("binance", MarketType::Spot, "BTCUSDT")
+        .is_none());
+
+    // a crossed snapshot stays out of sync
+    assert!(!manager.update(l2_msg(
+        120,
+        0,
+        true,
+        vec![order(101.0, 1.0)],
+        vec![order(102.0, 1.0)]
+    )));
+    assert_eq!(1, rx.try_iter().count());
+
+    assert!(manager.update(l2_msg(
+        130,
+        0,
+        true,
+        vec![order(103.0, 1.0)],
+        vec![order(102.0, 1.0)]
+    )));
+    assert!(manager
+        .get("binance", MarketType::Spot, "BTCUSDT")
+        .is_some());
+    assert!(rx.try_recv().is_err());
+
+    // not checked by default
+    let mut manager = OrderBookManager::new();
+    assert!(manager.update(l2_msg(
+        100,
+        0,
+        true,
+        vec![order(101.0, 1.0)],
+        vec![order(102.0, 1.0)]
+    )));
+}
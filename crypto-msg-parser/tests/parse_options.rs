@@ -0,0 +1,63 @@
+use crypto_msg_parser::{
+    parse_l2, parse_l2_with_options, parse_trade, parse_trade_with_options, MarketType,
+    ParseOptions,
+};
+
+const TRADE_MSG: &str = r#"{"table":"spot/trade","data":[{"side":"sell","trade_id":"161659503","price":"56000.1","size":"0.00020621","instrument_id":"BTC-USDT","timestamp":"2021-03-22T01:16:28.687Z"}]}"#;
+const L2_MSG: &str = r#"{"arg":{"channel":"books-l2-tbt","instId":"BTC-USDT"},"action":"update","data":[{"asks":[["46615.2","0.06","0","1"]],"bids":[["46604.5","0.00113","0","1"]],"ts":"1630057844232","checksum":-1066406713}]}"#;
+
+#[test]
+fn default_options() {
+    let options = ParseOptions::default();
+    assert_eq!(
+        serde_json::to_string(&parse_trade("okex", MarketType::Spot, TRADE_MSG).unwrap()).unwrap(),
+        serde_json::to_string(
+            &parse_trade_with_options("okex", MarketType::Spot, TRADE_MSG, &options).unwrap()
+        )
+        .unwrap()
+    );
+    assert_eq!(
+        serde_json::to_string(&parse_l2("okex", MarketType::Spot, L2_MSG, None).unwrap()).unwrap(),
+        serde_json::to_string(
+            &parse_l2_with_options("okex", MarketType::Spot, L2_MSG, None, &options).unwrap()
+        )
+        .unwrap()
+    );
+}
+
+#[test]
+fn skip_json() {
+    let options = ParseOptions::new().keep_json(false);
+    let trade =
+        &parse_trade_with_options("okex", MarketType::Spot, TRADE_MSG, &options).unwrap()[0];
+    assert!(trade.json.is_empty());
+    assert_eq!("BTC/USDT", trade.pair);
+    assert_eq!(56000.1 * 0.00020621, trade.quantity_quote);
+
+    let orderbook =
+        &parse_l2_with_options("okex", MarketType::Spot, L2_MSG, None, &options).unwrap()[0];
+    assert!(orderbook.json.is_empty());
+
+    // the option only applies to the call it is passed to
+    let trade = &parse_trade("okex", MarketType::Spot, TRADE_MSG).unwrap()[0];
+    assert!(!trade.json.is_empty());
+}
+
+#[test]
+fn skip_pair_and_quote() {
+    let options = ParseOptions::new()
+        .normalize_pair(false)
+        .compute_quote(false);
+    let trade =
+        &parse_trade_with_options("okex", MarketType::Spot, TRADE_MSG, &options).unwrap()[0];
+    assert!(trade.pair.is_empty());
+    assert_eq!(0.0, trade.quantity_quote);
+    assert_eq!(0.00020621, trade.quantity_base);
+    assert!(!trade.json.is_empty());
+
+    let orderbook =
+        &parse_l2_with_options("okex", MarketType::Spot, L2_MSG, None, &options).unwrap()[0];
+    assert!(orderbook.pair.is_empty());
+    assert_eq!(0.0, orderbook.asks[0].quantity_quote);
+    assert_eq!(0.06, orderbook.asks[0].quantity_base);
+}
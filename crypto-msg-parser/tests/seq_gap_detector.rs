@@ -0,0 +1,134 @@
+mod utils;
+
+use crypto_msg_parser::{parse_l2, MarketType, OrderBookMsg, SeqGapDetector};
+use utils::l2_msg;
+
+fn update(seq_id: u64, prev_seq_id: u64, snapshot: bool) -> OrderBookMsg {
+    l2_msg(seq_id, prev_seq_id, snapshot, Vec::new(), Vec::new())
+}
+
+#[test]
+fn contiguous() {
+    let mut detector = SeqGapDetector::new();
+    assert!(!detector.check(&update(100, 0, true)));
+    // overlaps with the snapshot
+    assert!(!detector.check(&update(105, 98, false)));
+    assert!(!detector.check(&update(110, 105, false)));
+    // stale
+    assert!(!detector.check(&update(108, 104, false)));
+}
+
+#[test]
+fn gap() {
+    let mut detector = SeqGapDetector::new();
+    assert!(!detector.check(&update(100, 99, false)));
+    assert!(detector.check(&update(110, 105, false)));
+    assert!(!detector.check(&update(120, 110, false)));
+
+    // a new snapshot resets the sequence
+    assert!(!detector.check(&update(200, 0, true)));
+    assert!(!detector.check(&update(201, 200, false)));
+
+    detector.reset("binance", MarketType::Spot, "BTCUSDT");
+    assert!(!detector.check(&update(300, 250, false)));
+}
+
+#[test]
+fn binance_spot() {
+    let mut detector = SeqGapDetector::new();
+
+    let raw_msg = r#"{"stream":"btcusdt@depth@100ms","data":{"e":"depthUpdate","E":1622363903670,"s":"BTCUSDT","U":11174297999,"u":11174298006,"b":[["36012.70000000","0.00000000"]],"a":[["36012.71000000","0.31062000"]]}}"#;
+    let orderbook = &parse_l2("binance", MarketType::Spot, raw_msg, None).unwrap()[0];
+    assert_eq!(orderbook.seq_id, Some(11174298006));
+    assert_eq!(orderbook.prev_seq_id, Some(11174297998));
+    assert!(!detector.check(orderbook));
+
+    let raw_msg = r#"{"stream":"btcusdt@depth@100ms","data":{"e":"depthUpdate","E":1622363903770,"s":"BTCUSDT","U":11174298007,"u":11174298012,"b":[["36012.70000000","0.12000000"]],"a":[]}}"#;
+    let orderbook = &parse_l2("binance", MarketType::Spot, raw_msg, None).unwrap()[0];
+    assert!(!detector.check(orderbook));
+
+    let raw_msg = r#"{"stream":"btcusdt@depth@100ms","data":{"e":"depthUpdate","E":1622363903970,"s":"BTCUSDT","U":11174298020,"u":11174298025,"b":[],"a":[["36012.71000000","0.00000000"]]}}"#;
+    let orderbook = &parse_l2("binance", MarketType::Spot, raw_msg, None).unwrap()[0];
+    assert!(detector.check(orderbook));
+}
+
+#[test]
+fn binance_linear_swap() {
+    let mut detector = SeqGapDetector::new();
+
+    let raw_msg = r#"{"stream":"btcusdt@depth@100ms","data":{"e":"depthUpdate","E":1622363903670,"T":1622363903668,"s":"BTCUSDT","U":303741000,"u":303741010,"pu":303740990,"b":[["36012.70","0.000"]],"a":[]}}"#;
+    let orderbook = &parse_l2("binance", MarketType::LinearSwap, raw_msg, None).unwrap()[0];
+    assert_eq!(orderbook.prev_seq_id, Some(303740990));
+    assert!(!detector.check(orderbook));
+
+    let raw_msg = r#"{"stream":"btcusdt@depth@100ms","data":{"e":"depthUpdate","E":1622363903770,"T":1622363903768,"s":"BTCUSDT","U":303741030,"u":303741040,"pu":303741020,"b":[],"a":[["36012.71","1.000"]]}}"#;
+    let orderbook = &parse_l2("binance", MarketType::LinearSwap, raw_msg, None).unwrap()[0];
+    assert!(detector.check(orderbook));
+}
+
+#[test]
+fn binance_spot_first_update() {
+    // U is 0 on the very first update of a new symbol, there is no previous update
+    let raw_msg = r#"{"stream":"btcusdt@depth@100ms","data":{"e":"depthUpdate","E":1622363903670,"s":"BTCUSDT","U":0,"u":3,"b":[["36012.70000000","0.12000000"]],"a":[]}}"#;
+    let orderbook = &parse_l2("binance", MarketType::Spot, raw_msg, None).unwrap()[0];
+    assert_eq!(orderbook.seq_id, Some(3));
+    assert_eq!(orderbook.prev_seq_id, None);
+    assert!(!SeqGapDetector::new().check(orderbook));
+}
+
+#[test]
+fn gate_spot() {
+    let mut detector = SeqGapDetector::new();
+
+    let raw_msg = r#"{"time":1631836142,"channel":"spot.order_book_update","event":"update","result":{"t":1631836142325,"e":"depthUpdate","E":1631836142,"s":"BTC_USDT","U":4622074361,"u":4622074364,"b":[["47737.89","0.002"],["47741.35","0"]],"a":[["47813.04","0.0355"],["47978.86","0"]]}}"#;
+    let orderbook = &parse_l2("gate", MarketType::Spot, raw_msg, Some(1631836142325)).unwrap()[0];
+    assert_eq!(orderbook.seq_id, Some(4622074364));
+    assert_eq!(orderbook.prev_seq_id, Some(4622074360));
+    assert!(!detector.check(orderbook));
+
+    let raw_msg = r#"{"time":1631836142,"channel":"spot.order_book_update","event":"update","result":{"t":1631836142425,"e":"depthUpdate","E":1631836142,"s":"BTC_USDT","U":4622074365,"u":4622074367,"b":[["47737.89","0"]],"a":[]}}"#;
+    let orderbook = &parse_l2("gate", MarketType::Spot, raw_msg, Some(1631836142325)).unwrap()[0];
+    assert!(!detector.check(orderbook));
+
+    let raw_msg = r#"{"time":1631836142,"channel":"spot.order_book_update","event":"update","result":{"t":1631836142625,"e":"depthUpdate","E":1631836142,"s":"BTC_USDT","U":4622074370,"u":4622074371,"b":[],"a":[["47813.04","0"]]}}"#;
+    let orderbook = &parse_l2("gate", MarketType::Spot, raw_msg, Some(1631836142325)).unwrap()[0];
+    assert!(detector.check(orderbook));
+}
+
+#[test]
+fn kucoin_spot() {
+    let mut detector = SeqGapDetector::new();
+
+    let raw_msg = r#"{"data":{"sequenceStart":1617071937790,"symbol":"BTC-USDT","changes":{"asks":[],"bids":[["39272","0.0530867","1617071937790"]]},"sequenceEnd":1617071937790},"subject":"trade.l2update","topic":"/market/level2:BTC-USDT","type":"message"}"#;
+    let orderbook = &parse_l2("kucoin", MarketType::Spot, raw_msg, Some(1625097804231)).unwrap()[0];
+    assert_eq!(orderbook.seq_id, Some(1617071937790));
+    assert_eq!(orderbook.prev_seq_id, Some(1617071937789));
+    assert!(!detector.check(orderbook));
+
+    let raw_msg = r#"{"data":{"sequenceStart":1617071937791,"symbol":"BTC-USDT","changes":{"asks":[["39273","0.1","1617071937791"]],"bids":[]},"sequenceEnd":1617071937791},"subject":"trade.l2update","topic":"/market/level2:BTC-USDT","type":"message"}"#;
+    let orderbook = &parse_l2("kucoin", MarketType::Spot, raw_msg, Some(1625097804231)).unwrap()[0];
+    assert!(!detector.check(orderbook));
+
+    let raw_msg = r#"{"data":{"sequenceStart":1617071937795,"symbol":"BTC-USDT","changes":{"asks":[],"bids":[["39272","0","1617071937795"]]},"sequenceEnd":1617071937795},"subject":"trade.l2update","topic":"/market/level2:BTC-USDT","type":"message"}"#;
+    let orderbook = &parse_l2("kucoin", MarketType::Spot, raw_msg, Some(1625097804231)).unwrap()[0];
+    assert!(detector.check(orderbook));
+}
+
+#[test]
+fn kucoin_inverse_swap() {
+    let mut detector = SeqGapDetector::new();
+
+    let raw_msg = r#"{"data":{"sequence":1617852459594,"change":"39069.0,buy,23960","timestamp":1622718985044},"subject":"level2","topic":"/contractMarket/level2:XBTUSDM","type":"message"}"#;
+    let orderbook = &parse_l2("kucoin", MarketType::InverseSwap, raw_msg, None).unwrap()[0];
+    assert_eq!(orderbook.seq_id, Some(1617852459594));
+    assert_eq!(orderbook.prev_seq_id, Some(1617852459593));
+    assert!(!detector.check(orderbook));
+
+    let raw_msg = r#"{"data":{"sequence":1617852459595,"change":"39070.0,sell,100","timestamp":1622718985144},"subject":"level2","topic":"/contractMarket/level2:XBTUSDM","type":"message"}"#;
+    let orderbook = &parse_l2("kucoin", MarketType::InverseSwap, raw_msg, None).unwrap()[0];
+    assert!(!detector.check(orderbook));
+
+    let raw_msg = r#"{"data":{"sequence":1617852459597,"change":"39069.0,buy,0","timestamp":1622718985344},"subject":"level2","topic":"/contractMarket/level2:XBTUSDM","type":"message"}"#;
+    let orderbook = &parse_l2("kucoin", MarketType::InverseSwap, raw_msg, None).unwrap()[0];
+    assert!(detector.check(orderbook));
+}
@@ -0,0 +1,105 @@
+mod utils;
+
+use crypto_msg_parser::{MarketType, OrderBookMsg, TopKTracker};
+use utils::{l2_msg, order};
+
+fn snapshot(symbol: &str, timestamp: i64, best_bid: f64) -> OrderBookMsg {
+    OrderBookMsg {
+        exchange: "okex".to_string(),
+        symbol: symbol.to_string(),
+        timestamp,
+        seq_id: None,
+        prev_seq_id: None,
+        ..l2_msg(
+            0,
+            0,
+            true,
+            vec![
+                order(best_bid + 0.3, 1.0),
+                order(best_bid + 0.1, 1.0),
+                order(best_bid + 0.2, 1.0),
+            ],
+            vec![
+                order(best_bid - 0.2, 1.0),
+                order(best_bid, 1.0),
+                order(best_bid - 0.1, 1.0),
+            ],
+        )
+    }
+}
+
+#[test]
+fn truncate_and_sort() {
+    let mut tracker = TopKTracker::new(2, 100);
+    let emitted = tracker.update(snapshot("BTC-USDT", 1000, 100.0)).unwrap();
+
+    assert_eq!(emitted.asks.len(), 2);
+    assert_eq!(emitted.bids.len(), 2);
+    assert_eq!(emitted.asks[0].price, 100.1);
+    assert_eq!(emitted.asks[1].price, 100.2);
+    assert_eq!(emitted.bids[0].price, 100.0);
+    assert_eq!(emitted.bids[1].price, 99.9);
+}
+
+#[test]
+fn nan_price() {
+    let mut tracker = TopKTracker::new(2, 100);
+    let mut msg = snapshot("BTC-USDT", 1000, 100.0);
+    msg.asks.push(order(f64::NAN, 1.0));
+    msg.bids.push(order(f64::NAN, 1.0));
+    let emitted = tracker.update(msg).unwrap();
+
+    assert_eq!(emitted.asks[0].price, 100.1);
+    assert_eq!(emitted.asks[1].price, 100.2);
+    assert_eq!(emitted.bids[0].price, 100.0);
+    assert_eq!(emitted.bids[1].price, 99.9);
+}
+
+#[test]
+fn throttle() {
+    let mut tracker = TopKTracker::new(5, 100);
+    assert!(tracker.update(snapshot("BTC-USDT", 1000, 100.0)).is_some());
+    assert!(tracker.update(snapshot("BTC-USDT", 1050, 101.0)).is_none());
+    // other symbols are throttled independently
+    assert!(tracker.update(snapshot("ETH-USDT", 1060, 10.0)).is_some());
+    assert_eq!(
+        tracker
+            .latest("okex", MarketType::Spot, "BTC-USDT")
+            .unwrap()
+            .timestamp,
+        1050
+    );
+
+    let emitted = tracker.update(snapshot("BTC-USDT", 1100, 102.0)).unwrap();
+    assert_eq!(emitted.timestamp, 1100);
+    assert_eq!(emitted.bids[0].price, 102.0);
+}
+
+#[test]
+fn flush() {
+    let mut tracker = TopKTracker::new(5, 100);
+    assert!(tracker.update(snapshot("BTC-USDT", 1000, 100.0)).is_some());
+    assert!(tracker.update(snapshot("BTC-USDT", 1050, 101.0)).is_none());
+
+    let flushed = tracker.flush();
+    assert_eq!(flushed.len(), 1);
+    assert_eq!(flushed[0].timestamp, 1050);
+    assert!(tracker.flush().is_empty());
+}
+
+#[test]
+fn ignore_stale_and_updates() {
+    let mut tracker = TopKTracker::new(5, 100);
+    assert!(tracker.update(snapshot("BTC-USDT", 1000, 100.0)).is_some());
+    assert!(tracker.update(snapshot("BTC-USDT", 900, 99.0)).is_none());
+
+    let mut update = snapshot("BTC-USDT", 2000, 103.0);
+    update.snapshot = false;
+    assert!(tracker.update(update).is_none());
+
+    let latest = tracker
+        .latest("okex", MarketType::Spot, "BTC-USDT")
+        .unwrap();
+    assert_eq!(latest.timestamp, 1000);
+    assert!(tracker.flush().is_empty());
+}
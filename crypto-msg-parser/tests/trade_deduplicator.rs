@@ -0,0 +1,61 @@
+use crypto_msg_parser::{MarketType, MessageType, TradeDeduplicator, TradeMsg, TradeSide};
+
+fn trade(symbol: &str, trade_id: &str) -> TradeMsg {
+    TradeMsg {
+        exchange: "binance".to_string(),
+        market_type: MarketType::Spot,
+        symbol: symbol.to_string(),
+        pair: "BTC/USDT".to_string(),
+        msg_type: MessageType::Trade,
+        timestamp: 1626330013405,
+        timestamp_us: None,
+        price: 31800.0,
+        quantity_base: 0.1,
+        quantity_quote: 3180.0,
+        quantity_contract: None,
+        side: TradeSide::Buy,
+        trade_id: trade_id.to_string(),
+        json: "".to_string(),
+    }
+}
+
+#[test]
+fn replayed_after_reconnect() {
+    let mut dedup = TradeDeduplicator::new(1000);
+    assert!(!dedup.is_duplicate(&trade("BTCUSDT", "100")));
+    assert!(!dedup.is_duplicate(&trade("BTCUSDT", "101")));
+    // replayed after resubscribing
+    let trades = dedup.filter(vec![
+        trade("BTCUSDT", "100"),
+        trade("BTCUSDT", "101"),
+        trade("BTCUSDT", "102"),
+    ]);
+    assert_eq!(1, trades.len());
+    assert_eq!("102", trades[0].trade_id);
+}
+
+#[test]
+fn per_symbol() {
+    let mut dedup = TradeDeduplicator::new(1000);
+    assert!(!dedup.is_duplicate(&trade("BTCUSDT", "100")));
+    assert!(!dedup.is_duplicate(&trade("ETHUSDT", "100")));
+    assert!(dedup.is_duplicate(&trade("ETHUSDT", "100")));
+}
+
+#[test]
+fn bounded_window() {
+    let mut dedup = TradeDeduplicator::new(2);
+    assert!(!dedup.is_duplicate(&trade("BTCUSDT", "100")));
+    assert!(!dedup.is_duplicate(&trade("BTCUSDT", "101")));
+    assert!(!dedup.is_duplicate(&trade("BTCUSDT", "102")));
+    // evicted
+    assert!(!dedup.is_duplicate(&trade("BTCUSDT", "100")));
+    assert!(dedup.is_duplicate(&trade("BTCUSDT", "102")));
+}
+
+#[test]
+fn without_trade_id() {
+    let mut dedup = TradeDeduplicator::new(1000);
+    assert!(!dedup.is_duplicate(&trade("BTCUSDT", "")));
+    assert!(!dedup.is_duplicate(&trade("BTCUSDT", "")));
+}
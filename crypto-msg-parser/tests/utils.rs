@@ -1,7 +1,8 @@
 use crypto_market_type::MarketType;
-use crypto_msg_parser::{FundingRateMsg, MessageType, OrderBookMsg, TradeMsg};
+use crypto_msg_parser::{BboMsg, FundingRateMsg, MessageType, Order, OrderBookMsg, TradeMsg};
 use float_cmp::approx_eq;
 
+#[allow(dead_code)]
 pub fn check_trade_fields(
     exchange: &str,
     market_type: MarketType,
@@ -77,3 +78,66 @@ pub fn check_funding_rate_fields(
         assert_eq!(funding_rate.funding_time % (8 * 3600000), 0);
     }
 }
+
+#[allow(dead_code)]
+pub fn check_bbo_fields(
+    exchange: &str,
+    market_type: MarketType,
+    pair: String,
+    symbol: String,
+    bbo: &BboMsg,
+) {
+    assert_eq!(bbo.exchange, exchange);
+    assert_eq!(bbo.market_type, market_type);
+    assert_eq!(bbo.pair, pair);
+    assert_eq!(bbo.symbol, symbol);
+    assert_eq!(bbo.msg_type, MessageType::BBO);
+    assert_eq!(bbo.timestamp.to_string().len(), 13);
+    assert!(bbo.bid_price > 0.0);
+    assert!(bbo.ask_price > 0.0);
+    assert!(bbo.bid_price <= bbo.ask_price);
+    assert!(bbo.bid_quantity >= 0.0);
+    assert!(bbo.ask_quantity >= 0.0);
+}
+
+#[allow(dead_code)]
+pub fn order(price: f64, quantity_base: f64) -> Order {
+    Order {
+        price,
+        quantity_base,
+        quantity_quote: price * quantity_base,
+        quantity_contract: None,
+        order_id: None,
+        order_type: None,
+    }
+}
+
+// A binance BTCUSDT level2 message whose timestamp grows with seq_id
+#[allow(dead_code)]
+pub fn l2_msg(
+    seq_id: u64,
+    prev_seq_id: u64,
+    snapshot: bool,
+    asks: Vec<Order>,
+    bids: Vec<Order>,
+) -> OrderBookMsg {
+    OrderBookMsg {
+        exchange: "binance".to_string(),
+        market_type: MarketType::Spot,
+        symbol: "BTCUSDT".to_string(),
+        pair: "BTC/USDT".to_string(),
+        msg_type: if snapshot {
+            MessageType::L2Snapshot
+        } else {
+            MessageType::L2Event
+        },
+        timestamp: 1626330013405 + seq_id as i64,
+        timestamp_us: None,
+        seq_id: Some(seq_id),
+        prev_seq_id: Some(prev_seq_id),
+        asks,
+        bids,
+        snapshot,
+        json: "".to_string(),
+    }
+}
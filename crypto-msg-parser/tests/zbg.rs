@@ -85,6 +85,27 @@ mod trade {
         assert_eq!(trade.quantity_contract, Some(188.0));
         assert_eq!(trade.side, TradeSide::Sell);
     }
+
+    #[test]
+    fn unknown_contract() {
+        let raw_msg =
+            r#"["future_tick",{"contractId":1,"trades":[1616385064674265,"57326","31",-1]}]"#;
+        assert!(parse_trade("zbg", MarketType::LinearSwap, raw_msg).is_err());
+        assert_eq!(None, extract_symbol("zbg", MarketType::LinearSwap, raw_msg));
+    }
+
+    #[test]
+    fn non_data_messages() {
+        let pong = r#"{"action":"PING","data":"1616385064"}"#;
+        assert_eq!(None, extract_symbol("zbg", MarketType::Spot, pong));
+        assert_eq!(None, extract_symbol("zbg", MarketType::LinearSwap, pong));
+        assert!(parse_trade("zbg", MarketType::Spot, pong).is_err());
+        assert!(parse_trade("zbg", MarketType::LinearSwap, pong).is_err());
+
+        // depth messages are not trades
+        let raw_msg = r#"["future_snapshot_depth",{"asks":[["38704","2684"]],"contractId":1000000,"bids":[["38703","1606"]],"tradeDate":20210603,"time":1622733219128160}]"#;
+        assert!(parse_trade("zbg", MarketType::LinearSwap, raw_msg).is_err());
+    }
 }
 
 #[cfg(test)]
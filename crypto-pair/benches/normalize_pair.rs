@@ -0,0 +1,40 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use crypto_pair::{normalize_pair, normalize_pair_cached};
+
+// A trade stream of 1M messages cycling through a handful of symbols
+const NUM_MESSAGES: usize = 1_000_000;
+const SYMBOLS: &[(&str, &str)] = &[
+    ("BTCUSDT", "binance"),
+    ("btcusd_perp", "binance"),
+    ("XBTUSD", "bitmex"),
+    ("tBTCUST", "bitfinex"),
+    ("BTC-USDT-SWAP", "okex"),
+    ("BTC-PERPETUAL", "deribit"),
+    ("BTC-0326", "ftx"),
+    ("XBT/USD", "kraken"),
+];
+
+fn trade_stream(c: &mut Criterion) {
+    let mut group = c.benchmark_group("trade_stream_1m");
+    group.sample_size(10);
+    group.bench_function("uncached", |b| {
+        b.iter(|| {
+            for i in 0..NUM_MESSAGES {
+                let (symbol, exchange) = SYMBOLS[i % SYMBOLS.len()];
+                black_box(normalize_pair(black_box(symbol), exchange));
+            }
+        })
+    });
+    group.bench_function("cached", |b| {
+        b.iter(|| {
+            for i in 0..NUM_MESSAGES {
+                let (symbol, exchange) = SYMBOLS[i % SYMBOLS.len()];
+                black_box(normalize_pair_cached(black_box(symbol), exchange));
+            }
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, trade_stream);
+criterion_main!(benches);
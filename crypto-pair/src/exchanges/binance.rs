@@ -1,6 +1,8 @@
+use crypto_market_type::MarketType;
 use std::collections::{BTreeSet, HashSet};
 
-use super::utils::{http_get, normalize_pair_with_quotes};
+use super::utils::normalize_pair_with_quotes;
+use crate::http_get;
 use lazy_static::lazy_static;
 
 use serde::{Deserialize, Serialize};
@@ -66,7 +68,10 @@ struct SpotMarket {
 
 // see <https://binance-docs.github.io/apidocs/spot/en/#exchange-information>
 fn fetch_spot_quotes() -> BTreeSet<String> {
-    if let Ok(txt) = http_get("https://api.binance.com/api/v3/exchangeInfo") {
+    if cfg!(feature = "offline") {
+        return BTreeSet::new();
+    }
+    if let Ok(txt) = http_get("binance", "https://api.binance.com/api/v3/exchangeInfo") {
         let resp = serde_json::from_str::<BinanceResponse>(&txt).unwrap();
         resp.symbols
             .into_iter()
@@ -78,6 +83,9 @@ fn fetch_spot_quotes() -> BTreeSet<String> {
 }
 
 pub(crate) fn normalize_pair(symbol: &str) -> Option<String> {
+    // symbols are case-insensitive, e.g., btcusdt in websocket streams
+    let symbol = symbol.to_uppercase();
+    let symbol = symbol.as_str();
     if let Some(base) = symbol.strip_suffix("USD_PERP") {
         // inverse swap
         Some(format!("{}/USD", base))
@@ -104,6 +112,14 @@ pub(crate) fn normalize_pair(symbol: &str) -> Option<String> {
     }
 }
 
+pub(crate) fn denormalize_pair(base: &str, quote: &str, market_type: MarketType) -> Option<String> {
+    match market_type {
+        MarketType::Spot | MarketType::LinearSwap => Some(format!("{}{}", base, quote)),
+        MarketType::InverseSwap => Some(format!("{}{}_PERP", base, quote)),
+        _ => None,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::fetch_spot_quotes;
@@ -1,4 +1,5 @@
-use super::utils::http_get;
+use crate::http_get;
+use crypto_market_type::MarketType;
 use lazy_static::lazy_static;
 use std::collections::{BTreeMap, HashMap};
 
@@ -70,9 +71,15 @@ lazy_static! {
 
 // see <https://api-pub.bitfinex.com/v2/conf/pub:map:currency:sym>
 fn fetch_currency_mapping() -> BTreeMap<String, String> {
+    if cfg!(feature = "offline") {
+        return BTreeMap::new();
+    }
     let mut mapping = BTreeMap::<String, String>::new();
 
-    if let Ok(txt) = http_get("https://api-pub.bitfinex.com/v2/conf/pub:map:currency:sym") {
+    if let Ok(txt) = http_get(
+        "bitfinex",
+        "https://api-pub.bitfinex.com/v2/conf/pub:map:currency:sym",
+    ) {
         let arr = serde_json::from_str::<Vec<Vec<Vec<String>>>>(&txt).unwrap();
         assert!(arr.len() == 1);
 
@@ -101,10 +108,15 @@ pub(crate) fn normalize_currency(mut currency: &str) -> String {
     currency.to_uppercase()
 }
 
-pub(crate) fn normalize_pair(mut symbol: &str) -> Option<String> {
-    if symbol.starts_with('t') {
-        symbol = &symbol[1..]; // e.g., tBTCUSD, remove t
-    };
+pub(crate) fn normalize_pair(symbol: &str) -> Option<String> {
+    // e.g., tBTCUSD, remove t, the rest is case-insensitive. The prefix is a
+    // lowercase t followed by an uppercase letter, in lowercase symbols it is
+    // ambiguous, e.g., trxusd is TRX/USD, so they must come without it.
+    let symbol = match symbol.strip_prefix('t') {
+        Some(rest) if rest.starts_with(|c: char| c.is_ascii_uppercase()) => rest,
+        _ => symbol,
+    }
+    .to_uppercase();
 
     let (base, quote) = if symbol.contains(':') {
         let v: Vec<&str> = symbol.split(':').collect();
@@ -123,6 +135,38 @@ pub(crate) fn normalize_pair(mut symbol: &str) -> Option<String> {
     ))
 }
 
+fn denormalize_currency(currency: &str) -> String {
+    // prefer spot currency names, futures ones end with F0
+    let mut candidates: Vec<&String> = BITFINEX_MAPPING
+        .iter()
+        .filter(|(k, v)| !k.ends_with("F0") && v.to_uppercase() == currency)
+        .map(|(k, _)| k)
+        .collect();
+    candidates.sort();
+    if let Some(raw) = candidates.first() {
+        raw.to_string()
+    } else {
+        currency.to_string()
+    }
+}
+
+pub(crate) fn denormalize_pair(base: &str, quote: &str, market_type: MarketType) -> Option<String> {
+    let (base, quote) = match market_type {
+        MarketType::Spot => (denormalize_currency(base), denormalize_currency(quote)),
+        MarketType::LinearSwap => (
+            format!("{}F0", denormalize_currency(base)),
+            format!("{}F0", denormalize_currency(quote)),
+        ),
+        _ => return None,
+    };
+
+    if base.len() > 3 || quote.len() > 3 {
+        Some(format!("t{}:{}", base, quote))
+    } else {
+        Some(format!("t{}{}", base, quote))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::fetch_currency_mapping;
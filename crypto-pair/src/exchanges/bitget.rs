@@ -1,4 +1,9 @@
+use crypto_market_type::MarketType;
+
 pub(crate) fn normalize_pair(symbol: &str) -> Option<String> {
+    // raw symbols are lowercase, e.g., cmt_btcusdt
+    let symbol = symbol.to_lowercase();
+    let symbol = symbol.as_str();
     if symbol.starts_with("cmt_") {
         // linear swap
         assert!(symbol.ends_with("usdt"));
@@ -15,3 +20,12 @@ pub(crate) fn normalize_pair(symbol: &str) -> Option<String> {
         None
     }
 }
+
+pub(crate) fn denormalize_pair(base: &str, quote: &str, market_type: MarketType) -> Option<String> {
+    match market_type {
+        MarketType::Spot => Some(format!("{}_{}", base, quote).to_lowercase()),
+        MarketType::LinearSwap => Some(format!("cmt_{}{}", base, quote).to_lowercase()),
+        MarketType::InverseSwap => Some(format!("{}{}", base, quote).to_lowercase()),
+        _ => None,
+    }
+}
@@ -0,0 +1,21 @@
+use crypto_market_type::MarketType;
+
+// Bithumb Global uses BTC-USDT, while Bithumb Korea uses KRW-quoted pairs such as BTC_KRW
+pub(crate) fn normalize_pair(symbol: &str) -> Option<String> {
+    let (base, quote) = if let Some((base, quote)) = symbol.split_once('-') {
+        (base, quote)
+    } else if let Some((base, quote)) = symbol.split_once('_') {
+        (base, quote)
+    } else {
+        return None;
+    };
+    Some(format!("{}/{}", base, quote).to_uppercase())
+}
+
+pub(crate) fn denormalize_pair(base: &str, quote: &str, market_type: MarketType) -> Option<String> {
+    if market_type == MarketType::Spot {
+        Some(format!("{}-{}", base, quote))
+    } else {
+        None
+    }
+}
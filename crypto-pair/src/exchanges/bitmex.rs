@@ -1,8 +1,14 @@
+use crypto_market_type::MarketType;
+
+use crate::OptionInfo;
+
 pub(crate) fn normalize_currency(currency: &str) -> String {
     if currency == "XBT" { "BTC" } else { currency }.to_string()
 }
 
-pub(crate) fn normalize_pair(mut symbol: &str) -> Option<String> {
+pub(crate) fn normalize_pair(symbol: &str) -> Option<String> {
+    let symbol = symbol.to_uppercase();
+    let mut symbol = symbol.as_str();
     if symbol[(symbol.len() - 2)..].parse::<f64>().is_ok() {
         symbol = &symbol[..(symbol.len() - 3)]
     }
@@ -34,3 +40,25 @@ pub(crate) fn normalize_pair(mut symbol: &str) -> Option<String> {
         normalize_currency(&quote)
     ))
 }
+
+pub(crate) fn denormalize_currency(currency: &str) -> String {
+    if currency == "BTC" { "XBT" } else { currency }.to_string()
+}
+
+pub(crate) fn denormalize_pair(base: &str, quote: &str, market_type: MarketType) -> Option<String> {
+    match market_type {
+        MarketType::InverseSwap | MarketType::QuantoSwap => Some(format!(
+            "{}{}",
+            denormalize_currency(base),
+            denormalize_currency(quote)
+        )),
+        _ => None,
+    }
+}
+
+// BitMEX only lists Up/Down contracts such as XBT7D_U105 and XBT7D_D95, whose
+// strike is a percentage of the settlement price a week ago, so the absolute
+// strike and expiry can NOT be recovered from the symbol.
+pub(crate) fn parse_option_symbol(_symbol: &str) -> Option<OptionInfo> {
+    None
+}
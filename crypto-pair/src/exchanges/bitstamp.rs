@@ -1,4 +1,8 @@
+use crypto_market_type::MarketType;
+
 pub(crate) fn normalize_pair(symbol: &str) -> Option<String> {
+    // raw symbols are lowercase, e.g., btcusd
+    let symbol = symbol.to_lowercase();
     let (base, quote) = if symbol.ends_with("usdc") || symbol.ends_with("usdt") {
         (
             symbol[0..(symbol.len() - 4)].to_string(),
@@ -13,3 +17,11 @@ pub(crate) fn normalize_pair(symbol: &str) -> Option<String> {
 
     Some(format!("{}/{}", base, quote).to_uppercase())
 }
+
+pub(crate) fn denormalize_pair(base: &str, quote: &str, market_type: MarketType) -> Option<String> {
+    if market_type == MarketType::Spot {
+        Some(format!("{}{}", base, quote).to_lowercase())
+    } else {
+        None
+    }
+}
@@ -0,0 +1,15 @@
+use crypto_market_type::MarketType;
+
+// e.g., btc_usdt
+pub(crate) fn normalize_pair(symbol: &str) -> Option<String> {
+    Some(symbol.replace('_', "/").to_uppercase())
+}
+
+// Swap markets are not supported, since bitz.com has shutdown
+pub(crate) fn denormalize_pair(base: &str, quote: &str, market_type: MarketType) -> Option<String> {
+    if market_type == MarketType::Spot {
+        Some(format!("{}_{}", base, quote).to_lowercase())
+    } else {
+        None
+    }
+}
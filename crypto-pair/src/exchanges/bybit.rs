@@ -1,4 +1,8 @@
+use crypto_market_type::MarketType;
+
 pub(crate) fn normalize_pair(symbol: &str) -> Option<String> {
+    let symbol = symbol.to_uppercase();
+    let symbol = symbol.as_str();
     let (base, quote) = if symbol.ends_with("USDT") {
         // linear swap
         let base = symbol.strip_suffix("USDT").unwrap();
@@ -16,3 +20,10 @@ pub(crate) fn normalize_pair(symbol: &str) -> Option<String> {
     };
     Some(format!("{}/{}", base, quote))
 }
+
+pub(crate) fn denormalize_pair(base: &str, quote: &str, market_type: MarketType) -> Option<String> {
+    match market_type {
+        MarketType::InverseSwap | MarketType::LinearSwap => Some(format!("{}{}", base, quote)),
+        _ => None,
+    }
+}
@@ -1,4 +1,10 @@
+use crypto_market_type::MarketType;
+
+use crate::OptionInfo;
+
 pub(crate) fn normalize_pair(symbol: &str) -> Option<String> {
+    let symbol = symbol.to_uppercase();
+    let symbol = symbol.as_str();
     if symbol.ends_with("-PERPETUAL") {
         // inverse_swap
         let base = symbol.strip_suffix("-PERPETUAL").unwrap();
@@ -17,3 +23,48 @@ pub(crate) fn normalize_pair(symbol: &str) -> Option<String> {
         None
     }
 }
+
+pub(crate) fn denormalize_pair(
+    base: &str,
+    _quote: &str,
+    market_type: MarketType,
+) -> Option<String> {
+    if market_type == MarketType::InverseSwap {
+        Some(format!("{}-PERPETUAL", base))
+    } else {
+        None
+    }
+}
+
+const MONTHS: [&str; 12] = [
+    "JAN", "FEB", "MAR", "APR", "MAY", "JUN", "JUL", "AUG", "SEP", "OCT", "NOV", "DEC",
+];
+
+// e.g., BTC-25JUN21-50000-C and ETH-5MAR21-1500-P
+pub(crate) fn parse_option_symbol(symbol: &str) -> Option<OptionInfo> {
+    let v: Vec<&str> = symbol.split('-').collect();
+    if v.len() != 4 {
+        return None;
+    }
+    let is_call = match v[3] {
+        "C" => true,
+        "P" => false,
+        _ => return None,
+    };
+    let date = v[1];
+    if date.len() < 6 {
+        return None;
+    }
+    let (day, rest) = date.split_at(date.len() - 5);
+    let (month, year) = rest.split_at(3);
+    let day = day.parse::<u32>().ok()?;
+    let month = MONTHS.iter().position(|m| *m == month)? + 1;
+    year.parse::<u32>().ok()?;
+
+    Some(OptionInfo {
+        underlying: format!("{}/USD", v[0]),
+        expiry: format!("{}{:02}{:02}", year, month, day),
+        strike: v[2].parse::<f64>().ok()?,
+        is_call,
+    })
+}
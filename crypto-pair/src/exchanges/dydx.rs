@@ -1,4 +1,8 @@
+use crypto_market_type::MarketType;
+
 pub(crate) fn normalize_pair(symbol: &str) -> Option<String> {
+    let symbol = symbol.to_uppercase();
+    let symbol = symbol.as_str();
     if symbol.contains('-') {
         let result = str::replace(symbol, "-", "/");
         Some(result)
@@ -6,3 +10,11 @@ pub(crate) fn normalize_pair(symbol: &str) -> Option<String> {
         None
     }
 }
+
+pub(crate) fn denormalize_pair(base: &str, quote: &str, market_type: MarketType) -> Option<String> {
+    if market_type == MarketType::LinearSwap {
+        Some(format!("{}-{}", base, quote))
+    } else {
+        None
+    }
+}
@@ -1,20 +1,35 @@
+use crypto_market_type::MarketType;
+
 pub(crate) fn normalize_pair(symbol: &str) -> Option<String> {
+    let symbol = symbol.to_uppercase();
+    let symbol = symbol.as_str();
     if symbol.ends_with("-PERP") {
-        // linear swap
+        // linear swap, e.g., BTC-PERP
         let base = symbol.strip_suffix("-PERP").unwrap();
         Some(format!("{}/USD", base))
-    } else if symbol.contains("-MOVE-") {
-        let v: Vec<&str> = symbol.split('-').collect();
-        Some(format!("{}/USD", v[0]))
-    } else if symbol.contains("BVOL/") || symbol.contains('/') {
-        // BVOL and Spot
+    } else if symbol.starts_with("BVOL/") || symbol.starts_with("IBVOL/") {
+        // volatility index tokens, e.g., BVOL/USD and IBVOL/BTC, are traded as spot
         Some(symbol.to_string())
+    } else if symbol.contains('/') {
+        // spot, e.g., BTC/USD
+        Some(symbol.to_string())
+    } else if let Some(pos) = symbol.find("-MOVE-") {
+        // MOVE contracts, e.g., BTC-MOVE-0326, BTC-MOVE-WK-0326 and BTC-MOVE-2021Q1
+        Some(format!("{}/USD", &symbol[..pos]))
     } else if let Some(pos) = symbol.rfind('-') {
-        // linear future
+        // linear future, e.g., BTC-0326, whose underlying is BTC and settled in USD
         let base = &symbol[..pos];
         Some(format!("{}/USD", base))
     } else {
-        // prediction
+        // prediction, e.g., BREXIT and TRUMP2024
         Some(format!("{}/USD", symbol))
     }
 }
+
+pub(crate) fn denormalize_pair(base: &str, quote: &str, market_type: MarketType) -> Option<String> {
+    match market_type {
+        MarketType::Spot => Some(format!("{}/{}", base, quote)),
+        MarketType::LinearSwap => Some(format!("{}-PERP", base)),
+        _ => None,
+    }
+}
@@ -0,0 +1,19 @@
+use crypto_market_type::MarketType;
+
+// e.g., BTC_USDT, and BTC_USD_20210625 for futures
+pub(crate) fn normalize_pair(symbol: &str) -> Option<String> {
+    let v: Vec<&str> = symbol.split('_').collect();
+    if v.len() < 2 {
+        return None;
+    }
+    Some(format!("{}/{}", v[0], v[1]).to_uppercase())
+}
+
+pub(crate) fn denormalize_pair(base: &str, quote: &str, market_type: MarketType) -> Option<String> {
+    match market_type {
+        MarketType::Spot | MarketType::InverseSwap | MarketType::LinearSwap => {
+            Some(format!("{}_{}", base, quote))
+        }
+        _ => None,
+    }
+}
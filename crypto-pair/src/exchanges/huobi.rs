@@ -1,6 +1,8 @@
+use crypto_market_type::MarketType;
 use std::collections::{BTreeSet, HashSet};
 
-use super::utils::{http_get, normalize_pair_with_quotes};
+use super::utils::normalize_pair_with_quotes;
+use crate::http_get;
 use lazy_static::lazy_static;
 
 use serde::{Deserialize, Serialize};
@@ -48,7 +50,10 @@ struct Response<T: Sized> {
 
 // see <https://huobiapi.github.io/docs/spot/v1/en/#get-all-supported-trading-symbol>
 fn fetch_spot_quotes() -> BTreeSet<String> {
-    if let Ok(txt) = http_get("https://api.huobi.pro/v1/common/symbols") {
+    if cfg!(feature = "offline") {
+        return BTreeSet::new();
+    }
+    if let Ok(txt) = http_get("huobi", "https://api.huobi.pro/v1/common/symbols") {
         let resp = serde_json::from_str::<Response<SpotMarket>>(&txt).unwrap();
         resp.data
             .into_iter()
@@ -60,6 +65,9 @@ fn fetch_spot_quotes() -> BTreeSet<String> {
 }
 
 pub(crate) fn normalize_pair(symbol: &str) -> Option<String> {
+    // contract symbols are uppercase, spot symbols are lowercase
+    let symbol = symbol.to_uppercase();
+    let symbol = symbol.as_str();
     if symbol.ends_with("-USD") || symbol.ends_with("-USDT") {
         // inverse and linear swap
         Some(symbol.replace('-', "/"))
@@ -78,10 +86,28 @@ pub(crate) fn normalize_pair(symbol: &str) -> Option<String> {
         // inverse future
         let base = &symbol[..symbol.len() - 3];
         Some(format!("{}/USD", base))
+    } else if symbol.len() > 6
+        && symbol.is_ascii()
+        && symbol[symbol.len() - 6..].parse::<u32>().is_ok()
+        && symbol[..symbol.len() - 6]
+            .chars()
+            .all(|c| c.is_ascii_uppercase())
+    {
+        // inverse future contract code, e.g., BTC210625
+        let base = &symbol[..symbol.len() - 6];
+        Some(format!("{}/USD", base))
     } else {
         // spot
         let quotes = &(*SPOT_QUOTES);
-        normalize_pair_with_quotes(symbol, quotes)
+        normalize_pair_with_quotes(&symbol.to_lowercase(), quotes)
+    }
+}
+
+pub(crate) fn denormalize_pair(base: &str, quote: &str, market_type: MarketType) -> Option<String> {
+    match market_type {
+        MarketType::Spot => Some(format!("{}{}", base, quote).to_lowercase()),
+        MarketType::InverseSwap | MarketType::LinearSwap => Some(format!("{}-{}", base, quote)),
+        _ => None,
     }
 }
 
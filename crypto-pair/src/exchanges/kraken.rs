@@ -1,3 +1,5 @@
+use crypto_market_type::MarketType;
+
 pub(crate) fn normalize_currency(mut currency: &str) -> String {
     // https://support.kraken.com/hc/en-us/articles/360001185506-How-to-interpret-asset-codes
     if currency.len() > 3 && (currency.starts_with('X') || currency.starts_with('Z')) {
@@ -14,15 +16,58 @@ pub(crate) fn normalize_currency(mut currency: &str) -> String {
     .to_string()
 }
 
+// Quote currencies of REST pair names without a slash, e.g., DOTUSD, DOTXBT
+const QUOTES: &[&str] = &[
+    "USDT", "USDC", "USD", "EUR", "GBP", "JPY", "CAD", "CHF", "AUD", "DAI", "XBT", "ETH",
+];
+
 pub(crate) fn normalize_pair(symbol: &str) -> Option<String> {
-    let (base, quote) = {
-        let v: Vec<&str> = symbol.split('/').collect();
-        (v[0].to_string(), v[1].to_string())
+    let symbol = symbol.to_uppercase();
+    let symbol = symbol.as_str();
+    let (base, quote) = if let Some((base, quote)) = symbol.split_once('/') {
+        // websocket pair names, e.g., XBT/USD
+        (base, quote)
+    } else if symbol.len() == 8
+        && symbol.is_ascii()
+        && (symbol.starts_with('X') || symbol.starts_with('Z'))
+        && (symbol[4..].starts_with('X') || symbol[4..].starts_with('Z'))
+    {
+        // REST pair names with asset codes, e.g., XXBTZUSD, XETHXXBT
+        symbol.split_at(4)
+    } else {
+        // REST pair names, e.g., DOTUSD
+        let quote = QUOTES
+            .iter()
+            .find(|quote| symbol.len() > quote.len() && symbol.ends_with(*quote))?;
+        (symbol.strip_suffix(quote).unwrap(), *quote)
     };
 
     Some(format!(
         "{}/{}",
-        normalize_currency(&base),
-        normalize_currency(&quote)
+        normalize_currency(base),
+        normalize_currency(quote)
     ))
 }
+
+pub(crate) fn denormalize_currency(currency: &str) -> String {
+    if currency == "BTC" {
+        "XBT"
+    } else if currency == "DOGE" {
+        "XDG"
+    } else {
+        currency
+    }
+    .to_string()
+}
+
+pub(crate) fn denormalize_pair(base: &str, quote: &str, market_type: MarketType) -> Option<String> {
+    if market_type == MarketType::Spot {
+        Some(format!(
+            "{}/{}",
+            denormalize_currency(base),
+            denormalize_currency(quote)
+        ))
+    } else {
+        None
+    }
+}
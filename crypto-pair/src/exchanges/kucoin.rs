@@ -1,3 +1,5 @@
+use crypto_market_type::MarketType;
+
 pub(crate) fn normalize_currency(currency: &str) -> String {
     if currency == "XBT" {
         "BTC"
@@ -16,6 +18,8 @@ pub(crate) fn normalize_currency(currency: &str) -> String {
 }
 
 pub(crate) fn normalize_pair(symbol: &str) -> Option<String> {
+    let symbol = symbol.to_uppercase();
+    let symbol = symbol.as_str();
     let (base, quote) = if symbol.ends_with("USDM") {
         // inverse swap
         (
@@ -46,3 +50,34 @@ pub(crate) fn normalize_pair(symbol: &str) -> Option<String> {
         normalize_currency(&quote)
     ))
 }
+
+fn denormalize_currency(currency: &str, market_type: MarketType) -> String {
+    if currency == "BTC" && market_type != MarketType::Spot {
+        "XBT"
+    } else if currency == "BSV" {
+        "BCHSV"
+    } else if currency == "REV" {
+        "R"
+    } else if currency == "WAXP" {
+        "WAX"
+    } else if currency == "OXEN" {
+        "LOKI"
+    } else {
+        currency
+    }
+    .to_string()
+}
+
+pub(crate) fn denormalize_pair(base: &str, quote: &str, market_type: MarketType) -> Option<String> {
+    let base = denormalize_currency(base, market_type);
+    match market_type {
+        MarketType::Spot => Some(format!(
+            "{}-{}",
+            base,
+            denormalize_currency(quote, market_type)
+        )),
+        MarketType::InverseSwap => Some(format!("{}USDM", base)),
+        MarketType::LinearSwap => Some(format!("{}USDTM", base)),
+        _ => None,
+    }
+}
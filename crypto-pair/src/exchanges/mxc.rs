@@ -0,0 +1,15 @@
+use crypto_market_type::MarketType;
+
+// e.g., BTC_USDT for both spot and swap markets
+pub(crate) fn normalize_pair(symbol: &str) -> Option<String> {
+    Some(symbol.replace('_', "/").to_uppercase())
+}
+
+pub(crate) fn denormalize_pair(base: &str, quote: &str, market_type: MarketType) -> Option<String> {
+    match market_type {
+        MarketType::Spot | MarketType::InverseSwap | MarketType::LinearSwap => {
+            Some(format!("{}_{}", base, quote))
+        }
+        _ => None,
+    }
+}
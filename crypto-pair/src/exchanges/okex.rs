@@ -0,0 +1,61 @@
+use crate::OptionInfo;
+use crypto_market_type::MarketType;
+
+// e.g., BTC-USDT, BTC-USD-SWAP
+pub(crate) fn normalize_pair(symbol: &str) -> Option<String> {
+    let v: Vec<&str> = symbol.split('-').collect();
+    if v.len() < 2 {
+        return None;
+    }
+    Some(format!("{}/{}", v[0], v[1]).to_uppercase())
+}
+
+// futures and options need a delivery date, which the pair doesn't have
+pub(crate) fn denormalize_pair(base: &str, quote: &str, market_type: MarketType) -> Option<String> {
+    match market_type {
+        MarketType::Spot => Some(format!("{}-{}", base, quote)),
+        MarketType::InverseSwap | MarketType::LinearSwap => {
+            Some(format!("{}-{}-SWAP", base, quote))
+        }
+        _ => None,
+    }
+}
+
+// e.g., BTC-USDT, BTC-USD-210625, BTC-USDT-SWAP, BTC-USD-210625-72000-C
+pub(crate) fn get_market_type(symbol: &str) -> MarketType {
+    let v: Vec<&str> = symbol.split('-').collect();
+    match v.len() {
+        2 => MarketType::Spot,
+        3 => {
+            // USDT-margined contracts are linear, USD ones are coin-margined
+            let is_inverse = v[1].eq_ignore_ascii_case("USD");
+            match (v[2].eq_ignore_ascii_case("SWAP"), is_inverse) {
+                (true, true) => MarketType::InverseSwap,
+                (true, false) => MarketType::LinearSwap,
+                (false, true) => MarketType::InverseFuture,
+                (false, false) => MarketType::LinearFuture,
+            }
+        }
+        5 => MarketType::EuropeanOption,
+        _ => MarketType::Unknown,
+    }
+}
+
+// e.g., BTC-USD-210625-50000-C
+pub(crate) fn parse_option_symbol(symbol: &str) -> Option<OptionInfo> {
+    let v: Vec<&str> = symbol.split('-').collect();
+    if v.len() != 5 || v[2].len() != 6 || v[2].parse::<u32>().is_err() {
+        return None;
+    }
+    let is_call = match v[4] {
+        "C" => true,
+        "P" => false,
+        _ => return None,
+    };
+    Some(OptionInfo {
+        underlying: format!("{}/{}", v[0], v[1]),
+        expiry: v[2].to_string(),
+        strike: v[3].parse::<f64>().ok()?,
+        is_call,
+    })
+}
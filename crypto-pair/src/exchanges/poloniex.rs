@@ -0,0 +1,14 @@
+use crypto_market_type::MarketType;
+
+// e.g., BTC_ETH
+pub(crate) fn normalize_pair(symbol: &str) -> Option<String> {
+    Some(symbol.replace('_', "/").to_uppercase())
+}
+
+pub(crate) fn denormalize_pair(base: &str, quote: &str, market_type: MarketType) -> Option<String> {
+    if market_type == MarketType::Spot {
+        Some(format!("{}_{}", base, quote))
+    } else {
+        None
+    }
+}
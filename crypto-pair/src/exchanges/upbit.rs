@@ -0,0 +1,14 @@
+use crypto_market_type::MarketType;
+
+// e.g., KRW-BTC
+pub(crate) fn normalize_pair(symbol: &str) -> Option<String> {
+    Some(symbol.replace('-', "/").to_uppercase())
+}
+
+pub(crate) fn denormalize_pair(base: &str, quote: &str, market_type: MarketType) -> Option<String> {
+    if market_type == MarketType::Spot {
+        Some(format!("{}-{}", base, quote))
+    } else {
+        None
+    }
+}
@@ -1,27 +1,5 @@
 use std::collections::HashSet;
 
-use reqwest::{header, Result};
-
-pub(super) fn http_get(url: &str) -> Result<String> {
-    let mut headers = header::HeaderMap::new();
-    headers.insert(
-        header::CONTENT_TYPE,
-        header::HeaderValue::from_static("application/json"),
-    );
-
-    let client = reqwest::blocking::Client::builder()
-         .default_headers(headers)
-         .user_agent("Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/87.0.4280.88 Safari/537.36")
-         .gzip(true)
-         .build()?;
-    let response = client.get(url).send()?;
-
-    match response.error_for_status() {
-        Ok(resp) => Ok(resp.text()?),
-        Err(error) => Err(error),
-    }
-}
-
 pub(super) fn normalize_pair_with_quotes(symbol: &str, quotes: &HashSet<String>) -> Option<String> {
     for quote in quotes.iter() {
         if symbol.ends_with(quote) {
@@ -32,3 +10,26 @@ pub(super) fn normalize_pair_with_quotes(symbol: &str, quotes: &HashSet<String>)
 
     None
 }
+
+#[cfg(all(test, feature = "offline"))]
+mod tests {
+    use crate::NUM_HTTP_REQUESTS;
+    use std::sync::atomic::Ordering;
+
+    #[test]
+    fn no_http_requests() {
+        assert_eq!(
+            Some("BTC/USDT".to_string()),
+            crate::normalize_pair("BTCUSDT", "binance")
+        );
+        assert_eq!(
+            Some("BTC/USDT".to_string()),
+            crate::normalize_pair("btcusdt", "huobi")
+        );
+        assert_eq!(
+            Some("BTC/USD".to_string()),
+            crate::normalize_pair("tBTCUSD", "bitfinex")
+        );
+        assert_eq!(0, NUM_HTTP_REQUESTS.load(Ordering::Relaxed));
+    }
+}
@@ -1,4 +1,8 @@
+use crypto_market_type::MarketType;
+
 pub(crate) fn normalize_pair(symbol: &str) -> Option<String> {
+    let symbol = symbol.to_uppercase();
+    let symbol = symbol.as_str();
     if symbol.ends_with("_USD-R") {
         let base = symbol.strip_suffix("_USD-R").unwrap();
         Some(format!("{}/USD", base))
@@ -6,3 +10,12 @@ pub(crate) fn normalize_pair(symbol: &str) -> Option<String> {
         Some(symbol.replace("_", "/").to_uppercase())
     }
 }
+
+pub(crate) fn denormalize_pair(base: &str, quote: &str, market_type: MarketType) -> Option<String> {
+    match market_type {
+        MarketType::Spot => Some(format!("{}_{}", base, quote).to_lowercase()),
+        MarketType::InverseSwap => Some(format!("{}_{}-R", base, quote)),
+        MarketType::LinearSwap => Some(format!("{}_{}", base, quote)),
+        _ => None,
+    }
+}
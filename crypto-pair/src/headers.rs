@@ -0,0 +1,108 @@
+use lazy_static::lazy_static;
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue, USER_AGENT};
+use std::{collections::HashMap, sync::RwLock};
+
+/// The User-Agent sent to exchanges without a custom one.
+pub const DEFAULT_USER_AGENT: &str = concat!("crypto-crawler-rs/", env!("CARGO_PKG_VERSION"));
+
+lazy_static! {
+    // exchange -> extra headers of HTTP requests and websocket handshakes
+    static ref HEADERS: RwLock<HashMap<String, HeaderMap>> = RwLock::new(HashMap::new());
+}
+
+/// Sets the User-Agent of requests to `exchange`.
+///
+/// Some exchanges rate-limit or block anonymous clients, they identify
+/// whitelisted clients by User-Agent. It applies to all crates of
+/// crypto-crawler-rs, i.e., REST requests, websocket handshakes and requests
+/// fetching symbols, markets and contract values, which re-export it.
+/// Websocket headers are sent when connecting, so set them before creating
+/// clients, reconnects of existing clients pick up the new values.
+///
+/// # Panics
+///
+/// Panics if `user_agent` is not a valid header value.
+///
+/// # Examples
+///
+/// ```
+/// crypto_pair::set_user_agent("binance", "my-crawler/1.0");
+/// ```
+pub fn set_user_agent(exchange: &str, user_agent: &str) {
+    set_header(exchange, USER_AGENT.as_str(), user_agent);
+}
+
+/// Adds a header to requests to `exchange`, replacing the previous value.
+///
+/// # Panics
+///
+/// Panics if `name` or `value` is not a valid header name or value.
+///
+/// # Examples
+///
+/// ```
+/// crypto_pair::set_header("binance", "X-MBX-APIKEY", "my-api-key");
+/// ```
+pub fn set_header(exchange: &str, name: &str, value: &str) {
+    let name = HeaderName::from_bytes(name.as_bytes())
+        .unwrap_or_else(|_| panic!("Invalid header name {}", name));
+    let value = HeaderValue::from_str(value)
+        .unwrap_or_else(|_| panic!("Invalid value of header {}: {}", name, value));
+    HEADERS
+        .write()
+        .unwrap()
+        .entry(exchange.to_string())
+        .or_default()
+        .insert(name, value);
+}
+
+/// Headers configured for `exchange`, with the default User-Agent if not set.
+///
+/// Used by sibling crates to send the same headers, not part of the public API.
+#[doc(hidden)]
+pub fn headers(exchange: &str) -> HeaderMap {
+    let mut headers = HEADERS
+        .read()
+        .unwrap()
+        .get(exchange)
+        .cloned()
+        .unwrap_or_default();
+    headers
+        .entry(USER_AGENT)
+        .or_insert(HeaderValue::from_static(DEFAULT_USER_AGENT));
+    headers
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{headers, set_header, set_user_agent, DEFAULT_USER_AGENT};
+    use reqwest::header::USER_AGENT;
+
+    #[test]
+    fn default_user_agent() {
+        assert!(DEFAULT_USER_AGENT.starts_with("crypto-crawler-rs/"));
+        assert_eq!(
+            DEFAULT_USER_AGENT,
+            headers("no_such_exchange").get(USER_AGENT).unwrap()
+        );
+    }
+
+    #[test]
+    fn per_exchange() {
+        set_user_agent("test_exchange", "my-crawler/1.0");
+        set_header("test_exchange", "X-Api-Key", "key1");
+        set_header("test_exchange", "x-api-key", "key2");
+
+        let headers = headers("test_exchange");
+        assert_eq!(2, headers.len());
+        assert_eq!("my-crawler/1.0", headers.get(USER_AGENT).unwrap());
+        assert_eq!("key2", headers.get("X-API-KEY").unwrap());
+        assert_eq!(1, super::headers("other_exchange").len());
+    }
+
+    #[test]
+    #[should_panic]
+    fn invalid_header_name() {
+        set_header("test_exchange", "bad header", "value");
+    }
+}
@@ -0,0 +1,70 @@
+use once_cell::sync::Lazy;
+use reqwest::header::{self, HeaderMap};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+
+// Number of calls to http_get(), offline tests expect 0, i.e., lazy_static
+// tables don't even try to fetch data with the offline feature
+#[doc(hidden)]
+pub static NUM_HTTP_REQUESTS: AtomicUsize = AtomicUsize::new(0);
+
+/// Sends a GET request to a URL of an exchange and returns the response body.
+///
+/// The arguments are the exchange name and the URL.
+pub type HttpGetFn = dyn Fn(&str, &str) -> Result<String, Box<dyn std::error::Error>> + Send + Sync;
+
+static HTTP_CLIENT: Lazy<RwLock<Option<Arc<HttpGetFn>>>> = Lazy::new(|| RwLock::new(None));
+
+/// Replaces the built-in HTTP client for the data fetched lazily by
+/// crypto-pair, crypto-contract-value and crypto-msg-parser, e.g., currency
+/// mappings, contract values and BitMEX tick sizes.
+///
+/// The client applies process-wide, `None` restores the built-in client, which
+/// sends the headers set by `set_user_agent()` and `set_header()`.
+pub fn set_http_client(client: Option<Arc<HttpGetFn>>) {
+    *HTTP_CLIENT.write().unwrap() = client;
+}
+
+// Fetches data merged into lazy_static tables, shared by crypto-pair,
+// crypto-contract-value and crypto-msg-parser. Callers check the offline
+// feature first, the check here only keeps the network untouched if one
+// doesn't.
+#[doc(hidden)]
+pub fn http_get(exchange: &str, url: &str) -> Result<String, Box<dyn std::error::Error>> {
+    NUM_HTTP_REQUESTS.fetch_add(1, Ordering::Relaxed);
+    if cfg!(feature = "offline") {
+        return Err(format!("{} skipped, the offline feature is enabled", url).into());
+    }
+    let client = HTTP_CLIENT.read().unwrap().clone();
+    if let Some(client) = client {
+        return client(exchange, url);
+    }
+    // User-Agent and custom headers set by set_user_agent() and set_header()
+    let text = get_with_headers(url, crate::headers(exchange), Some(Duration::from_secs(10)))?;
+    Ok(text)
+}
+
+// Sends a GET request with `headers` and a JSON content type, also used by
+// the HTTP clients of crypto-markets
+#[doc(hidden)]
+pub fn get_with_headers(
+    url: &str,
+    mut headers: HeaderMap,
+    timeout: Option<Duration>,
+) -> Result<String, reqwest::Error> {
+    headers.insert(
+        header::CONTENT_TYPE,
+        header::HeaderValue::from_static("application/json"),
+    );
+
+    let mut builder = reqwest::blocking::Client::builder()
+        .default_headers(headers)
+        .gzip(true);
+    if let Some(timeout) = timeout {
+        builder = builder.timeout(timeout);
+    }
+    let client = builder.build()?;
+    let response = client.get(url).send()?;
+    response.error_for_status()?.text()
+}
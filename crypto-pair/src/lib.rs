@@ -1,5 +1,20 @@
 #![allow(clippy::unnecessary_wraps)]
 mod exchanges;
+mod headers;
+mod http;
+
+use crypto_market_type::MarketType;
+use dashmap::DashMap;
+use lazy_static::lazy_static;
+use once_cell::sync::Lazy;
+use std::{collections::HashMap, sync::RwLock};
+
+#[doc(hidden)]
+pub use headers::headers;
+pub use headers::{set_header, set_user_agent, DEFAULT_USER_AGENT};
+#[doc(hidden)]
+pub use http::{get_with_headers, http_get, NUM_HTTP_REQUESTS};
+pub use http::{set_http_client, HttpGetFn};
 
 /// Normalize a trading currency.
 ///
@@ -36,38 +51,299 @@ pub fn normalize_currency(symbol: &str, exchange: &str) -> String {
 /// assert_eq!(Some("BTC/USDT".to_string()), normalize_pair("BTCUST", "bitfinex"));
 /// ```
 pub fn normalize_pair(symbol: &str, exchange: &str) -> Option<String> {
+    overridden_pair(symbol, exchange).or_else(|| normalize_pair_builtin(symbol, exchange))
+}
+
+// Overrides are matched case-insensitively, like the built-in mappings
+fn overridden_pair(symbol: &str, exchange: &str) -> Option<String> {
+    let overrides = OVERRIDES.read().unwrap();
+    let pairs = overrides.get(exchange)?;
+    pairs
+        .get(&symbol.to_lowercase())
+        .map(|(_, normalized)| normalized.clone())
+}
+
+// Symbols are matched case-insensitively, each exchange folds them to the case
+// of its raw symbols first.
+fn normalize_pair_builtin(symbol: &str, exchange: &str) -> Option<String> {
     match exchange {
         "binance" => exchanges::binance::normalize_pair(symbol),
         "bitfinex" => exchanges::bitfinex::normalize_pair(symbol),
         "bitget" => exchanges::bitget::normalize_pair(symbol),
-        "bithumb" => Some(symbol.replace("-", "/")),
+        "bithumb" => exchanges::bithumb::normalize_pair(symbol),
         "bitmex" => exchanges::bitmex::normalize_pair(symbol),
         "bitstamp" => exchanges::bitstamp::normalize_pair(symbol),
-        "bitz" => Some(symbol.replace("_", "/").to_uppercase()),
+        "bitz" => exchanges::bitz::normalize_pair(symbol),
         "bybit" => exchanges::bybit::normalize_pair(symbol),
-        "coinbase_pro" => Some(symbol.replace("-", "/")),
+        "coinbase_pro" => exchanges::coinbase_pro::normalize_pair(symbol),
         "deribit" => exchanges::deribit::normalize_pair(symbol),
         "dydx" => exchanges::dydx::normalize_pair(symbol),
         "ftx" => exchanges::ftx::normalize_pair(symbol),
-        "gate" => {
-            let (base, quote) = {
-                let v: Vec<&str> = symbol.split('_').collect();
-                (v[0].to_string(), v[1].to_string())
-            };
-
-            Some(format!("{}/{}", base, quote))
-        }
+        "gate" => exchanges::gate::normalize_pair(symbol),
         "huobi" => exchanges::huobi::normalize_pair(symbol),
         "kraken" => exchanges::kraken::normalize_pair(symbol),
         "kucoin" => exchanges::kucoin::normalize_pair(symbol),
-        "mxc" => Some(symbol.replace("_", "/")),
-        "okex" => {
-            let v: Vec<&str> = symbol.split('-').collect();
-            Some(format!("{}/{}", v[0], v[1]))
-        }
-        "Poloniex" => Some(symbol.replace("_", "/")),
-        "Upbit" => Some(symbol.replace("-", "/")),
+        "mxc" => exchanges::mxc::normalize_pair(symbol),
+        "okex" => exchanges::okex::normalize_pair(symbol),
+        "Poloniex" => exchanges::poloniex::normalize_pair(symbol),
+        "Upbit" => exchanges::upbit::normalize_pair(symbol),
         "zbg" => exchanges::zbg::normalize_pair(symbol),
         _ => panic!("Unknown exchange {}", exchange),
     }
 }
+
+// exchange -> symbol -> normalized pair, only successful results are cached
+static PAIR_CACHE: Lazy<DashMap<String, DashMap<String, String>>> = Lazy::new(DashMap::new);
+
+// Symbols cached per exchange, a full cache is cleared, which bounds memory
+// if callers feed arbitrary symbols, e.g., from user input.
+const MAX_CACHED_SYMBOLS: usize = 65536;
+
+// lowercase symbol -> (raw symbol, normalized pair)
+type Overrides = HashMap<String, (String, String)>;
+
+lazy_static! {
+    // exchange -> overrides registered by users
+    static ref OVERRIDES: RwLock<HashMap<String, Overrides>> =
+        RwLock::new(HashMap::new());
+}
+
+/// Override the normalized pair of a symbol at runtime.
+///
+/// The built-in mappings are compiled in, this function fixes a wrong
+/// normalization, e.g., of a rebranded token, without waiting for a release.
+/// Overrides take precedence in `normalize_pair()` and `normalize_pair_cached()`,
+/// and `denormalize_pair()` returns the raw symbol for the market type it belongs to.
+/// Symbols are matched case-insensitively.
+///
+/// # Arguments
+///
+/// * `exchange` - The exchange name
+/// * `raw_symbol` - The original symbol of the exchange
+/// * `normalized_pair` - The normalized pair, e.g., `LUNA2/USDT`
+///
+/// # Examples
+///
+/// ```
+/// use crypto_market_type::MarketType;
+/// use crypto_pair::{denormalize_pair, normalize_pair, register_override};
+///
+/// register_override("binance", "LUNAUSDT", "LUNA2/USDT");
+/// assert_eq!(Some("LUNA2/USDT".to_string()), normalize_pair("LUNAUSDT", "binance"));
+/// assert_eq!(Some("LUNAUSDT".to_string()), denormalize_pair("LUNA2/USDT", "binance", MarketType::Spot));
+/// ```
+pub fn register_override(exchange: &str, raw_symbol: &str, normalized_pair: &str) {
+    OVERRIDES
+        .write()
+        .unwrap()
+        .entry(exchange.to_string())
+        .or_default()
+        .insert(
+            raw_symbol.to_lowercase(),
+            (raw_symbol.to_string(), normalized_pair.to_string()),
+        );
+}
+
+/// Same as `normalize_pair()`, but memoizes results.
+///
+/// Symbols repeat a lot in market data, so message parsers should use this
+/// function, a symbol seen before only costs a lookup in a concurrent hash map.
+/// Unknown symbols are not cached and each exchange caches at most 65536
+/// symbols. `normalize_pair()` remains the better choice for one-off calls.
+///
+/// # Examples
+///
+/// ```
+/// use crypto_pair::normalize_pair_cached;
+///
+/// assert_eq!(Some("BTC/USDT".to_string()), normalize_pair_cached("BTCUSDT", "binance"));
+/// assert_eq!(Some("BTC/USDT".to_string()), normalize_pair_cached("BTCUSDT", "binance"));
+/// ```
+pub fn normalize_pair_cached(symbol: &str, exchange: &str) -> Option<String> {
+    // The cache only holds built-in results, overrides are looked up first,
+    // so an override registered concurrently can't be shadowed by a stale entry
+    if let Some(pair) = overridden_pair(symbol, exchange) {
+        return Some(pair);
+    }
+    if let Some(pair) = PAIR_CACHE
+        .get(exchange)
+        .and_then(|pairs| pairs.get(symbol).map(|pair| pair.clone()))
+    {
+        return Some(pair);
+    }
+
+    // unknown symbols are not cached, they may be listed later
+    let pair = normalize_pair_builtin(symbol, exchange)?;
+    let pairs = PAIR_CACHE.entry(exchange.to_string()).or_default();
+    if pairs.len() >= MAX_CACHED_SYMBOLS {
+        pairs.clear();
+    }
+    pairs.insert(symbol.to_string(), pair.clone());
+    Some(pair)
+}
+
+/// Strike, expiry and type of an option contract.
+#[derive(Clone, Debug, PartialEq)]
+pub struct OptionInfo {
+    /// The normalized pair of the underlying, e.g., `BTC/USD`
+    pub underlying: String,
+    /// Expiry date in `YYMMDD` format, e.g., `210625`
+    pub expiry: String,
+    /// Strike price
+    pub strike: f64,
+    /// true for call options, false for put options
+    pub is_call: bool,
+}
+
+/// Parse an option symbol into its underlying, expiry, strike and type.
+///
+/// `normalize_pair()` maps all options of the same underlying to one pair,
+/// so use this function to distinguish option instruments.
+///
+/// Returns `None` if the symbol is not an option or the exchange doesn't
+/// have options.
+///
+/// # Arguments
+///
+/// * `symbol` - The original option symbol of an exchange
+/// * `exchange` - The exchange name
+///
+/// # Examples
+///
+/// ```
+/// use crypto_pair::{parse_option_symbol, OptionInfo};
+///
+/// assert_eq!(
+///     Some(OptionInfo {
+///         underlying: "BTC/USD".to_string(),
+///         expiry: "210625".to_string(),
+///         strike: 50000.0,
+///         is_call: true,
+///     }),
+///     parse_option_symbol("BTC-25JUN21-50000-C", "deribit")
+/// );
+/// assert_eq!(
+///     Some(OptionInfo {
+///         underlying: "BTC/USD".to_string(),
+///         expiry: "210625".to_string(),
+///         strike: 50000.0,
+///         is_call: false,
+///     }),
+///     parse_option_symbol("BTC-USD-210625-50000-P", "okex")
+/// );
+/// ```
+pub fn parse_option_symbol(symbol: &str, exchange: &str) -> Option<OptionInfo> {
+    match exchange {
+        "bitmex" => exchanges::bitmex::parse_option_symbol(symbol),
+        "deribit" => exchanges::deribit::parse_option_symbol(symbol),
+        "okex" => exchanges::okex::parse_option_symbol(symbol),
+        _ => None,
+    }
+}
+
+/// Infer the market type of a symbol from its name.
+///
+/// Only exchanges whose symbols encode the market type are supported, i.e.,
+/// okex, `MarketType::Unknown` is returned for other exchanges and symbols
+/// not recognized.
+///
+/// # Examples
+///
+/// ```
+/// use crypto_market_type::MarketType;
+/// use crypto_pair::get_market_type;
+///
+/// assert_eq!(MarketType::Spot, get_market_type("BTC-USDT", "okex"));
+/// assert_eq!(MarketType::InverseFuture, get_market_type("BTC-USD-210625", "okex"));
+/// assert_eq!(MarketType::LinearSwap, get_market_type("BTC-USDT-SWAP", "okex"));
+/// assert_eq!(MarketType::EuropeanOption, get_market_type("BTC-USD-210625-72000-C", "okex"));
+/// assert_eq!(MarketType::Unknown, get_market_type("BTCUSDT", "binance"));
+/// ```
+pub fn get_market_type(symbol: &str, exchange: &str) -> MarketType {
+    match exchange {
+        "okex" => exchanges::okex::get_market_type(symbol),
+        _ => MarketType::Unknown,
+    }
+}
+
+/// Convert a normalized trading pair back to the exchange-specific symbol.
+///
+/// Returns `None` if the symbol can NOT be determined by the pair alone,
+/// for example, futures with delivery dates and options, if the exchange
+/// doesn't list the market type, or if the exchange is unknown.
+///
+/// # Arguments
+///
+/// * `pair` - The normalized pair, e.g., `BTC/USDT`
+/// * `exchange` - The exchange name
+/// * `market_type` - The market type
+///
+/// # Examples
+///
+/// ```
+/// use crypto_market_type::MarketType;
+/// use crypto_pair::denormalize_pair;
+///
+/// assert_eq!(Some("XBTUSD".to_string()), denormalize_pair("BTC/USD", "bitmex", MarketType::InverseSwap));
+/// assert_eq!(Some("BTCUSDT".to_string()), denormalize_pair("BTC/USDT", "binance", MarketType::Spot));
+/// assert_eq!(Some("btcusdt".to_string()), denormalize_pair("BTC/USDT", "huobi", MarketType::Spot));
+/// assert_eq!(Some("BTC-USDT".to_string()), denormalize_pair("BTC/USDT", "okex", MarketType::Spot));
+/// assert_eq!(Some("BTC-USDT-SWAP".to_string()), denormalize_pair("BTC/USDT", "okex", MarketType::LinearSwap));
+/// assert_eq!(None, denormalize_pair("BTC/USDT", "unknown", MarketType::Spot));
+/// ```
+pub fn denormalize_pair(pair: &str, exchange: &str, market_type: MarketType) -> Option<String> {
+    // An overridden symbol belongs to market_type if the built-in mappings
+    // round-trip it under this market type
+    let overridden = OVERRIDES.read().unwrap().get(exchange).and_then(|pairs| {
+        pairs
+            .values()
+            .filter(|(symbol, normalized)| {
+                *normalized == pair
+                    && normalize_pair_builtin(symbol, exchange)
+                        .and_then(|p| denormalize_pair_builtin(&p, exchange, market_type))
+                        .as_deref()
+                        == Some(symbol.as_str())
+            })
+            .map(|(symbol, _)| symbol.clone())
+            .min()
+    });
+    if overridden.is_some() {
+        return overridden;
+    }
+    denormalize_pair_builtin(pair, exchange, market_type)
+}
+
+fn denormalize_pair_builtin(pair: &str, exchange: &str, market_type: MarketType) -> Option<String> {
+    let (base, quote) = {
+        let v: Vec<&str> = pair.split('/').collect();
+        if v.len() != 2 {
+            return None;
+        }
+        (v[0], v[1])
+    };
+
+    match exchange {
+        "binance" => exchanges::binance::denormalize_pair(base, quote, market_type),
+        "bitfinex" => exchanges::bitfinex::denormalize_pair(base, quote, market_type),
+        "bitget" => exchanges::bitget::denormalize_pair(base, quote, market_type),
+        "bithumb" => exchanges::bithumb::denormalize_pair(base, quote, market_type),
+        "bitmex" => exchanges::bitmex::denormalize_pair(base, quote, market_type),
+        "bitstamp" => exchanges::bitstamp::denormalize_pair(base, quote, market_type),
+        "bitz" => exchanges::bitz::denormalize_pair(base, quote, market_type),
+        "bybit" => exchanges::bybit::denormalize_pair(base, quote, market_type),
+        "coinbase_pro" => exchanges::coinbase_pro::denormalize_pair(base, quote, market_type),
+        "deribit" => exchanges::deribit::denormalize_pair(base, quote, market_type),
+        "dydx" => exchanges::dydx::denormalize_pair(base, quote, market_type),
+        "ftx" => exchanges::ftx::denormalize_pair(base, quote, market_type),
+        "gate" => exchanges::gate::denormalize_pair(base, quote, market_type),
+        "huobi" => exchanges::huobi::denormalize_pair(base, quote, market_type),
+        "kraken" => exchanges::kraken::denormalize_pair(base, quote, market_type),
+        "kucoin" => exchanges::kucoin::denormalize_pair(base, quote, market_type),
+        "mxc" => exchanges::mxc::denormalize_pair(base, quote, market_type),
+        "okex" => exchanges::okex::denormalize_pair(base, quote, market_type),
+        "Poloniex" => exchanges::poloniex::denormalize_pair(base, quote, market_type),
+        "Upbit" => exchanges::upbit::denormalize_pair(base, quote, market_type),
+        "zbg" => exchanges::zbg::denormalize_pair(base, quote, market_type),
+        _ => None,
+    }
+}
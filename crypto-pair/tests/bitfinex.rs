@@ -29,3 +29,20 @@ fn verify_linear_swap_symbols() {
         normalize_pair("tBTCF0:USTF0", EXCHANGE_NAME).unwrap()
     );
 }
+
+#[test]
+fn t_prefix() {
+    assert_eq!(
+        "TRX/USD".to_string(),
+        normalize_pair("tTRXUSD", EXCHANGE_NAME).unwrap()
+    );
+    assert_eq!(
+        "TRX/USD".to_string(),
+        normalize_pair("trxusd", EXCHANGE_NAME).unwrap()
+    );
+    // ambiguous in lowercase, so lowercase symbols must come without the prefix
+    assert_eq!(
+        "TBTC/USD".to_string(),
+        normalize_pair("tbtcusd", EXCHANGE_NAME).unwrap()
+    );
+}
@@ -9,3 +9,20 @@ fn verify_spot_symbols() {
         normalize_pair("ETH-USDT", EXCHANGE_NAME).unwrap()
     );
 }
+
+#[test]
+fn verify_krw_symbols() {
+    assert_eq!(
+        "BTC/KRW".to_string(),
+        normalize_pair("BTC-KRW", EXCHANGE_NAME).unwrap()
+    );
+    assert_eq!(
+        "BTC/KRW".to_string(),
+        normalize_pair("BTC_KRW", EXCHANGE_NAME).unwrap()
+    );
+    assert_eq!(
+        "ETH/KRW".to_string(),
+        normalize_pair("eth_krw", EXCHANGE_NAME).unwrap()
+    );
+    assert_eq!(None, normalize_pair("BTCKRW", EXCHANGE_NAME));
+}
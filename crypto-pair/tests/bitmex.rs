@@ -1,6 +1,7 @@
 mod utils;
 
-use crypto_pair::{normalize_currency, normalize_pair};
+use crypto_market_type::MarketType;
+use crypto_pair::{denormalize_pair, normalize_currency, normalize_pair, parse_option_symbol};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::collections::HashMap;
@@ -54,3 +55,33 @@ fn verify_normalize_pair() {
         assert_eq!(pair, pair_expected);
     }
 }
+
+#[test]
+fn verify_denormalize_pair() {
+    assert_eq!(
+        Some("XBTUSD".to_string()),
+        denormalize_pair("BTC/USD", EXCHANGE_NAME, MarketType::InverseSwap)
+    );
+    assert_eq!(
+        Some("ETHUSD".to_string()),
+        denormalize_pair("ETH/USD", EXCHANGE_NAME, MarketType::QuantoSwap)
+    );
+    assert_eq!(
+        Some("BTC/USD".to_string()),
+        normalize_pair(
+            &denormalize_pair("BTC/USD", EXCHANGE_NAME, MarketType::InverseSwap).unwrap(),
+            EXCHANGE_NAME
+        )
+    );
+    assert_eq!(
+        None,
+        denormalize_pair("BTC/USD", EXCHANGE_NAME, MarketType::InverseFuture)
+    );
+}
+
+#[test]
+fn parse_options() {
+    // Up/Down contracts have relative strikes
+    assert_eq!(None, parse_option_symbol("XBT7D_U105", EXCHANGE_NAME));
+    assert_eq!(None, parse_option_symbol("XBTUSD", EXCHANGE_NAME));
+}
@@ -0,0 +1,269 @@
+use crypto_market_type::{get_market_types, MarketType};
+use crypto_pair::{denormalize_pair, get_market_type, normalize_pair};
+
+// (exchange, market_type, symbol, normalized pair) of the same assets on different exchanges
+const SYMBOLS: &[(&str, MarketType, &str, &str)] = &[
+    ("binance", MarketType::Spot, "BTCUSDT", "BTC/USDT"),
+    ("binance", MarketType::Spot, "ETHBTC", "ETH/BTC"),
+    ("binance", MarketType::InverseSwap, "BTCUSD_PERP", "BTC/USD"),
+    ("binance", MarketType::LinearSwap, "BTCUSDT", "BTC/USDT"),
+    ("bitfinex", MarketType::Spot, "tBTCUSD", "BTC/USD"),
+    ("bitfinex", MarketType::Spot, "tBTCUST", "BTC/USDT"),
+    (
+        "bitfinex",
+        MarketType::LinearSwap,
+        "tBTCF0:USTF0",
+        "BTC/USDT",
+    ),
+    ("bitget", MarketType::InverseSwap, "btcusd", "BTC/USD"),
+    ("bitget", MarketType::LinearSwap, "cmt_btcusdt", "BTC/USDT"),
+    ("bithumb", MarketType::Spot, "BTC-USDT", "BTC/USDT"),
+    ("bitmex", MarketType::InverseSwap, "XBTUSD", "BTC/USD"),
+    ("bitmex", MarketType::InverseSwap, "ETHUSD", "ETH/USD"),
+    ("bitstamp", MarketType::Spot, "btcusd", "BTC/USD"),
+    ("bitstamp", MarketType::Spot, "ethbtc", "ETH/BTC"),
+    ("bitz", MarketType::Spot, "btc_usdt", "BTC/USDT"),
+    ("bybit", MarketType::InverseSwap, "BTCUSD", "BTC/USD"),
+    ("bybit", MarketType::LinearSwap, "BTCUSDT", "BTC/USDT"),
+    ("coinbase_pro", MarketType::Spot, "BTC-USD", "BTC/USD"),
+    ("coinbase_pro", MarketType::Spot, "ETH-BTC", "ETH/BTC"),
+    (
+        "deribit",
+        MarketType::InverseSwap,
+        "BTC-PERPETUAL",
+        "BTC/USD",
+    ),
+    ("dydx", MarketType::LinearSwap, "BTC-USD", "BTC/USD"),
+    ("ftx", MarketType::Spot, "BTC/USDT", "BTC/USDT"),
+    ("ftx", MarketType::LinearSwap, "BTC-PERP", "BTC/USD"),
+    ("gate", MarketType::Spot, "BTC_USDT", "BTC/USDT"),
+    ("gate", MarketType::InverseSwap, "BTC_USD", "BTC/USD"),
+    ("gate", MarketType::LinearSwap, "BTC_USDT", "BTC/USDT"),
+    ("huobi", MarketType::Spot, "btcusdt", "BTC/USDT"),
+    ("huobi", MarketType::InverseSwap, "BTC-USD", "BTC/USD"),
+    ("huobi", MarketType::LinearSwap, "BTC-USDT", "BTC/USDT"),
+    ("kraken", MarketType::Spot, "XBT/USD", "BTC/USD"),
+    ("kraken", MarketType::Spot, "XBT/USDT", "BTC/USDT"),
+    ("kraken", MarketType::Spot, "ETH/XBT", "ETH/BTC"),
+    ("kucoin", MarketType::Spot, "BTC-USDT", "BTC/USDT"),
+    ("kucoin", MarketType::InverseSwap, "XBTUSDM", "BTC/USD"),
+    ("kucoin", MarketType::LinearSwap, "XBTUSDTM", "BTC/USDT"),
+    ("mxc", MarketType::Spot, "BTC_USDT", "BTC/USDT"),
+    ("mxc", MarketType::InverseSwap, "BTC_USD", "BTC/USD"),
+    ("mxc", MarketType::LinearSwap, "BTC_USDT", "BTC/USDT"),
+    ("okex", MarketType::Spot, "BTC-USDT", "BTC/USDT"),
+    ("okex", MarketType::InverseSwap, "BTC-USD-SWAP", "BTC/USD"),
+    ("okex", MarketType::LinearSwap, "BTC-USDT-SWAP", "BTC/USDT"),
+    ("zbg", MarketType::Spot, "btc_usdt", "BTC/USDT"),
+    ("zbg", MarketType::InverseSwap, "BTC_USD-R", "BTC/USD"),
+    ("zbg", MarketType::LinearSwap, "BTC_USDT", "BTC/USDT"),
+];
+
+// Spot pairs of REST APIs, which denormalize_pair() doesn't return
+const SPOT_REST: &[(&str, &str, &str)] = &[
+    ("kraken", "XXBTZUSD", "BTC/USD"),
+    ("kraken", "XETHXXBT", "ETH/BTC"),
+];
+
+// Futures have delivery dates, so they can't be denormalized from pairs
+const FUTURES: &[(&str, &str, &str)] = &[
+    ("binance", "BTCUSD_210625", "BTC/USD"),
+    ("binance", "BTCUSDT_210625", "BTC/USDT"),
+    ("bitmex", "XBTM21", "BTC/USD"),
+    ("bitmex", "XBTUSDTM21", "BTC/USDT"),
+    ("bitmex", "ETHUSDM21", "ETH/USD"),
+    ("bybit", "BTCUSDM21", "BTC/USD"),
+    ("deribit", "BTC-25JUN21", "BTC/USD"),
+    ("ftx", "BTC-0625", "BTC/USD"),
+    ("gate", "BTC_USD_20210625", "BTC/USD"),
+    ("huobi", "BTC210625", "BTC/USD"),
+    ("kucoin", "XBTMM21", "BTC/USD"),
+    ("okex", "BTC-USD-210625", "BTC/USD"),
+    ("okex", "BTC-USDT-210625", "BTC/USDT"),
+];
+
+#[test]
+fn same_asset_same_pair() {
+    let wrong = SYMBOLS
+        .iter()
+        .map(|(exchange, _, symbol, pair)| (*exchange, *symbol, *pair))
+        .chain(SPOT_REST.iter().copied())
+        .filter(|(exchange, symbol, pair)| {
+            normalize_pair(symbol, exchange).as_deref() != Some(*pair)
+        })
+        .map(|(exchange, symbol, _)| {
+            format!(
+                "{} {} => {:?}",
+                exchange,
+                symbol,
+                normalize_pair(symbol, exchange)
+            )
+        })
+        .collect::<Vec<String>>();
+    assert!(wrong.is_empty(), "{:?}", wrong);
+}
+
+#[test]
+fn futures_same_pair() {
+    let wrong = FUTURES
+        .iter()
+        .filter(|(exchange, symbol, pair)| {
+            normalize_pair(symbol, exchange).as_deref() != Some(*pair)
+        })
+        .map(|(exchange, symbol, _)| {
+            format!(
+                "{} {} => {:?}",
+                exchange,
+                symbol,
+                normalize_pair(symbol, exchange)
+            )
+        })
+        .collect::<Vec<String>>();
+    assert!(wrong.is_empty(), "{:?}", wrong);
+}
+
+#[test]
+fn round_trip() {
+    let wrong = SYMBOLS
+        .iter()
+        .filter(|(exchange, market_type, symbol, pair)| {
+            denormalize_pair(pair, exchange, *market_type).as_deref() != Some(*symbol)
+        })
+        .map(|(exchange, market_type, symbol, pair)| {
+            format!(
+                "{} {} {} => {:?}",
+                exchange,
+                symbol,
+                market_type,
+                denormalize_pair(pair, exchange, *market_type)
+            )
+        })
+        .collect::<Vec<String>>();
+    assert!(wrong.is_empty(), "{:?}", wrong);
+}
+
+// Futures, options and market types an exchange doesn't list can't be denormalized
+#[test]
+fn unsupported_market_types() {
+    let market_types = [
+        MarketType::Spot,
+        MarketType::LinearFuture,
+        MarketType::InverseFuture,
+        MarketType::LinearSwap,
+        MarketType::InverseSwap,
+        MarketType::AmericanOption,
+        MarketType::EuropeanOption,
+        MarketType::QuantoFuture,
+        MarketType::QuantoSwap,
+        MarketType::Move,
+        MarketType::BVOL,
+    ];
+    let mut exchanges = SYMBOLS
+        .iter()
+        .map(|(exchange, _, _, _)| *exchange)
+        .chain(FUTURES.iter().map(|(exchange, _, _)| *exchange))
+        .collect::<Vec<&str>>();
+    exchanges.sort_unstable();
+    exchanges.dedup();
+
+    let wrong = exchanges
+        .iter()
+        .flat_map(|exchange| {
+            let listed = get_market_types(exchange);
+            market_types
+                .iter()
+                .filter(move |market_type| {
+                    !matches!(
+                        market_type,
+                        MarketType::Spot
+                            | MarketType::LinearSwap
+                            | MarketType::InverseSwap
+                            | MarketType::QuantoSwap
+                    ) || !listed.contains(market_type)
+                })
+                .filter_map(move |market_type| {
+                    denormalize_pair("BTC/USD", exchange, *market_type)
+                        .map(|symbol| format!("{} {} => {}", exchange, market_type, symbol))
+                })
+        })
+        .collect::<Vec<String>>();
+    assert!(wrong.is_empty(), "{:?}", wrong);
+}
+
+// Symbols are case-insensitive, e.g., binance accepts both btcusdt and BTCUSDT
+#[test]
+fn lowercase_symbols() {
+    let wrong = SYMBOLS
+        .iter()
+        .map(|(exchange, _, symbol, pair)| (*exchange, *symbol, *pair))
+        .chain(SPOT_REST.iter().copied())
+        .chain(FUTURES.iter().copied())
+        .map(|(exchange, symbol, pair)| {
+            // the t prefix of bitfinex is ambiguous in lowercase, e.g., trxusd
+            let symbol = if exchange == "bitfinex" {
+                symbol.strip_prefix('t').unwrap_or(symbol)
+            } else {
+                symbol
+            };
+            (exchange, symbol.to_lowercase(), pair)
+        })
+        .filter(|(exchange, symbol, pair)| {
+            normalize_pair(symbol, exchange).as_deref() != Some(*pair)
+        })
+        .map(|(exchange, symbol, _)| {
+            format!(
+                "{} {} => {:?}",
+                exchange,
+                symbol,
+                normalize_pair(&symbol, exchange)
+            )
+        })
+        .collect::<Vec<String>>();
+    assert!(wrong.is_empty(), "{:?}", wrong);
+}
+
+#[test]
+fn uppercase_symbols() {
+    // bitfinex symbols start with a lowercase t
+    let wrong = SYMBOLS
+        .iter()
+        .filter(|(exchange, _, symbol, pair)| {
+            *exchange != "bitfinex"
+                && normalize_pair(&symbol.to_uppercase(), exchange).as_deref() != Some(*pair)
+        })
+        .map(|(exchange, _, symbol, _)| {
+            format!(
+                "{} {} => {:?}",
+                exchange,
+                symbol.to_uppercase(),
+                normalize_pair(&symbol.to_uppercase(), exchange)
+            )
+        })
+        .collect::<Vec<String>>();
+    assert!(wrong.is_empty(), "{:?}", wrong);
+}
+
+// okex symbols encode their market types
+#[test]
+fn okex_market_types() {
+    let wrong = SYMBOLS
+        .iter()
+        .filter(|(exchange, market_type, symbol, _)| {
+            *exchange == "okex" && get_market_type(symbol, exchange) != *market_type
+        })
+        .map(|(exchange, market_type, symbol, _)| {
+            format!(
+                "{} {} {} => {}",
+                exchange,
+                symbol,
+                market_type,
+                get_market_type(symbol, exchange)
+            )
+        })
+        .collect::<Vec<String>>();
+    assert!(wrong.is_empty(), "{:?}", wrong);
+    assert_eq!(
+        MarketType::LinearFuture,
+        get_market_type("BTC-USDT-210625", "okex")
+    );
+}
@@ -1,6 +1,6 @@
 mod utils;
 
-use crypto_pair::{normalize_currency, normalize_pair};
+use crypto_pair::{normalize_currency, normalize_pair, parse_option_symbol, OptionInfo};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::collections::HashMap;
@@ -76,3 +76,27 @@ fn verify_all_symbols() {
         assert_eq!(pair.as_str(), pair_expected);
     }
 }
+
+#[test]
+fn parse_options() {
+    assert_eq!(
+        Some(OptionInfo {
+            underlying: "BTC/USD".to_string(),
+            expiry: "210625".to_string(),
+            strike: 50000.0,
+            is_call: true,
+        }),
+        parse_option_symbol("BTC-25JUN21-50000-C", EXCHANGE_NAME)
+    );
+    assert_eq!(
+        Some(OptionInfo {
+            underlying: "ETH/USD".to_string(),
+            expiry: "210305".to_string(),
+            strike: 1500.0,
+            is_call: false,
+        }),
+        parse_option_symbol("ETH-5MAR21-1500-P", EXCHANGE_NAME)
+    );
+    assert_eq!(None, parse_option_symbol("BTC-PERPETUAL", EXCHANGE_NAME));
+    assert_eq!(None, parse_option_symbol("BTC-25JUN21", EXCHANGE_NAME));
+}
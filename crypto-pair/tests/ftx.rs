@@ -69,3 +69,30 @@ fn verify_all_symbols() {
         assert_eq!(pair.as_str(), pair_expected);
     }
 }
+
+#[test]
+fn normalize_pair_table() {
+    let cases = [
+        ("BTC/USD", "BTC/USD"),
+        ("ETH/BTC", "ETH/BTC"),
+        ("BVOL/USD", "BVOL/USD"),
+        ("IBVOL/BTC", "IBVOL/BTC"),
+        ("BTC-PERP", "BTC/USD"),
+        ("SHIT-PERP", "SHIT/USD"),
+        ("BTC-0326", "BTC/USD"),
+        ("DEFI-1231", "DEFI/USD"),
+        ("BTC-MOVE-0326", "BTC/USD"),
+        ("BTC-MOVE-WK-0326", "BTC/USD"),
+        ("BTC-MOVE-2021Q1", "BTC/USD"),
+        ("BREXIT", "BREXIT/USD"),
+        ("TRUMP2024", "TRUMP2024/USD"),
+    ];
+    for (symbol, pair) in cases {
+        assert_eq!(
+            Some(pair.to_string()),
+            normalize_pair(symbol, EXCHANGE_NAME),
+            "{}",
+            symbol
+        );
+    }
+}
@@ -0,0 +1,17 @@
+use crypto_pair::{http_get, set_http_client};
+use std::sync::Arc;
+
+#[test]
+#[cfg(not(feature = "offline"))]
+fn custom_http_client() {
+    set_http_client(Some(Arc::new(|exchange: &str, url: &str| {
+        Ok(format!("{} {}", exchange, url))
+    })));
+    assert_eq!(
+        "bitmex https://www.bitmex.com/api/v1/instrument/active",
+        http_get("bitmex", "https://www.bitmex.com/api/v1/instrument/active").unwrap()
+    );
+
+    set_http_client(None);
+    assert!(http_get("bitmex", "http://127.0.0.1:1/").is_err());
+}
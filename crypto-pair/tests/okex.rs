@@ -1,6 +1,9 @@
 mod utils;
 
-use crypto_pair::{normalize_currency, normalize_pair};
+use crypto_market_type::MarketType;
+use crypto_pair::{
+    denormalize_pair, normalize_currency, normalize_pair, parse_option_symbol, OptionInfo,
+};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::collections::HashMap;
@@ -115,3 +118,34 @@ fn verify_option_symbols() {
         assert_eq!(pair.as_str(), pair_expected);
     }
 }
+
+#[test]
+fn verify_denormalize_pair() {
+    assert_eq!(
+        Some("BTC-USDT".to_string()),
+        denormalize_pair("BTC/USDT", EXCHANGE_NAME, MarketType::Spot)
+    );
+    assert_eq!(
+        Some("BTC-USD-SWAP".to_string()),
+        denormalize_pair("BTC/USD", EXCHANGE_NAME, MarketType::InverseSwap)
+    );
+    assert_eq!(
+        Some("BTC-USDT-SWAP".to_string()),
+        denormalize_pair("BTC/USDT", EXCHANGE_NAME, MarketType::LinearSwap)
+    );
+}
+
+#[test]
+fn parse_options() {
+    assert_eq!(
+        Some(OptionInfo {
+            underlying: "BTC/USD".to_string(),
+            expiry: "210625".to_string(),
+            strike: 50000.0,
+            is_call: false,
+        }),
+        parse_option_symbol("BTC-USD-210625-50000-P", EXCHANGE_NAME)
+    );
+    assert_eq!(None, parse_option_symbol("BTC-USD-210625", EXCHANGE_NAME));
+    assert_eq!(None, parse_option_symbol("BTC-USDT-SWAP", EXCHANGE_NAME));
+}
@@ -0,0 +1,79 @@
+use crypto_market_type::MarketType;
+use crypto_pair::{denormalize_pair, normalize_pair, normalize_pair_cached, register_override};
+
+#[test]
+fn override_normalize_pair() {
+    assert_eq!(
+        Some("UST/USDT".to_string()),
+        normalize_pair_cached("USTUSDT", "binance")
+    );
+    register_override("binance", "USTUSDT", "USTC/USDT");
+    assert_eq!(
+        Some("USTC/USDT".to_string()),
+        normalize_pair("USTUSDT", "binance")
+    );
+    // cached results of the built-in mapping are replaced
+    assert_eq!(
+        Some("USTC/USDT".to_string()),
+        normalize_pair_cached("USTUSDT", "binance")
+    );
+    // other exchanges are not affected
+    assert_eq!(
+        Some("UST/USDT".to_string()),
+        normalize_pair("UST-USDT", "okex")
+    );
+}
+
+#[test]
+fn override_case_insensitive() {
+    register_override("huobi", "lunausdt", "LUNA2/USDT");
+    assert_eq!(
+        Some("LUNA2/USDT".to_string()),
+        normalize_pair_cached("LUNAUSDT", "huobi")
+    );
+    assert_eq!(
+        Some("LUNA2/USDT".to_string()),
+        normalize_pair("lunausdt", "huobi")
+    );
+    // the raw symbol is returned as registered
+    assert_eq!(
+        Some("lunausdt".to_string()),
+        denormalize_pair("LUNA2/USDT", "huobi", MarketType::Spot)
+    );
+}
+
+#[test]
+fn override_denormalize_pair() {
+    register_override("okex", "LUNA-USDT", "LUNA2/USDT");
+    register_override("okex", "LUNA-USDT-SWAP", "LUNA2/USDT");
+
+    assert_eq!(
+        Some("LUNA-USDT".to_string()),
+        denormalize_pair("LUNA2/USDT", "okex", MarketType::Spot)
+    );
+    assert_eq!(
+        Some("LUNA-USDT-SWAP".to_string()),
+        denormalize_pair("LUNA2/USDT", "okex", MarketType::LinearSwap)
+    );
+    // pairs without overrides use the built-in mapping
+    assert_eq!(
+        Some("BTC-USDT".to_string()),
+        denormalize_pair("BTC/USDT", "okex", MarketType::Spot)
+    );
+}
+
+#[test]
+fn override_concurrently() {
+    let handles = (0..8)
+        .map(|i| {
+            std::thread::spawn(move || {
+                let symbol = format!("TOKEN{}-USD", i);
+                register_override("coinbase_pro", &symbol, &format!("NEW{}/USD", i));
+                normalize_pair_cached(&symbol, "coinbase_pro")
+            })
+        })
+        .collect::<Vec<_>>();
+    for (i, handle) in handles.into_iter().enumerate() {
+        assert_eq!(Some(format!("NEW{}/USD", i)), handle.join().unwrap());
+    }
+}
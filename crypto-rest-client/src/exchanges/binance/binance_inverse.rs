@@ -1,8 +1,9 @@
-use super::super::utils::http_get;
+use super::super::utils::{http_get, round_up_depth};
 use super::utils::*;
 use crate::error::Result;
 use std::collections::BTreeMap;
 
+const EXCHANGE_NAME: &str = "binance";
 const BASE_URL: &str = "https://dapi.binance.com";
 
 /// Binance Coin-margined Future and Swap market
@@ -60,9 +61,20 @@ impl BinanceInverseRestClient {
     /// - <https://dapi.binance.com/dapi/v1/depth?symbol=BTCUSD_PERP&limit=1000>
     /// - <https://dapi.binance.com/dapi/v1/depth?symbol=BTCUSD_211231&limit=1000>
     pub fn fetch_l2_snapshot(symbol: &str) -> Result<String> {
+        Self::fetch_l2_snapshot_with_depth(symbol, None)
+    }
+
+    /// Same as `fetch_l2_snapshot()`, with the number of levels set by `depth`.
+    ///
+    /// `depth` is rounded up to the nearest valid limit, at most 1000.
+    pub fn fetch_l2_snapshot_with_depth(symbol: &str, depth: Option<usize>) -> Result<String> {
         check_symbol(symbol);
         let symbol = Some(symbol);
-        let limit = Some(1000);
+        let limit = Some(round_up_depth(
+            depth,
+            &[5, 10, 20, 50, 100, 500, 1000],
+            1000,
+        )?);
         gen_api_binance!("/dapi/v1/depth", symbol, limit)
     }
 
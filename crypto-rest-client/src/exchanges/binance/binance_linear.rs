@@ -1,8 +1,9 @@
-use super::super::utils::http_get;
+use super::super::utils::{http_get, round_up_depth};
 use super::utils::*;
 use crate::error::Result;
 use std::collections::BTreeMap;
 
+const EXCHANGE_NAME: &str = "binance";
 const BASE_URL: &str = "https://fapi.binance.com";
 
 /// Binance USDT-margined Future and Swap market.
@@ -51,6 +52,35 @@ impl BinanceLinearRestClient {
         )
     }
 
+    /// Get candlesticks, oldest first.
+    ///
+    /// Equivalent to `/fapi/v1/klines` with `limit=1000`, `start_time` and `end_time`
+    /// are in milliseconds and match the open time of candlesticks.
+    ///
+    /// For example: <https://fapi.binance.com/fapi/v1/klines?symbol=BTCUSDT&interval=1m&limit=1000>
+    #[allow(non_snake_case)]
+    pub fn fetch_klines(
+        symbol: &str,
+        interval: &str,
+        start_time: Option<u64>,
+        end_time: Option<u64>,
+    ) -> Result<String> {
+        check_symbol(symbol);
+        let symbol = Some(symbol);
+        let interval = Some(interval);
+        let startTime = start_time;
+        let endTime = end_time;
+        let limit = Some(1000);
+        gen_api_binance!(
+            "/fapi/v1/klines",
+            symbol,
+            interval,
+            startTime,
+            endTime,
+            limit
+        )
+    }
+
     /// Get a Level2 snapshot of orderbook.
     ///
     /// Equivalent to `/fapi/v1/depth` with `limit=1000`
@@ -60,9 +90,20 @@ impl BinanceLinearRestClient {
     /// - <https://fapi.binance.com/fapi/v1/depth?symbol=BTCUSDT&limit=1000>
     /// - <https://fapi.binance.com/fapi/v1/depth?symbol=BTCUSDT_211231&limit=1000>
     pub fn fetch_l2_snapshot(symbol: &str) -> Result<String> {
+        Self::fetch_l2_snapshot_with_depth(symbol, None)
+    }
+
+    /// Same as `fetch_l2_snapshot()`, with the number of levels set by `depth`.
+    ///
+    /// `depth` is rounded up to the nearest valid limit, at most 1000.
+    pub fn fetch_l2_snapshot_with_depth(symbol: &str, depth: Option<usize>) -> Result<String> {
         check_symbol(symbol);
         let symbol = Some(symbol);
-        let limit = Some(1000);
+        let limit = Some(round_up_depth(
+            depth,
+            &[5, 10, 20, 50, 100, 500, 1000],
+            1000,
+        )?);
         gen_api_binance!("/fapi/v1/depth", symbol, limit)
     }
 
@@ -1,8 +1,9 @@
-use super::super::utils::http_get;
+use super::super::utils::{http_get, round_up_depth};
 use super::utils::*;
 use crate::error::Result;
 use std::collections::BTreeMap;
 
+const EXCHANGE_NAME: &str = "binance";
 const BASE_URL: &str = "https://vapi.binance.com";
 
 /// Binance Option market.
@@ -37,9 +38,16 @@ impl BinanceOptionRestClient {
     ///
     /// For example: <https://vapi.binance.com/vapi/v1/depth?symbol=BTC-211001-30000-P&limit=1000>
     pub fn fetch_l2_snapshot(symbol: &str) -> Result<String> {
+        Self::fetch_l2_snapshot_with_depth(symbol, None)
+    }
+
+    /// Same as `fetch_l2_snapshot()`, with the number of levels set by `depth`.
+    ///
+    /// `depth` is rounded up to the nearest valid limit, at most 1000.
+    pub fn fetch_l2_snapshot_with_depth(symbol: &str, depth: Option<usize>) -> Result<String> {
         check_symbol(symbol);
         let symbol = Some(symbol);
-        let limit = Some(1000);
+        let limit = Some(round_up_depth(depth, &[10, 20, 50, 100, 500, 1000], 1000)?);
         gen_api_binance!("/vapi/v1/depth", symbol, limit)
     }
 }
@@ -6,23 +6,29 @@ pub(crate) mod binance_linear;
 pub(crate) mod binance_option;
 pub(crate) mod binance_spot;
 
-use crate::error::Result;
+use crate::error::{Error, Result};
 use crypto_market_type::MarketType;
 
-pub(crate) fn fetch_l2_snapshot(market_type: MarketType, symbol: &str) -> Result<String> {
+pub(crate) fn fetch_l2_snapshot(
+    market_type: MarketType,
+    symbol: &str,
+    depth: Option<usize>,
+) -> Result<String> {
     let func = match market_type {
-        MarketType::Spot => binance_spot::BinanceSpotRestClient::fetch_l2_snapshot,
+        MarketType::Spot => binance_spot::BinanceSpotRestClient::fetch_l2_snapshot_with_depth,
         MarketType::InverseFuture | MarketType::InverseSwap => {
-            binance_inverse::BinanceInverseRestClient::fetch_l2_snapshot
+            binance_inverse::BinanceInverseRestClient::fetch_l2_snapshot_with_depth
         }
         MarketType::LinearFuture | MarketType::LinearSwap => {
-            binance_linear::BinanceLinearRestClient::fetch_l2_snapshot
+            binance_linear::BinanceLinearRestClient::fetch_l2_snapshot_with_depth
+        }
+        MarketType::EuropeanOption => {
+            binance_option::BinanceOptionRestClient::fetch_l2_snapshot_with_depth
         }
-        MarketType::EuropeanOption => binance_option::BinanceOptionRestClient::fetch_l2_snapshot,
         _ => panic!("Binance unknown market_type: {}", market_type),
     };
 
-    func(symbol)
+    func(symbol, depth)
 }
 
 pub(crate) fn fetch_open_interest(market_type: MarketType, symbol: &str) -> Result<String> {
@@ -37,3 +43,50 @@ pub(crate) fn fetch_open_interest(market_type: MarketType, symbol: &str) -> Resu
     };
     func(symbol)
 }
+
+// Binance interval names, `interval` is in seconds
+fn to_interval_str(interval: usize) -> Option<&'static str> {
+    let interval_str = match interval {
+        60 => "1m",
+        180 => "3m",
+        300 => "5m",
+        900 => "15m",
+        1800 => "30m",
+        3600 => "1h",
+        7200 => "2h",
+        14400 => "4h",
+        21600 => "6h",
+        28800 => "8h",
+        43200 => "12h",
+        86400 => "1d",
+        259200 => "3d",
+        604800 => "1w",
+        2592000 => "1M",
+        _ => return None,
+    };
+    Some(interval_str)
+}
+
+pub(crate) fn fetch_candlestick(
+    market_type: MarketType,
+    symbol: &str,
+    interval: usize,
+    start_time: Option<u64>,
+    end_time: Option<u64>,
+) -> Result<String> {
+    let interval_str = to_interval_str(interval)
+        .ok_or_else(|| Error(format!("Binance does NOT have {}s candlesticks", interval)))?;
+    let func = match market_type {
+        MarketType::Spot => binance_spot::BinanceSpotRestClient::fetch_klines,
+        MarketType::LinearFuture | MarketType::LinearSwap => {
+            binance_linear::BinanceLinearRestClient::fetch_klines
+        }
+        _ => {
+            return Err(Error(format!(
+                "Binance {} does NOT have candlestick RESTful API",
+                market_type
+            )))
+        }
+    };
+    func(symbol, interval_str, start_time, end_time)
+}
@@ -47,7 +47,7 @@ macro_rules! gen_api_binance {
                     params.insert(stringify!($param_name).to_string(), param_name.to_string());
                 }
             )*
-            let ret = http_get(format!("{}{}",BASE_URL, $path).as_str(), &params);
+            let ret = http_get(EXCHANGE_NAME, format!("{}{}",BASE_URL, $path).as_str(), &params);
             match ret {
                 Ok(resp) => check_code_in_body(resp),
                 Err(_) => ret,
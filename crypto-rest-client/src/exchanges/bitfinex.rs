@@ -1,7 +1,8 @@
-use super::utils::http_get;
+use super::utils::{http_get, round_up_depth};
 use crate::error::Result;
 use std::collections::BTreeMap;
 
+const EXCHANGE_NAME: &str = "bitfinex";
 const BASE_URL: &str = "https://api-pub.bitfinex.com";
 
 /// The REST client for Bitfinex, including all markets.
@@ -48,7 +49,14 @@ impl BitfinexRestClient {
     ///
     /// Ratelimit: 90 req/min
     pub fn fetch_l2_snapshot(symbol: &str) -> Result<String> {
-        let len = Some(100);
+        Self::fetch_l2_snapshot_with_depth(symbol, None)
+    }
+
+    /// Same as `fetch_l2_snapshot()`, with the number of levels set by `depth`.
+    ///
+    /// `depth` is rounded up to the nearest valid length, i.e., 1, 25 or 100.
+    pub fn fetch_l2_snapshot_with_depth(symbol: &str, depth: Option<usize>) -> Result<String> {
+        let len = Some(round_up_depth(depth, &[1, 25, 100], 100)?);
         gen_api!(format!("/v2/book/{}/P0", symbol), len)
     }
 
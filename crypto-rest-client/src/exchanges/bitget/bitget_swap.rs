@@ -1,7 +1,8 @@
-use super::super::utils::http_get;
+use super::super::utils::{clamp_depth, http_get};
 use crate::error::Result;
 use std::collections::BTreeMap;
 
+const EXCHANGE_NAME: &str = "bitget";
 const BASE_URL: &str = "https://capi.bitget.com/api/swap";
 
 /// The RESTful client for Bitget swap markets.
@@ -27,7 +28,18 @@ impl BitgetSwapRestClient {
     ///
     /// Rate Limit：20 requests per 2 seconds
     pub fn fetch_l2_snapshot(symbol: &str) -> Result<String> {
-        gen_api!(format!("/v3/market/depth?symbol={}&limit=1000", symbol))
+        Self::fetch_l2_snapshot_with_depth(symbol, None)
+    }
+
+    /// Same as `fetch_l2_snapshot()`, with the number of levels set by `depth`.
+    ///
+    /// Top `depth` bids and asks are returned, at most 1000.
+    pub fn fetch_l2_snapshot_with_depth(symbol: &str, depth: Option<usize>) -> Result<String> {
+        gen_api!(format!(
+            "/v3/market/depth?symbol={}&limit={}",
+            symbol,
+            clamp_depth(depth, 1000)?
+        ))
     }
 
     /// Get open interest.
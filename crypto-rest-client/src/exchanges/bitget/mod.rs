@@ -7,16 +7,18 @@ pub use bitget_swap::BitgetSwapRestClient;
 use crate::error::Result;
 use crypto_market_type::MarketType;
 
-pub(crate) fn fetch_l2_snapshot(market_type: MarketType, symbol: &str) -> Result<String> {
-    let func = match market_type {
-        MarketType::Spot => bitget_spot::BitgetSpotRestClient::fetch_l2_snapshot,
+pub(crate) fn fetch_l2_snapshot(
+    market_type: MarketType,
+    symbol: &str,
+    depth: Option<usize>,
+) -> Result<String> {
+    match market_type {
+        MarketType::Spot => bitget_spot::BitgetSpotRestClient::fetch_l2_snapshot(symbol),
         MarketType::InverseSwap | MarketType::LinearSwap => {
-            bitget_swap::BitgetSwapRestClient::fetch_l2_snapshot
+            bitget_swap::BitgetSwapRestClient::fetch_l2_snapshot_with_depth(symbol, depth)
         }
         _ => panic!("Bitget unknown market_type: {}", market_type),
-    };
-
-    func(symbol)
+    }
 }
 
 pub(crate) fn fetch_open_interest(market_type: MarketType, symbol: &str) -> Result<String> {
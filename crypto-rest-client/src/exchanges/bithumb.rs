@@ -2,6 +2,7 @@ use super::utils::http_get;
 use crate::error::Result;
 use std::collections::BTreeMap;
 
+const EXCHANGE_NAME: &str = "bithumb";
 const BASE_URL: &str = "https://global-openapi.bithumb.pro/openapi/v1";
 
 /// The REST client for Bithumb.
@@ -1,7 +1,8 @@
-use super::utils::http_get;
-use crate::error::Result;
+use super::utils::{http_get, http_get_raw, parse_http_date};
+use crate::error::{Error, Result};
 use std::collections::BTreeMap;
 
+const EXCHANGE_NAME: &str = "bitmex";
 const BASE_URL: &str = "https://www.bitmex.com/api/v1";
 
 /// The REST client for BitMEX.
@@ -44,8 +45,47 @@ impl BitmexRestClient {
     ///
     /// For example: <https://www.bitmex.com/api/v1/orderBook/L2?symbol=XBTUSD&depth=0>
     pub fn fetch_l2_snapshot(symbol: &str) -> Result<String> {
+        Self::fetch_l2_snapshot_with_depth(symbol, None)
+    }
+
+    /// Same as `fetch_l2_snapshot()`, with the number of levels set by `depth`.
+    ///
+    /// `depth` None means the full orderbook.
+    pub fn fetch_l2_snapshot_with_depth(symbol: &str, depth: Option<usize>) -> Result<String> {
         let symbol = Some(symbol);
-        let depth = Some(0);
+        let depth = Some(depth.unwrap_or(0));
         gen_api!("/orderBook/L2", symbol, depth)
     }
+
+    /// Get open interest.
+    ///
+    /// Equivalent to `/instrument` with `columns=openInterest,openValue`,
+    /// `symbol` None means all active instruments.
+    ///
+    /// For example:
+    ///
+    /// - <https://www.bitmex.com/api/v1/instrument?symbol=XBTUSD&columns=openInterest,openValue>
+    /// - <https://www.bitmex.com/api/v1/instrument/active?columns=openInterest,openValue>
+    pub fn fetch_open_interest(symbol: Option<&str>) -> Result<String> {
+        let columns = Some("openInterest,openValue");
+        if symbol.is_some() {
+            gen_api!("/instrument", symbol, columns)
+        } else {
+            gen_api!("/instrument/active", columns)
+        }
+    }
+
+    /// Get the server time in milliseconds since the Unix epoch.
+    ///
+    /// BitMEX has no time endpoint, the time is read from the `Date` header
+    /// of <https://www.bitmex.com/api/v1>, which has a resolution of one second.
+    pub fn fetch_server_time() -> Result<i64> {
+        let response = http_get_raw(EXCHANGE_NAME, BASE_URL, &BTreeMap::new())?;
+        response
+            .headers()
+            .get(reqwest::header::DATE)
+            .and_then(|date| date.to_str().ok())
+            .and_then(parse_http_date)
+            .ok_or_else(|| Error("No valid Date header in the response".to_string()))
+    }
 }
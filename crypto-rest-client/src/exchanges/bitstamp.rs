@@ -2,6 +2,7 @@ use super::utils::http_get;
 use crate::error::Result;
 use std::collections::BTreeMap;
 
+const EXCHANGE_NAME: &str = "bitstamp";
 const BASE_URL: &str = "https://www.bitstamp.net/api";
 
 /// The REST client for Bitstamp.
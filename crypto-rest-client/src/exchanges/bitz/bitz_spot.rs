@@ -2,6 +2,7 @@ use super::super::utils::http_get;
 use crate::error::Result;
 use std::collections::BTreeMap;
 
+const EXCHANGE_NAME: &str = "bitz";
 const BASE_URL: &str = "https://apiv2.bitz.com";
 
 /// The RESTful client for BitZ spot market.
@@ -1,10 +1,11 @@
-use super::super::utils::http_get;
+use super::super::utils::{clamp_depth, http_get};
 use crate::error::{Error, Result};
 use std::collections::{BTreeMap, HashMap};
 
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
+const EXCHANGE_NAME: &str = "bitz";
 const BASE_URL: &str = "https://apiv2.bitz.com";
 
 /// The RESTful client for BitZ swap markets.
@@ -32,6 +33,13 @@ impl BitzSwapRestClient {
     ///
     /// For example: <https://apiv2.bitz.com/V2/Market/getContractOrderBook?contractId=101&depth=100>,
     pub fn fetch_l2_snapshot(symbol: &str) -> Result<String> {
+        Self::fetch_l2_snapshot_with_depth(symbol, None)
+    }
+
+    /// Same as `fetch_l2_snapshot()`, with the number of levels set by `depth`.
+    ///
+    /// Top `depth` bids and asks are returned, at most 100.
+    pub fn fetch_l2_snapshot_with_depth(symbol: &str, depth: Option<usize>) -> Result<String> {
         let symbol_id_map = get_symbol_id_map()?;
         if !symbol_id_map.contains_key(symbol) {
             return Err(Error(format!(
@@ -41,8 +49,9 @@ impl BitzSwapRestClient {
         }
         let contract_id = symbol_id_map.get(symbol).unwrap();
         gen_api!(format!(
-            "/V2/Market/getContractOrderBook?contractId={}&depth=100",
-            contract_id
+            "/V2/Market/getContractOrderBook?contractId={}&depth={}",
+            contract_id,
+            clamp_depth(depth, 100)?
         ))
     }
 
@@ -91,7 +100,11 @@ struct Response {
 
 fn get_symbol_id_map() -> Result<HashMap<String, String>> {
     let params = BTreeMap::new();
-    let txt = http_get("https://apiv2.bitz.com/Market/getContractCoin", &params)?;
+    let txt = http_get(
+        EXCHANGE_NAME,
+        "https://apiv2.bitz.com/Market/getContractCoin",
+        &params,
+    )?;
     let resp = serde_json::from_str::<Response>(&txt)?;
     if resp.status != 200 {
         return Err(Error(txt));
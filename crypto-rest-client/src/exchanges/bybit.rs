@@ -1,7 +1,8 @@
-use super::utils::http_get;
+use super::utils::{http_get, json_timestamp};
 use crate::error::Result;
 use std::collections::BTreeMap;
 
+const EXCHANGE_NAME: &str = "bybit";
 const BASE_URL: &str = "https://api.bybit.com/v2";
 
 /// The RESTful client for Bybit.
@@ -54,4 +55,12 @@ impl BybitRestClient {
             symbol
         ))
     }
+
+    /// Get the server time in milliseconds since the Unix epoch.
+    ///
+    /// For example: <https://api.bybit.com/v2/public/time>
+    pub fn fetch_server_time() -> Result<i64> {
+        let text = gen_api!("/public/time")?;
+        json_timestamp(&text, "/time_now")
+    }
 }
@@ -1,7 +1,8 @@
-use super::utils::http_get;
+use super::utils::{http_get, json_timestamp};
 use crate::error::Result;
 use std::collections::BTreeMap;
 
+const EXCHANGE_NAME: &str = "coinbase_pro";
 const BASE_URL: &str = "https://api.pro.coinbase.com";
 
 /// The REST client for CoinbasePro.
@@ -51,4 +52,12 @@ impl CoinbaseProRestClient {
     pub fn fetch_l3_snapshot(symbol: &str) -> Result<String> {
         gen_api!(format!("/products/{}/book?level=3", symbol))
     }
+
+    /// Get the server time in milliseconds since the Unix epoch.
+    ///
+    /// For example: <https://api.pro.coinbase.com/time>
+    pub fn fetch_server_time() -> Result<i64> {
+        let text = gen_api!("/time")?;
+        json_timestamp(&text, "/epoch")
+    }
 }
@@ -1,7 +1,8 @@
-use super::utils::http_get;
+use super::utils::{clamp_depth, http_get, json_timestamp};
 use crate::error::Result;
 use std::collections::BTreeMap;
 
+const EXCHANGE_NAME: &str = "deribit";
 const BASE_URL: &str = "https://www.deribit.com/api/v2";
 
 /// The RESTful client for Deribit.
@@ -45,8 +46,16 @@ impl DeribitRestClient {
     ///
     /// For example: <https://www.deribit.com/api/v2/public/get_order_book?depth=2000&instrument_name=BTC-PERPETUAL>,
     pub fn fetch_l2_snapshot(symbol: &str) -> Result<String> {
+        Self::fetch_l2_snapshot_with_depth(symbol, None)
+    }
+
+    /// Same as `fetch_l2_snapshot()`, with the number of levels set by `depth`.
+    ///
+    /// Top `depth` bids and asks are returned, at most 2000.
+    pub fn fetch_l2_snapshot_with_depth(symbol: &str, depth: Option<usize>) -> Result<String> {
         gen_api!(format!(
-            "/public/get_order_book?depth=2000&instrument_name={}",
+            "/public/get_order_book?depth={}&instrument_name={}",
+            clamp_depth(depth, 2000)?,
             symbol,
         ))
     }
@@ -68,4 +77,12 @@ impl DeribitRestClient {
             Ok(format!("{}\n{}", btc, eth))
         }
     }
+
+    /// Get the server time in milliseconds since the Unix epoch.
+    ///
+    /// For example: <https://www.deribit.com/api/v2/public/get_time>
+    pub fn fetch_server_time() -> Result<i64> {
+        let text = gen_api!("/public/get_time")?;
+        json_timestamp(&text, "/result")
+    }
 }
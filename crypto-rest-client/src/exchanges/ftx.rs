@@ -1,7 +1,8 @@
-use super::utils::http_get;
+use super::utils::{clamp_depth, http_get};
 use crate::error::Result;
 use std::collections::BTreeMap;
 
+const EXCHANGE_NAME: &str = "ftx";
 const BASE_URL: &str = "https://ftx.com/api";
 
 /// The RESTful client for FTX.
@@ -33,7 +34,18 @@ impl FtxRestClient {
     /// For example: <https://ftx.com/api/markets/BTC-PERP/orderbook?depth=100>,
     // <https://ftx.com/api/markets/BTC/USD/orderbook?depth=100>
     pub fn fetch_l2_snapshot(symbol: &str) -> Result<String> {
-        gen_api!(format!("/markets/{}/orderbook?depth=100", symbol))
+        Self::fetch_l2_snapshot_with_depth(symbol, None)
+    }
+
+    /// Same as `fetch_l2_snapshot()`, with the number of levels set by `depth`.
+    ///
+    /// Top `depth` bids and asks are returned, at most 100.
+    pub fn fetch_l2_snapshot_with_depth(symbol: &str, depth: Option<usize>) -> Result<String> {
+        gen_api!(format!(
+            "/markets/{}/orderbook?depth={}",
+            symbol,
+            clamp_depth(depth, 100)?
+        ))
     }
 
     /// Get open interest.
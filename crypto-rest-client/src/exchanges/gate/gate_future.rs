@@ -1,7 +1,8 @@
-use super::super::utils::http_get;
+use super::super::utils::{clamp_depth, http_get};
 use crate::error::Result;
 use std::collections::BTreeMap;
 
+const EXCHANGE_NAME: &str = "gate";
 const BASE_URL: &str = "https://api.gateio.ws/api/v4";
 
 /// The RESTful client for Gate Future markets.
@@ -29,6 +30,13 @@ impl GateFutureRestClient {
     ///
     /// - <https://api.gateio.ws/api/v4/delivery/usdt/order_book?contract=BTC_USDT_20211015&limit=50>
     pub fn fetch_l2_snapshot(symbol: &str) -> Result<String> {
+        Self::fetch_l2_snapshot_with_depth(symbol, None)
+    }
+
+    /// Same as `fetch_l2_snapshot()`, with the number of levels set by `depth`.
+    ///
+    /// Top `depth` asks and bids are returned, at most 50.
+    pub fn fetch_l2_snapshot_with_depth(symbol: &str, depth: Option<usize>) -> Result<String> {
         let without_date = &symbol[..(symbol.len() - 8)];
         let settle = if without_date.ends_with("_USD_") {
             "btc"
@@ -38,8 +46,10 @@ impl GateFutureRestClient {
             panic!("Unknown symbol {}", symbol);
         };
         gen_api!(format!(
-            "/delivery/{}/order_book?contract={}&limit=50",
-            settle, symbol
+            "/delivery/{}/order_book?contract={}&limit={}",
+            settle,
+            symbol,
+            clamp_depth(depth, 50)?
         ))
     }
 }
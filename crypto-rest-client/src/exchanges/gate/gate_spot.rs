@@ -1,7 +1,8 @@
-use super::super::utils::http_get;
+use super::super::utils::{clamp_depth, http_get};
 use crate::error::Result;
 use std::collections::BTreeMap;
 
+const EXCHANGE_NAME: &str = "gate";
 const BASE_URL: &str = "https://api.gateio.ws/api/v4";
 
 /// The RESTful client for Gate spot market.
@@ -29,9 +30,17 @@ impl GateSpotRestClient {
     ///
     /// For example: <https://api.gateio.ws/api/v4/spot/order_book?currency_pair=BTC_USDT&limit=1000>,
     pub fn fetch_l2_snapshot(symbol: &str) -> Result<String> {
+        Self::fetch_l2_snapshot_with_depth(symbol, None)
+    }
+
+    /// Same as `fetch_l2_snapshot()`, with the number of levels set by `depth`.
+    ///
+    /// Top `depth` asks and bids are returned, at most 1000.
+    pub fn fetch_l2_snapshot_with_depth(symbol: &str, depth: Option<usize>) -> Result<String> {
         gen_api!(format!(
-            "/spot/order_book?currency_pair={}&limit=1000",
-            symbol
+            "/spot/order_book?currency_pair={}&limit={}",
+            symbol,
+            clamp_depth(depth, 1000)?
         ))
     }
 }
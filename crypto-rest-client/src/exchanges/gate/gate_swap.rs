@@ -1,7 +1,8 @@
-use super::super::utils::http_get;
+use super::super::utils::{clamp_depth, http_get};
 use crate::error::Result;
 use std::collections::BTreeMap;
 
+const EXCHANGE_NAME: &str = "gate";
 const BASE_URL: &str = "https://api.gateio.ws/api/v4";
 
 /// The RESTful client for Gate Swap markets.
@@ -32,6 +33,13 @@ impl GateSwapRestClient {
     /// - <https://api.gateio.ws/api/v4/futures/btc/order_book?contract=BTC_USD&limit=200>
     /// - <https://api.gateio.ws/api/v4/futures/usdt/order_book?contract=BTC_USDT&limit=200>
     pub fn fetch_l2_snapshot(symbol: &str) -> Result<String> {
+        Self::fetch_l2_snapshot_with_depth(symbol, None)
+    }
+
+    /// Same as `fetch_l2_snapshot()`, with the number of levels set by `depth`.
+    ///
+    /// Top `depth` asks and bids are returned, at most 200.
+    pub fn fetch_l2_snapshot_with_depth(symbol: &str, depth: Option<usize>) -> Result<String> {
         let settle = if symbol.ends_with("_USD") {
             "btc"
         } else if symbol.ends_with("_USDT") {
@@ -40,8 +48,10 @@ impl GateSwapRestClient {
             panic!("Unknown symbol {}", symbol);
         };
         gen_api!(format!(
-            "/futures/{}/order_book?contract={}&limit=200",
-            settle, symbol
+            "/futures/{}/order_book?contract={}&limit={}",
+            settle,
+            symbol,
+            clamp_depth(depth, 200)?
         ))
     }
 
@@ -9,17 +9,21 @@ pub use gate_swap::GateSwapRestClient;
 use crate::error::Result;
 use crypto_market_type::MarketType;
 
-pub(crate) fn fetch_l2_snapshot(market_type: MarketType, symbol: &str) -> Result<String> {
+pub(crate) fn fetch_l2_snapshot(
+    market_type: MarketType,
+    symbol: &str,
+    depth: Option<usize>,
+) -> Result<String> {
     let func = match market_type {
-        MarketType::Spot => gate_spot::GateSpotRestClient::fetch_l2_snapshot,
+        MarketType::Spot => gate_spot::GateSpotRestClient::fetch_l2_snapshot_with_depth,
         MarketType::InverseSwap | MarketType::LinearSwap => {
-            gate_swap::GateSwapRestClient::fetch_l2_snapshot
+            gate_swap::GateSwapRestClient::fetch_l2_snapshot_with_depth
         }
-        MarketType::LinearFuture => gate_future::GateFutureRestClient::fetch_l2_snapshot,
+        MarketType::LinearFuture => gate_future::GateFutureRestClient::fetch_l2_snapshot_with_depth,
         _ => panic!("Gate unknown market_type: {}", market_type),
     };
 
-    func(symbol)
+    func(symbol, depth)
 }
 
 pub(crate) fn fetch_open_interest(market_type: MarketType, symbol: &str) -> Result<String> {
@@ -2,6 +2,7 @@ use super::super::utils::http_get;
 use crate::error::Result;
 use std::collections::BTreeMap;
 
+const EXCHANGE_NAME: &str = "huobi";
 const BASE_URL: &str = "https://api.hbdm.com";
 
 /// Huobi Linear Swap market.
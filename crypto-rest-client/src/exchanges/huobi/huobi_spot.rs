@@ -1,7 +1,8 @@
-use super::super::utils::http_get;
+use super::super::utils::{http_get, json_timestamp};
 use crate::error::Result;
 use std::collections::BTreeMap;
 
+const EXCHANGE_NAME: &str = "huobi";
 const BASE_URL: &str = "https://api.huobi.pro";
 
 /// Huobi Spot market.
@@ -26,4 +27,12 @@ impl HuobiSpotRestClient {
     pub fn fetch_l2_snapshot(symbol: &str) -> Result<String> {
         gen_api!(format!("/market/depth?symbol={}&type=step0", symbol))
     }
+
+    /// Get the server time in milliseconds since the Unix epoch.
+    ///
+    /// For example: <https://api.huobi.pro/v1/common/timestamp>
+    pub fn fetch_server_time() -> Result<i64> {
+        let text = gen_api!("/v1/common/timestamp")?;
+        json_timestamp(&text, "/data")
+    }
 }
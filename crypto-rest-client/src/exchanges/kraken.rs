@@ -1,7 +1,8 @@
-use super::utils::http_get;
+use super::utils::{clamp_depth, http_get, json_timestamp};
 use crate::error::Result;
 use std::collections::BTreeMap;
 
+const EXCHANGE_NAME: &str = "kraken";
 const BASE_URL: &str = "https://api.kraken.com";
 
 /// The WebSocket client for Kraken.
@@ -41,6 +42,25 @@ impl KrakenRestClient {
     ///
     /// For example: <https://api.kraken.com/0/public/Depth?pair=XXBTZUSD&count=500>
     pub fn fetch_l2_snapshot(symbol: &str) -> Result<String> {
-        gen_api!(format!("/0/public/Depth?pair={}&count=500", symbol))
+        Self::fetch_l2_snapshot_with_depth(symbol, None)
+    }
+
+    /// Same as `fetch_l2_snapshot()`, with the number of levels set by `depth`.
+    ///
+    /// Top `depth` bids and asks are returned, at most 500.
+    pub fn fetch_l2_snapshot_with_depth(symbol: &str, depth: Option<usize>) -> Result<String> {
+        gen_api!(format!(
+            "/0/public/Depth?pair={}&count={}",
+            symbol,
+            clamp_depth(depth, 500)?
+        ))
+    }
+
+    /// Get the server time in milliseconds since the Unix epoch.
+    ///
+    /// For example: <https://api.kraken.com/0/public/Time>
+    pub fn fetch_server_time() -> Result<i64> {
+        let text = gen_api!("/0/public/Time")?;
+        json_timestamp(&text, "/result/unixtime")
     }
 }
@@ -1,7 +1,8 @@
-use super::super::utils::http_get;
+use super::super::utils::{http_get, json_timestamp};
 use crate::error::Result;
 use std::collections::BTreeMap;
 
+const EXCHANGE_NAME: &str = "kucoin";
 const BASE_URL: &str = "https://api.kucoin.com";
 
 /// The RESTful client for KuCoin spot market.
@@ -43,4 +44,12 @@ impl KuCoinSpotRestClient {
     pub fn fetch_l3_snapshot(symbol: &str) -> Result<String> {
         gen_api!(format!("/api/v2/market/orderbook/level3?symbol={}", symbol))
     }
+
+    /// Get the server time in milliseconds since the Unix epoch.
+    ///
+    /// For example: <https://api.kucoin.com/api/v1/timestamp>
+    pub fn fetch_server_time() -> Result<i64> {
+        let text = gen_api!("/api/v1/timestamp")?;
+        json_timestamp(&text, "/data")
+    }
 }
@@ -2,6 +2,7 @@ use super::super::utils::http_get;
 use crate::error::Result;
 use std::collections::BTreeMap;
 
+const EXCHANGE_NAME: &str = "kucoin";
 const BASE_URL: &str = "https://api-futures.kucoin.com";
 
 /// The RESTful client for KuCoin Future and Swap markets.
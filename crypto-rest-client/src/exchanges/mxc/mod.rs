@@ -4,14 +4,18 @@ pub(crate) mod mxc_swap;
 use crate::error::Result;
 use crypto_market_type::MarketType;
 
-pub(crate) fn fetch_l2_snapshot(market_type: MarketType, symbol: &str) -> Result<String> {
+pub(crate) fn fetch_l2_snapshot(
+    market_type: MarketType,
+    symbol: &str,
+    depth: Option<usize>,
+) -> Result<String> {
     let func = match market_type {
-        MarketType::Spot => mxc_spot::MxcSpotRestClient::fetch_l2_snapshot,
+        MarketType::Spot => mxc_spot::MxcSpotRestClient::fetch_l2_snapshot_with_depth,
         MarketType::InverseSwap | MarketType::LinearSwap => {
-            mxc_swap::MxcSwapRestClient::fetch_l2_snapshot
+            mxc_swap::MxcSwapRestClient::fetch_l2_snapshot_with_depth
         }
         _ => panic!("MXC unknown market_type: {}", market_type),
     };
 
-    func(symbol)
+    func(symbol, depth)
 }
@@ -1,7 +1,8 @@
-use super::super::utils::http_get;
+use super::super::utils::{clamp_depth, http_get};
 use crate::error::Result;
 use std::collections::BTreeMap;
 
+const EXCHANGE_NAME: &str = "mxc";
 const BASE_URL: &str = "https://www.mexc.com";
 
 /// MXC Spot market.
@@ -42,9 +43,17 @@ impl MxcSpotRestClient {
     ///
     /// For example: <https://www.mxc.co/open/api/v2/market/depth?symbol=BTC_USDT&depth=2000>
     pub fn fetch_l2_snapshot(symbol: &str) -> Result<String> {
+        Self::fetch_l2_snapshot_with_depth(symbol, None)
+    }
+
+    /// Same as `fetch_l2_snapshot()`, with the number of levels set by `depth`.
+    ///
+    /// Top `depth` bids and asks will be returned, at most 2000.
+    pub fn fetch_l2_snapshot_with_depth(symbol: &str, depth: Option<usize>) -> Result<String> {
         gen_api!(format!(
-            "/open/api/v2/market/depth?symbol={}&depth=2000",
-            symbol
+            "/open/api/v2/market/depth?symbol={}&depth={}",
+            symbol,
+            clamp_depth(depth, 2000)?
         ))
     }
 }
@@ -1,7 +1,8 @@
-use super::super::utils::http_get;
+use super::super::utils::{clamp_depth, http_get};
 use crate::error::Result;
 use std::collections::BTreeMap;
 
+const EXCHANGE_NAME: &str = "mxc";
 const BASE_URL: &str = "https://contract.mexc.com";
 
 /// MXC Swap market.
@@ -36,6 +37,17 @@ impl MxcSwapRestClient {
     ///
     /// Rate limit: 20 times /2 seconds
     pub fn fetch_l2_snapshot(symbol: &str) -> Result<String> {
-        gen_api!(format!("/api/v1/contract/depth/{}?limit=2000", symbol))
+        Self::fetch_l2_snapshot_with_depth(symbol, None)
+    }
+
+    /// Same as `fetch_l2_snapshot()`, with the number of levels set by `depth`.
+    ///
+    /// Top `depth` bids and asks will be returned, at most 2000.
+    pub fn fetch_l2_snapshot_with_depth(symbol: &str, depth: Option<usize>) -> Result<String> {
+        gen_api!(format!(
+            "/api/v1/contract/depth/{}?limit={}",
+            symbol,
+            clamp_depth(depth, 2000)?
+        ))
     }
 }
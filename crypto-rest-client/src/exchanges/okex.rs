@@ -1,7 +1,9 @@
-use super::utils::http_get;
+use super::utils::{clamp_depth, http_get, json_timestamp};
 use crate::error::Result;
+use crypto_market_type::MarketType;
 use std::collections::BTreeMap;
 
+const EXCHANGE_NAME: &str = "okex";
 const BASE_URL: &str = "https://www.okex.com/api";
 
 /// The REST client for OKEx.
@@ -50,21 +52,30 @@ impl OkexRestClient {
     ///
     /// Rate limit: 20 requests per 2 seconds
     pub fn fetch_l2_snapshot(symbol: &str) -> Result<String> {
+        Self::fetch_l2_snapshot_with_depth(symbol, None)
+    }
+
+    /// Same as `fetch_l2_snapshot()`, with the number of levels set by `depth`.
+    ///
+    /// Top `depth` bids and asks are returned, at most 200.
+    pub fn fetch_l2_snapshot_with_depth(symbol: &str, depth: Option<usize>) -> Result<String> {
         gen_api!(format!(
-            "/{}/v3/instruments/{}/{}?size=200",
+            "/{}/v3/instruments/{}/{}?size={}",
             pair_to_market_type(symbol),
             symbol,
             if symbol.ends_with("-SWAP") {
                 "depth"
             } else {
                 "book"
-            }
+            },
+            clamp_depth(depth, 200)?
         ))
     }
 
     /// Get option underlying.
     pub fn fetch_option_underlying() -> Result<Vec<String>> {
         let txt = http_get(
+            EXCHANGE_NAME,
             "https://www.okex.com/api/option/v3/underlying",
             &BTreeMap::new(),
         )?;
@@ -83,23 +94,23 @@ impl OkexRestClient {
             symbol
         ))
     }
+
+    /// Get the server time in milliseconds since the Unix epoch.
+    ///
+    /// For example: <https://www.okex.com/api/general/v3/time>
+    pub fn fetch_server_time() -> Result<i64> {
+        let text = gen_api!("/general/v3/time")?;
+        json_timestamp(&text, "/epoch")
+    }
 }
 
+// The market type in the path of the v3 API
 fn pair_to_market_type(pair: &str) -> &'static str {
-    if pair.ends_with("-SWAP") {
-        "swap"
-    } else {
-        let c = pair.matches('-').count();
-        if c == 1 {
-            "spot"
-        } else if c == 2 {
-            let date = &pair[(pair.len() - 6)..];
-            debug_assert!(date.parse::<i64>().is_ok());
-            "futures"
-        } else {
-            debug_assert!(pair.ends_with("-C") || pair.ends_with("-P"));
-            "option"
-        }
+    match crypto_pair::get_market_type(pair, "okex") {
+        MarketType::InverseFuture | MarketType::LinearFuture => "futures",
+        MarketType::InverseSwap | MarketType::LinearSwap => "swap",
+        MarketType::EuropeanOption => "option",
+        _ => "spot",
     }
 }
 
@@ -4,7 +4,11 @@ use crate::error::{Error, Result};
 use std::collections::BTreeMap;
 
 // Returns the raw response directly.
-pub(super) fn http_get_raw(url: &str, params: &BTreeMap<String, String>) -> Result<Response> {
+pub(super) fn http_get_raw(
+    exchange: &str,
+    url: &str,
+    params: &BTreeMap<String, String>,
+) -> Result<Response> {
     let mut full_url = url.to_string();
     let mut first = true;
     for (k, v) in params.iter() {
@@ -17,24 +21,27 @@ pub(super) fn http_get_raw(url: &str, params: &BTreeMap<String, String>) -> Resu
     }
     // println!("{}", full_url);
 
-    let mut headers = header::HeaderMap::new();
-    headers.insert(
-        header::CONTENT_TYPE,
-        header::HeaderValue::from_static("application/json"),
-    );
+    // User-Agent and custom headers set by set_user_agent() and set_header()
+    let mut headers = crypto_pair::headers(exchange);
+    headers
+        .entry(header::CONTENT_TYPE)
+        .or_insert(header::HeaderValue::from_static("application/json"));
 
     let client = reqwest::blocking::Client::builder()
-         .default_headers(headers)
-         .user_agent("Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/87.0.4280.88 Safari/537.36")
-         .gzip(true)
-         .build()?;
+        .default_headers(headers)
+        .gzip(true)
+        .build()?;
     let response = client.get(full_url.as_str()).send()?;
     Ok(response)
 }
 
 // Returns the text in response.
-pub(super) fn http_get(url: &str, params: &BTreeMap<String, String>) -> Result<String> {
-    match http_get_raw(url, params) {
+pub(super) fn http_get(
+    exchange: &str,
+    url: &str,
+    params: &BTreeMap<String, String>,
+) -> Result<String> {
+    match http_get_raw(exchange, url, params) {
         Ok(response) => match response.error_for_status() {
             Ok(resp) => Ok(resp.text()?),
             Err(error) => Err(Error::from(error)),
@@ -54,24 +61,171 @@ macro_rules! gen_api {
                 }
             )*
             let url = if $path.starts_with("http") { $path.to_string() } else { format!("{}{}",BASE_URL, $path) };
-            http_get(&url, &params)
+            http_get(EXCHANGE_NAME, &url, &params)
         }
     }
 }
 
+// Reads the timestamp at `pointer` of a JSON text in milliseconds. Exchanges
+// return milliseconds or seconds with fractions, as numbers or strings, values
+// below 10^12 are taken as seconds since they would be before 2001 in milliseconds.
+pub(super) fn json_timestamp(text: &str, pointer: &str) -> Result<i64> {
+    let obj = serde_json::from_str::<serde_json::Value>(text)?;
+    let timestamp = obj.pointer(pointer).and_then(|x| match x {
+        serde_json::Value::Number(num) => num.as_f64(),
+        serde_json::Value::String(s) => s.parse::<f64>().ok(),
+        _ => None,
+    });
+    match timestamp {
+        Some(timestamp) if timestamp < 1e12 => Ok((timestamp * 1000.0).round() as i64),
+        Some(timestamp) => Ok(timestamp.round() as i64),
+        None => Err(Error(format!("No timestamp at {} in {}", pointer, text))),
+    }
+}
+
+// Parses an HTTP date, e.g., `Sun, 06 Nov 1994 08:49:37 GMT`, into milliseconds.
+pub(super) fn parse_http_date(date: &str) -> Option<i64> {
+    const MONTHS: [&str; 12] = [
+        "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+    ];
+    let parts: Vec<&str> = date.split_whitespace().collect();
+    if parts.len() != 6 || parts[5] != "GMT" {
+        return None;
+    }
+    let day = parts[1].parse::<i64>().ok()?;
+    let month = MONTHS.iter().position(|m| *m == parts[2])? as i64 + 1;
+    let year = parts[3].parse::<i64>().ok()?;
+    let hms = parts[4]
+        .split(':')
+        .map(|x| x.parse::<i64>().ok())
+        .collect::<Option<Vec<i64>>>()?;
+    if hms.len() != 3 {
+        return None;
+    }
+
+    // days since 1970-01-01, see <http://howardhinnant.github.io/date_algorithms.html#days_from_civil>
+    let y = if month <= 2 { year - 1 } else { year };
+    let era = y.div_euclid(400);
+    let yoe = y - era * 400;
+    let doy = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    let days = era * 146097 + doe - 719468;
+
+    Some(((days * 24 + hms[0]) * 60 + hms[1]) * 60_000 + hms[2] * 1000)
+}
+
+// Limit depth to the exchange's maximum, None means the maximum.
+pub(super) fn clamp_depth(depth: Option<usize>, max: usize) -> Result<usize> {
+    match depth {
+        Some(0) => Err(Error("depth must be positive".to_string())),
+        Some(depth) => Ok(depth.min(max)),
+        None => Ok(max),
+    }
+}
+
+// Round depth up to the nearest value accepted by the exchange, None means `default`.
+pub(super) fn round_up_depth(
+    depth: Option<usize>,
+    valid_values: &[usize],
+    default: usize,
+) -> Result<usize> {
+    match depth {
+        Some(0) => Err(Error("depth must be positive".to_string())),
+        Some(depth) => Ok(*valid_values
+            .iter()
+            .find(|x| **x >= depth)
+            .unwrap_or_else(|| valid_values.last().unwrap())),
+        None => Ok(default),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::collections::BTreeMap;
 
     use serde_json::Value;
 
+    #[test]
+    fn json_timestamp() {
+        let ms = r#"{"serverTime":1499827319559}"#;
+        assert_eq!(
+            1499827319559,
+            super::json_timestamp(ms, "/serverTime").unwrap()
+        );
+        let seconds = r#"{"iso":"2015-01-07T23:47:25.201Z","epoch":"1420674445.201"}"#;
+        assert_eq!(
+            1420674445201,
+            super::json_timestamp(seconds, "/epoch").unwrap()
+        );
+        let nested = r#"{"error":[],"result":{"unixtime":1688669448}}"#;
+        assert_eq!(
+            1688669448000,
+            super::json_timestamp(nested, "/result/unixtime").unwrap()
+        );
+        assert!(super::json_timestamp(nested, "/epoch").is_err());
+    }
+
+    #[test]
+    fn clamp_depth() {
+        assert_eq!(500, super::clamp_depth(None, 500).unwrap());
+        assert_eq!(20, super::clamp_depth(Some(20), 500).unwrap());
+        assert_eq!(500, super::clamp_depth(Some(5000), 500).unwrap());
+        assert!(super::clamp_depth(Some(0), 500).is_err());
+    }
+
+    #[test]
+    fn round_up_depth() {
+        let valid_values = [5, 10, 20, 50, 100, 500, 1000];
+        assert_eq!(
+            1000,
+            super::round_up_depth(None, &valid_values, 1000).unwrap()
+        );
+        assert_eq!(
+            5,
+            super::round_up_depth(Some(1), &valid_values, 1000).unwrap()
+        );
+        assert_eq!(
+            20,
+            super::round_up_depth(Some(20), &valid_values, 1000).unwrap()
+        );
+        assert_eq!(
+            50,
+            super::round_up_depth(Some(21), &valid_values, 1000).unwrap()
+        );
+        assert_eq!(
+            1000,
+            super::round_up_depth(Some(5000), &valid_values, 1000).unwrap()
+        );
+        assert!(super::round_up_depth(Some(0), &valid_values, 1000).is_err());
+    }
+
+    #[test]
+    fn parse_http_date() {
+        assert_eq!(
+            Some(784111777000),
+            super::parse_http_date("Sun, 06 Nov 1994 08:49:37 GMT")
+        );
+        assert_eq!(
+            Some(1709210096000),
+            super::parse_http_date("Thu, 29 Feb 2024 12:34:56 GMT")
+        );
+        assert_eq!(
+            None,
+            super::parse_http_date("Sunday, 06-Nov-94 08:49:37 GMT")
+        );
+    }
+
     // System proxies are enabled by default, see <https://docs.rs/reqwest/latest/reqwest/#proxies>
     #[test]
     #[ignore]
     fn use_system_socks_proxy() {
         std::env::set_var("https_proxy", "socks5://127.0.0.1:9050");
-        let text =
-            super::http_get("https://check.torproject.org/api/ip", &BTreeMap::new()).unwrap();
+        let text = super::http_get(
+            "tor",
+            "https://check.torproject.org/api/ip",
+            &BTreeMap::new(),
+        )
+        .unwrap();
         let obj = serde_json::from_str::<BTreeMap<String, Value>>(&text).unwrap();
         assert!(obj.get("IsTor").unwrap().as_bool().unwrap());
     }
@@ -80,8 +234,12 @@ mod tests {
     #[ignore]
     fn use_system_https_proxy() {
         std::env::set_var("https_proxy", "http://127.0.0.1:8118");
-        let text =
-            super::http_get("https://check.torproject.org/api/ip", &BTreeMap::new()).unwrap();
+        let text = super::http_get(
+            "tor",
+            "https://check.torproject.org/api/ip",
+            &BTreeMap::new(),
+        )
+        .unwrap();
         let obj = serde_json::from_str::<BTreeMap<String, Value>>(&text).unwrap();
         assert!(obj.get("IsTor").unwrap().as_bool().unwrap());
     }
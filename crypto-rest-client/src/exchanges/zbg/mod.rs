@@ -7,16 +7,20 @@ pub use zbg_swap::ZbgSwapRestClient;
 use crate::error::Result;
 use crypto_market_type::MarketType;
 
-pub(crate) fn fetch_l2_snapshot(market_type: MarketType, symbol: &str) -> Result<String> {
+pub(crate) fn fetch_l2_snapshot(
+    market_type: MarketType,
+    symbol: &str,
+    depth: Option<usize>,
+) -> Result<String> {
     let func = match market_type {
-        MarketType::Spot => zbg_spot::ZbgSpotRestClient::fetch_l2_snapshot,
+        MarketType::Spot => zbg_spot::ZbgSpotRestClient::fetch_l2_snapshot_with_depth,
         MarketType::InverseSwap | MarketType::LinearSwap => {
-            zbg_swap::ZbgSwapRestClient::fetch_l2_snapshot
+            zbg_swap::ZbgSwapRestClient::fetch_l2_snapshot_with_depth
         }
         _ => panic!("ZBG unknown market_type: {}", market_type),
     };
 
-    func(symbol)
+    func(symbol, depth)
 }
 
 pub(crate) fn fetch_open_interest(market_type: MarketType, symbol: &str) -> Result<String> {
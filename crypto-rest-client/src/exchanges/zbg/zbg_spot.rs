@@ -1,7 +1,8 @@
-use super::super::utils::http_get;
+use super::super::utils::{clamp_depth, http_get};
 use crate::error::Result;
 use std::collections::BTreeMap;
 
+const EXCHANGE_NAME: &str = "zbg";
 const BASE_URL: &str = "https://kline.zbg.com";
 
 /// The RESTful client for ZBG spot market.
@@ -27,9 +28,17 @@ impl ZbgSpotRestClient {
     ///
     /// For example: <https://kline.zbg.com/api/data/v1/entrusts?marketName=btc_usdt&dataSize=200>,
     pub fn fetch_l2_snapshot(symbol: &str) -> Result<String> {
+        Self::fetch_l2_snapshot_with_depth(symbol, None)
+    }
+
+    /// Same as `fetch_l2_snapshot()`, with the number of levels set by `depth`.
+    ///
+    /// Top `depth` bids and asks are returned, at most 200.
+    pub fn fetch_l2_snapshot_with_depth(symbol: &str, depth: Option<usize>) -> Result<String> {
         gen_api!(format!(
-            "/api/data/v1/entrusts?marketName={}&dataSize=200",
-            symbol
+            "/api/data/v1/entrusts?marketName={}&dataSize={}",
+            symbol,
+            clamp_depth(depth, 200)?
         ))
     }
 }
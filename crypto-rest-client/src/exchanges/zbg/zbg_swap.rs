@@ -1,7 +1,8 @@
-use super::super::utils::http_get;
+use super::super::utils::{clamp_depth, http_get};
 use crate::error::Result;
 use std::collections::BTreeMap;
 
+const EXCHANGE_NAME: &str = "zbg";
 const BASE_URL: &str = "https://www.zbg.com";
 
 /// The RESTful client for ZBG swap markets.
@@ -27,9 +28,17 @@ impl ZbgSwapRestClient {
     ///
     /// For example: <https://www.zbg.com/exchange/api/v1/future/market/depth?symbol=BTC_USD-R&size=200>
     pub fn fetch_l2_snapshot(symbol: &str) -> Result<String> {
+        Self::fetch_l2_snapshot_with_depth(symbol, None)
+    }
+
+    /// Same as `fetch_l2_snapshot()`, with the number of levels set by `depth`.
+    ///
+    /// Top `depth` bids and asks are returned, at most 1000.
+    pub fn fetch_l2_snapshot_with_depth(symbol: &str, depth: Option<usize>) -> Result<String> {
         gen_api!(format!(
-            "/exchange/api/v1/future/market/depth?symbol={}&size=1000",
-            symbol
+            "/exchange/api/v1/future/market/depth?symbol={}&size={}",
+            symbol,
+            clamp_depth(depth, 1000)?
         ))
     }
 
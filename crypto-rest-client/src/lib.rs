@@ -1,6 +1,7 @@
 mod error;
 mod exchanges;
 
+pub use crypto_pair::{set_header, set_user_agent, DEFAULT_USER_AGENT};
 pub use error::Error;
 pub use exchanges::binance::binance_inverse::BinanceInverseRestClient;
 pub use exchanges::binance::binance_linear::BinanceLinearRestClient;
@@ -35,31 +36,41 @@ use error::Result;
 use log::*;
 use std::time::{Duration, SystemTime};
 
+// Exchanges without a depth parameter always return their default depth.
 fn fetch_l2_snapshot_internal(
     exchange: &str,
     market_type: MarketType,
     symbol: &str,
+    depth: Option<usize>,
 ) -> Result<String> {
     match exchange {
-        "binance" => exchanges::binance::fetch_l2_snapshot(market_type, symbol),
-        "bitfinex" => exchanges::bitfinex::BitfinexRestClient::fetch_l2_snapshot(symbol),
-        "bitget" => exchanges::bitget::fetch_l2_snapshot(market_type, symbol),
+        "binance" => exchanges::binance::fetch_l2_snapshot(market_type, symbol, depth),
+        "bitfinex" => {
+            exchanges::bitfinex::BitfinexRestClient::fetch_l2_snapshot_with_depth(symbol, depth)
+        }
+        "bitget" => exchanges::bitget::fetch_l2_snapshot(market_type, symbol, depth),
         "bithumb" => exchanges::bithumb::BithumbRestClient::fetch_l2_snapshot(symbol),
-        "bitmex" => exchanges::bitmex::BitmexRestClient::fetch_l2_snapshot(symbol),
+        "bitmex" => {
+            exchanges::bitmex::BitmexRestClient::fetch_l2_snapshot_with_depth(symbol, depth)
+        }
         "bitstamp" => exchanges::bitstamp::BitstampRestClient::fetch_l2_snapshot(symbol),
-        "bitz" => exchanges::bitz::fetch_l2_snapshot(market_type, symbol),
+        "bitz" => exchanges::bitz::fetch_l2_snapshot(market_type, symbol, depth),
         "bybit" => exchanges::bybit::BybitRestClient::fetch_l2_snapshot(symbol),
         "coinbase_pro" => exchanges::coinbase_pro::CoinbaseProRestClient::fetch_l2_snapshot(symbol),
-        "deribit" => exchanges::deribit::DeribitRestClient::fetch_l2_snapshot(symbol),
+        "deribit" => {
+            exchanges::deribit::DeribitRestClient::fetch_l2_snapshot_with_depth(symbol, depth)
+        }
         "dydx" => exchanges::dydx::fetch_l2_snapshot(market_type, symbol),
-        "ftx" => exchanges::ftx::FtxRestClient::fetch_l2_snapshot(symbol),
-        "gate" => exchanges::gate::fetch_l2_snapshot(market_type, symbol),
+        "ftx" => exchanges::ftx::FtxRestClient::fetch_l2_snapshot_with_depth(symbol, depth),
+        "gate" => exchanges::gate::fetch_l2_snapshot(market_type, symbol, depth),
         "huobi" => exchanges::huobi::fetch_l2_snapshot(market_type, symbol),
-        "kraken" => exchanges::kraken::KrakenRestClient::fetch_l2_snapshot(symbol),
+        "kraken" => {
+            exchanges::kraken::KrakenRestClient::fetch_l2_snapshot_with_depth(symbol, depth)
+        }
         "kucoin" => exchanges::kucoin::fetch_l2_snapshot(market_type, symbol),
-        "mxc" => exchanges::mxc::fetch_l2_snapshot(market_type, symbol),
-        "okex" => exchanges::okex::OkexRestClient::fetch_l2_snapshot(symbol),
-        "zbg" => exchanges::zbg::fetch_l2_snapshot(market_type, symbol),
+        "mxc" => exchanges::mxc::fetch_l2_snapshot(market_type, symbol, depth),
+        "okex" => exchanges::okex::OkexRestClient::fetch_l2_snapshot_with_depth(symbol, depth),
+        "zbg" => exchanges::zbg::fetch_l2_snapshot(market_type, symbol, depth),
         _ => panic!("Unknown exchange {}", exchange),
     }
 }
@@ -92,6 +103,7 @@ pub fn fetch_open_interest(
     match exchange {
         "binance" => exchanges::binance::fetch_open_interest(market_type, symbol.unwrap()),
         "bitget" => exchanges::bitget::fetch_open_interest(market_type, symbol.unwrap()),
+        "bitmex" => exchanges::bitmex::BitmexRestClient::fetch_open_interest(symbol),
         "bybit" => exchanges::bybit::BybitRestClient::fetch_open_interest(symbol.unwrap()),
         "bitz" => exchanges::bitz::fetch_open_interest(market_type, symbol),
         "deribit" => exchanges::deribit::DeribitRestClient::fetch_open_interest(symbol),
@@ -106,6 +118,73 @@ pub fn fetch_open_interest(
     }
 }
 
+/// Fetch candlesticks of a symbol, oldest first.
+///
+/// `interval` is in seconds, `start_time` and `end_time` are in milliseconds
+/// and match the open time of candlesticks. Each request returns at most
+/// 1000 candlesticks, fetch later pages by moving `start_time` past the last
+/// one. Only binance spot and linear markets are supported.
+pub fn fetch_candlestick(
+    exchange: &str,
+    market_type: MarketType,
+    symbol: &str,
+    interval: usize,
+    start_time: Option<u64>,
+    end_time: Option<u64>,
+) -> Result<String> {
+    match exchange {
+        "binance" => exchanges::binance::fetch_candlestick(
+            market_type,
+            symbol,
+            interval,
+            start_time,
+            end_time,
+        ),
+        _ => Err(Error(format!(
+            "{} does NOT have candlestick RESTful API",
+            exchange
+        ))),
+    }
+}
+
+/// Fetch the current time of the exchange's server, in milliseconds since the Unix epoch.
+///
+/// Spot endpoints are used, since all markets of an exchange share the same clock.
+pub fn fetch_server_time(exchange: &str) -> Result<i64> {
+    match exchange {
+        "binance" => exchanges::binance::binance_spot::BinanceSpotRestClient::fetch_server_time(),
+        "bitmex" => exchanges::bitmex::BitmexRestClient::fetch_server_time(),
+        "bybit" => exchanges::bybit::BybitRestClient::fetch_server_time(),
+        "coinbase_pro" => exchanges::coinbase_pro::CoinbaseProRestClient::fetch_server_time(),
+        "deribit" => exchanges::deribit::DeribitRestClient::fetch_server_time(),
+        "huobi" => exchanges::huobi::huobi_spot::HuobiSpotRestClient::fetch_server_time(),
+        "kraken" => exchanges::kraken::KrakenRestClient::fetch_server_time(),
+        "kucoin" => exchanges::kucoin::KuCoinSpotRestClient::fetch_server_time(),
+        "okex" => exchanges::okex::OkexRestClient::fetch_server_time(),
+        _ => Err(Error(format!(
+            "{} does NOT have server time RESTful API",
+            exchange
+        ))),
+    }
+}
+
+/// Measure the clock skew of the local machine relative to the exchange, in milliseconds.
+///
+/// The server time is compared with the local time halfway through the
+/// request, which cancels out the network latency if it is symmetric. A
+/// positive skew means the local clock is ahead of the exchange, subtract it
+/// from local timestamps to align them with timestamps of the exchange.
+pub fn fetch_clock_skew(exchange: &str) -> Result<i64> {
+    let before = SystemTime::now();
+    let server_time = fetch_server_time(exchange)?;
+    let elapsed = before.elapsed().unwrap_or_default();
+    let local_time = (before + elapsed / 2)
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .unwrap()
+        .as_millis() as i64;
+    Ok(local_time - server_time)
+}
+
 /// Fetch level2 orderbook snapshot.
 ///
 /// `retry` None means no retry; Some(0) means retry unlimited times; Some(n) means retry n times.
@@ -115,11 +194,34 @@ pub fn fetch_l2_snapshot(
     symbol: &str,
     retry: Option<u64>,
 ) -> Result<String> {
+    fetch_l2_snapshot_with_depth(exchange, market_type, symbol, None, retry)
+}
+
+/// Fetch level2 orderbook snapshot with at most `depth` levels on each side.
+///
+/// `depth` None means the maximum depth of the exchange, otherwise it is
+/// clamped to the exchange's maximum; exchanges without a depth parameter
+/// ignore it. `Some(0)` is an error.
+///
+/// `retry` None means no retry; Some(0) means retry unlimited times; Some(n) means retry n times.
+pub fn fetch_l2_snapshot_with_depth(
+    exchange: &str,
+    market_type: MarketType,
+    symbol: &str,
+    depth: Option<usize>,
+    retry: Option<u64>,
+) -> Result<String> {
+    if depth == Some(0) {
+        // fail fast instead of retrying
+        return Err(Error("depth must be positive".to_string()));
+    }
     retriable(
         exchange,
         market_type,
         symbol,
-        fetch_l2_snapshot_internal,
+        |exchange, market_type, symbol| {
+            fetch_l2_snapshot_internal(exchange, market_type, symbol, depth)
+        },
         retry,
     )
 }
@@ -147,7 +249,7 @@ fn retriable(
     exchange: &str,
     market_type: MarketType,
     symbol: &str,
-    crawl_func: fn(&str, MarketType, &str) -> Result<String>,
+    crawl_func: impl Fn(&str, MarketType, &str) -> Result<String>,
     retry: Option<u64>,
 ) -> Result<String> {
     let retry_count = {
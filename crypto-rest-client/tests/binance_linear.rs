@@ -1,7 +1,9 @@
 #[cfg(test)]
 mod linear_swap {
     use crypto_market_type::MarketType;
-    use crypto_rest_client::{fetch_l2_snapshot, fetch_open_interest, BinanceLinearRestClient};
+    use crypto_rest_client::{
+        fetch_candlestick, fetch_l2_snapshot, fetch_open_interest, BinanceLinearRestClient,
+    };
 
     #[test]
     fn test_agg_trades() {
@@ -9,6 +11,20 @@ mod linear_swap {
         assert!(text.starts_with("[{"));
     }
 
+    #[test]
+    fn test_candlestick() {
+        let text = fetch_candlestick(
+            "binance",
+            MarketType::LinearSwap,
+            "BTCUSDT",
+            3600,
+            None,
+            None,
+        )
+        .unwrap();
+        assert!(text.starts_with("[["));
+    }
+
     #[test]
     fn test_l2_snapshot() {
         let text =
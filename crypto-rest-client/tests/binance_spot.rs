@@ -1,5 +1,16 @@
 use crypto_market_type::MarketType;
-use crypto_rest_client::{fetch_l2_snapshot, BinanceSpotRestClient};
+use crypto_rest_client::{
+    fetch_candlestick, fetch_clock_skew, fetch_l2_snapshot, fetch_l2_snapshot_with_depth,
+    BinanceSpotRestClient,
+};
+
+#[test]
+fn test_server_time() {
+    let server_time = BinanceSpotRestClient::fetch_server_time().unwrap();
+    assert!(server_time > 1600000000000);
+    // a healthy clock is within a minute of the exchange
+    assert!(fetch_clock_skew("binance").unwrap().abs() < 60000);
+}
 
 #[test]
 fn test_agg_trades() {
@@ -12,3 +23,37 @@ fn test_l2_snapshot() {
     let text = fetch_l2_snapshot("binance", MarketType::Spot, "BTCUSDT", Some(3)).unwrap();
     assert!(text.starts_with("{"));
 }
+
+#[test]
+fn test_l2_snapshot_with_depth() {
+    let text =
+        fetch_l2_snapshot_with_depth("binance", MarketType::Spot, "BTCUSDT", Some(100), Some(3))
+            .unwrap();
+    let obj = serde_json::from_str::<serde_json::Value>(&text).unwrap();
+    assert_eq!(obj["bids"].as_array().unwrap().len(), 100);
+    assert_eq!(obj["asks"].as_array().unwrap().len(), 100);
+}
+
+#[test]
+fn test_l2_snapshot_with_zero_depth() {
+    assert!(
+        fetch_l2_snapshot_with_depth("binance", MarketType::Spot, "BTCUSDT", Some(0), Some(3))
+            .is_err()
+    );
+}
+
+#[test]
+fn test_candlestick() {
+    let text = fetch_candlestick(
+        "binance",
+        MarketType::Spot,
+        "BTCUSDT",
+        60,
+        Some(1633046400000),
+        Some(1633046459999),
+    )
+    .unwrap();
+    let klines = serde_json::from_str::<Vec<serde_json::Value>>(&text).unwrap();
+    assert_eq!(klines.len(), 1);
+    assert_eq!(klines[0][0].as_i64(), Some(1633046400000));
+}
@@ -1,7 +1,7 @@
 use std::collections::HashMap;
 
 use crypto_market_type::MarketType;
-use crypto_rest_client::{fetch_l2_snapshot, BithumbRestClient};
+use crypto_rest_client::{fetch_clock_skew, fetch_l2_snapshot, BithumbRestClient};
 use serde_json::Value;
 
 #[test]
@@ -26,3 +26,8 @@ fn test_l2_snapshot() {
     assert!(buy.len() > 0);
     assert!(sell.len() > 0);
 }
+
+#[test]
+fn test_server_time_unsupported() {
+    assert!(fetch_clock_skew("bithumb").is_err());
+}
@@ -1,5 +1,14 @@
 use crypto_market_type::MarketType;
-use crypto_rest_client::{fetch_l2_snapshot, BitmexRestClient};
+use crypto_rest_client::{
+    fetch_l2_snapshot, fetch_open_interest, fetch_server_time, BitmexRestClient,
+};
+
+#[test]
+fn test_server_time() {
+    let server_time = fetch_server_time("bitmex").unwrap();
+    assert!(server_time > 1600000000000);
+    assert_eq!(0, server_time % 1000);
+}
 
 #[test]
 fn test_trades() {
@@ -12,3 +21,12 @@ fn test_l2_snapshot() {
     let text = fetch_l2_snapshot("bitmex", MarketType::InverseSwap, "XBTUSD", Some(3)).unwrap();
     assert!(text.starts_with("[{"));
 }
+
+#[test]
+fn test_open_interest() {
+    let text = fetch_open_interest("bitmex", MarketType::InverseSwap, Some("XBTUSD")).unwrap();
+    assert!(text.starts_with("[{"));
+
+    let text = fetch_open_interest("bitmex", MarketType::Unknown, None).unwrap();
+    assert!(text.starts_with("[{"));
+}
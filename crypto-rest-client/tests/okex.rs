@@ -1,7 +1,15 @@
 use crypto_market_type::MarketType;
-use crypto_rest_client::{fetch_l2_snapshot, fetch_open_interest};
+use crypto_rest_client::{
+    fetch_l2_snapshot, fetch_l2_snapshot_with_depth, fetch_open_interest, fetch_server_time,
+};
 use test_case::test_case;
 
+#[test]
+fn test_server_time() {
+    let server_time = fetch_server_time("okex").unwrap();
+    assert!(server_time > 1600000000000);
+}
+
 #[test_case(MarketType::Spot, "BTC-USDT")]
 #[test_case(MarketType::InverseFuture, "BTC-USD-211231")]
 #[test_case(MarketType::LinearFuture, "BTC-USDT-211231")]
@@ -13,6 +21,16 @@ fn test_l2_snapshot(market_type: MarketType, symbol: &str) {
     assert!(text.starts_with("{"));
 }
 
+#[test_case(MarketType::Spot, "BTC-USDT")]
+#[test_case(MarketType::LinearSwap, "BTC-USDT-SWAP")]
+fn test_l2_snapshot_with_depth(market_type: MarketType, symbol: &str) {
+    let text =
+        fetch_l2_snapshot_with_depth("okex", market_type, symbol, Some(10), Some(3)).unwrap();
+    let obj = serde_json::from_str::<serde_json::Value>(&text).unwrap();
+    assert_eq!(obj["bids"].as_array().unwrap().len(), 10);
+    assert_eq!(obj["asks"].as_array().unwrap().len(), 10);
+}
+
 #[test_case(MarketType::InverseFuture, "BTC-USD-211231")]
 #[test_case(MarketType::LinearFuture, "BTC-USDT-211231")]
 #[test_case(MarketType::InverseSwap, "BTC-USD-SWAP")]
@@ -0,0 +1,92 @@
+//! Compares allocations of decompressing a huobi-like gzip stream with a new
+//! decoder per frame against a reused `Decompressor`.
+//!
+//! Run with `cargo bench --bench decompress`.
+use crypto_ws_client::{Decompressor, Encoding};
+use flate2::{read::GzDecoder, write::GzEncoder, Compression};
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::io::{Read, Write};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Instant;
+
+struct CountingAllocator;
+
+static NUM_ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        NUM_ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        NUM_ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+const NUM_FRAMES: usize = 100_000;
+
+// Trade messages in the format of huobi market.$symbol.trade.detail, gzip compressed
+fn huobi_stream() -> Vec<Vec<u8>> {
+    (0..NUM_FRAMES)
+        .map(|i| {
+            let txt = format!(
+                r#"{{"ch":"market.btcusdt.trade.detail","ts":{},"tick":{{"id":{},"ts":{},"data":[{{"id":{},"ts":{},"tradeId":{},"amount":0.0{},"price":{}.{},"direction":"{}"}}]}}}}"#,
+                1616243180000_u64 + i as u64,
+                123675580000_u64 + i as u64,
+                1616243180000_u64 + i as u64,
+                100000000000_u64 + i as u64,
+                1616243180000_u64 + i as u64,
+                102000000 + i,
+                i % 97,
+                58000 + i % 1000,
+                i % 100,
+                if i % 2 == 0 { "buy" } else { "sell" }
+            );
+            let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+            encoder.write_all(txt.as_bytes()).unwrap();
+            encoder.finish().unwrap()
+        })
+        .collect()
+}
+
+fn measure<F: FnMut(&[u8]) -> usize>(name: &str, frames: &[Vec<u8>], mut decompress: F) {
+    let num_allocations = NUM_ALLOCATIONS.load(Ordering::Relaxed);
+    let start = Instant::now();
+    let total_bytes: usize = frames.iter().map(|frame| decompress(frame)).sum();
+    let elapsed = start.elapsed();
+    let num_allocations = NUM_ALLOCATIONS.load(Ordering::Relaxed) - num_allocations;
+    println!(
+        "{:<20} {:>10} allocations, {:.2} per frame, {:?}, {} bytes",
+        name,
+        num_allocations,
+        num_allocations as f64 / frames.len() as f64,
+        elapsed,
+        total_bytes
+    );
+}
+
+fn main() {
+    let frames = huobi_stream();
+    measure("decoder per frame", &frames, |frame| {
+        let mut txt = String::new();
+        let mut decoder = GzDecoder::new(frame);
+        decoder.read_to_string(&mut txt).unwrap();
+        txt.len()
+    });
+    let mut decompressor = Decompressor::new();
+    measure("reused Decompressor", &frames, |frame| {
+        decompressor
+            .decompress(Encoding::Gzip, frame)
+            .unwrap()
+            .len()
+    });
+}
@@ -0,0 +1,56 @@
+use std::sync::mpsc::Sender;
+
+/// Websocket clients that can connect to one of several candidate URLs.
+///
+/// Implemented by every client in this crate, usually used through `WSClientBuilder`.
+pub trait FromUrls: Sized {
+    /// Creates a websocket client connecting to `urls` in turn.
+    ///
+    /// The client starts with the first URL and moves on to the next one
+    /// after repeated connection failures, wrapping around at the end.
+    /// An empty `urls` means the default URL of the exchange.
+    fn from_urls(tx: Sender<String>, urls: &[String]) -> Self;
+}
+
+/// Builds a websocket client with backup URLs for failover.
+///
+/// ## Example
+///
+/// ```no_run
+/// use crypto_ws_client::{BinanceSpotWSClient, WSClientBuilder};
+///
+/// let (tx, _rx) = std::sync::mpsc::channel();
+/// let ws_client: BinanceSpotWSClient = WSClientBuilder::new()
+///     .urls(vec![
+///         "wss://stream.binance.com:9443/stream".to_string(),
+///         "wss://stream1.binance.com:9443/stream".to_string(),
+///         "wss://stream2.binance.com:9443/stream".to_string(),
+///     ])
+///     .build(tx);
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct WSClientBuilder {
+    urls: Vec<String>,
+}
+
+impl WSClientBuilder {
+    /// Creates a builder connecting to the default URL of the exchange.
+    pub fn new() -> Self {
+        WSClientBuilder::default()
+    }
+
+    /// Sets candidate URLs, in order of preference.
+    pub fn urls(mut self, urls: Vec<String>) -> Self {
+        self.urls = urls;
+        self
+    }
+
+    /// Connects and returns the client.
+    ///
+    /// # Arguments
+    ///
+    /// * `tx` - The sending part of a channel
+    pub fn build<C: FromUrls>(self, tx: Sender<String>) -> C {
+        C::from_urls(tx, &self.urls)
+    }
+}
@@ -1,21 +1,33 @@
-use crate::WSClient;
+use crate::{AuthError, Encoding, Interval, UnsupportedChannel, WSClient};
 use std::collections::HashMap;
 use std::sync::mpsc::Sender;
 
+use super::registry::ExchangeSpec;
 use super::utils::ensure_frame_size;
 use super::{
     ws_client_internal::{MiscMessage, WSClientInternal},
     Candlestick, Level3OrderBook, OrderBook, OrderBookTopK, Ticker, Trade, BBO,
 };
-use log::*;
 use serde_json::Value;
 
 pub(super) const EXCHANGE_NAME: &str = "binance";
 
+pub(super) const SPEC: ExchangeSpec = ExchangeSpec {
+    name: EXCHANGE_NAME,
+    encoding: Some(Encoding::Gzip),
+    // WebSocket connections have a limit of 10 incoming messages per second
+    send_interval_ms: Some(100),
+};
+
 const SPOT_WEBSOCKET_URL: &str = "wss://stream.binance.com:9443/stream";
 const LINEAR_WEBSOCKET_URL: &str = "wss://fstream.binance.com/stream";
 const INVERSE_WEBSOCKET_URL: &str = "wss://dstream.binance.com/stream";
 
+// Endpoints to create listenKeys of user data streams
+const SPOT_LISTEN_KEY_URL: &str = "https://api.binance.com/api/v3/userDataStream";
+const LINEAR_LISTEN_KEY_URL: &str = "https://fapi.binance.com/fapi/v1/listenKey";
+const INVERSE_LISTEN_KEY_URL: &str = "https://dapi.binance.com/dapi/v1/listenKey";
+
 // https://binance-docs.github.io/apidocs/futures/en/#websocket-market-streams
 // A single connection can listen to a maximum of 200 streams
 const MAX_NUM_CHANNELS: usize = 200;
@@ -57,11 +69,11 @@ pub struct BinanceLinearWSClient {
 }
 
 impl BinanceWSClient {
-    fn new(url: &str, tx: Sender<String>) -> Self {
+    fn new(urls: Vec<String>, tx: Sender<String>) -> Self {
         BinanceWSClient {
             client: WSClientInternal::new(
                 EXCHANGE_NAME,
-                url,
+                urls,
                 tx,
                 Self::on_misc_msg,
                 Self::channels_to_commands,
@@ -96,7 +108,7 @@ impl BinanceWSClient {
     fn on_misc_msg(msg: &str) -> MiscMessage {
         let resp = serde_json::from_str::<HashMap<String, Value>>(msg);
         if resp.is_err() {
-            error!("{} is not a JSON string, {}", msg, EXCHANGE_NAME);
+            exchange_log!(EXCHANGE_NAME, Error, "{} is not a JSON string", msg);
             return MiscMessage::Misc;
         }
         let obj = resp.unwrap();
@@ -110,16 +122,33 @@ impl BinanceWSClient {
                 if serde_json::Value::Null != *result {
                     panic!("Received {} from {}", msg, EXCHANGE_NAME);
                 } else {
-                    info!("Received {} from {}", msg, EXCHANGE_NAME);
+                    exchange_log!(EXCHANGE_NAME, Info, "Received {}", msg);
                 }
             } else {
-                warn!("Received {} from {}", msg, EXCHANGE_NAME);
+                exchange_log!(EXCHANGE_NAME, Warn, "Received {}", msg);
             }
             MiscMessage::Misc
         }
     }
 }
 
+// Creates a listenKey, or extends the validity of the existing one by 60 minutes, see
+// <https://binance-docs.github.io/apidocs/spot/en/#listen-key-spot>
+fn create_listen_key(url: &str, api_key: &str) -> Result<String, AuthError> {
+    let response = reqwest::blocking::Client::new()
+        .post(url)
+        .headers(crypto_pair::headers(EXCHANGE_NAME))
+        .header("X-MBX-APIKEY", api_key)
+        .send()
+        .and_then(|resp| resp.error_for_status())
+        .and_then(|resp| resp.text())
+        .map_err(|err| AuthError::Request(err.to_string()))?;
+    serde_json::from_str::<HashMap<String, Value>>(&response)
+        .ok()
+        .and_then(|obj| obj.get("listenKey")?.as_str().map(|x| x.to_string()))
+        .ok_or_else(|| AuthError::Request(format!("No listenKey in {}", response)))
+}
+
 fn to_raw_channel(channel: &str, pair: &str) -> String {
     format!("{}@{}", pair.to_lowercase(), channel)
 }
@@ -135,33 +164,36 @@ impl_trait!(OrderBook, BinanceWSClient, subscribe_orderbook, "depth", to_raw_cha
 #[rustfmt::skip]
 impl_trait!(OrderBookTopK, BinanceWSClient, subscribe_orderbook_topk, "depth5", to_raw_channel);
 
-fn to_candlestick_raw_channel(pair: &str, interval: usize) -> String {
+fn to_candlestick_raw_channel(
+    pair: &str,
+    interval: Interval,
+) -> Result<String, UnsupportedChannel> {
     let interval_str = match interval {
-        60 => "1m",
-        180 => "3m",
-        300 => "5m",
-        900 => "15m",
-        1800 => "30m",
-        3600 => "1h",
-        7200 => "2h",
-        14400 => "4h",
-        21600 => "6h",
-        28800 => "8h",
-        43200 => "12h",
-        86400 => "1d",
-        259200 => "3d",
-        604800 => "1w",
-        2592000 => "1M",
-        _ => panic!("Binance has intervals 1m,3m,5m,15m,30m,1h,2h,4h,6h,8h,12h,1d,3d,1w,1M"),
+        Interval::Min1 => "1m",
+        Interval::Min3 => "3m",
+        Interval::Min5 => "5m",
+        Interval::Min15 => "15m",
+        Interval::Min30 => "30m",
+        Interval::Hour1 => "1h",
+        Interval::Hour2 => "2h",
+        Interval::Hour4 => "4h",
+        Interval::Hour6 => "6h",
+        Interval::Hour8 => "8h",
+        Interval::Hour12 => "12h",
+        Interval::Day1 => "1d",
+        Interval::Day3 => "3d",
+        Interval::Week1 => "1w",
+        Interval::Month1 => "1M",
+        _ => return Err(UnsupportedChannel::interval(EXCHANGE_NAME, interval)),
     };
-    format!("{}@kline_{}", pair, interval_str)
+    Ok(format!("{}@kline_{}", pair, interval_str))
 }
 
 impl_candlestick!(BinanceWSClient);
 
 /// Define market specific client.
 macro_rules! define_market_client {
-    ($struct_name:ident, $default_url:ident) => {
+    ($struct_name:ident, $default_url:ident, $listen_key_url:ident) => {
         impl $struct_name {
             /// Creates a Binance websocket client.
             ///
@@ -170,43 +202,126 @@ macro_rules! define_market_client {
             /// * `on_msg` - A callback function to process websocket messages
             /// * `url` - Optional server url, usually you don't need specify it
             pub fn new(tx: Sender<String>, url: Option<&str>) -> Self {
-                let real_url = match url {
-                    Some(endpoint) => endpoint,
-                    None => $default_url,
+                let urls: Vec<String> = url.map(|url| vec![url.to_string()]).unwrap_or_default();
+                <$struct_name as crate::FromUrls>::from_urls(tx, &urls)
+            }
+        }
+
+        impl crate::FromUrls for $struct_name {
+            fn from_urls(tx: Sender<String>, urls: &[String]) -> Self {
+                let urls = if urls.is_empty() {
+                    vec![$default_url.to_string()]
+                } else {
+                    urls.to_vec()
                 };
                 $struct_name {
-                    client: BinanceWSClient::new(real_url, tx),
+                    client: BinanceWSClient::new(urls, tx),
                 }
             }
         }
 
         impl WSClient for $struct_name {
-            fn subscribe_trade(&self, channels: &[String]) {
-                <$struct_name as Trade>::subscribe_trade(self, channels);
+            fn subscribe_trade(&self, channels: &[String]) -> Result<(), UnsupportedChannel> {
+                <$struct_name as Trade>::subscribe_trade(self, channels)
+            }
+
+            fn subscribe_orderbook(&self, channels: &[String]) -> Result<(), UnsupportedChannel> {
+                <$struct_name as OrderBook>::subscribe_orderbook(self, channels)
+            }
+
+            fn subscribe_orderbook_topk(
+                &self,
+                channels: &[String],
+            ) -> Result<(), UnsupportedChannel> {
+                <$struct_name as OrderBookTopK>::subscribe_orderbook_topk(self, channels)
+            }
+
+            fn subscribe_l3_orderbook(
+                &self,
+                channels: &[String],
+            ) -> Result<(), UnsupportedChannel> {
+                <$struct_name as Level3OrderBook>::subscribe_l3_orderbook(self, channels)
+            }
+
+            fn subscribe_ticker(&self, channels: &[String]) -> Result<(), UnsupportedChannel> {
+                <$struct_name as Ticker>::subscribe_ticker(self, channels)
+            }
+
+            fn subscribe_bbo(&self, channels: &[String]) -> Result<(), UnsupportedChannel> {
+                <$struct_name as BBO>::subscribe_bbo(self, channels)
             }
 
-            fn subscribe_orderbook(&self, channels: &[String]) {
-                <$struct_name as OrderBook>::subscribe_orderbook(self, channels);
+            fn subscribe_candlestick(
+                &self,
+                symbol_interval_list: &[(String, Interval)],
+            ) -> Result<(), UnsupportedChannel> {
+                <$struct_name as Candlestick>::subscribe_candlestick(self, symbol_interval_list)
             }
 
-            fn subscribe_orderbook_topk(&self, channels: &[String]) {
-                <$struct_name as OrderBookTopK>::subscribe_orderbook_topk(self, channels);
+            fn unsubscribe_trade(&self, channels: &[String]) -> Result<(), UnsupportedChannel> {
+                let raw_channels = self.client.client.collect_channels(|| {
+                    <$struct_name as Trade>::subscribe_trade(self, channels)
+                })?;
+                self.client.client.unsubscribe(&raw_channels);
+                Ok(())
             }
 
-            fn subscribe_l3_orderbook(&self, channels: &[String]) {
-                <$struct_name as Level3OrderBook>::subscribe_l3_orderbook(self, channels);
+            fn unsubscribe_orderbook(&self, channels: &[String]) -> Result<(), UnsupportedChannel> {
+                let raw_channels = self.client.client.collect_channels(|| {
+                    <$struct_name as OrderBook>::subscribe_orderbook(self, channels)
+                })?;
+                self.client.client.unsubscribe(&raw_channels);
+                Ok(())
             }
 
-            fn subscribe_ticker(&self, channels: &[String]) {
-                <$struct_name as Ticker>::subscribe_ticker(self, channels);
+            fn unsubscribe_orderbook_topk(
+                &self,
+                channels: &[String],
+            ) -> Result<(), UnsupportedChannel> {
+                let raw_channels = self.client.client.collect_channels(|| {
+                    <$struct_name as OrderBookTopK>::subscribe_orderbook_topk(self, channels)
+                })?;
+                self.client.client.unsubscribe(&raw_channels);
+                Ok(())
             }
 
-            fn subscribe_bbo(&self, channels: &[String]) {
-                <$struct_name as BBO>::subscribe_bbo(self, channels);
+            fn unsubscribe_l3_orderbook(
+                &self,
+                channels: &[String],
+            ) -> Result<(), UnsupportedChannel> {
+                let raw_channels = self.client.client.collect_channels(|| {
+                    <$struct_name as Level3OrderBook>::subscribe_l3_orderbook(self, channels)
+                })?;
+                self.client.client.unsubscribe(&raw_channels);
+                Ok(())
             }
 
-            fn subscribe_candlestick(&self, symbol_interval_list: &[(String, usize)]) {
-                <$struct_name as Candlestick>::subscribe_candlestick(self, symbol_interval_list);
+            fn unsubscribe_ticker(&self, channels: &[String]) -> Result<(), UnsupportedChannel> {
+                let raw_channels = self.client.client.collect_channels(|| {
+                    <$struct_name as Ticker>::subscribe_ticker(self, channels)
+                })?;
+                self.client.client.unsubscribe(&raw_channels);
+                Ok(())
+            }
+
+            fn unsubscribe_bbo(&self, channels: &[String]) -> Result<(), UnsupportedChannel> {
+                let raw_channels = self
+                    .client
+                    .client
+                    .collect_channels(|| <$struct_name as BBO>::subscribe_bbo(self, channels))?;
+                self.client.client.unsubscribe(&raw_channels);
+                Ok(())
+            }
+
+            fn unsubscribe_candlestick(
+                &self,
+                symbol_interval_list: &[(String, Interval)],
+            ) -> Result<(), UnsupportedChannel> {
+                let raw_channels = self.client.client.collect_channels(|| {
+                    <$struct_name as Candlestick>::subscribe_candlestick(self, symbol_interval_list)
+                })?;
+                self.client.client.unsubscribe(&raw_channels);
+                Ok(())
             }
 
             fn subscribe(&self, channels: &[String]) {
@@ -217,10 +332,54 @@ macro_rules! define_market_client {
                 self.client.client.unsubscribe(channels);
             }
 
+            fn set_max_command_bytes(&self, max_bytes: usize) {
+                self.client.client.set_max_command_bytes(max_bytes);
+            }
+
+            fn set_on_stale(&self, threshold: u64, on_stale: Option<fn(&str)>) {
+                self.client.client.set_on_stale(threshold, on_stale);
+            }
+
+            fn set_raw_sender(&self, raw_tx: Option<Sender<Vec<u8>>>, keep_text: bool) {
+                self.client.client.set_raw_sender(raw_tx, keep_text);
+            }
+
+            fn set_reset_on_reconnect(&self, reset_tx: Option<Sender<crate::ResetNotification>>) {
+                self.client.client.set_reset_on_reconnect(reset_tx);
+            }
+
+            fn set_max_frame_size(&self, max_frame_size: Option<usize>) {
+                self.client.client.set_max_frame_size(max_frame_size);
+            }
+
+            fn authenticate(
+                &self,
+                api_key: &str,
+                _secret: &str,
+                _passphrase: &str,
+            ) -> Result<(), AuthError> {
+                let listen_key = create_listen_key($listen_key_url, api_key)?;
+                // the user data stream is named after the listenKey
+                self.client.client.subscribe(&[listen_key]);
+                Ok(())
+            }
+
+            fn stats(&self) -> crate::WSClientStats {
+                self.client.client.stats()
+            }
+
+            fn subscriptions(&self) -> Vec<String> {
+                self.client.client.subscriptions()
+            }
+
             fn run(&self, duration: Option<u64>) {
                 self.client.client.run(duration);
             }
 
+            fn close_gracefully(&self) {
+                self.client.client.close_gracefully();
+            }
+
             fn close(&self) {
                 self.client.client.close();
             }
@@ -228,15 +387,23 @@ macro_rules! define_market_client {
     };
 }
 
-define_market_client!(BinanceSpotWSClient, SPOT_WEBSOCKET_URL);
-define_market_client!(BinanceInverseWSClient, INVERSE_WEBSOCKET_URL);
-define_market_client!(BinanceLinearWSClient, LINEAR_WEBSOCKET_URL);
+define_market_client!(BinanceSpotWSClient, SPOT_WEBSOCKET_URL, SPOT_LISTEN_KEY_URL);
+define_market_client!(
+    BinanceInverseWSClient,
+    INVERSE_WEBSOCKET_URL,
+    INVERSE_LISTEN_KEY_URL
+);
+define_market_client!(
+    BinanceLinearWSClient,
+    LINEAR_WEBSOCKET_URL,
+    LINEAR_LISTEN_KEY_URL
+);
 
 macro_rules! impl_trade {
     ($struct_name:ident) => {
         impl Trade for $struct_name {
-            fn subscribe_trade(&self, pairs: &[String]) {
-                self.client.subscribe_trade(pairs);
+            fn subscribe_trade(&self, pairs: &[String]) -> Result<(), UnsupportedChannel> {
+                self.client.subscribe_trade(pairs)
             }
         }
     };
@@ -249,8 +416,8 @@ impl_trade!(BinanceLinearWSClient);
 macro_rules! impl_ticker {
     ($struct_name:ident) => {
         impl Ticker for $struct_name {
-            fn subscribe_ticker(&self, pairs: &[String]) {
-                self.client.subscribe_ticker(pairs);
+            fn subscribe_ticker(&self, pairs: &[String]) -> Result<(), UnsupportedChannel> {
+                self.client.subscribe_ticker(pairs)
             }
         }
     };
@@ -263,8 +430,8 @@ impl_ticker!(BinanceLinearWSClient);
 macro_rules! impl_bbo {
     ($struct_name:ident) => {
         impl BBO for $struct_name {
-            fn subscribe_bbo(&self, pairs: &[String]) {
-                self.client.subscribe_bbo(pairs);
+            fn subscribe_bbo(&self, pairs: &[String]) -> Result<(), UnsupportedChannel> {
+                self.client.subscribe_bbo(pairs)
             }
         }
     };
@@ -277,8 +444,8 @@ impl_bbo!(BinanceLinearWSClient);
 macro_rules! impl_orderbook {
     ($struct_name:ident) => {
         impl OrderBook for $struct_name {
-            fn subscribe_orderbook(&self, pairs: &[String]) {
-                self.client.subscribe_orderbook(pairs);
+            fn subscribe_orderbook(&self, pairs: &[String]) -> Result<(), UnsupportedChannel> {
+                self.client.subscribe_orderbook(pairs)
             }
         }
     };
@@ -291,8 +458,8 @@ impl_orderbook!(BinanceLinearWSClient);
 macro_rules! impl_orderbook_snapshot {
     ($struct_name:ident) => {
         impl OrderBookTopK for $struct_name {
-            fn subscribe_orderbook_topk(&self, pairs: &[String]) {
-                self.client.subscribe_orderbook_topk(pairs);
+            fn subscribe_orderbook_topk(&self, pairs: &[String]) -> Result<(), UnsupportedChannel> {
+                self.client.subscribe_orderbook_topk(pairs)
             }
         }
     };
@@ -305,8 +472,11 @@ impl_orderbook_snapshot!(BinanceLinearWSClient);
 macro_rules! impl_candlestick {
     ($struct_name:ident) => {
         impl Candlestick for $struct_name {
-            fn subscribe_candlestick(&self, symbol_interval_list: &[(String, usize)]) {
-                self.client.subscribe_candlestick(symbol_interval_list);
+            fn subscribe_candlestick(
+                &self,
+                symbol_interval_list: &[(String, Interval)],
+            ) -> Result<(), UnsupportedChannel> {
+                self.client.subscribe_candlestick(symbol_interval_list)
             }
         }
     };
@@ -316,12 +486,14 @@ impl_candlestick!(BinanceSpotWSClient);
 impl_candlestick!(BinanceInverseWSClient);
 impl_candlestick!(BinanceLinearWSClient);
 
-panic_l3_orderbook!(BinanceSpotWSClient);
-panic_l3_orderbook!(BinanceInverseWSClient);
-panic_l3_orderbook!(BinanceLinearWSClient);
+unsupported_l3_orderbook!(BinanceSpotWSClient);
+unsupported_l3_orderbook!(BinanceInverseWSClient);
+unsupported_l3_orderbook!(BinanceLinearWSClient);
 
 #[cfg(test)]
 mod tests {
+    use crate::{clients::mock_server::assert_unsupported, UnsupportedChannel, WSClient};
+
     #[test]
     fn test_one_channel() {
         let commands = super::BinanceWSClient::channels_to_commands(
@@ -347,4 +519,105 @@ mod tests {
             commands[0]
         );
     }
+
+    #[test]
+    fn test_2000_channels() {
+        let channels: Vec<String> = (0..2000)
+            .map(|i| format!("symbol{}usdt@aggTrade", i))
+            .collect();
+        let commands = super::BinanceWSClient::channels_to_commands(&channels, true);
+        assert!(commands.len() >= 10);
+        assert!(commands
+            .iter()
+            .all(|command| command.len() <= super::WS_FRAME_SIZE));
+
+        let commands = crate::clients::utils::split_oversized_commands(
+            &channels,
+            true,
+            super::BinanceWSClient::channels_to_commands,
+            1024,
+        );
+        assert!(commands.len() > 10);
+        assert!(commands.iter().all(|command| command.len() <= 1024));
+    }
+
+    // Serves one HTTP response on localhost, returns the URL and the request
+    fn serve_http(status: &str, body: &str) -> (String, std::thread::JoinHandle<String>) {
+        use std::io::{Read, Write};
+
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!(
+            "http://{}/api/v3/userDataStream",
+            listener.local_addr().unwrap()
+        );
+        let response = format!(
+            "HTTP/1.1 {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            status,
+            body.len(),
+            body
+        );
+        let handle = std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut buf = [0u8; 4096];
+            let n = stream.read(&mut buf).unwrap();
+            stream.write_all(response.as_bytes()).unwrap();
+            String::from_utf8_lossy(&buf[..n]).to_string()
+        });
+        (url, handle)
+    }
+
+    #[test]
+    fn test_create_listen_key() {
+        let (url, handle) = serve_http(
+            "200 OK",
+            r#"{"listenKey":"pqia91ma19a5s61cv6a81va65sdf19v8a65a1a5s61cv6a81va65sdf19v8a65a1"}"#,
+        );
+        assert_eq!(
+            "pqia91ma19a5s61cv6a81va65sdf19v8a65a1a5s61cv6a81va65sdf19v8a65a1",
+            super::create_listen_key(&url, "my-api-key").unwrap()
+        );
+        let request = handle.join().unwrap().to_lowercase();
+        assert!(request.starts_with("post /api/v3/userdatastream"));
+        assert!(request.contains("x-mbx-apikey: my-api-key"));
+    }
+
+    #[test]
+    fn test_create_listen_key_rejected() {
+        let (url, handle) = serve_http(
+            "401 Unauthorized",
+            r#"{"code":-2015,"msg":"Invalid API-key, IP, or permissions for action."}"#,
+        );
+        let err = super::create_listen_key(&url, "bad-api-key").unwrap_err();
+        assert!(matches!(err, crate::AuthError::Request(_)));
+        handle.join().unwrap();
+
+        let (url, handle) = serve_http("200 OK", r#"{"code":0}"#);
+        assert_eq!(
+            crate::AuthError::Request(r#"No listenKey in {"code":0}"#.to_string()),
+            super::create_listen_key(&url, "my-api-key").unwrap_err()
+        );
+        handle.join().unwrap();
+    }
+
+    #[test]
+    fn test_unsupported_channel() {
+        assert_unsupported(
+            |client: &super::BinanceSpotWSClient| {
+                client.subscribe_l3_orderbook(&["BTCUSDT".to_string()])
+            },
+            UnsupportedChannel::new(super::EXCHANGE_NAME, "level3 orderbook"),
+        );
+        assert_unsupported(
+            |client: &super::BinanceInverseWSClient| {
+                client.subscribe_l3_orderbook(&["BTCUSDT".to_string()])
+            },
+            UnsupportedChannel::new(super::EXCHANGE_NAME, "level3 orderbook"),
+        );
+        assert_unsupported(
+            |client: &super::BinanceLinearWSClient| {
+                client.subscribe_l3_orderbook(&["BTCUSDT".to_string()])
+            },
+            UnsupportedChannel::new(super::EXCHANGE_NAME, "level3 orderbook"),
+        );
+    }
 }
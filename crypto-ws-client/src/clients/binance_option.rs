@@ -1,12 +1,11 @@
-use crate::WSClient;
+use crate::{Interval, UnsupportedChannel, WSClient};
 use std::collections::HashMap;
 use std::sync::mpsc::Sender;
 
 use super::{
     ws_client_internal::{MiscMessage, WSClientInternal},
-    Candlestick, Level3OrderBook, OrderBook, OrderBookTopK, Ticker, Trade, BBO,
+    Authenticate, Candlestick, Level3OrderBook, OrderBook, OrderBookTopK, Ticker, Trade, BBO,
 };
-use log::*;
 use serde_json::Value;
 
 pub(super) const EXCHANGE_NAME: &str = "binance";
@@ -55,13 +54,13 @@ fn on_misc_msg(msg: &str) -> MiscMessage {
 
     let resp = serde_json::from_str::<HashMap<String, Value>>(msg);
     if resp.is_err() {
-        error!("{} is not a JSON string, {}", msg, EXCHANGE_NAME);
+        exchange_log!(EXCHANGE_NAME, Error, "{} is not a JSON string", msg);
         return MiscMessage::Misc;
     }
     let obj = resp.unwrap();
 
     if obj.contains_key("error") {
-        error!("Received {} from {}", msg, EXCHANGE_NAME);
+        exchange_log!(EXCHANGE_NAME, Error, "Received {}", msg);
         panic!("Received {} from {}", msg, EXCHANGE_NAME);
     }
 
@@ -72,7 +71,7 @@ fn on_misc_msg(msg: &str) -> MiscMessage {
     }
 
     if !obj.contains_key("stream") || !obj.contains_key("data") {
-        warn!("Received {} from {}", msg, EXCHANGE_NAME);
+        exchange_log!(EXCHANGE_NAME, Warn, "Received {}", msg);
         return MiscMessage::Misc;
     }
 
@@ -88,6 +87,7 @@ impl_new_constructor!(
     Some(CLIENT_PING_INTERVAL_AND_MSG),
     None
 );
+unsupported_authenticate!(BinanceOptionWSClient);
 impl_ws_client_trait!(BinanceOptionWSClient);
 
 fn to_raw_channel(channel: &str, pair: &str) -> String {
@@ -105,21 +105,39 @@ impl_trait!(OrderBook, BinanceOptionWSClient, subscribe_orderbook, "depth@100ms"
 #[rustfmt::skip]
 impl_trait!(OrderBookTopK, BinanceOptionWSClient, subscribe_orderbook_topk, "depth10", to_raw_channel);
 
-fn to_candlestick_raw_channel(symbol: &str, interval: usize) -> String {
+fn to_candlestick_raw_channel(
+    symbol: &str,
+    interval: Interval,
+) -> Result<String, UnsupportedChannel> {
     let interval_str = match interval {
-        60 => "1m",
-        300 => "5m",
-        900 => "15m",
-        1800 => "30m",
-        3600 => "1h",
-        14400 => "4h",
-        86400 => "1d",
-        604800 => "1w",
-        _ => panic!("Binance has intervals 1m,5m,15m,30m,1h4h,1d,1w"),
+        Interval::Min1 => "1m",
+        Interval::Min5 => "5m",
+        Interval::Min15 => "15m",
+        Interval::Min30 => "30m",
+        Interval::Hour1 => "1h",
+        Interval::Hour4 => "4h",
+        Interval::Day1 => "1d",
+        Interval::Week1 => "1w",
+        _ => return Err(UnsupportedChannel::interval(EXCHANGE_NAME, interval)),
     };
-    format!("{}@kline_{}", symbol, interval_str)
+    Ok(format!("{}@kline_{}", symbol, interval_str))
 }
 
 impl_candlestick!(BinanceOptionWSClient);
 
-panic_l3_orderbook!(BinanceOptionWSClient);
+unsupported_l3_orderbook!(BinanceOptionWSClient);
+
+#[cfg(test)]
+mod tests {
+    use crate::{clients::mock_server::assert_unsupported, UnsupportedChannel, WSClient};
+
+    #[test]
+    fn test_unsupported_channel() {
+        assert_unsupported(
+            |client: &super::BinanceOptionWSClient| {
+                client.subscribe_l3_orderbook(&["BTCUSDT".to_string()])
+            },
+            UnsupportedChannel::new(super::EXCHANGE_NAME, "level3 orderbook"),
+        );
+    }
+}
@@ -1,21 +1,23 @@
-use crate::WSClient;
+use crate::{
+    AuthError, FromUrls, Interval, ResetNotification, UnsupportedChannel, WSClient, WSClientStats,
+};
 
 use std::sync::{
-    atomic::{AtomicBool, Ordering},
+    atomic::{AtomicBool, AtomicUsize, Ordering},
     mpsc::Sender,
     Mutex,
 };
+use std::thread::ThreadId;
 use std::{
     collections::{HashMap, HashSet},
     time::{Duration, Instant},
 };
 
 use super::{
-    utils::{connect_with_retry, CHANNEL_PAIR_DELIMITER},
+    utils::{websocket_config, Endpoints, StalenessWatchdog, CHANNEL_PAIR_DELIMITER},
     Candlestick, Level3OrderBook, OrderBook, OrderBookTopK, Ticker, Trade, BBO,
 };
 
-use log::*;
 use serde_json::Value;
 use tungstenite::{client::AutoStream, error::ProtocolError, Error, Message, WebSocket};
 
@@ -34,11 +36,19 @@ const SERVER_PING_INTERVAL: u64 = 15;
 /// * Swap: <https://trading.bitfinex.com/t/BTCF0:USTF0>
 /// * Funding: <https://trading.bitfinex.com/funding>
 pub struct BitfinexWSClient {
+    endpoints: Endpoints, // Websocket base urls, tried in turn
     ws_stream: Mutex<WebSocket<AutoStream>>,
     channels: Mutex<HashSet<String>>, // subscribed channels
+    num_channels: AtomicUsize,        // channels.len(), readable without locking for logs
+    num_reconnects: AtomicUsize,      // reported by stats()
+    // raw channels collected by collect_channels() instead of being sent, per thread
+    collected_channels: Mutex<HashMap<ThreadId, Vec<String>>>,
     tx: Mutex<Sender<String>>,
     channel_id_meta: Mutex<HashMap<i64, String>>, // CHANNEL_ID information
+    watchdog: StalenessWatchdog,                  // calls on_stale when no data arrives
     should_stop: AtomicBool,                      // used by close() and run()
+    reset_tx: Mutex<Option<Sender<ResetNotification>>>, // see set_reset_on_reconnect()
+    max_frame_size: Mutex<Option<usize>>,         // see set_max_frame_size()
 }
 
 impl BitfinexWSClient {
@@ -48,14 +58,43 @@ impl BitfinexWSClient {
     ///
     /// * `on_msg` - A callback function to process websocket messages
     /// * `url` - Optional server url, usually you don't need specify it
-    pub fn new(tx: Sender<String>, _url: Option<&str>) -> Self {
-        let stream = connect_with_retry(WEBSOCKET_URL, Some(SERVER_PING_INTERVAL));
+    pub fn new(tx: Sender<String>, url: Option<&str>) -> Self {
+        let urls: Vec<String> = url.map(|url| vec![url.to_string()]).unwrap_or_default();
+        <Self as FromUrls>::from_urls(tx, &urls)
+    }
+
+    /// The websocket URL currently in use.
+    fn url(&self) -> &str {
+        self.endpoints.current()
+    }
+
+    fn num_channels(&self) -> usize {
+        self.num_channels.load(Ordering::Acquire)
+    }
+}
+
+impl FromUrls for BitfinexWSClient {
+    fn from_urls(tx: Sender<String>, urls: &[String]) -> Self {
+        let urls = if urls.is_empty() {
+            vec![WEBSOCKET_URL.to_string()]
+        } else {
+            urls.to_vec()
+        };
+        let endpoints = Endpoints::new(EXCHANGE_NAME, urls);
+        let stream = endpoints.connect(Some(SERVER_PING_INTERVAL), None);
         BitfinexWSClient {
+            endpoints,
             ws_stream: Mutex::new(stream),
             channels: Mutex::new(HashSet::new()),
+            num_channels: AtomicUsize::new(0),
+            num_reconnects: AtomicUsize::new(0),
+            collected_channels: Mutex::new(HashMap::new()),
             tx: Mutex::new(tx),
             channel_id_meta: Mutex::new(HashMap::new()),
+            watchdog: StalenessWatchdog::new(),
             should_stop: AtomicBool::new(false),
+            reset_tx: Mutex::new(None),
+            max_frame_size: Mutex::new(None),
         }
     }
 }
@@ -90,7 +129,7 @@ fn channels_to_commands(channels: &[String], subscribe: bool) -> Vec<String> {
 macro_rules! impl_trait_for_bitfinex {
     ($trait_name:ident, $method_name:ident, $channel_name:expr) => {
         impl $trait_name for BitfinexWSClient {
-            fn $method_name(&self, symbols: &[String]) {
+            fn $method_name(&self, symbols: &[String]) -> Result<(), UnsupportedChannel> {
                 let symbol_to_raw_channel =
                     |symbol: &String| format!("{}:{}", $channel_name, symbol);
 
@@ -99,6 +138,7 @@ macro_rules! impl_trait_for_bitfinex {
                     .map(symbol_to_raw_channel)
                     .collect::<Vec<String>>();
                 self.subscribe(&channels);
+                Ok(())
             }
         }
     };
@@ -108,7 +148,7 @@ impl_trait_for_bitfinex!(Trade, subscribe_trade, "trades");
 impl_trait_for_bitfinex!(Ticker, subscribe_ticker, "ticker");
 
 impl BBO for BitfinexWSClient {
-    fn subscribe_bbo(&self, symbols: &[String]) {
+    fn subscribe_bbo(&self, symbols: &[String]) -> Result<(), UnsupportedChannel> {
         let raw_channels = symbols
             .iter()
             .map(|symbol| {
@@ -126,11 +166,12 @@ impl BBO for BitfinexWSClient {
             .collect::<Vec<String>>();
 
         self.subscribe(&raw_channels);
+        Ok(())
     }
 }
 
 impl OrderBook for BitfinexWSClient {
-    fn subscribe_orderbook(&self, symbols: &[String]) {
+    fn subscribe_orderbook(&self, symbols: &[String]) -> Result<(), UnsupportedChannel> {
         let raw_channels = symbols
             .iter()
             .map(|symbol| {
@@ -149,17 +190,21 @@ impl OrderBook for BitfinexWSClient {
             .collect::<Vec<String>>();
 
         self.subscribe(&raw_channels);
+        Ok(())
     }
 }
 
 impl OrderBookTopK for BitfinexWSClient {
-    fn subscribe_orderbook_topk(&self, _symbols: &[String]) {
-        panic!("Bitfinex does NOT have orderbook snapshot channel");
+    fn subscribe_orderbook_topk(&self, _symbols: &[String]) -> Result<(), UnsupportedChannel> {
+        Err(UnsupportedChannel::new(
+            EXCHANGE_NAME,
+            "level2 top-k snapshot",
+        ))
     }
 }
 
 impl Level3OrderBook for BitfinexWSClient {
-    fn subscribe_l3_orderbook(&self, symbols: &[String]) {
+    fn subscribe_l3_orderbook(&self, symbols: &[String]) -> Result<(), UnsupportedChannel> {
         let raw_channels = symbols
             .iter()
             .map(|symbol| {
@@ -177,56 +222,101 @@ impl Level3OrderBook for BitfinexWSClient {
             .collect::<Vec<String>>();
 
         self.subscribe(&raw_channels);
+        Ok(())
     }
 }
 
-fn to_candlestick_raw_channel(symbol: &str, interval: usize) -> String {
+fn to_candlestick_raw_channel(
+    symbol: &str,
+    interval: Interval,
+) -> Result<String, UnsupportedChannel> {
     let interval_str = match interval {
-        60 => "1m",
-        300 => "5m",
-        900 => "15m",
-        1800 => "30m",
-        3600 => "1h",
-        10800 => "3h",
-        21600 => "6h",
-        43200 => "12h",
-        86400 => "1D",
-        604800 => "7D",
-        1209600 => "14D",
-        2592000 => "1M",
-        _ => panic!("Bitfinex has intervals 1m,5m,15m,30m,1h,3h,6h,12h,1D,7D,14D,1M"),
+        Interval::Min1 => "1m",
+        Interval::Min5 => "5m",
+        Interval::Min15 => "15m",
+        Interval::Min30 => "30m",
+        Interval::Hour1 => "1h",
+        Interval::Hour3 => "3h",
+        Interval::Hour6 => "6h",
+        Interval::Hour12 => "12h",
+        Interval::Day1 => "1D",
+        Interval::Week1 => "7D",
+        Interval::Week2 => "14D",
+        Interval::Month1 => "1M",
+        _ => return Err(UnsupportedChannel::interval(EXCHANGE_NAME, interval)),
     };
 
-    format!(
+    Ok(format!(
         r#"{{
             "event": "subscribe",
             "channel": "candles",
             "key": "trade:{}:{}"
         }}"#,
         interval_str, symbol
-    )
+    ))
 }
 
 impl Candlestick for BitfinexWSClient {
-    fn subscribe_candlestick(&self, symbol_interval_list: &[(String, usize)]) {
-        let raw_channels: Vec<String> = symbol_interval_list
+    fn subscribe_candlestick(
+        &self,
+        symbol_interval_list: &[(String, Interval)],
+    ) -> Result<(), UnsupportedChannel> {
+        let raw_channels = symbol_interval_list
             .iter()
             .map(|(symbol, interval)| to_candlestick_raw_channel(symbol, *interval))
-            .collect();
+            .collect::<Result<Vec<String>, UnsupportedChannel>>()?;
         self.subscribe(&raw_channels);
+        Ok(())
     }
 }
 
 impl BitfinexWSClient {
+    // Runs subscribe_fn without sending anything and returns the raw channels it would have subscribed to.
+    fn collect_channels<F: FnOnce() -> Result<(), UnsupportedChannel>>(
+        &self,
+        subscribe_fn: F,
+    ) -> Result<Vec<String>, UnsupportedChannel> {
+        let thread_id = std::thread::current().id();
+        self.collected_channels
+            .lock()
+            .unwrap()
+            .insert(thread_id, Vec::new());
+        let result = subscribe_fn();
+        let channels = self
+            .collected_channels
+            .lock()
+            .unwrap()
+            .remove(&thread_id)
+            .unwrap_or_default();
+        result.map(|_| channels)
+    }
+
     fn subscribe_or_unsubscribe(&self, channels: &[String], subscribe: bool) {
+        if subscribe {
+            if let Some(collected) = self
+                .collected_channels
+                .lock()
+                .unwrap()
+                .get_mut(&std::thread::current().id())
+            {
+                collected.extend_from_slice(channels);
+                return;
+            }
+        }
         let mut diff = Vec::<String>::new();
         {
             let mut guard = self.channels.lock().unwrap();
             for ch in channels.iter() {
-                if guard.insert(ch.clone()) {
+                let changed = if subscribe {
+                    guard.insert(ch.clone())
+                } else {
+                    guard.remove(ch)
+                };
+                if changed {
                     diff.push(ch.clone());
                 }
             }
+            self.num_channels.store(guard.len(), Ordering::Release);
         }
 
         if !diff.is_empty() {
@@ -235,19 +325,49 @@ impl BitfinexWSClient {
             commands.into_iter().for_each(|command| {
                 let ret = ws_stream.write_message(Message::Text(command));
                 if let Err(err) = ret {
-                    error!("{}", err);
+                    ws_log!(
+                        Error,
+                        EXCHANGE_NAME,
+                        self.url(),
+                        self.num_channels(),
+                        "{}",
+                        err
+                    );
                 }
             });
         }
     }
 
+    // Reconnects if set_reset_on_reconnect() is enabled, otherwise exits the process
+    fn reconnect_or_exit(&self) {
+        if self.reset_tx.lock().unwrap().is_some() {
+            self.reconnect();
+        } else {
+            std::thread::sleep(Duration::from_secs(5));
+            std::process::exit(1); // fail fast, pm2 will restart
+        }
+    }
+
     // reconnect and subscribe all channels
-    fn _reconnect(&self) {
-        warn!("Reconnecting to {}", WEBSOCKET_URL);
+    fn reconnect(&self) {
+        ws_log!(
+            Warn,
+            EXCHANGE_NAME,
+            self.url(),
+            self.num_channels(),
+            "Reconnecting to {}",
+            self.url()
+        );
+        self.num_reconnects.fetch_add(1, Ordering::AcqRel);
         {
             let mut guard = self.ws_stream.lock().unwrap();
-            *guard = connect_with_retry(WEBSOCKET_URL, Some(SERVER_PING_INTERVAL));
+            *guard = self.endpoints.connect(
+                Some(SERVER_PING_INTERVAL),
+                *self.max_frame_size.lock().unwrap(),
+            );
         }
+        // chanIds are assigned again by subscribed events of the new connection
+        self.channel_id_meta.lock().unwrap().clear();
 
         let channels = self
             .channels
@@ -256,13 +376,27 @@ impl BitfinexWSClient {
             .iter()
             .map(|s| s.to_string())
             .collect::<Vec<String>>();
+        // data of the new connection comes after the notification
+        if let Some(reset_tx) = self.reset_tx.lock().unwrap().as_ref() {
+            // the receiver may have been dropped if nobody handles resets
+            let _ = reset_tx.send(ResetNotification {
+                channels: channels.clone(),
+            });
+        }
         if !channels.is_empty() {
             let commands = channels_to_commands(&channels, true);
             let mut ws_stream = self.ws_stream.lock().unwrap();
             commands.into_iter().for_each(|command| {
                 let ret = ws_stream.write_message(Message::Text(command));
                 if let Err(err) = ret {
-                    error!("{}", err);
+                    ws_log!(
+                        Error,
+                        EXCHANGE_NAME,
+                        self.url(),
+                        self.num_channels(),
+                        "{}",
+                        err
+                    );
                 }
             });
         }
@@ -282,17 +416,38 @@ impl BitfinexWSClient {
                             // 10301: Already subscribed
                             // 10401: Not subscribed
                             // 10000: Unknown event
-                            warn!("{} from {}", txt, EXCHANGE_NAME);
+                            ws_log!(
+                                Warn,
+                                EXCHANGE_NAME,
+                                self.url(),
+                                self.num_channels(),
+                                "{}",
+                                txt
+                            );
                         }
                         10300 | 10400 | 10302 => {
                             // 10300, 10400:Subscription failed
                             // 10302: Unknown channel
                             // 10001: Unknown pair
                             // 10305: Reached limit of open channels
-                            error!("{} from {}", txt, EXCHANGE_NAME);
+                            ws_log!(
+                                Error,
+                                EXCHANGE_NAME,
+                                self.url(),
+                                self.num_channels(),
+                                "{}",
+                                txt
+                            );
                             panic!("{} from {}", txt, EXCHANGE_NAME);
                         }
-                        _ => warn!("{} from {}", txt, EXCHANGE_NAME),
+                        _ => ws_log!(
+                            Warn,
+                            EXCHANGE_NAME,
+                            self.url(),
+                            self.num_channels(),
+                            "{}",
+                            txt
+                        ),
                     }
                 }
                 "info" => {
@@ -315,9 +470,14 @@ impl BitfinexWSClient {
                         match code {
                             20051 => {
                                 // Stop/Restart Websocket Server (please reconnect)
-                                // self.reconnect();
-                                error!("Stop/Restart Websocket Server, exiting now...");
-                                std::process::exit(1); // fail fast, pm2 will restart
+                                ws_log!(
+                                    Error,
+                                    EXCHANGE_NAME,
+                                    self.url(),
+                                    self.num_channels(),
+                                    "Stop/Restart Websocket Server"
+                                );
+                                self.reconnect_or_exit();
                             }
                             20060 => {
                                 // Entering in Maintenance mode. Please pause any activity and resume
@@ -340,16 +500,44 @@ impl BitfinexWSClient {
                                 commands.into_iter().for_each(|command| {
                                     let ret = ws_stream.write_message(Message::Text(command));
                                     if let Err(err) = ret {
-                                        error!("{}", err);
+                                        ws_log!(
+                                            Error,
+                                            EXCHANGE_NAME,
+                                            self.url(),
+                                            self.num_channels(),
+                                            "{}",
+                                            err
+                                        );
                                     }
                                 });
                             }
-                            _ => info!("{} from {}", txt, EXCHANGE_NAME),
+                            _ => ws_log!(
+                                Info,
+                                EXCHANGE_NAME,
+                                self.url(),
+                                self.num_channels(),
+                                "{}",
+                                txt
+                            ),
                         }
                     }
                 }
-                "pong" => debug!("{} from {}", txt, EXCHANGE_NAME),
-                "conf" => warn!("{} from {}", txt, EXCHANGE_NAME),
+                "pong" => ws_log!(
+                    Debug,
+                    EXCHANGE_NAME,
+                    self.url(),
+                    self.num_channels(),
+                    "{}",
+                    txt
+                ),
+                "conf" => ws_log!(
+                    Warn,
+                    EXCHANGE_NAME,
+                    self.url(),
+                    self.num_channels(),
+                    "{}",
+                    txt
+                ),
                 "subscribed" => {
                     let chan_id = obj.get("chanId").unwrap().as_i64().unwrap();
                     obj.remove("event");
@@ -380,7 +568,14 @@ impl BitfinexWSClient {
                     .unwrap()
                     .write_message(Message::Text(r#"{"event":"ping"}"#.to_string()))
                 {
-                    error!("{}", err);
+                    ws_log!(
+                        Error,
+                        EXCHANGE_NAME,
+                        self.url(),
+                        self.num_channels(),
+                        "{}",
+                        err
+                    );
                 }
                 false
             } else {
@@ -405,32 +600,90 @@ impl BitfinexWSClient {
 }
 
 impl WSClient for BitfinexWSClient {
-    fn subscribe_trade(&self, channels: &[String]) {
-        <Self as Trade>::subscribe_trade(self, channels);
+    fn subscribe_trade(&self, channels: &[String]) -> Result<(), UnsupportedChannel> {
+        <Self as Trade>::subscribe_trade(self, channels)
+    }
+
+    fn subscribe_orderbook(&self, channels: &[String]) -> Result<(), UnsupportedChannel> {
+        <Self as OrderBook>::subscribe_orderbook(self, channels)
+    }
+
+    fn subscribe_orderbook_topk(&self, channels: &[String]) -> Result<(), UnsupportedChannel> {
+        <Self as OrderBookTopK>::subscribe_orderbook_topk(self, channels)
+    }
+
+    fn subscribe_l3_orderbook(&self, channels: &[String]) -> Result<(), UnsupportedChannel> {
+        <Self as Level3OrderBook>::subscribe_l3_orderbook(self, channels)
+    }
+
+    fn subscribe_ticker(&self, channels: &[String]) -> Result<(), UnsupportedChannel> {
+        <Self as Ticker>::subscribe_ticker(self, channels)
+    }
+
+    fn subscribe_bbo(&self, channels: &[String]) -> Result<(), UnsupportedChannel> {
+        <Self as BBO>::subscribe_bbo(self, channels)
     }
 
-    fn subscribe_orderbook(&self, channels: &[String]) {
-        <Self as OrderBook>::subscribe_orderbook(self, channels);
+    fn subscribe_candlestick(
+        &self,
+        symbol_interval_list: &[(String, Interval)],
+    ) -> Result<(), UnsupportedChannel> {
+        <Self as Candlestick>::subscribe_candlestick(self, symbol_interval_list)
     }
 
-    fn subscribe_orderbook_topk(&self, channels: &[String]) {
-        <Self as OrderBookTopK>::subscribe_orderbook_topk(self, channels);
+    fn unsubscribe_trade(&self, channels: &[String]) -> Result<(), UnsupportedChannel> {
+        let raw_channels =
+            self.collect_channels(|| <Self as Trade>::subscribe_trade(self, channels))?;
+        self.subscribe_or_unsubscribe(&raw_channels, false);
+        Ok(())
     }
 
-    fn subscribe_l3_orderbook(&self, channels: &[String]) {
-        <Self as Level3OrderBook>::subscribe_l3_orderbook(self, channels);
+    fn unsubscribe_orderbook(&self, channels: &[String]) -> Result<(), UnsupportedChannel> {
+        let raw_channels =
+            self.collect_channels(|| <Self as OrderBook>::subscribe_orderbook(self, channels))?;
+        self.subscribe_or_unsubscribe(&raw_channels, false);
+        Ok(())
     }
 
-    fn subscribe_ticker(&self, channels: &[String]) {
-        <Self as Ticker>::subscribe_ticker(self, channels);
+    fn unsubscribe_orderbook_topk(&self, channels: &[String]) -> Result<(), UnsupportedChannel> {
+        let raw_channels = self.collect_channels(|| {
+            <Self as OrderBookTopK>::subscribe_orderbook_topk(self, channels)
+        })?;
+        self.subscribe_or_unsubscribe(&raw_channels, false);
+        Ok(())
     }
 
-    fn subscribe_bbo(&self, channels: &[String]) {
-        <Self as BBO>::subscribe_bbo(self, channels);
+    fn unsubscribe_l3_orderbook(&self, channels: &[String]) -> Result<(), UnsupportedChannel> {
+        let raw_channels = self.collect_channels(|| {
+            <Self as Level3OrderBook>::subscribe_l3_orderbook(self, channels)
+        })?;
+        self.subscribe_or_unsubscribe(&raw_channels, false);
+        Ok(())
     }
 
-    fn subscribe_candlestick(&self, symbol_interval_list: &[(String, usize)]) {
-        <Self as Candlestick>::subscribe_candlestick(self, symbol_interval_list);
+    fn unsubscribe_ticker(&self, channels: &[String]) -> Result<(), UnsupportedChannel> {
+        let raw_channels =
+            self.collect_channels(|| <Self as Ticker>::subscribe_ticker(self, channels))?;
+        self.subscribe_or_unsubscribe(&raw_channels, false);
+        Ok(())
+    }
+
+    fn unsubscribe_bbo(&self, channels: &[String]) -> Result<(), UnsupportedChannel> {
+        let raw_channels =
+            self.collect_channels(|| <Self as BBO>::subscribe_bbo(self, channels))?;
+        self.subscribe_or_unsubscribe(&raw_channels, false);
+        Ok(())
+    }
+
+    fn unsubscribe_candlestick(
+        &self,
+        symbol_interval_list: &[(String, Interval)],
+    ) -> Result<(), UnsupportedChannel> {
+        let raw_channels = self.collect_channels(|| {
+            <Self as Candlestick>::subscribe_candlestick(self, symbol_interval_list)
+        })?;
+        self.subscribe_or_unsubscribe(&raw_channels, false);
+        Ok(())
     }
 
     fn subscribe(&self, channels: &[String]) {
@@ -441,6 +694,52 @@ impl WSClient for BitfinexWSClient {
         self.subscribe_or_unsubscribe(channels, false);
     }
 
+    fn set_on_stale(&self, threshold: u64, on_stale: Option<fn(&str)>) {
+        self.watchdog.set(Duration::from_secs(threshold), on_stale);
+    }
+
+    fn set_reset_on_reconnect(&self, reset_tx: Option<Sender<ResetNotification>>) {
+        *self.reset_tx.lock().unwrap() = reset_tx;
+    }
+
+    fn set_max_frame_size(&self, max_frame_size: Option<usize>) {
+        *self.max_frame_size.lock().unwrap() = max_frame_size;
+        let config = websocket_config(max_frame_size).unwrap_or_default();
+        self.ws_stream
+            .lock()
+            .unwrap()
+            .set_config(|current| *current = config);
+    }
+
+    fn authenticate(
+        &self,
+        _api_key: &str,
+        _secret: &str,
+        _passphrase: &str,
+    ) -> Result<(), AuthError> {
+        Err(AuthError::Unsupported(EXCHANGE_NAME.to_string()))
+    }
+
+    fn stats(&self) -> WSClientStats {
+        WSClientStats {
+            num_channels: self.num_channels(),
+            rejected_channels: Vec::new(),
+            num_reconnects: self.num_reconnects.load(Ordering::Acquire),
+        }
+    }
+
+    fn subscriptions(&self) -> Vec<String> {
+        let mut channels = self
+            .channels
+            .lock()
+            .unwrap()
+            .iter()
+            .cloned()
+            .collect::<Vec<String>>();
+        channels.sort();
+        channels
+    }
+
     fn run(&self, duration: Option<u64>) {
         let start_timstamp = Instant::now();
         let mut num_read_timeout = 0;
@@ -454,22 +753,47 @@ impl WSClient for BitfinexWSClient {
                         Message::Text(txt) => succeeded = self.handle_msg(&txt),
                         Message::Binary(_) => panic!("Unknown binary format from Bitfinex"),
                         Message::Ping(resp) => {
-                            info!(
+                            ws_log!(
+                                Info,
+                                EXCHANGE_NAME,
+                                self.url(),
+                                self.num_channels(),
                                 "Received a ping frame: {}",
                                 std::str::from_utf8(&resp).unwrap()
                             );
                             if let Err(err) = self.ws_stream.lock().unwrap().write_message(Message::Pong(resp)) {
-                                error!("{}", err);
+                                ws_log!(Error, EXCHANGE_NAME, self.url(), self.num_channels(), "{}", err);
                             }
                         }
                         Message::Pong(resp) => {
                             let tmp = std::str::from_utf8(&resp);
-                            warn!("Received a pong frame: {}", tmp.unwrap());
+                            ws_log!(
+                                Warn,
+                                EXCHANGE_NAME,
+                                self.url(),
+                                self.num_channels(),
+                                "Received a pong frame: {}",
+                                tmp.unwrap()
+                            );
                         }
                         Message::Close(resp) => {
                             match resp {
-                                Some(frame) => warn!("Received a Message::Close message with a CloseFrame: code: {}, reason: {}", frame.code, frame.reason),
-                                None => warn!("Received a close message without CloseFrame"),
+                                Some(frame) => ws_log!(
+                                    Warn,
+                                    EXCHANGE_NAME,
+                                    self.url(),
+                                    self.num_channels(),
+                                    "Received a Message::Close message with a CloseFrame: code: {}, reason: {}",
+                                    frame.code,
+                                    frame.reason
+                                ),
+                                None => ws_log!(
+                                    Warn,
+                                    EXCHANGE_NAME,
+                                    self.url(),
+                                    self.num_channels(),
+                                    "Received a close message without CloseFrame"
+                                ),
                             }
                         }
                     }
@@ -477,62 +801,121 @@ impl WSClient for BitfinexWSClient {
                 Err(err) => {
                     match err {
                         Error::ConnectionClosed => {
-                            error!("Server closed connection, exiting now...");
-                            // self.reconnect();
-                            std::thread::sleep(Duration::from_secs(5));
-                            std::process::exit(1); // fail fast, pm2 will restart
+                            ws_log!(
+                                Error,
+                                EXCHANGE_NAME,
+                                self.url(),
+                                self.num_channels(),
+                                "Server closed connection"
+                            );
+                            self.reconnect_or_exit();
                         }
                         Error::AlreadyClosed => {
-                            error!("Impossible to happen, fix the bug in the code");
+                            ws_log!(
+                                Error,
+                                EXCHANGE_NAME,
+                                self.url(),
+                                self.num_channels(),
+                                "Impossible to happen, fix the bug in the code"
+                            );
                             panic!("Impossible to happen, fix the bug in the code");
                         }
                         Error::Io(io_err) => {
                             if io_err.kind() == std::io::ErrorKind::WouldBlock {
-                                info!("read_message() timeout");
+                                ws_log!(
+                                    Info,
+                                    EXCHANGE_NAME,
+                                    self.url(),
+                                    self.num_channels(),
+                                    "read_message() timeout"
+                                );
                                 num_read_timeout += 1;
                             } else if io_err.kind() == std::io::ErrorKind::Interrupted {
                                 // ignore SIGHUP, which will be handled by reopen
-                                info!("Ignoring SIGHUP");
+                                ws_log!(
+                                    Info,
+                                    EXCHANGE_NAME,
+                                    self.url(),
+                                    self.num_channels(),
+                                    "Ignoring SIGHUP"
+                                );
                             } else {
-                                error!(
+                                ws_log!(
+                                    Error,
+                                    EXCHANGE_NAME,
+                                    self.url(),
+                                    self.num_channels(),
                                     "I/O error thrown from read_message(): {}, {:?}",
                                     io_err,
                                     io_err.kind()
                                 );
-                                // self.reconnect();
-                                std::thread::sleep(Duration::from_secs(5));
-                                std::process::exit(1); // fail fast, pm2 will restart
+                                self.reconnect_or_exit();
                             }
                         }
+                        Error::Capacity(capacity_err) => {
+                            ws_log!(
+                                Error,
+                                EXCHANGE_NAME,
+                                self.url(),
+                                self.num_channels(),
+                                "{}, raise the limit by set_max_frame_size()",
+                                capacity_err
+                            );
+                            self.reconnect_or_exit();
+                        }
                         Error::Protocol(protocol_err) => {
                             if protocol_err == ProtocolError::ResetWithoutClosingHandshake {
-                                error!("ResetWithoutClosingHandshake");
-                                // self.reconnect();
-                                std::thread::sleep(Duration::from_secs(5));
-                                std::process::exit(1); // fail fast, pm2 will restart
+                                ws_log!(
+                                    Error,
+                                    EXCHANGE_NAME,
+                                    self.url(),
+                                    self.num_channels(),
+                                    "ResetWithoutClosingHandshake"
+                                );
+                                self.reconnect_or_exit();
                             } else {
-                                error!(
+                                ws_log!(
+                                    Error,
+                                    EXCHANGE_NAME,
+                                    self.url(),
+                                    self.num_channels(),
                                     "Protocol error thrown from read_message(): {}",
                                     protocol_err
                                 );
                             }
                         }
                         _ => {
-                            error!("Error thrown from read_message(): {}", err);
+                            ws_log!(
+                                Error,
+                                EXCHANGE_NAME,
+                                self.url(),
+                                self.num_channels(),
+                                "Error thrown from read_message(): {}",
+                                err
+                            );
                             panic!("Error thrown from read_message(): {}", err);
                         }
                     }
                 }
             };
 
+            if succeeded {
+                self.watchdog.on_normal_msg();
+            }
+            self.watchdog.check(EXCHANGE_NAME, self.url());
+
             if num_read_timeout > 5 {
-                error!(
-                    "Exiting due to num_read_timeout: {}, duration: {} seconds",
+                ws_log!(
+                    Error,
+                    EXCHANGE_NAME,
+                    self.url(),
+                    self.num_channels(),
+                    "Too many read timeouts: {}, duration: {} seconds",
                     num_read_timeout,
                     start_timstamp.elapsed().as_secs()
                 );
-                std::thread::sleep(Duration::from_secs(5));
-                std::process::exit(1); // fail fast, pm2 will restart
+                num_read_timeout = 0;
+                self.reconnect_or_exit();
             }
 
             if let Some(seconds) = duration {
@@ -547,13 +930,22 @@ impl WSClient for BitfinexWSClient {
         self.should_stop.store(true, Ordering::Release);
         let ret = self.ws_stream.lock().unwrap().close(None);
         if let Err(err) = ret {
-            error!("{}", err);
+            ws_log!(
+                Error,
+                EXCHANGE_NAME,
+                self.url(),
+                self.num_channels(),
+                "{}",
+                err
+            );
         }
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use crate::{clients::mock_server::assert_unsupported, UnsupportedChannel, WSClient};
+
     #[test]
     fn test_spot_command() {
         assert_eq!(
@@ -579,4 +971,14 @@ mod tests {
             super::channel_to_command("trades:tBTCF0:USTF0", false)
         );
     }
+
+    #[test]
+    fn test_unsupported_channel() {
+        assert_unsupported(
+            |client: &super::BitfinexWSClient| {
+                client.subscribe_orderbook_topk(&["BTCUSDT".to_string()])
+            },
+            UnsupportedChannel::new(super::EXCHANGE_NAME, "level2 top-k snapshot"),
+        );
+    }
 }
@@ -1,12 +1,13 @@
-use crate::WSClient;
+use crate::{Interval, UnsupportedChannel, WSClient};
 use std::collections::HashMap;
 use std::sync::mpsc::Sender;
 
 use super::super::ws_client_internal::{MiscMessage, WSClientInternal};
-use super::super::{Candlestick, Level3OrderBook, OrderBook, OrderBookTopK, Ticker, Trade, BBO};
+use super::super::{
+    Authenticate, Candlestick, Level3OrderBook, OrderBook, OrderBookTopK, Ticker, Trade, BBO,
+};
 use crate::clients::utils::ensure_frame_size;
 
-use log::*;
 use serde_json::Value;
 
 const EXCHANGE_NAME: &str = "bitget";
@@ -54,16 +55,22 @@ fn on_misc_msg(msg: &str) -> MiscMessage {
     if obj.contains_key("event") {
         let event = obj.get("event").unwrap().as_str().unwrap();
         if event == "error" {
-            error!("Received {} from {}", msg, EXCHANGE_NAME);
+            exchange_log!(EXCHANGE_NAME, Error, "Received {}", msg);
             panic!("Received {} from {}", msg, EXCHANGE_NAME);
         } else {
-            info!("Received {} from {}", msg, EXCHANGE_NAME);
+            exchange_log!(EXCHANGE_NAME, Info, "Received {}", msg);
             MiscMessage::Misc
         }
     } else if obj.contains_key("table") && obj.contains_key("data") {
         if let Some(arr) = obj.get("data").unwrap().as_array() {
             if arr.is_empty() {
-                info!("data field is empty {} from {}", msg, EXCHANGE_NAME);
+                exchange_log!(
+                    EXCHANGE_NAME,
+                    Info,
+                    "data field is empty {} from {}",
+                    msg,
+                    EXCHANGE_NAME
+                );
                 MiscMessage::Misc
             } else {
                 MiscMessage::Normal
@@ -74,13 +81,13 @@ fn on_misc_msg(msg: &str) -> MiscMessage {
     } else if obj.contains_key("action") {
         let action = obj.get("action").unwrap().as_str().unwrap();
         if action == "ping" {
-            info!("Received {} from {}", msg, EXCHANGE_NAME);
+            exchange_log!(EXCHANGE_NAME, Info, "Received {}", msg);
         } else {
-            warn!("Received {} from {}", msg, EXCHANGE_NAME);
+            exchange_log!(EXCHANGE_NAME, Warn, "Received {}", msg);
         }
         MiscMessage::Misc
     } else {
-        warn!("Received {} from {}", msg, EXCHANGE_NAME);
+        exchange_log!(EXCHANGE_NAME, Warn, "Received {}", msg);
         MiscMessage::Misc
     }
 }
@@ -99,28 +106,35 @@ impl_trait!(OrderBook, BitgetSwapWSClient, subscribe_orderbook, "depth", to_raw_
 impl_trait!(Ticker, BitgetSwapWSClient, subscribe_ticker, "ticker", to_raw_channel);
 
 impl BBO for BitgetSwapWSClient {
-    fn subscribe_bbo(&self, _symbols: &[String]) {
-        panic!("Bitget does NOT have BBO channel");
+    fn subscribe_bbo(&self, _symbols: &[String]) -> Result<(), UnsupportedChannel> {
+        Err(UnsupportedChannel::new(EXCHANGE_NAME, "BBO"))
     }
 }
 
-fn to_candlestick_raw_channel(symbol: &str, interval: usize) -> String {
-    let valid_set: Vec<usize> = vec![60, 300, 900, 1800, 3600, 14400, 43200, 86400, 604800];
-    if !valid_set.contains(&interval) {
-        let joined = valid_set
-            .into_iter()
-            .map(|x| x.to_string())
-            .collect::<Vec<String>>()
-            .join(",");
-        panic!("Bitget available intervals: {}", joined);
+fn to_candlestick_raw_channel(
+    symbol: &str,
+    interval: Interval,
+) -> Result<String, UnsupportedChannel> {
+    match interval {
+        Interval::Min1
+        | Interval::Min5
+        | Interval::Min15
+        | Interval::Min30
+        | Interval::Hour1
+        | Interval::Hour4
+        | Interval::Hour12
+        | Interval::Day1
+        | Interval::Week1 => {
+            let channel = format!("candle{}s", interval.as_secs());
+            Ok(to_raw_channel(&channel, symbol))
+        }
+        _ => Err(UnsupportedChannel::interval(EXCHANGE_NAME, interval)),
     }
-    let channel = format!("candle{}s", interval);
-    to_raw_channel(&channel, symbol)
 }
 
 impl_candlestick!(BitgetSwapWSClient);
 
-panic_l3_orderbook!(BitgetSwapWSClient);
+unsupported_l3_orderbook!(BitgetSwapWSClient);
 
 impl_new_constructor!(
     BitgetSwapWSClient,
@@ -131,4 +145,18 @@ impl_new_constructor!(
     Some(CLIENT_PING_INTERVAL_AND_MSG),
     None
 );
+unsupported_authenticate!(BitgetSwapWSClient);
 impl_ws_client_trait!(BitgetSwapWSClient);
+
+#[cfg(test)]
+mod tests {
+    use crate::{clients::mock_server::assert_unsupported, UnsupportedChannel, WSClient};
+
+    #[test]
+    fn test_unsupported_channel() {
+        assert_unsupported(
+            |client: &super::BitgetSwapWSClient| client.subscribe_bbo(&["BTCUSDT".to_string()]),
+            UnsupportedChannel::new(super::EXCHANGE_NAME, "BBO"),
+        );
+    }
+}
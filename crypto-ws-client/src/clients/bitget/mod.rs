@@ -4,4 +4,13 @@ mod bitget_swap;
 // pub use bitget_spot::BitgetSpotWSClient;
 pub use bitget_swap::BitgetSwapWSClient;
 
+use super::registry::ExchangeSpec;
+use crate::Encoding;
+
 pub(super) const EXCHANGE_NAME: &str = "bitget";
+
+pub(super) const SPEC: ExchangeSpec = ExchangeSpec {
+    name: EXCHANGE_NAME,
+    encoding: Some(Encoding::Gzip),
+    ..ExchangeSpec::DEFAULT
+};
@@ -1,14 +1,13 @@
-use crate::WSClient;
+use crate::{Interval, UnsupportedChannel, WSClient};
 use std::collections::HashMap;
 use std::sync::mpsc::Sender;
 
 use super::{
     utils::CHANNEL_PAIR_DELIMITER,
     ws_client_internal::{MiscMessage, WSClientInternal},
-    Candlestick, Level3OrderBook, OrderBook, OrderBookTopK, Ticker, Trade, BBO,
+    Authenticate, Candlestick, Level3OrderBook, OrderBook, OrderBookTopK, Ticker, Trade, BBO,
 };
 
-use log::*;
 use serde_json::Value;
 
 pub(super) const EXCHANGE_NAME: &str = "bithumb";
@@ -68,12 +67,12 @@ fn on_misc_msg(msg: &str) -> MiscMessage {
             }
             7 => MiscMessage::Normal,
             _ => {
-                debug!("Received {} from {}", msg, EXCHANGE_NAME);
+                exchange_log!(EXCHANGE_NAME, Debug, "Received {}", msg);
                 MiscMessage::Misc
             }
         }
     } else {
-        error!("Received {} from {}", msg, EXCHANGE_NAME);
+        exchange_log!(EXCHANGE_NAME, Error, "Received {}", msg);
         panic!("Received {} from {}", msg, EXCHANGE_NAME);
     }
 }
@@ -90,24 +89,30 @@ impl_trait!(Ticker, BithumbWSClient, subscribe_ticker, "TICKER", to_raw_channel)
 impl_trait!(OrderBook, BithumbWSClient, subscribe_orderbook, "ORDERBOOK", to_raw_channel);
 
 impl BBO for BithumbWSClient {
-    fn subscribe_bbo(&self, _pairs: &[String]) {
-        panic!("bithumb WebSocket does NOT have BBO channel");
+    fn subscribe_bbo(&self, _pairs: &[String]) -> Result<(), UnsupportedChannel> {
+        Err(UnsupportedChannel::new(EXCHANGE_NAME, "BBO"))
     }
 }
 
 impl OrderBookTopK for BithumbWSClient {
-    fn subscribe_orderbook_topk(&self, _pairs: &[String]) {
-        panic!("bithumb does NOT have orderbook snapshot channel");
+    fn subscribe_orderbook_topk(&self, _pairs: &[String]) -> Result<(), UnsupportedChannel> {
+        Err(UnsupportedChannel::new(
+            EXCHANGE_NAME,
+            "level2 top-k snapshot",
+        ))
     }
 }
 
 impl Candlestick for BithumbWSClient {
-    fn subscribe_candlestick(&self, _symbol_interval_list: &[(String, usize)]) {
-        panic!("bithumb does NOT have candlestick channel");
+    fn subscribe_candlestick(
+        &self,
+        _symbol_interval_list: &[(String, Interval)],
+    ) -> Result<(), UnsupportedChannel> {
+        Err(UnsupportedChannel::new(EXCHANGE_NAME, "candlestick"))
     }
 }
 
-panic_l3_orderbook!(BithumbWSClient);
+unsupported_l3_orderbook!(BithumbWSClient);
 
 impl_new_constructor!(
     BithumbWSClient,
@@ -118,4 +123,18 @@ impl_new_constructor!(
     Some(CLIENT_PING_INTERVAL_AND_MSG),
     None
 );
+unsupported_authenticate!(BithumbWSClient);
 impl_ws_client_trait!(BithumbWSClient);
+
+#[cfg(test)]
+mod tests {
+    use crate::{clients::mock_server::assert_unsupported, UnsupportedChannel, WSClient};
+
+    #[test]
+    fn test_unsupported_channel() {
+        assert_unsupported(
+            |client: &super::BithumbWSClient| client.subscribe_bbo(&["BTCUSDT".to_string()]),
+            UnsupportedChannel::new(super::EXCHANGE_NAME, "BBO"),
+        );
+    }
+}
@@ -1,13 +1,12 @@
-use crate::WSClient;
+use crate::{Interval, UnsupportedChannel, WSClient};
 use std::sync::mpsc::Sender;
 use std::{collections::HashMap, time::Duration};
 
 use super::{
     utils::CHANNEL_PAIR_DELIMITER,
     ws_client_internal::{MiscMessage, WSClientInternal},
-    Candlestick, Level3OrderBook, OrderBook, OrderBookTopK, Ticker, Trade, BBO,
+    Authenticate, Candlestick, Level3OrderBook, OrderBook, OrderBookTopK, Ticker, Trade, BBO,
 };
-use log::*;
 use serde_json::Value;
 
 pub(super) const EXCHANGE_NAME: &str = "bitmex";
@@ -64,7 +63,7 @@ fn on_misc_msg(msg: &str) -> MiscMessage {
     }
     let resp = serde_json::from_str::<HashMap<String, Value>>(msg);
     if resp.is_err() {
-        error!("{} is not a JSON string, {}", msg, EXCHANGE_NAME);
+        exchange_log!(EXCHANGE_NAME, Error, "{} is not a JSON string", msg);
         return MiscMessage::Misc;
     }
     let obj = resp.unwrap();
@@ -76,28 +75,28 @@ fn on_misc_msg(msg: &str) -> MiscMessage {
         match code {
             // Rate limit exceeded
             429 => {
-                error!("Received {} from {}", msg, EXCHANGE_NAME);
+                exchange_log!(EXCHANGE_NAME, Error, "Received {}", msg);
                 std::thread::sleep(Duration::from_secs(3));
             }
             400 => {
                 if error_msg.starts_with("Unknown") {
                     panic!("Received {} from {}", msg, EXCHANGE_NAME);
                 } else if error_msg.starts_with("You are already subscribed to this topic") {
-                    info!("Received {} from {}", msg, EXCHANGE_NAME)
+                    exchange_log!(EXCHANGE_NAME, Info, "Received {}", msg)
                 } else {
-                    warn!("Received {} from {}", msg, EXCHANGE_NAME);
+                    exchange_log!(EXCHANGE_NAME, Warn, "Received {}", msg);
                 }
             }
-            _ => error!("Received {} from {}", msg, EXCHANGE_NAME),
+            _ => exchange_log!(EXCHANGE_NAME, Error, "Received {}", msg),
         }
         MiscMessage::Misc
     } else if obj.contains_key("success") || obj.contains_key("info") {
-        info!("Received {} from {}", msg, EXCHANGE_NAME);
+        exchange_log!(EXCHANGE_NAME, Info, "Received {}", msg);
         MiscMessage::Misc
     } else if obj.contains_key("table") && obj.contains_key("action") && obj.contains_key("data") {
         MiscMessage::Normal
     } else {
-        warn!("Received {} from {}", msg, EXCHANGE_NAME);
+        exchange_log!(EXCHANGE_NAME, Warn, "Received {}", msg);
         MiscMessage::Misc
     }
 }
@@ -116,25 +115,28 @@ impl_trait!(OrderBook, BitmexWSClient, subscribe_orderbook, "orderBookL2_25", to
 impl_trait!(OrderBookTopK, BitmexWSClient, subscribe_orderbook_topk, "orderBook10", to_raw_channel);
 
 impl Ticker for BitmexWSClient {
-    fn subscribe_ticker(&self, _pairs: &[String]) {
-        panic!("BitMEX WebSocket does NOT have ticker channel");
+    fn subscribe_ticker(&self, _pairs: &[String]) -> Result<(), UnsupportedChannel> {
+        Err(UnsupportedChannel::new(EXCHANGE_NAME, "ticker"))
     }
 }
 
-fn to_candlestick_raw_channel(pair: &str, interval: usize) -> String {
+fn to_candlestick_raw_channel(
+    pair: &str,
+    interval: Interval,
+) -> Result<String, UnsupportedChannel> {
     let interval_str = match interval {
-        60 => "1m",
-        300 => "5m",
-        3600 => "1h",
-        86400 => "1d",
-        _ => panic!("BitMEX has intervals 1m,5m,1h,1d"),
+        Interval::Min1 => "1m",
+        Interval::Min5 => "5m",
+        Interval::Hour1 => "1h",
+        Interval::Day1 => "1d",
+        _ => return Err(UnsupportedChannel::interval(EXCHANGE_NAME, interval)),
     };
-    format!("tradeBin{}:{}", interval_str, pair)
+    Ok(format!("tradeBin{}:{}", interval_str, pair))
 }
 
 impl_candlestick!(BitmexWSClient);
 
-panic_l3_orderbook!(BitmexWSClient);
+unsupported_l3_orderbook!(BitmexWSClient);
 
 impl_new_constructor!(
     BitmexWSClient,
@@ -145,10 +147,13 @@ impl_new_constructor!(
     Some(CLIENT_PING_INTERVAL_AND_MSG),
     None
 );
+unsupported_authenticate!(BitmexWSClient);
 impl_ws_client_trait!(BitmexWSClient);
 
 #[cfg(test)]
 mod tests {
+    use crate::{clients::mock_server::assert_unsupported, UnsupportedChannel, WSClient};
+
     #[test]
     fn test_one_channel() {
         let commands = super::channels_to_commands(&vec!["trade:XBTUSD".to_string()], true);
@@ -173,4 +178,35 @@ mod tests {
             commands[0]
         );
     }
+
+    #[test]
+    fn test_mixed_tables() {
+        // one command carries trade, orderBookL2 and funding of all symbols,
+        // split at MAX_CHANNELS_PER_COMMAND
+        let channels: Vec<String> = (0..8)
+            .flat_map(|i| {
+                vec![
+                    format!("trade:SYMBOL{}", i),
+                    format!("orderBookL2:SYMBOL{}", i),
+                    format!("funding:SYMBOL{}", i),
+                ]
+            })
+            .collect();
+        let commands = super::channels_to_commands(&channels, true);
+        assert_eq!(2, commands.len());
+        assert!(commands[0].starts_with(
+            r#"{"op":"subscribe","args":["trade:SYMBOL0","orderBookL2:SYMBOL0","funding:SYMBOL0","#
+        ));
+        assert!(
+            commands[1].ends_with(r#""trade:SYMBOL7","orderBookL2:SYMBOL7","funding:SYMBOL7"]}"#)
+        );
+    }
+
+    #[test]
+    fn test_unsupported_channel() {
+        assert_unsupported(
+            |client: &super::BitmexWSClient| client.subscribe_ticker(&["BTCUSDT".to_string()]),
+            UnsupportedChannel::new(super::EXCHANGE_NAME, "ticker"),
+        );
+    }
 }
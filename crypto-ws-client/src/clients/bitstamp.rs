@@ -1,10 +1,11 @@
-use crate::WSClient;
+use crate::{Interval, UnsupportedChannel, WSClient};
 use std::collections::HashMap;
 use std::sync::mpsc::Sender;
 
 use super::ws_client_internal::{MiscMessage, WSClientInternal};
-use super::{Candlestick, Level3OrderBook, OrderBook, OrderBookTopK, Ticker, Trade, BBO};
-use log::*;
+use super::{
+    Authenticate, Candlestick, Level3OrderBook, OrderBook, OrderBookTopK, Ticker, Trade, BBO,
+};
 use serde_json::Value;
 
 pub(super) const EXCHANGE_NAME: &str = "bitstamp";
@@ -49,23 +50,38 @@ fn channels_to_commands(channels: &[String], subscribe: bool) -> Vec<String> {
 fn on_misc_msg(msg: &str) -> MiscMessage {
     let resp = serde_json::from_str::<HashMap<String, Value>>(msg);
     if resp.is_err() {
-        error!("{} is not a JSON string, {}", msg, EXCHANGE_NAME);
+        exchange_log!(EXCHANGE_NAME, Error, "{} is not a JSON string", msg);
         return MiscMessage::Misc;
     }
     let obj = resp.unwrap();
 
-    let event = obj.get("event").unwrap().as_str().unwrap();
+    // Data messages are recognized by their own event, regardless of whether
+    // the subscription has been acknowledged, since Bitstamp may stream
+    // trades before bts:subscription_succeeded
+    let event = match obj.get("event").and_then(|x| x.as_str()) {
+        Some(event) => event,
+        None => {
+            exchange_log!(EXCHANGE_NAME, Warn, "Received {} without event", msg);
+            return if obj.contains_key("data") {
+                MiscMessage::Normal
+            } else {
+                MiscMessage::Misc
+            };
+        }
+    };
     match event {
         "bts:subscription_succeeded" | "bts:unsubscription_succeeded" | "bts:heartbeat" => {
-            debug!("Received {} from {}", msg, EXCHANGE_NAME);
+            exchange_log!(EXCHANGE_NAME, Debug, "Received {}", msg);
             MiscMessage::Misc
         }
         "bts:error" => {
-            error!("Received {} from {}", msg, EXCHANGE_NAME);
+            exchange_log!(EXCHANGE_NAME, Error, "Received {}", msg);
             panic!("Received {} from {}", msg, EXCHANGE_NAME);
         }
         "bts:request_reconnect" => {
-            warn!(
+            exchange_log!(
+                EXCHANGE_NAME,
+                Warn,
                 "Received {}, which means Bitstamp is under maintenance",
                 msg
             );
@@ -90,20 +106,23 @@ impl_trait!(OrderBookTopK, BitstampWSClient, subscribe_orderbook_topk, "order_bo
 impl_trait!(Level3OrderBook, BitstampWSClient, subscribe_l3_orderbook, "live_orders", to_raw_channel);
 
 impl Ticker for BitstampWSClient {
-    fn subscribe_ticker(&self, _pairs: &[String]) {
-        panic!("Bitstamp WebSocket does NOT have ticker channel");
+    fn subscribe_ticker(&self, _pairs: &[String]) -> Result<(), UnsupportedChannel> {
+        Err(UnsupportedChannel::new(EXCHANGE_NAME, "ticker"))
     }
 }
 
 impl BBO for BitstampWSClient {
-    fn subscribe_bbo(&self, _pairs: &[String]) {
-        panic!("Bitstamp WebSocket does NOT have BBO channel");
+    fn subscribe_bbo(&self, _pairs: &[String]) -> Result<(), UnsupportedChannel> {
+        Err(UnsupportedChannel::new(EXCHANGE_NAME, "BBO"))
     }
 }
 
 impl Candlestick for BitstampWSClient {
-    fn subscribe_candlestick(&self, _symbol_interval_list: &[(String, usize)]) {
-        panic!("Bitstamp does NOT have candlestick channel");
+    fn subscribe_candlestick(
+        &self,
+        _symbol_interval_list: &[(String, Interval)],
+    ) -> Result<(), UnsupportedChannel> {
+        Err(UnsupportedChannel::new(EXCHANGE_NAME, "candlestick"))
     }
 }
 
@@ -116,10 +135,15 @@ impl_new_constructor!(
     Some(CLIENT_PING_INTERVAL_AND_MSG),
     None
 );
+unsupported_authenticate!(BitstampWSClient);
 impl_ws_client_trait!(BitstampWSClient);
 
 #[cfg(test)]
 mod tests {
+    use super::super::mock_server::MockClient;
+    use crate::{clients::mock_server::assert_unsupported, UnsupportedChannel, WSClient};
+    use tungstenite::Message;
+
     #[test]
     fn test_channel_to_command() {
         assert_eq!(
@@ -132,4 +156,35 @@ mod tests {
             super::channel_to_command("live_trades_btcusd", false)
         );
     }
+
+    #[test]
+    fn test_trade_before_ack() {
+        let trade = r#"{"data":{"id":162656882,"timestamp":"1616243040","amount":0.00281,"amount_str":"0.00281","price":58700.57,"price_str":"58700.57","type":0,"microtimestamp":"1616243040458000","buy_order_id":1335446233862144,"sell_order_id":1335446209277952},"channel":"live_trades_btcusd","event":"trade"}"#;
+        let mock = MockClient::start(
+            super::EXCHANGE_NAME,
+            super::on_misc_msg,
+            super::channels_to_commands,
+            None,
+            vec![vec![
+                Message::Text(trade.to_string()),
+                Message::Text(
+                    r#"{"event":"bts:subscription_succeeded","channel":"live_trades_btcusd","data":{}}"#
+                        .to_string(),
+                ),
+            ]],
+        );
+        mock.client.subscribe(&["live_trades_btcusd".to_string()]);
+        mock.client.run(Some(0));
+        let (msgs, _) = mock.finish();
+
+        assert_eq!(vec![trade.to_string()], msgs);
+    }
+
+    #[test]
+    fn test_unsupported_channel() {
+        assert_unsupported(
+            |client: &super::BitstampWSClient| client.subscribe_ticker(&["BTCUSDT".to_string()]),
+            UnsupportedChannel::new(super::EXCHANGE_NAME, "ticker"),
+        );
+    }
 }
@@ -1,12 +1,13 @@
-use crate::{clients::utils::CHANNEL_PAIR_DELIMITER, WSClient};
+use crate::{clients::utils::CHANNEL_PAIR_DELIMITER, Interval, UnsupportedChannel, WSClient};
 use std::collections::HashMap;
 use std::sync::mpsc::Sender;
 use std::time::{SystemTime, UNIX_EPOCH};
 
 use super::super::ws_client_internal::{MiscMessage, WSClientInternal};
-use super::super::{Candlestick, Level3OrderBook, OrderBook, OrderBookTopK, Ticker, Trade, BBO};
+use super::super::{
+    Authenticate, Candlestick, Level3OrderBook, OrderBook, OrderBookTopK, Ticker, Trade, BBO,
+};
 
-use log::*;
 use serde_json::Value;
 
 const EXCHANGE_NAME: &str = "bitz";
@@ -84,22 +85,37 @@ fn on_misc_msg(msg: &str) -> MiscMessage {
         // see https://apidocv2.bitz.plus/en/#error
         match status {
             -101001 => {
-                error!("Subscription type parameter error: {}", msg);
+                exchange_log!(
+                    EXCHANGE_NAME,
+                    Error,
+                    "Subscription type parameter error: {}",
+                    msg
+                );
                 panic!("Subscription type parameter error: {}", msg);
             }
             -101002 => {
-                error!("Fail to get subscribed symbol of trading pair: {}", msg);
+                exchange_log!(
+                    EXCHANGE_NAME,
+                    Error,
+                    "Fail to get subscribed symbol of trading pair: {}",
+                    msg
+                );
                 panic!("Fail to get subscribed symbol of trading pair: {}", msg);
             }
             -101003 => {
-                error!("k-line scale resolution error: {}", msg);
+                exchange_log!(
+                    EXCHANGE_NAME,
+                    Error,
+                    "k-line scale resolution error: {}",
+                    msg
+                );
                 panic!("k-line scale resolution error: {}", msg);
             }
-            _ => warn!("Received {} from {}", msg, EXCHANGE_NAME),
+            _ => exchange_log!(EXCHANGE_NAME, Warn, "Received {}", msg),
         }
         MiscMessage::Misc
     } else {
-        warn!("Received {} from {}", msg, EXCHANGE_NAME);
+        exchange_log!(EXCHANGE_NAME, Warn, "Received {}", msg);
         MiscMessage::Misc
     }
 }
@@ -116,34 +132,38 @@ impl_trait!(OrderBook, BitzSpotWSClient, subscribe_orderbook, "depth", to_raw_ch
 impl_trait!(Ticker, BitzSpotWSClient, subscribe_ticker, "market", to_raw_channel);
 
 impl BBO for BitzSpotWSClient {
-    fn subscribe_bbo(&self, _pairs: &[String]) {
-        panic!("Bitz does NOT have BBO channel");
+    fn subscribe_bbo(&self, _pairs: &[String]) -> Result<(), UnsupportedChannel> {
+        Err(UnsupportedChannel::new(EXCHANGE_NAME, "BBO"))
     }
 }
 
 impl OrderBookTopK for BitzSpotWSClient {
-    fn subscribe_orderbook_topk(&self, _pairs: &[String]) {
-        panic!("Bitz does NOT have orderbook snapshot channel");
+    fn subscribe_orderbook_topk(&self, _pairs: &[String]) -> Result<(), UnsupportedChannel> {
+        Err(UnsupportedChannel::new(
+            EXCHANGE_NAME,
+            "level2 top-k snapshot",
+        ))
     }
 }
 
-fn to_candlestick_raw_channel(symbol: &str, interval: usize) -> String {
+fn to_candlestick_raw_channel(
+    symbol: &str,
+    interval: Interval,
+) -> Result<String, UnsupportedChannel> {
     let interval_str = match interval {
-        60 => "1min",
-        300 => "5min",
-        900 => "15min",
-        1800 => "30min",
-        3600 => "60min",
-        14400 => "4hour",
-        86400 => "1day",
-        432000 => "5day",
-        604800 => "1week",
-        2592000 => "1mon",
-        _ => panic!(
-            "Bitz available intervals 1min,5min,15min,30min,60min,4hour,1day,5day,1week,1mon"
-        ),
+        Interval::Min1 => "1min",
+        Interval::Min5 => "5min",
+        Interval::Min15 => "15min",
+        Interval::Min30 => "30min",
+        Interval::Hour1 => "60min",
+        Interval::Hour4 => "4hour",
+        Interval::Day1 => "1day",
+        Interval::Day5 => "5day",
+        Interval::Week1 => "1week",
+        Interval::Month1 => "1mon",
+        _ => return Err(UnsupportedChannel::interval(EXCHANGE_NAME, interval)),
     };
-    format!(
+    Ok(format!(
         r#"{{"action":"Topic.sub", "data":{{"symbol":"{}", "type":"kline", "resolution":"{}", "_CDID":"100002", "dataType":"1"}}, "msg_id":{}}}"#,
         symbol,
         interval_str,
@@ -151,12 +171,12 @@ fn to_candlestick_raw_channel(symbol: &str, interval: usize) -> String {
             .duration_since(UNIX_EPOCH)
             .unwrap()
             .as_millis(),
-    )
+    ))
 }
 
 impl_candlestick!(BitzSpotWSClient);
 
-panic_l3_orderbook!(BitzSpotWSClient);
+unsupported_l3_orderbook!(BitzSpotWSClient);
 
 impl_new_constructor!(
     BitzSpotWSClient,
@@ -167,4 +187,18 @@ impl_new_constructor!(
     Some(CLIENT_PING_INTERVAL_AND_MSG),
     None
 );
+unsupported_authenticate!(BitzSpotWSClient);
 impl_ws_client_trait!(BitzSpotWSClient);
+
+#[cfg(test)]
+mod tests {
+    use crate::{clients::mock_server::assert_unsupported, UnsupportedChannel, WSClient};
+
+    #[test]
+    fn test_unsupported_channel() {
+        assert_unsupported(
+            |client: &super::BitzSpotWSClient| client.subscribe_bbo(&["BTCUSDT".to_string()]),
+            UnsupportedChannel::new(super::EXCHANGE_NAME, "BBO"),
+        );
+    }
+}
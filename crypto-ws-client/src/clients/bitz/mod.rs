@@ -4,4 +4,13 @@ mod bitz_spot;
 pub use bitz_spot::BitzSpotWSClient;
 // pub use bitz_swap::BitzSwapWSClient;
 
+use super::registry::ExchangeSpec;
+use crate::Encoding;
+
 pub(super) const EXCHANGE_NAME: &str = "bitz";
+
+pub(super) const SPEC: ExchangeSpec = ExchangeSpec {
+    name: EXCHANGE_NAME,
+    encoding: Some(Encoding::Gzip),
+    ..ExchangeSpec::DEFAULT
+};
@@ -1,8 +1,10 @@
-use crate::WSClient;
+use crate::{Interval, UnsupportedChannel, WSClient};
 use std::sync::mpsc::Sender;
 
 use super::super::ws_client_internal::WSClientInternal;
-use super::super::{Candlestick, Level3OrderBook, OrderBook, OrderBookTopK, Ticker, Trade, BBO};
+use super::super::{
+    Authenticate, Candlestick, Level3OrderBook, OrderBook, OrderBookTopK, Ticker, Trade, BBO,
+};
 use super::utils::{
     channels_to_commands, on_misc_msg, to_raw_channel, CLIENT_PING_INTERVAL_AND_MSG, EXCHANGE_NAME,
 };
@@ -27,33 +29,36 @@ impl_trait!(OrderBook, BybitInverseSwapWSClient, subscribe_orderbook, "orderBook
 impl_trait!(Ticker, BybitInverseSwapWSClient, subscribe_ticker, "instrument_info.100ms", to_raw_channel);
 
 impl BBO for BybitInverseSwapWSClient {
-    fn subscribe_bbo(&self, _symbols: &[String]) {
-        panic!("bybit does NOT have BBO channel");
+    fn subscribe_bbo(&self, _symbols: &[String]) -> Result<(), UnsupportedChannel> {
+        Err(UnsupportedChannel::new(EXCHANGE_NAME, "BBO"))
     }
 }
 
-fn to_candlestick_raw_channel(symbol: &str, interval: usize) -> String {
+fn to_candlestick_raw_channel(
+    symbol: &str,
+    interval: Interval,
+) -> Result<String, UnsupportedChannel> {
     let interval_str = match interval {
-        60 => "1",
-        180 => "3",
-        300 => "5",
-        900 => "15",
-        1800 => "30",
-        3600 => "60",
-        7200 => "120",
-        14400 => "240",
-        21600 => "360",
-        86400 => "D",
-        604800 => "W",
-        2592000 => "M",
-        _ => panic!("Huobi has intervals 1min,5min,15min,30min,60min,4hour,1day,1week,1mon"),
+        Interval::Min1 => "1",
+        Interval::Min3 => "3",
+        Interval::Min5 => "5",
+        Interval::Min15 => "15",
+        Interval::Min30 => "30",
+        Interval::Hour1 => "60",
+        Interval::Hour2 => "120",
+        Interval::Hour4 => "240",
+        Interval::Hour6 => "360",
+        Interval::Day1 => "D",
+        Interval::Week1 => "W",
+        Interval::Month1 => "M",
+        _ => return Err(UnsupportedChannel::interval(EXCHANGE_NAME, interval)),
     };
-    format!("klineV2.{}.{}", interval_str, symbol)
+    Ok(format!("klineV2.{}.{}", interval_str, symbol))
 }
 
 impl_candlestick!(BybitInverseSwapWSClient);
 
-panic_l3_orderbook!(BybitInverseSwapWSClient);
+unsupported_l3_orderbook!(BybitInverseSwapWSClient);
 
 impl_new_constructor!(
     BybitInverseSwapWSClient,
@@ -64,4 +69,20 @@ impl_new_constructor!(
     Some(CLIENT_PING_INTERVAL_AND_MSG),
     None
 );
+unsupported_authenticate!(BybitInverseSwapWSClient);
 impl_ws_client_trait!(BybitInverseSwapWSClient);
+
+#[cfg(test)]
+mod tests {
+    use crate::{clients::mock_server::assert_unsupported, UnsupportedChannel, WSClient};
+
+    #[test]
+    fn test_unsupported_channel() {
+        assert_unsupported(
+            |client: &super::BybitInverseSwapWSClient| {
+                client.subscribe_bbo(&["BTCUSDT".to_string()])
+            },
+            UnsupportedChannel::new(super::EXCHANGE_NAME, "BBO"),
+        );
+    }
+}
@@ -1,6 +1,5 @@
 use std::collections::HashMap;
 
-use log::*;
 use serde_json::Value;
 
 use crate::clients::ws_client_internal::MiscMessage;
@@ -43,18 +42,18 @@ pub(super) fn on_misc_msg(msg: &str) -> MiscMessage {
     } else {
         if obj.contains_key("success") {
             if obj.get("success").unwrap().as_bool().unwrap() {
-                info!("Received {} from {}", msg, EXCHANGE_NAME);
+                exchange_log!(EXCHANGE_NAME, Info, "Received {}", msg);
                 if obj.contains_key("ret_msg")
                     && obj.get("ret_msg").unwrap().as_str().unwrap() == "pong"
                 {
                     return MiscMessage::Pong;
                 }
             } else {
-                error!("Received {} from {}", msg, EXCHANGE_NAME);
+                exchange_log!(EXCHANGE_NAME, Error, "Received {}", msg);
                 panic!("Received {} from {}", msg, EXCHANGE_NAME);
             }
         } else {
-            warn!("Received {} from {}", msg, EXCHANGE_NAME);
+            exchange_log!(EXCHANGE_NAME, Warn, "Received {}", msg);
         }
         MiscMessage::Misc
     }
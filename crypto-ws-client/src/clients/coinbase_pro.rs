@@ -1,14 +1,13 @@
-use crate::WSClient;
+use crate::{Interval, UnsupportedChannel, WSClient};
 use std::collections::HashMap;
 use std::sync::mpsc::Sender;
 
 use super::{
     utils::CHANNEL_PAIR_DELIMITER,
     ws_client_internal::{MiscMessage, WSClientInternal},
-    Candlestick, Level3OrderBook, OrderBook, OrderBookTopK, Ticker, Trade, BBO,
+    Authenticate, Candlestick, Level3OrderBook, OrderBook, OrderBookTopK, Ticker, Trade, BBO,
 };
 
-use log::*;
 use serde_json::Value;
 
 pub(super) const EXCHANGE_NAME: &str = "coinbase_pro";
@@ -82,14 +81,14 @@ fn channels_to_commands(channels: &[String], subscribe: bool) -> Vec<String> {
 fn on_misc_msg(msg: &str) -> MiscMessage {
     let resp = serde_json::from_str::<HashMap<String, Value>>(msg);
     if resp.is_err() {
-        error!("{} is not a JSON string, {}", msg, EXCHANGE_NAME);
+        exchange_log!(EXCHANGE_NAME, Error, "{} is not a JSON string", msg);
         return MiscMessage::Misc;
     }
     let obj = resp.unwrap();
 
     match obj.get("type").unwrap().as_str().unwrap() {
         "error" => {
-            error!("Received {} from {}", msg, EXCHANGE_NAME);
+            exchange_log!(EXCHANGE_NAME, Error, "Received {}", msg);
             if obj.contains_key("reason")
                 && obj
                     .get("reason")
@@ -104,11 +103,11 @@ fn on_misc_msg(msg: &str) -> MiscMessage {
             }
         }
         "subscriptions" => {
-            info!("Received {} from {}", msg, EXCHANGE_NAME);
+            exchange_log!(EXCHANGE_NAME, Info, "Received {}", msg);
             MiscMessage::Misc
         }
         "heartbeat" => {
-            debug!("Received {} from {}", msg, EXCHANGE_NAME);
+            exchange_log!(EXCHANGE_NAME, Debug, "Received {}", msg);
             MiscMessage::Misc
         }
         _ => MiscMessage::Normal,
@@ -127,30 +126,37 @@ impl_trait!(Ticker, CoinbaseProWSClient, subscribe_ticker, "ticker", to_raw_chan
 impl_trait!(OrderBook, CoinbaseProWSClient, subscribe_orderbook, "level2", to_raw_channel);
 
 impl BBO for CoinbaseProWSClient {
-    fn subscribe_bbo(&self, _pairs: &[String]) {
-        panic!("CoinbasePro WebSocket does NOT have BBO channel");
+    fn subscribe_bbo(&self, _pairs: &[String]) -> Result<(), UnsupportedChannel> {
+        Err(UnsupportedChannel::new(EXCHANGE_NAME, "BBO"))
     }
 }
 
 impl OrderBookTopK for CoinbaseProWSClient {
-    fn subscribe_orderbook_topk(&self, _pairs: &[String]) {
-        panic!("CoinbasePro does NOT have orderbook snapshot channel");
+    fn subscribe_orderbook_topk(&self, _pairs: &[String]) -> Result<(), UnsupportedChannel> {
+        Err(UnsupportedChannel::new(
+            EXCHANGE_NAME,
+            "level2 top-k snapshot",
+        ))
     }
 }
 
 impl Candlestick for CoinbaseProWSClient {
-    fn subscribe_candlestick(&self, _symbol_interval_list: &[(String, usize)]) {
-        panic!("CoinbasePro does NOT have candlestick channel");
+    fn subscribe_candlestick(
+        &self,
+        _symbol_interval_list: &[(String, Interval)],
+    ) -> Result<(), UnsupportedChannel> {
+        Err(UnsupportedChannel::new(EXCHANGE_NAME, "candlestick"))
     }
 }
 
 impl Level3OrderBook for CoinbaseProWSClient {
-    fn subscribe_l3_orderbook(&self, symbols: &[String]) {
+    fn subscribe_l3_orderbook(&self, symbols: &[String]) -> Result<(), UnsupportedChannel> {
         let raw_channels: Vec<String> = symbols
             .iter()
             .map(|symbol| to_raw_channel("full", symbol))
             .collect();
         self.client.subscribe(&raw_channels);
+        Ok(())
     }
 }
 
@@ -163,10 +169,27 @@ impl_new_constructor!(
     None,
     None
 );
+unsupported_authenticate!(CoinbaseProWSClient);
 impl_ws_client_trait!(CoinbaseProWSClient);
 
+impl CoinbaseProWSClient {
+    /// Subscribes to the `level2_batch` channel.
+    ///
+    /// It pushes the same `l2update` messages as `subscribe_orderbook()`, but
+    /// batched every 50 milliseconds, which takes far less bandwidth.
+    pub fn subscribe_orderbook_batch(&self, pairs: &[String]) {
+        let raw_channels: Vec<String> = pairs
+            .iter()
+            .map(|pair| to_raw_channel("level2_batch", pair))
+            .collect();
+        self.client.subscribe(&raw_channels);
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    use crate::{clients::mock_server::assert_unsupported, UnsupportedChannel, WSClient};
+
     #[test]
     fn test_two_pairs() {
         assert_eq!(
@@ -177,4 +200,27 @@ mod tests {
             )
         );
     }
+
+    #[test]
+    fn test_level2_batch() {
+        let commands = super::channels_to_commands(
+            &[
+                super::to_raw_channel("level2_batch", "BTC-USD"),
+                super::to_raw_channel("level2_batch", "ETH-USD"),
+            ],
+            true,
+        );
+        assert_eq!(1, commands.len());
+        assert!(
+            commands[0].contains(r#"{"name":"level2_batch","product_ids":["BTC-USD","ETH-USD"]}"#)
+        );
+    }
+
+    #[test]
+    fn test_unsupported_channel() {
+        assert_unsupported(
+            |client: &super::CoinbaseProWSClient| client.subscribe_bbo(&["BTCUSDT".to_string()]),
+            UnsupportedChannel::new(super::EXCHANGE_NAME, "BBO"),
+        );
+    }
 }
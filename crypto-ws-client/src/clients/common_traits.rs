@@ -1,27 +1,29 @@
+use crate::{AuthError, Interval, UnsupportedChannel};
+
 // tick-by-tick trade
 pub(super) trait Trade {
-    fn subscribe_trade(&self, pairs: &[String]);
+    fn subscribe_trade(&self, pairs: &[String]) -> Result<(), UnsupportedChannel>;
 }
 
 // 24hr rolling window ticker
 pub(super) trait Ticker {
-    fn subscribe_ticker(&self, pairs: &[String]);
+    fn subscribe_ticker(&self, pairs: &[String]) -> Result<(), UnsupportedChannel>;
 }
 
 // Best Bid & Offer
 #[allow(clippy::upper_case_acronyms)]
 pub(super) trait BBO {
-    fn subscribe_bbo(&self, pairs: &[String]);
+    fn subscribe_bbo(&self, pairs: &[String]) -> Result<(), UnsupportedChannel>;
 }
 
 // An orderbook snapshot followed by realtime updates.
 pub(super) trait OrderBook {
-    fn subscribe_orderbook(&self, pairs: &[String]);
+    fn subscribe_orderbook(&self, pairs: &[String]) -> Result<(), UnsupportedChannel>;
 }
 
 pub(super) trait OrderBookTopK {
     /// Subscribes to level2 orderbook top-k snapshot channels.
-    fn subscribe_orderbook_topk(&self, pairs: &[String]);
+    fn subscribe_orderbook_topk(&self, pairs: &[String]) -> Result<(), UnsupportedChannel>;
 }
 
 /// Level3 orderbook data.
@@ -30,20 +32,28 @@ pub(super) trait Level3OrderBook {
     ///
     /// The level3 orderbook is the orginal orderbook of an exchange, it is
     /// non-aggregated by price level and updated tick-by-tick.
-    fn subscribe_l3_orderbook(&self, symbols: &[String]);
+    fn subscribe_l3_orderbook(&self, symbols: &[String]) -> Result<(), UnsupportedChannel>;
 }
 
 pub(super) trait Candlestick {
     /// Subscribes to candlestick channels which send OHLCV messages.
     ///
-    /// `symbol_interval_list` is a list of symbols and intervals of candlesticks in seconds.
-    fn subscribe_candlestick(&self, symbol_interval_list: &[(String, usize)]);
+    /// `symbol_interval_list` is a list of symbols and intervals of candlesticks.
+    fn subscribe_candlestick(
+        &self,
+        symbol_interval_list: &[(String, Interval)],
+    ) -> Result<(), UnsupportedChannel>;
+}
+
+// Login for private channels
+pub(super) trait Authenticate {
+    fn authenticate(&self, api_key: &str, secret: &str, passphrase: &str) -> Result<(), AuthError>;
 }
 
 macro_rules! impl_trait {
     ($trait_name:ident, $struct_name:ident, $method_name:ident, $channel_name:expr, $to_raw_channel: ident) => {
         impl $trait_name for $struct_name {
-            fn $method_name(&self, pairs: &[String]) {
+            fn $method_name(&self, pairs: &[String]) -> Result<(), UnsupportedChannel> {
                 let pair_to_raw_channel = |pair: &String| $to_raw_channel($channel_name, pair);
 
                 let channels = pairs
@@ -51,6 +61,7 @@ macro_rules! impl_trait {
                     .map(pair_to_raw_channel)
                     .collect::<Vec<String>>();
                 self.client.subscribe(&channels);
+                Ok(())
             }
         }
     };
@@ -59,74 +70,93 @@ macro_rules! impl_trait {
 macro_rules! impl_candlestick {
     ($struct_name:ident) => {
         impl Candlestick for $struct_name {
-            fn subscribe_candlestick(&self, symbol_interval_list: &[(String, usize)]) {
-                let raw_channels: Vec<String> = symbol_interval_list
+            fn subscribe_candlestick(
+                &self,
+                symbol_interval_list: &[(String, Interval)],
+            ) -> Result<(), UnsupportedChannel> {
+                let raw_channels = symbol_interval_list
                     .iter()
                     .map(|(symbol, interval)| to_candlestick_raw_channel(&symbol, *interval))
-                    .collect();
+                    .collect::<Result<Vec<String>, UnsupportedChannel>>()?;
                 self.client.subscribe(&raw_channels);
+                Ok(())
             }
         }
     };
 }
 
-macro_rules! panic_ticker {
+macro_rules! unsupported_ticker {
     ($struct_name:ident) => {
         impl Ticker for $struct_name {
-            fn subscribe_ticker(&self, _symbols: &[String]) {
-                panic!(
-                    "{} does NOT have the ticker websocket channel",
-                    EXCHANGE_NAME
-                );
+            fn subscribe_ticker(&self, _symbols: &[String]) -> Result<(), UnsupportedChannel> {
+                Err(UnsupportedChannel::new(EXCHANGE_NAME, "ticker"))
             }
         }
     };
 }
 
-macro_rules! panic_bbo {
+macro_rules! unsupported_bbo {
     ($struct_name:ident) => {
         impl BBO for $struct_name {
-            fn subscribe_bbo(&self, _symbols: &[String]) {
-                panic!("{} does NOT have the BBO websocket channel", EXCHANGE_NAME);
+            fn subscribe_bbo(&self, _symbols: &[String]) -> Result<(), UnsupportedChannel> {
+                Err(UnsupportedChannel::new(EXCHANGE_NAME, "BBO"))
             }
         }
     };
 }
 
-macro_rules! panic_l2_topk {
+macro_rules! unsupported_l2_topk {
     ($struct_name:ident) => {
         impl OrderBookTopK for $struct_name {
-            fn subscribe_orderbook_topk(&self, _symbols: &[String]) {
-                panic!(
-                    "{} does NOT have the level2 top-k snapshot websocket channel",
-                    EXCHANGE_NAME
-                );
+            fn subscribe_orderbook_topk(
+                &self,
+                _symbols: &[String],
+            ) -> Result<(), UnsupportedChannel> {
+                Err(UnsupportedChannel::new(
+                    EXCHANGE_NAME,
+                    "level2 top-k snapshot",
+                ))
             }
         }
     };
 }
 
-macro_rules! panic_l3_orderbook {
+macro_rules! unsupported_l3_orderbook {
     ($struct_name:ident) => {
         impl Level3OrderBook for $struct_name {
-            fn subscribe_l3_orderbook(&self, _symbols: &[String]) {
-                panic!(
-                    "{} does NOT have the level3 websocket channel",
-                    EXCHANGE_NAME
-                );
+            fn subscribe_l3_orderbook(
+                &self,
+                _symbols: &[String],
+            ) -> Result<(), UnsupportedChannel> {
+                Err(UnsupportedChannel::new(EXCHANGE_NAME, "level3 orderbook"))
             }
         }
     };
 }
 
-macro_rules! panic_candlestick {
+macro_rules! unsupported_candlestick {
     ($struct_name:ident) => {
         impl Candlestick for $struct_name {
-            fn subscribe_candlestick(&self, _symbol_interval_list: &[(String, usize)]) {
-                panic!(
-                    "{} does NOT have the candlestick websocket channel",
-                    EXCHANGE_NAME
-                );
+            fn subscribe_candlestick(
+                &self,
+                _symbol_interval_list: &[(String, Interval)],
+            ) -> Result<(), UnsupportedChannel> {
+                Err(UnsupportedChannel::new(EXCHANGE_NAME, "candlestick"))
+            }
+        }
+    };
+}
+
+macro_rules! unsupported_authenticate {
+    ($struct_name:ident) => {
+        impl Authenticate for $struct_name {
+            fn authenticate(
+                &self,
+                _api_key: &str,
+                _secret: &str,
+                _passphrase: &str,
+            ) -> Result<(), crate::AuthError> {
+                Err(crate::AuthError::Unsupported(EXCHANGE_NAME.to_string()))
             }
         }
     };
@@ -1,12 +1,19 @@
-use crate::WSClient;
+use crate::{Interval, UnsupportedChannel, WSClient};
 use std::collections::HashMap;
-use std::sync::mpsc::Sender;
+use std::sync::{
+    atomic::{AtomicU64, Ordering},
+    mpsc::Sender,
+    Mutex,
+};
+use std::time::{Duration, Instant};
 
 use super::utils::ensure_frame_size;
 use super::ws_client_internal::{MiscMessage, WSClientInternal};
-use super::{Candlestick, Level3OrderBook, OrderBook, OrderBookTopK, Ticker, Trade, BBO};
+use super::{
+    Authenticate, Candlestick, Level3OrderBook, OrderBook, OrderBookTopK, Ticker, Trade, BBO,
+};
 
-use log::*;
+use lazy_static::lazy_static;
 use serde_json::Value;
 use tungstenite::Message;
 
@@ -18,6 +25,19 @@ const WEBSOCKET_URL: &str = "wss://www.deribit.com/ws/api/v2/";
 /// single frame in websocket connection frame exceeds the limit (32 kB)
 const WS_FRAME_SIZE: usize = 32 * 1024;
 
+// JSON-RPC request ids, unique within the process
+static NEXT_REQUEST_ID: AtomicU64 = AtomicU64::new(1);
+
+// Requests without a response within this time are forgotten
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(60);
+
+lazy_static! {
+    // request id -> (method, sent at), removed once the server responds,
+    // either with a result or an error, or once it times out
+    static ref PENDING_REQUESTS: Mutex<HashMap<u64, (String, Instant)>> =
+        Mutex::new(HashMap::new());
+}
+
 /// The WebSocket client for Deribit.
 ///
 /// Deribit has InverseFuture, InverseSwap and Option markets.
@@ -30,15 +50,61 @@ pub struct DeribitWSClient {
     client: WSClientInternal,
 }
 
-fn topics_to_command(chunk: &[String], subscribe: bool) -> String {
+fn to_jsonrpc_command(method: &str, params: &str) -> String {
     format!(
-        r#"{{"method": "public/{}", "params": {{"channels": {}}}}}"#,
+        r#"{{"jsonrpc": "2.0", "id": {}, "method": "{}", "params": {}}}"#,
+        NEXT_REQUEST_ID.fetch_add(1, Ordering::Relaxed),
+        method,
+        params
+    )
+}
+
+// Remember the id of a command about to be sent, so that on_misc_msg() can
+// match the response to it.
+fn track_request(command: &str) {
+    if let Ok(obj) = serde_json::from_str::<HashMap<String, Value>>(command) {
+        if let (Some(id), Some(method)) = (
+            obj.get("id").and_then(|x| x.as_u64()),
+            obj.get("method").and_then(|x| x.as_str()),
+        ) {
+            let mut pending = PENDING_REQUESTS.lock().unwrap();
+            expire_requests(&mut pending);
+            pending.insert(id, (method.to_string(), Instant::now()));
+        }
+    }
+}
+
+// Drop requests the server never responded to, e.g., commands lost in a
+// reconnect, so that they don't pile up.
+fn expire_requests(pending: &mut HashMap<u64, (String, Instant)>) {
+    pending.retain(|id, (method, sent_at)| {
+        let alive = sent_at.elapsed() < REQUEST_TIMEOUT;
+        if !alive {
+            exchange_log!(
+                EXCHANGE_NAME,
+                Warn,
+                "No response to {} with id {} from {} in {} seconds",
+                method,
+                id,
+                EXCHANGE_NAME,
+                REQUEST_TIMEOUT.as_secs()
+            );
+        }
+        alive
+    });
+}
+
+fn topics_to_command(chunk: &[String], subscribe: bool) -> String {
+    to_jsonrpc_command(
         if subscribe {
-            "subscribe"
+            "public/subscribe"
         } else {
-            "unsubscribe"
+            "public/unsubscribe"
         },
-        serde_json::to_string(chunk).unwrap()
+        &format!(
+            r#"{{"channels": {}}}"#,
+            serde_json::to_string(chunk).unwrap()
+        ),
     )
 }
 
@@ -46,19 +112,54 @@ fn channels_to_commands(channels: &[String], subscribe: bool) -> Vec<String> {
     let mut all_commands: Vec<String> =
         ensure_frame_size(channels, subscribe, topics_to_command, WS_FRAME_SIZE, None);
 
-    all_commands
-        .push(r#"{"method": "public/set_heartbeat", "params": {"interval": 10}}"#.to_string());
+    all_commands.push(to_jsonrpc_command(
+        "public/set_heartbeat",
+        r#"{"interval": 10}"#,
+    ));
 
+    for command in all_commands.iter() {
+        track_request(command);
+    }
     all_commands
 }
 
 fn on_misc_msg(msg: &str) -> MiscMessage {
     let obj = serde_json::from_str::<HashMap<String, Value>>(msg).unwrap();
+    // the request is removed before checking for errors, which panic
+    let request = {
+        let mut pending = PENDING_REQUESTS.lock().unwrap();
+        expire_requests(&mut pending);
+        obj.get("id")
+            .and_then(|x| x.as_u64())
+            .and_then(|id| pending.remove(&id))
+            .map(|(method, _)| method)
+    };
 
     if obj.contains_key("error") {
-        panic!("Received {} from {}", msg, EXCHANGE_NAME);
+        panic!(
+            "Received {} from {}, request: {}",
+            msg,
+            EXCHANGE_NAME,
+            request.unwrap_or_default()
+        );
     } else if obj.contains_key("result") {
-        info!("Received {} from {}", msg, EXCHANGE_NAME);
+        match request {
+            Some(method) => exchange_log!(
+                EXCHANGE_NAME,
+                Info,
+                "{} confirmed by {}, {}",
+                method,
+                EXCHANGE_NAME,
+                msg
+            ),
+            None => exchange_log!(
+                EXCHANGE_NAME,
+                Warn,
+                "Received {} with unknown id from {}",
+                msg,
+                EXCHANGE_NAME
+            ),
+        }
         MiscMessage::Misc
     } else if obj.contains_key("method") && obj.contains_key("params") {
         match obj.get("method").unwrap().as_str().unwrap() {
@@ -74,20 +175,21 @@ fn on_misc_msg(msg: &str) -> MiscMessage {
                     .as_str()
                     .unwrap();
                 if param_type == "test_request" {
-                    let ws_msg = Message::Text(r#"{"method": "public/test"}"#.to_string());
-                    MiscMessage::WebSocket(ws_msg)
+                    let command = to_jsonrpc_command("public/test", "{}");
+                    track_request(&command);
+                    MiscMessage::WebSocket(Message::Text(command))
                 } else {
-                    info!("Received {} from {}", msg, EXCHANGE_NAME);
+                    exchange_log!(EXCHANGE_NAME, Info, "Received {}", msg);
                     MiscMessage::Misc
                 }
             }
             _ => {
-                warn!("Received {} from {}", msg, EXCHANGE_NAME);
+                exchange_log!(EXCHANGE_NAME, Warn, "Received {}", msg);
                 MiscMessage::Misc
             }
         }
     } else {
-        error!("Received {} from {}", msg, EXCHANGE_NAME);
+        exchange_log!(EXCHANGE_NAME, Error, "Received {}", msg);
         MiscMessage::Misc
     }
 }
@@ -114,28 +216,31 @@ impl_trait!(OrderBookTopK, DeribitWSClient, subscribe_orderbook_topk, "orderbook
 #[rustfmt::skip]
 impl_trait!(BBO, DeribitWSClient, subscribe_bbo, "bbo", to_raw_channel);
 
-fn to_candlestick_raw_channel(pair: &str, interval: usize) -> String {
+fn to_candlestick_raw_channel(
+    pair: &str,
+    interval: Interval,
+) -> Result<String, UnsupportedChannel> {
     let interval_str = match interval {
-        60 => "1",
-        180 => "3",
-        300 => "5",
-        600 => "10",
-        900 => "15",
-        1800 => "30",
-        3600 => "60",
-        7200 => "120",
-        10800 => "180",
-        21600 => "360",
-        43200 => "720",
-        86400 => "1D",
-        _ => panic!("Unknown interval {}", interval),
+        Interval::Min1 => "1",
+        Interval::Min3 => "3",
+        Interval::Min5 => "5",
+        Interval::Min10 => "10",
+        Interval::Min15 => "15",
+        Interval::Min30 => "30",
+        Interval::Hour1 => "60",
+        Interval::Hour2 => "120",
+        Interval::Hour3 => "180",
+        Interval::Hour6 => "360",
+        Interval::Hour12 => "720",
+        Interval::Day1 => "1D",
+        _ => return Err(UnsupportedChannel::interval(EXCHANGE_NAME, interval)),
     };
-    format!("chart.trades.{}.{}", pair, interval_str)
+    Ok(format!("chart.trades.{}.{}", pair, interval_str))
 }
 
 impl_candlestick!(DeribitWSClient);
 
-panic_l3_orderbook!(DeribitWSClient);
+unsupported_l3_orderbook!(DeribitWSClient);
 
 impl_new_constructor!(
     DeribitWSClient,
@@ -146,23 +251,42 @@ impl_new_constructor!(
     None,
     None
 );
+unsupported_authenticate!(DeribitWSClient);
 impl_ws_client_trait!(DeribitWSClient);
 
 #[cfg(test)]
 mod tests {
+    use super::super::ws_client_internal::MiscMessage;
+    use crate::{clients::mock_server::assert_unsupported, UnsupportedChannel, WSClient};
+    use serde_json::Value;
+
+    fn parse_command(command: &str) -> Value {
+        let obj = serde_json::from_str::<Value>(command).unwrap();
+        assert_eq!("2.0", obj["jsonrpc"].as_str().unwrap());
+        assert!(obj["id"].as_u64().is_some());
+        obj
+    }
+
     #[test]
     fn test_one_channel() {
         let commands =
             super::channels_to_commands(&vec!["trades.BTC-26MAR21.raw".to_string()], true);
         assert_eq!(2, commands.len());
+
+        let subscribe = parse_command(&commands[0]);
+        assert_eq!("public/subscribe", subscribe["method"].as_str().unwrap());
         assert_eq!(
-            r#"{"method": "public/subscribe", "params": {"channels": ["trades.BTC-26MAR21.raw"]}}"#,
-            commands[0]
+            r#"{"channels":["trades.BTC-26MAR21.raw"]}"#,
+            subscribe["params"].to_string()
         );
+
+        let heartbeat = parse_command(&commands[1]);
         assert_eq!(
-            r#"{"method": "public/set_heartbeat", "params": {"interval": 10}}"#,
-            commands[1]
+            "public/set_heartbeat",
+            heartbeat["method"].as_str().unwrap()
         );
+        assert_eq!(r#"{"interval":10}"#, heartbeat["params"].to_string());
+        assert_ne!(subscribe["id"], heartbeat["id"]);
     }
 
     #[test]
@@ -172,16 +296,80 @@ mod tests {
                 "trades.BTC-26MAR21.raw".to_string(),
                 "ticker.BTC-26MAR21.100ms".to_string(),
             ],
-            true,
+            false,
         );
         assert_eq!(2, commands.len());
+
+        let unsubscribe = parse_command(&commands[0]);
         assert_eq!(
-            r#"{"method": "public/subscribe", "params": {"channels": ["trades.BTC-26MAR21.raw","ticker.BTC-26MAR21.100ms"]}}"#,
-            commands[0]
+            "public/unsubscribe",
+            unsubscribe["method"].as_str().unwrap()
         );
         assert_eq!(
-            r#"{"method": "public/set_heartbeat", "params": {"interval": 10}}"#,
-            commands[1]
+            r#"{"channels":["trades.BTC-26MAR21.raw","ticker.BTC-26MAR21.100ms"]}"#,
+            unsubscribe["params"].to_string()
+        );
+    }
+
+    #[test]
+    fn test_subscription_response() {
+        let commands =
+            super::channels_to_commands(&vec!["trades.BTC-PERPETUAL.raw".to_string()], true);
+        let id = parse_command(&commands[0])["id"].as_u64().unwrap();
+        assert!(super::PENDING_REQUESTS.lock().unwrap().contains_key(&id));
+
+        let response = format!(
+            r#"{{"jsonrpc":"2.0","id":{},"result":["trades.BTC-PERPETUAL.raw"],"usIn":1626330013316,"usOut":1626330013317,"usDiff":1,"testnet":false}}"#,
+            id
+        );
+        assert!(matches!(super::on_misc_msg(&response), MiscMessage::Misc));
+        assert!(!super::PENDING_REQUESTS.lock().unwrap().contains_key(&id));
+
+        let notification = r#"{"jsonrpc":"2.0","method":"subscription","params":{"channel":"trades.BTC-PERPETUAL.raw","data":[{"trade_seq":1,"trade_id":"1","timestamp":1626330013316,"price":32000.0,"amount":10.0,"direction":"buy","instrument_name":"BTC-PERPETUAL","index_price":32000.0,"mark_price":32000.0,"tick_direction":0}]}}"#;
+        assert!(matches!(
+            super::on_misc_msg(notification),
+            MiscMessage::Normal
+        ));
+    }
+
+    #[test]
+    fn test_error_response() {
+        let commands =
+            super::channels_to_commands(&vec!["trades.BTC-PERPETUAL.raw".to_string()], true);
+        let id = parse_command(&commands[0])["id"].as_u64().unwrap();
+
+        let response = format!(
+            r#"{{"jsonrpc":"2.0","id":{},"error":{{"message":"Invalid params","code":-32602}},"usIn":1626330013316,"usOut":1626330013317,"usDiff":1,"testnet":false}}"#,
+            id
+        );
+        assert!(std::panic::catch_unwind(|| super::on_misc_msg(&response)).is_err());
+        assert!(!super::PENDING_REQUESTS.lock().unwrap().contains_key(&id));
+    }
+
+    #[test]
+    fn test_request_timeout() {
+        let commands =
+            super::channels_to_commands(&vec!["trades.ETH-PERPETUAL.raw".to_string()], true);
+        let id = parse_command(&commands[0])["id"].as_u64().unwrap();
+        {
+            let mut pending = super::PENDING_REQUESTS.lock().unwrap();
+            let sent_at = std::time::Instant::now()
+                .checked_sub(super::REQUEST_TIMEOUT)
+                .unwrap();
+            pending.get_mut(&id).unwrap().1 = sent_at;
+        }
+
+        super::channels_to_commands(&vec!["trades.ETH-PERPETUAL.raw".to_string()], false);
+        assert!(!super::PENDING_REQUESTS.lock().unwrap().contains_key(&id));
+    }
+
+    #[test]
+    fn test_unsupported_channel() {
+        assert_unsupported(
+            |client: &super::DeribitWSClient| {
+                client.subscribe_l3_orderbook(&["BTCUSDT".to_string()])
+            },
+            UnsupportedChannel::new(super::EXCHANGE_NAME, "level3 orderbook"),
         );
     }
 }
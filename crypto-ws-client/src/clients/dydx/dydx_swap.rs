@@ -1,12 +1,13 @@
-use crate::WSClient;
+use crate::{Interval, UnsupportedChannel, WSClient};
 use std::collections::HashMap;
 use std::sync::mpsc::Sender;
 
 use super::super::ws_client_internal::{MiscMessage, WSClientInternal};
-use super::super::{Candlestick, Level3OrderBook, OrderBook, OrderBookTopK, Ticker, Trade, BBO};
+use super::super::{
+    Authenticate, Candlestick, Level3OrderBook, OrderBook, OrderBookTopK, Ticker, Trade, BBO,
+};
 use crate::clients::utils::CHANNEL_PAIR_DELIMITER;
 
-use log::*;
 use serde_json::Value;
 
 const EXCHANGE_NAME: &str = "dydx";
@@ -58,7 +59,7 @@ fn on_misc_msg(msg: &str) -> MiscMessage {
 
     match obj.get("type").unwrap().as_str().unwrap() {
         "error" => {
-            error!("Received {} from {}", msg, EXCHANGE_NAME);
+            exchange_log!(EXCHANGE_NAME, Error, "Received {}", msg);
             if obj.contains_key("message")
                 && obj
                     .get("message")
@@ -73,12 +74,12 @@ fn on_misc_msg(msg: &str) -> MiscMessage {
             }
         }
         "connected" | "pong" => {
-            debug!("Received {} from {}", msg, EXCHANGE_NAME);
+            exchange_log!(EXCHANGE_NAME, Debug, "Received {}", msg);
             MiscMessage::Misc
         }
         "channel_data" | "subscribed" => MiscMessage::Normal,
         _ => {
-            warn!("Received {} from {}", msg, EXCHANGE_NAME);
+            exchange_log!(EXCHANGE_NAME, Warn, "Received {}", msg);
             MiscMessage::Misc
         }
     }
@@ -93,11 +94,11 @@ impl_trait!(Trade, DydxSwapWSClient, subscribe_trade, "v3_trades", to_raw_channe
 #[rustfmt::skip]
 impl_trait!(OrderBook, DydxSwapWSClient, subscribe_orderbook, "v3_orderbook", to_raw_channel);
 
-panic_ticker!(DydxSwapWSClient);
-panic_bbo!(DydxSwapWSClient);
-panic_l2_topk!(DydxSwapWSClient);
-panic_l3_orderbook!(DydxSwapWSClient);
-panic_candlestick!(DydxSwapWSClient);
+unsupported_ticker!(DydxSwapWSClient);
+unsupported_bbo!(DydxSwapWSClient);
+unsupported_l2_topk!(DydxSwapWSClient);
+unsupported_l3_orderbook!(DydxSwapWSClient);
+unsupported_candlestick!(DydxSwapWSClient);
 
 impl_new_constructor!(
     DydxSwapWSClient,
@@ -108,4 +109,18 @@ impl_new_constructor!(
     Some(CLIENT_PING_INTERVAL_AND_MSG),
     None
 );
+unsupported_authenticate!(DydxSwapWSClient);
 impl_ws_client_trait!(DydxSwapWSClient);
+
+#[cfg(test)]
+mod tests {
+    use crate::{clients::mock_server::assert_unsupported, UnsupportedChannel, WSClient};
+
+    #[test]
+    fn test_unsupported_channel() {
+        assert_unsupported(
+            |client: &super::DydxSwapWSClient| client.subscribe_ticker(&["BTCUSDT".to_string()]),
+            UnsupportedChannel::new(super::EXCHANGE_NAME, "ticker"),
+        );
+    }
+}
@@ -1,4 +1,4 @@
-use crate::WSClient;
+use crate::{Interval, UnsupportedChannel, WSClient};
 use std::collections::HashMap;
 use std::sync::mpsc::Sender;
 
@@ -6,9 +6,10 @@ use super::{
     utils::CHANNEL_PAIR_DELIMITER,
     ws_client_internal::{MiscMessage, WSClientInternal},
 };
-use super::{Candlestick, Level3OrderBook, OrderBook, OrderBookTopK, Ticker, Trade, BBO};
+use super::{
+    Authenticate, Candlestick, Level3OrderBook, OrderBook, OrderBookTopK, Ticker, Trade, BBO,
+};
 
-use log::*;
 use serde_json::Value;
 
 pub(super) const EXCHANGE_NAME: &str = "ftx";
@@ -61,16 +62,16 @@ fn on_misc_msg(msg: &str) -> MiscMessage {
         // see https://docs.ftx.com/#response-format
         "pong" => MiscMessage::Pong,
         "subscribed" | "unsubscribed" | "info" => {
-            info!("Received {} from {}", msg, EXCHANGE_NAME);
+            exchange_log!(EXCHANGE_NAME, Info, "Received {}", msg);
             MiscMessage::Misc
         }
         "partial" | "update" => MiscMessage::Normal,
         "error" => {
-            error!("Received {} from {}", msg, EXCHANGE_NAME);
+            exchange_log!(EXCHANGE_NAME, Error, "Received {}", msg);
             panic!("Received {} from {}", msg, EXCHANGE_NAME);
         }
         _ => {
-            warn!("Received {} from {}", msg, EXCHANGE_NAME);
+            exchange_log!(EXCHANGE_NAME, Warn, "Received {}", msg);
             MiscMessage::Misc
         }
     }
@@ -88,24 +89,30 @@ impl_trait!(BBO, FtxWSClient, subscribe_bbo, "ticker", to_raw_channel);
 impl_trait!(OrderBook, FtxWSClient, subscribe_orderbook, "orderbook", to_raw_channel);
 
 impl OrderBookTopK for FtxWSClient {
-    fn subscribe_orderbook_topk(&self, _pairs: &[String]) {
-        panic!("FTX does NOT have orderbook snapshot channel");
+    fn subscribe_orderbook_topk(&self, _pairs: &[String]) -> Result<(), UnsupportedChannel> {
+        Err(UnsupportedChannel::new(
+            EXCHANGE_NAME,
+            "level2 top-k snapshot",
+        ))
     }
 }
 
 impl Ticker for FtxWSClient {
-    fn subscribe_ticker(&self, _pairs: &[String]) {
-        panic!("FTX does NOT have ticker channel");
+    fn subscribe_ticker(&self, _pairs: &[String]) -> Result<(), UnsupportedChannel> {
+        Err(UnsupportedChannel::new(EXCHANGE_NAME, "ticker"))
     }
 }
 
 impl Candlestick for FtxWSClient {
-    fn subscribe_candlestick(&self, _symbol_interval_list: &[(String, usize)]) {
-        panic!("FTX does NOT have candlestick channel");
+    fn subscribe_candlestick(
+        &self,
+        _symbol_interval_list: &[(String, Interval)],
+    ) -> Result<(), UnsupportedChannel> {
+        Err(UnsupportedChannel::new(EXCHANGE_NAME, "candlestick"))
     }
 }
 
-panic_l3_orderbook!(FtxWSClient);
+unsupported_l3_orderbook!(FtxWSClient);
 
 impl_new_constructor!(
     FtxWSClient,
@@ -116,4 +123,18 @@ impl_new_constructor!(
     Some(CLIENT_PING_INTERVAL_AND_MSG),
     None
 );
+unsupported_authenticate!(FtxWSClient);
 impl_ws_client_trait!(FtxWSClient);
+
+#[cfg(test)]
+mod tests {
+    use crate::{clients::mock_server::assert_unsupported, UnsupportedChannel, WSClient};
+
+    #[test]
+    fn test_unsupported_channel() {
+        assert_unsupported(
+            |client: &super::FtxWSClient| client.subscribe_ticker(&["BTCUSDT".to_string()]),
+            UnsupportedChannel::new(super::EXCHANGE_NAME, "ticker"),
+        );
+    }
+}
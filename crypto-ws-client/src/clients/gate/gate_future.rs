@@ -1,8 +1,10 @@
-use crate::WSClient;
+use crate::{Interval, UnsupportedChannel, WSClient};
 use std::sync::mpsc::Sender;
 
 use super::super::ws_client_internal::WSClientInternal;
-use super::super::{Candlestick, Level3OrderBook, OrderBook, OrderBookTopK, Ticker, Trade, BBO};
+use super::super::{
+    Authenticate, Candlestick, Level3OrderBook, OrderBook, OrderBookTopK, Ticker, Trade, BBO,
+};
 use super::utils::{
     channels_to_commands, on_misc_msg, to_candlestick_raw_channel_shared, to_raw_channel,
     CLIENT_PING_INTERVAL_AND_MSG, EXCHANGE_NAME,
@@ -35,13 +37,16 @@ impl_trait!(OrderBook, GateInverseFutureWSClient, subscribe_orderbook, "futures.
 impl_trait!(Ticker, GateInverseFutureWSClient, subscribe_ticker, "futures.tickers", to_raw_channel);
 
 impl BBO for GateInverseFutureWSClient {
-    fn subscribe_bbo(&self, _pairs: &[String]) {
-        panic!("Gate does NOT have BBO channel");
+    fn subscribe_bbo(&self, _pairs: &[String]) -> Result<(), UnsupportedChannel> {
+        Err(UnsupportedChannel::new(EXCHANGE_NAME, "BBO"))
     }
 }
 impl OrderBookTopK for GateInverseFutureWSClient {
-    fn subscribe_orderbook_topk(&self, _pairs: &[String]) {
-        panic!("Gate does NOT have orderbook snapshot channel");
+    fn subscribe_orderbook_topk(&self, _pairs: &[String]) -> Result<(), UnsupportedChannel> {
+        Err(UnsupportedChannel::new(
+            EXCHANGE_NAME,
+            "level2 top-k snapshot",
+        ))
     }
 }
 
@@ -53,25 +58,31 @@ impl_trait!(OrderBook, GateLinearFutureWSClient, subscribe_orderbook, "futures.o
 impl_trait!(Ticker, GateLinearFutureWSClient, subscribe_ticker, "futures.tickers", to_raw_channel);
 
 impl BBO for GateLinearFutureWSClient {
-    fn subscribe_bbo(&self, _pairs: &[String]) {
-        panic!("Gate does NOT have BBO channel");
+    fn subscribe_bbo(&self, _pairs: &[String]) -> Result<(), UnsupportedChannel> {
+        Err(UnsupportedChannel::new(EXCHANGE_NAME, "BBO"))
     }
 }
 impl OrderBookTopK for GateLinearFutureWSClient {
-    fn subscribe_orderbook_topk(&self, _pairs: &[String]) {
-        panic!("Gate does NOT have orderbook snapshot channel");
+    fn subscribe_orderbook_topk(&self, _pairs: &[String]) -> Result<(), UnsupportedChannel> {
+        Err(UnsupportedChannel::new(
+            EXCHANGE_NAME,
+            "level2 top-k snapshot",
+        ))
     }
 }
 
-fn to_candlestick_raw_channel(pair: &str, interval: usize) -> String {
+fn to_candlestick_raw_channel(
+    pair: &str,
+    interval: Interval,
+) -> Result<String, UnsupportedChannel> {
     to_candlestick_raw_channel_shared("futures", pair, interval)
 }
 
 impl_candlestick!(GateInverseFutureWSClient);
 impl_candlestick!(GateLinearFutureWSClient);
 
-panic_l3_orderbook!(GateInverseFutureWSClient);
-panic_l3_orderbook!(GateLinearFutureWSClient);
+unsupported_l3_orderbook!(GateInverseFutureWSClient);
+unsupported_l3_orderbook!(GateLinearFutureWSClient);
 
 impl_new_constructor!(
     GateInverseFutureWSClient,
@@ -82,6 +93,7 @@ impl_new_constructor!(
     Some(CLIENT_PING_INTERVAL_AND_MSG),
     None
 );
+unsupported_authenticate!(GateInverseFutureWSClient);
 impl_ws_client_trait!(GateInverseFutureWSClient);
 
 impl_new_constructor!(
@@ -93,4 +105,26 @@ impl_new_constructor!(
     Some(CLIENT_PING_INTERVAL_AND_MSG),
     None
 );
+unsupported_authenticate!(GateLinearFutureWSClient);
 impl_ws_client_trait!(GateLinearFutureWSClient);
+
+#[cfg(test)]
+mod tests {
+    use crate::{clients::mock_server::assert_unsupported, UnsupportedChannel, WSClient};
+
+    #[test]
+    fn test_unsupported_channel() {
+        assert_unsupported(
+            |client: &super::GateInverseFutureWSClient| {
+                client.subscribe_bbo(&["BTCUSDT".to_string()])
+            },
+            UnsupportedChannel::new(super::EXCHANGE_NAME, "BBO"),
+        );
+        assert_unsupported(
+            |client: &super::GateLinearFutureWSClient| {
+                client.subscribe_bbo(&["BTCUSDT".to_string()])
+            },
+            UnsupportedChannel::new(super::EXCHANGE_NAME, "BBO"),
+        );
+    }
+}
@@ -1,18 +1,17 @@
-use crate::WSClient;
+use crate::{Interval, UnsupportedChannel, WSClient};
 use std::sync::mpsc::Sender;
 
 use super::super::ws_client_internal::WSClientInternal;
-use super::super::{Candlestick, Level3OrderBook, OrderBook, OrderBookTopK, Ticker, Trade, BBO};
+use super::super::{
+    Authenticate, Candlestick, Level3OrderBook, OrderBook, OrderBookTopK, Ticker, Trade, BBO,
+};
 use super::utils::{
     channels_to_commands, on_misc_msg, to_candlestick_raw_channel_shared, to_raw_channel,
-    EXCHANGE_NAME,
+    EXCHANGE_NAME, SPOT_CLIENT_PING_INTERVAL_AND_MSG,
 };
 
 const WEBSOCKET_URL: &str = "wss://api.gateio.ws/ws/v4/";
 
-// https://www.gate.io/docs/apiv4/ws/en/#application-ping-pong
-const CLIENT_PING_INTERVAL_AND_MSG: (u64, &str) = (60, r#"{"channel":"spot.ping"}"#);
-
 /// The WebSocket client for Gate spot market.
 ///
 /// * WebSocket API doc: <https://www.gate.io/docs/apiv4/ws/en/index.html>
@@ -32,13 +31,16 @@ impl_trait!(BBO, GateSpotWSClient, subscribe_bbo, "spot.book_ticker", to_raw_cha
 #[rustfmt::skip]
 impl_trait!(Ticker, GateSpotWSClient, subscribe_ticker, "spot.tickers", to_raw_channel);
 
-fn to_candlestick_raw_channel(pair: &str, interval: usize) -> String {
+fn to_candlestick_raw_channel(
+    pair: &str,
+    interval: Interval,
+) -> Result<String, UnsupportedChannel> {
     to_candlestick_raw_channel_shared("spot", pair, interval)
 }
 
 impl_candlestick!(GateSpotWSClient);
 
-panic_l3_orderbook!(GateSpotWSClient);
+unsupported_l3_orderbook!(GateSpotWSClient);
 
 impl_new_constructor!(
     GateSpotWSClient,
@@ -46,7 +48,23 @@ impl_new_constructor!(
     WEBSOCKET_URL,
     channels_to_commands,
     on_misc_msg,
-    Some(CLIENT_PING_INTERVAL_AND_MSG),
+    Some(SPOT_CLIENT_PING_INTERVAL_AND_MSG),
     None
 );
+unsupported_authenticate!(GateSpotWSClient);
 impl_ws_client_trait!(GateSpotWSClient);
+
+#[cfg(test)]
+mod tests {
+    use crate::{clients::mock_server::assert_unsupported, UnsupportedChannel, WSClient};
+
+    #[test]
+    fn test_unsupported_channel() {
+        assert_unsupported(
+            |client: &super::GateSpotWSClient| {
+                client.subscribe_l3_orderbook(&["BTCUSDT".to_string()])
+            },
+            UnsupportedChannel::new(super::EXCHANGE_NAME, "level3 orderbook"),
+        );
+    }
+}
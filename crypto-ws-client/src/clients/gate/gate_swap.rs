@@ -1,8 +1,10 @@
-use crate::WSClient;
+use crate::{Interval, UnsupportedChannel, WSClient};
 use std::sync::mpsc::Sender;
 
 use super::super::ws_client_internal::WSClientInternal;
-use super::super::{Candlestick, Level3OrderBook, OrderBook, OrderBookTopK, Ticker, Trade, BBO};
+use super::super::{
+    Authenticate, Candlestick, Level3OrderBook, OrderBook, OrderBookTopK, Ticker, Trade, BBO,
+};
 use super::utils::{
     channels_to_commands, on_misc_msg, to_candlestick_raw_channel_shared, to_raw_channel,
     CLIENT_PING_INTERVAL_AND_MSG, EXCHANGE_NAME,
@@ -49,15 +51,18 @@ impl_trait!(BBO, GateLinearSwapWSClient, subscribe_bbo, "futures.book_ticker", t
 #[rustfmt::skip]
 impl_trait!(Ticker, GateLinearSwapWSClient, subscribe_ticker, "futures.tickers", to_raw_channel);
 
-fn to_candlestick_raw_channel(pair: &str, interval: usize) -> String {
+fn to_candlestick_raw_channel(
+    pair: &str,
+    interval: Interval,
+) -> Result<String, UnsupportedChannel> {
     to_candlestick_raw_channel_shared("futures", pair, interval)
 }
 
 impl_candlestick!(GateInverseSwapWSClient);
 impl_candlestick!(GateLinearSwapWSClient);
 
-panic_l3_orderbook!(GateInverseSwapWSClient);
-panic_l3_orderbook!(GateLinearSwapWSClient);
+unsupported_l3_orderbook!(GateInverseSwapWSClient);
+unsupported_l3_orderbook!(GateLinearSwapWSClient);
 
 impl_new_constructor!(
     GateInverseSwapWSClient,
@@ -68,6 +73,7 @@ impl_new_constructor!(
     Some(CLIENT_PING_INTERVAL_AND_MSG),
     None
 );
+unsupported_authenticate!(GateInverseSwapWSClient);
 impl_ws_client_trait!(GateInverseSwapWSClient);
 
 impl_new_constructor!(
@@ -79,4 +85,26 @@ impl_new_constructor!(
     Some(CLIENT_PING_INTERVAL_AND_MSG),
     None
 );
+unsupported_authenticate!(GateLinearSwapWSClient);
 impl_ws_client_trait!(GateLinearSwapWSClient);
+
+#[cfg(test)]
+mod tests {
+    use crate::{clients::mock_server::assert_unsupported, UnsupportedChannel, WSClient};
+
+    #[test]
+    fn test_unsupported_channel() {
+        assert_unsupported(
+            |client: &super::GateInverseSwapWSClient| {
+                client.subscribe_l3_orderbook(&["BTCUSDT".to_string()])
+            },
+            UnsupportedChannel::new(super::EXCHANGE_NAME, "level3 orderbook"),
+        );
+        assert_unsupported(
+            |client: &super::GateLinearSwapWSClient| {
+                client.subscribe_l3_orderbook(&["BTCUSDT".to_string()])
+            },
+            UnsupportedChannel::new(super::EXCHANGE_NAME, "level3 orderbook"),
+        );
+    }
+}
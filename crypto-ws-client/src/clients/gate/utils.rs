@@ -1,19 +1,46 @@
 use crate::clients::utils::CHANNEL_PAIR_DELIMITER;
+use crate::{Interval, UnsupportedChannel};
 use std::collections::HashMap;
 
 use super::super::ws_client_internal::MiscMessage;
 
-use log::*;
 use serde_json::Value;
 
 pub(super) const EXCHANGE_NAME: &str = "gate";
 
 // https://www.gate.io/docs/futures/ws/en/#ping-and-pong
 // https://www.gate.io/docs/delivery/ws/en/#ping-and-pong
+//
+// The server answers with the same channel ending in .pong, e.g.,
+// {"time":1545404023,"channel":"futures.pong","event":"","error":null,"result":null},
+// which on_misc_msg() classifies as MiscMessage::Pong. The request time is
+// omitted since the message is static.
 pub(super) const CLIENT_PING_INTERVAL_AND_MSG: (u64, &str) = (60, r#"{"channel":"futures.ping"}"#);
 
+// https://www.gate.io/docs/apiv4/ws/en/#application-ping-pong
+pub(super) const SPOT_CLIENT_PING_INTERVAL_AND_MSG: (u64, &str) =
+    (60, r#"{"channel":"spot.ping"}"#);
+
 fn channel_pairs_to_command(channel: &str, pairs: &[String], subscribe: bool) -> Vec<String> {
-    if channel.contains(".order_book") {
+    if channel.ends_with(".candlesticks") {
+        // pairs are interval_pair, e.g., 1m_BTC_USDT, and the payload is [interval, pair]
+        pairs
+            .iter()
+            .map(|interval_pair| {
+                let (interval, pair) = interval_pair.split_once('_').unwrap();
+                format!(
+                    r#"{{"channel":"{}", "event":"{}", "payload":{}}}"#,
+                    channel,
+                    if subscribe {
+                        "subscribe"
+                    } else {
+                        "unsubscribe"
+                    },
+                    serde_json::to_string(&[interval, pair]).unwrap(),
+                )
+            })
+            .collect()
+    } else if channel.contains(".order_book") {
         pairs
             .iter()
             .map(|pair| {
@@ -114,23 +141,25 @@ pub(super) fn on_misc_msg(msg: &str) -> MiscMessage {
         let code = err.get("code").unwrap().as_i64().unwrap();
         match code {
             1 | 2 => panic!("Received {} from {}", msg, EXCHANGE_NAME), // client side errors
-            _ => error!("Received {} from {}", msg, EXCHANGE_NAME),     // server side errors
+            _ => exchange_log!(EXCHANGE_NAME, Error, "Received {}", msg), // server side errors
         }
         return MiscMessage::Misc;
     }
 
-    let channel = obj.get("channel").unwrap().as_str().unwrap();
-    let event = obj.get("event").unwrap().as_str().unwrap();
-
+    let channel = obj.get("channel").and_then(|x| x.as_str()).unwrap_or("");
     if channel == "spot.pong" || channel == "futures.pong" {
-        MiscMessage::Pong
-    } else if event == "update" || event == "all" {
+        // pongs have an empty event, which might be missing as well
+        return MiscMessage::Pong;
+    }
+    let event = obj.get("event").and_then(|x| x.as_str()).unwrap_or("");
+
+    if event == "update" || event == "all" {
         MiscMessage::Normal
     } else if event == "subscribe" || event == "unsubscribe" {
-        debug!("Received {} from {}", msg, EXCHANGE_NAME);
+        exchange_log!(EXCHANGE_NAME, Debug, "Received {}", msg);
         MiscMessage::Misc
     } else {
-        warn!("Received {} from {}", msg, EXCHANGE_NAME);
+        exchange_log!(EXCHANGE_NAME, Warn, "Received {}", msg);
         MiscMessage::Misc
     }
 }
@@ -138,23 +167,105 @@ pub(super) fn on_misc_msg(msg: &str) -> MiscMessage {
 pub(super) fn to_candlestick_raw_channel_shared(
     market_type: &str,
     pair: &str,
-    interval: usize,
-) -> String {
+    interval: Interval,
+) -> Result<String, UnsupportedChannel> {
     let interval_str = match interval {
-        10 => "10s",
-        60 => "1m",
-        300 => "5m",
-        900 => "15m",
-        1800 => "30m",
-        3600 => "1h",
-        14400 => "4h",
-        28800 => "8h",
-        86400 => "1d",
-        604800 => "7d",
-        _ => panic!("Gate available intervals 10s,1m,5m,15m,30m,1h,4h,8h,1d,7d"),
+        Interval::Sec10 => "10s",
+        Interval::Min1 => "1m",
+        Interval::Min5 => "5m",
+        Interval::Min15 => "15m",
+        Interval::Min30 => "30m",
+        Interval::Hour1 => "1h",
+        Interval::Hour4 => "4h",
+        Interval::Hour8 => "8h",
+        Interval::Day1 => "1d",
+        Interval::Week1 => "7d",
+        _ => return Err(UnsupportedChannel::interval(EXCHANGE_NAME, interval)),
     };
-    format!(
-        r#"{{"channel": "{}.candlesticks", "event": "subscribe", "payload" : ["{}", "{}"]}}"#,
-        market_type, interval_str, pair
-    )
+    Ok(to_raw_channel(
+        &format!("{}.candlesticks", market_type),
+        &format!("{}_{}", interval_str, pair),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        channels_to_commands, on_misc_msg, to_candlestick_raw_channel_shared, to_raw_channel,
+    };
+    use crate::{clients::ws_client_internal::MiscMessage, Interval};
+
+    #[test]
+    fn spot_pairs_with_underscore() {
+        let channels = vec![
+            to_raw_channel("spot.trades", "BTC_USDT"),
+            to_raw_channel("spot.trades", "ETH_USDT"),
+        ];
+        assert_eq!(
+            vec![
+                r#"{"channel":"spot.trades", "event":"subscribe", "payload":["BTC_USDT","ETH_USDT"]}"#
+                    .to_string()
+            ],
+            channels_to_commands(&channels, true)
+        );
+    }
+
+    #[test]
+    fn candlestick() {
+        let channels =
+            vec![to_candlestick_raw_channel_shared("spot", "BTC_USDT", Interval::Min1).unwrap()];
+        assert_eq!("spot.candlesticks:1m_BTC_USDT", channels[0]);
+        assert_eq!(
+            vec![
+                r#"{"channel":"spot.candlesticks", "event":"subscribe", "payload":["1m","BTC_USDT"]}"#
+                    .to_string()
+            ],
+            channels_to_commands(&channels, true)
+        );
+        assert_eq!(
+            vec![
+                r#"{"channel":"spot.candlesticks", "event":"unsubscribe", "payload":["1m","BTC_USDT"]}"#
+                    .to_string()
+            ],
+            channels_to_commands(&channels, false)
+        );
+
+        let channels =
+            vec![to_candlestick_raw_channel_shared("futures", "BTC_USD", Interval::Week1).unwrap()];
+        assert_eq!(
+            vec![
+                r#"{"channel":"futures.candlesticks", "event":"subscribe", "payload":["7d","BTC_USD"]}"#
+                    .to_string()
+            ],
+            channels_to_commands(&channels, true)
+        );
+    }
+
+    #[test]
+    fn ping_pong() {
+        assert!(matches!(
+            on_misc_msg(
+                r#"{"time":1545404023,"time_ms":1545404023123,"channel":"spot.pong","event":"","error":null,"result":null}"#
+            ),
+            MiscMessage::Pong
+        ));
+        assert!(matches!(
+            on_misc_msg(
+                r#"{"time":1545404023,"channel":"futures.pong","event":"","error":null,"result":null}"#
+            ),
+            MiscMessage::Pong
+        ));
+        assert!(matches!(
+            on_misc_msg(
+                r#"{"time":1545404023,"channel":"futures.pong","error":null,"result":null}"#
+            ),
+            MiscMessage::Pong
+        ));
+        assert!(matches!(
+            on_misc_msg(
+                r#"{"time":1545404023,"channel":"futures.trades","event":"update","result":[]}"#
+            ),
+            MiscMessage::Normal
+        ));
+    }
 }
@@ -1,16 +1,22 @@
-use crate::WSClient;
+use crate::{Encoding, Interval, UnsupportedChannel, WSClient};
 use std::collections::HashMap;
 use std::sync::mpsc::Sender;
 
-use log::*;
 use serde_json::Value;
 use tungstenite::Message;
 
+use super::registry::ExchangeSpec;
 use super::ws_client_internal::{MiscMessage, WSClientInternal};
 use super::{Candlestick, Level3OrderBook, OrderBook, OrderBookTopK, Ticker, Trade, BBO};
 
 pub(super) const EXCHANGE_NAME: &str = "huobi";
 
+pub(super) const SPEC: ExchangeSpec = ExchangeSpec {
+    name: EXCHANGE_NAME,
+    encoding: Some(Encoding::Gzip),
+    ..ExchangeSpec::DEFAULT
+};
+
 const SPOT_WEBSOCKET_URL: &str = "wss://api.huobi.pro/ws";
 // const FUTURES_WEBSOCKET_URL: &str = "wss://www.hbdm.com/ws";
 // const COIN_SWAP_WEBSOCKET_URL: &str = "wss://api.hbdm.com/swap-ws";
@@ -74,11 +80,11 @@ pub struct HuobiOptionWSClient {
 }
 
 impl HuobiWSClient {
-    fn new(url: &str, tx: Sender<String>) -> Self {
+    fn new(urls: Vec<String>, tx: Sender<String>) -> Self {
         HuobiWSClient {
             client: WSClientInternal::new(
                 EXCHANGE_NAME,
-                url,
+                urls,
                 tx,
                 Self::on_misc_msg,
                 Self::channels_to_commands,
@@ -114,7 +120,7 @@ impl HuobiWSClient {
     fn on_misc_msg(msg: &str) -> MiscMessage {
         let resp = serde_json::from_str::<HashMap<String, Value>>(msg);
         if resp.is_err() {
-            error!("{} is not a JSON string, {}", msg, EXCHANGE_NAME);
+            exchange_log!(EXCHANGE_NAME, Error, "{} is not a JSON string", msg);
             return MiscMessage::Misc;
         }
         let obj = resp.unwrap();
@@ -127,7 +133,7 @@ impl HuobiWSClient {
             // - InverseSwap <https://huobiapi.github.io/docs/coin_margined_swap/v1/en/#market-heartbeat>
             // - LinearSwap <https://huobiapi.github.io/docs/usdt_swap/v1/en/#websocket-heartbeat-and-authentication-interface>
             // - Option <https://huobiapi.github.io/docs/option/v1/en/#websocket-heartbeat-and-authentication-interface>
-            debug!("Received {} from {}", msg, EXCHANGE_NAME);
+            exchange_log!(EXCHANGE_NAME, Debug, "Received {}", msg);
             let timestamp = obj.get("ping").unwrap();
             let mut pong_msg = HashMap::<String, &Value>::new();
             pong_msg.insert("pong".to_string(), timestamp);
@@ -137,7 +143,7 @@ impl HuobiWSClient {
         // Order Push Heartbeat
         // https://huobiapi.github.io/docs/usdt_swap/v1/en/#market-heartbeat
         if obj.contains_key("op") && obj.get("op").unwrap().as_str().unwrap() == "ping" {
-            debug!("Received {} from {}", msg, EXCHANGE_NAME);
+            exchange_log!(EXCHANGE_NAME, Debug, "Received {}", msg);
             let mut pong_msg = obj;
             pong_msg.insert("op".to_string(), serde_json::from_str("\"pong\"").unwrap()); // change ping to pong
             let ws_msg = Message::Text(serde_json::to_string(&pong_msg).unwrap());
@@ -152,27 +158,27 @@ impl HuobiWSClient {
         } else {
             if let Some(status) = obj.get("status") {
                 match status.as_str().unwrap() {
-                    "ok" => info!("Received {} from {}", msg, EXCHANGE_NAME),
+                    "ok" => exchange_log!(EXCHANGE_NAME, Info, "Received {}", msg),
                     "error" => {
-                        error!("Received {} from {}", msg, EXCHANGE_NAME);
+                        exchange_log!(EXCHANGE_NAME, Error, "Received {}", msg);
                         let err_msg = obj.get("err-msg").unwrap().as_str().unwrap();
                         if err_msg.starts_with("invalid") {
                             panic!("Received {} from {}", msg, EXCHANGE_NAME);
                         }
                     }
-                    _ => warn!("Received {} from {}", msg, EXCHANGE_NAME),
+                    _ => exchange_log!(EXCHANGE_NAME, Warn, "Received {}", msg),
                 }
             } else if let Some(op) = obj.get("op") {
                 match op.as_str().unwrap() {
                     "sub" | "unsub" => MiscMessage::Misc,
                     "notify" => MiscMessage::Normal,
                     _ => {
-                        warn!("Received {} from {}", msg, EXCHANGE_NAME);
+                        exchange_log!(EXCHANGE_NAME, Warn, "Received {}", msg);
                         MiscMessage::Misc
                     }
                 };
             } else {
-                warn!("Received {} from {}", msg, EXCHANGE_NAME);
+                exchange_log!(EXCHANGE_NAME, Warn, "Received {}", msg);
             }
             MiscMessage::Misc
         }
@@ -193,7 +199,7 @@ impl_trait!(BBO, HuobiWSClient, subscribe_bbo, "bbo", to_raw_channel);
 impl_trait!(OrderBookTopK, HuobiWSClient, subscribe_orderbook_topk, "depth.step7", to_raw_channel);
 
 impl OrderBook for HuobiWSClient {
-    fn subscribe_orderbook(&self, pairs: &[String]) {
+    fn subscribe_orderbook(&self, pairs: &[String]) -> Result<(), UnsupportedChannel> {
         let pair_to_raw_channel = |pair: &String| {
             format!(
                 r#"{{"sub": "market.{}.depth.size_20.high_freq","data_type":"incremental","id": "crypto-ws-client"}}"#,
@@ -206,23 +212,27 @@ impl OrderBook for HuobiWSClient {
             .map(pair_to_raw_channel)
             .collect::<Vec<String>>();
         self.client.subscribe(&channels);
+        Ok(())
     }
 }
 
-fn to_candlestick_raw_channel(pair: &str, interval: usize) -> String {
+fn to_candlestick_raw_channel(
+    pair: &str,
+    interval: Interval,
+) -> Result<String, UnsupportedChannel> {
     let interval_str = match interval {
-        60 => "1min",
-        300 => "5min",
-        900 => "15min",
-        1800 => "30min",
-        3600 => "60min",
-        14400 => "4hour",
-        86400 => "1day",
-        604800 => "1week",
-        2592000 => "1mon",
-        _ => panic!("Huobi has intervals 1min,5min,15min,30min,60min,4hour,1day,1week,1mon"),
+        Interval::Min1 => "1min",
+        Interval::Min5 => "5min",
+        Interval::Min15 => "15min",
+        Interval::Min30 => "30min",
+        Interval::Hour1 => "60min",
+        Interval::Hour4 => "4hour",
+        Interval::Day1 => "1day",
+        Interval::Week1 => "1week",
+        Interval::Month1 => "1mon",
+        _ => return Err(UnsupportedChannel::interval(EXCHANGE_NAME, interval)),
     };
-    format!("market.{}.kline.{}", pair, interval_str)
+    Ok(format!("market.{}.kline.{}", pair, interval_str))
 }
 
 impl_candlestick!(HuobiWSClient);
@@ -238,43 +248,126 @@ macro_rules! define_market_client {
             /// * `on_msg` - A callback function to process websocket messages
             /// * `url` - Optional server url, usually you don't need specify it
             pub fn new(tx: Sender<String>, url: Option<&str>) -> Self {
-                let real_url = match url {
-                    Some(endpoint) => endpoint,
-                    None => $default_url,
+                let urls: Vec<String> = url.map(|url| vec![url.to_string()]).unwrap_or_default();
+                <$struct_name as crate::FromUrls>::from_urls(tx, &urls)
+            }
+        }
+
+        impl crate::FromUrls for $struct_name {
+            fn from_urls(tx: Sender<String>, urls: &[String]) -> Self {
+                let urls = if urls.is_empty() {
+                    vec![$default_url.to_string()]
+                } else {
+                    urls.to_vec()
                 };
                 $struct_name {
-                    client: HuobiWSClient::new(real_url, tx),
+                    client: HuobiWSClient::new(urls, tx),
                 }
             }
         }
 
         impl WSClient for $struct_name {
-            fn subscribe_trade(&self, channels: &[String]) {
-                <$struct_name as Trade>::subscribe_trade(self, channels);
+            fn subscribe_trade(&self, channels: &[String]) -> Result<(), UnsupportedChannel> {
+                <$struct_name as Trade>::subscribe_trade(self, channels)
             }
 
-            fn subscribe_orderbook(&self, channels: &[String]) {
-                <$struct_name as OrderBook>::subscribe_orderbook(self, channels);
+            fn subscribe_orderbook(&self, channels: &[String]) -> Result<(), UnsupportedChannel> {
+                <$struct_name as OrderBook>::subscribe_orderbook(self, channels)
             }
 
-            fn subscribe_orderbook_topk(&self, channels: &[String]) {
-                <$struct_name as OrderBookTopK>::subscribe_orderbook_topk(self, channels);
+            fn subscribe_orderbook_topk(
+                &self,
+                channels: &[String],
+            ) -> Result<(), UnsupportedChannel> {
+                <$struct_name as OrderBookTopK>::subscribe_orderbook_topk(self, channels)
             }
 
-            fn subscribe_l3_orderbook(&self, channels: &[String]) {
-                <$struct_name as Level3OrderBook>::subscribe_l3_orderbook(self, channels);
+            fn subscribe_l3_orderbook(
+                &self,
+                channels: &[String],
+            ) -> Result<(), UnsupportedChannel> {
+                <$struct_name as Level3OrderBook>::subscribe_l3_orderbook(self, channels)
             }
 
-            fn subscribe_ticker(&self, channels: &[String]) {
-                <$struct_name as Ticker>::subscribe_ticker(self, channels);
+            fn subscribe_ticker(&self, channels: &[String]) -> Result<(), UnsupportedChannel> {
+                <$struct_name as Ticker>::subscribe_ticker(self, channels)
             }
 
-            fn subscribe_bbo(&self, channels: &[String]) {
-                <$struct_name as BBO>::subscribe_bbo(self, channels);
+            fn subscribe_bbo(&self, channels: &[String]) -> Result<(), UnsupportedChannel> {
+                <$struct_name as BBO>::subscribe_bbo(self, channels)
             }
 
-            fn subscribe_candlestick(&self, symbol_interval_list: &[(String, usize)]) {
-                <$struct_name as Candlestick>::subscribe_candlestick(self, symbol_interval_list);
+            fn subscribe_candlestick(
+                &self,
+                symbol_interval_list: &[(String, Interval)],
+            ) -> Result<(), UnsupportedChannel> {
+                <$struct_name as Candlestick>::subscribe_candlestick(self, symbol_interval_list)
+            }
+
+            fn unsubscribe_trade(&self, channels: &[String]) -> Result<(), UnsupportedChannel> {
+                let raw_channels = self.client.client.collect_channels(|| {
+                    <$struct_name as Trade>::subscribe_trade(self, channels)
+                })?;
+                self.client.client.unsubscribe(&raw_channels);
+                Ok(())
+            }
+
+            fn unsubscribe_orderbook(&self, channels: &[String]) -> Result<(), UnsupportedChannel> {
+                let raw_channels = self.client.client.collect_channels(|| {
+                    <$struct_name as OrderBook>::subscribe_orderbook(self, channels)
+                })?;
+                self.client.client.unsubscribe(&raw_channels);
+                Ok(())
+            }
+
+            fn unsubscribe_orderbook_topk(
+                &self,
+                channels: &[String],
+            ) -> Result<(), UnsupportedChannel> {
+                let raw_channels = self.client.client.collect_channels(|| {
+                    <$struct_name as OrderBookTopK>::subscribe_orderbook_topk(self, channels)
+                })?;
+                self.client.client.unsubscribe(&raw_channels);
+                Ok(())
+            }
+
+            fn unsubscribe_l3_orderbook(
+                &self,
+                channels: &[String],
+            ) -> Result<(), UnsupportedChannel> {
+                let raw_channels = self.client.client.collect_channels(|| {
+                    <$struct_name as Level3OrderBook>::subscribe_l3_orderbook(self, channels)
+                })?;
+                self.client.client.unsubscribe(&raw_channels);
+                Ok(())
+            }
+
+            fn unsubscribe_ticker(&self, channels: &[String]) -> Result<(), UnsupportedChannel> {
+                let raw_channels = self.client.client.collect_channels(|| {
+                    <$struct_name as Ticker>::subscribe_ticker(self, channels)
+                })?;
+                self.client.client.unsubscribe(&raw_channels);
+                Ok(())
+            }
+
+            fn unsubscribe_bbo(&self, channels: &[String]) -> Result<(), UnsupportedChannel> {
+                let raw_channels = self
+                    .client
+                    .client
+                    .collect_channels(|| <$struct_name as BBO>::subscribe_bbo(self, channels))?;
+                self.client.client.unsubscribe(&raw_channels);
+                Ok(())
+            }
+
+            fn unsubscribe_candlestick(
+                &self,
+                symbol_interval_list: &[(String, Interval)],
+            ) -> Result<(), UnsupportedChannel> {
+                let raw_channels = self.client.client.collect_channels(|| {
+                    <$struct_name as Candlestick>::subscribe_candlestick(self, symbol_interval_list)
+                })?;
+                self.client.client.unsubscribe(&raw_channels);
+                Ok(())
             }
 
             fn subscribe(&self, channels: &[String]) {
@@ -285,10 +378,52 @@ macro_rules! define_market_client {
                 self.client.client.unsubscribe(channels);
             }
 
+            fn set_max_command_bytes(&self, max_bytes: usize) {
+                self.client.client.set_max_command_bytes(max_bytes);
+            }
+
+            fn set_on_stale(&self, threshold: u64, on_stale: Option<fn(&str)>) {
+                self.client.client.set_on_stale(threshold, on_stale);
+            }
+
+            fn set_raw_sender(&self, raw_tx: Option<Sender<Vec<u8>>>, keep_text: bool) {
+                self.client.client.set_raw_sender(raw_tx, keep_text);
+            }
+
+            fn set_reset_on_reconnect(&self, reset_tx: Option<Sender<crate::ResetNotification>>) {
+                self.client.client.set_reset_on_reconnect(reset_tx);
+            }
+
+            fn set_max_frame_size(&self, max_frame_size: Option<usize>) {
+                self.client.client.set_max_frame_size(max_frame_size);
+            }
+
+            fn authenticate(
+                &self,
+                _api_key: &str,
+                _secret: &str,
+                _passphrase: &str,
+            ) -> Result<(), crate::AuthError> {
+                // private channels are served by a different endpoint, /ws/v2
+                Err(crate::AuthError::Unsupported(EXCHANGE_NAME.to_string()))
+            }
+
+            fn stats(&self) -> crate::WSClientStats {
+                self.client.client.stats()
+            }
+
+            fn subscriptions(&self) -> Vec<String> {
+                self.client.client.subscriptions()
+            }
+
             fn run(&self, duration: Option<u64>) {
                 self.client.client.run(duration);
             }
 
+            fn close_gracefully(&self) {
+                self.client.client.close_gracefully();
+            }
+
             fn close(&self) {
                 self.client.client.close();
             }
@@ -305,8 +440,8 @@ define_market_client!(HuobiOptionWSClient, OPTION_WEBSOCKET_URL);
 macro_rules! impl_trade {
     ($struct_name:ident) => {
         impl Trade for $struct_name {
-            fn subscribe_trade(&self, pairs: &[String]) {
-                self.client.subscribe_trade(pairs);
+            fn subscribe_trade(&self, pairs: &[String]) -> Result<(), UnsupportedChannel> {
+                self.client.subscribe_trade(pairs)
             }
         }
     };
@@ -321,8 +456,8 @@ impl_trade!(HuobiOptionWSClient);
 macro_rules! impl_ticker {
     ($struct_name:ident) => {
         impl Ticker for $struct_name {
-            fn subscribe_ticker(&self, pairs: &[String]) {
-                self.client.subscribe_ticker(pairs);
+            fn subscribe_ticker(&self, pairs: &[String]) -> Result<(), UnsupportedChannel> {
+                self.client.subscribe_ticker(pairs)
             }
         }
     };
@@ -337,8 +472,8 @@ impl_ticker!(HuobiOptionWSClient);
 macro_rules! impl_bbo {
     ($struct_name:ident) => {
         impl BBO for $struct_name {
-            fn subscribe_bbo(&self, pairs: &[String]) {
-                self.client.subscribe_bbo(pairs);
+            fn subscribe_bbo(&self, pairs: &[String]) -> Result<(), UnsupportedChannel> {
+                self.client.subscribe_bbo(pairs)
             }
         }
     };
@@ -353,8 +488,8 @@ impl_bbo!(HuobiOptionWSClient);
 macro_rules! impl_orderbook {
     ($struct_name:ident) => {
         impl OrderBook for $struct_name {
-            fn subscribe_orderbook(&self, pairs: &[String]) {
-                self.client.subscribe_orderbook(pairs);
+            fn subscribe_orderbook(&self, pairs: &[String]) -> Result<(), UnsupportedChannel> {
+                self.client.subscribe_orderbook(pairs)
             }
         }
     };
@@ -365,9 +500,9 @@ impl_orderbook!(HuobiInverseSwapWSClient);
 impl_orderbook!(HuobiLinearSwapWSClient);
 impl_orderbook!(HuobiOptionWSClient);
 impl OrderBook for HuobiSpotWSClient {
-    fn subscribe_orderbook(&self, pairs: &[String]) {
-        if self.client.client.url.as_str() == "wss://api.huobi.pro/feed"
-            || self.client.client.url.as_str() == "wss://api-aws.huobi.pro/feed"
+    fn subscribe_orderbook(&self, pairs: &[String]) -> Result<(), UnsupportedChannel> {
+        if self.client.client.url() == "wss://api.huobi.pro/feed"
+            || self.client.client.url() == "wss://api-aws.huobi.pro/feed"
         {
             let pair_to_raw_channel = |pair: &String| to_raw_channel("mbp.20", pair);
 
@@ -379,6 +514,7 @@ impl OrderBook for HuobiSpotWSClient {
         } else {
             panic!("Huobi Spot market.$symbol.mbp.$levels must use wss://api.huobi.pro/feed or wss://api-aws.huobi.pro/feed");
         }
+        Ok(())
     }
 }
 
@@ -391,8 +527,8 @@ impl_candlestick!(HuobiOptionWSClient);
 macro_rules! impl_orderbook_snapshot {
     ($struct_name:ident) => {
         impl OrderBookTopK for $struct_name {
-            fn subscribe_orderbook_topk(&self, pairs: &[String]) {
-                self.client.subscribe_orderbook_topk(pairs);
+            fn subscribe_orderbook_topk(&self, pairs: &[String]) -> Result<(), UnsupportedChannel> {
+                self.client.subscribe_orderbook_topk(pairs)
             }
         }
     };
@@ -405,14 +541,18 @@ impl_orderbook_snapshot!(HuobiInverseSwapWSClient);
 impl_orderbook_snapshot!(HuobiLinearSwapWSClient);
 impl_orderbook_snapshot!(HuobiOptionWSClient);
 
-panic_l3_orderbook!(HuobiSpotWSClient);
-panic_l3_orderbook!(HuobiFutureWSClient);
-panic_l3_orderbook!(HuobiInverseSwapWSClient);
-panic_l3_orderbook!(HuobiLinearSwapWSClient);
-panic_l3_orderbook!(HuobiOptionWSClient);
+unsupported_l3_orderbook!(HuobiSpotWSClient);
+unsupported_l3_orderbook!(HuobiFutureWSClient);
+unsupported_l3_orderbook!(HuobiInverseSwapWSClient);
+unsupported_l3_orderbook!(HuobiLinearSwapWSClient);
+unsupported_l3_orderbook!(HuobiOptionWSClient);
 
 #[cfg(test)]
 mod tests {
+    use super::super::mock_server::MockClient;
+    use crate::{clients::mock_server::assert_unsupported, UnsupportedChannel, WSClient};
+    use tungstenite::Message;
+
     #[test]
     fn test_channel_to_command() {
         assert_eq!(
@@ -425,4 +565,59 @@ mod tests {
             super::HuobiWSClient::channel_to_command("market.btcusdt.trade.detail", false)
         );
     }
+
+    #[test]
+    fn test_numeric_ping() {
+        let mock = MockClient::start(
+            super::EXCHANGE_NAME,
+            super::HuobiWSClient::on_misc_msg,
+            super::HuobiWSClient::channels_to_commands,
+            Some(super::SERVER_PING_INTERVAL),
+            vec![vec![
+                Message::Text(r#"{"ping":123}"#.to_string()),
+                Message::Text(
+                    r#"{"ch":"market.btcusdt.trade.detail","ts":1,"tick":{}}"#.to_string(),
+                ),
+            ]],
+        );
+        mock.client.run(Some(0));
+        let (_, received) = mock.finish();
+
+        // the same value is echoed back
+        assert_eq!(Message::Text(r#"{"pong":123}"#.to_string()), received[0]);
+    }
+
+    #[test]
+    fn test_unsupported_channel() {
+        assert_unsupported(
+            |client: &super::HuobiSpotWSClient| {
+                client.subscribe_l3_orderbook(&["BTCUSDT".to_string()])
+            },
+            UnsupportedChannel::new(super::EXCHANGE_NAME, "level3 orderbook"),
+        );
+        assert_unsupported(
+            |client: &super::HuobiFutureWSClient| {
+                client.subscribe_l3_orderbook(&["BTCUSDT".to_string()])
+            },
+            UnsupportedChannel::new(super::EXCHANGE_NAME, "level3 orderbook"),
+        );
+        assert_unsupported(
+            |client: &super::HuobiInverseSwapWSClient| {
+                client.subscribe_l3_orderbook(&["BTCUSDT".to_string()])
+            },
+            UnsupportedChannel::new(super::EXCHANGE_NAME, "level3 orderbook"),
+        );
+        assert_unsupported(
+            |client: &super::HuobiLinearSwapWSClient| {
+                client.subscribe_l3_orderbook(&["BTCUSDT".to_string()])
+            },
+            UnsupportedChannel::new(super::EXCHANGE_NAME, "level3 orderbook"),
+        );
+        assert_unsupported(
+            |client: &super::HuobiOptionWSClient| {
+                client.subscribe_l3_orderbook(&["BTCUSDT".to_string()])
+            },
+            UnsupportedChannel::new(super::EXCHANGE_NAME, "level3 orderbook"),
+        );
+    }
 }
@@ -1,16 +1,15 @@
-use crate::WSClient;
+use crate::{Interval, UnsupportedChannel, WSClient};
 use std::collections::HashMap;
 use std::sync::mpsc::Sender;
+use std::time::Duration;
 
 use super::{
     utils::CHANNEL_PAIR_DELIMITER,
     ws_client_internal::{MiscMessage, WSClientInternal},
-    Candlestick, Level3OrderBook, OrderBook, OrderBookTopK, Ticker, Trade, BBO,
+    Authenticate, Candlestick, Level3OrderBook, OrderBook, OrderBookTopK, Ticker, Trade, BBO,
 };
 
-use log::*;
 use serde_json::Value;
-use tungstenite::Message;
 
 pub(super) const EXCHANGE_NAME: &str = "kraken";
 
@@ -20,6 +19,9 @@ const WEBSOCKET_URL: &str = "wss://ws.kraken.com";
 // https://docs.kraken.com/websockets/#message-ping
 const CLIENT_PING_INTERVAL_AND_MSG: (u64, &str) = (10, r#"{"event":"ping"}"#);
 
+// How long to wait before reconnecting when systemStatus is maintenance
+const MAINTENANCE_WAIT: Duration = Duration::from_secs(20);
+
 /// The WebSocket client for Kraken.
 ///
 /// Kraken has only Spot market.
@@ -71,64 +73,78 @@ fn channels_to_commands(channels: &[String], subscribe: bool) -> Vec<String> {
     all_commands
 }
 
-fn on_misc_msg(msg: &str) -> MiscMessage {
+pub(super) fn on_misc_msg(msg: &str) -> MiscMessage {
+    on_misc_msg_with_wait(msg, MAINTENANCE_WAIT)
+}
+
+// `maintenance_wait` is how long to sleep before reconnecting on maintenance
+fn on_misc_msg_with_wait(msg: &str, maintenance_wait: Duration) -> MiscMessage {
     let resp = serde_json::from_str::<Value>(msg);
     if resp.is_err() {
-        error!("{} is not a JSON string, {}", msg, EXCHANGE_NAME);
+        exchange_log!(EXCHANGE_NAME, Error, "{} is not a JSON string", msg);
         return MiscMessage::Misc;
     }
     let value = resp.unwrap();
 
     if value.is_object() {
         let obj = value.as_object().unwrap();
-        let event = obj.get("event").unwrap().as_str().unwrap();
+        let event = obj
+            .get("event")
+            .and_then(|x| x.as_str())
+            .unwrap_or_default();
         match event {
-            "heartbeat" => {
-                debug!("Received {} from {}", msg, EXCHANGE_NAME);
-                let ping = r#"{
-                    "event": "ping",
-                    "reqid": 9527
-                }"#;
-                MiscMessage::WebSocket(Message::Text(ping.to_string()))
-            }
+            // sent when no data arrives for 1 second, it proves the connection
+            // is alive just like a pong, so it resets num_unanswered_ping too
+            "heartbeat" => MiscMessage::Pong,
             "pong" => MiscMessage::Pong,
             "subscriptionStatus" => {
                 let status = obj.get("status").unwrap().as_str().unwrap();
                 match status {
                     "subscribed" | "unsubscribed" => {
-                        info!("Received {} from {}", msg, EXCHANGE_NAME)
+                        exchange_log!(EXCHANGE_NAME, Info, "Received {}", msg)
                     }
                     "error" => {
-                        error!("Received {} from {}", msg, EXCHANGE_NAME);
+                        exchange_log!(EXCHANGE_NAME, Error, "Received {}", msg);
                         let error_msg = obj.get("errorMessage").unwrap().as_str().unwrap();
                         if error_msg.starts_with("Currency pair not supported") {
                             panic!("Received {} from {}", msg, EXCHANGE_NAME)
                         }
                     }
-                    _ => warn!("Received {} from {}", msg, EXCHANGE_NAME),
+                    _ => exchange_log!(EXCHANGE_NAME, Warn, "Received {}", msg),
                 }
 
                 MiscMessage::Misc
             }
             "systemStatus" => {
-                let status = obj.get("status").unwrap().as_str().unwrap();
+                let status = obj
+                    .get("status")
+                    .and_then(|x| x.as_str())
+                    .unwrap_or_default();
                 match status {
-                    "maintenance" | "cancel_only" => {
-                        warn!(
+                    "online" => {
+                        exchange_log!(EXCHANGE_NAME, Info, "Received {}", msg);
+                        MiscMessage::Misc
+                    }
+                    "maintenance" => {
+                        exchange_log!(
+                            EXCHANGE_NAME,
+                            Warn,
                             "Received {}, which means Kraken is in maintenance mode",
                             msg
                         );
-                        std::thread::sleep(std::time::Duration::from_secs(20));
+                        std::thread::sleep(maintenance_wait);
                         MiscMessage::Reconnect
                     }
+                    // cancel_only, post_only and limit_only restrict trading,
+                    // market data keeps flowing
                     _ => {
-                        info!("Received {} from {}", msg, EXCHANGE_NAME);
+                        exchange_log!(EXCHANGE_NAME, Warn, "Received {}", msg);
                         MiscMessage::Misc
                     }
                 }
             }
             _ => {
-                warn!("Received {} from {}", msg, EXCHANGE_NAME);
+                exchange_log!(EXCHANGE_NAME, Warn, "Received {}", msg);
                 MiscMessage::Misc
             }
         }
@@ -149,7 +165,7 @@ impl_trait!(Ticker, KrakenWSClient, subscribe_ticker, "ticker", to_raw_channel);
 impl_trait!(BBO, KrakenWSClient, subscribe_bbo, "spread", to_raw_channel);
 
 impl OrderBook for KrakenWSClient {
-    fn subscribe_orderbook(&self, pairs: &[String]) {
+    fn subscribe_orderbook(&self, pairs: &[String]) -> Result<(), UnsupportedChannel> {
         let command = format!(
             r#"{{"event":"subscribe","pair":{},"subscription":{{"name":"book", "depth":25}}}}"#,
             serde_json::to_string(pairs).unwrap(),
@@ -157,69 +173,71 @@ impl OrderBook for KrakenWSClient {
         let channels = vec![command];
 
         self.client.subscribe(&channels);
+        Ok(())
     }
 }
 
 impl OrderBookTopK for KrakenWSClient {
-    fn subscribe_orderbook_topk(&self, _pairs: &[String]) {
-        panic!("Kraken does NOT have orderbook snapshot channel");
+    fn subscribe_orderbook_topk(&self, _pairs: &[String]) -> Result<(), UnsupportedChannel> {
+        Err(UnsupportedChannel::new(
+            EXCHANGE_NAME,
+            "level2 top-k snapshot",
+        ))
+    }
+}
+
+// Kraken intervals are in minutes
+fn to_kraken_interval(interval: Interval) -> Result<usize, UnsupportedChannel> {
+    match interval {
+        Interval::Min1 => Ok(1),
+        Interval::Min5 => Ok(5),
+        Interval::Min15 => Ok(15),
+        Interval::Min30 => Ok(30),
+        Interval::Hour1 => Ok(60),
+        Interval::Hour4 => Ok(240),
+        Interval::Day1 => Ok(1440),
+        Interval::Week1 => Ok(10080),
+        Interval::Day15 => Ok(21600),
+        _ => Err(UnsupportedChannel::interval(EXCHANGE_NAME, interval)),
     }
 }
 
 fn convert_symbol_interval_list(
-    symbol_interval_list: &[(String, usize)],
-) -> Vec<(Vec<String>, usize)> {
+    symbol_interval_list: &[(String, Interval)],
+) -> Result<Vec<(Vec<String>, usize)>, UnsupportedChannel> {
     let mut map = HashMap::<usize, Vec<String>>::new();
     for task in symbol_interval_list {
-        let v = map.entry(task.1).or_insert_with(Vec::new);
+        let v = map
+            .entry(to_kraken_interval(task.1)?)
+            .or_insert_with(Vec::new);
         v.push(task.0.clone());
     }
     let mut result = Vec::new();
     for (k, v) in map {
         result.push((v, k));
     }
-    result
+    Ok(result)
 }
 
 impl Candlestick for KrakenWSClient {
-    fn subscribe_candlestick(&self, symbol_interval_list: &[(String, usize)]) {
-        let valid_set: Vec<usize> = vec![1, 5, 15, 30, 60, 240, 1440, 10080, 21600]
-            .into_iter()
-            .map(|x| x * 60)
-            .collect();
-        let invalid_intervals = symbol_interval_list
-            .iter()
-            .map(|(_, interval)| *interval)
-            .filter(|x| !valid_set.contains(x))
-            .collect::<Vec<usize>>();
-        if !invalid_intervals.is_empty() {
-            panic!(
-                "Invalid intervals: {}, available intervals: {}",
-                invalid_intervals
-                    .into_iter()
-                    .map(|x| x.to_string())
-                    .collect::<Vec<String>>()
-                    .join(","),
-                valid_set
-                    .into_iter()
-                    .map(|x| x.to_string())
-                    .collect::<Vec<String>>()
-                    .join(",")
-            );
-        }
-        let symbols_interval_list = convert_symbol_interval_list(symbol_interval_list);
+    fn subscribe_candlestick(
+        &self,
+        symbol_interval_list: &[(String, Interval)],
+    ) -> Result<(), UnsupportedChannel> {
+        let symbols_interval_list = convert_symbol_interval_list(symbol_interval_list)?;
 
         let commands: Vec<String> = symbols_interval_list.into_iter().map(|(symbols, interval)| format!(
             r#"{{"event":"subscribe","pair":{},"subscription":{{"name":"ohlc", "interval":{}}}}}"#,
             serde_json::to_string(&symbols).unwrap(),
-            interval / 60
+            interval
         )).collect();
 
         self.client.subscribe(&commands);
+        Ok(())
     }
 }
 
-panic_l3_orderbook!(KrakenWSClient);
+unsupported_l3_orderbook!(KrakenWSClient);
 
 impl_new_constructor!(
     KrakenWSClient,
@@ -230,10 +248,13 @@ impl_new_constructor!(
     Some(CLIENT_PING_INTERVAL_AND_MSG),
     None
 );
+unsupported_authenticate!(KrakenWSClient);
 impl_ws_client_trait!(KrakenWSClient);
 
 #[cfg(test)]
 mod tests {
+    use crate::{clients::mock_server::assert_unsupported, UnsupportedChannel, WSClient};
+
     #[test]
     fn test_one_pair() {
         assert_eq!(
@@ -267,4 +288,55 @@ mod tests {
             )
         );
     }
+
+    #[test]
+    fn test_misc_messages() {
+        use super::MiscMessage;
+        use std::time::Duration;
+
+        let on_misc_msg = |msg: &str| super::on_misc_msg_with_wait(msg, Duration::ZERO);
+
+        assert!(matches!(
+            on_misc_msg(r#"{"event":"heartbeat"}"#),
+            MiscMessage::Pong
+        ));
+        assert!(matches!(
+            on_misc_msg(r#"{"event":"pong","reqid":9527}"#),
+            MiscMessage::Pong
+        ));
+        assert!(matches!(
+            on_misc_msg(
+                r#"{"connectionID":8628615390848610000,"event":"systemStatus","status":"online","version":"1.0.0"}"#
+            ),
+            MiscMessage::Misc
+        ));
+        assert!(matches!(
+            on_misc_msg(
+                r#"{"connectionID":8628615390848610000,"event":"systemStatus","status":"cancel_only","version":"1.0.0"}"#
+            ),
+            MiscMessage::Misc
+        ));
+        assert!(matches!(
+            on_misc_msg(
+                r#"{"connectionID":8628615390848610000,"event":"systemStatus","status":"maintenance","version":"1.0.0"}"#
+            ),
+            MiscMessage::Reconnect
+        ));
+        assert!(matches!(
+            on_misc_msg(
+                r#"[0,[["5541.20000","0.15850568","1534614057.321597","s","l",""]],"trade","XBT/USD"]"#
+            ),
+            MiscMessage::Normal
+        ));
+    }
+
+    #[test]
+    fn test_unsupported_channel() {
+        assert_unsupported(
+            |client: &super::KrakenWSClient| {
+                client.subscribe_orderbook_topk(&["BTCUSDT".to_string()])
+            },
+            UnsupportedChannel::new(super::EXCHANGE_NAME, "level2 top-k snapshot"),
+        );
+    }
 }
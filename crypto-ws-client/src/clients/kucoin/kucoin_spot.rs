@@ -1,8 +1,10 @@
-use crate::WSClient;
+use crate::{FromUrls, Interval, UnsupportedChannel, WSClient};
 use std::sync::mpsc::Sender;
 
 use super::super::ws_client_internal::WSClientInternal;
-use super::super::{Candlestick, Level3OrderBook, OrderBook, OrderBookTopK, Ticker, Trade, BBO};
+use super::super::{
+    Authenticate, Candlestick, Level3OrderBook, OrderBook, OrderBookTopK, Ticker, Trade, BBO,
+};
 use super::utils::{
     channels_to_commands, fetch_ws_token, on_misc_msg, to_raw_channel,
     CLIENT_PING_INTERVAL_AND_MSG, EXCHANGE_NAME,
@@ -24,18 +26,24 @@ impl KuCoinSpotWSClient {
     /// * `tx` - The sending part of a channel
     /// * `url` - Optional server url, usually you don't need specify it
     pub fn new(tx: Sender<String>, url: Option<&str>) -> Self {
-        let real_url = match url {
-            Some(endpoint) => endpoint.to_string(),
-            None => {
-                let ws_token = fetch_ws_token();
-                let ws_url = format!("{}?token={}", ws_token.endpoint, ws_token.token);
-                ws_url
-            }
+        let urls: Vec<String> = url.map(|url| vec![url.to_string()]).unwrap_or_default();
+        <Self as FromUrls>::from_urls(tx, &urls)
+    }
+}
+
+impl FromUrls for KuCoinSpotWSClient {
+    fn from_urls(tx: Sender<String>, urls: &[String]) -> Self {
+        let urls = if urls.is_empty() {
+            let ws_token = fetch_ws_token();
+            let ws_url = format!("{}?token={}", ws_token.endpoint, ws_token.token);
+            vec![ws_url]
+        } else {
+            urls.to_vec()
         };
         KuCoinSpotWSClient {
             client: WSClientInternal::new(
                 EXCHANGE_NAME,
-                &real_url,
+                urls,
                 tx,
                 on_misc_msg,
                 channels_to_commands,
@@ -57,41 +65,59 @@ impl_trait!(OrderBookTopK, KuCoinSpotWSClient, subscribe_orderbook_topk, "/spotM
 #[rustfmt::skip]
 impl_trait!(Ticker, KuCoinSpotWSClient, subscribe_ticker, "/market/snapshot", to_raw_channel);
 
-fn to_candlestick_raw_channel(pair: &str, interval: usize) -> String {
+fn to_candlestick_raw_channel(
+    pair: &str,
+    interval: Interval,
+) -> Result<String, UnsupportedChannel> {
     let interval_str = match interval {
-        60 => "1min",
-        180 => "3min",
-        300 => "5min",
-        900 => "15min",
-        1800 => "30min",
-        3600 => "1hour",
-        7200 => "2hour",
-        14400 => "4hour",
-        21600 => "6hour",
-        28800 => "8hour",
-        43200 => "12hour",
-        86400 => "1day",
-        604800 => "1week",
-        _ => panic!(
-            "KuCoin available intervals 1min,3min,5min,15min,30min,1hour,2hour,4hour,6hour,8hour,12hour,1day,1week"
-        ),
+        Interval::Min1 => "1min",
+        Interval::Min3 => "3min",
+        Interval::Min5 => "5min",
+        Interval::Min15 => "15min",
+        Interval::Min30 => "30min",
+        Interval::Hour1 => "1hour",
+        Interval::Hour2 => "2hour",
+        Interval::Hour4 => "4hour",
+        Interval::Hour6 => "6hour",
+        Interval::Hour8 => "8hour",
+        Interval::Hour12 => "12hour",
+        Interval::Day1 => "1day",
+        Interval::Week1 => "1week",
+        _ => return Err(UnsupportedChannel::interval(EXCHANGE_NAME, interval)),
     };
-    format!(
+    Ok(format!(
         r#"{{"id":"crypto-ws-client","type":"subscribe","topic":"/market/candles:{}_{}","privateChannel":false,"response":true}}"#,
         pair, interval_str,
-    )
+    ))
 }
 
 impl_candlestick!(KuCoinSpotWSClient);
 
 impl Level3OrderBook for KuCoinSpotWSClient {
-    fn subscribe_l3_orderbook(&self, symbols: &[String]) {
+    fn subscribe_l3_orderbook(&self, symbols: &[String]) -> Result<(), UnsupportedChannel> {
         let raw_channels: Vec<String> = symbols
             .iter()
             .map(|symbol| to_raw_channel("/spotMarket/level3", symbol))
             .collect();
         self.client.subscribe(&raw_channels);
+        Ok(())
     }
 }
 
+unsupported_authenticate!(KuCoinSpotWSClient);
 impl_ws_client_trait!(KuCoinSpotWSClient);
+
+#[cfg(test)]
+mod tests {
+    use crate::{clients::mock_server::assert_unsupported, Interval, UnsupportedChannel, WSClient};
+
+    #[test]
+    fn test_unsupported_channel() {
+        assert_unsupported(
+            |client: &super::KuCoinSpotWSClient| {
+                client.subscribe_candlestick(&[("BTCUSDT".to_string(), Interval::Sec10)])
+            },
+            UnsupportedChannel::interval(super::EXCHANGE_NAME, Interval::Sec10),
+        );
+    }
+}
@@ -1,8 +1,10 @@
-use crate::WSClient;
+use crate::{FromUrls, Interval, UnsupportedChannel, WSClient};
 use std::sync::mpsc::Sender;
 
 use super::super::ws_client_internal::WSClientInternal;
-use super::super::{Candlestick, Level3OrderBook, OrderBook, OrderBookTopK, Ticker, Trade, BBO};
+use super::super::{
+    Authenticate, Candlestick, Level3OrderBook, OrderBook, OrderBookTopK, Ticker, Trade, BBO,
+};
 use super::utils::{
     channels_to_commands, fetch_ws_token, on_misc_msg, to_raw_channel,
     CLIENT_PING_INTERVAL_AND_MSG, EXCHANGE_NAME,
@@ -24,18 +26,24 @@ impl KuCoinSwapWSClient {
     /// * `tx` - The sending part of a channel
     /// * `url` - Optional server url, usually you don't need specify it
     pub fn new(tx: Sender<String>, url: Option<&str>) -> Self {
-        let real_url = match url {
-            Some(endpoint) => endpoint.to_string(),
-            None => {
-                let ws_token = fetch_ws_token();
-                let ws_url = format!("{}?token={}", ws_token.endpoint, ws_token.token);
-                ws_url
-            }
+        let urls: Vec<String> = url.map(|url| vec![url.to_string()]).unwrap_or_default();
+        <Self as FromUrls>::from_urls(tx, &urls)
+    }
+}
+
+impl FromUrls for KuCoinSwapWSClient {
+    fn from_urls(tx: Sender<String>, urls: &[String]) -> Self {
+        let urls = if urls.is_empty() {
+            let ws_token = fetch_ws_token();
+            let ws_url = format!("{}?token={}", ws_token.endpoint, ws_token.token);
+            vec![ws_url]
+        } else {
+            urls.to_vec()
         };
         KuCoinSwapWSClient {
             client: WSClientInternal::new(
                 EXCHANGE_NAME,
-                &real_url,
+                urls,
                 tx,
                 on_misc_msg,
                 channels_to_commands,
@@ -57,35 +65,58 @@ impl_trait!(OrderBookTopK, KuCoinSwapWSClient, subscribe_orderbook_topk, "/contr
 #[rustfmt::skip]
 impl_trait!(Ticker, KuCoinSwapWSClient, subscribe_ticker, "/contractMarket/snapshot", to_raw_channel);
 
-fn to_candlestick_raw_channel(pair: &str, interval: usize) -> String {
-    let valid_set: Vec<usize> = vec![
-        60, 300, 900, 1800, 3600, 7200, 14400, 28800, 43200, 86400, 604800,
-    ];
-    if !valid_set.contains(&interval) {
-        let joined = valid_set
-            .into_iter()
-            .map(|x| x.to_string())
-            .collect::<Vec<String>>()
-            .join(",");
-        panic!("KuCoin Swap available intervals {}", joined);
+fn to_candlestick_raw_channel(
+    pair: &str,
+    interval: Interval,
+) -> Result<String, UnsupportedChannel> {
+    match interval {
+        Interval::Min1
+        | Interval::Min5
+        | Interval::Min15
+        | Interval::Min30
+        | Interval::Hour1
+        | Interval::Hour2
+        | Interval::Hour4
+        | Interval::Hour8
+        | Interval::Hour12
+        | Interval::Day1
+        | Interval::Week1 => (),
+        _ => return Err(UnsupportedChannel::interval(EXCHANGE_NAME, interval)),
     }
-    format!(
+    Ok(format!(
         r#"{{"id":"crypto-ws-client","type":"subscribe","topic":"/contractMarket/candle:{}_{}","privateChannel":false,"response":true}}"#,
         pair,
-        interval / 60,
-    )
+        interval.as_secs() / 60,
+    ))
 }
 
 impl_candlestick!(KuCoinSwapWSClient);
 
 impl Level3OrderBook for KuCoinSwapWSClient {
-    fn subscribe_l3_orderbook(&self, symbols: &[String]) {
+    fn subscribe_l3_orderbook(&self, symbols: &[String]) -> Result<(), UnsupportedChannel> {
         let raw_channels: Vec<String> = symbols
             .iter()
             .map(|symbol| to_raw_channel("/contractMarket/level3v2", symbol))
             .collect();
         self.client.subscribe(&raw_channels);
+        Ok(())
     }
 }
 
+unsupported_authenticate!(KuCoinSwapWSClient);
 impl_ws_client_trait!(KuCoinSwapWSClient);
+
+#[cfg(test)]
+mod tests {
+    use crate::{clients::mock_server::assert_unsupported, Interval, UnsupportedChannel, WSClient};
+
+    #[test]
+    fn test_unsupported_channel() {
+        assert_unsupported(
+            |client: &super::KuCoinSwapWSClient| {
+                client.subscribe_candlestick(&[("BTCUSDT".to_string(), Interval::Sec10)])
+            },
+            UnsupportedChannel::interval(super::EXCHANGE_NAME, Interval::Sec10),
+        );
+    }
+}
@@ -4,3 +4,12 @@ mod utils;
 
 pub use kucoin_spot::KuCoinSpotWSClient;
 pub use kucoin_swap::KuCoinSwapWSClient;
+
+use super::registry::ExchangeSpec;
+
+pub(super) const SPEC: ExchangeSpec = ExchangeSpec {
+    name: utils::EXCHANGE_NAME,
+    // Message limit sent to the server: 100 per 10 seconds
+    send_interval_ms: Some(100),
+    ..ExchangeSpec::DEFAULT
+};
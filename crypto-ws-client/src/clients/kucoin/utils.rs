@@ -1,6 +1,5 @@
 use std::collections::HashMap;
 
-use log::*;
 use reqwest::{header, Result};
 use serde_json::Value;
 
@@ -26,17 +25,15 @@ pub(super) struct WebsocketToken {
 }
 
 fn http_post(url: &str) -> Result<String> {
-    let mut headers = header::HeaderMap::new();
-    headers.insert(
-        header::CONTENT_TYPE,
-        header::HeaderValue::from_static("application/json"),
-    );
+    let mut headers = crypto_pair::headers(EXCHANGE_NAME);
+    headers
+        .entry(header::CONTENT_TYPE)
+        .or_insert(header::HeaderValue::from_static("application/json"));
 
     let client = reqwest::blocking::Client::builder()
-         .default_headers(headers)
-         .user_agent("Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/87.0.4280.88 Safari/537.36")
-         .gzip(true)
-         .build()?;
+        .default_headers(headers)
+        .gzip(true)
+        .build()?;
     let response = client.post(url).send()?;
 
     match response.error_for_status() {
@@ -75,20 +72,20 @@ pub(super) fn on_misc_msg(msg: &str) -> MiscMessage {
     match msg_type {
         "pong" => MiscMessage::Pong,
         "welcome" | "ack" => {
-            debug!("Received {} from {}", msg, EXCHANGE_NAME);
+            exchange_log!(EXCHANGE_NAME, Debug, "Received {}", msg);
             MiscMessage::Misc
         }
         "notice" | "command" => {
-            info!("Received {} from {}", msg, EXCHANGE_NAME);
+            exchange_log!(EXCHANGE_NAME, Info, "Received {}", msg);
             MiscMessage::Misc
         }
         "message" => MiscMessage::Normal,
         "error" => {
-            error!("Received {} from {}", msg, EXCHANGE_NAME);
+            exchange_log!(EXCHANGE_NAME, Error, "Received {}", msg);
             panic!("Received {} from {}", msg, EXCHANGE_NAME);
         }
         _ => {
-            error!("Received {} from {}", msg, EXCHANGE_NAME);
+            exchange_log!(EXCHANGE_NAME, Error, "Received {}", msg);
             panic!("Received {} from {}", msg, EXCHANGE_NAME);
         }
     }
@@ -0,0 +1,57 @@
+// Logging macros that attach the client context as structured key-values,
+// and log under the `crypto_ws_client::{exchange}` target, so that users can
+// filter one exchange, e.g., `RUST_LOG=crypto_ws_client::binance=debug`.
+
+// Logs with exchange, url and the number of subscribed channels.
+macro_rules! ws_log {
+    ($level:ident, $exchange:expr, $url:expr, $num_channels:expr, $($arg:tt)+) => {
+        log::log!(
+            target: &format!("crypto_ws_client::{}", $exchange),
+            log::Level::$level,
+            exchange = $exchange,
+            url = $url,
+            channels = $num_channels;
+            $($arg)+
+        )
+    };
+}
+
+// Logs with the context of a WSClientInternal.
+macro_rules! client_log {
+    ($client:expr, $level:ident, $($arg:tt)+) => {
+        ws_log!(
+            $level,
+            $client.exchange,
+            $client.url(),
+            $client.num_channels(),
+            $($arg)+
+        )
+    };
+}
+
+// Logs with the exchange only, for functions that don't own a connection,
+// e.g., on_misc_msg().
+macro_rules! exchange_log {
+    ($exchange:expr, $level:ident, $($arg:tt)+) => {
+        log::log!(
+            target: &format!("crypto_ws_client::{}", $exchange),
+            log::Level::$level,
+            exchange = $exchange;
+            $($arg)+
+        )
+    };
+}
+
+// Logs with exchange and url, for functions that own a connection but not
+// the subscribed channels, e.g., connect_with_retry().
+macro_rules! endpoint_log {
+    ($exchange:expr, $url:expr, $level:ident, $($arg:tt)+) => {
+        log::log!(
+            target: &format!("crypto_ws_client::{}", $exchange),
+            log::Level::$level,
+            exchange = $exchange,
+            url = $url;
+            $($arg)+
+        )
+    };
+}
@@ -0,0 +1,115 @@
+// A websocket server on localhost, so that tests can drive clients without network access.
+use super::ws_client_internal::{MiscMessage, WSClientInternal};
+use crate::{FromUrls, UnsupportedChannel, WSClient};
+use std::{
+    net::TcpListener,
+    sync::mpsc::{channel, Receiver},
+    thread::{self, JoinHandle},
+};
+use tungstenite::Message;
+
+pub(super) struct MockServer {
+    pub url: String,
+    handle: JoinHandle<Vec<Message>>,
+}
+
+impl MockServer {
+    /// Accepts one client, sends `frames` to it in order, then records
+    /// messages from the client until it disconnects.
+    pub fn start(frames: Vec<Message>) -> Self {
+        MockServer::start_sessions(vec![frames])
+    }
+
+    /// Like `start()`, but accepts one connection per session, e.g., to
+    /// serve a client that reconnects. Sessions are served concurrently.
+    pub fn start_sessions(sessions: Vec<Vec<Message>>) -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("ws://{}", listener.local_addr().unwrap());
+        let handle = thread::spawn(move || {
+            let handles: Vec<JoinHandle<Vec<Message>>> = sessions
+                .into_iter()
+                .map(|frames| {
+                    let (stream, _) = listener.accept().unwrap();
+                    thread::spawn(move || {
+                        let mut ws_stream = tungstenite::accept(stream).unwrap();
+                        for frame in frames {
+                            ws_stream.write_message(frame).unwrap();
+                        }
+                        let mut received = Vec::new();
+                        while let Ok(msg) = ws_stream.read_message() {
+                            received.push(msg);
+                        }
+                        received
+                    })
+                })
+                .collect();
+            handles
+                .into_iter()
+                .flat_map(|handle| handle.join().unwrap())
+                .collect()
+        });
+        MockServer { url, handle }
+    }
+
+    /// Waits for the client to disconnect and returns messages sent by it,
+    /// in the order of sessions.
+    pub fn received(self) -> Vec<Message> {
+        self.handle.join().unwrap()
+    }
+}
+
+/// A WSClientInternal connected to a MockServer.
+pub(super) struct MockClient {
+    pub client: WSClientInternal,
+    rx: Receiver<String>,
+    server: MockServer,
+}
+
+impl MockClient {
+    /// Starts a MockServer serving `sessions` and connects a client of
+    /// `exchange` to it, see `MockServer::start_sessions()`.
+    pub fn start(
+        exchange: &'static str,
+        on_misc_msg: fn(&str) -> MiscMessage,
+        channels_to_commands: fn(&[String], bool) -> Vec<String>,
+        server_ping_interval: Option<u64>,
+        sessions: Vec<Vec<Message>>,
+    ) -> Self {
+        let server = MockServer::start_sessions(sessions);
+        let (tx, rx) = channel();
+        let client = WSClientInternal::new(
+            exchange,
+            vec![server.url.clone()],
+            tx,
+            on_misc_msg,
+            channels_to_commands,
+            None,
+            server_ping_interval,
+        );
+        MockClient { client, rx, server }
+    }
+
+    /// Closes the client, returns messages forwarded to its sender and
+    /// messages received by the server.
+    pub fn finish(self) -> (Vec<String>, Vec<Message>) {
+        self.client.close();
+        drop(self.client);
+        (self.rx.iter().collect(), self.server.received())
+    }
+}
+
+/// Connects a `C` client to a MockServer and checks that `subscribe` returns
+/// `expected` instead of panicking, without sending anything to the server.
+pub(super) fn assert_unsupported<C: FromUrls + WSClient>(
+    subscribe: impl FnOnce(&C) -> Result<(), UnsupportedChannel>,
+    expected: UnsupportedChannel,
+) {
+    let server = MockServer::start(Vec::new());
+    let (tx, _rx) = channel();
+    let client = C::from_urls(tx, &[server.url.clone()]);
+    assert_eq!(Err(expected), subscribe(&client));
+    client.close();
+    drop(client);
+
+    assert!(server.received().iter().all(|msg| !msg.is_text()));
+}
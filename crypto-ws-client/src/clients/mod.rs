@@ -1,10 +1,17 @@
+#[macro_use]
+mod logging;
+
 #[macro_use]
 mod common_traits;
 
 #[macro_use]
 mod ws_client_internal;
 
-mod utils;
+mod registry;
+pub(crate) mod utils;
+
+#[cfg(test)]
+mod mock_server;
 
 use common_traits::*;
 
@@ -27,4 +34,5 @@ pub(super) mod kraken;
 pub(super) mod kucoin;
 pub(super) mod mxc;
 pub(super) mod okex;
+pub(super) mod okex_v5;
 pub(super) mod zbg;
@@ -1,15 +1,14 @@
 use std::collections::HashMap;
 use std::sync::mpsc::Sender;
 
-use crate::WSClient;
+use crate::{Interval, UnsupportedChannel, WSClient};
 
 use super::{
     utils::CHANNEL_PAIR_DELIMITER,
     ws_client_internal::{MiscMessage, WSClientInternal},
-    Candlestick, Level3OrderBook, OrderBook, OrderBookTopK, Ticker, Trade, BBO,
+    Authenticate, Candlestick, Level3OrderBook, OrderBook, OrderBookTopK, Ticker, Trade, BBO,
 };
 
-use log::*;
 use serde_json::Value;
 
 pub(super) const EXCHANGE_NAME: &str = "mxc";
@@ -19,6 +18,15 @@ pub(super) const SPOT_WEBSOCKET_URL: &str =
     "wss://wbs.mxc.com/socket.io/?EIO=3&transport=websocket";
 pub(super) const SWAP_WEBSOCKET_URL: &str = "wss://contract.mexc.com/ws";
 
+// Spot endpoints speak socket.io, whose data frames carry the "42" prefix.
+// Checks the path rather than the whole URL, so that failing over to
+// another host or query string still strips the prefix.
+pub(super) fn is_socketio_url(url: &str) -> bool {
+    url.parse::<http::Uri>()
+        .map(|uri| uri.path().starts_with("/socket.io"))
+        .unwrap_or(false)
+}
+
 const SPOT_CLIENT_PING_INTERVAL_AND_MSG: (u64, &str) = (5, "2");
 // more than 60 seconds no response, close the channel
 const SWAP_CLIENT_PING_INTERVAL_AND_MSG: (u64, &str) = (60, r#"{"method":"ping"}"#);
@@ -100,7 +108,7 @@ fn swap_channels_to_commands(channels: &[String], subscribe: bool) -> Vec<String
 
 fn on_misc_msg(msg: &str) -> MiscMessage {
     if msg == "1" {
-        warn!("Server closed the connection");
+        exchange_log!(EXCHANGE_NAME, Warn, "Server closed the connection");
         return MiscMessage::Reconnect;
     }
 
@@ -108,10 +116,20 @@ fn on_misc_msg(msg: &str) -> MiscMessage {
         if !msg.starts_with("42") {
             // see https://stackoverflow.com/a/65244958/381712
             if msg.starts_with("0{") {
-                debug!("Connection opened {}", SPOT_WEBSOCKET_URL);
+                exchange_log!(
+                    EXCHANGE_NAME,
+                    Debug,
+                    "Connection opened {}",
+                    SPOT_WEBSOCKET_URL
+                );
                 MiscMessage::Misc
             } else if msg == "40" {
-                debug!("Connected successfully {}", SPOT_WEBSOCKET_URL);
+                exchange_log!(
+                    EXCHANGE_NAME,
+                    Debug,
+                    "Connected successfully {}",
+                    SPOT_WEBSOCKET_URL
+                );
                 MiscMessage::Misc
             } else if msg == "3" {
                 // socket.io pong
@@ -129,20 +147,26 @@ fn on_misc_msg(msg: &str) -> MiscMessage {
             match channel {
                 "pong" => MiscMessage::Pong,
                 "rs.error" => {
-                    error!("Received {} from {}", msg, EXCHANGE_NAME);
+                    exchange_log!(EXCHANGE_NAME, Error, "Received {}", msg);
                     panic!("Received {} from {}", msg, EXCHANGE_NAME);
                 }
                 _ => {
                     if obj.contains_key("symbol") && channel.starts_with("push.") {
                         MiscMessage::Normal
                     } else {
-                        info!("Received {} from {}", msg, EXCHANGE_NAME);
+                        exchange_log!(EXCHANGE_NAME, Info, "Received {}", msg);
                         MiscMessage::Misc
                     }
                 }
             }
         } else {
-            error!("Received {} from {}", msg, SWAP_WEBSOCKET_URL);
+            exchange_log!(
+                EXCHANGE_NAME,
+                Error,
+                "Received {} from {}",
+                msg,
+                SWAP_WEBSOCKET_URL
+            );
             MiscMessage::Misc
         }
     }
@@ -158,8 +182,8 @@ impl_trait!(Trade, MxcSpotWSClient, subscribe_trade, "symbol", to_raw_channel);
 impl_trait!(Trade, MxcSwapWSClient, subscribe_trade, "deal", to_raw_channel);
 
 impl Ticker for MxcSpotWSClient {
-    fn subscribe_ticker(&self, _pairs: &[String]) {
-        panic!("MXC Spot WebSocket does NOT have ticker channel");
+    fn subscribe_ticker(&self, _pairs: &[String]) -> Result<(), UnsupportedChannel> {
+        Err(UnsupportedChannel::new(EXCHANGE_NAME, "ticker"))
     }
 }
 #[rustfmt::skip]
@@ -175,69 +199,77 @@ impl_trait!(OrderBookTopK, MxcSpotWSClient, subscribe_orderbook_topk, "get.depth
 impl_trait!(OrderBookTopK, MxcSwapWSClient, subscribe_orderbook_topk, "depth.full", to_raw_channel);
 
 impl BBO for MxcSpotWSClient {
-    fn subscribe_bbo(&self, _pairs: &[String]) {
-        panic!("MXC Spot WebSocket does NOT have BBO channel");
+    fn subscribe_bbo(&self, _pairs: &[String]) -> Result<(), UnsupportedChannel> {
+        Err(UnsupportedChannel::new(EXCHANGE_NAME, "BBO"))
     }
 }
 impl BBO for MxcSwapWSClient {
-    fn subscribe_bbo(&self, _pairs: &[String]) {
-        panic!("MXC Swap WebSocket does NOT have BBO channel");
+    fn subscribe_bbo(&self, _pairs: &[String]) -> Result<(), UnsupportedChannel> {
+        Err(UnsupportedChannel::new(EXCHANGE_NAME, "BBO"))
     }
 }
 
-fn interval_to_string(interval: usize) -> String {
+fn interval_to_string(interval: Interval) -> Result<String, UnsupportedChannel> {
     let tmp = match interval {
-        60 => "Min1",
-        300 => "Min5",
-        900 => "Min15",
-        1800 => "Min30",
-        3600 => "Min60",
-        14400 => "Hour4",
-        28800 => "Hour8",
-        86400 => "Day1",
-        604800 => "Week1",
-        2592000 => "Month1",
-        _ => panic!("MXC has intervals Min1,Min5,Min15,Min30,Min60,Hour4,Hour8,Day1,Week1,Month1"),
+        Interval::Min1 => "Min1",
+        Interval::Min5 => "Min5",
+        Interval::Min15 => "Min15",
+        Interval::Min30 => "Min30",
+        Interval::Hour1 => "Min60",
+        Interval::Hour4 => "Hour4",
+        Interval::Hour8 => "Hour8",
+        Interval::Day1 => "Day1",
+        Interval::Week1 => "Week1",
+        Interval::Month1 => "Month1",
+        _ => return Err(UnsupportedChannel::interval(EXCHANGE_NAME, interval)),
     };
-    tmp.to_string()
+    Ok(tmp.to_string())
 }
 
 impl Candlestick for MxcSpotWSClient {
-    fn subscribe_candlestick(&self, symbol_interval_list: &[(String, usize)]) {
+    fn subscribe_candlestick(
+        &self,
+        symbol_interval_list: &[(String, Interval)],
+    ) -> Result<(), UnsupportedChannel> {
         let channels = symbol_interval_list
             .iter()
             .map(|(symbol, interval)| {
-                format!(
+                Ok(format!(
                     r#"["sub.kline",{{"symbol":"{}","interval":"{}"}}]"#,
                     symbol,
-                    interval_to_string(*interval)
-                )
+                    interval_to_string(*interval)?
+                ))
             })
-            .collect::<Vec<String>>();
+            .collect::<Result<Vec<String>, UnsupportedChannel>>()?;
 
         self.client.subscribe(&channels);
+        Ok(())
     }
 }
 
 impl Candlestick for MxcSwapWSClient {
-    fn subscribe_candlestick(&self, symbol_interval_list: &[(String, usize)]) {
+    fn subscribe_candlestick(
+        &self,
+        symbol_interval_list: &[(String, Interval)],
+    ) -> Result<(), UnsupportedChannel> {
         let channels = symbol_interval_list
             .iter()
             .map(|(symbol, interval)| {
-                format!(
+                Ok(format!(
                     r#"{{"method":"sub.kline","param":{{"symbol":"{}","interval":"{}"}}}}"#,
                     symbol,
-                    interval_to_string(*interval)
-                )
+                    interval_to_string(*interval)?
+                ))
             })
-            .collect::<Vec<String>>();
+            .collect::<Result<Vec<String>, UnsupportedChannel>>()?;
 
         self.client.subscribe(&channels);
+        Ok(())
     }
 }
 
-panic_l3_orderbook!(MxcSpotWSClient);
-panic_l3_orderbook!(MxcSwapWSClient);
+unsupported_l3_orderbook!(MxcSpotWSClient);
+unsupported_l3_orderbook!(MxcSwapWSClient);
 
 impl_new_constructor!(
     MxcSpotWSClient,
@@ -248,6 +280,7 @@ impl_new_constructor!(
     Some(SPOT_CLIENT_PING_INTERVAL_AND_MSG),
     None
 );
+unsupported_authenticate!(MxcSpotWSClient);
 impl_ws_client_trait!(MxcSpotWSClient);
 
 impl_new_constructor!(
@@ -259,10 +292,23 @@ impl_new_constructor!(
     Some(SWAP_CLIENT_PING_INTERVAL_AND_MSG),
     None
 );
+unsupported_authenticate!(MxcSwapWSClient);
 impl_ws_client_trait!(MxcSwapWSClient);
 
 #[cfg(test)]
 mod tests {
+    use crate::{clients::mock_server::assert_unsupported, UnsupportedChannel, WSClient};
+
+    #[test]
+    fn test_is_socketio_url() {
+        assert!(super::is_socketio_url(super::SPOT_WEBSOCKET_URL));
+        assert!(super::is_socketio_url(
+            "wss://wbs.mexc.com/socket.io/?EIO=3&transport=websocket"
+        ));
+        assert!(!super::is_socketio_url(super::SWAP_WEBSOCKET_URL));
+        assert!(!super::is_socketio_url("not a url"));
+    }
+
     #[test]
     fn test_spot_channel_to_command() {
         let channel = "symbol:BTC_USDT";
@@ -296,4 +342,16 @@ mod tests {
             unsubscribe_command
         );
     }
+
+    #[test]
+    fn test_unsupported_channel() {
+        assert_unsupported(
+            |client: &super::MxcSpotWSClient| client.subscribe_ticker(&["BTCUSDT".to_string()]),
+            UnsupportedChannel::new(super::EXCHANGE_NAME, "ticker"),
+        );
+        assert_unsupported(
+            |client: &super::MxcSwapWSClient| client.subscribe_bbo(&["BTCUSDT".to_string()]),
+            UnsupportedChannel::new(super::EXCHANGE_NAME, "BBO"),
+        );
+    }
 }
@@ -1,16 +1,28 @@
-use crate::WSClient;
+use crate::{AuthError, Encoding, Interval, UnsupportedChannel, WSClient};
+use crypto_market_type::MarketType;
 use std::collections::HashMap;
 use std::sync::mpsc::Sender;
+use std::time::{SystemTime, UNIX_EPOCH};
 
+use super::registry::ExchangeSpec;
 use super::utils::ensure_frame_size;
 use super::ws_client_internal::{MiscMessage, WSClientInternal};
-use super::{Candlestick, Level3OrderBook, OrderBook, OrderBookTopK, Ticker, Trade, BBO};
+use super::{
+    Authenticate, Candlestick, Level3OrderBook, OrderBook, OrderBookTopK, Ticker, Trade, BBO,
+};
 
-use log::*;
+use hmac::{Hmac, Mac};
 use serde_json::Value;
+use sha2::Sha256;
 
 pub(super) const EXCHANGE_NAME: &str = "okex";
 
+pub(super) const SPEC: ExchangeSpec = ExchangeSpec {
+    name: EXCHANGE_NAME,
+    encoding: Some(Encoding::Deflate),
+    ..ExchangeSpec::DEFAULT
+};
+
 const WEBSOCKET_URL: &str = "wss://real.okex.com:8443/ws/v3";
 
 const CLIENT_PING_INTERVAL_AND_MSG: (u64, &str) = (30, "ping");
@@ -48,57 +60,78 @@ fn channels_to_commands(channels: &[String], subscribe: bool) -> Vec<String> {
     ensure_frame_size(channels, subscribe, topics_to_command, WS_FRAME_SIZE, None)
 }
 
+// Extracts the channel from the message of error 30040, e.g.,
+// `Channel spot/trade:BTC-ABC doesn't exist`
+fn rejected_channel(message: &str) -> Option<String> {
+    message
+        .strip_prefix("Channel ")
+        .and_then(|x| x.strip_suffix(" doesn't exist"))
+        .map(|channel| channel.to_string())
+}
+
 fn on_misc_msg(msg: &str) -> MiscMessage {
     if msg == "pong" {
         return MiscMessage::Pong;
     }
     let resp = serde_json::from_str::<HashMap<String, Value>>(msg);
     if resp.is_err() {
-        error!("{} is not a JSON string, {}", msg, EXCHANGE_NAME);
+        exchange_log!(EXCHANGE_NAME, Error, "{} is not a JSON string", msg);
         return MiscMessage::Misc;
     }
     let obj = resp.unwrap();
 
     if let Some(event) = obj.get("event") {
-        match event.as_str().unwrap() {
+        match event.as_str().unwrap_or_default() {
             "error" => {
                 let error_code = obj.get("errorCode").unwrap().as_i64().unwrap();
                 match error_code {
+                    // errors of the login command, e.g., 30013 invalid sign
+                    30001..=30015 => {
+                        exchange_log!(EXCHANGE_NAME, Error, "Received {}", msg);
+                        return MiscMessage::Login(false);
+                    }
                     30040 => {
-                        // channel doesn't exist, ignore because some symbols don't exist in websocket while they exist in `/v3/instruments`
-                        error!("Received {} from {}", msg, EXCHANGE_NAME);
+                        // channel doesn't exist, because some symbols don't exist in websocket while they exist in `/v3/instruments`,
+                        // the client removes it and logs all rejected channels at once
+                        if let Some(channel) = obj
+                            .get("message")
+                            .and_then(|x| x.as_str())
+                            .and_then(rejected_channel)
+                        {
+                            return MiscMessage::Rejected(channel);
+                        }
+                        exchange_log!(EXCHANGE_NAME, Warn, "Received {}", msg);
                     }
-                    _ => warn!("Received {} from {}", msg, EXCHANGE_NAME),
+                    _ => exchange_log!(EXCHANGE_NAME, Warn, "Received {}", msg),
                 }
             }
-            "subscribe" => info!("Received {} from {}", msg, EXCHANGE_NAME),
-            "unsubscribe" => info!("Received {} from {}", msg, EXCHANGE_NAME),
-            _ => warn!("Received {} from {}", msg, EXCHANGE_NAME),
+            "login" => {
+                exchange_log!(EXCHANGE_NAME, Info, "Received {}", msg);
+                let succeeded = obj.get("success").and_then(|x| x.as_bool()) == Some(true);
+                return MiscMessage::Login(succeeded);
+            }
+            "subscribe" => exchange_log!(EXCHANGE_NAME, Info, "Received {}", msg),
+            "unsubscribe" => exchange_log!(EXCHANGE_NAME, Info, "Received {}", msg),
+            _ => exchange_log!(EXCHANGE_NAME, Warn, "Received {}", msg),
         }
         MiscMessage::Misc
     } else if !obj.contains_key("table") || !obj.contains_key("data") {
-        error!("Received {} from {}", msg, EXCHANGE_NAME);
+        // data messages are recognized by their shape alone, they may arrive
+        // before the subscribe event that acknowledges the channel
+        exchange_log!(EXCHANGE_NAME, Error, "Received {}", msg);
         MiscMessage::Misc
     } else {
         MiscMessage::Normal
     }
 }
 
+// The market type in the path of the v3 API
 fn pair_to_market_type(pair: &str) -> &'static str {
-    if pair.ends_with("-SWAP") {
-        "swap"
-    } else {
-        let c = pair.matches('-').count();
-        if c == 1 {
-            "spot"
-        } else if c == 2 {
-            let date = &pair[(pair.len() - 6)..];
-            debug_assert!(date.parse::<i64>().is_ok());
-            "futures"
-        } else {
-            debug_assert!(pair.ends_with("-C") || pair.ends_with("-P"));
-            "option"
-        }
+    match crypto_pair::get_market_type(pair, "okex") {
+        MarketType::InverseFuture | MarketType::LinearFuture => "futures",
+        MarketType::InverseSwap | MarketType::LinearSwap => "swap",
+        MarketType::EuropeanOption => "option",
+        _ => "spot",
     }
 }
 
@@ -117,25 +150,66 @@ impl_trait!(OrderBook, OkexWSClient, subscribe_orderbook, "depth_l2_tbt", to_raw
 #[rustfmt::skip]
 impl_trait!(OrderBookTopK, OkexWSClient, subscribe_orderbook_topk, "depth5", to_raw_channel);
 
-fn to_candlestick_raw_channel(pair: &str, interval: usize) -> String {
-    let valid_set: Vec<usize> = vec![
-        60, 180, 300, 900, 1800, 3600, 7200, 14400, 21600, 43200, 86400, 604800,
-    ];
-    if !valid_set.contains(&interval) {
-        let joined = valid_set
-            .into_iter()
-            .map(|x| x.to_string())
-            .collect::<Vec<String>>()
-            .join(",");
-        panic!("OKEx has intervals {}", joined);
-    }
-    let channel = format!("candle{}s", interval);
-    to_raw_channel(&channel, pair)
+fn to_candlestick_raw_channel(
+    pair: &str,
+    interval: Interval,
+) -> Result<String, UnsupportedChannel> {
+    match interval {
+        Interval::Min1
+        | Interval::Min3
+        | Interval::Min5
+        | Interval::Min15
+        | Interval::Min30
+        | Interval::Hour1
+        | Interval::Hour2
+        | Interval::Hour4
+        | Interval::Hour6
+        | Interval::Hour12
+        | Interval::Day1
+        | Interval::Week1 => {
+            let channel = format!("candle{}s", interval.as_secs());
+            Ok(to_raw_channel(&channel, pair))
+        }
+        _ => Err(UnsupportedChannel::interval(EXCHANGE_NAME, interval)),
+    }
 }
 
 impl_candlestick!(OkexWSClient);
 
-panic_l3_orderbook!(OkexWSClient);
+unsupported_l3_orderbook!(OkexWSClient);
+
+// Signs the login command, see <https://www.okex.com/docs/en/#websocket-login>
+fn login_command(
+    api_key: &str,
+    secret: &str,
+    passphrase: &str,
+    timestamp: &str,
+) -> Result<String, AuthError> {
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes())
+        .map_err(|err| AuthError::InvalidCredentials(err.to_string()))?;
+    mac.update(format!("{}GET/users/self/verify", timestamp).as_bytes());
+    let sign = base64::encode(mac.finalize().into_bytes());
+    Ok(format!(
+        r#"{{"op":"login","args":{}}}"#,
+        serde_json::to_string(&[api_key, passphrase, timestamp, &sign]).unwrap()
+    ))
+}
+
+impl Authenticate for OkexWSClient {
+    fn authenticate(&self, api_key: &str, secret: &str, passphrase: &str) -> Result<(), AuthError> {
+        let (api_key, secret, passphrase) = (
+            api_key.to_string(),
+            secret.to_string(),
+            passphrase.to_string(),
+        );
+        // the signature expires after 30 seconds, so it is signed again on reconnect
+        self.client.login(Box::new(move || {
+            let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap();
+            let timestamp = format!("{}.{:03}", now.as_secs(), now.subsec_millis());
+            login_command(&api_key, &secret, &passphrase, &timestamp)
+        }))
+    }
+}
 
 impl_new_constructor!(
     OkexWSClient,
@@ -150,6 +224,9 @@ impl_ws_client_trait!(OkexWSClient);
 
 #[cfg(test)]
 mod tests {
+    use super::super::mock_server::MockClient;
+    use crate::{clients::mock_server::assert_unsupported, UnsupportedChannel, WSClient};
+
     #[test]
     fn test_one_channel() {
         let commands = super::channels_to_commands(&vec!["spot/trade:BTC-USDT".to_string()], true);
@@ -186,4 +263,240 @@ mod tests {
             super::pair_to_market_type("BTC-USD-210625-72000-C")
         );
     }
+
+    #[test]
+    fn test_candlestick_interval() {
+        use crate::Interval;
+
+        assert_eq!(
+            "spot/candle60s:BTC-USDT",
+            super::to_candlestick_raw_channel("BTC-USDT", Interval::Min1).unwrap()
+        );
+        assert_eq!(
+            "swap/candle604800s:BTC-USDT-SWAP",
+            super::to_candlestick_raw_channel("BTC-USDT-SWAP", Interval::Week1).unwrap()
+        );
+        let err = super::to_candlestick_raw_channel("BTC-USDT", Interval::Month1).unwrap_err();
+        assert_eq!(
+            "okex does NOT have the Month1 candlestick websocket channel",
+            err.to_string()
+        );
+    }
+
+    #[test]
+    fn test_login_command() {
+        assert_eq!(
+            r#"{"op":"login","args":["key","passphrase","1538054050.975","DgKNU9uKoPJG46YwbcAFln7Tc3z9O96ErLmt10USdMM="]}"#,
+            super::login_command("key", "secret", "passphrase", "1538054050.975").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_subscribe_after_login() {
+        use tungstenite::Message;
+
+        let login = r#"{"op":"login","args":["key","passphrase","1538054050.975","DgKNU9uKoPJG46YwbcAFln7Tc3z9O96ErLmt10USdMM="]}"#;
+        let mock = MockClient::start(
+            super::EXCHANGE_NAME,
+            super::on_misc_msg,
+            super::channels_to_commands,
+            None,
+            vec![vec![
+                Message::Text(r#"{"event":"login","success":true}"#.to_string()),
+                Message::Text(r#"{"table":"spot/order","data":[]}"#.to_string()),
+            ]],
+        );
+        mock.client
+            .login(Box::new(|| {
+                super::login_command("key", "secret", "passphrase", "1538054050.975")
+            }))
+            .unwrap();
+        // held back until the login reply arrives
+        mock.client.subscribe(&["spot/order:BTC-USDT".to_string()]);
+        mock.client.run(Some(0));
+        let (_, received) = mock.finish();
+
+        assert_eq!(
+            vec![
+                Message::Text(login.to_string()),
+                Message::Text(r#"{"op":"subscribe","args":["spot/order:BTC-USDT"]}"#.to_string()),
+            ],
+            received
+                .into_iter()
+                .filter(|msg| msg.is_text())
+                .collect::<Vec<Message>>()
+        );
+    }
+
+    // Keeps summaries of rejected channels logged by clients
+    struct SummaryLogger {
+        summaries: std::sync::Mutex<Vec<String>>,
+    }
+
+    impl log::Log for SummaryLogger {
+        fn enabled(&self, _metadata: &log::Metadata) -> bool {
+            true
+        }
+
+        fn log(&self, record: &log::Record) {
+            let msg = record.args().to_string();
+            if msg.contains("rejected by the server") {
+                self.summaries.lock().unwrap().push(msg);
+            }
+        }
+
+        fn flush(&self) {}
+    }
+
+    static LOGGER: SummaryLogger = SummaryLogger {
+        summaries: std::sync::Mutex::new(Vec::new()),
+    };
+
+    #[test]
+    fn test_rejected_channels() {
+        use tungstenite::Message;
+
+        log::set_logger(&LOGGER).unwrap();
+        log::set_max_level(log::LevelFilter::Warn);
+
+        let error = |channel: &str| {
+            Message::Text(format!(
+                r#"{{"event":"error","message":"Channel {} doesn't exist","errorCode":30040}}"#,
+                channel
+            ))
+        };
+        let mock = MockClient::start(
+            super::EXCHANGE_NAME,
+            super::on_misc_msg,
+            super::channels_to_commands,
+            None,
+            vec![vec![
+                error("spot/trade:BTC-ABC"),
+                error("spot/trade:ETH-ABC"),
+                error("spot/trade:BTC-ABC"),
+                Message::Text(r#"{"table":"spot/trade","data":[]}"#.to_string()),
+            ]],
+        );
+        let client = &mock.client;
+        client.subscribe(&[
+            "spot/trade:BTC-USDT".to_string(),
+            "spot/trade:BTC-ABC".to_string(),
+            "spot/trade:ETH-ABC".to_string(),
+        ]);
+        client.run(Some(0));
+
+        let stats = client.stats();
+        assert_eq!(1, stats.num_channels);
+        assert_eq!(vec!["spot/trade:BTC-USDT"], client.subscriptions());
+        assert_eq!(
+            vec!["spot/trade:BTC-ABC", "spot/trade:ETH-ABC"],
+            stats.rejected_channels
+        );
+        assert_eq!(
+            vec!["2 channels were rejected by the server and removed: spot/trade:BTC-ABC, spot/trade:ETH-ABC"],
+            *LOGGER.summaries.lock().unwrap()
+        );
+        mock.finish();
+    }
+
+    #[test]
+    fn test_subscriptions() {
+        let mock = MockClient::start(
+            super::EXCHANGE_NAME,
+            super::on_misc_msg,
+            super::channels_to_commands,
+            None,
+            vec![Vec::new()],
+        );
+        let client = &mock.client;
+        assert!(client.subscriptions().is_empty());
+
+        client.subscribe(&[
+            "spot/trade:ETH-USDT".to_string(),
+            "spot/trade:BTC-USDT".to_string(),
+            "spot/trade:ETH-USDT".to_string(),
+        ]);
+        assert_eq!(
+            vec!["spot/trade:BTC-USDT", "spot/trade:ETH-USDT"],
+            client.subscriptions()
+        );
+
+        client.unsubscribe(&["spot/trade:ETH-USDT".to_string()]);
+        assert_eq!(vec!["spot/trade:BTC-USDT"], client.subscriptions());
+        mock.finish();
+    }
+
+    #[test]
+    fn test_trade_before_ack() {
+        use tungstenite::Message;
+
+        let trade = r#"{"table":"spot/trade","data":[{"side":"buy","trade_id":"131221557","price":"58700.5","size":"0.0012","instrument_id":"BTC-USDT","timestamp":"2021-03-20T12:24:00.458Z"}]}"#;
+        let mock = MockClient::start(
+            super::EXCHANGE_NAME,
+            super::on_misc_msg,
+            super::channels_to_commands,
+            None,
+            vec![vec![
+                Message::Text(trade.to_string()),
+                Message::Text(
+                    r#"{"event":"subscribe","channel":"spot/trade:BTC-USDT"}"#.to_string(),
+                ),
+            ]],
+        );
+        mock.client.subscribe(&["spot/trade:BTC-USDT".to_string()]);
+        mock.client.run(Some(0));
+        let (msgs, _) = mock.finish();
+
+        assert_eq!(vec![trade.to_string()], msgs);
+    }
+
+    #[test]
+    fn test_raw_channel() {
+        use tungstenite::Message;
+
+        let raw_channel = "swap/mark_price:BTC-USD-SWAP".to_string();
+        let command = r#"{"op":"subscribe","args":["swap/mark_price:BTC-USD-SWAP"]}"#;
+        assert_eq!(
+            vec![command.to_string()],
+            super::channels_to_commands(std::slice::from_ref(&raw_channel), true)
+        );
+
+        // the oversized frame of the first session forces a reconnect
+        let mark_price = r#"{"table":"swap/mark_price","data":[{"instrument_id":"BTC-USD-SWAP","mark_price":"58700.5","timestamp":"2021-03-20T12:24:00.458Z"}]}"#;
+        let mock = MockClient::start(
+            super::EXCHANGE_NAME,
+            super::on_misc_msg,
+            super::channels_to_commands,
+            None,
+            vec![
+                vec![Message::Text("x".repeat(2048))],
+                vec![Message::Text(mark_price.to_string())],
+            ],
+        );
+        let (reset_tx, _reset_rx) = std::sync::mpsc::channel();
+        mock.client.set_reset_on_reconnect(Some(reset_tx));
+        mock.client.set_max_frame_size(Some(1024));
+        mock.client.subscribe(std::slice::from_ref(&raw_channel));
+        mock.client.run(Some(0));
+        assert_eq!(vec![raw_channel], mock.client.subscriptions());
+        let (msgs, received) = mock.finish();
+
+        assert_eq!(mark_price, msgs.last().unwrap());
+        // subscribed on the first connection and resubscribed on the second
+        assert_eq!(
+            2,
+            received
+                .iter()
+                .filter(|msg| **msg == Message::Text(command.to_string()))
+                .count()
+        );
+    }
+
+    #[test]
+    fn test_unsupported_channel() {
+        assert_unsupported(
+            |client: &super::OkexWSClient| client.subscribe_l3_orderbook(&["BTCUSDT".to_string()]),
+            UnsupportedChannel::new(super::EXCHANGE_NAME, "level3 orderbook"),
+        );
+    }
 }
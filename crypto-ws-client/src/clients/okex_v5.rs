@@ -0,0 +1,218 @@
+use crate::{Interval, UnsupportedChannel, WSClient};
+use std::collections::HashMap;
+use std::sync::mpsc::Sender;
+
+use super::utils::ensure_frame_size;
+use super::ws_client_internal::{MiscMessage, WSClientInternal};
+use super::{
+    Authenticate, Candlestick, Level3OrderBook, OrderBook, OrderBookTopK, Ticker, Trade, BBO,
+};
+
+use serde_json::Value;
+
+const EXCHANGE_NAME: &str = "okex";
+
+const WEBSOCKET_URL: &str = "wss://ws.okex.com:8443/ws/v5/public";
+
+// If there’s a network problem, the system will automatically disable the connection.
+// The connection will break automatically if the subscription is not established or data has not been pushed for more than 30 seconds.
+const CLIENT_PING_INTERVAL_AND_MSG: (u64, &str) = (30, "ping");
+
+/// The total length of multiple channels cannot exceed 4096 bytes
+const WS_FRAME_SIZE: usize = 4096;
+
+/// The WebSocket client for OKEx API v5.
+///
+/// OKEx has Spot, Future, Swap and Option markets.
+///
+/// A v5 raw channel is `channel:instId`, for example, `trades:BTC-USDT`,
+/// `books5:BTC-USDT-SWAP`.
+///
+/// * WebSocket API doc: <https://www.okex.com/docs-v5/en/#websocket-api>
+/// * Trading at:
+///     * Spot <https://www.okex.com/trade-spot>
+///     * Future <https://www.okex.com/derivatives/futures>
+///     * Swap <https://www.okex.com/derivatives/swap>
+///     * Option <https://www.okex.com/derivatives/options>
+pub struct OkexV5WSClient {
+    client: WSClientInternal,
+}
+
+fn topics_to_command(chunk: &[String], subscribe: bool) -> String {
+    let args = chunk
+        .iter()
+        .map(|ch| {
+            let mut arg = HashMap::new();
+            if let Some((channel, inst_id)) = ch.split_once(':') {
+                arg.insert("channel".to_string(), channel.to_string());
+                arg.insert("instId".to_string(), inst_id.to_string());
+            } else {
+                // channels without instId, e.g., status
+                arg.insert("channel".to_string(), ch.to_string());
+            }
+            arg
+        })
+        .collect::<Vec<HashMap<String, String>>>();
+    format!(
+        r#"{{"op":"{}","args":{}}}"#,
+        if subscribe {
+            "subscribe"
+        } else {
+            "unsubscribe"
+        },
+        serde_json::to_string(&args).unwrap()
+    )
+}
+
+fn channels_to_commands(channels: &[String], subscribe: bool) -> Vec<String> {
+    ensure_frame_size(channels, subscribe, topics_to_command, WS_FRAME_SIZE, None)
+}
+
+fn on_misc_msg(msg: &str) -> MiscMessage {
+    if msg == "pong" {
+        return MiscMessage::Pong;
+    }
+    let resp = serde_json::from_str::<HashMap<String, Value>>(msg);
+    if resp.is_err() {
+        exchange_log!(EXCHANGE_NAME, Error, "{} is not a JSON string", msg);
+        return MiscMessage::Misc;
+    }
+    let obj = resp.unwrap();
+
+    if let Some(event) = obj.get("event") {
+        match event.as_str().unwrap() {
+            "error" => {
+                let error_code = obj
+                    .get("code")
+                    .and_then(|code| code.as_str())
+                    .unwrap_or_default();
+                match error_code {
+                    "60018" => {
+                        // channel doesn't exist
+                        exchange_log!(EXCHANGE_NAME, Error, "Received {}", msg);
+                    }
+                    _ => exchange_log!(EXCHANGE_NAME, Warn, "Received {}", msg),
+                }
+            }
+            "subscribe" => exchange_log!(EXCHANGE_NAME, Info, "Received {}", msg),
+            "unsubscribe" => exchange_log!(EXCHANGE_NAME, Info, "Received {}", msg),
+            _ => exchange_log!(EXCHANGE_NAME, Warn, "Received {}", msg),
+        }
+        MiscMessage::Misc
+    } else if !obj.contains_key("arg") || !obj.contains_key("data") {
+        exchange_log!(EXCHANGE_NAME, Error, "Received {}", msg);
+        MiscMessage::Misc
+    } else {
+        MiscMessage::Normal
+    }
+}
+
+fn to_raw_channel(channel: &str, pair: &str) -> String {
+    format!("{}:{}", channel, pair)
+}
+
+#[rustfmt::skip]
+impl_trait!(Trade, OkexV5WSClient, subscribe_trade, "trades", to_raw_channel);
+#[rustfmt::skip]
+impl_trait!(BBO, OkexV5WSClient, subscribe_bbo, "tickers", to_raw_channel);
+#[rustfmt::skip]
+impl_trait!(Ticker, OkexV5WSClient, subscribe_ticker, "tickers", to_raw_channel);
+#[rustfmt::skip]
+impl_trait!(OrderBook, OkexV5WSClient, subscribe_orderbook, "books", to_raw_channel);
+#[rustfmt::skip]
+impl_trait!(OrderBookTopK, OkexV5WSClient, subscribe_orderbook_topk, "books5", to_raw_channel);
+
+fn to_candlestick_raw_channel(
+    pair: &str,
+    interval: Interval,
+) -> Result<String, UnsupportedChannel> {
+    let channel = match interval {
+        Interval::Min1 => "candle1m",
+        Interval::Min3 => "candle3m",
+        Interval::Min5 => "candle5m",
+        Interval::Min15 => "candle15m",
+        Interval::Min30 => "candle30m",
+        Interval::Hour1 => "candle1H",
+        Interval::Hour2 => "candle2H",
+        Interval::Hour4 => "candle4H",
+        Interval::Hour6 => "candle6H",
+        Interval::Hour12 => "candle12H",
+        Interval::Day1 => "candle1D",
+        Interval::Week1 => "candle1W",
+        _ => return Err(UnsupportedChannel::interval(EXCHANGE_NAME, interval)),
+    };
+    Ok(to_raw_channel(channel, pair))
+}
+
+impl_candlestick!(OkexV5WSClient);
+
+unsupported_l3_orderbook!(OkexV5WSClient);
+
+impl_new_constructor!(
+    OkexV5WSClient,
+    EXCHANGE_NAME,
+    WEBSOCKET_URL,
+    channels_to_commands,
+    on_misc_msg,
+    Some(CLIENT_PING_INTERVAL_AND_MSG),
+    None
+);
+unsupported_authenticate!(OkexV5WSClient);
+impl_ws_client_trait!(OkexV5WSClient);
+
+#[cfg(test)]
+mod tests {
+    use crate::{clients::mock_server::assert_unsupported, UnsupportedChannel, WSClient};
+
+    #[test]
+    fn test_one_channel() {
+        let commands = super::channels_to_commands(&["trades:BTC-USDT".to_string()], true);
+        assert_eq!(1, commands.len());
+        let command: serde_json::Value = serde_json::from_str(&commands[0]).unwrap();
+        assert_eq!(
+            serde_json::json!({"op":"subscribe","args":[{"channel":"trades","instId":"BTC-USDT"}]}),
+            command
+        );
+    }
+
+    #[test]
+    fn test_two_channel() {
+        let commands = super::channels_to_commands(
+            &[
+                "trades:BTC-USDT".to_string(),
+                "books5:BTC-USDT-SWAP".to_string(),
+            ],
+            false,
+        );
+        assert_eq!(1, commands.len());
+        let command: serde_json::Value = serde_json::from_str(&commands[0]).unwrap();
+        assert_eq!(
+            serde_json::json!({"op":"unsubscribe","args":[
+                {"channel":"trades","instId":"BTC-USDT"},
+                {"channel":"books5","instId":"BTC-USDT-SWAP"}
+            ]}),
+            command
+        );
+    }
+
+    #[test]
+    fn test_channel_without_inst_id() {
+        let commands = super::channels_to_commands(&["status".to_string()], true);
+        assert_eq!(1, commands.len());
+        let command: serde_json::Value = serde_json::from_str(&commands[0]).unwrap();
+        assert_eq!(
+            serde_json::json!({"op":"subscribe","args":[{"channel":"status"}]}),
+            command
+        );
+    }
+
+    #[test]
+    fn test_unsupported_channel() {
+        assert_unsupported(
+            |client: &super::OkexV5WSClient| {
+                client.subscribe_l3_orderbook(&["BTCUSDT".to_string()])
+            },
+            UnsupportedChannel::new(super::EXCHANGE_NAME, "level3 orderbook"),
+        );
+    }
+}
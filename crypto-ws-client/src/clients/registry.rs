@@ -0,0 +1,62 @@
+// Per-exchange properties used by the generic websocket code.
+//
+// Each exchange declares its `ExchangeSpec` next to its `EXCHANGE_NAME`, and
+// `WSClientInternal` looks it up by name instead of matching on exchange
+// names in several places, so a new exchange only needs to be added to
+// `EXCHANGES` here. Exchanges without a spec use `ExchangeSpec::DEFAULT`.
+use crate::Encoding;
+
+pub(super) struct ExchangeSpec {
+    pub name: &'static str,
+    // compression of binary frames, None if the exchange sends text frames only
+    pub encoding: Option<Encoding>,
+    // minimum interval between two commands sent to the server
+    pub send_interval_ms: Option<u64>,
+}
+
+impl ExchangeSpec {
+    pub const DEFAULT: ExchangeSpec = ExchangeSpec {
+        name: "",
+        encoding: None,
+        send_interval_ms: None,
+    };
+}
+
+const EXCHANGES: &[&ExchangeSpec] = &[
+    &super::binance::SPEC,
+    &super::bitget::SPEC,
+    &super::bitz::SPEC,
+    &super::huobi::SPEC,
+    &super::kucoin::SPEC,
+    &super::okex::SPEC,
+];
+
+pub(super) fn lookup(exchange: &str) -> &'static ExchangeSpec {
+    EXCHANGES
+        .iter()
+        .find(|spec| spec.name == exchange)
+        .copied()
+        .unwrap_or(&ExchangeSpec::DEFAULT)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{lookup, EXCHANGES};
+    use crate::Encoding;
+
+    #[test]
+    fn unique_names() {
+        for (i, spec) in EXCHANGES.iter().enumerate() {
+            assert!(EXCHANGES[i + 1..].iter().all(|x| x.name != spec.name));
+        }
+    }
+
+    #[test]
+    fn lookup_by_name() {
+        assert_eq!(Some(Encoding::Gzip), lookup("binance").encoding);
+        assert_eq!(Some(100), lookup("binance").send_interval_ms);
+        assert_eq!(Some(Encoding::Deflate), lookup("okex").encoding);
+        assert_eq!(None, lookup("kraken").encoding);
+        assert_eq!(None, lookup("kraken").send_interval_ms);
+    }
+}
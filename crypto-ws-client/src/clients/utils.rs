@@ -1,23 +1,54 @@
-use http::Uri;
-use log::*;
-use rustls::{ClientConfig, ClientSession, StreamOwned};
+use http::{HeaderMap, Uri};
 use std::{
     net::{SocketAddr, TcpStream, ToSocketAddrs},
-    sync::Arc,
+    sync::{
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+        Mutex,
+    },
     thread,
-    time::{self, Duration},
+    time::{self, Duration, Instant},
 };
 use tungstenite::{
     client::{self, AutoStream, IntoClientRequest},
     error::{TlsError, UrlError},
     handshake::{client::Response, HandshakeError},
+    protocol::WebSocketConfig,
     stream::{Mode, NoDelay, Stream as StreamSwitcher},
     Error, Result, WebSocket,
 };
-use webpki::DNSNameRef;
+
+#[cfg(not(any(feature = "native-tls", feature = "rustls")))]
+compile_error!("Either feature \"rustls\" or \"native-tls\" must be enabled");
+
+// copied from https://github.com/snapview/tungstenite-rs/blob/master/src/client.rs#L20
+#[cfg(feature = "native-tls")]
+fn wrap_stream(stream: TcpStream, domain: &str, mode: Mode) -> Result<AutoStream> {
+    use native_tls::{HandshakeError as TlsHandshakeError, TlsConnector};
+
+    match mode {
+        Mode::Plain => Ok(StreamSwitcher::Plain(stream)),
+        Mode::Tls => {
+            let connector = TlsConnector::builder().build().map_err(TlsError::Native)?;
+            connector
+                .connect(domain, stream)
+                .map_err(|e| match e {
+                    TlsHandshakeError::Failure(f) => TlsError::Native(f).into(),
+                    TlsHandshakeError::WouldBlock(_) => {
+                        panic!("Bug: TLS handshake not blocked")
+                    }
+                })
+                .map(StreamSwitcher::Tls)
+        }
+    }
+}
 
 // copied from https://github.com/snapview/tungstenite-rs/blob/master/src/client.rs#L69
+#[cfg(all(feature = "rustls", not(feature = "native-tls")))]
 fn wrap_stream(stream: TcpStream, domain: &str, mode: Mode) -> Result<AutoStream> {
+    use rustls::{ClientConfig, ClientSession, StreamOwned};
+    use std::sync::Arc;
+    use webpki::DNSNameRef;
+
     match mode {
         Mode::Plain => Ok(StreamSwitcher::Plain(stream)),
         Mode::Tls => {
@@ -40,6 +71,7 @@ fn wrap_stream(stream: TcpStream, domain: &str, mode: Mode) -> Result<AutoStream
 
 // copied from https://github.com/snapview/tungstenite-rs/blob/master/src/client.rs#L206
 fn connect_to_some(
+    exchange: &str,
     addrs: &[SocketAddr],
     uri: &Uri,
     mode: Mode,
@@ -47,7 +79,14 @@ fn connect_to_some(
 ) -> Result<AutoStream> {
     let domain = uri.host().ok_or(Error::Url(UrlError::NoHostName))?;
     for addr in addrs {
-        debug!("Trying to contact {} at {}...", uri, addr);
+        endpoint_log!(
+            exchange,
+            uri.to_string().as_str(),
+            Debug,
+            "Trying to contact {} at {}...",
+            uri,
+            addr
+        );
         if let Ok(raw_stream) = TcpStream::connect(addr) {
             if let Some(seconds) = timeout {
                 let _ = raw_stream.set_read_timeout(Some(Duration::from_secs(seconds)));
@@ -60,13 +99,34 @@ fn connect_to_some(
     Err(Error::Url(UrlError::UnableToConnect(uri.to_string())))
 }
 
+/// Builds the tungstenite config for `max_frame_size`, None means tungstenite defaults.
+///
+/// The message size limit is raised along with the frame size, so that a
+/// message consisting of a single frame never hits it first.
+pub(super) fn websocket_config(max_frame_size: Option<usize>) -> Option<WebSocketConfig> {
+    max_frame_size.map(|size| {
+        let default_config = WebSocketConfig::default();
+        WebSocketConfig {
+            max_frame_size: Some(size),
+            max_message_size: default_config.max_message_size.map(|x| x.max(size)),
+            ..default_config
+        }
+    })
+}
+
 // Usually ws_stream.read_message() blocks forever,
 // with this function, it returns after `timeout` seconds if no data comming in
 fn connect_with_timeout(
+    exchange: &str,
     url: &str,
+    headers: &HeaderMap,
     timeout: Option<u64>,
+    max_frame_size: Option<usize>,
 ) -> Result<(WebSocket<AutoStream>, Response)> {
-    let request = url.into_client_request()?;
+    let mut request = url.into_client_request()?;
+    for (name, value) in headers {
+        request.headers_mut().insert(name, value.clone());
+    }
 
     let uri = request.uri();
     let mode = client::uri_mode(uri)?;
@@ -79,42 +139,173 @@ fn connect_with_timeout(
         Mode::Tls => 443,
     });
     let addrs = (host, port).to_socket_addrs()?;
-    let mut stream = connect_to_some(addrs.as_slice(), request.uri(), mode, timeout)?;
+    let mut stream = connect_to_some(exchange, addrs.as_slice(), request.uri(), mode, timeout)?;
     NoDelay::set_nodelay(&mut stream, true)?;
-    tungstenite::client(request, stream).map_err(|e| match e {
-        HandshakeError::Failure(f) => f,
-        HandshakeError::Interrupted(_) => panic!("Bug: blocking handshake not blocked"),
+    client::client_with_config(request, stream, websocket_config(max_frame_size)).map_err(|e| {
+        match e {
+            HandshakeError::Failure(f) => f,
+            HandshakeError::Interrupted(_) => panic!("Bug: blocking handshake not blocked"),
+        }
     })
 }
 
+/// Maximum number of connecting attempts before giving up.
+const MAX_CONNECT_ATTEMPTS: u32 = 8;
+/// The delay before the first retry, doubled after each failed attempt.
+const BACKOFF_BASE: Duration = Duration::from_secs(1);
+/// The delay between two attempts never exceeds this value.
+const BACKOFF_MAX: Duration = Duration::from_secs(60);
+
+// Capped exponential backoff, i.e., 1s, 2s, 4s ... 60s, callers randomize it
+// with with_jitter()
+fn backoff_delay(attempt: u32, base: Duration) -> Duration {
+    let factor = 2u32.saturating_pow(attempt);
+    std::cmp::min(base.saturating_mul(factor), BACKOFF_MAX)
+}
+
+// Randomizes the delay into [delay/2, delay), so that many clients
+// disconnected at the same time don't reconnect at the same time
+fn with_jitter(delay: Duration) -> Duration {
+    let nanos = time::SystemTime::now()
+        .duration_since(time::UNIX_EPOCH)
+        .unwrap()
+        .subsec_nanos();
+    let half = delay / 2;
+    half + half.mul_f64(nanos as f64 / 1_000_000_000.0)
+}
+
+// The Retry-After header of a rejected handshake, e.g., HTTP 429, capped at
+// BACKOFF_MAX. Only the delay-seconds form is supported.
+fn retry_after(err: &Error) -> Option<Duration> {
+    if let Error::Http(response) = err {
+        let seconds = response
+            .headers()
+            .get(http::header::RETRY_AFTER)?
+            .to_str()
+            .ok()?
+            .trim()
+            .parse::<u64>()
+            .ok()?;
+        Some(std::cmp::min(Duration::from_secs(seconds), BACKOFF_MAX))
+    } else {
+        None
+    }
+}
+
+/// How long close_gracefully() waits between unsubscribing and closing.
+pub(crate) const GRACEFUL_CLOSE_DELAY: Duration = Duration::from_secs(1);
+
+/// Number of consecutive failed attempts before moving on to the next URL.
+const ATTEMPTS_PER_URL: u32 = 2;
+
+// Index of the URL to try at `attempt`, starting from `start` and moving on
+// to the next URL after every ATTEMPTS_PER_URL failures
+fn url_index(num_urls: usize, start: usize, attempt: u32) -> usize {
+    (start + (attempt / ATTEMPTS_PER_URL) as usize) % num_urls
+}
+
 // This function is equivalent to tungstenite::connect(), with an additional benefit that
-// it can make read_message() timeout after 5 seconds
-pub(super) fn connect_with_retry(url: &str, timeout: Option<u64>) -> WebSocket<AutoStream> {
-    let max_count = 5;
-    let mut backoff_factor = 1;
-    let backoff_duration = time::Duration::from_secs(if url.contains("bitmex") { 16 } else { 4 });
+// it can make read_message() timeout after 5 seconds.
+//
+// It starts from urls[start] and rotates through `urls` on repeated failures,
+// returns the stream and the index of the URL connected to. Incoming frames
+// larger than `max_frame_size` are refused, None means the tungstenite default.
+// `headers` are added to the handshake request.
+pub(super) fn connect_with_retry(
+    exchange: &str,
+    urls: &[String],
+    headers: &HeaderMap,
+    start: usize,
+    timeout: Option<u64>,
+    max_frame_size: Option<usize>,
+) -> (WebSocket<AutoStream>, usize) {
+    assert!(!urls.is_empty(), "No websocket URL to connect to");
+    let base = if exchange == "bitmex" {
+        BACKOFF_BASE * 4
+    } else {
+        BACKOFF_BASE
+    };
+    let max_attempts = std::cmp::max(MAX_CONNECT_ATTEMPTS, ATTEMPTS_PER_URL * urls.len() as u32);
     let mut error_msg: String = String::new();
-    for i in 0..max_count {
-        let res = connect_with_timeout(url, timeout);
+    for attempt in 0..max_attempts {
+        let index = url_index(urls.len(), start, attempt);
+        let url = urls[index].as_str();
+        let res = connect_with_timeout(exchange, url, headers, timeout, max_frame_size);
         match res {
-            Ok((ws_stream, _)) => return ws_stream,
+            Ok((ws_stream, _)) => return (ws_stream, index),
             Err(err) => {
                 error_msg = err.to_string();
-                if error_msg.contains("429") {
-                    backoff_factor += 1;
-                } else {
-                    backoff_factor *= 2;
+                if attempt + 1 == max_attempts {
+                    break;
                 }
-                warn!(
-                    "Failed connecting to {} the {}th time, error: {}",
-                    url, i, err
+                let delay =
+                    retry_after(&err).unwrap_or_else(|| with_jitter(backoff_delay(attempt, base)));
+                endpoint_log!(
+                    exchange,
+                    url,
+                    Warn,
+                    "Failed connecting to {}, attempt {}/{}, retrying in {:?}, error: {}",
+                    url,
+                    attempt + 1,
+                    max_attempts,
+                    delay,
+                    err
                 );
-                thread::sleep(backoff_duration * backoff_factor);
+                thread::sleep(delay);
             }
         }
     }
 
-    panic!("Error connecting to {}, error: {}, aborted", url, error_msg);
+    panic!(
+        "Error connecting to {} after {} attempts, error: {}, aborted",
+        urls.join(", "),
+        max_attempts,
+        error_msg
+    );
+}
+
+/// Candidate URLs of a websocket server, remembering which one is in use.
+pub(super) struct Endpoints {
+    exchange: &'static str,
+    urls: Vec<String>,
+    current: AtomicUsize,
+}
+
+impl Endpoints {
+    pub fn new(exchange: &'static str, urls: Vec<String>) -> Self {
+        Endpoints {
+            exchange,
+            urls,
+            current: AtomicUsize::new(0),
+        }
+    }
+
+    /// The URL connected to most recently.
+    pub fn current(&self) -> &str {
+        self.urls[self.current.load(Ordering::Acquire)].as_str()
+    }
+
+    /// Connects to the current URL, and moves on to the next ones if it keeps failing.
+    ///
+    /// Headers set by `set_user_agent()` and `set_header()` are read on every call.
+    pub fn connect(
+        &self,
+        timeout: Option<u64>,
+        max_frame_size: Option<usize>,
+    ) -> WebSocket<AutoStream> {
+        let start = self.current.load(Ordering::Acquire);
+        let headers = crypto_pair::headers(self.exchange);
+        let (ws_stream, index) = connect_with_retry(
+            self.exchange,
+            &self.urls,
+            &headers,
+            start,
+            timeout,
+            max_frame_size,
+        );
+        self.current.store(index, Ordering::Release);
+        ws_stream
+    }
 }
 
 pub(super) const CHANNEL_PAIR_DELIMITER: char = ':';
@@ -167,3 +358,257 @@ pub(crate) fn ensure_frame_size(
 
     all_commands
 }
+
+/// Default upper bound of a subscribe/unsubscribe command, in bytes.
+pub(crate) const DEFAULT_MAX_COMMAND_BYTES: usize = 65536;
+
+/// Ensure that no command produced by `channels_to_commands` exceeds `max_bytes`.
+///
+/// Channels are bisected until every command fits, so this works with any
+/// `channels_to_commands` on top of its own exchange-specific limits. A single
+/// channel whose command is still too long is sent out as it is.
+pub(crate) fn split_oversized_commands(
+    channels: &[String],
+    subscribe: bool,
+    channels_to_commands: fn(&[String], bool) -> Vec<String>,
+    max_bytes: usize,
+) -> Vec<String> {
+    let commands = channels_to_commands(channels, subscribe);
+    if channels.len() <= 1 || commands.iter().all(|command| command.len() <= max_bytes) {
+        return commands;
+    }
+    let (left, right) = channels.split_at(channels.len() / 2);
+    let mut commands = split_oversized_commands(left, subscribe, channels_to_commands, max_bytes);
+    commands.extend(split_oversized_commands(
+        right,
+        subscribe,
+        channels_to_commands,
+        max_bytes,
+    ));
+    commands
+}
+
+// threshold and the callback
+type StaleConfig = (Duration, fn(&str));
+
+/// Tracks when the last normal message arrived and calls `on_stale` once
+/// no normal message has arrived for `threshold`.
+///
+/// Unlike the read-timeout exit in `run()`, this doesn't tear down the
+/// connection. The callback fires once per quiet period, and is re-armed by
+/// the next normal message.
+pub(crate) struct StalenessWatchdog {
+    config: Mutex<Option<StaleConfig>>,
+    last_normal_msg: Mutex<Instant>,
+    reported: AtomicBool,
+}
+
+impl StalenessWatchdog {
+    pub(crate) fn new() -> Self {
+        StalenessWatchdog {
+            config: Mutex::new(None),
+            last_normal_msg: Mutex::new(Instant::now()),
+            reported: AtomicBool::new(false),
+        }
+    }
+
+    pub(crate) fn set(&self, threshold: Duration, on_stale: Option<fn(&str)>) {
+        *self.config.lock().unwrap() = on_stale.map(|f| (threshold, f));
+        self.on_normal_msg();
+    }
+
+    pub(crate) fn on_normal_msg(&self) {
+        *self.last_normal_msg.lock().unwrap() = Instant::now();
+        self.reported.store(false, Ordering::Release);
+    }
+
+    /// Calls `on_stale(url)` if the threshold has been exceeded, returns true if called.
+    pub(crate) fn check(&self, exchange: &str, url: &str) -> bool {
+        if let Some((threshold, on_stale)) = *self.config.lock().unwrap() {
+            let elapsed = self.last_normal_msg.lock().unwrap().elapsed();
+            if elapsed >= threshold && !self.reported.swap(true, Ordering::AcqRel) {
+                endpoint_log!(
+                    exchange,
+                    url,
+                    Warn,
+                    "No data from {} for {} seconds",
+                    url,
+                    elapsed.as_secs()
+                );
+                on_stale(url);
+                return true;
+            }
+        }
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        backoff_delay, retry_after, split_oversized_commands, url_index, with_jitter, Endpoints,
+        StalenessWatchdog, BACKOFF_BASE, BACKOFF_MAX,
+    };
+    use crate::clients::mock_server::MockServer;
+    use std::{net::TcpListener, time::Duration};
+
+    #[test]
+    fn test_backoff_delay() {
+        let delays: Vec<u64> = (0..8)
+            .map(|attempt| backoff_delay(attempt, BACKOFF_BASE).as_secs())
+            .collect();
+        assert_eq!(vec![1, 2, 4, 8, 16, 32, 60, 60], delays);
+        assert_eq!(BACKOFF_MAX, backoff_delay(100, BACKOFF_BASE));
+    }
+
+    #[test]
+    fn test_with_jitter() {
+        let delay = Duration::from_secs(8);
+        for _ in 0..10 {
+            let jittered = with_jitter(delay);
+            assert!(jittered >= delay / 2 && jittered < delay);
+        }
+    }
+
+    #[test]
+    fn test_retry_after() {
+        let rejected = |retry_after: Option<&str>| {
+            let mut builder = http::Response::builder().status(429);
+            if let Some(value) = retry_after {
+                builder = builder.header(http::header::RETRY_AFTER, value);
+            }
+            tungstenite::Error::Http(builder.body(None).unwrap())
+        };
+        assert_eq!(
+            Some(Duration::from_secs(5)),
+            retry_after(&rejected(Some("5")))
+        );
+        assert_eq!(Some(BACKOFF_MAX), retry_after(&rejected(Some("3600"))));
+        assert_eq!(
+            None,
+            retry_after(&rejected(Some("Wed, 21 Oct 2015 07:28:00 GMT")))
+        );
+        assert_eq!(None, retry_after(&rejected(None)));
+        assert_eq!(None, retry_after(&tungstenite::Error::ConnectionClosed));
+    }
+
+    #[test]
+    fn test_url_index() {
+        let indices: Vec<usize> = (0..8).map(|attempt| url_index(3, 0, attempt)).collect();
+        assert_eq!(vec![0, 0, 1, 1, 2, 2, 0, 0], indices);
+        assert_eq!(2, url_index(3, 2, 0));
+        assert_eq!(0, url_index(3, 2, 2));
+        assert_eq!(0, url_index(1, 0, 5));
+    }
+
+    #[test]
+    fn test_connect_failover() {
+        // nothing listens on this port once the listener is dropped
+        let refused_port = TcpListener::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap()
+            .port();
+
+        let server = MockServer::start(Vec::new());
+
+        let endpoints = Endpoints::new(
+            "mock",
+            vec![
+                format!("ws://127.0.0.1:{}", refused_port),
+                server.url.clone(),
+            ],
+        );
+        assert_eq!(
+            format!("ws://127.0.0.1:{}", refused_port),
+            endpoints.current()
+        );
+        let ws_stream = endpoints.connect(None, None);
+        assert_eq!(server.url, endpoints.current());
+        drop(ws_stream);
+        server.received();
+    }
+
+    // Accepts one client and returns headers of its handshake request
+    fn connect_and_capture_headers(exchange: &'static str) -> http::HeaderMap {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("ws://{}", listener.local_addr().unwrap());
+        let server = std::thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut headers = http::HeaderMap::new();
+            let _ws_stream = tungstenite::accept_hdr(
+                stream,
+                |request: &tungstenite::handshake::server::Request, response| {
+                    headers = request.headers().clone();
+                    Ok(response)
+                },
+            )
+            .unwrap();
+            headers
+        });
+        let ws_stream = Endpoints::new(exchange, vec![url]).connect(None, None);
+        let headers = server.join().unwrap();
+        drop(ws_stream);
+        headers
+    }
+
+    #[test]
+    fn test_handshake_headers() {
+        let headers = connect_and_capture_headers("mock_default");
+        assert_eq!(
+            crate::DEFAULT_USER_AGENT,
+            headers.get(http::header::USER_AGENT).unwrap()
+        );
+
+        crate::set_user_agent("mock_custom", "my-crawler/1.0");
+        crate::set_header("mock_custom", "X-Custom", "value");
+        let headers = connect_and_capture_headers("mock_custom");
+        assert_eq!(
+            "my-crawler/1.0",
+            headers.get(http::header::USER_AGENT).unwrap()
+        );
+        assert_eq!("value", headers.get("x-custom").unwrap());
+        // handshake headers are kept
+        assert!(headers.contains_key(http::header::SEC_WEBSOCKET_KEY));
+    }
+
+    fn all_in_one_command(channels: &[String], _subscribe: bool) -> Vec<String> {
+        vec![serde_json::to_string(channels).unwrap()]
+    }
+
+    #[test]
+    fn test_split_oversized_commands() {
+        let channels: Vec<String> = (0..1000).map(|i| format!("trade:SYMBOL{}", i)).collect();
+        let commands = split_oversized_commands(&channels, true, all_in_one_command, 1024);
+        assert!(commands.len() > 1);
+        assert!(commands.iter().all(|command| command.len() <= 1024));
+
+        let restored: Vec<String> = commands
+            .iter()
+            .flat_map(|command| serde_json::from_str::<Vec<String>>(command).unwrap())
+            .collect();
+        assert_eq!(channels, restored);
+
+        let commands = split_oversized_commands(&channels[..1], true, all_in_one_command, 1);
+        assert_eq!(1, commands.len());
+    }
+
+    #[test]
+    fn test_staleness_watchdog() {
+        fn on_stale(_url: &str) {}
+
+        let watchdog = StalenessWatchdog::new();
+        assert!(!watchdog.check("binance", "wss://example.com")); // disabled by default
+
+        watchdog.set(Duration::from_millis(50), Some(on_stale));
+        assert!(!watchdog.check("binance", "wss://example.com"));
+        std::thread::sleep(Duration::from_millis(60));
+        assert!(watchdog.check("binance", "wss://example.com"));
+        assert!(!watchdog.check("binance", "wss://example.com")); // only once per quiet period
+
+        watchdog.on_normal_msg();
+        assert!(!watchdog.check("binance", "wss://example.com"));
+        std::thread::sleep(Duration::from_millis(60));
+        assert!(watchdog.check("binance", "wss://example.com"));
+    }
+}
@@ -1,41 +1,78 @@
-use super::utils::connect_with_retry;
+use super::registry;
+use super::utils::{
+    split_oversized_commands, websocket_config, Endpoints, StalenessWatchdog,
+    DEFAULT_MAX_COMMAND_BYTES, GRACEFUL_CLOSE_DELAY,
+};
+use crate::{AuthError, Decompressor, ResetNotification, UnsupportedChannel, WSClientStats};
 use std::{
-    collections::HashSet,
-    io::prelude::*,
+    cell::RefCell,
+    collections::{HashMap, HashSet},
     sync::{
-        atomic::{AtomicBool, AtomicIsize, Ordering},
+        atomic::{AtomicBool, AtomicIsize, AtomicUsize, Ordering},
         mpsc::Sender,
         Mutex,
     },
+    thread::ThreadId,
     time::{Duration, Instant},
 };
 
-use flate2::read::{DeflateDecoder, GzDecoder};
-use log::*;
 use tungstenite::{
     client::AutoStream, error::ProtocolError, protocol::frame::coding::CloseCode, Error, Message,
     WebSocket,
 };
 
+thread_local! {
+    // decompresses binary frames of all clients running on this thread
+    static DECOMPRESSOR: RefCell<Decompressor> = RefCell::new(Decompressor::new());
+}
+
 pub(super) enum MiscMessage {
     WebSocket(Message), // WebSocket message that needs to be sent to the server
     Reconnect,          // Needs to reconnect
     Misc,               // Misc message
     Pong,               // Pong message
+    Rejected(String),   // The server rejected to subscribe this channel
+    Login(bool),        // The server replied to the login command, true if it succeeded
     Normal,             // Normal message will be passed to on_msg
 }
 
+// Builds the login command, see WSClientInternal::login()
+pub(super) type LoginCommandFn = Box<dyn Fn() -> Result<String, AuthError> + Send + Sync>;
+
 // `WSClientInternal` should be Sync + Send so that it can be put into Arc directly.
 pub(super) struct WSClientInternal {
     exchange: &'static str, // Eexchange name
-    pub(super) url: String, // Websocket base url
+    endpoints: Endpoints,   // Websocket base urls, tried in turn
     ws_stream: Mutex<WebSocket<AutoStream>>,
-    channels: Mutex<HashSet<String>>,     // subscribed channels
+    channels: Mutex<HashSet<String>>, // subscribed channels
+    num_channels: AtomicUsize,        // channels.len(), readable without locking for logs
+    // channels rejected by the server, removed from channels so that reconnect skips them
+    rejected_channels: Mutex<Vec<String>>,
+    // rejected channels already logged by report_rejected_channels()
+    num_reported_rejections: AtomicUsize,
+    num_reconnects: AtomicUsize, // reported by stats()
+    // raw channels collected by collect_channels() instead of being sent, per thread
+    collected_channels: Mutex<HashMap<ThreadId, Vec<String>>>,
+    // builds the login command, called again on reconnect since it may be time-sensitive
+    login_command: Mutex<Option<LoginCommandFn>>,
+    // channels are not sent until the server replies to the login command, see on_login()
+    awaiting_login: AtomicBool,
     tx: Mutex<Sender<String>>,            // The sending half of a channel
     on_misc_msg: fn(&str) -> MiscMessage, // handle misc messages
     // converts raw channels to subscribe/unsubscribe commands
     channels_to_commands: fn(&[String], bool) -> Vec<String>,
+    // commands longer than this are split by split_oversized_commands()
+    max_command_bytes: AtomicUsize,
+    // calls on_stale when no normal message arrives for a while
+    watchdog: StalenessWatchdog,
+    // receives binary frames of normal messages as they are, and whether tx still
+    // receives the decompressed text, see set_raw_sender()
+    raw_tx: Mutex<Option<(Sender<Vec<u8>>, bool)>>,
     should_stop: AtomicBool, // used by close() and run()
+    // reconnects in place instead of exiting, see set_reset_on_reconnect()
+    reset_tx: Mutex<Option<Sender<ResetNotification>>>,
+    // incoming frames larger than this are refused, None means the tungstenite default
+    max_frame_size: Mutex<Option<usize>>,
     // how often the client should send a ping, None means the client doesn't need to send
     // ping, instead the server will send ping and the client just needs to reply a pong
     client_ping_interval_and_msg: Option<(u64, &'static str)>,
@@ -50,7 +87,7 @@ pub(super) struct WSClientInternal {
 impl WSClientInternal {
     pub fn new(
         exchange: &'static str,
-        url: &str,
+        urls: Vec<String>,
         tx: Sender<String>,
         on_misc_msg: fn(&str) -> MiscMessage,
         channels_to_commands: fn(&[String], bool) -> Vec<String>,
@@ -66,16 +103,29 @@ impl WSClientInternal {
         } else {
             server_ping_interval
         };
-        let stream = connect_with_retry(url, timeout);
+        let endpoints = Endpoints::new(exchange, urls);
+        let stream = endpoints.connect(timeout, None);
         WSClientInternal {
             exchange,
-            url: url.to_string(),
+            endpoints,
             ws_stream: Mutex::new(stream),
             tx: Mutex::new(tx),
             on_misc_msg,
             channels: Mutex::new(HashSet::new()),
+            num_channels: AtomicUsize::new(0),
+            rejected_channels: Mutex::new(Vec::new()),
+            num_reported_rejections: AtomicUsize::new(0),
+            num_reconnects: AtomicUsize::new(0),
+            collected_channels: Mutex::new(HashMap::new()),
+            login_command: Mutex::new(None),
+            awaiting_login: AtomicBool::new(false),
             channels_to_commands,
+            max_command_bytes: AtomicUsize::new(DEFAULT_MAX_COMMAND_BYTES),
+            watchdog: StalenessWatchdog::new(),
+            raw_tx: Mutex::new(None),
             should_stop: AtomicBool::new(false),
+            reset_tx: Mutex::new(None),
+            max_frame_size: Mutex::new(None),
             client_ping_interval_and_msg,
             num_unanswered_ping: AtomicIsize::new(0),
             server_ping_interval,
@@ -83,39 +133,198 @@ impl WSClientInternal {
     }
 
     pub fn subscribe(&self, channels: &[String]) {
+        if let Some(collected) = self
+            .collected_channels
+            .lock()
+            .unwrap()
+            .get_mut(&std::thread::current().id())
+        {
+            collected.extend_from_slice(channels);
+            return;
+        }
         self.subscribe_or_unsubscribe(channels, true);
     }
 
+    /// Runs `subscribe_fn` without sending anything to the server, and returns
+    /// the raw channels it would have subscribed to.
+    ///
+    /// High-level APIs such as `subscribe_trade()` only know how to build
+    /// subscribe channels, this function reuses them to build the channels to
+    /// unsubscribe from.
+    pub fn collect_channels<F: FnOnce() -> Result<(), UnsupportedChannel>>(
+        &self,
+        subscribe_fn: F,
+    ) -> Result<Vec<String>, UnsupportedChannel> {
+        let thread_id = std::thread::current().id();
+        self.collected_channels
+            .lock()
+            .unwrap()
+            .insert(thread_id, Vec::new());
+        let result = subscribe_fn();
+        let channels = self
+            .collected_channels
+            .lock()
+            .unwrap()
+            .remove(&thread_id)
+            .unwrap_or_default();
+        result.map(|_| channels)
+    }
+
     pub fn unsubscribe(&self, channels: &[String]) {
         self.subscribe_or_unsubscribe(channels, false);
     }
 
-    fn get_send_interval_ms(&self) -> Option<u64> {
-        match self.exchange {
-            "binance" => Some(100), // WebSocket connections have a limit of 10 incoming messages per second
-            "kucoin" => Some(100),  //  Message limit sent to the server: 100 per 10 seconds
-            _ => None,
+    /// Sends the command built by `login_command` now and after every reconnect.
+    ///
+    /// Channels are sent once the server replies to the login command, so
+    /// private channels are never subscribed before the login succeeds.
+    pub fn login(&self, login_command: LoginCommandFn) -> Result<(), AuthError> {
+        let command = login_command()?;
+        *self.login_command.lock().unwrap() = Some(login_command);
+        self.send_login_command(command);
+        Ok(())
+    }
+
+    fn send_login_command(&self, command: String) {
+        {
+            // under the lock, so that subscribe_or_unsubscribe() either sends
+            // its channels or leaves them to on_login()
+            let _guard = self.channels.lock().unwrap();
+            self.awaiting_login.store(true, Ordering::Release);
+        }
+        if let Err(err) = self
+            .ws_stream
+            .lock()
+            .unwrap()
+            .write_message(Message::Text(command))
+        {
+            client_log!(
+                self,
+                Error,
+                "Failed to send the login command due to {}",
+                err
+            );
+        }
+    }
+
+    // Sends channels held back while waiting for the reply to the login command
+    fn on_login(&self, succeeded: bool) {
+        let channels = {
+            let guard = self.channels.lock().unwrap();
+            if !self.awaiting_login.swap(false, Ordering::AcqRel) {
+                return;
+            }
+            guard.iter().cloned().collect::<Vec<String>>()
+        };
+        if !succeeded {
+            client_log!(
+                self,
+                Error,
+                "Login failed, private channels will be rejected"
+            );
+        }
+        self.resubscribe(&channels);
+    }
+
+    pub fn set_max_command_bytes(&self, max_bytes: usize) {
+        self.max_command_bytes.store(max_bytes, Ordering::Release);
+    }
+
+    pub fn set_on_stale(&self, threshold: u64, on_stale: Option<fn(&str)>) {
+        self.watchdog.set(Duration::from_secs(threshold), on_stale);
+    }
+
+    pub fn set_raw_sender(&self, raw_tx: Option<Sender<Vec<u8>>>, keep_text: bool) {
+        *self.raw_tx.lock().unwrap() = raw_tx.map(|raw_tx| (raw_tx, keep_text));
+    }
+
+    pub fn set_reset_on_reconnect(&self, reset_tx: Option<Sender<ResetNotification>>) {
+        *self.reset_tx.lock().unwrap() = reset_tx;
+    }
+
+    pub fn set_max_frame_size(&self, max_frame_size: Option<usize>) {
+        *self.max_frame_size.lock().unwrap() = max_frame_size;
+        let config = websocket_config(max_frame_size).unwrap_or_default();
+        self.ws_stream
+            .lock()
+            .unwrap()
+            .set_config(|current| *current = config);
+    }
+
+    pub fn stats(&self) -> WSClientStats {
+        WSClientStats {
+            num_channels: self.num_channels(),
+            rejected_channels: self.rejected_channels.lock().unwrap().clone(),
+            num_reconnects: self.num_reconnects.load(Ordering::Acquire),
         }
     }
 
+    pub fn subscriptions(&self) -> Vec<String> {
+        // clone under the lock and sort afterwards, so that subscribe() waits as short as possible
+        let mut channels = self
+            .channels
+            .lock()
+            .unwrap()
+            .iter()
+            .cloned()
+            .collect::<Vec<String>>();
+        channels.sort();
+        channels
+    }
+
+    fn channels_to_commands(&self, channels: &[String], subscribe: bool) -> Vec<String> {
+        split_oversized_commands(
+            channels,
+            subscribe,
+            self.channels_to_commands,
+            self.max_command_bytes.load(Ordering::Acquire),
+        )
+    }
+
+    /// The websocket URL currently in use.
+    pub(super) fn url(&self) -> &str {
+        self.endpoints.current()
+    }
+
+    fn num_channels(&self) -> usize {
+        self.num_channels.load(Ordering::Acquire)
+    }
+
+    fn get_send_interval_ms(&self) -> Option<u64> {
+        registry::lookup(self.exchange).send_interval_ms
+    }
+
     fn subscribe_or_unsubscribe(&self, channels: &[String], subscribe: bool) {
         let mut diff = Vec::<String>::new();
-        {
+        let awaiting_login = {
             let mut guard = self.channels.lock().unwrap();
             for ch in channels.iter() {
-                if guard.insert(ch.clone()) {
+                let changed = if subscribe {
+                    guard.insert(ch.clone())
+                } else {
+                    guard.remove(ch)
+                };
+                if changed {
                     diff.push(ch.clone());
                 }
             }
-        }
+            self.num_channels.store(guard.len(), Ordering::Release);
+            self.awaiting_login.load(Ordering::Acquire)
+        };
 
-        if !diff.is_empty() {
-            let commands = (self.channels_to_commands)(&diff, subscribe);
+        // on_login() sends all channels later
+        if !diff.is_empty() && !awaiting_login {
+            let commands = self.channels_to_commands(&diff, subscribe);
             let mut ws_stream = self.ws_stream.lock().unwrap();
             commands.into_iter().for_each(|command| {
                 let ret = ws_stream.write_message(Message::Text(command));
                 if let Err(err) = ret {
-                    error!("Failed to send commands due to {}, exiting", err);
+                    client_log!(
+                        self,
+                        Error,
+                        "Failed to send commands due to {}, exiting",
+                        err
+                    );
                     std::thread::sleep(Duration::from_secs(5));
                     std::process::exit(1); // fail fast, pm2 will restart
                 }
@@ -126,9 +335,52 @@ impl WSClientInternal {
         }
     }
 
+    // Stops tracking a channel rejected by the server, it is reported later
+    // by report_rejected_channels() together with others.
+    fn reject_channel(&self, channel: String) {
+        {
+            let mut guard = self.channels.lock().unwrap();
+            guard.remove(&channel);
+            self.num_channels.store(guard.len(), Ordering::Release);
+        }
+        let mut rejected = self.rejected_channels.lock().unwrap();
+        if !rejected.contains(&channel) {
+            rejected.push(channel);
+        }
+    }
+
+    // Logs channels rejected since the last call in one line, because
+    // subscribing all symbols may get hundreds of rejections in a row.
+    fn report_rejected_channels(&self) {
+        let rejected = self.rejected_channels.lock().unwrap();
+        let num_reported = self.num_reported_rejections.load(Ordering::Acquire);
+        if rejected.len() > num_reported {
+            client_log!(
+                self,
+                Warn,
+                "{} channels were rejected by the server and removed: {}",
+                rejected.len() - num_reported,
+                rejected[num_reported..].join(", ")
+            );
+            self.num_reported_rejections
+                .store(rejected.len(), Ordering::Release);
+        }
+    }
+
+    // Reconnects if set_reset_on_reconnect() is enabled, otherwise exits the process
+    fn reconnect_or_exit(&self) {
+        if self.reset_tx.lock().unwrap().is_some() {
+            self.reconnect();
+        } else {
+            std::thread::sleep(Duration::from_secs(5));
+            std::process::exit(1); // fail fast, pm2 will restart
+        }
+    }
+
     // reconnect and subscribe all channels
-    fn _reconnect(&self) {
-        warn!("Reconnecting to {}", &self.url);
+    fn reconnect(&self) {
+        client_log!(self, Warn, "Reconnecting to {}", self.url());
+        self.num_reconnects.fetch_add(1, Ordering::AcqRel);
         {
             let mut guard = self.ws_stream.lock().unwrap();
             let timeout = if self.client_ping_interval_and_msg.is_some()
@@ -142,7 +394,22 @@ impl WSClientInternal {
             } else {
                 self.server_ping_interval
             };
-            *guard = connect_with_retry(self.url.as_str(), timeout);
+            *guard = self
+                .endpoints
+                .connect(timeout, *self.max_frame_size.lock().unwrap());
+        }
+        // a login command sent on the previous connection may never be answered
+        self.awaiting_login.store(false, Ordering::Release);
+        let login_command = self
+            .login_command
+            .lock()
+            .unwrap()
+            .as_ref()
+            .map(|login_command| login_command());
+        match login_command {
+            Some(Ok(command)) => self.send_login_command(command),
+            Some(Err(err)) => client_log!(self, Error, "{}", err),
+            None => (),
         }
         let channels = self
             .channels
@@ -151,13 +418,27 @@ impl WSClientInternal {
             .iter()
             .map(|s| s.to_string())
             .collect::<Vec<String>>();
+        // data of the new connection comes after the notification
+        if let Some(reset_tx) = self.reset_tx.lock().unwrap().as_ref() {
+            // the receiver may have been dropped if nobody handles resets
+            let _ = reset_tx.send(ResetNotification {
+                channels: channels.clone(),
+            });
+        }
+        // otherwise on_login() resubscribes
+        if !self.awaiting_login.load(Ordering::Acquire) {
+            self.resubscribe(&channels);
+        }
+    }
+
+    fn resubscribe(&self, channels: &[String]) {
         if !channels.is_empty() {
-            let commands = (self.channels_to_commands)(&channels, true);
+            let commands = self.channels_to_commands(channels, true);
             let mut ws_stream = self.ws_stream.lock().unwrap();
             commands.into_iter().for_each(|command| {
                 let ret = ws_stream.write_message(Message::Text(command));
                 if let Err(err) = ret {
-                    error!("{}", err);
+                    client_log!(self, Error, "{}", err);
                 }
                 if let Some(interval) = self.get_send_interval_ms() {
                     std::thread::sleep(Duration::from_millis(interval));
@@ -166,48 +447,79 @@ impl WSClientInternal {
         }
     }
 
-    // Handle a text msg from Message::Text or Message::Binary
+    // Handle a text msg from Message::Text or Message::Binary, binary is the
+    // original frame before decompression.
     // Returns true if gets a normal message, otherwise false
-    fn handle_msg(&self, txt: &str) -> bool {
+    fn handle_msg(&self, txt: &str, binary: Option<&[u8]>) -> bool {
         match (self.on_misc_msg)(txt) {
             MiscMessage::Misc => false,
             MiscMessage::Pong => {
                 self.num_unanswered_ping.store(0, Ordering::Release);
-                debug!(
-                    "Received {} from {}, reset num_unanswered_ping to {}",
+                client_log!(
+                    self,
+                    Debug,
+                    "Received {}, reset num_unanswered_ping to {}",
                     txt,
-                    self.exchange,
                     self.num_unanswered_ping.load(Ordering::Acquire)
                 );
                 false
             }
+            MiscMessage::Rejected(channel) => {
+                self.reject_channel(channel);
+                false
+            }
+            MiscMessage::Login(succeeded) => {
+                self.on_login(succeeded);
+                false
+            }
             MiscMessage::Reconnect => {
-                // self.reconnect();
-                std::thread::sleep(Duration::from_secs(5));
-                std::process::exit(1); // fail fast, pm2 will restart
+                self.reconnect_or_exit();
+                false
             }
             MiscMessage::WebSocket(ws_msg) => {
+                // replies to server heartbeats, which prove the connection is
+                // alive just like a pong does
+                self.num_unanswered_ping.store(0, Ordering::Release);
                 let ret = self.ws_stream.lock().unwrap().write_message(ws_msg);
                 if let Err(err) = ret {
-                    error!("{}", err);
+                    client_log!(self, Error, "{}", err);
                 }
                 false
             }
             MiscMessage::Normal => {
+                self.report_rejected_channels();
+                if let Some(binary) = binary {
+                    let mut guard = self.raw_tx.lock().unwrap();
+                    if let Some((raw_tx, keep_text)) = guard.as_ref() {
+                        if raw_tx.send(binary.to_vec()).is_ok() {
+                            if !keep_text {
+                                self.watchdog.on_normal_msg();
+                                return true;
+                            }
+                        } else {
+                            // the receiver has been dropped, fall back to text
+                            client_log!(self, Warn, "raw receiver dropped, disable raw frames");
+                            *guard = None;
+                        }
+                    }
+                }
                 if self.exchange == super::mxc::EXCHANGE_NAME
-                    && self.url.as_str() == super::mxc::SPOT_WEBSOCKET_URL
+                    && super::mxc::is_socketio_url(self.url())
                 {
                     // special logic for MXC Spot
                     match txt.strip_prefix("42") {
                         Some(msg) => self.tx.lock().unwrap().send(msg.to_string()).unwrap(),
-                        None => error!(
-                            "{}, Not possible, should be handled by {}.on_misc_msg() previously",
-                            txt, self.exchange
+                        None => client_log!(
+                            self,
+                            Error,
+                            "{}, Not possible, should be handled by on_misc_msg() previously",
+                            txt
                         ),
                     }
                 } else {
                     self.tx.lock().unwrap().send(txt.to_string()).unwrap();
                 }
+                self.watchdog.on_normal_msg();
                 true
             }
         }
@@ -224,34 +536,29 @@ impl WSClientInternal {
                 Ok(msg) => {
                     num_read_timeout = 0;
                     match msg {
-                        Message::Text(txt) => succeeded = self.handle_msg(&txt),
+                        Message::Text(txt) => succeeded = self.handle_msg(&txt, None),
                         Message::Binary(binary) => {
-                            let mut txt = String::new();
-                            let resp = match self.exchange {
-                                super::huobi::EXCHANGE_NAME
-                                | super::binance::EXCHANGE_NAME
-                                | super::bitget::EXCHANGE_NAME
-                                | super::bitz::EXCHANGE_NAME => {
-                                    let mut decoder = GzDecoder::new(&binary[..]);
-                                    decoder.read_to_string(&mut txt)
-                                }
-                                super::okex::EXCHANGE_NAME => {
-                                    let mut decoder = DeflateDecoder::new(&binary[..]);
-                                    decoder.read_to_string(&mut txt)
-                                }
-                                _ => {
-                                    error!("Unknown binary format from {}", self.url);
-                                    panic!("Unknown binary format from {}", self.url);
+                            let encoding = match registry::lookup(self.exchange).encoding {
+                                Some(encoding) => encoding,
+                                None => {
+                                    client_log!(self, Error, "Unknown binary format");
+                                    panic!("Unknown binary format from {}", self.url());
                                 }
                             };
 
-                            match resp {
-                                Ok(_) => succeeded = self.handle_msg(&txt),
-                                Err(err) => error!("Decompression failed, {}", err),
-                            }
+                            DECOMPRESSOR.with(|decompressor| {
+                                match decompressor.borrow_mut().decompress(encoding, &binary) {
+                                    Ok(txt) => succeeded = self.handle_msg(txt, Some(&binary)),
+                                    Err(err) => {
+                                        client_log!(self, Error, "Decompression failed, {}", err)
+                                    }
+                                }
+                            });
                         }
                         Message::Ping(resp) => {
-                            info!(
+                            client_log!(
+                                self,
+                                Info,
                                 "Received a ping frame: {}",
                                 std::str::from_utf8(&resp).unwrap()
                             );
@@ -261,121 +568,155 @@ impl WSClientInternal {
                                 .unwrap()
                                 .write_message(Message::Pong(resp));
                             if let Err(err) = ret {
-                                error!("{}", err);
+                                client_log!(self, Error, "{}", err);
                             }
                         }
                         Message::Pong(resp) => {
                             let tmp = std::str::from_utf8(&resp);
                             self.num_unanswered_ping.store(0, Ordering::Release);
-                            debug!("Received a pong frame: {} from {}, reset num_unanswered_ping to {}", tmp.unwrap(), self.exchange, self.num_unanswered_ping.load(Ordering::Acquire));
+                            client_log!(
+                                self,
+                                Debug,
+                                "Received a pong frame: {}, reset num_unanswered_ping to {}",
+                                tmp.unwrap(),
+                                self.num_unanswered_ping.load(Ordering::Acquire)
+                            );
                         }
-                        Message::Close(resp) => {
-                            match resp {
-                                Some(frame) => {
-                                    if frame.code != CloseCode::Normal
-                                        && frame.code != CloseCode::Away
-                                    {
-                                        error!(
-                                            "Received a CloseFrame: code: {}, reason: {}, {}",
-                                            frame.code,
-                                            frame.reason,
-                                            self.get_error_msg(),
-                                        );
-                                        std::thread::sleep(Duration::from_secs(5));
-                                        std::process::exit(1); // fail fast, pm2 will restart
-                                    } else {
-                                        warn!(
-                                            "Received a CloseFrame: code: {}, reason: {} from {}",
-                                            frame.code, frame.reason, self.url
-                                        );
-                                    }
+                        Message::Close(resp) => match resp {
+                            Some(frame) => {
+                                if frame.code != CloseCode::Normal && frame.code != CloseCode::Away
+                                {
+                                    client_log!(
+                                        self,
+                                        Error,
+                                        "Received a CloseFrame: code: {}, reason: {}, {}",
+                                        frame.code,
+                                        frame.reason,
+                                        self.get_error_msg(),
+                                    );
+                                    self.reconnect_or_exit();
+                                } else {
+                                    client_log!(
+                                        self,
+                                        Warn,
+                                        "Received a CloseFrame: code: {}, reason: {}",
+                                        frame.code,
+                                        frame.reason
+                                    );
                                 }
-                                None => warn!("Received a close message without CloseFrame"),
                             }
-                        }
+                            None => client_log!(
+                                self,
+                                Warn,
+                                "Received a close message without CloseFrame"
+                            ),
+                        },
                     }
                 }
                 Err(err) => {
                     match err {
                         Error::ConnectionClosed => {
-                            error!("Server closed connection, exiting now...");
-                            std::thread::sleep(Duration::from_secs(5));
-                            std::process::exit(1); // fail fast, pm2 will restart
+                            client_log!(self, Error, "Server closed connection");
+                            self.reconnect_or_exit();
                         }
                         Error::AlreadyClosed => {
-                            error!("Impossible to happen, fix the bug in the code");
+                            client_log!(
+                                self,
+                                Error,
+                                "Impossible to happen, fix the bug in the code"
+                            );
                             panic!("Impossible to happen, fix the bug in the code");
                         }
                         Error::Io(io_err) => {
                             match io_err.kind() {
                                 std::io::ErrorKind::WouldBlock => {
                                     num_read_timeout += 1;
-                                    debug!(
+                                    client_log!(
+                                        self,
+                                        Debug,
                                         "read_message() timeout, increased num_read_timeout to {}",
                                         num_read_timeout
                                     );
                                 }
                                 std::io::ErrorKind::Interrupted => {
                                     // ignore SIGHUP, which will be handled by reopen
-                                    info!("Ignoring SIGHUP");
+                                    client_log!(self, Info, "Ignoring SIGHUP");
                                 }
                                 std::io::ErrorKind::BrokenPipe => {
-                                    error!(
+                                    client_log!(
+                                        self,
+                                        Error,
                                         "I/O error thrown from read_message(): {}, {:?} {} {}",
                                         io_err,
                                         io_err.kind(),
                                         self.exchange,
-                                        self.url
+                                        self.url()
                                     );
-                                    std::thread::sleep(Duration::from_secs(5));
-                                    std::process::exit(1); // fail fast, pm2 will restart
+                                    self.reconnect_or_exit();
                                 }
                                 _ => {
-                                    error!(
+                                    client_log!(
+                                        self,
+                                        Error,
                                         "I/O error thrown from read_message(): {}, {:?} {} {}",
                                         io_err,
                                         io_err.kind(),
                                         self.exchange,
-                                        self.url
+                                        self.url()
                                     );
-                                    std::thread::sleep(Duration::from_secs(5));
-                                    std::process::exit(1); // fail fast, pm2 will restart
+                                    self.reconnect_or_exit();
                                 }
                             }
                         }
+                        Error::Capacity(capacity_err) => {
+                            // the rest of the frame is still in the socket, so the
+                            // connection can't be read any further
+                            client_log!(
+                                self,
+                                Error,
+                                "{}, raise the limit by set_max_frame_size()",
+                                capacity_err
+                            );
+                            self.reconnect_or_exit();
+                        }
                         Error::Protocol(protocol_err) => {
                             if protocol_err == ProtocolError::ResetWithoutClosingHandshake {
-                                error!("ResetWithoutClosingHandshake");
-                                std::thread::sleep(Duration::from_secs(5));
-                                std::process::exit(1); // fail fast, pm2 will restart
+                                client_log!(self, Error, "ResetWithoutClosingHandshake");
+                                self.reconnect_or_exit();
                             } else {
-                                error!(
+                                client_log!(
+                                    self,
+                                    Error,
                                     "Protocol error thrown from read_message(): {}",
                                     protocol_err
                                 );
                             }
                         }
                         _ => {
-                            error!("Error thrown from read_message(): {}", err);
+                            client_log!(self, Error, "Error thrown from read_message(): {}", err);
                             panic!("Error thrown from read_message(): {}", err);
                         }
                     }
                 }
             };
 
+            self.watchdog.check(self.exchange, self.url());
+
             if let Some(interval_and_msg) = self.client_ping_interval_and_msg {
                 let num_unanswered_ping = self.num_unanswered_ping.load(Ordering::Acquire);
                 if num_unanswered_ping > 5 {
-                    error!(
-                        "Exiting due to num_unanswered_ping: {}, duration: {} seconds",
+                    client_log!(
+                        self,
+                        Error,
+                        "Too many unanswered pings: {}, duration: {} seconds",
                         num_unanswered_ping,
                         start_timstamp.elapsed().as_secs()
                     );
-                    std::thread::sleep(Duration::from_secs(5));
-                    std::process::exit(1); // fail fast, pm2 will restart
+                    self.num_unanswered_ping.store(0, Ordering::Release);
+                    self.reconnect_or_exit();
                 }
                 if last_ping_timestamp.elapsed() >= Duration::from_secs(interval_and_msg.0 / 2) {
-                    debug!("Sending ping: {}", interval_and_msg.1);
+                    client_log!(self, Debug, "Sending ping: {}", interval_and_msg.1);
                     // send ping
                     let ping_msg = if interval_and_msg.1.is_empty() {
                         Message::Ping(Vec::new())
@@ -384,17 +725,19 @@ impl WSClientInternal {
                     };
                     last_ping_timestamp = Instant::now();
                     if let Err(err) = self.ws_stream.lock().unwrap().write_message(ping_msg) {
-                        error!("{}", err);
+                        client_log!(self, Error, "{}", err);
                     }
                 }
             } else if num_read_timeout > 5 {
-                error!(
-                    "Exiting due to num_read_timeout: {}, duration: {} seconds",
+                client_log!(
+                    self,
+                    Error,
+                    "Too many read timeouts: {}, duration: {} seconds",
                     num_read_timeout,
                     start_timstamp.elapsed().as_secs()
                 );
-                std::thread::sleep(Duration::from_secs(5));
-                std::process::exit(1); // fail fast, pm2 will restart
+                num_read_timeout = 0;
+                self.reconnect_or_exit();
             }
 
             if let Some(seconds) = duration {
@@ -403,6 +746,26 @@ impl WSClientInternal {
                 }
             }
         }
+        self.report_rejected_channels();
+    }
+
+    /// Unsubscribes from all channels, waits for the server to stop pushing, then closes.
+    ///
+    /// JSON channels are skipped, since they are the subscribe commands themselves.
+    pub fn close_gracefully(&self) {
+        let channels: Vec<String> = self
+            .channels
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|ch| !ch.starts_with('{'))
+            .cloned()
+            .collect();
+        if !channels.is_empty() {
+            self.unsubscribe(&channels);
+            std::thread::sleep(GRACEFUL_CLOSE_DELAY);
+        }
+        self.close();
     }
 
     pub fn close(&self) {
@@ -410,7 +773,7 @@ impl WSClientInternal {
         self.should_stop.store(true, Ordering::Release);
         let ret = self.ws_stream.lock().unwrap().close(None);
         if let Err(err) = ret {
-            error!("{}", err);
+            client_log!(self, Error, "{}", err);
         }
     }
 
@@ -418,7 +781,7 @@ impl WSClientInternal {
         format!(
             "{}, {}\n{}",
             self.exchange,
-            self.url,
+            self.url(),
             self.channels
                 .lock()
                 .unwrap()
@@ -441,14 +804,22 @@ macro_rules! impl_new_constructor {
             /// * `tx` - The sending part of a channel
             /// * `url` - Optional server url, usually you don't need specify it
             pub fn new(tx: Sender<String>, url: Option<&str>) -> Self {
-                let real_url = match url {
-                    Some(endpoint) => endpoint,
-                    None => $default_url,
+                let urls: Vec<String> = url.map(|url| vec![url.to_string()]).unwrap_or_default();
+                <$struct_name as crate::FromUrls>::from_urls(tx, &urls)
+            }
+        }
+
+        impl crate::FromUrls for $struct_name {
+            fn from_urls(tx: Sender<String>, urls: &[String]) -> Self {
+                let urls = if urls.is_empty() {
+                    vec![$default_url.to_string()]
+                } else {
+                    urls.to_vec()
                 };
                 $struct_name {
                     client: WSClientInternal::new(
                         $exchange,
-                        real_url,
+                        urls,
                         tx,
                         $on_misc_msg,
                         $channels_to_commands,
@@ -465,32 +836,106 @@ macro_rules! impl_new_constructor {
 macro_rules! impl_ws_client_trait {
     ($struct_name:ident) => {
         impl WSClient for $struct_name {
-            fn subscribe_trade(&self, channels: &[String]) {
-                <$struct_name as Trade>::subscribe_trade(self, channels);
+            fn subscribe_trade(&self, channels: &[String]) -> Result<(), UnsupportedChannel> {
+                <$struct_name as Trade>::subscribe_trade(self, channels)
+            }
+
+            fn subscribe_orderbook(&self, channels: &[String]) -> Result<(), UnsupportedChannel> {
+                <$struct_name as OrderBook>::subscribe_orderbook(self, channels)
+            }
+
+            fn subscribe_orderbook_topk(
+                &self,
+                channels: &[String],
+            ) -> Result<(), UnsupportedChannel> {
+                <$struct_name as OrderBookTopK>::subscribe_orderbook_topk(self, channels)
+            }
+
+            fn subscribe_l3_orderbook(
+                &self,
+                channels: &[String],
+            ) -> Result<(), UnsupportedChannel> {
+                <$struct_name as Level3OrderBook>::subscribe_l3_orderbook(self, channels)
+            }
+
+            fn subscribe_ticker(&self, channels: &[String]) -> Result<(), UnsupportedChannel> {
+                <$struct_name as Ticker>::subscribe_ticker(self, channels)
+            }
+
+            fn subscribe_bbo(&self, channels: &[String]) -> Result<(), UnsupportedChannel> {
+                <$struct_name as BBO>::subscribe_bbo(self, channels)
+            }
+
+            fn subscribe_candlestick(
+                &self,
+                symbol_interval_list: &[(String, Interval)],
+            ) -> Result<(), UnsupportedChannel> {
+                <$struct_name as Candlestick>::subscribe_candlestick(self, symbol_interval_list)
+            }
+
+            fn unsubscribe_trade(&self, channels: &[String]) -> Result<(), UnsupportedChannel> {
+                let raw_channels = self.client.collect_channels(|| {
+                    <$struct_name as Trade>::subscribe_trade(self, channels)
+                })?;
+                self.client.unsubscribe(&raw_channels);
+                Ok(())
             }
 
-            fn subscribe_orderbook(&self, channels: &[String]) {
-                <$struct_name as OrderBook>::subscribe_orderbook(self, channels);
+            fn unsubscribe_orderbook(&self, channels: &[String]) -> Result<(), UnsupportedChannel> {
+                let raw_channels = self.client.collect_channels(|| {
+                    <$struct_name as OrderBook>::subscribe_orderbook(self, channels)
+                })?;
+                self.client.unsubscribe(&raw_channels);
+                Ok(())
             }
 
-            fn subscribe_orderbook_topk(&self, channels: &[String]) {
-                <$struct_name as OrderBookTopK>::subscribe_orderbook_topk(self, channels);
+            fn unsubscribe_orderbook_topk(
+                &self,
+                channels: &[String],
+            ) -> Result<(), UnsupportedChannel> {
+                let raw_channels = self.client.collect_channels(|| {
+                    <$struct_name as OrderBookTopK>::subscribe_orderbook_topk(self, channels)
+                })?;
+                self.client.unsubscribe(&raw_channels);
+                Ok(())
             }
 
-            fn subscribe_l3_orderbook(&self, channels: &[String]) {
-                <$struct_name as Level3OrderBook>::subscribe_l3_orderbook(self, channels);
+            fn unsubscribe_l3_orderbook(
+                &self,
+                channels: &[String],
+            ) -> Result<(), UnsupportedChannel> {
+                let raw_channels = self.client.collect_channels(|| {
+                    <$struct_name as Level3OrderBook>::subscribe_l3_orderbook(self, channels)
+                })?;
+                self.client.unsubscribe(&raw_channels);
+                Ok(())
             }
 
-            fn subscribe_ticker(&self, channels: &[String]) {
-                <$struct_name as Ticker>::subscribe_ticker(self, channels);
+            fn unsubscribe_ticker(&self, channels: &[String]) -> Result<(), UnsupportedChannel> {
+                let raw_channels = self.client.collect_channels(|| {
+                    <$struct_name as Ticker>::subscribe_ticker(self, channels)
+                })?;
+                self.client.unsubscribe(&raw_channels);
+                Ok(())
             }
 
-            fn subscribe_bbo(&self, channels: &[String]) {
-                <$struct_name as BBO>::subscribe_bbo(self, channels);
+            fn unsubscribe_bbo(&self, channels: &[String]) -> Result<(), UnsupportedChannel> {
+                let raw_channels = self
+                    .client
+                    .collect_channels(|| <$struct_name as BBO>::subscribe_bbo(self, channels))?;
+                self.client.unsubscribe(&raw_channels);
+                Ok(())
             }
 
-            fn subscribe_candlestick(&self, symbol_interval_list: &[(String, usize)]) {
-                <$struct_name as Candlestick>::subscribe_candlestick(self, symbol_interval_list);
+            fn unsubscribe_candlestick(
+                &self,
+                symbol_interval_list: &[(String, Interval)],
+            ) -> Result<(), UnsupportedChannel> {
+                let raw_channels = self.client.collect_channels(|| {
+                    <$struct_name as Candlestick>::subscribe_candlestick(self, symbol_interval_list)
+                })?;
+                self.client.unsubscribe(&raw_channels);
+                Ok(())
             }
 
             fn subscribe(&self, channels: &[String]) {
@@ -501,13 +946,375 @@ macro_rules! impl_ws_client_trait {
                 self.client.unsubscribe(channels);
             }
 
+            fn set_max_command_bytes(&self, max_bytes: usize) {
+                self.client.set_max_command_bytes(max_bytes);
+            }
+
+            fn set_on_stale(&self, threshold: u64, on_stale: Option<fn(&str)>) {
+                self.client.set_on_stale(threshold, on_stale);
+            }
+
+            fn set_raw_sender(&self, raw_tx: Option<Sender<Vec<u8>>>, keep_text: bool) {
+                self.client.set_raw_sender(raw_tx, keep_text);
+            }
+
+            fn set_reset_on_reconnect(&self, reset_tx: Option<Sender<crate::ResetNotification>>) {
+                self.client.set_reset_on_reconnect(reset_tx);
+            }
+
+            fn set_max_frame_size(&self, max_frame_size: Option<usize>) {
+                self.client.set_max_frame_size(max_frame_size);
+            }
+
+            fn authenticate(
+                &self,
+                api_key: &str,
+                secret: &str,
+                passphrase: &str,
+            ) -> Result<(), crate::AuthError> {
+                <$struct_name as Authenticate>::authenticate(self, api_key, secret, passphrase)
+            }
+
+            fn stats(&self) -> crate::WSClientStats {
+                self.client.stats()
+            }
+
+            fn subscriptions(&self) -> Vec<String> {
+                self.client.subscriptions()
+            }
+
             fn run(&self, duration: Option<u64>) {
                 self.client.run(duration);
             }
 
+            fn close_gracefully(&self) {
+                self.client.close_gracefully();
+            }
+
             fn close(&self) {
                 self.client.close();
             }
         }
     };
 }
+
+#[cfg(test)]
+mod tests {
+    use super::MiscMessage;
+    use crate::clients::mock_server::MockClient;
+    use flate2::{
+        write::{DeflateEncoder, GzEncoder},
+        Compression,
+    };
+    use std::{
+        io::Write,
+        sync::{atomic::Ordering, mpsc::channel},
+    };
+    use tungstenite::Message;
+
+    fn on_misc_msg(msg: &str) -> MiscMessage {
+        if msg == "pong" {
+            MiscMessage::Pong
+        } else if msg == "reconnect" {
+            MiscMessage::Reconnect
+        } else if let Some(result) = msg.strip_prefix("login ") {
+            MiscMessage::Login(result == "ok")
+        } else if msg.starts_with("misc") {
+            MiscMessage::Misc
+        } else {
+            MiscMessage::Normal
+        }
+    }
+
+    fn channels_to_commands(channels: &[String], subscribe: bool) -> Vec<String> {
+        let prefix = if subscribe { "sub" } else { "unsub" };
+        channels
+            .iter()
+            .map(|ch| format!("{}:{}", prefix, ch))
+            .collect()
+    }
+
+    // Connects a client of the mock protocol above to a mock server
+    fn start_client(exchange: &'static str, sessions: Vec<Vec<Message>>) -> MockClient {
+        MockClient::start(exchange, on_misc_msg, channels_to_commands, None, sessions)
+    }
+
+    // Connects a client to a mock server, runs it until the first normal message,
+    // then returns messages forwarded to tx and messages received by the server.
+    fn run_client(
+        exchange: &'static str,
+        frames: Vec<Message>,
+        channels: &[String],
+    ) -> (Vec<String>, Vec<Message>) {
+        let mock = start_client(exchange, vec![frames]);
+        mock.client.subscribe(channels);
+        mock.client.run(Some(0));
+        mock.finish()
+    }
+
+    fn compress<W: Write>(mut encoder: W, txt: &str) -> W {
+        encoder.write_all(txt.as_bytes()).unwrap();
+        encoder
+    }
+
+    #[test]
+    fn text_message() {
+        let frames = vec![
+            Message::Text("misc".to_string()),
+            Message::Text("pong".to_string()),
+            Message::Text("data".to_string()),
+        ];
+        let (msgs, _) = run_client("mock", frames, &[]);
+        assert_eq!(vec!["data".to_string()], msgs);
+    }
+
+    #[test]
+    fn kraken_heartbeat_resets_unanswered_pings() {
+        let mock = MockClient::start(
+            super::super::kraken::EXCHANGE_NAME,
+            super::super::kraken::on_misc_msg,
+            channels_to_commands,
+            None,
+            vec![Vec::new()],
+        );
+        mock.client.num_unanswered_ping.store(3, Ordering::Release);
+        assert!(!mock.client.handle_msg(r#"{"event":"heartbeat"}"#, None));
+        assert_eq!(0, mock.client.num_unanswered_ping.load(Ordering::Acquire));
+
+        let (msgs, _) = mock.finish();
+        assert!(msgs.is_empty());
+    }
+
+    #[test]
+    fn subscribe_commands() {
+        let channels = vec!["trade:BTC".to_string(), "trade:BTC".to_string()];
+        let (_, received) = run_client("mock", vec![Message::Text("data".to_string())], &channels);
+        assert_eq!(Message::Text("sub:trade:BTC".to_string()), received[0]);
+        assert!(matches!(received.last(), Some(Message::Close(_))));
+    }
+
+    #[test]
+    fn gzip_binary_message() {
+        let binary = compress(GzEncoder::new(Vec::new(), Compression::default()), "data")
+            .finish()
+            .unwrap();
+        let (msgs, _) = run_client(
+            super::super::huobi::EXCHANGE_NAME,
+            vec![Message::Binary(binary)],
+            &[],
+        );
+        assert_eq!(vec!["data".to_string()], msgs);
+    }
+
+    #[test]
+    fn deflate_binary_message() {
+        let binary = compress(
+            DeflateEncoder::new(Vec::new(), Compression::default()),
+            "data",
+        )
+        .finish()
+        .unwrap();
+        let (msgs, _) = run_client(
+            super::super::okex::EXCHANGE_NAME,
+            vec![Message::Binary(binary)],
+            &[],
+        );
+        assert_eq!(vec!["data".to_string()], msgs);
+    }
+
+    #[test]
+    fn raw_binary_message() {
+        let binary = compress(GzEncoder::new(Vec::new(), Compression::default()), "data")
+            .finish()
+            .unwrap();
+        let ping = compress(GzEncoder::new(Vec::new(), Compression::default()), "misc")
+            .finish()
+            .unwrap();
+
+        for keep_text in [false, true] {
+            let mock = start_client(
+                super::super::huobi::EXCHANGE_NAME,
+                vec![vec![
+                    Message::Binary(ping.clone()),
+                    Message::Binary(binary.clone()),
+                ]],
+            );
+            let (raw_tx, raw_rx) = channel();
+            mock.client.set_raw_sender(Some(raw_tx), keep_text);
+            mock.client.run(Some(0));
+            let (msgs, _) = mock.finish();
+
+            // misc messages are never forwarded
+            assert_eq!(
+                vec![binary.clone()],
+                raw_rx.iter().collect::<Vec<Vec<u8>>>()
+            );
+            if keep_text {
+                assert_eq!(vec!["data".to_string()], msgs);
+            } else {
+                assert!(msgs.is_empty());
+            }
+        }
+    }
+
+    #[test]
+    fn raw_receiver_dropped() {
+        let binary = compress(GzEncoder::new(Vec::new(), Compression::default()), "data")
+            .finish()
+            .unwrap();
+        let mock = start_client(
+            super::super::huobi::EXCHANGE_NAME,
+            vec![vec![Message::Binary(binary)]],
+        );
+        let (raw_tx, raw_rx) = channel();
+        mock.client.set_raw_sender(Some(raw_tx), false);
+        drop(raw_rx);
+        mock.client.run(Some(0));
+        let (msgs, _) = mock.finish();
+
+        // falls back to text instead of panicking
+        assert_eq!(vec!["data".to_string()], msgs);
+    }
+
+    #[test]
+    fn close_gracefully() {
+        let mock = start_client("mock", vec![Vec::new()]);
+        mock.client
+            .subscribe(&["trade:BTC".to_string(), r#"{"op":"subscribe"}"#.to_string()]);
+        mock.client.close_gracefully();
+        let (_, received) = mock.finish();
+
+        assert_eq!(4, received.len());
+        assert_eq!(Message::Text("unsub:trade:BTC".to_string()), received[2]);
+        assert!(matches!(received[3], Message::Close(_)));
+    }
+
+    #[test]
+    fn subscribe_after_login() {
+        let mock = start_client(
+            "mock",
+            vec![
+                vec![
+                    Message::Text("login ok".to_string()),
+                    Message::Text("data1".to_string()),
+                    Message::Text("reconnect".to_string()),
+                ],
+                vec![
+                    Message::Text("login failed".to_string()),
+                    Message::Text("data2".to_string()),
+                ],
+            ],
+        );
+        let (reset_tx, _reset_rx) = channel();
+        mock.client.set_reset_on_reconnect(Some(reset_tx));
+        mock.client
+            .login(Box::new(|| Ok("login".to_string())))
+            .unwrap();
+        // held back until the reply to the login command
+        mock.client.subscribe(&["order:BTC".to_string()]);
+        mock.client.run(Some(0)); // returns after data1
+        mock.client.run(Some(0)); // reconnects, then returns after data2
+        let (_, received) = mock.finish();
+
+        let received = received
+            .into_iter()
+            .filter(|msg| msg.is_text())
+            .collect::<Vec<Message>>();
+        // the login command always comes first, channels are resubscribed
+        // even if the login fails, public channels still work
+        let expected = ["login", "sub:order:BTC", "login", "sub:order:BTC"]
+            .iter()
+            .map(|x| Message::Text(x.to_string()))
+            .collect::<Vec<Message>>();
+        assert_eq!(expected, received);
+    }
+
+    #[test]
+    fn invalid_login_command() {
+        let mock = start_client("mock", vec![Vec::new()]);
+        let err = mock
+            .client
+            .login(Box::new(|| {
+                Err(crate::AuthError::InvalidCredentials(
+                    "empty secret".to_string(),
+                ))
+            }))
+            .unwrap_err();
+        assert_eq!("Invalid credentials, empty secret", err.to_string());
+        // nothing is held back
+        mock.client.subscribe(&["trade:BTC".to_string()]);
+        let (_, received) = mock.finish();
+
+        assert_eq!(Message::Text("sub:trade:BTC".to_string()), received[0]);
+    }
+
+    #[test]
+    fn ping_frame() {
+        let frames = vec![
+            Message::Ping(b"hello".to_vec()),
+            Message::Text("data".to_string()),
+        ];
+        let (msgs, received) = run_client("mock", frames, &[]);
+        assert_eq!(vec!["data".to_string()], msgs);
+        assert!(received.contains(&Message::Pong(b"hello".to_vec())));
+    }
+
+    #[test]
+    fn reset_on_reconnect() {
+        let mock = start_client(
+            "mock",
+            vec![
+                vec![
+                    Message::Text("data1".to_string()),
+                    Message::Text("reconnect".to_string()),
+                ],
+                vec![Message::Text("data2".to_string())],
+            ],
+        );
+        let (reset_tx, reset_rx) = channel();
+        mock.client.set_reset_on_reconnect(Some(reset_tx));
+        mock.client.subscribe(&["trade:BTC".to_string()]);
+        mock.client.run(Some(0)); // returns after data1
+        assert!(reset_rx.try_recv().is_err());
+        mock.client.run(Some(0)); // reconnects, then returns after data2
+        assert_eq!(1, mock.client.stats().num_reconnects);
+        let (msgs, received) = mock.finish();
+
+        assert_eq!(vec!["data1".to_string(), "data2".to_string()], msgs);
+        assert_eq!(
+            vec![crate::ResetNotification {
+                channels: vec!["trade:BTC".to_string()]
+            }],
+            reset_rx.iter().collect::<Vec<crate::ResetNotification>>()
+        );
+        // resubscribed on the new connection
+        assert_eq!(
+            2,
+            received
+                .iter()
+                .filter(|msg| **msg == Message::Text("sub:trade:BTC".to_string()))
+                .count()
+        );
+    }
+
+    #[test]
+    fn oversized_frame() {
+        let mock = start_client(
+            "mock",
+            vec![
+                vec![Message::Text("x".repeat(2048))],
+                vec![Message::Text("data".to_string())],
+            ],
+        );
+        let (reset_tx, reset_rx) = channel();
+        // nobody handles resets
+        drop(reset_rx);
+        mock.client.set_reset_on_reconnect(Some(reset_tx));
+        mock.client.set_max_frame_size(Some(1024));
+        mock.client.run(Some(0)); // reconnects, then returns after data
+        assert_eq!(1, mock.client.stats().num_reconnects);
+        let (msgs, _) = mock.finish();
+
+        assert_eq!(vec!["data".to_string()], msgs);
+    }
+}
@@ -1,8 +1,10 @@
-use crate::WSClient;
+use crate::{Interval, UnsupportedChannel, WSClient};
 use std::{collections::HashMap, sync::mpsc::Sender, sync::RwLock};
 
 use super::super::ws_client_internal::{MiscMessage, WSClientInternal};
-use super::super::{Candlestick, Level3OrderBook, OrderBook, OrderBookTopK, Ticker, Trade, BBO};
+use super::super::{
+    Authenticate, Candlestick, Level3OrderBook, OrderBook, OrderBookTopK, Ticker, Trade, BBO,
+};
 use super::utils::fetch_symbol_id_map_spot;
 
 use lazy_static::lazy_static;
@@ -88,28 +90,34 @@ impl_trait!(OrderBook, ZbgSpotWSClient, subscribe_orderbook, "ENTRUST_ADD", to_r
 impl_trait!(Ticker, ZbgSpotWSClient, subscribe_ticker, "TRADE_STATISTIC_24H", to_raw_channel);
 
 impl BBO for ZbgSpotWSClient {
-    fn subscribe_bbo(&self, _pairs: &[String]) {
-        panic!("ZBG does NOT have BBO channel");
+    fn subscribe_bbo(&self, _pairs: &[String]) -> Result<(), UnsupportedChannel> {
+        Err(UnsupportedChannel::new(EXCHANGE_NAME, "BBO"))
     }
 }
 
 impl OrderBookTopK for ZbgSpotWSClient {
-    fn subscribe_orderbook_topk(&self, _pairs: &[String]) {
-        panic!("Bitget does NOT have orderbook snapshot channel");
+    fn subscribe_orderbook_topk(&self, _pairs: &[String]) -> Result<(), UnsupportedChannel> {
+        Err(UnsupportedChannel::new(
+            EXCHANGE_NAME,
+            "level2 top-k snapshot",
+        ))
     }
 }
 
-fn to_candlestick_raw_channel(pair: &str, interval: usize) -> String {
+fn to_candlestick_raw_channel(
+    pair: &str,
+    interval: Interval,
+) -> Result<String, UnsupportedChannel> {
     let interval_str = match interval {
-        60 => "1M",
-        300 => "5M",
-        900 => "15M",
-        1800 => "30M",
-        3600 => "1H",
-        14400 => "4H",
-        86400 => "1D",
-        604800 => "1W",
-        _ => panic!("ZBG spot available intervals 1M,5M,15M,30M,1H,4H,1D,1W"),
+        Interval::Min1 => "1M",
+        Interval::Min5 => "5M",
+        Interval::Min15 => "15M",
+        Interval::Min30 => "30M",
+        Interval::Hour1 => "1H",
+        Interval::Hour4 => "4H",
+        Interval::Day1 => "1D",
+        Interval::Week1 => "1W",
+        _ => return Err(UnsupportedChannel::interval(EXCHANGE_NAME, interval)),
     };
 
     if !SYMBOL_ID_MAP.read().unwrap().contains_key(pair) {
@@ -123,17 +131,17 @@ fn to_candlestick_raw_channel(pair: &str, interval: usize) -> String {
         .unwrap_or_else(|| panic!("Failed to find symbol_id for {}", pair))
         .clone();
 
-    format!(
+    Ok(format!(
         "{}_KLINE_{}_{}",
         symbol_id,
         interval_str,
         pair.to_uppercase()
-    )
+    ))
 }
 
 impl_candlestick!(ZbgSpotWSClient);
 
-panic_l3_orderbook!(ZbgSpotWSClient);
+unsupported_l3_orderbook!(ZbgSpotWSClient);
 
 impl_new_constructor!(
     ZbgSpotWSClient,
@@ -144,4 +152,18 @@ impl_new_constructor!(
     Some(CLIENT_PING_INTERVAL_AND_MSG),
     None
 );
+unsupported_authenticate!(ZbgSpotWSClient);
 impl_ws_client_trait!(ZbgSpotWSClient);
+
+#[cfg(test)]
+mod tests {
+    use crate::{clients::mock_server::assert_unsupported, UnsupportedChannel, WSClient};
+
+    #[test]
+    fn test_unsupported_channel() {
+        assert_unsupported(
+            |client: &super::ZbgSpotWSClient| client.subscribe_bbo(&["BTCUSDT".to_string()]),
+            UnsupportedChannel::new(super::EXCHANGE_NAME, "BBO"),
+        );
+    }
+}
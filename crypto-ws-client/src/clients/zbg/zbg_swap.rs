@@ -1,12 +1,13 @@
-use crate::WSClient;
+use crate::{Interval, UnsupportedChannel, WSClient};
 use std::{collections::HashMap, sync::mpsc::Sender, sync::RwLock};
 
 use super::super::ws_client_internal::{MiscMessage, WSClientInternal};
-use super::super::{Candlestick, Level3OrderBook, OrderBook, OrderBookTopK, Ticker, Trade, BBO};
+use super::super::{
+    Authenticate, Candlestick, Level3OrderBook, OrderBook, OrderBookTopK, Ticker, Trade, BBO,
+};
 use super::utils::fetch_symbol_contract_id_map_swap;
 
 use lazy_static::lazy_static;
-use log::*;
 
 const EXCHANGE_NAME: &str = "zbg";
 
@@ -62,7 +63,7 @@ fn on_misc_msg(msg: &str) -> MiscMessage {
     if msg.starts_with('[') {
         MiscMessage::Normal
     } else {
-        warn!("Received {} from {}", msg, EXCHANGE_NAME);
+        exchange_log!(EXCHANGE_NAME, Warn, "Received {}", msg);
         MiscMessage::Misc
     }
 }
@@ -88,28 +89,38 @@ impl_trait!(OrderBook, ZbgSwapWSClient, subscribe_orderbook, "future_snapshot_de
 impl_trait!(Ticker, ZbgSwapWSClient, subscribe_ticker, "future_snapshot_indicator", to_raw_channel);
 
 impl BBO for ZbgSwapWSClient {
-    fn subscribe_bbo(&self, _pairs: &[String]) {
-        panic!("ZBG does NOT have BBO channel");
+    fn subscribe_bbo(&self, _pairs: &[String]) -> Result<(), UnsupportedChannel> {
+        Err(UnsupportedChannel::new(EXCHANGE_NAME, "BBO"))
     }
 }
 
 impl OrderBookTopK for ZbgSwapWSClient {
-    fn subscribe_orderbook_topk(&self, _pairs: &[String]) {
-        panic!("Bitget does NOT have orderbook snapshot channel");
+    fn subscribe_orderbook_topk(&self, _pairs: &[String]) -> Result<(), UnsupportedChannel> {
+        Err(UnsupportedChannel::new(
+            EXCHANGE_NAME,
+            "level2 top-k snapshot",
+        ))
     }
 }
 
-fn to_candlestick_raw_channel(pair: &str, interval: usize) -> String {
-    let valid_set: Vec<usize> = vec![
-        60, 180, 300, 900, 1800, 3600, 7200, 14400, 21600, 43200, 86400, 604800,
-    ];
-    if !valid_set.contains(&interval) {
-        let joined = valid_set
-            .into_iter()
-            .map(|x| x.to_string())
-            .collect::<Vec<String>>()
-            .join(",");
-        panic!("ZBG Swap available intervals {}", joined);
+fn to_candlestick_raw_channel(
+    pair: &str,
+    interval: Interval,
+) -> Result<String, UnsupportedChannel> {
+    match interval {
+        Interval::Min1
+        | Interval::Min3
+        | Interval::Min5
+        | Interval::Min15
+        | Interval::Min30
+        | Interval::Hour1
+        | Interval::Hour2
+        | Interval::Hour4
+        | Interval::Hour6
+        | Interval::Hour12
+        | Interval::Day1
+        | Interval::Week1 => (),
+        _ => return Err(UnsupportedChannel::interval(EXCHANGE_NAME, interval)),
     }
 
     let contract_id = *SYMBOL_CONTRACT_ID_MAP
@@ -118,12 +129,16 @@ fn to_candlestick_raw_channel(pair: &str, interval: usize) -> String {
         .get(pair)
         .unwrap_or_else(|| panic!("Failed to find contract_id for {}", pair));
 
-    format!("future_kline-{}-{}", contract_id, interval * 1000)
+    Ok(format!(
+        "future_kline-{}-{}",
+        contract_id,
+        interval.as_secs() * 1000
+    ))
 }
 
 impl_candlestick!(ZbgSwapWSClient);
 
-panic_l3_orderbook!(ZbgSwapWSClient);
+unsupported_l3_orderbook!(ZbgSwapWSClient);
 
 impl_new_constructor!(
     ZbgSwapWSClient,
@@ -134,4 +149,18 @@ impl_new_constructor!(
     Some(CLIENT_PING_INTERVAL_AND_MSG),
     None
 );
+unsupported_authenticate!(ZbgSwapWSClient);
 impl_ws_client_trait!(ZbgSwapWSClient);
+
+#[cfg(test)]
+mod tests {
+    use crate::{clients::mock_server::assert_unsupported, UnsupportedChannel, WSClient};
+
+    #[test]
+    fn test_unsupported_channel() {
+        assert_unsupported(
+            |client: &super::ZbgSwapWSClient| client.subscribe_bbo(&["BTCUSDT".to_string()]),
+            UnsupportedChannel::new(super::EXCHANGE_NAME, "BBO"),
+        );
+    }
+}
@@ -0,0 +1,162 @@
+use flate2::read::{DeflateDecoder, GzDecoder};
+use std::io::{Cursor, Read, Result};
+
+// Buffers grown beyond this by a huge frame, e.g., a full orderbook snapshot,
+// are shrunk back so that one frame doesn't pin the memory forever.
+const MAX_RETAINED_CAPACITY: usize = 1 << 20;
+
+/// Compression formats of binary websocket frames.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Encoding {
+    /// Used by Binance, Bitget, Bitz and Huobi
+    Gzip,
+    /// Raw deflate without zlib header, used by OKEx
+    Deflate,
+}
+
+/// Decompresses binary websocket frames into text.
+///
+/// The decoders and buffers are reused across frames, so a stream of frames
+/// only allocates when a frame is larger than all frames before it. Clients
+/// use one per thread, it is also handy for frames stored by
+/// `WSClient::set_raw_sender()`.
+///
+/// ## Example
+///
+/// ```
+/// use crypto_ws_client::{Decompressor, Encoding};
+/// use flate2::{write::GzEncoder, Compression};
+/// use std::io::Write;
+///
+/// let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+/// encoder.write_all(br#"{"ping":1}"#).unwrap();
+/// let frame = encoder.finish().unwrap();
+///
+/// let mut decompressor = Decompressor::new();
+/// assert_eq!(r#"{"ping":1}"#, decompressor.decompress(Encoding::Gzip, &frame).unwrap());
+/// ```
+pub struct Decompressor {
+    gzip: GzDecoder<Cursor<Vec<u8>>>,
+    deflate: DeflateDecoder<Cursor<Vec<u8>>>,
+    // the compressed frame, owned so that decoders don't borrow it
+    input: Vec<u8>,
+    output: String,
+}
+
+impl Default for Decompressor {
+    fn default() -> Self {
+        Decompressor {
+            gzip: GzDecoder::new(Cursor::new(Vec::new())),
+            deflate: DeflateDecoder::new(Cursor::new(Vec::new())),
+            input: Vec::new(),
+            output: String::new(),
+        }
+    }
+}
+
+impl Decompressor {
+    pub fn new() -> Self {
+        Decompressor::default()
+    }
+
+    /// Decompresses a frame, the returned text is valid until the next call.
+    pub fn decompress(&mut self, encoding: Encoding, frame: &[u8]) -> Result<&str> {
+        if self.output.capacity() > MAX_RETAINED_CAPACITY {
+            self.output = String::new();
+        }
+        if self.input.capacity() > MAX_RETAINED_CAPACITY {
+            self.input = Vec::new();
+        }
+        let mut input = std::mem::take(&mut self.input);
+        input.clear();
+        input.extend_from_slice(frame);
+        self.output.clear();
+
+        // resetting with an empty Vec doesn't allocate and gives the input back
+        let result = match encoding {
+            Encoding::Gzip => {
+                self.gzip.reset(Cursor::new(input));
+                let result = self.gzip.read_to_string(&mut self.output);
+                self.input = self.gzip.reset(Cursor::new(Vec::new())).into_inner();
+                result
+            }
+            Encoding::Deflate => {
+                self.deflate.reset(Cursor::new(input));
+                let result = self.deflate.read_to_string(&mut self.output);
+                self.input = self.deflate.reset(Cursor::new(Vec::new())).into_inner();
+                result
+            }
+        };
+        result.map(|_| self.output.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Decompressor, Encoding};
+    use flate2::{
+        write::{DeflateEncoder, GzEncoder},
+        Compression,
+    };
+    use std::io::Write;
+
+    fn gzip(txt: &str) -> Vec<u8> {
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(txt.as_bytes()).unwrap();
+        encoder.finish().unwrap()
+    }
+
+    fn deflate(txt: &str) -> Vec<u8> {
+        let mut encoder = DeflateEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(txt.as_bytes()).unwrap();
+        encoder.finish().unwrap()
+    }
+
+    #[test]
+    fn reuse_across_frames() {
+        let mut decompressor = Decompressor::new();
+        let long = "x".repeat(10000);
+        assert_eq!(
+            long,
+            decompressor
+                .decompress(Encoding::Gzip, &gzip(&long))
+                .unwrap()
+        );
+        // a shorter frame doesn't see leftovers of the previous one
+        assert_eq!(
+            "short",
+            decompressor
+                .decompress(Encoding::Gzip, &gzip("short"))
+                .unwrap()
+        );
+        assert_eq!(
+            "okex",
+            decompressor
+                .decompress(Encoding::Deflate, &deflate("okex"))
+                .unwrap()
+        );
+        assert_eq!(
+            "huobi",
+            decompressor
+                .decompress(Encoding::Gzip, &gzip("huobi"))
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn recover_from_corrupted_frame() {
+        let mut decompressor = Decompressor::new();
+        let mut frame = gzip("corrupted");
+        frame.truncate(frame.len() / 2);
+        assert!(decompressor.decompress(Encoding::Gzip, &frame).is_err());
+        assert!(decompressor
+            .decompress(Encoding::Gzip, b"not compressed")
+            .is_err());
+        assert_eq!(
+            "valid",
+            decompressor
+                .decompress(Encoding::Gzip, &gzip("valid"))
+                .unwrap()
+        );
+    }
+}
@@ -0,0 +1,62 @@
+use crate::Interval;
+use std::{error::Error as StdError, fmt};
+
+/// Returned by high-level APIs when the exchange doesn't have the channel.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnsupportedChannel {
+    /// The exchange name
+    pub exchange: String,
+    /// The channel type, e.g., `BBO`, `ticker`, `level3 orderbook`
+    pub channel: String,
+}
+
+impl UnsupportedChannel {
+    pub(crate) fn new(exchange: &str, channel: &str) -> Self {
+        UnsupportedChannel {
+            exchange: exchange.to_string(),
+            channel: channel.to_string(),
+        }
+    }
+
+    /// The exchange doesn't have candlesticks of this interval.
+    pub(crate) fn interval(exchange: &str, interval: Interval) -> Self {
+        UnsupportedChannel::new(exchange, &format!("{} candlestick", interval))
+    }
+}
+
+impl fmt::Display for UnsupportedChannel {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} does NOT have the {} websocket channel",
+            self.exchange, self.channel
+        )
+    }
+}
+
+impl StdError for UnsupportedChannel {}
+
+/// Returned by `WSClient::authenticate()`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AuthError {
+    /// The client doesn't support private channels of the exchange
+    Unsupported(String),
+    /// Failed to get credentials from the exchange, e.g., a Binance listenKey
+    Request(String),
+    /// The credentials can't be used, e.g., the secret is not a valid HMAC key
+    InvalidCredentials(String),
+}
+
+impl fmt::Display for AuthError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            AuthError::Unsupported(exchange) => {
+                write!(f, "{} private channels are NOT supported", exchange)
+            }
+            AuthError::Request(reason) => write!(f, "Authentication failed, {}", reason),
+            AuthError::InvalidCredentials(reason) => write!(f, "Invalid credentials, {}", reason),
+        }
+    }
+}
+
+impl StdError for AuthError {}
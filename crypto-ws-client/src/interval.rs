@@ -0,0 +1,119 @@
+use std::fmt;
+
+/// Candlestick intervals, see `WSClient::subscribe_candlestick()`.
+///
+/// Each exchange supports a subset of them, subscribing to an interval the
+/// exchange doesn't have returns `UnsupportedChannel`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Interval {
+    Sec10,
+    Min1,
+    Min3,
+    Min5,
+    Min10,
+    Min15,
+    Min30,
+    Hour1,
+    Hour2,
+    Hour3,
+    Hour4,
+    Hour6,
+    Hour8,
+    Hour12,
+    Day1,
+    Day3,
+    Day5,
+    Week1,
+    Week2,
+    Day15,
+    /// 30 days
+    Month1,
+}
+
+impl Interval {
+    /// All intervals in ascending order.
+    pub const ALL: [Interval; 21] = [
+        Interval::Sec10,
+        Interval::Min1,
+        Interval::Min3,
+        Interval::Min5,
+        Interval::Min10,
+        Interval::Min15,
+        Interval::Min30,
+        Interval::Hour1,
+        Interval::Hour2,
+        Interval::Hour3,
+        Interval::Hour4,
+        Interval::Hour6,
+        Interval::Hour8,
+        Interval::Hour12,
+        Interval::Day1,
+        Interval::Day3,
+        Interval::Day5,
+        Interval::Week1,
+        Interval::Week2,
+        Interval::Day15,
+        Interval::Month1,
+    ];
+
+    /// Length of the interval in seconds.
+    pub fn as_secs(&self) -> usize {
+        match self {
+            Interval::Sec10 => 10,
+            Interval::Min1 => 60,
+            Interval::Min3 => 180,
+            Interval::Min5 => 300,
+            Interval::Min10 => 600,
+            Interval::Min15 => 900,
+            Interval::Min30 => 1800,
+            Interval::Hour1 => 3600,
+            Interval::Hour2 => 7200,
+            Interval::Hour3 => 10800,
+            Interval::Hour4 => 14400,
+            Interval::Hour6 => 21600,
+            Interval::Hour8 => 28800,
+            Interval::Hour12 => 43200,
+            Interval::Day1 => 86400,
+            Interval::Day3 => 259200,
+            Interval::Day5 => 432000,
+            Interval::Week1 => 604800,
+            Interval::Week2 => 1209600,
+            Interval::Day15 => 1296000,
+            Interval::Month1 => 2592000,
+        }
+    }
+
+    /// Returns the interval of `secs` seconds, or None if there is no such interval.
+    pub fn from_secs(secs: usize) -> Option<Interval> {
+        Interval::ALL
+            .iter()
+            .find(|interval| interval.as_secs() == secs)
+            .copied()
+    }
+}
+
+impl fmt::Display for Interval {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Interval;
+
+    #[test]
+    fn secs_round_trip() {
+        for interval in Interval::ALL {
+            assert_eq!(Some(interval), Interval::from_secs(interval.as_secs()));
+        }
+        assert_eq!(None, Interval::from_secs(42));
+    }
+
+    #[test]
+    fn ascending() {
+        assert!(Interval::ALL
+            .windows(2)
+            .all(|pair| pair[0] < pair[1] && pair[0].as_secs() < pair[1].as_secs()));
+    }
+}
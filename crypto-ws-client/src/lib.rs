@@ -28,7 +28,8 @@
 //! * `subscribe_bbo(&mut self, pairs: &[String])`
 //! * `subscribe_orderbook(&mut self, pairs: &[String])`
 //! * `subscribe_ticker(&mut self, pairs: &[String])`
-//! * `subscribe_candlestick(&mut self, pairs: &[String], interval: u32)`
+//! * `subscribe_candlestick(&mut self, symbol_interval_list: &[(String, Interval)])`
+//! * `subscribe_by_type(&self, msg_type: MessageType, pairs: &[String])`
 //!
 //! They are easier to use and cover mostly used scenarios.
 //!
@@ -68,7 +69,11 @@
 //! * Level2 data is aggregated by price level, updated per tick.
 //! * Level3 data is the original orderbook, which is not aggregated.
 
+mod builder;
 mod clients;
+mod decompress;
+mod error;
+mod interval;
 
 pub use clients::binance::*;
 pub use clients::binance_option::*;
@@ -90,8 +95,42 @@ pub use clients::kraken::*;
 pub use clients::kucoin::*;
 pub use clients::mxc::*;
 pub use clients::okex::*;
+pub use clients::okex_v5::*;
 pub use clients::zbg::*;
 
+pub use builder::{FromUrls, WSClientBuilder};
+pub use crypto_market_type::MessageType;
+pub use crypto_pair::{set_header, set_user_agent, DEFAULT_USER_AGENT};
+pub use decompress::{Decompressor, Encoding};
+pub use error::{AuthError, UnsupportedChannel};
+pub use interval::Interval;
+
+use std::sync::mpsc::Sender;
+
+/// Sent by a client after reconnecting, see `WSClient::set_reset_on_reconnect()`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ResetNotification {
+    /// Raw channels being resubscribed on the new connection
+    pub channels: Vec<String>,
+}
+
+// Names a client in errors of default methods of `WSClient`, e.g., `KrakenWSClient`
+fn client_name<T: ?Sized>() -> String {
+    let name = std::any::type_name::<T>();
+    name.rsplit("::").next().unwrap_or(name).to_string()
+}
+
+/// Statistics of a WebSocket client, see `WSClient::stats()`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct WSClientStats {
+    /// Number of subscribed channels
+    pub num_channels: usize,
+    /// Channels rejected by the server, in the order of rejection
+    pub rejected_channels: Vec<String>,
+    /// Number of reconnects since the client was created
+    pub num_reconnects: usize,
+}
+
 /// The public interface of every WebSocket client.
 pub trait WSClient {
     /// Subscribes to trade channels.
@@ -104,7 +143,7 @@ pub trait WSClient {
     /// * BitMEX `XBTUSD`, `XBTM21`
     /// * Binance `btcusdt`, `btcusd_perp`
     /// * OKEx `BTC-USDT`
-    fn subscribe_trade(&self, pairs: &[String]);
+    fn subscribe_trade(&self, pairs: &[String]) -> Result<(), UnsupportedChannel>;
 
     /// Subscribes to BBO(best bid & offer) channels.
     ///
@@ -113,11 +152,11 @@ pub trait WSClient {
     /// is updated per tick and non-aggregated.
     ///
     /// Not all exchanges have the BBO channel, calling this function with
-    /// these exchanges will panic.
+    /// these exchanges returns `UnsupportedChannel`.
     ///
     /// * Binance, BitMEX, Huobi and Kraken have BBO directly.
     /// * Bitfinex uses `book` channel with `len=1` and `prec="R0"` to get BBO data.
-    fn subscribe_bbo(&self, pairs: &[String]);
+    fn subscribe_bbo(&self, pairs: &[String]) -> Result<(), UnsupportedChannel>;
 
     /// Subscribes to incremental level2 orderbook channels.
     ///
@@ -138,7 +177,7 @@ pub trait WSClient {
     /// * Kraken `book` with `depth=25`
     /// * MXC `depth` for Swap, `symbol` for Spot
     /// * OKEx `depth_l2_tbt`, top 100
-    fn subscribe_orderbook(&self, pairs: &[String]);
+    fn subscribe_orderbook(&self, pairs: &[String]) -> Result<(), UnsupportedChannel>;
 
     /// Subscribes to level2 orderbook snapshot channels.
     ///
@@ -155,7 +194,7 @@ pub trait WSClient {
     /// * Kraken has no snapshot channel
     /// * MXC `depth.full` for Swap, top 20, every 100ms; `get.depth` for Spot, full, every 26s
     /// * OKEx `depth5`, top 5, every 100ms
-    fn subscribe_orderbook_topk(&self, pairs: &[String]);
+    fn subscribe_orderbook_topk(&self, pairs: &[String]) -> Result<(), UnsupportedChannel>;
 
     /// Subscribes to level3 orderebook channels.
     ///
@@ -163,7 +202,7 @@ pub trait WSClient {
     ///
     /// The level3 orderbook is the orginal orderbook of an exchange, it is
     /// non-aggregated by price level and updated tick-by-tick.
-    fn subscribe_l3_orderbook(&self, symbols: &[String]);
+    fn subscribe_l3_orderbook(&self, symbols: &[String]) -> Result<(), UnsupportedChannel>;
 
     /// Subscribes to ticker channels.
     ///
@@ -172,17 +211,132 @@ pub trait WSClient {
     ///
     /// Not all exchanges have the ticker channel, for example, BitMEX,
     /// Bitstamp, MXC Spot, etc.
-    fn subscribe_ticker(&self, pairs: &[String]);
+    fn subscribe_ticker(&self, pairs: &[String]) -> Result<(), UnsupportedChannel>;
 
     /// Subscribes to candlestick channels.
     ///
     /// The candlestick channel sends OHLCV messages at interval.
     ///
-    /// `symbol_interval_list` is a list of symbols and intervals of candlesticks in seconds.
+    /// `symbol_interval_list` is a list of symbols and intervals of candlesticks.
     ///
     /// Not all exchanges have candlestick channels, for example, Bitstamp
-    /// and CoinbasePro.
-    fn subscribe_candlestick(&self, symbol_interval_list: &[(String, usize)]);
+    /// and CoinbasePro. Intervals not supported by the exchange also return
+    /// `UnsupportedChannel`, and nothing is subscribed.
+    fn subscribe_candlestick(
+        &self,
+        symbol_interval_list: &[(String, Interval)],
+    ) -> Result<(), UnsupportedChannel>;
+
+    /// Subscribes to channels of the given type, dispatched to its `subscribe_*()` method.
+    ///
+    /// Useful when channel types come from configuration rather than code.
+    /// Candlesticks need intervals, use `subscribe_candlestick()` for them.
+    fn subscribe_by_type(
+        &self,
+        msg_type: MessageType,
+        pairs: &[String],
+    ) -> Result<(), UnsupportedChannel> {
+        match msg_type {
+            MessageType::Trade => self.subscribe_trade(pairs),
+            MessageType::L2Event => self.subscribe_orderbook(pairs),
+            MessageType::L2TopK => self.subscribe_orderbook_topk(pairs),
+            MessageType::L3Event => self.subscribe_l3_orderbook(pairs),
+            MessageType::BBO => self.subscribe_bbo(pairs),
+            MessageType::Ticker => self.subscribe_ticker(pairs),
+            _ => Err(UnsupportedChannel::new(
+                &client_name::<Self>(),
+                &msg_type.to_string(),
+            )),
+        }
+    }
+
+    /// Subscribes to multiple types of channels of the same pairs at once.
+    ///
+    /// Each kind in `kinds` is passed to `subscribe_by_type()`, kinds the
+    /// exchange doesn't support are skipped and returned to the caller.
+    fn subscribe_all(&self, pairs: &[String], kinds: &[MessageType]) -> Vec<MessageType> {
+        kinds
+            .iter()
+            .filter(|kind| self.subscribe_by_type(**kind, pairs).is_err())
+            .copied()
+            .collect()
+    }
+
+    /// Unsubscribes from trade channels previously subscribed by `subscribe_trade()`.
+    ///
+    /// The default implementations of `unsubscribe_*()` return `UnsupportedChannel`.
+    fn unsubscribe_trade(&self, _pairs: &[String]) -> Result<(), UnsupportedChannel> {
+        Err(UnsupportedChannel::new(&client_name::<Self>(), "trade"))
+    }
+
+    /// Unsubscribes from BBO channels previously subscribed by `subscribe_bbo()`.
+    fn unsubscribe_bbo(&self, _pairs: &[String]) -> Result<(), UnsupportedChannel> {
+        Err(UnsupportedChannel::new(&client_name::<Self>(), "BBO"))
+    }
+
+    /// Unsubscribes from incremental level2 orderbook channels previously
+    /// subscribed by `subscribe_orderbook()`.
+    fn unsubscribe_orderbook(&self, _pairs: &[String]) -> Result<(), UnsupportedChannel> {
+        Err(UnsupportedChannel::new(
+            &client_name::<Self>(),
+            "level2 orderbook",
+        ))
+    }
+
+    /// Unsubscribes from level2 orderbook snapshot channels previously
+    /// subscribed by `subscribe_orderbook_topk()`.
+    fn unsubscribe_orderbook_topk(&self, _pairs: &[String]) -> Result<(), UnsupportedChannel> {
+        Err(UnsupportedChannel::new(
+            &client_name::<Self>(),
+            "level2 top-k snapshot",
+        ))
+    }
+
+    /// Unsubscribes from level3 orderbook channels previously subscribed by
+    /// `subscribe_l3_orderbook()`.
+    fn unsubscribe_l3_orderbook(&self, _symbols: &[String]) -> Result<(), UnsupportedChannel> {
+        Err(UnsupportedChannel::new(
+            &client_name::<Self>(),
+            "level3 orderbook",
+        ))
+    }
+
+    /// Unsubscribes from ticker channels previously subscribed by `subscribe_ticker()`.
+    fn unsubscribe_ticker(&self, _pairs: &[String]) -> Result<(), UnsupportedChannel> {
+        Err(UnsupportedChannel::new(&client_name::<Self>(), "ticker"))
+    }
+
+    /// Unsubscribes from candlestick channels previously subscribed by
+    /// `subscribe_candlestick()`.
+    fn unsubscribe_candlestick(
+        &self,
+        _symbol_interval_list: &[(String, Interval)],
+    ) -> Result<(), UnsupportedChannel> {
+        Err(UnsupportedChannel::new(
+            &client_name::<Self>(),
+            "candlestick",
+        ))
+    }
+
+    /// Unsubscribes from channels previously subscribed by `subscribe_by_type()`.
+    fn unsubscribe_by_type(
+        &self,
+        msg_type: MessageType,
+        pairs: &[String],
+    ) -> Result<(), UnsupportedChannel> {
+        match msg_type {
+            MessageType::Trade => self.unsubscribe_trade(pairs),
+            MessageType::L2Event => self.unsubscribe_orderbook(pairs),
+            MessageType::L2TopK => self.unsubscribe_orderbook_topk(pairs),
+            MessageType::L3Event => self.unsubscribe_l3_orderbook(pairs),
+            MessageType::BBO => self.unsubscribe_bbo(pairs),
+            MessageType::Ticker => self.unsubscribe_ticker(pairs),
+            _ => Err(UnsupportedChannel::new(
+                &client_name::<Self>(),
+                &msg_type.to_string(),
+            )),
+        }
+    }
 
     /// Subscribes to raw channels, lower level API.
     ///
@@ -200,11 +354,130 @@ pub trait WSClient {
     /// * A JSON string, supported by all exchanges. If a `raw_channel` starts
     /// with `{`, which means it is the final JSON string, thus it will be
     /// sent out directly without parsing.
+    ///
+    /// Use it for channels without a typed method, e.g., OKEx
+    /// `swap/mark_price:BTC-USD-SWAP`, `index/ticker:BTC-USD` or
+    /// `option/summary:BTC-USD`. Raw channels are tracked together with
+    /// channels of typed methods, so they are listed by `subscriptions()`
+    /// and resubscribed after a reconnect.
     fn subscribe(&self, raw_channels: &[String]);
 
     /// Unsubscribes from raw channels, lower level API.
+    ///
+    /// Only channels that are currently subscribed will be sent to the server,
+    /// JSON strings are sent out as they are.
     fn unsubscribe(&self, raw_channels: &[String]);
 
+    /// Sets the maximum size in bytes of a subscribe/unsubscribe command.
+    ///
+    /// Commands exceeding it are split into multiple commands, on top of
+    /// limits built into clients, e.g., Binance never sends more than 4096
+    /// bytes or 200 streams per command. Defaults to 65536. Clients that
+    /// don't split commands ignore it, which is the default implementation.
+    fn set_max_command_bytes(&self, _max_bytes: usize) {}
+
+    /// Calls `on_stale` with the websocket URL when no data message has been
+    /// received for `threshold` seconds.
+    ///
+    /// Unlike the read-timeout exit in `run()`, the connection is kept open,
+    /// so a supervisor can tell an illiquid market from a dead connection.
+    /// The callback fires once per quiet period and is checked after each
+    /// read from the socket. `None` disables it, which is the default. The
+    /// default implementation ignores it.
+    fn set_on_stale(&self, _threshold: u64, _on_stale: Option<fn(&str)>) {}
+
+    /// Sends binary frames of data messages to `raw_tx` as they are, still compressed.
+    ///
+    /// Huobi, OKEx, etc. send gzip or deflate compressed frames, storing them
+    /// is far cheaper than storing decompressed JSON and allows exact replay.
+    /// Frames are still decompressed to handle pings and other non-data
+    /// messages. If `keep_text` is true, the decompressed text of data messages
+    /// is sent to the client's channel as well, otherwise it is dropped.
+    /// Text frames always go to the client's channel. `None` disables it,
+    /// which is the default. Clients of exchanges sending text frames only,
+    /// e.g., Bitfinex, ignore it, which is the default implementation.
+    ///
+    /// Both channels are fed by the same reading thread, so each of them keeps
+    /// the order of arrival and a raw frame is sent before its text. There is
+    /// no ordering between the two channels from the receivers' point of view
+    /// though, so replay from `raw_tx` alone if the order matters. If `raw_tx`
+    /// is disconnected, raw frames are disabled and data messages go to the
+    /// client's channel as text.
+    fn set_raw_sender(&self, _raw_tx: Option<Sender<Vec<u8>>>, _keep_text: bool) {}
+
+    /// Reconnects in place instead of exiting the process when the connection drops.
+    ///
+    /// By default a dropped connection exits the process so that a supervisor
+    /// such as pm2 restarts it. If `reset_tx` is set, the client reconnects
+    /// and, before resubscribing, sends a `ResetNotification` listing the
+    /// resubscribed channels to `reset_tx`. Sequence numbers restart on the
+    /// new connection, so consumers should discard per-channel state such as
+    /// orderbooks when they receive it.
+    ///
+    /// Messages of the new connection are sent to the client's channel after
+    /// the notification, so checking `reset_tx` with `try_recv()` before
+    /// handling each message sees the reset before any of them. Messages of
+    /// the old connection may still be queued in the client's channel by
+    /// then. A dropped receiver of `reset_tx` is ignored. `None` disables it,
+    /// which is the default. The default implementation ignores it, so the
+    /// process still exits.
+    fn set_reset_on_reconnect(&self, _reset_tx: Option<Sender<ResetNotification>>) {}
+
+    /// Sets the maximum size in bytes of an incoming websocket frame.
+    ///
+    /// A larger frame can't be read, the client closes the connection and
+    /// exits or reconnects, see `set_reset_on_reconnect()`. It applies to the
+    /// current connection and all reconnects. `None` means the tungstenite
+    /// default of 16 MiB, which all exchanges stay far below. The default
+    /// implementation ignores it.
+    ///
+    /// Only frames received are limited. Servers limit the frames they
+    /// receive as well, e.g., OKEx closes the connection with error 1009
+    /// "Max frame length of 65536 exceeded", commands sent are kept below
+    /// such limits by `set_max_command_bytes()`.
+    fn set_max_frame_size(&self, _max_frame_size: Option<usize>) {}
+
+    /// Logs in so that private channels, e.g., orders and fills, can be subscribed.
+    ///
+    /// * OKEx sends a login command signed by `secret`, and sends it again
+    ///   on reconnect. Channels, e.g., `spot/order:BTC-USDT`, are held back
+    ///   until the server replies to the login command.
+    /// * Binance creates a listenKey with `api_key` and subscribes to the user
+    ///   data stream, `secret` and `passphrase` are not used. A listenKey
+    ///   expires after 60 minutes, calling this method again extends it.
+    ///
+    /// Other exchanges return `AuthError::Unsupported`, which is also what the
+    /// default implementation does.
+    fn authenticate(
+        &self,
+        _api_key: &str,
+        _secret: &str,
+        _passphrase: &str,
+    ) -> Result<(), AuthError> {
+        Err(AuthError::Unsupported(client_name::<Self>()))
+    }
+
+    /// Returns statistics of this client.
+    ///
+    /// Channels the server refuses, e.g., OKEx error 30040 for delisted
+    /// symbols, are removed from subscribed channels so that they are not
+    /// requested again on reconnect, and are listed in `rejected_channels`.
+    /// The default implementation returns `WSClientStats::default()`.
+    fn stats(&self) -> WSClientStats {
+        WSClientStats::default()
+    }
+
+    /// Returns the raw channels currently subscribed, sorted.
+    ///
+    /// Channels are tracked when subscribe commands are sent, so a channel
+    /// is listed before the server confirms it, and removed once the server
+    /// rejects it, see `stats()`. Useful to find out why no data arrives for
+    /// a symbol, or to diff against the desired channels before resubscribing.
+    /// The default implementation returns an empty list.
+    fn subscriptions(&self) -> Vec<String> {
+        Vec::new()
+    }
+
     /// Starts the infinite loop until time is up or the server closes the connection.
     ///
     /// # Arguments
@@ -214,4 +487,86 @@ pub trait WSClient {
 
     /// Breaks the loop and closes the connection.
     fn close(&self);
+
+    /// Unsubscribes from all subscribed channels, waits a second for the
+    /// server to stop pushing, then breaks the loop and closes the connection.
+    ///
+    /// Channels subscribed as JSON strings can't be unsubscribed automatically.
+    /// Use `close()` to close immediately. The default implementation
+    /// unsubscribes from `subscriptions()`.
+    fn close_gracefully(&self) {
+        let channels: Vec<String> = self
+            .subscriptions()
+            .into_iter()
+            .filter(|ch| !ch.starts_with('{'))
+            .collect();
+        if !channels.is_empty() {
+            self.unsubscribe(&channels);
+            std::thread::sleep(clients::utils::GRACEFUL_CLOSE_DELAY);
+        }
+        self.close();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Implements only the required methods of `WSClient`
+    struct MinimalWSClient;
+
+    impl WSClient for MinimalWSClient {
+        fn subscribe_trade(&self, _pairs: &[String]) -> Result<(), UnsupportedChannel> {
+            Ok(())
+        }
+        fn subscribe_bbo(&self, _pairs: &[String]) -> Result<(), UnsupportedChannel> {
+            Ok(())
+        }
+        fn subscribe_orderbook(&self, _pairs: &[String]) -> Result<(), UnsupportedChannel> {
+            Ok(())
+        }
+        fn subscribe_orderbook_topk(&self, _pairs: &[String]) -> Result<(), UnsupportedChannel> {
+            Ok(())
+        }
+        fn subscribe_l3_orderbook(&self, _symbols: &[String]) -> Result<(), UnsupportedChannel> {
+            Ok(())
+        }
+        fn subscribe_ticker(&self, _pairs: &[String]) -> Result<(), UnsupportedChannel> {
+            Ok(())
+        }
+        fn subscribe_candlestick(
+            &self,
+            _symbol_interval_list: &[(String, Interval)],
+        ) -> Result<(), UnsupportedChannel> {
+            Ok(())
+        }
+        fn subscribe(&self, _raw_channels: &[String]) {}
+        fn unsubscribe(&self, _raw_channels: &[String]) {}
+        fn run(&self, _duration: Option<u64>) {}
+        fn close(&self) {}
+    }
+
+    #[test]
+    fn default_methods() {
+        let client = MinimalWSClient;
+        let pairs = vec!["BTCUSDT".to_string()];
+
+        let err = client.unsubscribe_trade(&pairs).unwrap_err();
+        assert_eq!("MinimalWSClient", err.exchange);
+        assert_eq!("trade", err.channel);
+        assert_eq!(
+            "level3 orderbook",
+            client.unsubscribe_l3_orderbook(&pairs).unwrap_err().channel
+        );
+        assert_eq!(
+            Err(AuthError::Unsupported("MinimalWSClient".to_string())),
+            client.authenticate("key", "secret", "passphrase")
+        );
+
+        client.set_max_command_bytes(1024);
+        client.set_max_frame_size(Some(1024));
+        assert_eq!(WSClientStats::default(), client.stats());
+        assert!(client.subscriptions().is_empty());
+        client.close_gracefully();
+    }
 }
@@ -3,7 +3,7 @@ mod utils;
 
 #[cfg(test)]
 mod binance_spot {
-    use crypto_ws_client::{BinanceSpotWSClient, WSClient};
+    use crypto_ws_client::{BinanceSpotWSClient, Interval, WSClient};
     use std::sync::mpsc::{Receiver, Sender};
 
     #[test]
@@ -60,6 +60,27 @@ mod binance_spot {
         );
     }
 
+    #[test]
+    fn unsubscribe_trade() {
+        let (tx, rx): (Sender<String>, Receiver<String>) = std::sync::mpsc::channel();
+        let mut messages = Vec::<String>::new();
+        {
+            let ws_client = BinanceSpotWSClient::new(tx, None);
+            ws_client
+                .subscribe_trade(&vec!["btcusdt".to_string(), "ethusdt".to_string()])
+                .unwrap();
+            ws_client
+                .unsubscribe_trade(&vec!["ethusdt".to_string()])
+                .unwrap();
+            ws_client.run(Some(0)); // return immediately once after a normal message
+            ws_client.close();
+        }
+        for msg in rx {
+            messages.push(msg);
+        }
+        assert!(!messages.is_empty());
+    }
+
     #[test]
     fn subscribe_bbo() {
         gen_test_code!(
@@ -91,13 +112,16 @@ mod binance_spot {
     fn subscribe_candlestick() {
         gen_test_subscribe_candlestick!(
             BinanceSpotWSClient,
-            &vec![("btcusdt".to_string(), 60), ("ethusdt".to_string(), 60)]
+            &vec![
+                ("btcusdt".to_string(), Interval::Min1),
+                ("ethusdt".to_string(), Interval::Min1)
+            ]
         );
         gen_test_subscribe_candlestick!(
             BinanceSpotWSClient,
             &vec![
-                ("btcusdt".to_string(), 2592000),
-                ("ethusdt".to_string(), 2592000)
+                ("btcusdt".to_string(), Interval::Month1),
+                ("ethusdt".to_string(), Interval::Month1)
             ]
         );
     }
@@ -105,7 +129,7 @@ mod binance_spot {
 
 #[cfg(test)]
 mod binance_inverse_future {
-    use crypto_ws_client::{BinanceInverseWSClient, WSClient};
+    use crypto_ws_client::{BinanceInverseWSClient, Interval, WSClient};
     use std::sync::mpsc::{Receiver, Sender};
 
     #[test]
@@ -185,15 +209,15 @@ mod binance_inverse_future {
         gen_test_subscribe_candlestick!(
             BinanceInverseWSClient,
             &vec![
-                ("btcusd_211231".to_string(), 60),
-                ("ethusd_211231".to_string(), 60)
+                ("btcusd_211231".to_string(), Interval::Min1),
+                ("ethusd_211231".to_string(), Interval::Min1)
             ]
         );
         gen_test_subscribe_candlestick!(
             BinanceInverseWSClient,
             &vec![
-                ("btcusd_211231".to_string(), 2592000),
-                ("ethusd_211231".to_string(), 2592000)
+                ("btcusd_211231".to_string(), Interval::Month1),
+                ("ethusd_211231".to_string(), Interval::Month1)
             ]
         );
     }
@@ -201,7 +225,7 @@ mod binance_inverse_future {
 
 #[cfg(test)]
 mod binance_linear_future {
-    use crypto_ws_client::{BinanceLinearWSClient, WSClient};
+    use crypto_ws_client::{BinanceLinearWSClient, Interval, WSClient};
     use std::sync::mpsc::{Receiver, Sender};
 
     #[test]
@@ -281,15 +305,15 @@ mod binance_linear_future {
         gen_test_subscribe_candlestick!(
             BinanceLinearWSClient,
             &vec![
-                ("btcusdt_211231".to_string(), 60),
-                ("ethusdt_211231".to_string(), 60)
+                ("btcusdt_211231".to_string(), Interval::Min1),
+                ("ethusdt_211231".to_string(), Interval::Min1)
             ]
         );
         gen_test_subscribe_candlestick!(
             BinanceLinearWSClient,
             &vec![
-                ("btcusdt_211231".to_string(), 2592000),
-                ("ethusdt_211231".to_string(), 2592000)
+                ("btcusdt_211231".to_string(), Interval::Month1),
+                ("ethusdt_211231".to_string(), Interval::Month1)
             ]
         );
     }
@@ -297,7 +321,7 @@ mod binance_linear_future {
 
 #[cfg(test)]
 mod binance_inverse_swap {
-    use crypto_ws_client::{BinanceInverseWSClient, WSClient};
+    use crypto_ws_client::{BinanceInverseWSClient, Interval, WSClient};
     use std::sync::mpsc::{Receiver, Sender};
 
     #[test]
@@ -377,15 +401,15 @@ mod binance_inverse_swap {
         gen_test_subscribe_candlestick!(
             BinanceInverseWSClient,
             &vec![
-                ("btcusd_perp".to_string(), 60),
-                ("ethusd_perp".to_string(), 60)
+                ("btcusd_perp".to_string(), Interval::Min1),
+                ("ethusd_perp".to_string(), Interval::Min1)
             ]
         );
         gen_test_subscribe_candlestick!(
             BinanceInverseWSClient,
             &vec![
-                ("btcusd_perp".to_string(), 2592000),
-                ("ethusd_perp".to_string(), 2592000)
+                ("btcusd_perp".to_string(), Interval::Month1),
+                ("ethusd_perp".to_string(), Interval::Month1)
             ]
         );
     }
@@ -411,7 +435,7 @@ mod binance_inverse_swap {
 
 #[cfg(test)]
 mod binance_linear_swap {
-    use crypto_ws_client::{BinanceLinearWSClient, WSClient};
+    use crypto_ws_client::{BinanceLinearWSClient, Interval, WSClient};
     use std::sync::mpsc::{Receiver, Sender};
 
     #[test]
@@ -490,13 +514,16 @@ mod binance_linear_swap {
     fn subscribe_candlestick() {
         gen_test_subscribe_candlestick!(
             BinanceLinearWSClient,
-            &vec![("btcusdt".to_string(), 60), ("ethusdt".to_string(), 60)]
+            &vec![
+                ("btcusdt".to_string(), Interval::Min1),
+                ("ethusdt".to_string(), Interval::Min1)
+            ]
         );
         gen_test_subscribe_candlestick!(
             BinanceLinearWSClient,
             &vec![
-                ("btcusdt".to_string(), 2592000),
-                ("ethusdt".to_string(), 2592000)
+                ("btcusdt".to_string(), Interval::Month1),
+                ("ethusdt".to_string(), Interval::Month1)
             ]
         );
     }
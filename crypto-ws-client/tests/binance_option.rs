@@ -1,4 +1,4 @@
-use crypto_ws_client::{BinanceOptionWSClient, WSClient};
+use crypto_ws_client::{BinanceOptionWSClient, Interval, WSClient};
 use std::sync::mpsc::{Receiver, Sender};
 
 #[macro_use]
@@ -88,15 +88,15 @@ fn subscribe_candlestick() {
     gen_test_subscribe_candlestick!(
         BinanceOptionWSClient,
         &vec![
-            ("BTC-210430-64000-C".to_string(), 60),
-            ("BTC-210430-68000-C".to_string(), 60),
+            ("BTC-210430-64000-C".to_string(), Interval::Min1),
+            ("BTC-210430-68000-C".to_string(), Interval::Min1),
         ]
     );
     gen_test_subscribe_candlestick!(
         BinanceOptionWSClient,
         &vec![
-            ("BTC-210430-64000-C".to_string(), 60),
-            ("BTC-210430-68000-C".to_string(), 60),
+            ("BTC-210430-64000-C".to_string(), Interval::Min1),
+            ("BTC-210430-68000-C".to_string(), Interval::Min1),
         ]
     );
 }
@@ -3,7 +3,7 @@ mod utils;
 
 #[cfg(test)]
 mod bitfinex_spot {
-    use crypto_ws_client::{BitfinexWSClient, WSClient};
+    use crypto_ws_client::{BitfinexWSClient, Interval, WSClient};
     use std::sync::mpsc::{Receiver, Sender};
 
     #[test]
@@ -72,14 +72,20 @@ mod bitfinex_spot {
 
     #[test]
     fn subscribe_candlestick() {
-        gen_test_subscribe_candlestick!(BitfinexWSClient, &vec![("tBTCUST".to_string(), 60)]);
-        gen_test_subscribe_candlestick!(BitfinexWSClient, &vec![("tBTCUST".to_string(), 2592000)]);
+        gen_test_subscribe_candlestick!(
+            BitfinexWSClient,
+            &vec![("tBTCUST".to_string(), Interval::Min1)]
+        );
+        gen_test_subscribe_candlestick!(
+            BitfinexWSClient,
+            &vec![("tBTCUST".to_string(), Interval::Month1)]
+        );
     }
 }
 
 #[cfg(test)]
 mod bitfinex_swap {
-    use crypto_ws_client::{BitfinexWSClient, WSClient};
+    use crypto_ws_client::{BitfinexWSClient, Interval, WSClient};
     use std::sync::mpsc::{Receiver, Sender};
 
     #[test]
@@ -138,10 +144,13 @@ mod bitfinex_swap {
 
     #[test]
     fn subscribe_candlestick() {
-        gen_test_subscribe_candlestick!(BitfinexWSClient, &vec![("tBTCF0:USTF0".to_string(), 60)]);
         gen_test_subscribe_candlestick!(
             BitfinexWSClient,
-            &vec![("tBTCF0:USTF0".to_string(), 2592000)]
+            &vec![("tBTCF0:USTF0".to_string(), Interval::Min1)]
+        );
+        gen_test_subscribe_candlestick!(
+            BitfinexWSClient,
+            &vec![("tBTCF0:USTF0".to_string(), Interval::Month1)]
         );
     }
 }
@@ -3,7 +3,7 @@ mod utils;
 
 #[cfg(test)]
 mod bitget_inverse_swap {
-    use crypto_ws_client::{BitgetSwapWSClient, WSClient};
+    use crypto_ws_client::{BitgetSwapWSClient, Interval, WSClient};
     use std::sync::mpsc::{Receiver, Sender};
 
     #[test]
@@ -62,8 +62,14 @@ mod bitget_inverse_swap {
 
     #[test]
     fn subscribe_candlestick() {
-        gen_test_subscribe_candlestick!(BitgetSwapWSClient, &vec![("btcusd".to_string(), 60)]);
-        gen_test_subscribe_candlestick!(BitgetSwapWSClient, &vec![("btcusd".to_string(), 604800)]);
+        gen_test_subscribe_candlestick!(
+            BitgetSwapWSClient,
+            &vec![("btcusd".to_string(), Interval::Min1)]
+        );
+        gen_test_subscribe_candlestick!(
+            BitgetSwapWSClient,
+            &vec![("btcusd".to_string(), Interval::Week1)]
+        );
     }
 
     #[ignore]
@@ -79,7 +85,7 @@ mod bitget_inverse_swap {
 
 #[cfg(test)]
 mod bitget_linear_swap {
-    use crypto_ws_client::{BitgetSwapWSClient, WSClient};
+    use crypto_ws_client::{BitgetSwapWSClient, Interval, WSClient};
     use std::sync::mpsc::{Receiver, Sender};
 
     #[test]
@@ -120,10 +126,13 @@ mod bitget_linear_swap {
 
     #[test]
     fn subscribe_candlestick() {
-        gen_test_subscribe_candlestick!(BitgetSwapWSClient, &vec![("cmt_btcusdt".to_string(), 60)]);
         gen_test_subscribe_candlestick!(
             BitgetSwapWSClient,
-            &vec![("cmt_btcusdt".to_string(), 604800)]
+            &vec![("cmt_btcusdt".to_string(), Interval::Min1)]
+        );
+        gen_test_subscribe_candlestick!(
+            BitgetSwapWSClient,
+            &vec![("cmt_btcusdt".to_string(), Interval::Week1)]
         );
     }
 
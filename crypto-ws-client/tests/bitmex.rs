@@ -11,7 +11,7 @@ fn bitmex_instrument() {
 
 #[cfg(test)]
 mod bitmex_inverse_swap {
-    use crypto_ws_client::{BitmexWSClient, WSClient};
+    use crypto_ws_client::{BitmexWSClient, Interval, WSClient};
     use std::sync::mpsc::{Receiver, Sender};
 
     #[test]
@@ -62,8 +62,14 @@ mod bitmex_inverse_swap {
 
     #[test]
     fn subscribe_candlestick() {
-        gen_test_subscribe_candlestick!(BitmexWSClient, &vec![("XBTUSD".to_string(), 60)]);
-        gen_test_subscribe_candlestick!(BitmexWSClient, &vec![("XBTUSD".to_string(), 86400)]);
+        gen_test_subscribe_candlestick!(
+            BitmexWSClient,
+            &vec![("XBTUSD".to_string(), Interval::Min1)]
+        );
+        gen_test_subscribe_candlestick!(
+            BitmexWSClient,
+            &vec![("XBTUSD".to_string(), Interval::Day1)]
+        );
     }
 
     #[test]
@@ -88,7 +94,7 @@ mod bitmex_inverse_swap {
 
 #[cfg(test)]
 mod bitmex_inverse_future {
-    use crypto_ws_client::{BitmexWSClient, WSClient};
+    use crypto_ws_client::{BitmexWSClient, Interval, WSClient};
     use std::sync::mpsc::{Receiver, Sender};
 
     #[test]
@@ -140,18 +146,24 @@ mod bitmex_inverse_future {
     fn subscribe_candlestick() {
         gen_test_subscribe_candlestick!(
             BitmexWSClient,
-            &vec![("XBTZ21".to_string(), 60), ("XBTZ21".to_string(), 60)]
+            &vec![
+                ("XBTZ21".to_string(), Interval::Min1),
+                ("XBTZ21".to_string(), Interval::Min1)
+            ]
         );
         gen_test_subscribe_candlestick!(
             BitmexWSClient,
-            &vec![("XBTZ21".to_string(), 86400), ("XBTZ21".to_string(), 86400)]
+            &vec![
+                ("XBTZ21".to_string(), Interval::Day1),
+                ("XBTZ21".to_string(), Interval::Day1)
+            ]
         );
     }
 }
 
 #[cfg(test)]
 mod bitmex_quanto_swap {
-    use crypto_ws_client::{BitmexWSClient, WSClient};
+    use crypto_ws_client::{BitmexWSClient, Interval, WSClient};
     use std::sync::mpsc::{Receiver, Sender};
 
     #[test]
@@ -184,8 +196,14 @@ mod bitmex_quanto_swap {
 
     #[test]
     fn subscribe_candlestick() {
-        gen_test_subscribe_candlestick!(BitmexWSClient, &vec![("ETHUSD".to_string(), 60)]);
-        gen_test_subscribe_candlestick!(BitmexWSClient, &vec![("ETHUSD".to_string(), 86400)]);
+        gen_test_subscribe_candlestick!(
+            BitmexWSClient,
+            &vec![("ETHUSD".to_string(), Interval::Min1)]
+        );
+        gen_test_subscribe_candlestick!(
+            BitmexWSClient,
+            &vec![("ETHUSD".to_string(), Interval::Day1)]
+        );
     }
 
     #[test]
@@ -60,3 +60,52 @@ fn subscribe_l3_orderbook() {
         &vec!["btcusd".to_string(), "ethusd".to_string()]
     );
 }
+
+#[test]
+fn subscribe_by_type() {
+    use crypto_ws_client::MessageType;
+
+    let (tx, rx): (Sender<String>, Receiver<String>) = std::sync::mpsc::channel();
+    let mut messages = Vec::<String>::new();
+    {
+        let ws_client = BitstampWSClient::new(tx, None);
+        let pairs = vec!["btcusd".to_string()];
+        ws_client
+            .subscribe_by_type(MessageType::L2Event, &pairs)
+            .unwrap();
+        assert!(ws_client
+            .subscribe_by_type(MessageType::Ticker, &pairs)
+            .is_err());
+        ws_client.run(Some(0)); // return immediately once after a normal message
+        ws_client
+            .unsubscribe_by_type(MessageType::L2Event, &pairs)
+            .unwrap();
+        ws_client.close();
+    }
+    for msg in rx {
+        messages.push(msg);
+    }
+    assert!(!messages.is_empty());
+}
+
+#[test]
+fn subscribe_all() {
+    use crypto_ws_client::MessageType;
+
+    let (tx, rx): (Sender<String>, Receiver<String>) = std::sync::mpsc::channel();
+    let mut messages = Vec::<String>::new();
+    {
+        let ws_client = BitstampWSClient::new(tx, None);
+        let skipped = ws_client.subscribe_all(
+            &vec!["btcusd".to_string()],
+            &[MessageType::Trade, MessageType::BBO, MessageType::L2Event],
+        );
+        assert_eq!(skipped, vec![MessageType::BBO]);
+        ws_client.run(Some(0)); // return immediately once after a normal message
+        ws_client.close();
+    }
+    for msg in rx {
+        messages.push(msg);
+    }
+    assert!(!messages.is_empty());
+}
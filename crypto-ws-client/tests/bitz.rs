@@ -3,7 +3,7 @@ mod utils;
 
 #[cfg(test)]
 mod bitz_spot {
-    use crypto_ws_client::{BitzSpotWSClient, WSClient};
+    use crypto_ws_client::{BitzSpotWSClient, Interval, WSClient};
     use std::sync::mpsc::{Receiver, Sender};
     use std::time::{SystemTime, UNIX_EPOCH};
 
@@ -70,7 +70,13 @@ mod bitz_spot {
     #[test]
     #[ignore = "bitz.com has shutdown since October 2021"]
     fn subscribe_candlestick() {
-        gen_test_subscribe_candlestick!(BitzSpotWSClient, &vec![("btc_usdt".to_string(), 60)]);
-        gen_test_subscribe_candlestick!(BitzSpotWSClient, &vec![("btc_usdt".to_string(), 2592000)]);
+        gen_test_subscribe_candlestick!(
+            BitzSpotWSClient,
+            &vec![("btc_usdt".to_string(), Interval::Min1)]
+        );
+        gen_test_subscribe_candlestick!(
+            BitzSpotWSClient,
+            &vec![("btc_usdt".to_string(), Interval::Month1)]
+        );
     }
 }
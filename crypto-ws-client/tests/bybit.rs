@@ -3,7 +3,7 @@ mod utils;
 
 #[cfg(test)]
 mod bybit_inverse_future {
-    use crypto_ws_client::{BybitInverseFutureWSClient, WSClient};
+    use crypto_ws_client::{BybitInverseFutureWSClient, Interval, WSClient};
     use std::sync::mpsc::{Receiver, Sender};
 
     #[test]
@@ -64,18 +64,18 @@ mod bybit_inverse_future {
     fn subscribe_candlestick() {
         gen_test_subscribe_candlestick!(
             BybitInverseFutureWSClient,
-            &vec![("BTCUSDZ21".to_string(), 60)]
+            &vec![("BTCUSDZ21".to_string(), Interval::Min1)]
         );
         gen_test_subscribe_candlestick!(
             BybitInverseFutureWSClient,
-            &vec![("BTCUSDZ21".to_string(), 2592000)]
+            &vec![("BTCUSDZ21".to_string(), Interval::Month1)]
         );
     }
 }
 
 #[cfg(test)]
 mod bybit_inverse_swap {
-    use crypto_ws_client::{BybitInverseSwapWSClient, WSClient};
+    use crypto_ws_client::{BybitInverseSwapWSClient, Interval, WSClient};
     use std::sync::mpsc::{Receiver, Sender};
 
     #[test]
@@ -136,18 +136,18 @@ mod bybit_inverse_swap {
     fn subscribe_candlestick() {
         gen_test_subscribe_candlestick!(
             BybitInverseSwapWSClient,
-            &vec![("BTCUSD".to_string(), 60)]
+            &vec![("BTCUSD".to_string(), Interval::Min1)]
         );
         gen_test_subscribe_candlestick!(
             BybitInverseSwapWSClient,
-            &vec![("BTCUSD".to_string(), 2592000)]
+            &vec![("BTCUSD".to_string(), Interval::Month1)]
         );
     }
 }
 
 #[cfg(test)]
 mod bybit_linear_swap {
-    use crypto_ws_client::{BybitLinearSwapWSClient, WSClient};
+    use crypto_ws_client::{BybitLinearSwapWSClient, Interval, WSClient};
     use std::sync::mpsc::{Receiver, Sender};
 
     #[test]
@@ -190,11 +190,11 @@ mod bybit_linear_swap {
     fn subscribe_candlestick() {
         gen_test_subscribe_candlestick!(
             BybitLinearSwapWSClient,
-            &vec![("BTCUSDT".to_string(), 60)]
+            &vec![("BTCUSDT".to_string(), Interval::Min1)]
         );
         gen_test_subscribe_candlestick!(
             BybitLinearSwapWSClient,
-            &vec![("BTCUSDT".to_string(), 2592000)]
+            &vec![("BTCUSDT".to_string(), Interval::Month1)]
         );
     }
 }
@@ -19,7 +19,7 @@ fn deribit_all_trades() {
 
 #[cfg(test)]
 mod deribit_inverse_future {
-    use crypto_ws_client::{DeribitWSClient, WSClient};
+    use crypto_ws_client::{DeribitWSClient, Interval, WSClient};
     use std::sync::mpsc::{Receiver, Sender};
 
     #[test]
@@ -69,14 +69,20 @@ mod deribit_inverse_future {
 
     #[test]
     fn subscribe_candlestick() {
-        gen_test_subscribe_candlestick!(DeribitWSClient, &vec![("BTC-31DEC21".to_string(), 60)]);
-        gen_test_subscribe_candlestick!(DeribitWSClient, &vec![("BTC-31DEC21".to_string(), 86400)]);
+        gen_test_subscribe_candlestick!(
+            DeribitWSClient,
+            &vec![("BTC-31DEC21".to_string(), Interval::Min1)]
+        );
+        gen_test_subscribe_candlestick!(
+            DeribitWSClient,
+            &vec![("BTC-31DEC21".to_string(), Interval::Day1)]
+        );
     }
 }
 
 #[cfg(test)]
 mod deribit_inverse_swap {
-    use crypto_ws_client::{DeribitWSClient, WSClient};
+    use crypto_ws_client::{DeribitWSClient, Interval, WSClient};
     use std::sync::mpsc::{Receiver, Sender};
 
     #[test]
@@ -126,17 +132,20 @@ mod deribit_inverse_swap {
 
     #[test]
     fn subscribe_candlestick() {
-        gen_test_subscribe_candlestick!(DeribitWSClient, &vec![("BTC-PERPETUAL".to_string(), 60)]);
         gen_test_subscribe_candlestick!(
             DeribitWSClient,
-            &vec![("BTC-PERPETUAL".to_string(), 86400)]
+            &vec![("BTC-PERPETUAL".to_string(), Interval::Min1)]
+        );
+        gen_test_subscribe_candlestick!(
+            DeribitWSClient,
+            &vec![("BTC-PERPETUAL".to_string(), Interval::Day1)]
         );
     }
 }
 
 #[cfg(test)]
 mod deribit_option {
-    use crypto_ws_client::{DeribitWSClient, WSClient};
+    use crypto_ws_client::{DeribitWSClient, Interval, WSClient};
     use std::sync::mpsc::{Receiver, Sender};
 
     const SYMBOLS: &'static [&str] = &[
@@ -212,16 +221,16 @@ mod deribit_option {
             DeribitWSClient,
             SYMBOLS
                 .iter()
-                .map(|s| (s.to_string(), 60))
-                .collect::<Vec<(String, usize)>>()
+                .map(|s| (s.to_string(), Interval::Min1))
+                .collect::<Vec<(String, Interval)>>()
                 .as_slice()
         );
         gen_test_subscribe_candlestick!(
             DeribitWSClient,
             SYMBOLS
                 .iter()
-                .map(|s| (s.to_string(), 86400))
-                .collect::<Vec<(String, usize)>>()
+                .map(|s| (s.to_string(), Interval::Day1))
+                .collect::<Vec<(String, Interval)>>()
                 .as_slice()
         );
     }
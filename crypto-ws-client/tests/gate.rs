@@ -3,7 +3,7 @@ mod utils;
 
 #[cfg(test)]
 mod gate_spot {
-    use crypto_ws_client::{GateSpotWSClient, WSClient};
+    use crypto_ws_client::{GateSpotWSClient, Interval, WSClient};
     use std::sync::mpsc::{Receiver, Sender};
 
     #[test]
@@ -76,14 +76,20 @@ mod gate_spot {
 
     #[test]
     fn subscribe_candlestick() {
-        gen_test_subscribe_candlestick!(GateSpotWSClient, &vec![("BTC_USDT".to_string(), 10)]);
-        gen_test_subscribe_candlestick!(GateSpotWSClient, &vec![("BTC_USDT".to_string(), 604800)]);
+        gen_test_subscribe_candlestick!(
+            GateSpotWSClient,
+            &vec![("BTC_USDT".to_string(), Interval::Sec10)]
+        );
+        gen_test_subscribe_candlestick!(
+            GateSpotWSClient,
+            &vec![("BTC_USDT".to_string(), Interval::Week1)]
+        );
     }
 }
 
 #[cfg(test)]
 mod gate_inverse_swap {
-    use crypto_ws_client::{GateInverseSwapWSClient, WSClient};
+    use crypto_ws_client::{GateInverseSwapWSClient, Interval, WSClient};
     use std::sync::mpsc::{Receiver, Sender};
 
     #[test]
@@ -160,18 +166,18 @@ mod gate_inverse_swap {
     fn subscribe_candlestick() {
         gen_test_subscribe_candlestick!(
             GateInverseSwapWSClient,
-            &vec![("BTC_USD".to_string(), 10)]
+            &vec![("BTC_USD".to_string(), Interval::Sec10)]
         );
         gen_test_subscribe_candlestick!(
             GateInverseSwapWSClient,
-            &vec![("BTC_USD".to_string(), 604800)]
+            &vec![("BTC_USD".to_string(), Interval::Week1)]
         );
     }
 }
 
 #[cfg(test)]
 mod gate_linear_swap {
-    use crypto_ws_client::{GateLinearSwapWSClient, WSClient};
+    use crypto_ws_client::{GateLinearSwapWSClient, Interval, WSClient};
     use std::sync::mpsc::{Receiver, Sender};
 
     #[test]
@@ -224,18 +230,18 @@ mod gate_linear_swap {
     fn subscribe_candlestick() {
         gen_test_subscribe_candlestick!(
             GateLinearSwapWSClient,
-            &vec![("BTC_USDT".to_string(), 10)]
+            &vec![("BTC_USDT".to_string(), Interval::Sec10)]
         );
         gen_test_subscribe_candlestick!(
             GateLinearSwapWSClient,
-            &vec![("BTC_USDT".to_string(), 604800)]
+            &vec![("BTC_USDT".to_string(), Interval::Week1)]
         );
     }
 }
 
 #[cfg(test)]
 mod gate_inverse_future {
-    use crypto_ws_client::{GateInverseFutureWSClient, WSClient};
+    use crypto_ws_client::{GateInverseFutureWSClient, Interval, WSClient};
     use std::sync::mpsc::{Receiver, Sender};
 
     #[test]
@@ -272,18 +278,18 @@ mod gate_inverse_future {
     fn subscribe_candlestick() {
         gen_test_subscribe_candlestick!(
             GateInverseFutureWSClient,
-            &vec![("BTC_USD_20211231".to_string(), 10)]
+            &vec![("BTC_USD_20211231".to_string(), Interval::Sec10)]
         );
         gen_test_subscribe_candlestick!(
             GateInverseFutureWSClient,
-            &vec![("BTC_USD_20211231".to_string(), 604800)]
+            &vec![("BTC_USD_20211231".to_string(), Interval::Week1)]
         );
     }
 }
 
 #[cfg(test)]
 mod gate_linear_future {
-    use crypto_ws_client::{GateLinearFutureWSClient, WSClient};
+    use crypto_ws_client::{GateLinearFutureWSClient, Interval, WSClient};
     use std::sync::mpsc::{Receiver, Sender};
 
     #[test]
@@ -320,11 +326,11 @@ mod gate_linear_future {
     fn subscribe_candlestick() {
         gen_test_subscribe_candlestick!(
             GateLinearFutureWSClient,
-            &vec![("BTC_USDT_20211015".to_string(), 10)]
+            &vec![("BTC_USDT_20211015".to_string(), Interval::Sec10)]
         );
         gen_test_subscribe_candlestick!(
             GateLinearFutureWSClient,
-            &vec![("BTC_USDT_20211015".to_string(), 604800)]
+            &vec![("BTC_USDT_20211015".to_string(), Interval::Week1)]
         );
     }
 }
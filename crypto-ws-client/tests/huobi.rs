@@ -3,7 +3,7 @@ mod utils;
 
 #[cfg(test)]
 mod huobi_spot {
-    use crypto_ws_client::{HuobiSpotWSClient, WSClient};
+    use crypto_ws_client::{HuobiSpotWSClient, Interval, WSClient};
     use std::sync::mpsc::{Receiver, Sender};
 
     #[test]
@@ -57,7 +57,9 @@ mod huobi_spot {
         let mut messages = Vec::<String>::new();
         {
             let ws_client = HuobiSpotWSClient::new(tx, Some("wss://api.huobi.pro/feed"));
-            ws_client.subscribe_orderbook(&vec!["btcusdt".to_string()]);
+            ws_client
+                .subscribe_orderbook(&vec!["btcusdt".to_string()])
+                .unwrap();
             ws_client.run(Some(0)); // return immediately once after getting a normal message
         }
         for msg in rx {
@@ -77,14 +79,20 @@ mod huobi_spot {
 
     #[test]
     fn subscribe_candlestick() {
-        gen_test_subscribe_candlestick!(HuobiSpotWSClient, &vec![("btcusdt".to_string(), 60)]);
-        gen_test_subscribe_candlestick!(HuobiSpotWSClient, &vec![("btcusdt".to_string(), 2592000)]);
+        gen_test_subscribe_candlestick!(
+            HuobiSpotWSClient,
+            &vec![("btcusdt".to_string(), Interval::Min1)]
+        );
+        gen_test_subscribe_candlestick!(
+            HuobiSpotWSClient,
+            &vec![("btcusdt".to_string(), Interval::Month1)]
+        );
     }
 }
 
 #[cfg(test)]
 mod huobi_inverse_future {
-    use crypto_ws_client::{HuobiFutureWSClient, WSClient};
+    use crypto_ws_client::{HuobiFutureWSClient, Interval, WSClient};
     use std::sync::mpsc::{Receiver, Sender};
 
     #[test]
@@ -143,17 +151,20 @@ mod huobi_inverse_future {
 
     #[test]
     fn subscribe_candlestick() {
-        gen_test_subscribe_candlestick!(HuobiFutureWSClient, &vec![("BTC_CQ".to_string(), 60)]);
         gen_test_subscribe_candlestick!(
             HuobiFutureWSClient,
-            &vec![("BTC_CQ".to_string(), 2592000)]
+            &vec![("BTC_CQ".to_string(), Interval::Min1)]
+        );
+        gen_test_subscribe_candlestick!(
+            HuobiFutureWSClient,
+            &vec![("BTC_CQ".to_string(), Interval::Month1)]
         );
     }
 }
 
 #[cfg(test)]
 mod huobi_linear_swap {
-    use crypto_ws_client::{HuobiLinearSwapWSClient, WSClient};
+    use crypto_ws_client::{HuobiLinearSwapWSClient, Interval, WSClient};
     use std::sync::mpsc::{Receiver, Sender};
 
     #[test]
@@ -214,11 +225,11 @@ mod huobi_linear_swap {
     fn subscribe_candlestick() {
         gen_test_subscribe_candlestick!(
             HuobiLinearSwapWSClient,
-            &vec![("BTC-USDT".to_string(), 60)]
+            &vec![("BTC-USDT".to_string(), Interval::Min1)]
         );
         gen_test_subscribe_candlestick!(
             HuobiLinearSwapWSClient,
-            &vec![("BTC-USDT".to_string(), 2592000)]
+            &vec![("BTC-USDT".to_string(), Interval::Month1)]
         );
     }
 
@@ -267,7 +278,7 @@ mod huobi_linear_swap {
 
 #[cfg(test)]
 mod huobi_inverse_swap {
-    use crypto_ws_client::{HuobiInverseSwapWSClient, WSClient};
+    use crypto_ws_client::{HuobiInverseSwapWSClient, Interval, WSClient};
     use std::sync::mpsc::{Receiver, Sender};
 
     #[test]
@@ -328,11 +339,11 @@ mod huobi_inverse_swap {
     fn subscribe_candlestick() {
         gen_test_subscribe_candlestick!(
             HuobiInverseSwapWSClient,
-            &vec![("BTC-USD".to_string(), 60)]
+            &vec![("BTC-USD".to_string(), Interval::Min1)]
         );
         gen_test_subscribe_candlestick!(
             HuobiInverseSwapWSClient,
-            &vec![("BTC-USD".to_string(), 2592000)]
+            &vec![("BTC-USD".to_string(), Interval::Month1)]
         );
     }
 
@@ -386,7 +397,7 @@ mod huobi_inverse_swap {
 
 #[cfg(test)]
 mod huobi_option {
-    use crypto_ws_client::{HuobiOptionWSClient, WSClient};
+    use crypto_ws_client::{HuobiOptionWSClient, Interval, WSClient};
     use std::sync::mpsc::{Receiver, Sender};
 
     #[test]
@@ -454,11 +465,11 @@ mod huobi_option {
     fn subscribe_candlestick() {
         gen_test_subscribe_candlestick!(
             HuobiOptionWSClient,
-            &vec![("BTC-USDT-210625-P-27000".to_string(), 60)]
+            &vec![("BTC-USDT-210625-P-27000".to_string(), Interval::Min1)]
         );
         gen_test_subscribe_candlestick!(
             HuobiOptionWSClient,
-            &vec![("BTC-USDT-210625-P-27000".to_string(), 2592000)]
+            &vec![("BTC-USDT-210625-P-27000".to_string(), Interval::Month1)]
         );
     }
 
@@ -1,4 +1,4 @@
-use crypto_ws_client::{KrakenWSClient, WSClient};
+use crypto_ws_client::{Interval, KrakenWSClient, WSClient};
 use std::sync::mpsc::{Receiver, Sender};
 
 #[macro_use]
@@ -70,14 +70,17 @@ fn subscribe_orderbook() {
 fn subscribe_candlestick() {
     gen_test_subscribe_candlestick!(
         KrakenWSClient,
-        &vec![("XBT/USD".to_string(), 60), ("ETH/USD".to_string(), 60)]
+        &vec![
+            ("XBT/USD".to_string(), Interval::Min1),
+            ("ETH/USD".to_string(), Interval::Min1)
+        ]
     );
 
     gen_test_subscribe_candlestick!(
         KrakenWSClient,
         &vec![
-            ("XBT/USD".to_string(), 1296000),
-            ("ETH/USD".to_string(), 1296000)
+            ("XBT/USD".to_string(), Interval::Day15),
+            ("ETH/USD".to_string(), Interval::Day15)
         ]
     );
 }
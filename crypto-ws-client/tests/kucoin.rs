@@ -3,7 +3,7 @@ mod utils;
 
 #[cfg(test)]
 mod kucoin_spot {
-    use crypto_ws_client::{KuCoinSpotWSClient, WSClient};
+    use crypto_ws_client::{Interval, KuCoinSpotWSClient, WSClient};
     use std::sync::mpsc::{Receiver, Sender};
 
     #[test]
@@ -89,17 +89,20 @@ mod kucoin_spot {
 
     #[test]
     fn subscribe_candlestick() {
-        gen_test_subscribe_candlestick!(KuCoinSpotWSClient, &vec![("BTC-USDT".to_string(), 60)]);
         gen_test_subscribe_candlestick!(
             KuCoinSpotWSClient,
-            &vec![("BTC-USDT".to_string(), 604800)]
+            &vec![("BTC-USDT".to_string(), Interval::Min1)]
+        );
+        gen_test_subscribe_candlestick!(
+            KuCoinSpotWSClient,
+            &vec![("BTC-USDT".to_string(), Interval::Week1)]
         );
     }
 }
 
 #[cfg(test)]
 mod kucoin_inverse_swap {
-    use crypto_ws_client::{KuCoinSwapWSClient, WSClient};
+    use crypto_ws_client::{Interval, KuCoinSwapWSClient, WSClient};
     use std::sync::mpsc::{Receiver, Sender};
 
     #[test]
@@ -179,13 +182,16 @@ mod kucoin_inverse_swap {
     fn subscribe_candlestick() {
         gen_test_subscribe_candlestick!(
             KuCoinSwapWSClient,
-            &vec![("XBTUSDM".to_string(), 60), ("ETHUSDM".to_string(), 60)]
+            &vec![
+                ("XBTUSDM".to_string(), Interval::Min1),
+                ("ETHUSDM".to_string(), Interval::Min1)
+            ]
         );
         gen_test_subscribe_candlestick!(
             KuCoinSwapWSClient,
             &vec![
-                ("XBTUSDM".to_string(), 604800),
-                ("ETHUSDM".to_string(), 604800)
+                ("XBTUSDM".to_string(), Interval::Week1),
+                ("ETHUSDM".to_string(), Interval::Week1)
             ]
         );
     }
@@ -193,7 +199,7 @@ mod kucoin_inverse_swap {
 
 #[cfg(test)]
 mod kucoin_linear_swap {
-    use crypto_ws_client::{KuCoinSwapWSClient, WSClient};
+    use crypto_ws_client::{Interval, KuCoinSwapWSClient, WSClient};
     use std::sync::mpsc::{Receiver, Sender};
 
     #[test]
@@ -273,13 +279,16 @@ mod kucoin_linear_swap {
     fn subscribe_candlestick() {
         gen_test_subscribe_candlestick!(
             KuCoinSwapWSClient,
-            &vec![("XBTUSDTM".to_string(), 60), ("ETHUSDTM".to_string(), 60)]
+            &vec![
+                ("XBTUSDTM".to_string(), Interval::Min1),
+                ("ETHUSDTM".to_string(), Interval::Min1)
+            ]
         );
         gen_test_subscribe_candlestick!(
             KuCoinSwapWSClient,
             &vec![
-                ("XBTUSDTM".to_string(), 604800),
-                ("ETHUSDTM".to_string(), 604800)
+                ("XBTUSDTM".to_string(), Interval::Week1),
+                ("ETHUSDTM".to_string(), Interval::Week1)
             ]
         );
     }
@@ -287,7 +296,7 @@ mod kucoin_linear_swap {
 
 #[cfg(test)]
 mod kucoin_inverse_future {
-    use crypto_ws_client::{KuCoinSwapWSClient, WSClient};
+    use crypto_ws_client::{Interval, KuCoinSwapWSClient, WSClient};
     use std::sync::mpsc::{Receiver, Sender};
 
     #[test]
@@ -369,7 +378,13 @@ mod kucoin_inverse_future {
     #[test]
     #[ignore]
     fn subscribe_candlestick() {
-        gen_test_subscribe_candlestick!(KuCoinSwapWSClient, &vec![("XBTMZ21".to_string(), 60)]);
-        gen_test_subscribe_candlestick!(KuCoinSwapWSClient, &vec![("XBTMZ21".to_string(), 604800)]);
+        gen_test_subscribe_candlestick!(
+            KuCoinSwapWSClient,
+            &vec![("XBTMZ21".to_string(), Interval::Min1)]
+        );
+        gen_test_subscribe_candlestick!(
+            KuCoinSwapWSClient,
+            &vec![("XBTMZ21".to_string(), Interval::Week1)]
+        );
     }
 }
@@ -3,7 +3,7 @@ mod utils;
 
 #[cfg(test)]
 mod mxc_spot {
-    use crypto_ws_client::{MxcSpotWSClient, WSClient};
+    use crypto_ws_client::{Interval, MxcSpotWSClient, WSClient};
     use std::sync::mpsc::{Receiver, Sender};
 
     #[test]
@@ -80,17 +80,17 @@ mod mxc_spot {
         gen_test_subscribe_candlestick!(
             MxcSpotWSClient,
             &vec![
-                ("BTC_USDT".to_string(), 60),
-                ("ETH_USDT".to_string(), 60),
-                ("MX_USDT".to_string(), 60)
+                ("BTC_USDT".to_string(), Interval::Min1),
+                ("ETH_USDT".to_string(), Interval::Min1),
+                ("MX_USDT".to_string(), Interval::Min1)
             ]
         );
         gen_test_subscribe_candlestick!(
             MxcSpotWSClient,
             &vec![
-                ("BTC_USDT".to_string(), 2592000),
-                ("ETH_USDT".to_string(), 2592000),
-                ("MX_USDT".to_string(), 2592000)
+                ("BTC_USDT".to_string(), Interval::Month1),
+                ("ETH_USDT".to_string(), Interval::Month1),
+                ("MX_USDT".to_string(), Interval::Month1)
             ]
         );
     }
@@ -98,7 +98,7 @@ mod mxc_spot {
 
 #[cfg(test)]
 mod mxc_linear_swap {
-    use crypto_ws_client::{MxcSwapWSClient, WSClient};
+    use crypto_ws_client::{Interval, MxcSwapWSClient, WSClient};
     use std::sync::mpsc::{Receiver, Sender};
 
     #[test]
@@ -157,14 +157,20 @@ mod mxc_linear_swap {
 
     #[test]
     fn subscribe_candlestick() {
-        gen_test_subscribe_candlestick!(MxcSwapWSClient, &vec![("BTC_USDT".to_string(), 60)]);
-        gen_test_subscribe_candlestick!(MxcSwapWSClient, &vec![("BTC_USDT".to_string(), 2592000)]);
+        gen_test_subscribe_candlestick!(
+            MxcSwapWSClient,
+            &vec![("BTC_USDT".to_string(), Interval::Min1)]
+        );
+        gen_test_subscribe_candlestick!(
+            MxcSwapWSClient,
+            &vec![("BTC_USDT".to_string(), Interval::Month1)]
+        );
     }
 }
 
 #[cfg(test)]
 mod mxc_inverse_swap {
-    use crypto_ws_client::{MxcSwapWSClient, WSClient};
+    use crypto_ws_client::{Interval, MxcSwapWSClient, WSClient};
     use std::sync::mpsc::{Receiver, Sender};
 
     #[test]
@@ -205,7 +211,13 @@ mod mxc_inverse_swap {
 
     #[test]
     fn subscribe_candlestick() {
-        gen_test_subscribe_candlestick!(MxcSwapWSClient, &vec![("BTC_USD".to_string(), 60)]);
-        gen_test_subscribe_candlestick!(MxcSwapWSClient, &vec![("BTC_USD".to_string(), 2592000)]);
+        gen_test_subscribe_candlestick!(
+            MxcSwapWSClient,
+            &vec![("BTC_USD".to_string(), Interval::Min1)]
+        );
+        gen_test_subscribe_candlestick!(
+            MxcSwapWSClient,
+            &vec![("BTC_USD".to_string(), Interval::Month1)]
+        );
     }
 }
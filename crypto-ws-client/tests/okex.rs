@@ -15,7 +15,7 @@ fn okex_index() {
 
 #[cfg(test)]
 mod okex_spot {
-    use crypto_ws_client::{OkexWSClient, WSClient};
+    use crypto_ws_client::{Interval, OkexWSClient, WSClient};
     use std::sync::mpsc::{Receiver, Sender};
 
     #[test]
@@ -75,14 +75,20 @@ mod okex_spot {
 
     #[test]
     fn subscribe_candlestick() {
-        gen_test_subscribe_candlestick!(OkexWSClient, &vec![("BTC-USDT".to_string(), 60)]);
-        gen_test_subscribe_candlestick!(OkexWSClient, &vec![("BTC-USDT".to_string(), 604800)]);
+        gen_test_subscribe_candlestick!(
+            OkexWSClient,
+            &vec![("BTC-USDT".to_string(), Interval::Min1)]
+        );
+        gen_test_subscribe_candlestick!(
+            OkexWSClient,
+            &vec![("BTC-USDT".to_string(), Interval::Week1)]
+        );
     }
 }
 
 #[cfg(test)]
 mod okex_future {
-    use crypto_ws_client::{OkexWSClient, WSClient};
+    use crypto_ws_client::{Interval, OkexWSClient, WSClient};
     use std::sync::mpsc::{Receiver, Sender};
 
     #[test]
@@ -132,17 +138,20 @@ mod okex_future {
 
     #[test]
     fn subscribe_candlestick() {
-        gen_test_subscribe_candlestick!(OkexWSClient, &vec![("BTC-USDT-211231".to_string(), 60)]);
         gen_test_subscribe_candlestick!(
             OkexWSClient,
-            &vec![("BTC-USDT-211231".to_string(), 604800)]
+            &vec![("BTC-USDT-211231".to_string(), Interval::Min1)]
+        );
+        gen_test_subscribe_candlestick!(
+            OkexWSClient,
+            &vec![("BTC-USDT-211231".to_string(), Interval::Week1)]
         );
     }
 }
 
 #[cfg(test)]
 mod okex_swap {
-    use crypto_ws_client::{OkexWSClient, WSClient};
+    use crypto_ws_client::{Interval, OkexWSClient, WSClient};
     use std::sync::mpsc::{Receiver, Sender};
 
     #[test]
@@ -192,8 +201,14 @@ mod okex_swap {
 
     #[test]
     fn subscribe_candlestick() {
-        gen_test_subscribe_candlestick!(OkexWSClient, &vec![("BTC-USDT-SWAP".to_string(), 60)]);
-        gen_test_subscribe_candlestick!(OkexWSClient, &vec![("BTC-USDT-SWAP".to_string(), 604800)]);
+        gen_test_subscribe_candlestick!(
+            OkexWSClient,
+            &vec![("BTC-USDT-SWAP".to_string(), Interval::Min1)]
+        );
+        gen_test_subscribe_candlestick!(
+            OkexWSClient,
+            &vec![("BTC-USDT-SWAP".to_string(), Interval::Week1)]
+        );
     }
 
     #[test]
@@ -208,7 +223,7 @@ mod okex_swap {
 
 #[cfg(test)]
 mod okex_option {
-    use crypto_ws_client::{OkexWSClient, WSClient};
+    use crypto_ws_client::{Interval, OkexWSClient, WSClient};
     use std::sync::mpsc::{Receiver, Sender};
 
     #[test]
@@ -268,11 +283,62 @@ mod okex_option {
     fn subscribe_candlestick() {
         gen_test_subscribe_candlestick!(
             OkexWSClient,
-            &vec![("BTC-USD-211231-18000-P".to_string(), 60)]
+            &vec![("BTC-USD-211231-18000-P".to_string(), Interval::Min1)]
         );
         gen_test_subscribe_candlestick!(
             OkexWSClient,
-            &vec![("BTC-USD-211231-18000-P".to_string(), 604800)]
+            &vec![("BTC-USD-211231-18000-P".to_string(), Interval::Week1)]
+        );
+    }
+}
+
+#[cfg(test)]
+mod okex_v5 {
+    use crypto_ws_client::{Interval, OkexV5WSClient, WSClient};
+    use std::sync::mpsc::{Receiver, Sender};
+
+    #[test]
+    fn subscribe() {
+        gen_test_code!(
+            OkexV5WSClient,
+            subscribe,
+            &vec!["trades:BTC-USDT".to_string()]
+        );
+    }
+
+    #[test]
+    fn subscribe_trade() {
+        gen_test_code!(
+            OkexV5WSClient,
+            subscribe_trade,
+            &vec!["BTC-USDT".to_string(), "BTC-USDT-SWAP".to_string()]
+        );
+    }
+
+    #[test]
+    fn subscribe_orderbook() {
+        gen_test_code!(
+            OkexV5WSClient,
+            subscribe_orderbook,
+            &vec!["BTC-USDT-SWAP".to_string()]
+        );
+    }
+
+    #[test]
+    fn subscribe_orderbook_topk() {
+        gen_test_code!(
+            OkexV5WSClient,
+            subscribe_orderbook_topk,
+            &vec!["BTC-USDT".to_string()]
+        );
+    }
+
+    #[test]
+    fn subscribe_candlestick() {
+        gen_test_code!(
+            OkexV5WSClient,
+            subscribe_candlestick,
+            &vec![("BTC-USDT".to_string(), Interval::Min1)]
         );
     }
 }
@@ -1,10 +1,27 @@
+use crypto_ws_client::UnsupportedChannel;
+
+// subscribe() returns nothing, while high-level APIs return Result
+pub trait SubscribeResult {
+    fn check(self);
+}
+
+impl SubscribeResult for () {
+    fn check(self) {}
+}
+
+impl SubscribeResult for Result<(), UnsupportedChannel> {
+    fn check(self) {
+        self.unwrap();
+    }
+}
+
 macro_rules! gen_test_code {
     ($client:ident, $func_name:ident, $pairs:expr) => {
         let (tx, rx): (Sender<String>, Receiver<String>) = std::sync::mpsc::channel();
         let mut messages = Vec::<String>::new();
         {
             let ws_client = $client::new(tx, None);
-            ws_client.$func_name($pairs);
+            crate::utils::SubscribeResult::check(ws_client.$func_name($pairs));
             ws_client.run(Some(0)); // return immediately once after a normal message
             ws_client.close();
         }
@@ -23,7 +40,9 @@ macro_rules! gen_test_subscribe_candlestick {
         let mut messages = Vec::<String>::new();
         {
             let ws_client = $client::new(tx, None);
-            ws_client.subscribe_candlestick($symbol_interval_list);
+            ws_client
+                .subscribe_candlestick($symbol_interval_list)
+                .unwrap();
             ws_client.run(Some(0)); // return immediately once after a normal message
             ws_client.close();
         }
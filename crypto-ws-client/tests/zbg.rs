@@ -3,7 +3,7 @@ mod utils;
 
 #[cfg(test)]
 mod zbg_spot {
-    use crypto_ws_client::{WSClient, ZbgSpotWSClient};
+    use crypto_ws_client::{Interval, WSClient, ZbgSpotWSClient};
     use std::sync::mpsc::{Receiver, Sender};
 
     #[test]
@@ -53,14 +53,20 @@ mod zbg_spot {
 
     #[test]
     fn subscribe_candlestick() {
-        gen_test_subscribe_candlestick!(ZbgSpotWSClient, &vec![("btc_usdt".to_string(), 60)]);
-        gen_test_subscribe_candlestick!(ZbgSpotWSClient, &vec![("btc_usdt".to_string(), 604800)]);
+        gen_test_subscribe_candlestick!(
+            ZbgSpotWSClient,
+            &vec![("btc_usdt".to_string(), Interval::Min1)]
+        );
+        gen_test_subscribe_candlestick!(
+            ZbgSpotWSClient,
+            &vec![("btc_usdt".to_string(), Interval::Week1)]
+        );
     }
 }
 
 #[cfg(test)]
 mod zbg_inverse_swap {
-    use crypto_ws_client::{WSClient, ZbgSwapWSClient};
+    use crypto_ws_client::{Interval, WSClient, ZbgSwapWSClient};
     use std::sync::mpsc::{Receiver, Sender};
 
     #[test]
@@ -124,13 +130,16 @@ mod zbg_inverse_swap {
     fn subscribe_candlestick() {
         gen_test_subscribe_candlestick!(
             ZbgSwapWSClient,
-            &vec![("BTC_USD-R".to_string(), 60), ("ETH_USD-R".to_string(), 60)]
+            &vec![
+                ("BTC_USD-R".to_string(), Interval::Min1),
+                ("ETH_USD-R".to_string(), Interval::Min1)
+            ]
         );
         gen_test_subscribe_candlestick!(
             ZbgSwapWSClient,
             &vec![
-                ("BTC_USD-R".to_string(), 604800),
-                ("ETH_USD-R".to_string(), 604800)
+                ("BTC_USD-R".to_string(), Interval::Week1),
+                ("ETH_USD-R".to_string(), Interval::Week1)
             ]
         );
     }
@@ -138,7 +147,7 @@ mod zbg_inverse_swap {
 
 #[cfg(test)]
 mod zbg_linear_swap {
-    use crypto_ws_client::{WSClient, ZbgSwapWSClient};
+    use crypto_ws_client::{Interval, WSClient, ZbgSwapWSClient};
     use std::sync::mpsc::{Receiver, Sender};
 
     #[test]
@@ -202,13 +211,16 @@ mod zbg_linear_swap {
     fn subscribe_candlestick() {
         gen_test_subscribe_candlestick!(
             ZbgSwapWSClient,
-            &vec![("BTC_USDT".to_string(), 60), ("ETH_USDT".to_string(), 60)]
+            &vec![
+                ("BTC_USDT".to_string(), Interval::Min1),
+                ("ETH_USDT".to_string(), Interval::Min1)
+            ]
         );
         gen_test_subscribe_candlestick!(
             ZbgSwapWSClient,
             &vec![
-                ("BTC_USDT".to_string(), 604800),
-                ("ETH_USDT".to_string(), 604800)
+                ("BTC_USDT".to_string(), Interval::Week1),
+                ("ETH_USDT".to_string(), Interval::Week1)
             ]
         );
     }